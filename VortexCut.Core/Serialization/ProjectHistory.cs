@@ -0,0 +1,273 @@
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Serialization;
+
+namespace VortexCut.Core.Serialization;
+
+/// <summary>
+/// 프로젝트 히스토리 항목 (history/index.json에 저장).
+/// </summary>
+public class ProjectHistoryEntry
+{
+    [JsonPropertyName("version")]
+    public int Version { get; set; }
+
+    [JsonPropertyName("savedAtUtc")]
+    public DateTime SavedAtUtc { get; set; }
+
+    /// <summary>true = 전체 스냅샷, false = 직전 버전 대비 diff</summary>
+    [JsonPropertyName("isFull")]
+    public bool IsFull { get; set; }
+
+    [JsonPropertyName("fileName")]
+    public string FileName { get; set; } = string.Empty;
+}
+
+/// <summary>
+/// 줄 단위 diff. 공통 앞/뒤 줄을 제외한 단일 구간 치환으로 표현한다.
+/// (클립 이동/트림 같은 국소 편집은 몇 줄짜리 diff가 됨)
+/// </summary>
+public class ProjectDiff
+{
+    [JsonPropertyName("start")]
+    public int Start { get; set; }
+
+    [JsonPropertyName("deleteCount")]
+    public int DeleteCount { get; set; }
+
+    [JsonPropertyName("insert")]
+    public List<string> Insert { get; set; } = new();
+
+    public static ProjectDiff Create(string[] oldLines, string[] newLines)
+    {
+        int prefix = 0;
+        int maxPrefix = Math.Min(oldLines.Length, newLines.Length);
+        while (prefix < maxPrefix && oldLines[prefix] == newLines[prefix])
+            prefix++;
+
+        int suffix = 0;
+        int maxSuffix = maxPrefix - prefix;
+        while (suffix < maxSuffix
+               && oldLines[oldLines.Length - 1 - suffix] == newLines[newLines.Length - 1 - suffix])
+            suffix++;
+
+        return new ProjectDiff
+        {
+            Start = prefix,
+            DeleteCount = oldLines.Length - prefix - suffix,
+            Insert = newLines.Skip(prefix).Take(newLines.Length - prefix - suffix).ToList()
+        };
+    }
+
+    public string[] Apply(string[] oldLines)
+    {
+        if (Start < 0 || DeleteCount < 0 || Start + DeleteCount > oldLines.Length)
+            throw new InvalidDataException("프로젝트 히스토리 diff가 손상되었습니다.");
+
+        return oldLines.Take(Start)
+            .Concat(Insert)
+            .Concat(oldLines.Skip(Start + DeleteCount))
+            .ToArray();
+    }
+}
+
+/// <summary>
+/// 증분(diff) 저장 + 디스크 히스토리.
+/// 프로젝트 파일 옆 "&lt;파일명&gt;.history" 폴더에 최근 버전을 보관하고
+/// 오래된 버전은 maxEntries 개수를 넘으면 삭제한다.
+/// </summary>
+public class ProjectHistory
+{
+    public const int DefaultMaxEntries = 50;
+
+    /// <summary>전체 스냅샷 주기 (복원 시 적용할 diff 체인 길이 제한)</summary>
+    public const int FullSnapshotInterval = 10;
+
+    private const string IndexFileName = "index.json";
+
+    private static readonly JsonSerializerOptions IndexOptions = new() { WriteIndented = true };
+
+    private readonly string _projectFilePath;
+    private readonly string _historyDir;
+    private readonly int _maxEntries;
+
+    public ProjectHistory(string projectFilePath, int maxEntries = DefaultMaxEntries)
+    {
+        if (maxEntries < 1)
+            throw new ArgumentOutOfRangeException(nameof(maxEntries));
+
+        _projectFilePath = projectFilePath;
+        _historyDir = GetHistoryDirectory(projectFilePath);
+        _maxEntries = maxEntries;
+    }
+
+    public string HistoryDirectory => _historyDir;
+
+    /// <summary>
+    /// 마지막 SaveAsync의 히스토리 기록 실패 사유 (null = 성공).
+    /// 프로젝트 파일은 이미 저장된 상태이므로 호출자는 경고로만 표시한다.
+    /// </summary>
+    public string? HistoryError { get; private set; }
+
+    public static string GetHistoryDirectory(string projectFilePath) => projectFilePath + ".history";
+
+    /// <summary>
+    /// 프로젝트 저장 + 히스토리 기록.
+    /// 직전 버전과 내용이 같으면 히스토리를 추가하지 않고 false 반환.
+    /// 히스토리 기록 실패(손상된 히스토리, 디스크 오류)는 저장 실패로 취급하지 않고
+    /// HistoryError에 남긴 뒤 false 반환.
+    /// </summary>
+    public async Task<bool> SaveAsync(ProjectData project, DateTime? savedAtUtc = null)
+    {
+        var json = ProjectSerializer.Serialize(project);
+        await File.WriteAllTextAsync(_projectFilePath, json, Encoding.UTF8);
+
+        HistoryError = null;
+        try
+        {
+            return await RecordAsync(json, savedAtUtc);
+        }
+        catch (Exception ex) when (ex is IOException or InvalidDataException
+                                       or JsonException or UnauthorizedAccessException)
+        {
+            HistoryError = ex.Message;
+            return false;
+        }
+    }
+
+    /// <summary>저장된 프로젝트 JSON을 히스토리에 추가 (직전 버전과 같으면 false)</summary>
+    private async Task<bool> RecordAsync(string json, DateTime? savedAtUtc)
+    {
+        Directory.CreateDirectory(_historyDir);
+        var entries = await LoadIndexAsync();
+
+        string? previous = entries.Count > 0
+            ? await ReconstructAsync(entries, entries.Count - 1)
+            : null;
+        if (previous == json)
+            return false;
+
+        int version = entries.Count > 0 ? entries[^1].Version + 1 : 1;
+        int diffsSinceFull = entries.Count - 1 - entries.FindLastIndex(e => e.IsFull);
+        bool isFull = previous == null || diffsSinceFull >= FullSnapshotInterval - 1;
+
+        var entry = new ProjectHistoryEntry
+        {
+            Version = version,
+            SavedAtUtc = savedAtUtc ?? DateTime.UtcNow,
+            IsFull = isFull,
+            FileName = isFull ? $"{version:D6}.full.json" : $"{version:D6}.diff.json"
+        };
+
+        string content = isFull
+            ? json
+            : JsonSerializer.Serialize(ProjectDiff.Create(SplitLines(previous!), SplitLines(json)), IndexOptions);
+        await File.WriteAllTextAsync(Path.Combine(_historyDir, entry.FileName), content, Encoding.UTF8);
+
+        entries.Add(entry);
+        await PruneAsync(entries);
+        await SaveIndexAsync(entries);
+        return true;
+    }
+
+    /// <summary>히스토리 항목 목록 (오래된 순)</summary>
+    public async Task<IReadOnlyList<ProjectHistoryEntry>> GetEntriesAsync()
+        => await LoadIndexAsync();
+
+    /// <summary>특정 버전의 프로젝트 복원 (없으면 null)</summary>
+    public async Task<ProjectData?> LoadVersionAsync(int version)
+    {
+        var entries = await LoadIndexAsync();
+        int index = entries.FindIndex(e => e.Version == version);
+        if (index < 0)
+            return null;
+
+        return ProjectSerializer.Deserialize(await ReconstructAsync(entries, index));
+    }
+
+    /// <summary>
+    /// 지정 시각 이전의 마지막 저장 버전 (예: "30분 전" 롤백).
+    /// 해당 시각 이전 기록이 없으면 null.
+    /// </summary>
+    public async Task<ProjectHistoryEntry?> FindVersionAtAsync(DateTime utc)
+    {
+        var entries = await LoadIndexAsync();
+        return entries.LastOrDefault(e => e.SavedAtUtc <= utc);
+    }
+
+    /// <summary>maxEntries 초과분 삭제 (새 첫 항목이 diff면 전체 스냅샷으로 변환)</summary>
+    private async Task PruneAsync(List<ProjectHistoryEntry> entries)
+    {
+        while (entries.Count > _maxEntries)
+        {
+            var next = entries[1];
+            if (!next.IsFull)
+            {
+                var content = await ReconstructAsync(entries, 1);
+                var fullName = $"{next.Version:D6}.full.json";
+                await File.WriteAllTextAsync(Path.Combine(_historyDir, fullName), content, Encoding.UTF8);
+                TryDelete(next.FileName);
+                next.IsFull = true;
+                next.FileName = fullName;
+            }
+
+            TryDelete(entries[0].FileName);
+            entries.RemoveAt(0);
+        }
+    }
+
+    /// <summary>index 위치 버전의 JSON 복원 (직전 전체 스냅샷 + diff 체인)</summary>
+    private async Task<string> ReconstructAsync(List<ProjectHistoryEntry> entries, int index)
+    {
+        int baseIndex = entries.FindLastIndex(index, e => e.IsFull);
+        if (baseIndex < 0)
+            throw new InvalidDataException("프로젝트 히스토리에 기준 스냅샷이 없습니다.");
+
+        var text = await File.ReadAllTextAsync(Path.Combine(_historyDir, entries[baseIndex].FileName), Encoding.UTF8);
+        if (baseIndex == index)
+            return text;
+
+        var lines = SplitLines(text);
+        for (int i = baseIndex + 1; i <= index; i++)
+        {
+            var diffJson = await File.ReadAllTextAsync(Path.Combine(_historyDir, entries[i].FileName), Encoding.UTF8);
+            var diff = JsonSerializer.Deserialize<ProjectDiff>(diffJson)
+                       ?? throw new InvalidDataException("프로젝트 히스토리 diff를 읽을 수 없습니다.");
+            lines = diff.Apply(lines);
+        }
+
+        return string.Join('\n', lines);
+    }
+
+    private async Task<List<ProjectHistoryEntry>> LoadIndexAsync()
+    {
+        var indexPath = Path.Combine(_historyDir, IndexFileName);
+        if (!File.Exists(indexPath))
+            return new List<ProjectHistoryEntry>();
+
+        var json = await File.ReadAllTextAsync(indexPath, Encoding.UTF8);
+        return JsonSerializer.Deserialize<List<ProjectHistoryEntry>>(json, IndexOptions)
+               ?? new List<ProjectHistoryEntry>();
+    }
+
+    private async Task SaveIndexAsync(List<ProjectHistoryEntry> entries)
+    {
+        var json = JsonSerializer.Serialize(entries, IndexOptions);
+        await File.WriteAllTextAsync(Path.Combine(_historyDir, IndexFileName), json, Encoding.UTF8);
+    }
+
+    private void TryDelete(string fileName)
+    {
+        try
+        {
+            File.Delete(Path.Combine(_historyDir, fileName));
+        }
+        catch (IOException)
+        {
+            // 삭제 실패는 다음 저장 시 무시 (인덱스에서만 제거)
+        }
+    }
+
+    /// <summary>'\n' 기준 분할 ('\r'은 줄 내용에 남겨 원문 그대로 복원)</summary>
+    private static string[] SplitLines(string text) => text.Split('\n');
+}
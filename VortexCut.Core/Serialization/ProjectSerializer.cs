@@ -36,6 +36,12 @@ public class ProjectData
     [JsonPropertyName("outPointMs")]
     public long? OutPointMs { get; set; }
 
+    /// <summary>
+    /// 타임라인 배경색 (클립이 없는 구간/레터박스 영역, 항상 불투명)
+    /// </summary>
+    [JsonPropertyName("backgroundColorArgb")]
+    public uint BackgroundColorArgb { get; set; } = 0xFF000000;
+
     [JsonPropertyName("mediaItems")]
     public List<MediaItemData> MediaItems { get; set; } = new();
 
@@ -128,6 +128,18 @@ public static class NativeMethods
     [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
     public static extern int timeline_get_video_clip_count(IntPtr timeline, ulong trackId, out nuint outCount);
 
+    /// <summary>
+    /// 타임라인 배경색 설정 (클립이 없는 구간/레터박스 영역)
+    /// </summary>
+    [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int timeline_set_background_color(IntPtr timeline, byte r, byte g, byte b);
+
+    /// <summary>
+    /// 타임라인 배경색 가져오기
+    /// </summary>
+    [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int timeline_get_background_color(IntPtr timeline, out byte outR, out byte outG, out byte outB);
+
     // ==================== Video Info Functions ====================
 
     /// <summary>
@@ -357,6 +369,12 @@ public static class NativeMethods
     [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
     public static extern int exporter_get_error(IntPtr job, out IntPtr outError);
 
+    /// <summary>
+    /// Export 에러 분류 (0=에러 없음, 3=권한 없음, 6=디스크 공간 부족 등)
+    /// </summary>
+    [DllImport(DllName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern int exporter_get_error_category(IntPtr job, out int outCategory);
+
     /// <summary>
     /// Export 취소
     /// </summary>
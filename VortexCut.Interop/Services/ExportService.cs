@@ -101,6 +101,17 @@ public class ExportService : IDisposable
         }
     }
 
+    /// <summary>
+    /// Export 에러 분류 (0이면 에러 없음, 3=권한 없음, 6=디스크 공간 부족)
+    /// </summary>
+    public int GetErrorCategory()
+    {
+        if (_jobHandle == IntPtr.Zero) return 0;
+
+        int result = NativeMethods.exporter_get_error_category(_jobHandle, out int category);
+        return result == ErrorCodes.SUCCESS ? category : 0;
+    }
+
     /// <summary>
     /// 자막 포함 Export 시작 (v2)
     /// subtitleListHandle: Rust SubtitleOverlayList 핸들 (null이면 자막 없음)
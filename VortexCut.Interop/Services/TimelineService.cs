@@ -249,6 +249,37 @@ public class TimelineService : IDisposable
         return (int)count;
     }
 
+    /// <summary>
+    /// 타임라인 배경색 설정 (0xAARRGGBB, 배경은 항상 불투명이므로 알파 무시)
+    /// </summary>
+    public void SetBackgroundColor(uint colorArgb)
+    {
+        ThrowIfDisposed();
+        ThrowIfNoTimeline();
+
+        int result = NativeMethods.timeline_set_background_color(
+            _timeline!.DangerousGetHandle(),
+            (byte)((colorArgb >> 16) & 0xFF),
+            (byte)((colorArgb >> 8) & 0xFF),
+            (byte)(colorArgb & 0xFF));
+        CheckError(result);
+    }
+
+    /// <summary>
+    /// 타임라인 배경색 가져오기 (0xFFRRGGBB)
+    /// </summary>
+    public uint GetBackgroundColor()
+    {
+        ThrowIfDisposed();
+        ThrowIfNoTimeline();
+
+        int result = NativeMethods.timeline_get_background_color(
+            _timeline!.DangerousGetHandle(), out byte r, out byte g, out byte b);
+        CheckError(result);
+
+        return 0xFF000000u | ((uint)r << 16) | ((uint)g << 8) | b;
+    }
+
     private void ThrowIfDisposed()
     {
         if (_disposed)
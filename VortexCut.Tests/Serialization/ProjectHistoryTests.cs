@@ -0,0 +1,145 @@
+using VortexCut.Core.Serialization;
+using Xunit;
+
+namespace VortexCut.Tests.Serialization;
+
+/// <summary>
+/// ProjectHistory 증분 저장/복원 테스트
+/// </summary>
+public class ProjectHistoryTests : IDisposable
+{
+    private readonly string _tempDir;
+    private readonly string _projectPath;
+
+    public ProjectHistoryTests()
+    {
+        _tempDir = Path.Combine(Path.GetTempPath(), "vortexcut_history_" + Guid.NewGuid().ToString("N"));
+        Directory.CreateDirectory(_tempDir);
+        _projectPath = Path.Combine(_tempDir, "test.vortex");
+    }
+
+    public void Dispose()
+    {
+        if (Directory.Exists(_tempDir))
+            Directory.Delete(_tempDir, true);
+    }
+
+    private static ProjectData CreateProject(int clipCount)
+    {
+        var data = new ProjectData { ProjectName = "History Test" };
+        for (int i = 0; i < clipCount; i++)
+        {
+            data.Clips.Add(new ClipData
+            {
+                Id = (ulong)(i + 1),
+                FilePath = $"clip{i}.mp4",
+                StartTimeMs = i * 5000,
+                DurationMs = 5000
+            });
+        }
+        return data;
+    }
+
+    [Fact]
+    public async Task SaveAsync_UnchangedProject_DoesNotAddVersion()
+    {
+        var history = new ProjectHistory(_projectPath);
+
+        Assert.True(await history.SaveAsync(CreateProject(1)));
+        Assert.False(await history.SaveAsync(CreateProject(1)));
+
+        var entries = await history.GetEntriesAsync();
+        Assert.Single(entries);
+        Assert.True(entries[0].IsFull);
+    }
+
+    [Fact]
+    public async Task LoadVersionAsync_ReconstructsDiffVersions()
+    {
+        var history = new ProjectHistory(_projectPath);
+
+        for (int i = 1; i <= 5; i++)
+            await history.SaveAsync(CreateProject(i));
+
+        var entries = await history.GetEntriesAsync();
+        Assert.Equal(5, entries.Count);
+        Assert.False(entries[4].IsFull);
+
+        var restored = await history.LoadVersionAsync(3);
+        Assert.NotNull(restored);
+        Assert.Equal(3, restored!.Clips.Count);
+        Assert.Equal(10000, restored.Clips[2].StartTimeMs);
+    }
+
+    [Fact]
+    public async Task SaveAsync_ExceedsMaxEntries_PrunesOldestAndKeepsLatestRestorable()
+    {
+        var history = new ProjectHistory(_projectPath, maxEntries: 3);
+
+        for (int i = 1; i <= 6; i++)
+            await history.SaveAsync(CreateProject(i));
+
+        var entries = await history.GetEntriesAsync();
+        Assert.Equal(3, entries.Count);
+        Assert.Equal(4, entries[0].Version);
+        Assert.True(entries[0].IsFull);
+        Assert.Null(await history.LoadVersionAsync(1));
+
+        var latest = await history.LoadVersionAsync(6);
+        Assert.Equal(6, latest!.Clips.Count);
+
+        // 삭제된 버전 파일은 디스크에서도 제거
+        Assert.Equal(4, Directory.GetFiles(history.HistoryDirectory).Length); // index + 3
+    }
+
+    [Fact]
+    public async Task FindVersionAtAsync_ReturnsLastVersionBeforeTime()
+    {
+        var history = new ProjectHistory(_projectPath);
+        var now = new DateTime(2026, 1, 1, 12, 0, 0, DateTimeKind.Utc);
+
+        await history.SaveAsync(CreateProject(1), now.AddMinutes(-60));
+        await history.SaveAsync(CreateProject(2), now.AddMinutes(-40));
+        await history.SaveAsync(CreateProject(3), now.AddMinutes(-10));
+
+        var entry = await history.FindVersionAtAsync(now.AddMinutes(-30));
+        Assert.NotNull(entry);
+        Assert.Equal(2, entry!.Version);
+
+        Assert.Null(await history.FindVersionAtAsync(now.AddMinutes(-90)));
+    }
+
+    [Fact]
+    public async Task SaveAsync_CorruptHistory_SavesProjectAndReportsHistoryError()
+    {
+        var history = new ProjectHistory(_projectPath);
+        await history.SaveAsync(CreateProject(1));
+
+        // 기준 스냅샷 삭제 → 직전 버전 복원 불가
+        foreach (var file in Directory.GetFiles(history.HistoryDirectory, "*.full.json"))
+            File.Delete(file);
+
+        Assert.False(await history.SaveAsync(CreateProject(2)));
+        Assert.NotNull(history.HistoryError);
+
+        var saved = ProjectSerializer.Deserialize(await File.ReadAllTextAsync(_projectPath));
+        Assert.Equal(2, saved!.Clips.Count);
+
+        // 다음 저장에서 성공하면 에러 초기화
+        File.Delete(Path.Combine(history.HistoryDirectory, "index.json"));
+        Assert.True(await history.SaveAsync(CreateProject(3)));
+        Assert.Null(history.HistoryError);
+    }
+
+    [Fact]
+    public void ProjectDiff_RoundTrip()
+    {
+        var oldLines = new[] { "a", "b", "c", "d" };
+        var newLines = new[] { "a", "x", "y", "d" };
+
+        var diff = ProjectDiff.Create(oldLines, newLines);
+        Assert.Equal(1, diff.Start);
+        Assert.Equal(2, diff.DeleteCount);
+        Assert.Equal(newLines, diff.Apply(oldLines));
+    }
+}
@@ -0,0 +1,34 @@
+using VortexCut.Core.Serialization;
+using Xunit;
+
+namespace VortexCut.Tests.Serialization;
+
+/// <summary>
+/// ProjectSerializer 직렬화/역직렬화 테스트
+/// </summary>
+public class ProjectSerializerTests
+{
+    [Fact]
+    public void Serialize_BackgroundColor_RoundTrips()
+    {
+        var data = new ProjectData
+        {
+            ProjectName = "Background Test",
+            BackgroundColorArgb = 0xFF102030
+        };
+
+        var restored = ProjectSerializer.Deserialize(ProjectSerializer.Serialize(data));
+
+        Assert.NotNull(restored);
+        Assert.Equal(0xFF102030u, restored!.BackgroundColorArgb);
+    }
+
+    [Fact]
+    public void Deserialize_WithoutBackground_DefaultsToSolidBlack()
+    {
+        var restored = ProjectSerializer.Deserialize("{\"projectName\":\"Old Project\"}");
+
+        Assert.NotNull(restored);
+        Assert.Equal(0xFF000000u, restored!.BackgroundColorArgb);
+    }
+}
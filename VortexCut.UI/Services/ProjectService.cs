@@ -254,6 +254,9 @@ public class ProjectService : IDisposable
             OutPointMs = timelineVm.OutPointMs
         };
 
+        // 타임라인 배경 (Rust Timeline이 원본)
+        data.BackgroundColorArgb = _timelineService.GetBackgroundColor();
+
         // MediaItems
         foreach (var item in mainVm.ProjectBin.MediaItems)
         {
@@ -304,6 +307,7 @@ public class ProjectService : IDisposable
         // 2) Project / Timeline 재생성
         _currentProject = new Project(data.ProjectName, data.Width, data.Height, data.Fps);
         _timelineService.CreateTimeline(data.Width, data.Height, data.Fps);
+        _timelineService.SetBackgroundColor(data.BackgroundColorArgb);
 
         _timelineHandle = _timelineService.GetTimelineHandle();
         _renderService.CreateRenderer(_timelineHandle);
@@ -480,7 +480,9 @@ public partial class MainViewModel : ViewModelBase
             if (string.IsNullOrEmpty(filePath))
                 return;
 
-            await ProjectSerializer.SaveToFileAsync(data, filePath);
+            // 프로젝트 파일 + 증분 히스토리 기록 (<파일>.history)
+            var history = new ProjectHistory(filePath);
+            await history.SaveAsync(data);
 
             if (currentProject != null)
             {
@@ -488,7 +490,7 @@ public partial class MainViewModel : ViewModelBase
                 currentProject.Name = data.ProjectName;
             }
 
-            _toastService?.ShowSuccess("프로젝트 저장 완료", Path.GetFileName(filePath));
+            ShowSaveResult(filePath, history);
         }
         catch (Exception ex)
         {
@@ -513,7 +515,9 @@ public partial class MainViewModel : ViewModelBase
             if (string.IsNullOrEmpty(filePath))
                 return;
 
-            await ProjectSerializer.SaveToFileAsync(data, filePath);
+            // 프로젝트 파일 + 증분 히스토리 기록 (<파일>.history)
+            var history = new ProjectHistory(filePath);
+            await history.SaveAsync(data);
 
             var currentProject = _projectService.CurrentProject;
             if (currentProject != null)
@@ -522,7 +526,7 @@ public partial class MainViewModel : ViewModelBase
                 currentProject.Name = data.ProjectName;
             }
 
-            _toastService?.ShowSuccess("프로젝트 저장 완료", Path.GetFileName(filePath));
+            ShowSaveResult(filePath, history);
         }
         catch (Exception ex)
         {
@@ -531,6 +535,21 @@ public partial class MainViewModel : ViewModelBase
         }
     }
 
+    /// <summary>
+    /// 저장 결과 알림 (프로젝트는 저장됐지만 히스토리 기록만 실패하면 경고)
+    /// </summary>
+    private void ShowSaveResult(string filePath, ProjectHistory history)
+    {
+        if (history.HistoryError != null)
+        {
+            System.Diagnostics.Debug.WriteLine($"ProjectHistory ERROR: {history.HistoryError}");
+            _toastService?.ShowWarning("프로젝트 저장 완료 (히스토리 기록 실패)", history.HistoryError);
+            return;
+        }
+
+        _toastService?.ShowSuccess("프로젝트 저장 완료", Path.GetFileName(filePath));
+    }
+
     [RelayCommand]
     private async Task LoadProject()
     {
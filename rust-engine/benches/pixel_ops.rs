@@ -0,0 +1,74 @@
+// 픽셀 연산 벤치마크 - SIMD(기본 경로) vs 스칼라 기준 구현, 1080p 프레임 1장
+// 실행: cargo bench --bench pixel_ops
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rust_engine::ffmpeg::YuvColor;
+use rust_engine::rendering::effects::{apply_effects, apply_effects_scalar, EffectParams};
+use rust_engine::subtitle::overlay::{
+    rgba_to_yuv420p, rgba_to_yuv420p_scalar, yuv420p_to_rgba, yuv420p_to_rgba_scalar,
+};
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+
+/// 재현 가능한 의사 난수 바이트 (LCG)
+fn noise(len: usize) -> Vec<u8> {
+    let mut state = 12345u32;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+fn bench_color_conversion(c: &mut Criterion) {
+    let pixels = (WIDTH * HEIGHT) as usize;
+    let yuv = noise(pixels * 3 / 2);
+    let rgba = noise(pixels * 4);
+
+    let mut group = c.benchmark_group("color_conversion");
+    group.throughput(Throughput::Elements(pixels as u64));
+    group.bench_function("yuv420p_to_rgba", |b| {
+        b.iter(|| yuv420p_to_rgba(black_box(&yuv), WIDTH, HEIGHT, YuvColor::BT709))
+    });
+    group.bench_function("yuv420p_to_rgba_scalar", |b| {
+        b.iter(|| yuv420p_to_rgba_scalar(black_box(&yuv), WIDTH, HEIGHT, YuvColor::BT709))
+    });
+    group.bench_function("rgba_to_yuv420p", |b| {
+        b.iter(|| rgba_to_yuv420p(black_box(&rgba), WIDTH, HEIGHT, YuvColor::BT709))
+    });
+    group.bench_function("rgba_to_yuv420p_scalar", |b| {
+        b.iter(|| rgba_to_yuv420p_scalar(black_box(&rgba), WIDTH, HEIGHT, YuvColor::BT709))
+    });
+    group.finish();
+}
+
+fn bench_effects(c: &mut Criterion) {
+    let pixels = (WIDTH * HEIGHT) as usize;
+    let source = noise(pixels * 4);
+    let cases = [
+        ("basic", EffectParams { brightness: 0.1, contrast: 0.2, saturation: 0.3, ..EffectParams::default() }),
+        (
+            "all",
+            EffectParams {
+                brightness: 0.1, contrast: 0.2, saturation: 0.3, temperature: 0.2,
+                gamma: 0.4, highlights: -0.2, shadows: 0.3, tint: 0.1, vignette: 0.5,
+            },
+        ),
+    ];
+
+    let mut group = c.benchmark_group("effects");
+    group.throughput(Throughput::Elements(pixels as u64));
+    for (name, params) in &cases {
+        let mut frame = source.clone();
+        group.bench_function(*name, |b| b.iter(|| apply_effects(black_box(&mut frame), WIDTH, HEIGHT, params)));
+        group.bench_function(format!("{}_scalar", name), |b| {
+            b.iter(|| apply_effects_scalar(black_box(&mut frame), WIDTH, HEIGHT, params))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_color_conversion, bench_effects);
+criterion_main!(benches);
@@ -0,0 +1,133 @@
+// 채널 다운믹스/업믹스 - 임의 채널 레이아웃 PCM을 믹서/인코더의 스테레오로 변환
+// 채널 순서는 FFmpeg 네이티브 순서 (채널 마스크 비트 오름차순 = interleaved 순서)
+// 5.1/7.1: ITU-R BS.775 계수 (센터/서라운드 -3dB, LFE 제외), 모노: 양쪽 같은 레벨로 복제
+// f32 파이프라인이므로 정규화하지 않음 (합산 초과는 믹서 마스터 소프트 클리핑이 처리)
+
+use std::f32::consts::FRAC_1_SQRT_2;
+
+/// FFmpeg 채널 마스크 (AV_CH_*)
+pub const FRONT_LEFT: u64 = 0x1;
+pub const FRONT_RIGHT: u64 = 0x2;
+pub const FRONT_CENTER: u64 = 0x4;
+pub const LOW_FREQUENCY: u64 = 0x8;
+pub const BACK_LEFT: u64 = 0x10;
+pub const BACK_RIGHT: u64 = 0x20;
+const FRONT_LEFT_OF_CENTER: u64 = 0x40;
+const FRONT_RIGHT_OF_CENTER: u64 = 0x80;
+const BACK_CENTER: u64 = 0x100;
+pub const SIDE_LEFT: u64 = 0x200;
+pub const SIDE_RIGHT: u64 = 0x400;
+const TOP_CENTER: u64 = 0x800;
+const TOP_FRONT_LEFT: u64 = 0x1000;
+const TOP_FRONT_CENTER: u64 = 0x2000;
+const TOP_FRONT_RIGHT: u64 = 0x4000;
+const TOP_BACK_LEFT: u64 = 0x8000;
+const TOP_BACK_CENTER: u64 = 0x10000;
+const TOP_BACK_RIGHT: u64 = 0x20000;
+const STEREO_LEFT: u64 = 0x2000_0000;
+const STEREO_RIGHT: u64 = 0x4000_0000;
+const WIDE_LEFT: u64 = 0x8000_0000;
+const WIDE_RIGHT: u64 = 0x1_0000_0000;
+const SURROUND_DIRECT_LEFT: u64 = 0x2_0000_0000;
+const SURROUND_DIRECT_RIGHT: u64 = 0x4_0000_0000;
+const LOW_FREQUENCY_2: u64 = 0x8_0000_0000;
+
+pub const STEREO: u64 = FRONT_LEFT | FRONT_RIGHT;
+pub const SURROUND_5_1: u64 = STEREO | FRONT_CENTER | LOW_FREQUENCY | BACK_LEFT | BACK_RIGHT;
+
+/// 채널 하나의 스테레오 배치 (왼쪽, 오른쪽 게인)
+fn stereo_gains(channel: u64) -> (f32, f32) {
+    match channel {
+        FRONT_LEFT | FRONT_LEFT_OF_CENTER | STEREO_LEFT | WIDE_LEFT => (1.0, 0.0),
+        FRONT_RIGHT | FRONT_RIGHT_OF_CENTER | STEREO_RIGHT | WIDE_RIGHT => (0.0, 1.0),
+        BACK_LEFT | SIDE_LEFT | TOP_FRONT_LEFT | TOP_BACK_LEFT | SURROUND_DIRECT_LEFT => (FRAC_1_SQRT_2, 0.0),
+        BACK_RIGHT | SIDE_RIGHT | TOP_FRONT_RIGHT | TOP_BACK_RIGHT | SURROUND_DIRECT_RIGHT => (0.0, FRAC_1_SQRT_2),
+        LOW_FREQUENCY | LOW_FREQUENCY_2 => (0.0, 0.0),
+        // 센터 계열 + 알 수 없는 채널: 양쪽 -3dB
+        FRONT_CENTER | BACK_CENTER | TOP_CENTER | TOP_FRONT_CENTER | TOP_BACK_CENTER => (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        _ => (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    }
+}
+
+/// 소스 채널 → 스테레오 변환 행렬
+#[derive(Debug, Clone, PartialEq)]
+pub struct StereoDownmix {
+    /// 입력 채널별 (왼쪽, 오른쪽) 게인 (interleaved 순서)
+    gains: Vec<(f32, f32)>,
+}
+
+impl StereoDownmix {
+    /// 채널 마스크로 생성 (0이면 스테레오로 간주)
+    pub fn from_mask(mask: u64) -> Self {
+        let mask = if mask == 0 { STEREO } else { mask };
+        let gains: Vec<(f32, f32)> = (0..64)
+            .map(|bit| 1u64 << bit)
+            .filter(|channel| mask & channel != 0)
+            .map(stereo_gains)
+            .collect();
+        // 모노(센터 1채널 등): 센터 감쇄 없이 양쪽 그대로 — 모노 나레이션이 작게 들리지 않도록
+        if gains.len() == 1 {
+            return Self { gains: vec![(1.0, 1.0)] };
+        }
+        Self { gains }
+    }
+
+    /// 입력 채널 수
+    pub fn channels(&self) -> usize {
+        self.gains.len()
+    }
+
+    /// 변환 없이 그대로 쓰는 스테레오 소스인지
+    pub fn is_passthrough(&self) -> bool {
+        self.gains == [(1.0, 0.0), (0.0, 1.0)]
+    }
+
+    /// interleaved N채널 → interleaved 스테레오 (남는 불완전 프레임은 버림)
+    pub fn apply(&self, input: Vec<f32>) -> Vec<f32> {
+        if self.is_passthrough() {
+            return input;
+        }
+        let channels = self.channels();
+        let mut output = Vec::with_capacity(input.len() / channels * 2);
+        for frame in input.chunks_exact(channels) {
+            let (mut left, mut right) = (0.0, 0.0);
+            for (sample, (gain_left, gain_right)) in frame.iter().zip(&self.gains) {
+                left += sample * gain_left;
+                right += sample * gain_right;
+            }
+            output.push(left);
+            output.push(right);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downmix_layouts() {
+        let stereo = StereoDownmix::from_mask(STEREO);
+        assert!(stereo.is_passthrough());
+        assert_eq!(stereo.apply(vec![0.1, 0.2, 0.3, 0.4]), vec![0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(StereoDownmix::from_mask(0), stereo);
+
+        // 모노 → 양쪽 같은 레벨
+        let mono = StereoDownmix::from_mask(FRONT_CENTER);
+        assert_eq!(mono.channels(), 1);
+        assert_eq!(mono.apply(vec![0.5, -0.25]), vec![0.5, 0.5, -0.25, -0.25]);
+
+        // 5.1 (L R C LFE BL BR): 센터/서라운드 -3dB, LFE 제외
+        let surround = StereoDownmix::from_mask(SURROUND_5_1);
+        assert_eq!(surround.channels(), 6);
+        let out = surround.apply(vec![0.2, 0.1, 0.4, 1.0, 0.2, 0.0]);
+        let c = FRAC_1_SQRT_2;
+        assert!((out[0] - (0.2 + 0.4 * c + 0.2 * c)).abs() < 1e-6);
+        assert!((out[1] - (0.1 + 0.4 * c)).abs() < 1e-6);
+
+        // 5.1(side)도 같은 배치
+        let side = StereoDownmix::from_mask(STEREO | FRONT_CENTER | LOW_FREQUENCY | SIDE_LEFT | SIDE_RIGHT);
+        assert_eq!(side.apply(vec![0.2, 0.1, 0.4, 1.0, 0.2, 0.0]), out);
+    }
+}
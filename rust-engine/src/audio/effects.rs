@@ -0,0 +1,328 @@
+// 오디오 이펙트 체인 - 게인 → 3밴드 EQ → 컴프레서 → 리미터
+// 믹서 출력 포맷(interleaved 스테레오) 청크 단위 처리, 필터/엔벌로프 상태는 체인 인스턴스가 청크 간 유지
+// EQ: RBJ 바이쿼드 (저역 셸프 200Hz, 중역 피크 1kHz, 고역 셸프 5kHz)
+// 컴프레서/리미터는 L/R 링크 (한 채널 피크로 양 채널에 같은 게인 → 스테레오 이미지 유지)
+
+/// EQ 밴드 게인 범위 (dB)
+pub const MAX_EQ_GAIN_DB: f32 = 18.0;
+/// 입력 게인 범위 (dB)
+pub const MAX_EFFECT_GAIN_DB: f32 = 24.0;
+/// 컴프레서 최대 비율
+pub const MAX_COMPRESSOR_RATIO: f32 = 20.0;
+
+/// EQ 밴드 중심/코너 주파수 (Hz)
+const EQ_LOW_HZ: f64 = 200.0;
+const EQ_MID_HZ: f64 = 1000.0;
+const EQ_HIGH_HZ: f64 = 5000.0;
+/// 중역 피크 Q (넓은 대역)
+const EQ_MID_Q: f64 = 0.7;
+
+/// 리미터 게인 복귀 시간 (ms)
+const LIMITER_RELEASE_MS: f32 = 50.0;
+
+/// 이 값 미만의 dB 변화는 0으로 간주
+const DB_EPSILON: f32 = 0.01;
+
+const CHANNELS: usize = 2;
+
+/// 클립/트랙 오디오 이펙트 파라미터 (기본값 = 이펙트 없음)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioEffectParams {
+    /// 입력 게인 (dB, ±24)
+    pub gain_db: f32,
+    /// EQ 저역/중역/고역 (dB, ±18)
+    pub eq_low_db: f32,
+    pub eq_mid_db: f32,
+    pub eq_high_db: f32,
+    /// 컴프레서 임계값 (dBFS, -60 ~ 0)
+    pub compressor_threshold_db: f32,
+    /// 컴프레서 비율 (1.0 = 꺼짐 ~ 20.0)
+    pub compressor_ratio: f32,
+    /// 어택/릴리스 (ms)
+    pub compressor_attack_ms: f32,
+    pub compressor_release_ms: f32,
+    /// 메이크업 게인 (dB, 컴프레서가 켜져 있을 때만)
+    pub compressor_makeup_db: f32,
+    pub limiter_enabled: bool,
+    /// 리미터 천장 (dBFS, -24 ~ 0)
+    pub limiter_ceiling_db: f32,
+}
+
+impl Default for AudioEffectParams {
+    fn default() -> Self {
+        Self {
+            gain_db: 0.0,
+            eq_low_db: 0.0,
+            eq_mid_db: 0.0,
+            eq_high_db: 0.0,
+            compressor_threshold_db: -18.0,
+            compressor_ratio: 1.0,
+            compressor_attack_ms: 10.0,
+            compressor_release_ms: 100.0,
+            compressor_makeup_db: 0.0,
+            limiter_enabled: false,
+            limiter_ceiling_db: -1.0,
+        }
+    }
+}
+
+impl AudioEffectParams {
+    /// 컴프레서 동작 여부
+    pub fn compressor_enabled(&self) -> bool {
+        self.compressor_ratio > 1.0
+    }
+
+    /// 아무 처리도 하지 않는지 — true이면 체인 건너뜀
+    pub fn is_default(&self) -> bool {
+        self.gain_db.abs() < DB_EPSILON
+            && self.eq_gains().iter().all(|g| g.abs() < DB_EPSILON)
+            && !self.compressor_enabled()
+            && !self.limiter_enabled
+    }
+
+    fn eq_gains(&self) -> [f32; 3] {
+        [self.eq_low_db, self.eq_mid_db, self.eq_high_db]
+    }
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// 시간 상수(ms) → 1차 평활 계수
+fn smoothing(time_ms: f32, sample_rate: u32) -> f32 {
+    (-1.0 / (time_ms.max(0.1) * 0.001 * sample_rate as f32)).exp()
+}
+
+/// 바이쿼드 필터 (Transposed Direct Form II, 채널별 상태)
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f32; 3],
+    a: [f32; 2],
+    state: [[f32; 2]; CHANNELS],
+}
+
+#[derive(Clone, Copy)]
+enum BandShape {
+    LowShelf,
+    Peak,
+    HighShelf,
+}
+
+impl Biquad {
+    fn new() -> Self {
+        Self { b: [1.0, 0.0, 0.0], a: [0.0, 0.0], state: [[0.0; 2]; CHANNELS] }
+    }
+
+    /// 계수 갱신 (RBJ Audio EQ Cookbook, 셸프 기울기 S = 1), 필터 상태는 유지 (슬라이더 조작 중 클릭 방지)
+    fn set(&mut self, shape: BandShape, freq: f64, gain_db: f32, sample_rate: u32) {
+        let a = 10f64.powf(gain_db as f64 / 40.0);
+        let w0 = 2.0 * std::f64::consts::PI * freq / sample_rate as f64;
+        let (sin, cos) = w0.sin_cos();
+        let (b0, b1, b2, a0, a1, a2) = match shape {
+            BandShape::Peak => {
+                let alpha = sin / (2.0 * EQ_MID_Q);
+                (1.0 + alpha * a, -2.0 * cos, 1.0 - alpha * a, 1.0 + alpha / a, -2.0 * cos, 1.0 - alpha / a)
+            }
+            BandShape::LowShelf | BandShape::HighShelf => {
+                let k = 2.0 * a.sqrt() * sin / std::f64::consts::SQRT_2;
+                let sign = if matches!(shape, BandShape::LowShelf) { 1.0 } else { -1.0 };
+                (
+                    a * ((a + 1.0) - sign * (a - 1.0) * cos + k),
+                    sign * 2.0 * a * ((a - 1.0) - sign * (a + 1.0) * cos),
+                    a * ((a + 1.0) - sign * (a - 1.0) * cos - k),
+                    (a + 1.0) + sign * (a - 1.0) * cos + k,
+                    -sign * 2.0 * ((a - 1.0) + sign * (a + 1.0) * cos),
+                    (a + 1.0) + sign * (a - 1.0) * cos - k,
+                )
+            }
+        };
+        self.b = [(b0 / a0) as f32, (b1 / a0) as f32, (b2 / a0) as f32];
+        self.a = [(a1 / a0) as f32, (a2 / a0) as f32];
+    }
+
+    fn process(&mut self, channel: usize, x: f32) -> f32 {
+        let z = &mut self.state[channel];
+        let y = self.b[0] * x + z[0];
+        z[0] = self.b[1] * x - self.a[0] * y + z[1];
+        z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// 이펙트 체인 처리 상태 (클립/트랙마다 하나, 청크 간 연속)
+pub struct AudioEffectChain {
+    sample_rate: u32,
+    /// 현재 계수를 계산한 EQ 게인 (바뀌면 계수 재계산)
+    eq_gains: [f32; 3],
+    bands: [Biquad; 3],
+    /// 컴프레서 게인 감소량 (dB, 0 이상)
+    reduction_db: f32,
+    /// 리미터 게인 (선형, 1 이하)
+    limiter_gain: f32,
+}
+
+impl AudioEffectChain {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            eq_gains: [0.0; 3],
+            bands: [Biquad::new(); 3],
+            reduction_db: 0.0,
+            limiter_gain: 1.0,
+        }
+    }
+
+    /// interleaved 스테레오 샘플 제자리 처리
+    pub fn process(&mut self, params: &AudioEffectParams, samples: &mut [f32]) {
+        let gains = params.eq_gains();
+        if gains != self.eq_gains {
+            let shapes = [(BandShape::LowShelf, EQ_LOW_HZ), (BandShape::Peak, EQ_MID_HZ), (BandShape::HighShelf, EQ_HIGH_HZ)];
+            for ((band, (shape, freq)), gain) in self.bands.iter_mut().zip(shapes).zip(gains) {
+                band.set(shape, freq, gain, self.sample_rate);
+            }
+            self.eq_gains = gains;
+        }
+        let active_bands: Vec<usize> = (0..3).filter(|&i| gains[i].abs() >= DB_EPSILON).collect();
+
+        let input_gain = db_to_linear(params.gain_db);
+        let attack = smoothing(params.compressor_attack_ms, self.sample_rate);
+        let release = smoothing(params.compressor_release_ms, self.sample_rate);
+        let slope = 1.0 - 1.0 / params.compressor_ratio.max(1.0);
+        let makeup = db_to_linear(params.compressor_makeup_db);
+        let ceiling = db_to_linear(params.limiter_ceiling_db.min(0.0));
+        let limiter_release = smoothing(LIMITER_RELEASE_MS, self.sample_rate);
+
+        for frame in samples.chunks_exact_mut(CHANNELS) {
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                let mut x = *sample * input_gain;
+                for &band in &active_bands {
+                    x = self.bands[band].process(channel, x);
+                }
+                *sample = x;
+            }
+
+            if params.compressor_enabled() {
+                let peak = frame[0].abs().max(frame[1].abs()).max(1e-9);
+                let over = 20.0 * peak.log10() - params.compressor_threshold_db;
+                let target = over.max(0.0) * slope;
+                let coef = if target > self.reduction_db { attack } else { release };
+                self.reduction_db = target + (self.reduction_db - target) * coef;
+                let gain = db_to_linear(-self.reduction_db) * makeup;
+                frame.iter_mut().for_each(|s| *s *= gain);
+            }
+
+            if params.limiter_enabled {
+                // 천장을 넘는 피크는 즉시 눌러 담고, 이후 천천히 복귀
+                let peak = frame[0].abs().max(frame[1].abs());
+                let required = if peak > ceiling { ceiling / peak } else { 1.0 };
+                self.limiter_gain = if required < self.limiter_gain {
+                    required
+                } else {
+                    1.0 + (self.limiter_gain - 1.0) * limiter_release
+                };
+                let gain = self.limiter_gain;
+                frame.iter_mut().for_each(|s| *s = (*s * gain).clamp(-ceiling, ceiling));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48000;
+
+    /// 스테레오 사인파 (같은 신호 L/R)
+    fn sine(freq: f32, amplitude: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let s = amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / RATE as f32).sin();
+                [s, s]
+            })
+            .collect()
+    }
+
+    /// 뒤쪽 절반의 피크 (필터/엔벌로프 안정 이후)
+    fn tail_peak(samples: &[f32]) -> f32 {
+        samples[samples.len() / 2..].iter().fold(0.0, |m, s| m.max(s.abs()))
+    }
+
+    fn processed(params: &AudioEffectParams, mut samples: Vec<f32>) -> Vec<f32> {
+        AudioEffectChain::new(RATE).process(params, &mut samples);
+        samples
+    }
+
+    /// 1초 사인파 처리 전후 RMS 배율 (뒤쪽 절반 기준, 고주파도 샘플 위치와 무관)
+    fn rms_gain(params: &AudioEffectParams, freq: f32) -> f32 {
+        let input = sine(freq, 0.1, 48000);
+        let output = processed(params, input.clone());
+        let rms = |s: &[f32]| (s[s.len() / 2..].iter().map(|x| x * x).sum::<f32>() / (s.len() / 2) as f32).sqrt();
+        rms(&output) / rms(&input)
+    }
+
+    #[test]
+    fn test_default_params_bypass() {
+        let params = AudioEffectParams::default();
+        assert!(params.is_default());
+        let input = sine(440.0, 0.5, 4800);
+        assert_eq!(processed(&params, input.clone()), input);
+
+        let params = AudioEffectParams { compressor_makeup_db: 6.0, ..AudioEffectParams::default() };
+        assert!(params.is_default(), "컴프레서가 꺼져 있으면 메이크업 게인 무시");
+        assert!(!AudioEffectParams { limiter_enabled: true, ..params }.is_default());
+    }
+
+    #[test]
+    fn test_gain_and_eq_bands() {
+        let gain = AudioEffectParams { gain_db: -6.0, ..AudioEffectParams::default() };
+        assert!((tail_peak(&processed(&gain, sine(440.0, 0.5, 4800))) - 0.25).abs() < 0.01);
+
+        // 저역 셸프 +12dB: 50Hz는 약 4배, 8kHz는 거의 그대로
+        let low = AudioEffectParams { eq_low_db: 12.0, ..AudioEffectParams::default() };
+        let boosted = rms_gain(&low, 50.0);
+        assert!((boosted - 3.98).abs() < 0.4, "low shelf boost: {}", boosted);
+        assert!((rms_gain(&low, 8000.0) - 1.0).abs() < 0.05);
+
+        // 중역 피크 -12dB: 1kHz 약 1/4
+        let mid = AudioEffectParams { eq_mid_db: -12.0, ..AudioEffectParams::default() };
+        let cut = rms_gain(&mid, 1000.0);
+        assert!((cut - 0.25).abs() < 0.02, "mid cut: {}", cut);
+
+        // 고역 셸프 +6dB: 15kHz 약 2배
+        let high = AudioEffectParams { eq_high_db: 6.0, ..AudioEffectParams::default() };
+        let bright = rms_gain(&high, 15000.0);
+        assert!((bright - 2.0).abs() < 0.2, "high shelf boost: {}", bright);
+    }
+
+    #[test]
+    fn test_compressor_and_limiter() {
+        // 0dBFS 입력, 임계값 -20dB, 4:1 → 초과분 20dB가 5dB로 (출력 약 -15dBFS)
+        let compressor = AudioEffectParams {
+            compressor_threshold_db: -20.0,
+            compressor_ratio: 4.0,
+            compressor_attack_ms: 1.0,
+            compressor_release_ms: 500.0,
+            ..AudioEffectParams::default()
+        };
+        let out = tail_peak(&processed(&compressor, sine(440.0, 1.0, 48000)));
+        assert!((20.0 * out.log10() + 15.0).abs() < 1.5, "compressed peak: {} dBFS", 20.0 * out.log10());
+
+        // 임계값보다 작은 신호는 그대로
+        let quiet = tail_peak(&processed(&compressor, sine(440.0, 0.05, 48000)));
+        assert!((quiet - 0.05).abs() < 0.002);
+
+        // 리미터: +12dB 게인으로 넘친 신호도 천장 이하
+        let limiter = AudioEffectParams {
+            gain_db: 12.0,
+            limiter_enabled: true,
+            limiter_ceiling_db: -3.0,
+            ..AudioEffectParams::default()
+        };
+        let limited = processed(&limiter, sine(440.0, 0.9, 9600));
+        let ceiling = db_to_linear(-3.0);
+        assert!(limited.iter().all(|s| s.abs() <= ceiling + 1e-6));
+        assert!(tail_peak(&limited) > ceiling * 0.95);
+    }
+}
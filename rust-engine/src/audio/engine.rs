@@ -0,0 +1,225 @@
+// Pull 방식 오디오 엔진 - 호스트(C# WASAPI/NAudio)가 필요한 만큼 PCM 요청
+// 타임라인 오디오를 믹서(48kHz 스테레오)로 디코딩/믹스 → 호스트 포맷으로 리샘플/채널 변환
+// 연속 요청은 샘플 단위로 이어 붙이고, 위치가 크게 벗어나면 seek로 간주
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::encoding::audio_mixer::AudioMixer;
+use crate::timeline::Timeline;
+
+/// 믹서 출력 포맷 (AudioMixer와 동일)
+const MIX_SAMPLE_RATE: u32 = 48000;
+const MIX_CHANNELS: usize = 2;
+
+/// 믹스 청크 길이 (ms 단위로 잘라야 청크 경계가 샘플 정수 위치와 일치)
+const MIX_CHUNK_MS: i64 = 20;
+
+/// 요청 위치와 내부 위치 차이가 이 이상이면 seek (호스트 클럭 지터 허용)
+const SEEK_TOLERANCE_MS: f64 = 50.0;
+
+/// 지원 출력 포맷 범위
+const MIN_SAMPLE_RATE: u32 = 8000;
+const MAX_SAMPLE_RATE: u32 = 192000;
+const MAX_CHANNELS: u32 = 8;
+
+/// Pull 방식 오디오 엔진
+pub struct AudioEngine {
+    timeline: Arc<Mutex<Timeline>>,
+    mixer: AudioMixer,
+    /// 호스트 출력 샘플레이트/채널
+    sample_rate: u32,
+    channels: u32,
+    /// 믹스된 48kHz 스테레오 샘플 (interleaved, pending_start 프레임부터)
+    pending: VecDeque<f32>,
+    pending_start: i64,
+    /// 마지막 seek 위치 (48kHz 기준 타임라인 프레임) + 이후 출력 프레임 수
+    /// 누적 덧셈 대신 곱셈으로 위치 계산 → 장시간 재생에도 오차 누적 없음
+    origin: f64,
+    output_frames: u64,
+}
+
+impl AudioEngine {
+    /// 엔진 생성 (sample_rate: 8000~192000, channels: 1~8)
+    pub fn new(timeline: Arc<Mutex<Timeline>>, sample_rate: u32, channels: u32) -> Result<Self, String> {
+        if !(MIN_SAMPLE_RATE..=MAX_SAMPLE_RATE).contains(&sample_rate) {
+            return Err(format!("Unsupported sample rate: {}", sample_rate));
+        }
+        if channels == 0 || channels > MAX_CHANNELS {
+            return Err(format!("Unsupported channel count: {}", channels));
+        }
+
+        Ok(Self {
+            timeline,
+            mixer: AudioMixer::new(),
+            sample_rate,
+            channels,
+            pending: VecDeque::new(),
+            pending_start: 0,
+            origin: 0.0,
+            output_frames: 0,
+        })
+    }
+
+    /// timestamp_ms부터 frame_count 프레임 분량 PCM을 out에 채움
+    /// - out: f32 interleaved, 길이 frame_count * channels 이상
+    /// - 직전 요청 끝과 가까운 위치면 끊김 없이 이어서 출력
+    pub fn pull_samples(&mut self, timestamp_ms: i64, frame_count: usize, out: &mut [f32]) -> Result<(), String> {
+        let channels = self.channels as usize;
+        if out.len() < frame_count * channels {
+            return Err(format!("Output buffer too small: {} < {}", out.len(), frame_count * channels));
+        }
+
+        let requested = timestamp_ms.max(0) as f64 * MIX_SAMPLE_RATE as f64 / 1000.0;
+        let tolerance = SEEK_TOLERANCE_MS * MIX_SAMPLE_RATE as f64 / 1000.0;
+        if (requested - self.position()).abs() > tolerance {
+            self.seek(requested);
+        }
+
+        // 선형 보간 리샘플링 (출력 프레임마다 믹서 위치를 step씩 전진)
+        for frame in out[..frame_count * channels].chunks_exact_mut(channels) {
+            let position = self.position();
+            let index = position.floor() as i64;
+            let frac = (position - index as f64) as f32;
+            self.ensure_mixed(index + 1)?;
+
+            let (l0, r0) = self.mixed_frame(index);
+            let (l1, r1) = self.mixed_frame(index + 1);
+            let left = l0 + (l1 - l0) * frac;
+            let right = r0 + (r1 - r0) * frac;
+            write_channels(frame, left, right);
+
+            self.output_frames += 1;
+        }
+
+        // 소비한 믹서 프레임 제거 (보간용 현재 프레임은 유지)
+        let consumed = (self.position().floor() as i64 - self.pending_start).max(0) as usize;
+        let drain = (consumed * MIX_CHANNELS).min(self.pending.len());
+        self.pending.drain(..drain);
+        self.pending_start += (drain / MIX_CHANNELS) as i64;
+
+        Ok(())
+    }
+
+    /// 현재 재생 위치 (다음 출력 샘플의 타임라인 시간)
+    pub fn position_ms(&self) -> i64 {
+        (self.position() * 1000.0 / MIX_SAMPLE_RATE as f64).round() as i64
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u32 {
+        self.channels
+    }
+
+    /// 다음 출력 샘플의 믹서 프레임 위치
+    fn position(&self) -> f64 {
+        self.origin + self.output_frames as f64 * MIX_SAMPLE_RATE as f64 / self.sample_rate as f64
+    }
+
+    /// 위치 이동 (믹스 버퍼는 ms 경계부터 다시 채움)
+    fn seek(&mut self, position: f64) {
+        let frames_per_ms = (MIX_SAMPLE_RATE / 1000) as i64;
+        self.origin = position;
+        self.output_frames = 0;
+        self.pending.clear();
+        self.pending_start = (position.floor() as i64 / frames_per_ms) * frames_per_ms;
+    }
+
+    /// 믹서 프레임 last_index까지 믹스 버퍼 확보
+    fn ensure_mixed(&mut self, last_index: i64) -> Result<(), String> {
+        let frames_per_ms = (MIX_SAMPLE_RATE / 1000) as i64;
+        loop {
+            let pending_end = self.pending_start + (self.pending.len() / MIX_CHANNELS) as i64;
+            if pending_end > last_index {
+                return Ok(());
+            }
+
+            let chunk_start_ms = pending_end / frames_per_ms;
+            let audio_clips = self.timeline.lock()
+                .map_err(|e| format!("Failed to lock timeline: {}", e))?
+                .get_all_audio_sources_at_time(chunk_start_ms);
+
+            let mut samples = self.mixer.mix_range(&audio_clips, chunk_start_ms, MIX_CHUNK_MS as f64);
+            // 디코딩 부족분은 무음으로 채워 위치 정렬 유지
+            samples.resize((MIX_CHUNK_MS * frames_per_ms) as usize * MIX_CHANNELS, 0.0);
+            self.pending.extend(samples);
+        }
+    }
+
+    /// 믹서 프레임 (L, R)
+    fn mixed_frame(&self, index: i64) -> (f32, f32) {
+        let offset = (index - self.pending_start).max(0) as usize * MIX_CHANNELS;
+        match (self.pending.get(offset), self.pending.get(offset + 1)) {
+            (Some(&l), Some(&r)) => (l, r),
+            _ => (0.0, 0.0),
+        }
+    }
+}
+
+/// 스테레오 → 출력 채널 매핑 (모노: 평균, 3채널 이상: L/R 외 무음)
+fn write_channels(frame: &mut [f32], left: f32, right: f32) {
+    match frame.len() {
+        1 => frame[0] = (left + right) * 0.5,
+        _ => {
+            frame[0] = left;
+            frame[1] = right;
+            for sample in &mut frame[2..] {
+                *sample = 0.0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(sample_rate: u32, channels: u32) -> AudioEngine {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        AudioEngine::new(timeline, sample_rate, channels).unwrap()
+    }
+
+    #[test]
+    fn test_engine_format_validation() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        assert!(AudioEngine::new(timeline.clone(), 4000, 2).is_err());
+        assert!(AudioEngine::new(timeline.clone(), 44100, 0).is_err());
+        assert!(AudioEngine::new(timeline, 44100, 6).is_ok());
+    }
+
+    #[test]
+    fn test_pull_continuity_and_seek() {
+        let mut engine = engine(44100, 2);
+        let mut out = vec![1.0f32; 441 * 2];
+
+        // 빈 타임라인 → 무음, 10ms 전진
+        engine.pull_samples(0, 441, &mut out).unwrap();
+        assert!(out.iter().all(|&s| s == 0.0));
+        assert_eq!(engine.position_ms(), 10);
+
+        // 약간 어긋난 요청은 이어서 재생 (seek 안 함)
+        engine.pull_samples(12, 441, &mut out).unwrap();
+        assert_eq!(engine.position_ms(), 20);
+
+        // 멀리 떨어진 요청은 seek
+        engine.pull_samples(5000, 441, &mut out).unwrap();
+        assert_eq!(engine.position_ms(), 5010);
+
+        // 버퍼 부족
+        assert!(engine.pull_samples(5010, 441, &mut out[..100]).is_err());
+    }
+
+    #[test]
+    fn test_write_channels() {
+        let mut mono = [0.0f32; 1];
+        write_channels(&mut mono, 0.2, 0.4);
+        assert!((mono[0] - 0.3).abs() < 1e-6);
+
+        let mut surround = [1.0f32; 6];
+        write_channels(&mut surround, 0.2, 0.4);
+        assert_eq!(surround, [0.2, 0.4, 0.0, 0.0, 0.0, 0.0]);
+    }
+}
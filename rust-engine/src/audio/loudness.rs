@@ -0,0 +1,312 @@
+// 라우드니스 측정 (EBU R128 / ITU-R BS.1770) - 타임라인 믹스의 통합 라우드니스(LUFS)와 트루 피크(dBTP)
+// K-weighting 필터 → 400ms 블록(100ms 간격) 평균 제곱 → 절대 게이트(-70 LUFS) + 상대 게이트(-10 LU)
+// 트루 피크는 4배 오버샘플링 보간 (BS.1770 Annex 2)
+
+use crate::encoding::audio_mixer::AudioMixer;
+use crate::timeline::Timeline;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 절대 게이트 (LUFS)
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+/// 상대 게이트 (1차 게이트 평균 대비 LU)
+const RELATIVE_GATE_LU: f64 = -10.0;
+/// 게이트 블록 = 서브블록(100ms) 4개 (400ms, 75% 겹침)
+const SUB_BLOCKS_PER_BLOCK: usize = 4;
+/// 트루 피크 오버샘플링 배율 / 위상당 탭 수
+const OVERSAMPLING: usize = 4;
+const TAPS_PER_PHASE: usize = 12;
+/// 정규화 시 트루 피크 상한 (dBTP, 스트리밍 플랫폼 권장값)
+pub const TRUE_PEAK_CEILING_DBTP: f64 = -1.0;
+/// 타임라인 측정 시 믹스 단위 (ms)
+const MEASURE_CHUNK_MS: i64 = 100;
+
+/// 측정 결과 (무음이면 둘 다 NEG_INFINITY)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessReport {
+    pub integrated_lufs: f64,
+    pub true_peak_dbtp: f64,
+}
+
+impl LoudnessReport {
+    /// target_lufs로 맞추는 선형 게인 (트루 피크가 TRUE_PEAK_CEILING_DBTP를 넘지 않도록 제한, 무음이면 1.0)
+    pub fn normalization_gain(&self, target_lufs: f64) -> f32 {
+        if !self.integrated_lufs.is_finite() {
+            return 1.0;
+        }
+        let mut gain_db = target_lufs - self.integrated_lufs;
+        if self.true_peak_dbtp.is_finite() {
+            gain_db = gain_db.min(TRUE_PEAK_CEILING_DBTP - self.true_peak_dbtp);
+        }
+        10f64.powf(gain_db / 20.0) as f32
+    }
+}
+
+/// 2차 IIR 필터 (Direct Form I)
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self { b, a, ..Self::default() }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0] - self.a[1] * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// K-weighting 필터 (하이 쉘프 + 하이패스, 샘플레이트별 계수 계산)
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let fs = sample_rate as f64;
+
+    // 1단: 머리 효과 보정 하이 쉘프 (+4dB)
+    let f0 = 1681.974450955533;
+    let gain_db = 3.999843853973347;
+    let q = 0.7071752369554196;
+    let k = (std::f64::consts::PI * f0 / fs).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        [(vh + vb * k / q + k * k) / a0, 2.0 * (k * k - vh) / a0, (vh - vb * k / q + k * k) / a0],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    // 2단: RLB 하이패스 (38Hz)
+    let f0 = 38.13547087602444;
+    let q = 0.5003270373238773;
+    let k = (std::f64::consts::PI * f0 / fs).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad::new([1.0, -2.0, 1.0], [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0]);
+
+    [shelf, high_pass]
+}
+
+/// 트루 피크 보간 필터 (위상별 윈도 싱크, 위상마다 합 1로 정규화)
+fn oversampling_filter() -> Vec<[f64; TAPS_PER_PHASE]> {
+    let taps = OVERSAMPLING * TAPS_PER_PHASE;
+    let center = (taps - 1) as f64 / 2.0;
+    (0..OVERSAMPLING)
+        .map(|phase| {
+            let mut coeffs = [0.0; TAPS_PER_PHASE];
+            for (j, coeff) in coeffs.iter_mut().enumerate() {
+                let n = (phase + OVERSAMPLING * j) as f64;
+                let x = (n - center) / OVERSAMPLING as f64;
+                let sinc = if x == 0.0 { 1.0 } else { (std::f64::consts::PI * x).sin() / (std::f64::consts::PI * x) };
+                let window = 0.5 - 0.5 * (2.0 * std::f64::consts::PI * (n + 0.5) / taps as f64).cos();
+                *coeff = sinc * window;
+            }
+            let sum: f64 = coeffs.iter().sum();
+            coeffs.iter_mut().for_each(|c| *c /= sum);
+            coeffs
+        })
+        .collect()
+}
+
+/// 채널별 상태 (K-weighting 필터 + 트루 피크 입력 이력)
+struct ChannelState {
+    filters: [Biquad; 2],
+    history: [f64; TAPS_PER_PHASE],
+}
+
+/// 스트리밍 라우드니스 측정기 (interleaved PCM 누적 입력)
+pub struct LoudnessMeter {
+    channels: Vec<ChannelState>,
+    oversampling: Vec<[f64; TAPS_PER_PHASE]>,
+    sub_block_frames: usize,
+    sub_block_pos: usize,
+    sub_block_energy: f64,
+    /// 완료된 100ms 서브블록별 채널 합산 평균 제곱
+    sub_blocks: Vec<f64>,
+    /// 최대 절대값 (선형, 샘플/보간 포함)
+    peak: f64,
+}
+
+impl LoudnessMeter {
+    /// 측정기 생성 (채널 가중치는 모두 1.0 — 모노/스테레오 기준, 서라운드 채널 가중치 미적용)
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        let filters = k_weighting(sample_rate);
+        Self {
+            channels: (0..channels.max(1)).map(|_| ChannelState { filters, history: [0.0; TAPS_PER_PHASE] }).collect(),
+            oversampling: oversampling_filter(),
+            sub_block_frames: (sample_rate as usize / 10).max(1),
+            sub_block_pos: 0,
+            sub_block_energy: 0.0,
+            sub_blocks: Vec::new(),
+            peak: 0.0,
+        }
+    }
+
+    /// interleaved PCM 추가
+    pub fn push(&mut self, samples: &[f32]) {
+        let channel_count = self.channels.len();
+        for frame in samples.chunks_exact(channel_count) {
+            for (state, &sample) in self.channels.iter_mut().zip(frame) {
+                let sample = sample as f64;
+
+                // 트루 피크: 이력 갱신 후 위상별 보간값
+                state.history.copy_within(0..TAPS_PER_PHASE - 1, 1);
+                state.history[0] = sample;
+                self.peak = self.peak.max(sample.abs());
+                for phase in &self.oversampling {
+                    let value: f64 = phase.iter().zip(&state.history).map(|(c, x)| c * x).sum();
+                    self.peak = self.peak.max(value.abs());
+                }
+
+                let weighted = state.filters.iter_mut().fold(sample, |x, filter| filter.process(x));
+                self.sub_block_energy += weighted * weighted;
+            }
+
+            self.sub_block_pos += 1;
+            if self.sub_block_pos == self.sub_block_frames {
+                self.sub_blocks.push(self.sub_block_energy / self.sub_block_frames as f64);
+                self.sub_block_pos = 0;
+                self.sub_block_energy = 0.0;
+            }
+        }
+    }
+
+    /// 통합 라우드니스 (LUFS, 게이트 통과 블록이 없으면 NEG_INFINITY)
+    pub fn integrated_lufs(&self) -> f64 {
+        let blocks: Vec<f64> = self.sub_blocks
+            .windows(SUB_BLOCKS_PER_BLOCK)
+            .map(|w| w.iter().sum::<f64>() / SUB_BLOCKS_PER_BLOCK as f64)
+            .filter(|&energy| loudness(energy) > ABSOLUTE_GATE_LUFS)
+            .collect();
+        if blocks.is_empty() {
+            return f64::NEG_INFINITY;
+        }
+
+        let relative_gate = loudness(mean(&blocks)) + RELATIVE_GATE_LU;
+        let gated: Vec<f64> = blocks.into_iter().filter(|&energy| loudness(energy) > relative_gate).collect();
+        if gated.is_empty() {
+            return f64::NEG_INFINITY;
+        }
+        loudness(mean(&gated))
+    }
+
+    /// 트루 피크 (dBTP, 무음이면 NEG_INFINITY)
+    pub fn true_peak_dbtp(&self) -> f64 {
+        if self.peak > 0.0 {
+            20.0 * self.peak.log10()
+        } else {
+            f64::NEG_INFINITY
+        }
+    }
+
+    pub fn report(&self) -> LoudnessReport {
+        LoudnessReport {
+            integrated_lufs: self.integrated_lufs(),
+            true_peak_dbtp: self.true_peak_dbtp(),
+        }
+    }
+}
+
+/// 채널 합산 평균 제곱 → LUFS
+fn loudness(energy: f64) -> f64 {
+    if energy > 0.0 {
+        -0.691 + 10.0 * energy.log10()
+    } else {
+        f64::NEG_INFINITY
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// 타임라인 믹스 구간 측정 (range: 타임라인 ms, 취소되면 None)
+/// 믹서 게인은 1.0 (재생/Export와 같은 트랙/클립 볼륨, 페이드, 엔벌로프 반영)
+pub fn measure_timeline(
+    timeline: &Arc<Mutex<Timeline>>,
+    range: (i64, i64),
+    cancelled: &AtomicBool,
+) -> Result<Option<LoudnessReport>, String> {
+    let mut mixer = AudioMixer::new();
+    let mut meter = LoudnessMeter::new(mixer.sample_rate(), mixer.channels() as usize);
+
+    let mut time_ms = range.0;
+    while time_ms < range.1 {
+        if cancelled.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let sources = timeline.lock()
+            .map_err(|e| format!("Timeline lock failed: {}", e))?
+            .get_all_audio_sources_at_time(time_ms);
+        let chunk_ms = MEASURE_CHUNK_MS.min(range.1 - time_ms);
+        meter.push(&mixer.mix_range(&sources, time_ms, chunk_ms as f64));
+        time_ms += chunk_ms;
+    }
+
+    Ok(Some(meter.report()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo_sine(frequency: f64, amplitude: f64, phase: f64, seconds: f64) -> Vec<f32> {
+        let frames = (48000.0 * seconds) as usize;
+        (0..frames)
+            .flat_map(|i| {
+                let s = (amplitude * (2.0 * std::f64::consts::PI * frequency * i as f64 / 48000.0 + phase).sin()) as f32;
+                [s, s]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_integrated_loudness_sine() {
+        // EBU Tech 3341 기준: 1kHz 스테레오 사인 -23 dBFS → -23 LUFS
+        let mut meter = LoudnessMeter::new(48000, 2);
+        meter.push(&stereo_sine(1000.0, 10f64.powf(-23.0 / 20.0), 0.0, 5.0));
+        assert!((meter.integrated_lufs() + 23.0).abs() < 0.1, "{}", meter.integrated_lufs());
+
+        // 무음은 게이트에서 모두 제외
+        let mut meter = LoudnessMeter::new(48000, 2);
+        meter.push(&vec![0.0; 48000 * 2]);
+        assert_eq!(meter.report().integrated_lufs, f64::NEG_INFINITY);
+        assert_eq!(meter.report().true_peak_dbtp, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_relative_gate_ignores_quiet_part() {
+        // 조용한 구간(-50 dBFS)은 상대 게이트로 제외 → 큰 구간 라우드니스만 남음 (경계 블록 3개만 약간 반영)
+        let mut meter = LoudnessMeter::new(48000, 2);
+        meter.push(&stereo_sine(1000.0, 10f64.powf(-20.0 / 20.0), 0.0, 3.0));
+        meter.push(&stereo_sine(1000.0, 10f64.powf(-50.0 / 20.0), 0.0, 3.0));
+        assert!((meter.integrated_lufs() + 20.0).abs() < 0.3, "{}", meter.integrated_lufs());
+    }
+
+    #[test]
+    fn test_true_peak_between_samples() {
+        // fs/4 사인, 45° 위상 → 샘플 값은 진폭의 0.707배지만 트루 피크는 진폭
+        let mut meter = LoudnessMeter::new(48000, 2);
+        meter.push(&stereo_sine(12000.0, 0.5, std::f64::consts::FRAC_PI_4, 0.5));
+        let expected = 20.0 * 0.5f64.log10();
+        assert!((meter.true_peak_dbtp() - expected).abs() < 0.5, "{}", meter.true_peak_dbtp());
+    }
+
+    #[test]
+    fn test_normalization_gain() {
+        let report = LoudnessReport { integrated_lufs: -20.0, true_peak_dbtp: -10.0 };
+        assert!((report.normalization_gain(-14.0) - 10f32.powf(6.0 / 20.0)).abs() < 1e-5);
+
+        // 트루 피크 상한 (-1 dBTP) 때문에 +2dB까지만
+        let report = LoudnessReport { integrated_lufs: -20.0, true_peak_dbtp: -3.0 };
+        assert!((report.normalization_gain(-14.0) - 10f32.powf(2.0 / 20.0)).abs() < 1e-5);
+
+        let silent = LoudnessReport { integrated_lufs: f64::NEG_INFINITY, true_peak_dbtp: f64::NEG_INFINITY };
+        assert_eq!(silent.normalization_gain(-14.0), 1.0);
+    }
+}
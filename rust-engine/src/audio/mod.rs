@@ -1,4 +1,16 @@
 // 실시간 오디오 재생 모듈
 // cpal 기반 오디오 출력 + 링 버퍼 + 백그라운드 디코딩
+// Pull 방식 PCM 공급 (호스트 오디오 출력용)
+// 멀티캠 오디오 동기화 분석
+// 라우드니스 측정 (EBU R128)
+// 피치 유지 시간 늘이기 (WSOLA)
+// 클립/트랙 오디오 이펙트 (EQ, 컴프레서, 리미터)
+// 채널 다운믹스/업믹스 (5.1/모노 → 스테레오)
 
 pub mod playback;
+pub mod engine;
+pub mod sync;
+pub mod loudness;
+pub mod time_stretch;
+pub mod effects;
+pub mod downmix;
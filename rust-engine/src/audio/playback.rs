@@ -1,5 +1,6 @@
 // 실시간 오디오 재생 엔진
 // cpal로 오디오 출력, 링 버퍼로 샘플 공급, fill thread로 백그라운드 디코딩
+// 출력 콜백마다 실제로 들리는 타임라인 위치를 AudioClock에 보고 (프리뷰 A/V 동기화 기준)
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use std::collections::VecDeque;
@@ -8,7 +9,9 @@ use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 
 use crate::encoding::audio_mixer::AudioMixer;
+use crate::playback::sync::AudioClock;
 use crate::timeline::Timeline;
+use crate::utils::log::log_error;
 
 /// 출력 포맷 상수 (AudioDecoder/AudioMixer와 동일)
 const SAMPLE_RATE: u32 = 48000;
@@ -38,6 +41,8 @@ pub struct AudioPlayback {
     cancelled: Arc<AtomicBool>,
     /// 백그라운드 디코딩 스레드
     fill_thread: Option<JoinHandle<()>>,
+    /// 출력 위치 (cpal 콜백이 갱신)
+    clock: Arc<AudioClock>,
 }
 
 /// 링 버퍼
@@ -66,7 +71,8 @@ impl AudioRingBuffer {
     /// 출력 버퍼에 직접 복사 (할당 없음 — cpal 실시간 callback용)
     /// VecDeque::as_slices()로 내부 슬라이스에서 직접 copy_from_slice
     /// → Vec 할당 제거, 이중 복사 제거, lock 시간 최소화
-    fn fill_output(&mut self, output: &mut [f32]) {
+    /// 반환: 버퍼에서 꺼낸 샘플 수 (부족분 무음 제외)
+    fn fill_output(&mut self, output: &mut [f32]) -> usize {
         let available = self.samples.len().min(output.len());
 
         if available > 0 {
@@ -99,6 +105,7 @@ impl AudioRingBuffer {
         for sample in &mut output[available..] {
             *sample = 0.0;
         }
+        available
     }
 
     fn len(&self) -> usize {
@@ -230,10 +237,14 @@ impl AudioPlayback {
         // cpal 출력 스트림 (선행 디코딩된 버퍼에서 즉시 재생)
         let buffer_for_stream = Arc::clone(&buffer);
         let is_playing_for_stream = Arc::clone(&is_playing);
+        let clock = Arc::new(AudioClock::new());
+        let clock_for_stream = Arc::clone(&clock);
+        // 출력한 링 버퍼 샘플 수 (링 버퍼는 start_time_ms부터 연속 → 출력 위치)
+        let mut played_samples: u64 = 0;
 
         let stream = device.build_output_stream(
             &config,
-            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+            move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
                 if !is_playing_for_stream.load(Ordering::Relaxed) {
                     for sample in data.iter_mut() {
                         *sample = 0.0;
@@ -241,11 +252,20 @@ impl AudioPlayback {
                     return;
                 }
 
+                // 지금 들리는 위치 = 이번 버퍼 시작 위치 - 장치 출력 지연
+                let timestamp = info.timestamp();
+                let latency_ms = timestamp.playback.duration_since(&timestamp.callback)
+                    .map(|d| d.as_secs_f64() * 1000.0)
+                    .unwrap_or(0.0);
+                let buffer_start_ms = start_time_ms as f64
+                    + played_samples as f64 * 1000.0 / (SAMPLE_RATE * CHANNELS) as f64;
+                clock_for_stream.update_nonblocking(buffer_start_ms - latency_ms, true, std::time::Instant::now());
+
                 // try_lock: 오디오 스레드는 절대 블로킹하면 안 됨
                 // fill_output: VecDeque에서 직접 복사 → 힙 할당 없음
                 match buffer_for_stream.try_lock() {
                     Ok(mut buf) => {
-                        buf.fill_output(data);
+                        played_samples += buf.fill_output(data) as u64;
                     }
                     Err(_) => {
                         // lock 실패 시 무음 (fill thread가 push 중)
@@ -256,7 +276,7 @@ impl AudioPlayback {
                 }
             },
             move |err| {
-                eprintln!("[AUDIO_PLAYBACK] 스트림 에러: {}", err);
+                log_error!("[AUDIO_PLAYBACK] 스트림 에러: {}", err);
             },
             None,
         ).map_err(|e| format!("오디오 스트림 생성 실패: {}", e))?;
@@ -269,6 +289,7 @@ impl AudioPlayback {
             is_playing,
             cancelled,
             fill_thread: Some(fill_thread),
+            clock,
         })
     }
 
@@ -276,6 +297,7 @@ impl AudioPlayback {
     pub fn stop(&mut self) {
         self.cancelled.store(true, Ordering::Relaxed);
         self.is_playing.store(false, Ordering::Relaxed);
+        self.clock.set_running(false);
 
         // fill thread 종료 대기
         if let Some(handle) = self.fill_thread.take() {
@@ -291,12 +313,18 @@ impl AudioPlayback {
     /// 일시정지
     pub fn pause(&self) {
         self.is_playing.store(false, Ordering::Relaxed);
+        self.clock.set_running(false);
     }
 
-    /// 재개
+    /// 재개 (다음 출력 콜백부터 위치 보고 재개)
     pub fn resume(&self) {
         self.is_playing.store(true, Ordering::Relaxed);
     }
+
+    /// 출력 위치 클럭 (PlaybackController::set_audio_clock에 연결)
+    pub fn clock(&self) -> Arc<AudioClock> {
+        Arc::clone(&self.clock)
+    }
 }
 
 impl Drop for AudioPlayback {
@@ -0,0 +1,160 @@
+// 멀티캠 오디오 동기화 - 오디오 엔벨로프 상호상관(cross-correlation)으로 오프셋 계산
+// 같은 현장음을 녹음한 여러 클립의 시작 시점 차이를 추정
+
+/// 엔벨로프 샘플레이트 (Hz) — 5ms 블록, 30fps 프레임(33ms)보다 충분히 정밀
+pub const ENVELOPE_RATE: u32 = 200;
+
+/// 최소 겹침 비율 — 겹침이 너무 짧은 lag는 우연한 상관으로 간주하고 제외
+const MIN_OVERLAP_RATIO: f64 = 0.5;
+
+/// 동기화 결과
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncOffset {
+    /// reference 시작 기준 target 시작 위치 (ms)
+    /// 양수: target이 늦게 녹화 시작 → 타임라인에서 offset만큼 뒤에 배치
+    pub offset_ms: i64,
+    /// 정규화 상관계수 (0.0~1.0, 높을수록 신뢰)
+    pub confidence: f32,
+}
+
+/// interleaved PCM → 진폭 엔벨로프 (모노, ENVELOPE_RATE Hz)
+/// 블록별 평균 절대값. 블록 경계가 맞도록 청크 길이는 5ms 배수로 넘길 것
+pub fn envelope(samples: &[f32], channels: usize, sample_rate: u32) -> Vec<f32> {
+    let channels = channels.max(1);
+    let frames_per_block = ((sample_rate / ENVELOPE_RATE) as usize).max(1);
+    let block_len = frames_per_block * channels;
+
+    samples
+        .chunks(block_len)
+        .map(|block| block.iter().map(|s| s.abs()).sum::<f32>() / block.len() as f32)
+        .collect()
+}
+
+/// 두 엔벨로프의 최적 lag 탐색 (엔벨로프 샘플 단위)
+/// reference[i + lag] ≈ target[i]가 되는 lag와 정규화 상관계수 반환
+/// max_lag: 탐색 범위 (±)
+pub fn find_lag(reference: &[f32], target: &[f32], max_lag: usize) -> Option<(i64, f32)> {
+    if reference.is_empty() || target.is_empty() {
+        return None;
+    }
+
+    let x = zero_mean(reference);
+    let y = zero_mean(target);
+
+    // 겹침 구간 에너지를 O(1)로 구하기 위한 누적합
+    let x_energy = prefix_energy(&x);
+    let y_energy = prefix_energy(&y);
+
+    let min_overlap = ((x.len().min(y.len()) as f64 * MIN_OVERLAP_RATIO) as usize).max(1);
+    let max_lag = max_lag as i64;
+
+    let mut best: Option<(i64, f32)> = None;
+
+    for lag in -max_lag..=max_lag {
+        // target 인덱스 i의 유효 범위: 0 <= i < y.len(), 0 <= i + lag < x.len()
+        let i_start = (-lag).max(0) as usize;
+        let i_end = (x.len() as i64 - lag).min(y.len() as i64);
+        if i_end <= i_start as i64 {
+            continue;
+        }
+        let i_end = i_end as usize;
+        if i_end - i_start < min_overlap {
+            continue;
+        }
+
+        let x_start = (i_start as i64 + lag) as usize;
+        let x_end = (i_end as i64 + lag) as usize;
+
+        let dot: f64 = x[x_start..x_end]
+            .iter()
+            .zip(&y[i_start..i_end])
+            .map(|(a, b)| (*a as f64) * (*b as f64))
+            .sum();
+
+        let ex = x_energy[x_end] - x_energy[x_start];
+        let ey = y_energy[i_end] - y_energy[i_start];
+        if ex <= f64::EPSILON || ey <= f64::EPSILON {
+            continue;
+        }
+
+        let score = (dot / (ex * ey).sqrt()) as f32;
+        if !matches!(best, Some((_, s)) if s >= score) {
+            best = Some((lag, score));
+        }
+    }
+
+    best
+}
+
+/// 엔벨로프 기준 오프셋 계산 (ms 변환 포함)
+pub fn compute_offset(reference_env: &[f32], target_env: &[f32], max_offset_ms: i64) -> Option<SyncOffset> {
+    let ms_per_sample = 1000 / ENVELOPE_RATE as i64;
+    let max_lag = (max_offset_ms.max(0) / ms_per_sample) as usize;
+
+    find_lag(reference_env, target_env, max_lag).map(|(lag, score)| SyncOffset {
+        offset_ms: lag * ms_per_sample,
+        confidence: score.clamp(0.0, 1.0),
+    })
+}
+
+fn zero_mean(data: &[f32]) -> Vec<f32> {
+    let mean = data.iter().sum::<f32>() / data.len() as f32;
+    data.iter().map(|v| v - mean).collect()
+}
+
+fn prefix_energy(data: &[f32]) -> Vec<f64> {
+    let mut prefix = Vec::with_capacity(data.len() + 1);
+    let mut acc = 0.0f64;
+    prefix.push(acc);
+    for v in data {
+        acc += (*v as f64) * (*v as f64);
+        prefix.push(acc);
+    }
+    prefix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 의사 난수 엔벨로프 (박수/대사처럼 불규칙한 진폭)
+    fn test_signal(len: usize) -> Vec<f32> {
+        let mut state = 12345u32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                ((state >> 16) & 0x7fff) as f32 / 32768.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_envelope_block_size() {
+        // 48kHz stereo 1초 → 200 블록
+        let samples = vec![0.5f32; 48000 * 2];
+        let env = envelope(&samples, 2, 48000);
+        assert_eq!(env.len(), ENVELOPE_RATE as usize);
+        assert!((env[0] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_compute_offset() {
+        let source = test_signal(4000);
+
+        // target이 reference보다 300 샘플(1500ms) 늦게 녹화 시작
+        let reference = source[..3000].to_vec();
+        let target = source[300..3300].to_vec();
+        let result = compute_offset(&reference, &target, 5000).unwrap();
+        assert_eq!(result.offset_ms, 1500);
+        assert!(result.confidence > 0.9);
+
+        // 반대 방향 (target이 먼저 시작)
+        let result = compute_offset(&target, &reference, 5000).unwrap();
+        assert_eq!(result.offset_ms, -1500);
+    }
+
+    #[test]
+    fn test_compute_offset_empty() {
+        assert!(compute_offset(&[], &[0.1, 0.2], 1000).is_none());
+    }
+}
@@ -0,0 +1,197 @@
+// 시간 늘이기 (WSOLA) - 피치를 유지한 채 재생 속도 변경
+// 출력 hop마다 입력에서 speed배 간격의 세그먼트를 고르되, 이전 세그먼트의 자연스러운 이어짐과
+// 파형이 가장 비슷한 위치(±SEARCH_FRAMES)를 골라 Hann 창으로 겹쳐 더함 (위상 불연속 방지)
+// 스트리밍: push로 입력을 넣고 pull로 원하는 만큼 출력 (청크 경계에서도 연속)
+
+use std::collections::VecDeque;
+
+/// 세그먼트 길이 (프레임, 48kHz 기준 ~21ms)
+const WINDOW_FRAMES: usize = 1024;
+/// 출력 hop (50% 겹침 → Hann 창 합 = 1)
+const HOP_FRAMES: usize = WINDOW_FRAMES / 2;
+/// 세그먼트 위치 탐색 범위 (±프레임)
+const SEARCH_FRAMES: usize = 256;
+/// 파형 유사도 비교 길이 (프레임)
+const COMPARE_FRAMES: usize = 256;
+
+/// 스트리밍 WSOLA 시간 늘이기 (interleaved PCM)
+pub struct TimeStretcher {
+    speed: f64,
+    channels: usize,
+    window: Vec<f32>,
+    /// 버퍼된 입력 (interleaved), input[0] = 절대 프레임 input_start
+    input: Vec<f32>,
+    input_start: usize,
+    /// 다음 세그먼트 기준 위치 (절대 입력 프레임)
+    next_analysis: f64,
+    /// 이전 세그먼트의 자연스러운 이어짐 위치 (절대 입력 프레임)
+    prev_natural: Option<usize>,
+    /// 겹침 누적 버퍼 (WINDOW_FRAMES 프레임)
+    overlap: Vec<f32>,
+    /// 완성된 출력
+    output: VecDeque<f32>,
+    /// 시작 정렬용으로 버릴 출력 프레임 수
+    skip_frames: usize,
+}
+
+impl TimeStretcher {
+    /// speed: 재생 속도 (2.0 = 2배속, 출력 길이 = 입력 길이 / speed)
+    pub fn new(speed: f64, channels: usize) -> Self {
+        let channels = channels.max(1);
+        let window = (0..WINDOW_FRAMES)
+            .map(|n| (0.5 - 0.5 * (2.0 * std::f64::consts::PI * n as f64 / WINDOW_FRAMES as f64).cos()) as f32)
+            .collect();
+        // 출력 프레임 j ↔ 입력 프레임 j * speed가 되도록 앞에 무음을 두고 첫 hop 출력은 버림
+        let padding = (HOP_FRAMES as f64 * speed).round() as usize;
+        Self {
+            speed,
+            channels,
+            window,
+            input: vec![0.0; padding * channels],
+            input_start: 0,
+            next_analysis: 0.0,
+            prev_natural: None,
+            overlap: vec![0.0; WINDOW_FRAMES * channels],
+            output: VecDeque::new(),
+            skip_frames: HOP_FRAMES,
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// 입력 추가 후 가능한 만큼 세그먼트 처리
+    pub fn push(&mut self, samples: &[f32]) {
+        self.input.extend_from_slice(samples);
+        while self.process_segment() {}
+    }
+
+    /// 출력 가능한 프레임 수
+    pub fn available_frames(&self) -> usize {
+        self.output.len() / self.channels
+    }
+
+    /// 출력 frames개 꺼내기 (부족하면 뒤를 무음으로 채움)
+    pub fn pull(&mut self, frames: usize) -> Vec<f32> {
+        let len = frames * self.channels;
+        let take = len.min(self.output.len());
+        let mut out: Vec<f32> = self.output.drain(..take).collect();
+        out.resize(len, 0.0);
+        out
+    }
+
+    /// 세그먼트 하나 처리 (입력이 부족하면 false)
+    fn process_segment(&mut self) -> bool {
+        let ch = self.channels;
+        let buffered_end = self.input_start + self.input.len() / ch;
+        let nominal = self.next_analysis.round() as usize;
+        let search_start = nominal.saturating_sub(SEARCH_FRAMES).max(self.input_start);
+        if nominal + SEARCH_FRAMES + WINDOW_FRAMES > buffered_end {
+            return false;
+        }
+
+        // 이전 세그먼트 이어짐과 가장 비슷한 위치
+        let position = match self.prev_natural {
+            Some(natural) => self.best_match(natural, search_start, nominal + SEARCH_FRAMES),
+            None => nominal,
+        };
+
+        // Hann 창 세그먼트 겹쳐 더하기
+        let offset = (position - self.input_start) * ch;
+        for (i, &w) in self.window.iter().enumerate() {
+            for c in 0..ch {
+                self.overlap[i * ch + c] += self.input[offset + i * ch + c] * w;
+            }
+        }
+
+        // 앞 hop 완성 → 출력
+        let done = HOP_FRAMES * ch;
+        let skip = self.skip_frames.min(HOP_FRAMES);
+        self.skip_frames -= skip;
+        self.output.extend(&self.overlap[skip * ch..done]);
+        self.overlap.copy_within(done.., 0);
+        let tail = self.overlap.len() - done;
+        self.overlap[tail..].fill(0.0);
+
+        self.prev_natural = Some(position + HOP_FRAMES);
+        self.next_analysis += HOP_FRAMES as f64 * self.speed;
+
+        // 더 이상 참조하지 않는 입력 버림
+        let keep_from = (self.next_analysis.round() as usize)
+            .saturating_sub(SEARCH_FRAMES)
+            .min(position + HOP_FRAMES)
+            .max(self.input_start);
+        let drop = (keep_from - self.input_start) * ch;
+        self.input.drain(..drop);
+        self.input_start = keep_from;
+        true
+    }
+
+    /// [start, end) 범위에서 natural 위치 파형과 상관이 가장 큰 위치 (채널 합산)
+    fn best_match(&self, natural: usize, start: usize, end: usize) -> usize {
+        let ch = self.channels;
+        let frame_sum = |frame: usize| -> f32 {
+            let offset = (frame - self.input_start) * ch;
+            self.input[offset..offset + ch].iter().sum()
+        };
+        let reference: Vec<f32> = (0..COMPARE_FRAMES).map(|i| frame_sum(natural + i)).collect();
+
+        let mut best = (natural.clamp(start, end - 1), f32::MIN);
+        for candidate in start..end {
+            let score: f32 = reference.iter().enumerate().map(|(i, r)| r * frame_sum(candidate + i)).sum();
+            if score > best.1 {
+                best = (candidate, score);
+            }
+        }
+        best.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo_sine(frequency: f64, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let s = (0.5 * (2.0 * std::f64::consts::PI * frequency * i as f64 / 48000.0).sin()) as f32;
+                [s, s]
+            })
+            .collect()
+    }
+
+    /// 왼쪽 채널 상승 영교차 수
+    fn zero_crossings(samples: &[f32]) -> usize {
+        let left: Vec<f32> = samples.iter().step_by(2).copied().collect();
+        left.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count()
+    }
+
+    #[test]
+    fn test_stretch_preserves_pitch() {
+        for speed in [2.0, 0.5] {
+            let mut stretcher = TimeStretcher::new(speed, 2);
+            // 청크 단위 입력 (스트리밍)
+            for chunk in stereo_sine(440.0, 96000).chunks(960 * 2) {
+                stretcher.push(chunk);
+            }
+
+            // 출력 길이 = 입력 / speed (탐색/창 여유분 제외)
+            let frames = stretcher.available_frames();
+            let expected = 96000.0 / speed;
+            assert!((frames as f64 - expected).abs() < 3000.0, "speed {}: {} frames", speed, frames);
+
+            // 피치 유지: 앞 0.05초를 뺀 0.45초 출력의 주기 수 ≈ 440 × 0.45
+            let out = stretcher.pull(24000);
+            let crossings = zero_crossings(&out[2400 * 2..]) as f64 / 0.45;
+            assert!((crossings - 440.0).abs() < 10.0, "speed {}: {} Hz", speed, crossings);
+        }
+    }
+
+    #[test]
+    fn test_pull_pads_with_silence() {
+        let mut stretcher = TimeStretcher::new(1.5, 2);
+        assert_eq!(stretcher.available_frames(), 0);
+        assert_eq!(stretcher.pull(4), vec![0.0; 8]);
+    }
+}
@@ -1,15 +1,21 @@
 // 오디오 디코더 - FFmpeg으로 오디오 스트림을 f32 PCM으로 디코딩
 // Export 오디오 믹싱 + 실시간 재생 겸용
+// 소스별 리샘플러(샘플레이트/포맷 → 48kHz f32, 채널 유지) + 스테레오 다운믹스/업믹스 (audio::downmix)
 
+use crate::audio::downmix::StereoDownmix;
+use crate::ffmpeg::network;
 use ffmpeg_next as ffmpeg;
 use std::path::Path;
 
-/// 오디오 디코더 (f32 stereo 48kHz 출력)
+/// 오디오 디코더 (f32 stereo 48kHz 출력, 소스 44.1kHz/모노/5.1 등 무관)
 pub struct AudioDecoder {
     input_ctx: ffmpeg::format::context::Input,
     audio_stream_index: usize,
     decoder: ffmpeg::codec::decoder::Audio,
+    /// 입력 포맷/샘플레이트 → f32 48kHz (소스 채널 레이아웃 유지)
     resampler: ffmpeg::software::resampling::Context,
+    /// 소스 채널 → 스테레오
+    downmix: StereoDownmix,
     sample_rate: u32,
     channels: u32,
     duration_ms: i64,
@@ -40,18 +46,21 @@ enum SkipResult {
 }
 
 impl AudioDecoder {
-    /// 오디오 파일 열기
+    /// 오디오 파일 열기 (기본 오디오 스트림)
     pub fn open(file_path: &Path) -> Result<Self, String> {
+        Self::open_stream(file_path, None)
+    }
+
+    /// 지정한 오디오 스트림으로 열기 (다국어 더빙, 해설 트랙 등)
+    /// stream_index: 컨테이너 스트림 번호 (None이면 기본 오디오 스트림)
+    pub fn open_stream(file_path: &Path, stream_index: Option<usize>) -> Result<Self, String> {
         ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
 
-        let input_ctx = ffmpeg::format::input(file_path)
+        let input_ctx = network::open_input(file_path)
             .map_err(|e| format!("Failed to open audio file: {}", e))?;
 
         // 오디오 스트림 찾기
-        let audio_stream = input_ctx
-            .streams()
-            .best(ffmpeg::media::Type::Audio)
-            .ok_or("No audio stream found")?;
+        let audio_stream = select_audio_stream(&input_ctx, stream_index)?;
 
         let audio_stream_index = audio_stream.index();
         let codec_params = audio_stream.parameters();
@@ -72,27 +81,21 @@ impl AudioDecoder {
         // 디코더 생성
         let context = ffmpeg::codec::context::Context::from_parameters(codec_params)
             .map_err(|e| format!("Failed to create audio context: {}", e))?;
-        let decoder = context.decoder().audio()
+        let mut decoder = context.decoder().audio()
             .map_err(|e| format!("Failed to get audio decoder: {}", e))?;
 
         let input_sample_rate = decoder.rate();
 
-        // 리샘플러 설정 (입력 포맷 → f32 stereo 48kHz)
-        let resampler = ffmpeg::software::resampling::Context::get(
-            decoder.format(),
-            decoder.channel_layout(),
-            decoder.rate(),
-            ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
-            ffmpeg::ChannelLayout::STEREO,
-            OUTPUT_SAMPLE_RATE,
-        )
-        .map_err(|e| format!("Failed to create resampler: {}", e))?;
+        // 채널 레이아웃 확정 → 리샘플러(입력 포맷 → f32 48kHz) + 스테레오 변환
+        let downmix = StereoDownmix::from_mask(native_channel_mask(&mut decoder));
+        let resampler = create_resampler(&decoder)?;
 
         Ok(Self {
             input_ctx,
             audio_stream_index,
             decoder,
             resampler,
+            downmix,
             sample_rate: OUTPUT_SAMPLE_RATE,
             channels: OUTPUT_CHANNELS,
             duration_ms,
@@ -214,7 +217,7 @@ impl AudioDecoder {
                         SkipResult::SkipEntire => continue,
                         SkipResult::Partial(skip_count) => {
                             skip_active = false;
-                            let samples = self.resample_frame(&decoded)?;
+                            let samples = self.resample_frame(&mut decoded)?;
                             if skip_count < samples.len() {
                                 result.extend_from_slice(&samples[skip_count..]);
                             }
@@ -227,7 +230,7 @@ impl AudioDecoder {
                     }
                 }
 
-                let samples = self.resample_frame(&decoded)?;
+                let samples = self.resample_frame(&mut decoded)?;
                 result.extend_from_slice(&samples);
 
                 if result.len() >= num_samples {
@@ -269,17 +272,36 @@ impl AudioDecoder {
     }
 
     /// 리샘플링: ffmpeg Audio 프레임 → f32 interleaved stereo
-    fn resample_frame(&mut self, frame: &ffmpeg::frame::Audio) -> Result<Vec<f32>, String> {
-        let mut resampled = ffmpeg::frame::Audio::empty();
+    fn resample_frame(&mut self, frame: &mut ffmpeg::frame::Audio) -> Result<Vec<f32>, String> {
+        let source_channels = self.downmix.channels();
+        unsafe {
+            // 레이아웃 정보 없는 프레임 → 디코더에서 확정한 레이아웃 (다르면 리샘플러가 입력 변경 에러)
+            let frame_layout = &mut (*frame.as_mut_ptr()).ch_layout;
+            if frame_layout.order != ffmpeg::ffi::AVChannelOrder::AV_CHANNEL_ORDER_NATIVE {
+                ffmpeg::ffi::av_channel_layout_uninit(frame_layout);
+                ffmpeg::ffi::av_channel_layout_copy(frame_layout, &(*self.decoder.as_ptr()).ch_layout);
+            }
+        }
+
+        // 출력 버퍼는 리샘플러 예상 출력 수만큼 (빈 프레임을 넘기면 입력 샘플 수로 할당
+        // → 업샘플링(44.1→48kHz) 초과분이 리샘플러에 쌓여 재생이 점점 늦어짐)
+        let capacity = unsafe {
+            ffmpeg::ffi::swr_get_out_samples(self.resampler.as_mut_ptr(), frame.samples() as i32)
+        }.max(1) as usize;
+        let mut resampled = ffmpeg::frame::Audio::new(
+            ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
+            capacity,
+            self.decoder.channel_layout(),
+        );
         self.resampler.run(frame, &mut resampled)
             .map_err(|e| format!("Resample failed: {}", e))?;
 
         let data = resampled.data(0);
-        let sample_count = resampled.samples() * self.channels as usize;
+        let sample_count = resampled.samples() * source_channels;
         let byte_count = sample_count * std::mem::size_of::<f32>();
 
         if data.len() < byte_count {
-            return Ok(vec![0.0f32; sample_count]);
+            return Ok(vec![0.0f32; resampled.samples() * self.channels as usize]);
         }
 
         // f32 변환
@@ -292,7 +314,7 @@ impl AudioDecoder {
             );
         }
 
-        Ok(samples)
+        Ok(self.downmix.apply(samples))
     }
 
     /// 특정 시간으로 seek
@@ -303,6 +325,8 @@ impl AudioDecoder {
         self.input_ctx.seek(ts_us, ..ts_us)
             .map_err(|e| format!("Audio seek failed: {}", e))?;
         self.decoder.flush();
+        // 리샘플러 지연 버퍼도 이전 위치 샘플 → 새로 생성
+        self.resampler = create_resampler(&self.decoder)?;
         // seek 시 leftover 폐기 (이전 위치의 샘플이므로 무효)
         self.leftover_samples.clear();
         self.current_pos_ms = timestamp_ms;
@@ -313,3 +337,50 @@ impl AudioDecoder {
     pub fn channels(&self) -> u32 { self.channels }
     pub fn duration_ms(&self) -> i64 { self.duration_ms }
 }
+
+/// 디코더 채널 레이아웃 확정 → 채널 마스크 (interleaved 채널 순서)
+/// 레이아웃 정보가 없거나(모노/5.1 WAV 등) 네이티브 순서가 아니면 채널 수 기본 레이아웃으로 대체
+fn native_channel_mask(decoder: &mut ffmpeg::codec::decoder::Audio) -> u64 {
+    unsafe {
+        let layout = &mut (*decoder.as_mut_ptr()).ch_layout;
+        if layout.order != ffmpeg::ffi::AVChannelOrder::AV_CHANNEL_ORDER_NATIVE {
+            let channels = layout.nb_channels.max(1);
+            ffmpeg::ffi::av_channel_layout_uninit(layout);
+            ffmpeg::ffi::av_channel_layout_default(layout, channels);
+        }
+        layout.u.mask
+    }
+}
+
+/// 리샘플러 생성 (입력 포맷/샘플레이트 → f32 packed 48kHz, 채널 레이아웃은 소스 그대로)
+fn create_resampler(decoder: &ffmpeg::codec::decoder::Audio) -> Result<ffmpeg::software::resampling::Context, String> {
+    ffmpeg::software::resampling::Context::get(
+        decoder.format(),
+        decoder.channel_layout(),
+        decoder.rate(),
+        ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
+        decoder.channel_layout(),
+        OUTPUT_SAMPLE_RATE,
+    )
+    .map_err(|e| format!("Failed to create resampler: {}", e))
+}
+
+/// 오디오 스트림 선택 (지정 번호가 오디오가 아니면 에러, None이면 기본 스트림)
+pub fn select_audio_stream(
+    input_ctx: &ffmpeg::format::context::Input,
+    stream_index: Option<usize>,
+) -> Result<ffmpeg::Stream<'_>, String> {
+    match stream_index {
+        Some(index) => {
+            let stream = input_ctx.stream(index).ok_or_else(|| format!("Stream {} not found", index))?;
+            if stream.parameters().medium() != ffmpeg::media::Type::Audio {
+                return Err(format!("Stream {} is not an audio stream", index));
+            }
+            Ok(stream)
+        }
+        None => input_ctx
+            .streams()
+            .best(ffmpeg::media::Type::Audio)
+            .ok_or_else(|| "No audio stream found".to_string()),
+    }
+}
@@ -1,35 +1,93 @@
 // 오디오 믹서 - 다중 오디오 클립을 하나의 PCM 스트림으로 합성
 // Export 시 프레임 단위로 호출
+// 클립 이펙트는 클립별, 트랙 이펙트는 트랙 버스(트랙 클립 합산)에 적용 후 마스터로 합산
+// 소스 샘플레이트/채널 레이아웃(44.1kHz, 모노, 5.1 등)은 소스별 AudioDecoder가 48kHz 스테레오로 변환
 
+use crate::audio::effects::AudioEffectChain;
+use crate::audio::time_stretch::TimeStretcher;
 use crate::encoding::audio_decoder::AudioDecoder;
-use crate::timeline::AudioClip;
+use crate::timeline::{AudioClip, AudioSource};
+use crate::utils::log::log_warn;
 use std::collections::HashMap;
 
 /// 출력 포맷 상수
 const OUTPUT_SAMPLE_RATE: u32 = 48000;
 const OUTPUT_CHANNELS: u32 = 2;
 
+/// 피치 유지 클립 디코딩 단위 (ms, 정수 ms로 요청해야 디코더가 seek 없이 이어서 디코딩)
+const STRETCH_FEED_MS: i64 = 20;
+/// 예상 시간과 이만큼 어긋나면 탐색으로 보고 시간 늘이기 상태 초기화 (ms)
+const STRETCH_RESYNC_MS: f64 = 2.0;
+
+/// 피치 유지 클립별 시간 늘이기 상태 (청크 간 연속)
+struct StretchState {
+    stretcher: TimeStretcher,
+    /// 다음 청크로 예상되는 타임라인 시간
+    next_timestamp_ms: f64,
+    /// 디코더에 요청한 원본 끝 위치 (ms)
+    source_fed_ms: i64,
+}
+
+impl StretchState {
+    fn new(speed: f64, source_start_ms: i64, timestamp_ms: i64) -> Self {
+        Self {
+            stretcher: TimeStretcher::new(speed, OUTPUT_CHANNELS as usize),
+            next_timestamp_ms: timestamp_ms as f64,
+            source_fed_ms: source_start_ms,
+        }
+    }
+
+    /// 출력 frames개가 모일 때까지 원본을 이어서 디코딩 (파일 끝이면 무음으로 채움)
+    fn fill(&mut self, decoder: &mut AudioDecoder, frames: usize) -> Result<Vec<f32>, String> {
+        while self.stretcher.available_frames() < frames {
+            let input = decoder.decode_range(self.source_fed_ms, STRETCH_FEED_MS as f64)?;
+            self.source_fed_ms += STRETCH_FEED_MS;
+            if input.is_empty() {
+                break;
+            }
+            self.stretcher.push(&input);
+        }
+        Ok(self.stretcher.pull(frames))
+    }
+}
+
 /// 오디오 믹서
 pub struct AudioMixer {
-    /// 파일별 디코더 캐시 (파일 경로 → AudioDecoder)
+    /// 소스별 디코더 캐시 (소스 키 = 파일 경로 + 오디오 스트림 → AudioDecoder)
     decoder_cache: HashMap<String, AudioDecoder>,
+    /// 피치 유지 클립별 시간 늘이기 상태 (클립 ID → 상태)
+    stretchers: HashMap<u64, StretchState>,
+    /// 이펙트가 있는 클립/트랙별 이펙트 처리 상태 (ID → 체인, 청크 간 필터/엔벌로프 연속)
+    clip_effects: HashMap<u64, AudioEffectChain>,
+    track_effects: HashMap<u64, AudioEffectChain>,
+    /// 마스터 게인 (라우드니스 정규화, 소프트 클리핑 전 적용)
+    master_gain: f32,
 }
 
 impl AudioMixer {
     pub fn new() -> Self {
         Self {
             decoder_cache: HashMap::new(),
+            stretchers: HashMap::new(),
+            clip_effects: HashMap::new(),
+            track_effects: HashMap::new(),
+            master_gain: 1.0,
         }
     }
 
+    /// 마스터 게인 설정 (선형 배율)
+    pub fn set_master_gain(&mut self, gain: f32) {
+        self.master_gain = gain;
+    }
+
     /// 특정 시간 범위의 오디오 믹스 (모든 활성 클립 합산)
-    /// - audio_clips: 현재 시간에 활성인 오디오 클립들
+    /// - audio_clips: 현재 시간에 활성인 오디오 소스들 (클립 볼륨/이펙트 × 트랙 볼륨/팬/이펙트 적용)
     /// - timestamp_ms: 타임라인 시간
     /// - duration_ms: 믹스할 시간 길이 (보통 1 프레임 ≈ 33ms)
     /// - 반환: f32 interleaved stereo PCM (sample_rate = 48kHz)
     pub fn mix_range(
         &mut self,
-        audio_clips: &[AudioClip],
+        audio_clips: &[AudioSource],
         timestamp_ms: i64,
         duration_ms: f64,
     ) -> Vec<f32> {
@@ -38,29 +96,43 @@ impl AudioMixer {
         let mut mixed = vec![0.0f32; num_samples];
 
         if audio_clips.is_empty() {
+            self.stretchers.clear();
+            self.clip_effects.clear();
+            self.track_effects.clear();
             return mixed;
         }
 
-        for clip in audio_clips {
-            // 클립이 이 시간 범위와 겹치는지 확인
-            if timestamp_ms >= clip.end_time_ms() || timestamp_ms + duration_ms as i64 <= clip.start_time_ms {
-                continue;
-            }
+        // 이펙트가 있는 트랙의 버스 (트랙 ID → 합산 버퍼)
+        let mut track_buses: HashMap<u64, Vec<f32>> = HashMap::new();
+
+        let total_frames = num_samples / OUTPUT_CHANNELS as usize;
 
-            // 원본 파일에서의 시간 계산
-            let clip_offset = timestamp_ms - clip.start_time_ms;
-            let source_start = clip.trim_start_ms + clip_offset;
+        for source in audio_clips {
+            let clip = &source.clip;
+            // 청크 중 클립 구간 [start, end)만 믹스 (클립 밖 = 트림된 원본 구간은 들리지 않음)
+            let window = match clip_window(clip, timestamp_ms, total_frames) {
+                Some(window) => window,
+                None => continue,
+            };
+            let window_ms = window.frames as f64 * 1000.0 / OUTPUT_SAMPLE_RATE as f64;
+
+            // 원본 파일에서의 시간 계산 (비디오 timeline_to_source_time과 동일 매핑)
+            let source_start = match clip.timeline_to_source_time(window.start_ms) {
+                Some(source_start) => source_start,
+                None => continue,
+            };
 
-            let file_path = clip.file_path.to_string_lossy().to_string();
+            // 같은 파일이라도 오디오 스트림이 다르면 별도 디코더
+            let file_path = clip.source_key();
 
             // 디코더 가져오기 (캐시에 없으면 생성)
             if !self.decoder_cache.contains_key(&file_path) {
-                match AudioDecoder::open(&clip.file_path) {
+                match AudioDecoder::open_stream(&clip.file_path, clip.stream_index) {
                     Ok(decoder) => {
                         self.decoder_cache.insert(file_path.clone(), decoder);
                     }
                     Err(e) => {
-                        eprintln!("[AUDIO_MIX] 디코더 열기 실패 {}: {}", file_path, e);
+                        log_warn!("[AUDIO_MIX] 디코더 열기 실패 {}: {}", file_path, e);
                         continue;
                     }
                 }
@@ -72,24 +144,90 @@ impl AudioMixer {
             };
 
             // PCM 디코딩 (duration_ms를 f64로 전달 — i64 truncation하면 매 프레임 샘플 부족 → 노이즈)
-            let samples = match decoder.decode_range(source_start, duration_ms) {
+            // 속도 변경 클립은 원본 구간(duration * speed)을 디코딩 후 출력 길이로 리샘플 (피치 함께 변경)
+            // 피치 유지 클립은 클립별 시간 늘이기 상태로 원본을 이어서 디코딩 (청크 간 연속)
+            let decoded = if clip.speed != 1.0 && clip.preserve_pitch {
+                let state = self.stretchers.entry(clip.id)
+                    .or_insert_with(|| StretchState::new(clip.speed, source_start, window.start_ms));
+                if state.stretcher.speed() != clip.speed
+                    || (state.next_timestamp_ms - window.start_ms as f64).abs() > STRETCH_RESYNC_MS
+                {
+                    *state = StretchState::new(clip.speed, source_start, window.start_ms);
+                }
+                state.next_timestamp_ms = window.start_ms as f64 + window_ms;
+                state.fill(decoder, window.frames)
+            } else {
+                decoder.decode_range(source_start, window_ms * clip.speed).map(|s| {
+                    if clip.speed != 1.0 {
+                        resample_linear(&s, OUTPUT_CHANNELS as usize, window.frames)
+                    } else {
+                        s
+                    }
+                })
+            };
+            let mut samples = match decoded {
                 Ok(s) => s,
                 Err(e) => {
-                    eprintln!("[AUDIO_MIX] 디코딩 실패 {}: {}", file_path, e);
+                    log_warn!("[AUDIO_MIX] 디코딩 실패 {}: {}", file_path, e);
                     continue;
                 }
             };
 
-            // 볼륨 적용 + 합산
-            let volume = clip.volume;
-            let len = mixed.len().min(samples.len());
-            for i in 0..len {
-                mixed[i] += samples[i] * volume;
+            if !clip.effects.is_default() {
+                self.clip_effects.entry(clip.id)
+                    .or_insert_with(|| AudioEffectChain::new(OUTPUT_SAMPLE_RATE))
+                    .process(&clip.effects, &mut samples);
+            }
+
+            // 트랙 이펙트가 있으면 트랙 버스, 없으면 바로 마스터에 합산
+            let target = match source.track_id {
+                Some(track_id) if !source.track_effects.is_default() => {
+                    track_buses.entry(track_id).or_insert_with(|| vec![0.0; num_samples])
+                }
+                _ => &mut mixed,
+            };
+
+            // 볼륨/팬/페이드/엔벌로프 적용 + 합산 (페이드/엔벌로프는 프레임마다 타임라인 시간으로 계산)
+            let (left, right) = source.channel_gains();
+            let ms_per_frame = 1000.0 / OUTPUT_SAMPLE_RATE as f64;
+            let frames = target.chunks_exact_mut(OUTPUT_CHANNELS as usize)
+                .skip(window.offset_frames)
+                .take(window.frames)
+                .zip(samples.chunks_exact(OUTPUT_CHANNELS as usize));
+            for (index, (out, frame)) in frames.enumerate() {
+                let time_ms = timestamp_ms as f64 + (window.offset_frames + index) as f64 * ms_per_frame;
+                let gain = clip.fade_gain(time_ms) * clip.envelope_gain(time_ms);
+                out[0] += frame[0] * left * gain;
+                out[1] += frame[1] * right * gain;
             }
         }
 
-        // 소프트 클리핑 (tanh) — 합산 시 1.0 초과 방지
+        // 트랙 이펙트 적용 후 마스터에 합산
+        for (track_id, mut bus) in track_buses {
+            let params = match audio_clips.iter().find(|s| s.track_id == Some(track_id)) {
+                Some(source) => &source.track_effects,
+                None => continue,
+            };
+            self.track_effects.entry(track_id)
+                .or_insert_with(|| AudioEffectChain::new(OUTPUT_SAMPLE_RATE))
+                .process(params, &mut bus);
+            for (out, sample) in mixed.iter_mut().zip(&bus) {
+                *out += sample;
+            }
+        }
+
+        // 이번 청크에 없는 클립의 시간 늘이기/이펙트 상태 정리 (이펙트를 끈 클립/트랙 포함)
+        self.stretchers.retain(|id, _| audio_clips.iter().any(|source| source.clip.id == *id));
+        self.clip_effects.retain(|id, _| {
+            audio_clips.iter().any(|source| source.clip.id == *id && !source.clip.effects.is_default())
+        });
+        self.track_effects.retain(|id, _| {
+            audio_clips.iter().any(|source| source.track_id == Some(*id) && !source.track_effects.is_default())
+        });
+
+        // 마스터 게인 + 소프트 클리핑 (tanh) — 합산 시 1.0 초과 방지
         for sample in &mut mixed {
+            *sample *= self.master_gain;
             if *sample > 1.0 || *sample < -1.0 {
                 *sample = sample.tanh();
             }
@@ -103,3 +241,90 @@ impl AudioMixer {
     /// 출력 채널 수
     pub fn channels(&self) -> u32 { OUTPUT_CHANNELS }
 }
+
+/// 청크 안에서 클립이 차지하는 구간
+#[derive(Debug, PartialEq)]
+struct ClipWindow {
+    /// 구간 시작 (타임라인 ms)
+    start_ms: i64,
+    /// 청크 시작부터 구간 시작까지 (출력 프레임)
+    offset_frames: usize,
+    /// 구간 길이 (출력 프레임)
+    frames: usize,
+}
+
+/// timestamp_ms부터 total_frames 길이 청크와 클립 [start, end)의 겹침 (없으면 None)
+fn clip_window(clip: &AudioClip, timestamp_ms: i64, total_frames: usize) -> Option<ClipWindow> {
+    let frames_per_ms = OUTPUT_SAMPLE_RATE as usize / 1000;
+    let start_ms = clip.start_time_ms.max(timestamp_ms);
+    let offset_frames = (start_ms - timestamp_ms) as usize * frames_per_ms;
+    let end_frames = match usize::try_from(clip.end_time_ms() - timestamp_ms) {
+        Ok(end_ms) => (end_ms * frames_per_ms).min(total_frames),
+        Err(_) => return None,
+    };
+    if offset_frames >= end_frames {
+        return None;
+    }
+    Some(ClipWindow { start_ms, offset_frames, frames: end_frames - offset_frames })
+}
+
+/// interleaved PCM을 out_frames 프레임으로 선형 보간 리샘플 (속도 변경용)
+fn resample_linear(samples: &[f32], channels: usize, out_frames: usize) -> Vec<f32> {
+    let in_frames = samples.len() / channels;
+    if in_frames == 0 || out_frames == 0 {
+        return vec![0.0; out_frames * channels];
+    }
+
+    let step = in_frames as f64 / out_frames as f64;
+    let mut out = Vec::with_capacity(out_frames * channels);
+    for frame in 0..out_frames {
+        let position = frame as f64 * step;
+        let index = (position.floor() as usize).min(in_frames - 1);
+        let next = (index + 1).min(in_frames - 1);
+        let frac = (position - index as f64) as f32;
+        for ch in 0..channels {
+            let a = samples[index * channels + ch];
+            let b = samples[next * channels + ch];
+            out.push(a + (b - a) * frac);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample_linear() {
+        // 2배속: 4프레임 → 2프레임 (하나 건너 하나)
+        let stereo = [0.0, 0.0, 0.1, -0.1, 0.2, -0.2, 0.3, -0.3];
+        assert_eq!(resample_linear(&stereo, 2, 2), vec![0.0, 0.0, 0.2, -0.2]);
+
+        // 0.5배속: 2프레임 → 4프레임 (중간값 보간, 끝은 마지막 프레임 유지)
+        let out = resample_linear(&stereo[..4], 2, 4);
+        assert_eq!(out.len(), 8);
+        assert!((out[2] - 0.05).abs() < 1e-6 && (out[3] + 0.05).abs() < 1e-6);
+        assert_eq!(&out[6..], &[0.1, -0.1]);
+
+        assert_eq!(resample_linear(&[], 2, 3), vec![0.0; 6]);
+    }
+
+    #[test]
+    fn test_clip_window_limits_mix_to_clip_range() {
+        // 클립 [1010, 1030), 청크 1000ms부터 33ms (1584프레임)
+        let clip = AudioClip::new(1, "a.wav".into(), 1010, 20);
+        let window = clip_window(&clip, 1000, 1584).unwrap();
+        assert_eq!(window, ClipWindow { start_ms: 1010, offset_frames: 480, frames: 960 });
+
+        // 청크 안에서 시작, 청크 끝까지
+        let long = AudioClip::new(2, "a.wav".into(), 1010, 5000);
+        assert_eq!(clip_window(&long, 1000, 1584).unwrap().frames, 1584 - 480);
+        // 청크 중간에 끝남
+        assert_eq!(clip_window(&long, 5990, 1584).unwrap(), ClipWindow { start_ms: 5990, offset_frames: 0, frames: 960 });
+
+        // 겹치지 않음
+        assert_eq!(clip_window(&clip, 1030, 1584), None);
+        assert_eq!(clip_window(&clip, 900, 1584), None);
+    }
+}
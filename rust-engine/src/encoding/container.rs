@@ -0,0 +1,101 @@
+// 출력 컨테이너 - 확장자로 먹서 결정 + 코덱 호환성 검증
+// MP4 / MOV / MKV / WebM
+
+use crate::encoding::encoder::VideoCodec;
+use std::path::Path;
+
+/// 출력 컨테이너
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Container {
+    Mp4,
+    Mov,
+    Mkv,
+    WebM,
+}
+
+impl Container {
+    /// 출력 경로 확장자로 컨테이너 결정 (확장자 없으면 MP4)
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        match ext.as_deref() {
+            None | Some("mp4") | Some("m4v") => Ok(Container::Mp4),
+            Some("mov") => Ok(Container::Mov),
+            Some("mkv") => Ok(Container::Mkv),
+            Some("webm") => Ok(Container::WebM),
+            Some(other) => Err(format!("지원하지 않는 컨테이너 형식입니다: .{}", other)),
+        }
+    }
+
+    /// FFmpeg 먹서 이름
+    pub fn format_name(self) -> &'static str {
+        match self {
+            Container::Mp4 => "mp4",
+            Container::Mov => "mov",
+            Container::Mkv => "matroska",
+            Container::WebM => "webm",
+        }
+    }
+
+    /// 비디오 코덱 호환 여부
+    pub fn supports_video(self, codec: VideoCodec) -> bool {
+        match self {
+            Container::Mp4 => codec != VideoCodec::ProRes,
+            Container::Mov => matches!(codec, VideoCodec::H264 | VideoCodec::H265 | VideoCodec::ProRes),
+            Container::Mkv => true,
+            Container::WebM => matches!(codec, VideoCodec::Vp9 | VideoCodec::Av1),
+        }
+    }
+
+    /// 코덱/컨테이너 조합 검증
+    pub fn validate(self, codec: VideoCodec) -> Result<(), String> {
+        if self.supports_video(codec) {
+            Ok(())
+        } else {
+            Err(format!("{:?} 코덱은 {:?} 컨테이너에서 지원되지 않습니다", codec, self))
+        }
+    }
+
+    /// 오디오 인코더 이름 (우선순위 순, WebM은 Opus/Vorbis만 허용)
+    pub fn audio_encoders(self) -> &'static [&'static str] {
+        match self {
+            Container::WebM => &["libopus", "libvorbis"],
+            _ => &["aac"],
+        }
+    }
+
+    /// HEVC 스트림에 hvc1 태그 필요 여부 (QuickTime/Apple 기기 재생 호환)
+    pub fn needs_hvc1_tag(self) -> bool {
+        matches!(self, Container::Mp4 | Container::Mov)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_from_path() {
+        assert_eq!(Container::from_path("out.mp4").unwrap(), Container::Mp4);
+        assert_eq!(Container::from_path("C:\\영상\\out.MOV").unwrap(), Container::Mov);
+        assert_eq!(Container::from_path("out.mkv").unwrap(), Container::Mkv);
+        assert_eq!(Container::from_path("out.webm").unwrap(), Container::WebM);
+        assert_eq!(Container::from_path("out").unwrap(), Container::Mp4);
+        assert!(Container::from_path("out.avi").is_err());
+    }
+
+    #[test]
+    fn test_container_codec_validation() {
+        assert!(Container::Mp4.validate(VideoCodec::H265).is_ok());
+        assert!(Container::Mp4.validate(VideoCodec::ProRes).is_err());
+        assert!(Container::Mov.validate(VideoCodec::ProRes).is_ok());
+        assert!(Container::Mov.validate(VideoCodec::Vp9).is_err());
+        assert!(Container::WebM.validate(VideoCodec::H264).is_err());
+        assert!(Container::WebM.validate(VideoCodec::Av1).is_ok());
+        assert!(Container::Mkv.validate(VideoCodec::ProRes).is_ok());
+        assert_eq!(Container::WebM.audio_encoders()[0], "libopus");
+    }
+}
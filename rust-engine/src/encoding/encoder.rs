@@ -1,13 +1,20 @@
-// 비디오/오디오 인코더 - FFmpeg 기반 H.264 + AAC 인코딩
-// RGBA 프레임 → YUV420P → H.264 인코딩
-// f32 PCM → FLTP → AAC 인코딩
-// → MP4 먹싱
+// 비디오/오디오 인코더 - FFmpeg 기반 H.264/H.265/VP9/AV1/ProRes + AAC/Opus 인코딩
+// RGBA 프레임 → YUV420P → 비디오 인코딩
+// f32 PCM → FLTP/FLT → AAC (WebM은 Opus/Vorbis) 인코딩
+// → MP4/MOV/MKV/WebM 먹싱 (출력 확장자로 결정)
 // GPU 하드웨어 가속: NVENC / QSV / AMF 지원
 
 use ffmpeg_next as ffmpeg;
 use ffmpeg::format::Pixel;
 use ffmpeg::codec;
 use ffmpeg::software::scaling;
+use crate::encoding::container::Container;
+use crate::ffmpeg::decoder::set_scaler_colorspace;
+use crate::ffmpeg::{YuvColor, YuvMatrix};
+use crate::timeline::Chapter;
+use crate::utils::log::{log_debug, log_info, log_warn};
+use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
 
 /// 인코더 타입 (FFI u32 매핑)
 #[repr(u32)]
@@ -32,6 +39,164 @@ impl EncoderType {
     }
 }
 
+/// 비디오 코덱 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoCodec {
+    H264 = 0,
+    H265 = 1,
+    Vp9 = 2,
+    Av1 = 3,
+    ProRes = 4, // 마스터용 (4:2:2 10bit, MOV 컨테이너)
+}
+
+impl VideoCodec {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => VideoCodec::H265,
+            2 => VideoCodec::Vp9,
+            3 => VideoCodec::Av1,
+            4 => VideoCodec::ProRes,
+            _ => VideoCodec::H264,
+        }
+    }
+
+    /// 하드웨어 인코더 이름 (NVENC, QSV, AMF 순, 미지원은 None)
+    fn hardware_encoders(self) -> [Option<&'static str>; 3] {
+        match self {
+            VideoCodec::H264 => [Some("h264_nvenc"), Some("h264_qsv"), Some("h264_amf")],
+            VideoCodec::H265 => [Some("hevc_nvenc"), Some("hevc_qsv"), Some("hevc_amf")],
+            VideoCodec::Vp9 => [None, Some("vp9_qsv"), None],
+            VideoCodec::Av1 => [Some("av1_nvenc"), Some("av1_qsv"), Some("av1_amf")],
+            VideoCodec::ProRes => [None, None, None],
+        }
+    }
+
+    /// 소프트웨어 인코더 이름 (우선순위 순)
+    fn software_encoders(self) -> &'static [&'static str] {
+        match self {
+            VideoCodec::H264 => &["libx264"],
+            VideoCodec::H265 => &["libx265"],
+            VideoCodec::Vp9 => &["libvpx-vp9"],
+            VideoCodec::Av1 => &["libsvtav1", "libaom-av1"],
+            VideoCodec::ProRes => &["prores_ks", "prores_aw"],
+        }
+    }
+
+    fn codec_id(self) -> codec::Id {
+        match self {
+            VideoCodec::H264 => codec::Id::H264,
+            VideoCodec::H265 => codec::Id::HEVC,
+            VideoCodec::Vp9 => codec::Id::VP9,
+            VideoCodec::Av1 => codec::Id::AV1,
+            VideoCodec::ProRes => codec::Id::PRORES,
+        }
+    }
+
+    /// 인코더 입력 픽셀 포맷 (ProRes만 4:2:2 10bit)
+    fn pixel_format(self) -> Pixel {
+        match self {
+            VideoCodec::ProRes => Pixel::YUV422P10LE,
+            _ => Pixel::YUV420P,
+        }
+    }
+}
+
+/// 비트레이트 제어 모드 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateControlMode {
+    Crf = 0,        // 고정 품질 (CRF/CQ)
+    Cbr = 1,        // 고정 비트레이트
+    Vbr = 2,        // 가변 비트레이트 (목표 + 최대)
+    TwoPassVbr = 3, // 분석 패스 후 가변 비트레이트 (목표 비트레이트 정확도 우선)
+}
+
+impl RateControlMode {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => RateControlMode::Cbr,
+            2 => RateControlMode::Vbr,
+            3 => RateControlMode::TwoPassVbr,
+            _ => RateControlMode::Crf,
+        }
+    }
+}
+
+/// 비트레이트 제어 설정 (비트레이트 단위: bps)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateControl {
+    pub mode: RateControlMode,
+    pub crf: u32,
+    pub target_bitrate: usize,
+    /// VBR 최대 비트레이트 (0 = 제한 없음)
+    pub max_bitrate: usize,
+    /// VBV 버퍼 크기 (0 = 자동: CBR은 1초, VBR은 최대 비트레이트 2초 분량)
+    pub buffer_size: usize,
+}
+
+impl RateControl {
+    /// CRF 고정 품질
+    pub fn crf(crf: u32) -> Self {
+        Self { mode: RateControlMode::Crf, crf, target_bitrate: 0, max_bitrate: 0, buffer_size: 0 }
+    }
+}
+
+/// 인코딩 패스 (2-pass VBR: 분석 패스 통계 파일을 최종 패스에서 사용)
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncodePass {
+    Single,
+    /// 분석 패스: 통계만 기록 (출력은 null 먹서로 버림)
+    Analysis { stats_path: PathBuf },
+    /// 최종 패스: 분석 통계를 읽어 비트 배분
+    Final { stats_path: PathBuf },
+}
+
+/// 비디오 인코더 설정
+#[derive(Debug, Clone)]
+pub struct VideoEncoderSettings {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    pub encoder_type: EncoderType,
+    pub codec: VideoCodec,
+    pub rate_control: RateControl,
+    pub pass: EncodePass,
+    /// 10bit 픽셀 포맷 출력 (YUV420P10LE/P010, 인코더 미지원 시 8bit)
+    /// 입력 프레임은 8bit YUV420P를 변환한 값 → 컨테이너/스트림 형식만 10bit, 밴딩은 줄지 않음
+    pub high_bit_depth_container: bool,
+}
+
+/// 2-pass 통계 전달 방식 (인코더별)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StatsMode {
+    /// 2-pass 미지원 (단일 패스로 인코딩)
+    Unsupported,
+    /// 인코더가 통계 파일을 직접 읽고 씀 (libx264/libx265)
+    EncoderFile,
+    /// stats_out/stats_in 버퍼로 전달 (libvpx/libaom)
+    Buffer,
+}
+
+fn stats_mode(codec_name: &str) -> StatsMode {
+    match codec_name {
+        "libx264" | "libx265" => StatsMode::EncoderFile,
+        "libvpx-vp9" | "libaom-av1" => StatsMode::Buffer,
+        _ => StatsMode::Unsupported,
+    }
+}
+
+/// 2-pass 분석 패스 출력 (avio가 항상 파일을 열기 때문에 null 디바이스로 보냄)
+#[cfg(windows)]
+const NULL_DEVICE: &str = "NUL";
+#[cfg(not(windows))]
+const NULL_DEVICE: &str = "/dev/null";
+
+/// x264 기준 CRF(0~51)를 0~63 스케일 인코더(VP9/AV1)의 CRF로 변환
+fn scale_crf_63(crf: u32) -> u32 {
+    (crf.min(51) * 63 + 25) / 51
+}
+
 /// 사용 가능한 인코더 탐지 (비트마스크 반환)
 /// bit 0 = libx264, bit 1 = NVENC, bit 2 = QSV, bit 3 = AMF
 pub fn detect_available_encoders() -> u32 {
@@ -41,53 +206,93 @@ pub fn detect_available_encoders() -> u32 {
     if ffmpeg::encoder::find_by_name("h264_nvenc").is_some() { mask |= 2; }
     if ffmpeg::encoder::find_by_name("h264_qsv").is_some() { mask |= 4; }
     if ffmpeg::encoder::find_by_name("h264_amf").is_some() { mask |= 8; }
-    eprintln!("[ENCODER] 탐지된 인코더: mask=0b{:04b} (x264={}, nvenc={}, qsv={}, amf={})",
+    log_info!("[ENCODER] 탐지된 인코더: mask=0b{:04b} (x264={}, nvenc={}, qsv={}, amf={})",
         mask, mask & 1 != 0, mask & 2 != 0, mask & 4 != 0, mask & 8 != 0);
     mask
 }
 
-/// 비디오+오디오 인코더 (H.264 + AAC + MP4 컨테이너)
+/// 비디오+오디오 인코더 (컨테이너는 출력 확장자로 결정)
+/// 출력 컨텍스트에 챕터 추가 (ms 타임베이스, write_header 전)
+/// MP4/MOV는 챕터 텍스트 트랙, MKV/WebM은 Chapters 요소로 기록
+pub fn add_output_chapters(
+    output_ctx: &mut ffmpeg::format::context::Output,
+    chapters: &[Chapter],
+) -> Result<(), String> {
+    for (id, chapter) in chapters.iter().enumerate() {
+        output_ctx
+            .add_chapter(id as i64 + 1, ffmpeg::Rational::new(1, 1000), chapter.start_ms, chapter.end_ms, &chapter.title)
+            .map_err(|e| format!("Failed to add chapter '{}': {}", chapter.title, e))?;
+    }
+    Ok(())
+}
+
 pub struct VideoEncoder {
     output_ctx: ffmpeg::format::context::Output,
     encoder: ffmpeg::encoder::Video,
     audio_encoder: Option<ffmpeg::encoder::Audio>,
     scaler: scaling::Context,
+    // YUV420P → 인코더 픽셀 포맷 변환 (ProRes 등 YUV420P가 아닌 인코더만)
+    yuv_converter: Option<scaling::Context>,
     video_stream_index: usize,
     audio_stream_index: Option<usize>,
     frame_count: i64,
     audio_pts: i64,
+    /// 출력에 기록한 패킷 바이트 합 (비디오+오디오, 평균 비트레이트 통계용)
+    bytes_written: u64,
     time_base: ffmpeg::Rational,
     audio_time_base: Option<ffmpeg::Rational>,
     width: u32,
     height: u32,
-    // 오디오 버퍼링 (AAC 프레임 크기 정렬)
+    // 오디오 버퍼링 (인코더 프레임 크기 정렬)
     audio_buffer: Vec<f32>,       // interleaved stereo (L, R, L, R, ...)
-    audio_frame_size: usize,      // AAC 프레임당 채널당 샘플 수 (보통 1024)
+    audio_frame_size: usize,      // 프레임당 채널당 샘플 수 (AAC 1024, Opus 960)
     audio_channels: u32,
+    audio_sample_format: ffmpeg::format::Sample, // F32 planar(AAC/Vorbis) 또는 packed(Opus)
+    container: Container,
+    // 2-pass: 분석 패스 통계 누적 (Buffer 방식) / 최종 패스 입력 (인코더 수명 동안 유지)
+    pass: EncodePass,
+    stats_mode: StatsMode,
+    stats_out: String,
+    _stats_in: Option<CString>,
 }
 
 impl VideoEncoder {
     /// 비디오 인코더 생성 (오디오는 init_audio로 추가)
-    pub fn new(
-        output_path: &str,
-        width: u32,
-        height: u32,
-        fps: f64,
-        crf: u32,
-        encoder_type: EncoderType,
-    ) -> Result<Self, String> {
+    pub fn new(output_path: &str, settings: &VideoEncoderSettings) -> Result<Self, String> {
         ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
 
-        // 출력 컨텍스트 생성 (MP4 포맷)
-        let mut output_ctx = ffmpeg::format::output(output_path)
+        let (width, height, fps) = (settings.width, settings.height, settings.fps);
+        let (encoder_type, video_codec) = (settings.encoder_type, settings.codec);
+        let rate = settings.rate_control;
+        let crf = rate.crf;
+
+        // 확장자로 컨테이너 결정 + 코덱 호환성 검증
+        let container = Container::from_path(output_path)?;
+        container.validate(video_codec)?;
+
+        // 출력 컨텍스트 생성 (분석 패스는 null 먹서 + null 디바이스 — 출력 파일 미생성)
+        let (open_path, format_name) = match settings.pass {
+            EncodePass::Analysis { .. } => (NULL_DEVICE, "null"),
+            _ => (output_path, container.format_name()),
+        };
+        let mut output_ctx = ffmpeg::format::output_as(open_path, format_name)
             .map_err(|e| format!("Failed to create output: {}", e))?;
 
-        // H.264 인코더 찾기 (타입별 분기 + 자동 폴백)
-        let (codec, codec_name) = Self::find_h264_encoder(encoder_type)?;
+        // 코덱/타입별 인코더 찾기 (하드웨어 → 소프트웨어 → H.264 순 폴백)
+        let (codec, codec_name) = Self::find_encoder(video_codec, encoder_type)?;
+        let actual_codec = if codec.id() == video_codec.codec_id() { video_codec } else { VideoCodec::H264 };
+        if actual_codec != video_codec {
+            // H.264 폴백이 컨테이너와 맞지 않으면 (WebM 등) 에러
+            container.validate(actual_codec)
+                .map_err(|e| format!("{:?} 인코더 없음 → H.264 폴백 불가: {}", video_codec, e))?;
+        }
+        let is_prores = actual_codec == VideoCodec::ProRes;
+        let pixel_format = Self::select_pixel_format(codec, actual_codec, settings.high_bit_depth_container);
 
-        eprintln!(
-            "[ENCODER] 사용 인코더: {} (요청={:?})",
+        log_info!(
+            "[ENCODER] 사용 인코더: {} (요청={:?}/{:?})",
             codec_name,
+            video_codec,
             encoder_type
         );
 
@@ -114,50 +319,40 @@ impl VideoEncoder {
 
         encoder.set_width(width);
         encoder.set_height(height);
-        encoder.set_format(Pixel::YUV420P);
+        encoder.set_format(pixel_format);
         encoder.set_time_base(time_base);
         encoder.set_frame_rate(Some(ffmpeg::Rational::new(fps_num, fps_den)));
 
+        // 출력 색공간 태그: BT.709 limited (렌더러/스케일러 YUV 변환 기준과 동일, 플레이어 추정에 맡기지 않음)
+        encoder.set_colorspace(ffmpeg::color::Space::BT709);
+        encoder.set_color_range(ffmpeg::color::Range::MPEG);
+        unsafe {
+            (*encoder.as_mut_ptr()).color_primaries = ffmpeg::ffi::AVColorPrimaries::AVCOL_PRI_BT709;
+            (*encoder.as_mut_ptr()).color_trc = ffmpeg::ffi::AVColorTransferCharacteristic::AVCOL_TRC_BT709;
+        }
+
         // 인코더별 옵션 설정
         let mut opts = ffmpeg::Dictionary::new();
-        match codec_name.as_str() {
-            "libx264" => {
-                opts.set("crf", &crf.to_string());
-                opts.set("preset", "medium");
-            }
-            "h264_nvenc" => {
-                // NVENC: VBR + CQ (Constant Quality) 모드
-                opts.set("rc", "vbr");
-                opts.set("cq", &crf.to_string());
-                opts.set("preset", "p4"); // medium 상당
-                eprintln!("[ENCODER] NVENC CQ={}", crf);
-            }
-            "h264_qsv" => {
-                opts.set("global_quality", &crf.to_string());
-                opts.set("preset", "medium");
-                eprintln!("[ENCODER] QSV global_quality={}", crf);
-            }
-            "h264_amf" => {
-                let bitrate = Self::crf_to_bitrate(crf, width, height);
-                encoder.set_bit_rate(bitrate);
-                eprintln!("[ENCODER] AMF bitrate={}kbps", bitrate / 1000);
-            }
-            _ => {
-                let bitrate = Self::crf_to_bitrate(crf, width, height);
-                encoder.set_bit_rate(bitrate);
-                eprintln!("[ENCODER] {} bitrate={}kbps", codec_name, bitrate / 1000);
-            }
+        // ProRes는 프로파일별 비트레이트 고정 → 비트레이트 제어 무시
+        if rate.mode == RateControlMode::Crf || is_prores {
+            Self::apply_crf_options(&mut encoder, &mut opts, &codec_name, crf, width, height);
+        } else {
+            Self::apply_bitrate_options(&mut encoder, &mut opts, &codec_name, &rate);
         }
 
+        // 2-pass 설정 (미지원 인코더는 단일 패스)
+        let stats_mode = if settings.pass == EncodePass::Single { StatsMode::Unsupported } else { stats_mode(&codec_name) };
+        let stats_in = Self::apply_pass_options(&mut encoder, &mut opts, &codec_name, &settings.pass, stats_mode)?;
+
         // 글로벌 헤더 플래그 (MP4 컨테이너 호환)
         if needs_global_header {
             unsafe {
                 (*encoder.as_mut_ptr()).flags |= codec::flag::Flags::GLOBAL_HEADER.bits() as i32;
             }
-            eprintln!("[ENCODER] GLOBAL_HEADER 플래그 설정");
+            log_debug!("[ENCODER] GLOBAL_HEADER 플래그 설정");
         }
 
-        eprintln!(
+        log_debug!(
             "[ENCODER] 인코더 열기: {}x{}, fmt={:?}, tb={}/{}",
             encoder.width(), encoder.height(), encoder.format(),
             time_base.numerator(), time_base.denominator(),
@@ -167,32 +362,59 @@ impl VideoEncoder {
         let encoder = encoder.open_as_with(codec, opts)
             .map_err(|e| format!("Failed to open encoder: {}", e))?;
 
-        eprintln!("[ENCODER] 비디오 인코더 열기 성공");
+        log_debug!("[ENCODER] 비디오 인코더 열기 성공");
 
         // 스트림 파라미터 업데이트 (open 후 — extradata/SPS/PPS 반영)
         video_stream.set_parameters(&encoder);
 
-        // RGBA → YUV420P 스케일러 (BICUBIC: 색상 변환 품질 최적화)
-        let scaler = scaling::Context::get(
+        // HEVC: MP4/MOV는 hvc1 태그 (기본 hev1은 QuickTime 재생 불가)
+        if actual_codec == VideoCodec::H265 && container.needs_hvc1_tag() {
+            unsafe {
+                (*(*video_stream.as_mut_ptr()).codecpar).codec_tag = u32::from_le_bytes(*b"hvc1");
+            }
+        }
+
+        // RGBA → 인코더 포맷 스케일러 (BICUBIC: 색상 변환 품질 최적화, BT.709 행렬)
+        let mut scaler = scaling::Context::get(
             Pixel::RGBA,
             width,
             height,
-            Pixel::YUV420P,
+            pixel_format,
             width,
             height,
             scaling::Flags::BICUBIC,
         )
         .map_err(|e| format!("Failed to create scaler: {}", e))?;
+        // SAFETY: 방금 생성한 유효한 스케일러
+        unsafe { set_scaler_colorspace(&mut scaler, (YuvColor::BT709, YuvMatrix::Bt709)) };
+
+        let yuv_converter = if pixel_format != Pixel::YUV420P {
+            let converter = scaling::Context::get(
+                Pixel::YUV420P,
+                width,
+                height,
+                pixel_format,
+                width,
+                height,
+                scaling::Flags::BICUBIC,
+            )
+            .map_err(|e| format!("Failed to create YUV converter: {}", e))?;
+            Some(converter)
+        } else {
+            None
+        };
 
         Ok(Self {
             output_ctx,
             encoder,
             audio_encoder: None,
             scaler,
+            yuv_converter,
             video_stream_index,
             audio_stream_index: None,
             frame_count: 0,
             audio_pts: 0,
+            bytes_written: 0,
             time_base,
             audio_time_base: None,
             width,
@@ -200,18 +422,25 @@ impl VideoEncoder {
             audio_buffer: Vec::new(),
             audio_frame_size: 1024,
             audio_channels: 2,
+            audio_sample_format: ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Planar),
+            container,
+            pass: settings.pass.clone(),
+            stats_mode,
+            stats_out: String::new(),
+            _stats_in: stats_in,
         })
     }
 
-    /// AAC 오디오 인코더 초기화 (write_header 전에 호출)
+    /// 오디오 인코더 초기화 (write_header 전에 호출)
+    /// - 컨테이너별 코덱: AAC (MP4/MOV/MKV), Opus → Vorbis (WebM)
     /// - sample_rate: 48000
     /// - channels: 2 (stereo)
     /// - bitrate: 192000 (192kbps)
     pub fn init_audio(&mut self, sample_rate: u32, channels: u32, bitrate: usize) -> Result<(), String> {
-        let codec = ffmpeg::encoder::find(codec::Id::AAC)
-            .ok_or("AAC 인코더를 찾을 수 없습니다")?;
+        let (codec, sample_format) = Self::find_audio_encoder(self.container)
+            .ok_or_else(|| format!("{:?} 컨테이너용 오디오 인코더를 찾을 수 없습니다", self.container))?;
 
-        eprintln!("[ENCODER] AAC 인코더: {}", codec.name());
+        log_info!("[ENCODER] 오디오 인코더: {} ({:?})", codec.name(), sample_format);
 
         let needs_global_header = self.output_ctx.format().flags()
             .contains(ffmpeg::format::flag::Flags::GLOBAL_HEADER);
@@ -231,7 +460,7 @@ impl VideoEncoder {
 
         audio_enc.set_rate(sample_rate as i32);
         audio_enc.set_channel_layout(ffmpeg::ChannelLayout::STEREO);
-        audio_enc.set_format(ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Planar));
+        audio_enc.set_format(sample_format);
         audio_enc.set_bit_rate(bitrate);
         audio_enc.set_time_base(audio_time_base);
 
@@ -244,12 +473,12 @@ impl VideoEncoder {
         let audio_enc = audio_enc.open_as_with(codec, ffmpeg::Dictionary::new())
             .map_err(|e| format!("Failed to open audio encoder: {}", e))?;
 
-        // AAC 프레임 크기 (보통 1024)
+        // 프레임 크기 (AAC 1024, Opus 960, 가변 프레임 인코더는 0 → 1024)
         let frame_size = unsafe { (*audio_enc.as_ptr()).frame_size as usize };
         let frame_size = if frame_size > 0 { frame_size } else { 1024 };
 
-        eprintln!(
-            "[ENCODER] AAC 오디오 인코더 성공: {}Hz {}ch, {}kbps, frame_size={}",
+        log_info!(
+            "[ENCODER] 오디오 인코더 성공: {}Hz {}ch, {}kbps, frame_size={}",
             sample_rate, channels, bitrate / 1000, frame_size
         );
 
@@ -260,59 +489,283 @@ impl VideoEncoder {
         self.audio_time_base = Some(audio_time_base);
         self.audio_frame_size = frame_size;
         self.audio_channels = channels;
+        self.audio_sample_format = sample_format;
 
         Ok(())
     }
 
-    /// H.264 인코더 찾기 (EncoderType에 따라 분기 + 자동 폴백)
-    /// 반환: (Codec, codec_name)
-    fn find_h264_encoder(encoder_type: EncoderType) -> Result<(ffmpeg::Codec, String), String> {
-        match encoder_type {
-            EncoderType::Auto => {
-                // GPU 우선: NVENC → QSV → AMF → libx264 → generic
-                let try_order = ["h264_nvenc", "h264_qsv", "h264_amf", "libx264"];
-                for name in &try_order {
-                    if let Some(codec) = ffmpeg::encoder::find_by_name(name) {
-                        return Ok((codec, name.to_string()));
-                    }
-                }
-                // 최후의 폴백: generic H264
-                if let Some(codec) = ffmpeg::encoder::find(codec::Id::H264) {
-                    return Ok((codec, codec.name().to_string()));
-                }
-                Err("H.264 인코더를 찾을 수 없습니다".to_string())
+    /// 컨테이너별 오디오 인코더 + 입력 샘플 포맷 (F32 planar 우선, 없으면 packed)
+    fn find_audio_encoder(container: Container) -> Option<(ffmpeg::Codec, ffmpeg::format::Sample)> {
+        use ffmpeg::format::{sample::Type, Sample};
+
+        container.audio_encoders().iter().find_map(|name| {
+            let codec = ffmpeg::encoder::find_by_name(name)?;
+            let formats: Vec<Sample> = codec.audio().ok()
+                .and_then(|audio| audio.formats())
+                .map(|formats| formats.collect())
+                .unwrap_or_default();
+            let planar = Sample::F32(Type::Planar);
+            let packed = Sample::F32(Type::Packed);
+            if formats.is_empty() || formats.contains(&planar) {
+                Some((codec, planar))
+            } else if formats.contains(&packed) {
+                Some((codec, packed))
+            } else {
+                log_warn!("[ENCODER] {} — F32 샘플 포맷 미지원, 건너뜀", name);
+                None
             }
-            EncoderType::Software => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("libx264") {
-                    return Ok((codec, "libx264".to_string()));
-                }
-                if let Some(codec) = ffmpeg::encoder::find(codec::Id::H264) {
-                    return Ok((codec, codec.name().to_string()));
-                }
-                Err("libx264 인코더를 찾을 수 없습니다".to_string())
+        })
+    }
+
+    /// 인코더 입력 픽셀 포맷 (10bit 컨테이너 요청 시 인코더가 지원하는 10bit 4:2:0 포맷, 없으면 기본 포맷)
+    fn select_pixel_format(codec: ffmpeg::Codec, video_codec: VideoCodec, high_bit_depth_container: bool) -> Pixel {
+        let default = video_codec.pixel_format();
+        if !high_bit_depth_container || default != Pixel::YUV420P {
+            return default;
+        }
+        let formats: Vec<Pixel> = codec.video().ok()
+            .and_then(|video| video.formats())
+            .map(|formats| formats.collect())
+            .unwrap_or_default();
+        [Pixel::YUV420P10LE, Pixel::P010LE].into_iter()
+            .find(|format| formats.contains(format))
+            .unwrap_or_else(|| {
+                log_warn!("[ENCODER] {} — 10bit 미지원, 8bit로 인코딩", codec.name());
+                default
+            })
+    }
+
+    /// 코덱/타입별 인코더 찾기
+    /// - Auto: 하드웨어(NVENC → QSV → AMF) → 소프트웨어 → 범용 인코더
+    /// - Software: 소프트웨어 → 범용 인코더
+    /// - 하드웨어 지정: 해당 인코더 없으면 Software로 폴백
+    /// - 해당 코덱 인코더가 전혀 없으면 H.264로 폴백
+    fn find_encoder(video_codec: VideoCodec, encoder_type: EncoderType) -> Result<(ffmpeg::Codec, String), String> {
+        let by_name = |name: &str| ffmpeg::encoder::find_by_name(name).map(|c| (c, name.to_string()));
+        let hardware = video_codec.hardware_encoders();
+        let software = || video_codec.software_encoders().iter().find_map(|name| by_name(name));
+        let generic = || ffmpeg::encoder::find(video_codec.codec_id()).map(|c| (c, c.name().to_string()));
+
+        let found = match encoder_type {
+            EncoderType::Auto => hardware.iter().flatten().find_map(|name| by_name(name))
+                .or_else(software)
+                .or_else(generic),
+            EncoderType::Software => software().or_else(generic),
+            EncoderType::Nvenc | EncoderType::Qsv | EncoderType::Amf => {
+                let index = match encoder_type {
+                    EncoderType::Nvenc => 0,
+                    EncoderType::Qsv => 1,
+                    _ => 2,
+                };
+                hardware[index].and_then(&by_name).or_else(|| {
+                    log_warn!("[ENCODER] {:?} {:?} 인코더 없음 → 소프트웨어 폴백", video_codec, encoder_type);
+                    software().or_else(generic)
+                })
             }
-            EncoderType::Nvenc => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("h264_nvenc") {
-                    return Ok((codec, "h264_nvenc".to_string()));
+        };
+
+        match found {
+            Some(found) => Ok(found),
+            None if video_codec != VideoCodec::H264 => {
+                log_warn!("[ENCODER] {:?} 인코더 없음 → H.264 폴백", video_codec);
+                Self::find_encoder(VideoCodec::H264, encoder_type)
+            }
+            None => Err("H.264 인코더를 찾을 수 없습니다".to_string()),
+        }
+    }
+
+    /// 고정 품질(CRF/CQ) 옵션 (인코더별 품질 파라미터)
+    fn apply_crf_options(
+        encoder: &mut codec::encoder::video::Video,
+        opts: &mut ffmpeg::Dictionary,
+        codec_name: &str,
+        crf: u32,
+        width: u32,
+        height: u32,
+    ) {
+        match codec_name {
+            "libx264" => {
+                opts.set("crf", &crf.to_string());
+                opts.set("preset", "medium");
+            }
+            "libx265" => {
+                opts.set("crf", &crf.to_string());
+                opts.set("preset", "medium");
+                opts.set("x265-params", "log-level=error");
+            }
+            "libvpx-vp9" => {
+                // Constant Quality 모드: bitrate 0 + crf
+                encoder.set_bit_rate(0);
+                opts.set("crf", &scale_crf_63(crf).to_string());
+                opts.set("deadline", "good");
+                opts.set("cpu-used", "4");
+                opts.set("row-mt", "1");
+            }
+            "libsvtav1" => {
+                opts.set("crf", &scale_crf_63(crf).to_string());
+                opts.set("preset", "8");
+            }
+            "libaom-av1" => {
+                opts.set("crf", &scale_crf_63(crf).to_string());
+                opts.set("cpu-used", "6");
+                opts.set("row-mt", "1");
+            }
+            "prores_ks" | "prores_aw" => {
+                // ProRes 422 HQ (CRF 무시, 품질은 프로파일로 결정)
+                opts.set("profile", "3");
+                opts.set("vendor", "apl0");
+            }
+            "h264_nvenc" | "hevc_nvenc" | "av1_nvenc" => {
+                // NVENC: VBR + CQ (Constant Quality) 모드
+                opts.set("rc", "vbr");
+                opts.set("cq", &crf.to_string());
+                opts.set("preset", "p4"); // medium 상당
+                log_debug!("[ENCODER] NVENC CQ={}", crf);
+            }
+            "h264_qsv" | "hevc_qsv" | "vp9_qsv" | "av1_qsv" => {
+                opts.set("global_quality", &crf.to_string());
+                opts.set("preset", "medium");
+                log_debug!("[ENCODER] QSV global_quality={}", crf);
+            }
+            "h264_amf" | "hevc_amf" | "av1_amf" => {
+                let bitrate = Self::crf_to_bitrate(crf, width, height);
+                encoder.set_bit_rate(bitrate);
+                log_debug!("[ENCODER] AMF bitrate={}kbps", bitrate / 1000);
+            }
+            _ => {
+                let bitrate = Self::crf_to_bitrate(crf, width, height);
+                encoder.set_bit_rate(bitrate);
+                log_debug!("[ENCODER] {} bitrate={}kbps", codec_name, bitrate / 1000);
+            }
+        }
+    }
+
+    /// 비트레이트 제어 옵션 (CBR/VBR/2-pass VBR)
+    fn apply_bitrate_options(
+        encoder: &mut codec::encoder::video::Video,
+        opts: &mut ffmpeg::Dictionary,
+        codec_name: &str,
+        rate: &RateControl,
+    ) {
+        let target = rate.target_bitrate.max(1);
+        let is_cbr = rate.mode == RateControlMode::Cbr;
+        let max_rate = if is_cbr { target } else { rate.max_bitrate };
+        let buffer_size = match rate.buffer_size {
+            0 if is_cbr => target,
+            0 => max_rate * 2,
+            size => size,
+        };
+
+        encoder.set_bit_rate(target);
+        if max_rate > 0 {
+            encoder.set_max_bit_rate(max_rate);
+        }
+        unsafe {
+            let ctx = encoder.as_mut_ptr();
+            if is_cbr {
+                (*ctx).rc_min_rate = target as i64;
+            }
+            (*ctx).rc_buffer_size = buffer_size.min(i32::MAX as usize) as i32;
+        }
+
+        match codec_name {
+            "libx264" => {
+                opts.set("preset", "medium");
+                if is_cbr {
+                    opts.set("nal-hrd", "cbr");
                 }
-                eprintln!("[ENCODER] h264_nvenc 없음 → libx264 폴백");
-                Self::find_h264_encoder(EncoderType::Software)
             }
-            EncoderType::Qsv => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("h264_qsv") {
-                    return Ok((codec, "h264_qsv".to_string()));
+            "libx265" => {
+                opts.set("preset", "medium");
+                opts.set("x265-params", "log-level=error");
+            }
+            "libvpx-vp9" => {
+                opts.set("deadline", "good");
+                opts.set("cpu-used", "4");
+                opts.set("row-mt", "1");
+            }
+            "libaom-av1" => {
+                opts.set("cpu-used", "6");
+                opts.set("row-mt", "1");
+            }
+            "h264_nvenc" | "hevc_nvenc" | "av1_nvenc" => {
+                opts.set("rc", if is_cbr { "cbr" } else { "vbr" });
+                opts.set("preset", "p4");
+                // 2-pass VBR: NVENC 내부 멀티패스로 대체
+                if rate.mode == RateControlMode::TwoPassVbr {
+                    opts.set("multipass", "fullres");
                 }
-                eprintln!("[ENCODER] h264_qsv 없음 → libx264 폴백");
-                Self::find_h264_encoder(EncoderType::Software)
             }
-            EncoderType::Amf => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("h264_amf") {
-                    return Ok((codec, "h264_amf".to_string()));
+            "h264_amf" | "hevc_amf" | "av1_amf" => {
+                opts.set("rc", if is_cbr { "cbr" } else { "vbr_peak" });
+            }
+            _ => {}
+        }
+
+        log_debug!(
+            "[ENCODER] {:?} {} target={}kbps max={}kbps buffer={}kbit",
+            rate.mode, codec_name, target / 1000, max_rate / 1000, buffer_size / 1000
+        );
+    }
+
+    /// 2-pass 옵션 설정 (open 전에 호출)
+    /// 반환: 최종 패스 stats_in 버퍼 (인코더가 닫힐 때까지 유지해야 함)
+    fn apply_pass_options(
+        encoder: &mut codec::encoder::video::Video,
+        opts: &mut ffmpeg::Dictionary,
+        codec_name: &str,
+        pass: &EncodePass,
+        stats_mode: StatsMode,
+    ) -> Result<Option<CString>, String> {
+        let (stats_path, flag) = match pass {
+            EncodePass::Single => return Ok(None),
+            EncodePass::Analysis { stats_path } => (stats_path, codec::flag::Flags::PASS1),
+            EncodePass::Final { stats_path } => (stats_path, codec::flag::Flags::PASS2),
+        };
+        let pass_number = if flag == codec::flag::Flags::PASS1 { 1 } else { 2 };
+
+        match stats_mode {
+            StatsMode::Unsupported => {
+                log_warn!("[ENCODER] {} 2-pass 미지원 → 단일 패스", codec_name);
+                return Ok(None);
+            }
+            StatsMode::EncoderFile => {
+                let path = stats_path.to_string_lossy();
+                if codec_name == "libx265" {
+                    opts.set("x265-params", &format!("log-level=error:pass={}:stats={}", pass_number, path));
+                } else {
+                    opts.set("stats", &path);
                 }
-                eprintln!("[ENCODER] h264_amf 없음 → libx264 폴백");
-                Self::find_h264_encoder(EncoderType::Software)
             }
+            StatsMode::Buffer => {}
         }
+
+        unsafe {
+            (*encoder.as_mut_ptr()).flags |= flag.bits() as i32;
+        }
+
+        // 최종 패스 (버퍼 방식): 분석 패스가 기록한 통계를 stats_in으로 전달
+        if stats_mode == StatsMode::Buffer && pass_number == 2 {
+            let stats = std::fs::read_to_string(stats_path)
+                .map_err(|e| format!("2-pass 통계 읽기 실패: {}", e))?;
+            let stats = CString::new(stats).map_err(|e| format!("2-pass 통계 형식 오류: {}", e))?;
+            unsafe {
+                (*encoder.as_mut_ptr()).stats_in = stats.as_ptr() as *mut _;
+            }
+            return Ok(Some(stats));
+        }
+
+        log_info!("[ENCODER] 2-pass {}/2: {}", pass_number, stats_path.display());
+        Ok(None)
+    }
+
+    /// 2-pass 분석 패스를 지원하는 인코더인지 (미지원이면 분석 패스 결과 무시)
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    pub fn supports_two_pass(&self) -> bool {
+        self.stats_mode != StatsMode::Unsupported
     }
 
     /// CRF → 대략적 bitrate 변환 (비 libx264 인코더용)
@@ -331,12 +784,17 @@ impl VideoEncoder {
         (base_rate * multiplier) as usize
     }
 
+    /// 챕터 메타데이터 추가 (write_header 전에 호출)
+    pub fn add_chapters(&mut self, chapters: &[Chapter]) -> Result<(), String> {
+        add_output_chapters(&mut self.output_ctx, chapters)
+    }
+
     /// 출력 파일 헤더 작성 (init_audio 후, 첫 프레임 인코딩 전에 호출)
     pub fn write_header(&mut self) -> Result<(), String> {
-        eprintln!("[ENCODER] write_header 호출...");
+        log_debug!("[ENCODER] write_header 호출...");
         self.output_ctx.write_header()
             .map_err(|e| format!("Failed to write header: {}", e))?;
-        eprintln!("[ENCODER] write_header 성공");
+        log_debug!("[ENCODER] write_header 성공");
         Ok(())
     }
 
@@ -390,7 +848,7 @@ impl VideoEncoder {
 
         // 처음 5프레임만 로그
         if self.frame_count <= 5 {
-            eprintln!("[ENCODER] 비디오 프레임 {} 인코딩 완료 ({}x{})", self.frame_count, width, height);
+            log_debug!("[ENCODER] 비디오 프레임 {} 인코딩 완료 ({}x{})", self.frame_count, width, height);
         }
 
         Ok(())
@@ -459,6 +917,14 @@ impl VideoEncoder {
             }
         }
 
+        // 인코더 포맷이 YUV420P가 아니면 변환 (ProRes 4:2:2 10bit)
+        if let Some(converter) = self.yuv_converter.as_mut() {
+            let mut converted = ffmpeg::frame::Video::empty();
+            converter.run(&yuv_frame, &mut converted)
+                .map_err(|e| format!("YUV converter failed: {}", e))?;
+            yuv_frame = converted;
+        }
+
         // PTS 설정
         yuv_frame.set_pts(Some(self.frame_count));
         self.frame_count += 1;
@@ -470,7 +936,7 @@ impl VideoEncoder {
         self.receive_and_write_video_packets()?;
 
         if self.frame_count <= 5 {
-            eprintln!("[ENCODER] YUV 프레임 {} 인코딩 완료 ({}x{})", self.frame_count, width, height);
+            log_debug!("[ENCODER] YUV 프레임 {} 인코딩 완료 ({}x{})", self.frame_count, width, height);
         }
 
         Ok(())
@@ -495,7 +961,7 @@ impl VideoEncoder {
         result
     }
 
-    /// 오디오 버퍼에서 완전한 AAC 프레임만큼 인코딩
+    /// 오디오 버퍼에서 완전한 프레임만큼 인코딩
     fn flush_audio_buffer(&mut self, audio_enc: &mut ffmpeg::encoder::Audio) -> Result<(), String> {
         let frame_size = self.audio_frame_size;
         let channels = self.audio_channels as usize;
@@ -510,9 +976,9 @@ impl VideoEncoder {
         };
 
         while self.audio_buffer.len() >= samples_per_frame {
-            // FLTP 오디오 프레임 생성
+            // F32 오디오 프레임 생성 (planar 또는 packed)
             let mut frame = ffmpeg::frame::Audio::new(
-                ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Planar),
+                self.audio_sample_format,
                 frame_size,
                 ffmpeg::ChannelLayout::STEREO,
             );
@@ -520,17 +986,29 @@ impl VideoEncoder {
             frame.set_rate(48000);
             self.audio_pts += frame_size as i64;
 
-            // Deinterleave: (L,R,L,R,...) → plane0=[L,L,...], plane1=[R,R,...]
-            for ch in 0..channels {
-                let plane = frame.data_mut(ch);
-                let plane_f32 = unsafe {
+            if self.audio_sample_format.is_packed() {
+                // Packed: interleaved 그대로 복사
+                let data = frame.data_mut(0);
+                let data_f32 = unsafe {
                     std::slice::from_raw_parts_mut(
-                        plane.as_mut_ptr() as *mut f32,
-                        frame_size,
+                        data.as_mut_ptr() as *mut f32,
+                        samples_per_frame,
                     )
                 };
-                for i in 0..frame_size {
-                    plane_f32[i] = self.audio_buffer[i * channels + ch];
+                data_f32.copy_from_slice(&self.audio_buffer[..samples_per_frame]);
+            } else {
+                // Deinterleave: (L,R,L,R,...) → plane0=[L,L,...], plane1=[R,R,...]
+                for ch in 0..channels {
+                    let plane = frame.data_mut(ch);
+                    let plane_f32 = unsafe {
+                        std::slice::from_raw_parts_mut(
+                            plane.as_mut_ptr() as *mut f32,
+                            frame_size,
+                        )
+                    };
+                    for i in 0..frame_size {
+                        plane_f32[i] = self.audio_buffer[i * channels + ch];
+                    }
                 }
             }
 
@@ -550,6 +1028,7 @@ impl VideoEncoder {
                         .ok_or("Audio stream not found")?
                         .time_base(),
                 );
+                self.bytes_written += packet.size() as u64;
                 packet.write_interleaved(&mut self.output_ctx)
                     .map_err(|e| format!("Failed to write audio packet: {}", e))?;
             }
@@ -560,14 +1039,14 @@ impl VideoEncoder {
 
     /// 인코딩 완료 (flush + trailer)
     pub fn finish(&mut self) -> Result<(), String> {
-        eprintln!("[ENCODER] finish 호출 (비디오 {}프레임, 오디오 {}샘플)",
+        log_debug!("[ENCODER] finish 호출 (비디오 {}프레임, 오디오 {}샘플)",
             self.frame_count, self.audio_pts);
 
         // 비디오 flush
         self.encoder.send_eof()
             .map_err(|e| format!("Failed to send video EOF: {}", e))?;
         self.receive_and_write_video_packets()?;
-        eprintln!("[ENCODER] 비디오 flush 완료");
+        log_debug!("[ENCODER] 비디오 flush 완료");
 
         // 오디오 flush (잔여 버퍼 + EOF)
         if let Some(mut audio_enc) = self.audio_encoder.take() {
@@ -596,22 +1075,100 @@ impl VideoEncoder {
                         .ok_or("Audio stream not found")?
                         .time_base(),
                 );
+                self.bytes_written += packet.size() as u64;
                 packet.write_interleaved(&mut self.output_ctx)
                     .map_err(|e| format!("Failed to write audio packet: {}", e))?;
             }
 
             self.audio_encoder = Some(audio_enc);
-            eprintln!("[ENCODER] 오디오 flush 완료");
+            log_debug!("[ENCODER] 오디오 flush 완료");
         }
 
         // 파일 트레일러 작성
         self.output_ctx.write_trailer()
             .map_err(|e| format!("Failed to write trailer: {}", e))?;
-        eprintln!("[ENCODER] write_trailer 성공 → 파일 완성");
+        log_info!("[ENCODER] write_trailer 성공 → 파일 완성");
+
+        // 분석 패스 (버퍼 방식): 누적 통계를 파일로 저장 (libx264/libx265는 인코더가 직접 기록)
+        if let EncodePass::Analysis { stats_path } = &self.pass {
+            if self.stats_mode == StatsMode::Buffer {
+                std::fs::write(stats_path, &self.stats_out)
+                    .map_err(|e| format!("2-pass 통계 저장 실패: {}", e))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 비디오 스트림 파라미터를 다른 파일에서 복사 (write_header 전에 호출)
+    /// 세그먼트 병합용: 패킷을 만든 세그먼트 인코더의 extradata(SPS/PPS 등) 사용
+    pub fn copy_video_parameters(&mut self, path: &Path) -> Result<(), String> {
+        let input_ctx = ffmpeg::format::input(&path)
+            .map_err(|e| format!("Failed to open segment: {}", e))?;
+        let input_stream = input_ctx.streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or("No video stream found")?;
+        let params = input_stream.parameters();
+        let tag = if params.id() == codec::Id::HEVC && self.container.needs_hvc1_tag() {
+            u32::from_le_bytes(*b"hvc1")
+        } else {
+            0
+        };
 
+        let mut video_stream = self.output_ctx.stream_mut(self.video_stream_index)
+            .ok_or("Video stream not found")?;
+        video_stream.set_parameters(params);
+        unsafe {
+            (*(*video_stream.as_mut_ptr()).codecpar).codec_tag = tag;
+        }
         Ok(())
     }
 
+    /// 다른 파일의 인코딩된 비디오 패킷을 출력에 이어 붙임 (세그먼트 병렬 Export 병합)
+    /// - offset_frames: 세그먼트 첫 프레임 번호 (출력 PTS 오프셋)
+    /// - before_packet(인코더, 패킷 DTS ms): 패킷 기록 전 호출 (오디오 인터리브용), false 반환 시 중단
+    /// - 반환: 중단되면 false
+    pub fn append_video_segment<F>(&mut self, path: &Path, offset_frames: i64, mut before_packet: F) -> Result<bool, String>
+    where
+        F: FnMut(&mut Self, i64) -> Result<bool, String>,
+    {
+        let mut input_ctx = ffmpeg::format::input(&path)
+            .map_err(|e| format!("Failed to open segment: {}", e))?;
+        let input_index = input_ctx.streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or("No video stream found")?
+            .index();
+        let stream_time_base = self.output_ctx.stream(self.video_stream_index)
+            .ok_or("Video stream not found")?
+            .time_base();
+
+        for (stream, mut packet) in input_ctx.packets() {
+            if stream.index() != input_index {
+                continue;
+            }
+            // 세그먼트 time_base → 인코더 time_base (1 = 1프레임) 후 프레임 오프셋
+            packet.rescale_ts(stream.time_base(), self.time_base);
+            packet.set_pts(packet.pts().map(|pts| pts + offset_frames));
+            packet.set_dts(packet.dts().map(|dts| dts + offset_frames));
+
+            let time_ms = packet.dts().or(packet.pts()).unwrap_or(offset_frames)
+                * i64::from(self.time_base.numerator()) * 1000
+                / i64::from(self.time_base.denominator());
+            if !before_packet(self, time_ms)? {
+                return Ok(false);
+            }
+
+            packet.set_stream(self.video_stream_index);
+            packet.set_position(-1);
+            packet.rescale_ts(self.time_base, stream_time_base);
+            self.bytes_written += packet.size() as u64;
+            packet.write_interleaved(&mut self.output_ctx)
+                .map_err(|e| format!("Failed to write video packet: {}", e))?;
+            self.frame_count += 1;
+        }
+        Ok(true)
+    }
+
     /// 비디오 패킷 수신 → 출력 파일에 기록
     fn receive_and_write_video_packets(&mut self) -> Result<(), String> {
         let mut packet = ffmpeg::Packet::empty();
@@ -623,12 +1180,27 @@ impl VideoEncoder {
                     .ok_or("Video stream not found")?
                     .time_base(),
             );
+            self.bytes_written += packet.size() as u64;
             packet.write_interleaved(&mut self.output_ctx)
                 .map_err(|e| format!("Failed to write video packet: {}", e))?;
+            self.collect_pass_stats();
         }
         Ok(())
     }
 
+    /// 분석 패스 통계 누적 (패킷마다 인코더가 갱신하는 stats_out)
+    fn collect_pass_stats(&mut self) {
+        if self.stats_mode != StatsMode::Buffer || !matches!(self.pass, EncodePass::Analysis { .. }) {
+            return;
+        }
+        unsafe {
+            let stats = (*self.encoder.as_ptr()).stats_out;
+            if !stats.is_null() {
+                self.stats_out.push_str(&CStr::from_ptr(stats).to_string_lossy());
+            }
+        }
+    }
+
     /// 너비 반환
     pub fn width(&self) -> u32 { self.width }
     /// 높이 반환
@@ -0,0 +1,274 @@
+// Export 큐 - 여러 Export 작업을 순차(또는 N개 병렬)로 실행
+// 작업마다 타임라인 스냅샷을 보관 → 큐 실행 중 편집해도 결과 불변
+// 디스패처 스레드가 빈 슬롯에 대기 작업을 ExportJob으로 시작
+
+use crate::encoding::exporter::{ExportConfig, ExportJob};
+use crate::subtitle::overlay::SubtitleOverlayList;
+use crate::timeline::Timeline;
+use crate::utils::log::{log_debug, log_info};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 디스패처 폴링 간격
+const DISPATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 큐 작업 상태 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueJobStatus {
+    Pending = 0,
+    Running = 1,
+    Completed = 2,
+    Failed = 3,
+    Cancelled = 4,
+}
+
+impl QueueJobStatus {
+    /// 종료 상태 (완료/실패/취소)
+    pub fn is_done(self) -> bool {
+        matches!(self, QueueJobStatus::Completed | QueueJobStatus::Failed | QueueJobStatus::Cancelled)
+    }
+}
+
+/// 큐 항목
+struct QueueEntry {
+    /// 시작 전 설정/스냅샷 (시작 시 ExportJob으로 이동)
+    pending: Option<(ExportConfig, Arc<Mutex<Timeline>>, Option<SubtitleOverlayList>)>,
+    job: Option<ExportJob>,
+    cancelled: bool,
+}
+
+impl QueueEntry {
+    fn status(&self) -> QueueJobStatus {
+        if self.cancelled {
+            return match &self.job {
+                Some(job) if !job.is_finished() => QueueJobStatus::Running,
+                _ => QueueJobStatus::Cancelled,
+            };
+        }
+        match &self.job {
+            None => QueueJobStatus::Pending,
+            Some(job) if !job.is_finished() => QueueJobStatus::Running,
+            Some(job) if job.get_error().is_some() => QueueJobStatus::Failed,
+            Some(_) => QueueJobStatus::Completed,
+        }
+    }
+
+    fn progress(&self) -> u32 {
+        match (&self.job, self.status()) {
+            (_, status) if status.is_done() => 100,
+            (Some(job), _) => job.get_progress(),
+            (None, _) => 0,
+        }
+    }
+}
+
+/// 다음에 시작할 대기 작업 인덱스 (실행 중 작업 수가 max_parallel 미만인 만큼, 추가 순서)
+fn next_to_start(statuses: &[QueueJobStatus], max_parallel: usize) -> Vec<usize> {
+    let running = statuses.iter().filter(|s| **s == QueueJobStatus::Running).count();
+    let slots = max_parallel.max(1).saturating_sub(running);
+    statuses.iter()
+        .enumerate()
+        .filter(|(_, s)| **s == QueueJobStatus::Pending)
+        .map(|(i, _)| i)
+        .take(slots)
+        .collect()
+}
+
+/// Export 큐
+pub struct ExportQueue {
+    entries: Arc<Mutex<Vec<QueueEntry>>>,
+    max_parallel: usize,
+    /// 디스패처 실행 중
+    dispatching: Arc<AtomicBool>,
+    /// 디스패처 중단 요청 (큐 파괴)
+    stopped: Arc<AtomicBool>,
+}
+
+impl ExportQueue {
+    /// max_parallel: 동시 실행 작업 수 (1 = 순차)
+    pub fn new(max_parallel: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(Vec::new())),
+            max_parallel: max_parallel.max(1),
+            dispatching: Arc::new(AtomicBool::new(false)),
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 작업 추가 (타임라인은 현재 상태로 스냅샷)
+    /// 반환: 작업 인덱스
+    pub fn add(
+        &self,
+        timeline: &Timeline,
+        config: ExportConfig,
+        subtitles: Option<SubtitleOverlayList>,
+    ) -> usize {
+        let snapshot = Arc::new(Mutex::new(timeline.clone()));
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.push(QueueEntry {
+            pending: Some((config, snapshot, subtitles)),
+            job: None,
+            cancelled: false,
+        });
+        entries.len() - 1
+    }
+
+    /// 큐 실행 시작 (이미 실행 중이면 무시, 실행 중 추가된 작업도 이어서 처리)
+    pub fn start(&self) {
+        if self.dispatching.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let entries = self.entries.clone();
+        let max_parallel = self.max_parallel;
+        let dispatching = self.dispatching.clone();
+        let stopped = self.stopped.clone();
+
+        std::thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                let all_done = match entries.lock() {
+                    Ok(mut entries) => {
+                        let statuses: Vec<QueueJobStatus> = entries.iter().map(|e| e.status()).collect();
+                        for index in next_to_start(&statuses, max_parallel) {
+                            let entry = &mut entries[index];
+                            if let Some((config, timeline, subtitles)) = entry.pending.take() {
+                                log_info!("[EXPORT_QUEUE] 작업 {} 시작: {}", index, config.output_path);
+                                entry.job = Some(ExportJob::start_with_subtitles(timeline, config, subtitles));
+                            }
+                        }
+                        entries.iter().all(|e| e.status().is_done())
+                    }
+                    Err(_) => true,
+                };
+                if all_done {
+                    break;
+                }
+                std::thread::sleep(DISPATCH_INTERVAL);
+            }
+            dispatching.store(false, Ordering::SeqCst);
+            log_debug!("[EXPORT_QUEUE] 디스패처 종료");
+        });
+    }
+
+    pub fn job_count(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    pub fn job_status(&self, index: usize) -> Option<QueueJobStatus> {
+        self.entries.lock().ok()?.get(index).map(|e| e.status())
+    }
+
+    /// 작업 진행률 (0~100, 종료된 작업은 100)
+    pub fn job_progress(&self, index: usize) -> Option<u32> {
+        self.entries.lock().ok()?.get(index).map(|e| e.progress())
+    }
+
+    pub fn job_error(&self, index: usize) -> Option<String> {
+        self.entries.lock().ok()?.get(index)?.job.as_ref()?.get_error()
+    }
+
+    /// 전체 진행률 (작업 진행률 평균, 0~100)
+    pub fn overall_progress(&self) -> u32 {
+        let entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return 0,
+        };
+        if entries.is_empty() {
+            return 0;
+        }
+        let total: u32 = entries.iter().map(|e| e.progress()).sum();
+        total / entries.len() as u32
+    }
+
+    /// 모든 작업 종료 여부
+    pub fn is_finished(&self) -> bool {
+        self.entries.lock()
+            .map(|e| e.iter().all(|e| e.status().is_done()))
+            .unwrap_or(true)
+    }
+
+    /// 작업 취소 (대기 중이면 건너뜀, 실행 중이면 ExportJob 취소)
+    pub fn cancel_job(&self, index: usize) -> bool {
+        let mut entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return false,
+        };
+        match entries.get_mut(index) {
+            Some(entry) if !entry.status().is_done() => {
+                entry.cancelled = true;
+                entry.pending = None;
+                if let Some(job) = &entry.job {
+                    job.cancel();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn cancel_all(&self) {
+        for index in 0..self.job_count() {
+            self.cancel_job(index);
+        }
+    }
+}
+
+impl Drop for ExportQueue {
+    fn drop(&mut self) {
+        self.cancel_all();
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use QueueJobStatus::*;
+
+    #[test]
+    fn test_next_to_start() {
+        assert_eq!(next_to_start(&[Pending, Pending, Pending], 1), vec![0]);
+        assert_eq!(next_to_start(&[Running, Pending, Pending], 1), Vec::<usize>::new());
+        assert_eq!(next_to_start(&[Completed, Running, Pending, Pending], 2), vec![2]);
+        assert_eq!(next_to_start(&[Failed, Cancelled, Pending, Pending], 2), vec![2, 3]);
+        // 0은 1로 취급 (순차)
+        assert_eq!(next_to_start(&[Pending, Pending], 0), vec![0]);
+    }
+
+    #[test]
+    fn test_queue_runs_and_cancels() {
+        let queue = ExportQueue::new(1);
+        let timeline = Timeline::new(1920, 1080, 30.0);
+        let config = |name: &str| ExportConfig::new(name.to_string(), 1920, 1080, 30.0, 23);
+
+        queue.add(&timeline, config("a.mp4"), None);
+        queue.add(&timeline, config("b.mp4"), None);
+        queue.add(&timeline, config("c.mp4"), None);
+        assert_eq!(queue.job_count(), 3);
+        assert_eq!(queue.job_status(0), Some(Pending));
+        assert_eq!(queue.overall_progress(), 0);
+
+        // 시작 전 취소한 작업은 실행되지 않음
+        assert!(queue.cancel_job(2));
+        assert_eq!(queue.job_status(2), Some(Cancelled));
+
+        queue.start();
+        for _ in 0..100 {
+            if queue.is_finished() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(queue.is_finished());
+
+        // 빈 타임라인 → 실패로 종료
+        assert_eq!(queue.job_status(0), Some(Failed));
+        assert_eq!(queue.job_status(1), Some(Failed));
+        assert!(queue.job_error(0).is_some());
+        assert!(queue.job_error(2).is_none());
+        assert_eq!(queue.overall_progress(), 100);
+        assert!(!queue.cancel_job(0));
+    }
+}
@@ -1,15 +1,26 @@
 // Export 작업 관리 - 백그라운드 스레드, 진행률, 취소
-// ExportJob: 타임라인 → MP4 파일 내보내기 전체 흐름
-// 비디오 (H.264) + 오디오 (AAC) 동시 인코딩
+// ExportJob: 타임라인 → 비디오 파일 / 이미지 시퀀스 내보내기 전체 흐름
+// 비디오 (H.264/H.265/VP9/AV1) + 오디오 (AAC) 동시 인코딩
 
-use crate::encoding::encoder::{VideoEncoder, EncoderType};
+use crate::encoding::encoder::{
+    VideoEncoder, VideoEncoderSettings, EncoderType, VideoCodec, EncodePass, RateControl, RateControlMode,
+};
+use crate::audio::loudness;
 use crate::encoding::audio_mixer::AudioMixer;
+use crate::encoding::container::Container;
+use crate::encoding::image_sequence::{ImageFormat, ImageSequenceWriter};
+use crate::encoding::remux;
+use crate::encoding::smart_render::{self, StreamCopyPlan};
+use crate::ffmpeg::YuvColor;
 use crate::rendering::Renderer;
-use crate::subtitle::overlay::{SubtitleOverlayList, blend_overlay_rgba, yuv420p_to_rgba, rgba_to_yuv420p};
-use crate::timeline::Timeline;
-use std::path::Path;
+use crate::subtitle::overlay::{SubtitleOverlayList, blend_overlays_rgba, yuv420p_to_rgba, rgba_to_yuv420p};
+use crate::timeline::{frame_to_ms, Chapter, Timeline};
+use crate::utils::error::{self, ErrorCategory};
+use crate::utils::log::{log_debug, log_error, log_info, log_warn};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Export 설정
 pub struct ExportConfig {
@@ -19,18 +30,243 @@ pub struct ExportConfig {
     pub fps: f64,
     pub crf: u32,
     pub encoder_type: u32,  // 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+    pub codec: u32,         // 0=H.264, 1=H.265, 2=VP9, 3=AV1, 4=ProRes (없으면 H.264 폴백)
+    pub rate_control: u32,  // 0=CRF, 1=CBR, 2=VBR, 3=2-pass VBR
+    pub target_bitrate_kbps: u32,
+    pub max_bitrate_kbps: u32,   // VBR 최대 (0 = 제한 없음)
+    pub buffer_size_kbits: u32,  // VBV 버퍼 (0 = 자동)
+    pub target_size_bytes: u64,  // 0보다 크면 목표 파일 크기로 비트레이트 계산 (target_bitrate 대신)
+    pub start_ms: i64,      // Export 구간 시작 (타임라인 기준)
+    pub end_ms: i64,        // Export 구간 끝 (0 이하 = 타임라인 끝까지)
+    pub audio_bitrate_kbps: u32, // AAC 비트레이트 (0 = 기본 192kbps)
+    pub target: u32,        // 0=비디오, 1=PNG 시퀀스, 2=JPEG 시퀀스 (output_path 파일명 + _000000 번호)
+    pub smart_render: bool, // 손대지 않은 단일 클립 구간은 재인코딩 없이 패킷 복사 (CRF 비디오 Export만, 기본 꺼짐)
+    pub parallel_segments: u32, // 세그먼트 병렬 Export 스레드 수 (0/1 = 사용 안 함, 소프트웨어 인코더 단일 패스만)
+    pub loudness_target_lufs: f64, // 0 = 정규화 안 함, 음수면 믹스를 측정해 목표 LUFS로 맞춤 (예: -14.0, 스마트 렌더링 비활성)
+    pub high_bit_depth_container: bool, // 10bit 픽셀 포맷으로 인코딩만 (디코딩/합성은 8bit → 정밀도 향상 없음, 10bit 납품 규격용, 스마트 렌더링 비활성)
 }
 
-/// Export 작업 핸들 (C#에서 폴링으로 상태 확인)
-pub struct ExportJob {
+impl ExportConfig {
+    /// 기본 설정 (CRF, 자동 인코더, H.264)
+    pub fn new(output_path: String, width: u32, height: u32, fps: f64, crf: u32) -> Self {
+        Self {
+            output_path,
+            width,
+            height,
+            fps,
+            crf,
+            encoder_type: 0,
+            codec: 0,
+            rate_control: 0,
+            target_bitrate_kbps: 0,
+            max_bitrate_kbps: 0,
+            buffer_size_kbits: 0,
+            target_size_bytes: 0,
+            start_ms: 0,
+            end_ms: 0,
+            audio_bitrate_kbps: 0,
+            target: 0,
+            smart_render: false,
+            parallel_segments: 0,
+            loudness_target_lufs: 0.0,
+            high_bit_depth_container: false,
+        }
+    }
+
+    /// 이미지 시퀀스 형식 (비디오 Export면 None)
+    fn image_format(&self) -> Option<ImageFormat> {
+        match self.target {
+            1 => Some(ImageFormat::Png),
+            2 => Some(ImageFormat::Jpeg),
+            _ => None,
+        }
+    }
+
+    /// AAC 오디오 비트레이트 (bps)
+    fn audio_bitrate(&self) -> usize {
+        if self.audio_bitrate_kbps > 0 {
+            self.audio_bitrate_kbps as usize * 1000
+        } else {
+            DEFAULT_AUDIO_BITRATE
+        }
+    }
+
+    /// Export 구간 (타임라인 길이로 클램프)
+    /// 반환: (시작 ms, 끝 ms) — 구간이 비면 에러
+    fn export_range(&self, timeline_duration_ms: i64) -> Result<(i64, i64), String> {
+        let start_ms = self.start_ms.max(0);
+        let end_ms = if self.end_ms > 0 {
+            self.end_ms.min(timeline_duration_ms)
+        } else {
+            timeline_duration_ms
+        };
+        if start_ms >= end_ms {
+            return Err(format!("Export 구간이 비어있습니다 ({}ms ~ {}ms)", start_ms, end_ms));
+        }
+        Ok((start_ms, end_ms))
+    }
+
+    /// 인코더 비트레이트 설정 (비트레이트 모드는 목표 크기 → 목표 비트레이트 순으로 결정)
+    fn rate_control(&self, duration_ms: i64) -> Result<RateControl, String> {
+        let mode = RateControlMode::from_u32(self.rate_control);
+        if mode == RateControlMode::Crf {
+            return Ok(RateControl::crf(self.crf));
+        }
+
+        let target_bitrate = if self.target_size_bytes > 0 {
+            bitrate_for_target_size(self.target_size_bytes, duration_ms, self.audio_bitrate())
+        } else {
+            self.target_bitrate_kbps as usize * 1000
+        };
+        if target_bitrate == 0 {
+            return Err("목표 비트레이트가 지정되지 않았습니다".to_string());
+        }
+
+        Ok(RateControl {
+            mode,
+            crf: self.crf,
+            target_bitrate,
+            max_bitrate: self.max_bitrate_kbps as usize * 1000,
+            buffer_size: self.buffer_size_kbits as usize * 1000,
+        })
+    }
+}
+
+/// 렌더링 프레임 출력 대상
+enum FrameSink<'a> {
+    Encoder(&'a mut VideoEncoder),
+    Images(&'a mut ImageSequenceWriter),
+}
+
+/// 렌더링 스레드 → 인코딩 스레드로 넘기는 프레임 (자막 블렌딩/포맷 변환 완료)
+struct PipelineFrame {
+    data: Arc<Vec<u8>>,
+    width: u32,
+    height: u32,
+    /// true: YUV420P, false: RGBA
+    is_yuv: bool,
+    /// 렌더러 통계 (렌더러는 렌더링 스레드 소유)
+    dropped_frames: u64,
+    cache_hit_rate: f64,
+}
+
+/// 파이프라인 단계 사이 채널 크기 (렌더링/믹싱이 인코딩보다 앞서 준비할 수 있는 프레임 수)
+const PIPELINE_DEPTH: usize = 4;
+
+/// 기본 AAC 오디오 비트레이트 (bps)
+const DEFAULT_AUDIO_BITRATE: usize = 192000;
+
+/// 목표 비트레이트 하한 (목표 크기가 너무 작을 때)
+const MIN_TARGET_BITRATE: usize = 100_000;
+
+/// 목표 파일 크기에 맞는 비디오 비트레이트 (오디오 비트레이트 + 컨테이너 오버헤드 2% 제외)
+fn bitrate_for_target_size(size_bytes: u64, duration_ms: i64, audio_bitrate: usize) -> usize {
+    if duration_ms <= 0 {
+        return 0;
+    }
+    let total_bitrate = size_bytes as f64 * 8.0 * 1000.0 / duration_ms as f64 * 0.98;
+    ((total_bitrate - audio_bitrate as f64) as usize).max(MIN_TARGET_BITRATE)
+}
+
+/// Export 통계 (exporter_get_stats로 폴링)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExportStats {
+    /// 현재 패스에서 처리한 프레임 수
+    pub frames_done: u64,
+    /// 현재 패스 총 프레임 수
+    pub total_frames: u64,
+    /// 최근 구간 처리 속도 (fps)
+    pub encode_fps: f64,
+    /// 평균 출력 비트레이트 (kbps, 현재 패스 기준)
+    pub average_bitrate_kbps: f64,
+    /// 경과 시간 (ms, 전체 패스)
+    pub elapsed_ms: u64,
+    /// 남은 예상 시간 (ms, 진행률 기반 — 아직 모르면 -1)
+    pub eta_ms: i64,
+    /// 디코딩 스킵/실패로 이전 프레임을 재사용한 프레임 수
+    pub dropped_frames: u64,
+    /// 렌더러 프레임 캐시 적중률 (0.0~1.0)
+    pub cache_hit_rate: f64,
+}
+
+/// encode_fps 측정 구간 (프레임)
+const FPS_WINDOW_FRAMES: u64 = 30;
+
+/// 진행률 기반 남은 시간 추정 (progress: 0.0~1.0)
+fn estimate_eta_ms(elapsed_ms: u64, progress: f64) -> i64 {
+    if progress <= 0.0 {
+        return -1;
+    }
+    (elapsed_ms as f64 * (1.0 - progress.min(1.0)) / progress) as i64
+}
+
+/// 출력 바이트 → 평균 비트레이트 (kbps)
+fn average_bitrate_kbps(bytes: u64, frames: u64, fps: f64) -> f64 {
+    if frames == 0 || fps <= 0.0 {
+        return 0.0;
+    }
+    let seconds = frames as f64 / fps;
+    bytes as f64 * 8.0 / seconds / 1000.0
+}
+
+/// 구간 프레임 수 (프레임 i의 시각 = 시작 + i * 1000/fps)
+fn frame_count(range: (i64, i64), fps: f64) -> i64 {
+    ((range.1 - range.0) as f64 * fps / 1000.0).ceil() as i64
+}
+
+/// 세그먼트 최소 프레임 수 (세그먼트마다 키프레임 + 인코더 lookahead 비용)
+const MIN_SEGMENT_FRAMES: i64 = 150;
+
+/// 세그먼트 스레드 상태 집계 간격
+const SEGMENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 프레임 구간의 타임라인 시각 목록 (range 끝을 넘는 프레임 제외)
+fn frame_timestamps(range: (i64, i64), frames: (i64, i64), fps: f64) -> Vec<i64> {
+    (frames.0..frames.1)
+        .map(|i| range.0 + frame_to_ms(i, fps))
+        .take_while(|&timestamp_ms| timestamp_ms < range.1)
+        .collect()
+}
+
+/// 전체 프레임을 세그먼트로 균등 분할 (각 세그먼트 MIN_SEGMENT_FRAMES 이상)
+/// 반환: (첫 프레임, 끝 프레임) 목록 — 1개면 분할 불필요
+fn segment_frames(total_frames: i64, segments: u32) -> Vec<(i64, i64)> {
+    let count = (segments as i64).min(total_frames / MIN_SEGMENT_FRAMES).max(1);
+    (0..count)
+        .map(|i| (total_frames * i / count, total_frames * (i + 1) / count))
+        .collect()
+}
+
+/// Export 스레드와 공유하는 작업 상태
+struct JobShared {
     /// 진행률 (0~100)
-    progress: Arc<AtomicU32>,
+    progress: AtomicU32,
     /// 취소 플래그
-    cancelled: Arc<AtomicBool>,
+    cancelled: AtomicBool,
     /// 완료 플래그
-    finished: Arc<AtomicBool>,
+    finished: AtomicBool,
     /// 에러 메시지 (있으면 실패)
-    error: Arc<Mutex<Option<String>>>,
+    error: Mutex<Option<String>>,
+    stats: Mutex<ExportStats>,
+    started: Instant,
+}
+
+impl JobShared {
+    fn new() -> Self {
+        Self {
+            progress: AtomicU32::new(0),
+            cancelled: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            error: Mutex::new(None),
+            stats: Mutex::new(ExportStats { eta_ms: -1, ..ExportStats::default() }),
+            started: Instant::now(),
+        }
+    }
+}
+
+/// Export 작업 핸들 (C#에서 폴링으로 상태 확인)
+pub struct ExportJob {
+    shared: Arc<JobShared>,
 }
 
 impl ExportJob {
@@ -45,34 +281,32 @@ impl ExportJob {
         config: ExportConfig,
         subtitles: Option<SubtitleOverlayList>,
     ) -> Self {
-        let progress = Arc::new(AtomicU32::new(0));
-        let cancelled = Arc::new(AtomicBool::new(false));
-        let finished = Arc::new(AtomicBool::new(false));
-        let error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+        let shared = Arc::new(JobShared::new());
 
-        let p = progress.clone();
-        let c = cancelled.clone();
-        let f = finished.clone();
-        let e = error.clone();
+        let job = shared.clone();
 
         std::thread::spawn(move || {
-            let result = Self::export_thread(timeline, &config, &p, &c, subtitles.as_ref());
+            let result = Self::export_thread(timeline, &config, &job, subtitles.as_ref());
             match result {
                 Ok(()) => {
-                    p.store(100, Ordering::SeqCst);
-                    eprintln!("[EXPORT] 완료: {}", config.output_path);
+                    job.progress.store(100, Ordering::SeqCst);
+                    if let Ok(mut stats) = job.stats.lock() {
+                        stats.eta_ms = 0;
+                        stats.elapsed_ms = job.started.elapsed().as_millis() as u64;
+                    }
+                    log_info!("[EXPORT] 완료: {}", config.output_path);
                 }
                 Err(msg) => {
-                    if let Ok(mut err) = e.lock() {
+                    if let Ok(mut err) = job.error.lock() {
                         *err = Some(msg.clone());
                     }
-                    eprintln!("[EXPORT] 에러: {}", msg);
+                    log_error!("[EXPORT] 에러: {}", msg);
                 }
             }
-            f.store(true, Ordering::SeqCst);
+            job.finished.store(true, Ordering::SeqCst);
         });
 
-        Self { progress, cancelled, finished, error }
+        Self { shared }
     }
 
     /// 비ASCII 경로(한글 등) 안전 처리
@@ -92,14 +326,14 @@ impl ExportJob {
 
         let temp_str = temp_path.to_string_lossy().to_string();
         if temp_str.is_ascii() {
-            eprintln!("[EXPORT] 비ASCII 경로 → 임시 경로: {}", temp_str);
+            log_info!("[EXPORT] 비ASCII 경로 → 임시 경로: {}", temp_str);
             return (temp_str, true);
         }
 
         if let Some(drive) = output_path.chars().next() {
             if output_path.chars().nth(1) == Some(':') {
                 let root_temp = format!("{}:\\{}", drive, temp_name);
-                eprintln!("[EXPORT] TEMP도 비ASCII → 드라이브 루트: {}", root_temp);
+                log_info!("[EXPORT] TEMP도 비ASCII → 드라이브 루트: {}", root_temp);
                 return (root_temp, true);
             }
         }
@@ -131,13 +365,12 @@ impl ExportJob {
     fn export_thread(
         timeline: Arc<Mutex<Timeline>>,
         config: &ExportConfig,
-        progress: &AtomicU32,
-        cancelled: &AtomicBool,
+        job: &JobShared,
         subtitles: Option<&SubtitleOverlayList>,
     ) -> Result<(), String> {
-        eprintln!(
-            "[EXPORT] 시작: {}x{} @ {}fps, CRF={}, 출력={}",
-            config.width, config.height, config.fps, config.crf, config.output_path
+        log_info!(
+            "[EXPORT] 시작: {}x{} @ {}fps, CRF={}, 비트레이트 모드={}, 출력={}",
+            config.width, config.height, config.fps, config.crf, config.rate_control, config.output_path
         );
 
         // 0. 출력 디렉토리 생성
@@ -157,173 +390,955 @@ impl ExportJob {
             return Err("타임라인이 비어있습니다".to_string());
         }
 
-        eprintln!("[EXPORT] 타임라인 길이: {}ms", duration_ms);
+        // 구간 지정 시 해당 구간만 렌더링 (진행률도 구간 기준)
+        let range = config.export_range(duration_ms)?;
+        log_info!("[EXPORT] 타임라인 길이: {}ms, 구간: {}ms ~ {}ms", duration_ms, range.0, range.1);
 
-        // 2. Export용 전용 Renderer + AudioMixer 생성
-        let mut renderer = Renderer::new_for_export(
-            timeline.clone(),
-            config.width,
-            config.height,
-        );
-        let mut audio_mixer = AudioMixer::new();
+        // 이미지 시퀀스 Export (인코딩/오디오 없음)
+        if let Some(format) = config.image_format() {
+            return Self::image_sequence_pass(&timeline, config, format, range, job, subtitles);
+        }
+
+        // 스마트 렌더링: 원본 그대로인 구간은 패킷 복사 (불가하면 일반 렌더링)
+        match Self::stream_copy_pass(&timeline, config, range, job, subtitles) {
+            Ok(true) => return Ok(()),
+            Ok(false) => {}
+            Err(e) => log_warn!("[EXPORT] 스트림 복사 실패 → 재인코딩: {}", e),
+        }
+
+        // 2. 비트레이트 설정 + 2-pass 분석 패스 (진행률 0~50%)
+        let rate_control = config.rate_control(range.1 - range.0)?;
+        let mut settings = VideoEncoderSettings {
+            width: config.width,
+            height: config.height,
+            fps: config.fps,
+            encoder_type: EncoderType::from_u32(config.encoder_type),
+            codec: VideoCodec::from_u32(config.codec),
+            rate_control,
+            pass: EncodePass::Single,
+            high_bit_depth_container: config.high_bit_depth_container,
+        };
+        let mut progress_span = (0, 100);
+        let stats_path = (rate_control.mode == RateControlMode::TwoPassVbr).then(Self::stats_path);
+        if let Some(stats_path) = &stats_path {
+            settings.pass = EncodePass::Analysis { stats_path: stats_path.clone() };
+            let analyzed = Self::analysis_pass(&timeline, config, &settings, range, job, subtitles);
+            match analyzed {
+                Ok(true) => {
+                    settings.pass = EncodePass::Final { stats_path: stats_path.clone() };
+                    progress_span = (50, 50);
+                }
+                Ok(false) => settings.pass = EncodePass::Single,
+                Err(e) => {
+                    Self::remove_stats_files(stats_path);
+                    return Err(e);
+                }
+            }
+        }
+
+        // 세그먼트 병렬 Export (단일 패스 + 소프트웨어 인코더 — HW 인코더는 동시 세션 수 제한)
+        let segments = segment_frames(frame_count(range, config.fps), config.parallel_segments);
+        let parallel = segments.len() > 1
+            && stats_path.is_none()
+            && matches!(settings.encoder_type, EncoderType::Auto | EncoderType::Software);
+        if parallel {
+            settings.encoder_type = EncoderType::Software;
+            return Self::parallel_pass(&timeline, config, &settings, range, &segments, job, subtitles);
+        }
+
+        let result = Self::final_pass(&timeline, config, &settings, range, progress_span, job, subtitles);
+        if let Some(stats_path) = &stats_path {
+            Self::remove_stats_files(stats_path);
+        }
+        result
+    }
+
+    /// 2-pass 분석 패스 (비디오만, 출력 버림)
+    /// 반환: false면 인코더가 2-pass 미지원 → 단일 패스로 진행
+    fn analysis_pass(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        settings: &VideoEncoderSettings,
+        range: (i64, i64),
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<bool, String> {
+        // 출력 경로는 컨테이너 검증용 (분석 패스는 null 먹서로 파일 미생성)
+        let mut encoder = VideoEncoder::new(&config.output_path, settings)
+            .map_err(|e| format!("분석 패스 인코더 생성 실패: {}", e))?;
+        if !encoder.supports_two_pass() {
+            return Ok(false);
+        }
+        encoder.write_header()?;
+
+        log_info!("[EXPORT] 2-pass 분석 패스 시작");
+        let frames = (0, frame_count(range, config.fps));
+        let completed = Self::encode_timeline(
+            timeline, config, FrameSink::Encoder(&mut encoder), None, range, frames, (0, 50), job, subtitles,
+        )?;
+        encoder.finish()?;
+        if !completed {
+            return Err("Export가 취소되었습니다".to_string());
+        }
+        Ok(true)
+    }
+
+    /// 최종 인코딩 패스 (비디오 + 오디오 → 출력 파일)
+    fn final_pass(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        settings: &VideoEncoderSettings,
+        range: (i64, i64),
+        progress_span: (u32, u32),
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<(), String> {
+        let mut audio_mixer = Self::create_audio_mixer(timeline, config, range, job)?;
+
+        // 3~5. 출력 인코더 생성 (비ASCII 경로 처리 + 오디오)
+        let (mut encoder, encoder_path, needs_move) = Self::create_output_encoder(config, settings)?;
+
+        // 6. 챕터 + 헤더 작성 (비디오+오디오 스트림 모두 등록 후)
+        encoder.add_chapters(&Self::export_chapters(timeline, range)?)?;
+        encoder.write_header()?;
+
+        // 7. 프레임 단위로 렌더링 → 인코딩
+        let frames = (0, frame_count(range, config.fps));
+        let completed = Self::encode_timeline(
+            timeline, config, FrameSink::Encoder(&mut encoder), Some(&mut audio_mixer), range, frames, progress_span, job, subtitles,
+        )?;
+        if !completed {
+            let _ = encoder.finish();
+            if needs_move {
+                let _ = std::fs::remove_file(&encoder_path);
+            }
+            return Err("Export가 취소되었습니다".to_string());
+        }
 
-        // 3. 비ASCII 경로 처리
+        // 8. 인코딩 완료 (flush + trailer)
+        encoder.finish()?;
+
+        // 9. 임시 파일을 최종 경로로 이동 (비ASCII 경로)
+        if needs_move {
+            log_debug!("[EXPORT] 임시 파일 이동: {} → {}", encoder_path, config.output_path);
+            Self::move_file(&encoder_path, &config.output_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Export 구간의 마커 → 챕터 (출력 시간 기준)
+    fn export_chapters(timeline: &Arc<Mutex<Timeline>>, range: (i64, i64)) -> Result<Vec<Chapter>, String> {
+        let tl = timeline.lock().map_err(|e| format!("Timeline lock failed: {}", e))?;
+        Ok(tl.chapters(range))
+    }
+
+    /// 최종 출력 인코더 생성 (비ASCII 경로는 임시 경로 → 실패 시 원본 경로 재시도) + 오디오 인코더
+    /// 반환: (인코더, 실제 출력 경로, 완료 후 이동 필요 여부)
+    fn create_output_encoder(
+        config: &ExportConfig,
+        settings: &VideoEncoderSettings,
+    ) -> Result<(VideoEncoder, String, bool), String> {
+        // 비ASCII 경로 처리
         let (encoder_path, needs_move) = Self::safe_encoder_path(&config.output_path);
 
-        // 4. VideoEncoder 생성 (인코더 타입 전달)
-        let enc_type = EncoderType::from_u32(config.encoder_type);
-        let (mut encoder, encoder_path, needs_move) = match VideoEncoder::new(
-            &encoder_path,
-            config.width,
-            config.height,
-            config.fps,
-            config.crf,
-            enc_type,
-        ) {
+        // VideoEncoder 생성 (인코더 타입/코덱/비트레이트 전달)
+        let (mut encoder, encoder_path, needs_move) = match VideoEncoder::new(&encoder_path, settings) {
             Ok(enc) => (enc, encoder_path, needs_move),
             Err(e) if needs_move => {
-                eprintln!("[EXPORT] 안전 경로 실패 ({}), 원본 경로로 재시도", e);
-                let enc = VideoEncoder::new(
-                    &config.output_path,
-                    config.width,
-                    config.height,
-                    config.fps,
-                    config.crf,
-                    enc_type,
-                ).map_err(|e2| format!("인코더 생성 실패: {} (재시도: {})", e, e2))?;
+                log_warn!("[EXPORT] 안전 경로 실패 ({}), 원본 경로로 재시도", e);
+                let enc = VideoEncoder::new(&config.output_path, settings)
+                    .map_err(|e2| format!("인코더 생성 실패: {} (재시도: {})", e, e2))?;
                 (enc, config.output_path.clone(), false)
             }
             Err(e) => return Err(format!("인코더 생성 실패: {}", e)),
         };
 
-        // 5. AAC 오디오 인코더 초기화 (48kHz stereo, 192kbps)
-        match encoder.init_audio(48000, 2, 192000) {
-            Ok(()) => eprintln!("[EXPORT] 오디오 인코더 초기화 성공"),
+        // AAC 오디오 인코더 초기화 (48kHz stereo, 기본 192kbps)
+        match encoder.init_audio(48000, 2, config.audio_bitrate()) {
+            Ok(()) => log_debug!("[EXPORT] 오디오 인코더 초기화 성공"),
             Err(e) => {
                 // 오디오 인코더 실패해도 비디오만이라도 Export 계속
-                eprintln!("[EXPORT] 오디오 인코더 초기화 실패 (비디오만 Export): {}", e);
+                log_warn!("[EXPORT] 오디오 인코더 초기화 실패 (비디오만 Export): {}", e);
+            }
+        }
+
+        Ok((encoder, encoder_path, needs_move))
+    }
+
+    /// 세그먼트 병렬 Export
+    /// 1. 세그먼트마다 독립 Renderer + 인코더로 비디오만 임시 파일에 인코딩 (스레드당 1개, 진행률 0~90%)
+    /// 2. 세그먼트 비디오 패킷을 순서대로 이어 붙이며 전체 오디오 믹싱/인코딩 (90~100%)
+    #[allow(clippy::too_many_arguments)]
+    fn parallel_pass(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        settings: &VideoEncoderSettings,
+        range: (i64, i64),
+        segments: &[(i64, i64)],
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<(), String> {
+        log_info!("[EXPORT] 세그먼트 병렬 Export: {}개 세그먼트", segments.len());
+
+        let ext = Path::new(&config.output_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("mp4");
+        let segment_paths: Vec<PathBuf> = (0..segments.len())
+            .map(|i| Self::segment_path(i, ext))
+            .collect();
+
+        let result = Self::encode_segments(timeline, config, settings, range, segments, &segment_paths, job, subtitles)
+            .and_then(|()| Self::merge_segments(timeline, config, settings, range, segments, &segment_paths, job));
+        for path in &segment_paths {
+            let _ = std::fs::remove_file(path);
+        }
+        result
+    }
+
+    /// 세그먼트 동시 인코딩 (메인 스레드는 취소 전파 + 진행률/통계 집계)
+    #[allow(clippy::too_many_arguments)]
+    fn encode_segments(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        settings: &VideoEncoderSettings,
+        range: (i64, i64),
+        segments: &[(i64, i64)],
+        segment_paths: &[PathBuf],
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<(), String> {
+        let segment_jobs: Vec<JobShared> = segments.iter().map(|_| JobShared::new()).collect();
+        let total_frames = frame_count(range, config.fps) as u64;
+
+        let results: Vec<Result<bool, String>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = segments.iter()
+                .zip(segment_paths)
+                .zip(&segment_jobs)
+                .map(|((&frames, path), segment_job)| {
+                    scope.spawn(move || {
+                        let result = Self::encode_segment(
+                            timeline, config, settings, range, frames, path, segment_job, subtitles,
+                        );
+                        if let Err(e) = &result {
+                            if let Ok(mut err) = segment_job.error.lock() {
+                                *err = Some(e.clone());
+                            }
+                        }
+                        segment_job.finished.store(true, Ordering::SeqCst);
+                        result
+                    })
+                })
+                .collect();
+
+            while !segment_jobs.iter().all(|j| j.finished.load(Ordering::SeqCst)) {
+                // 취소 또는 세그먼트 하나라도 실패 → 나머지 중단
+                let failed = segment_jobs.iter().any(|j| j.error.lock().map(|e| e.is_some()).unwrap_or(true));
+                if failed || job.cancelled.load(Ordering::SeqCst) {
+                    for segment_job in &segment_jobs {
+                        segment_job.cancelled.store(true, Ordering::SeqCst);
+                    }
+                }
+                Self::aggregate_segment_stats(job, &segment_jobs, total_frames);
+                std::thread::sleep(SEGMENT_POLL_INTERVAL);
             }
+
+            handles.into_iter()
+                .map(|h| h.join().unwrap_or_else(|_| Err("세그먼트 스레드 패닉".to_string())))
+                .collect()
+        });
+
+        // 실패한 세그먼트 에러 우선 (나머지는 그에 따른 중단)
+        if let Some(Err(e)) = results.iter().find(|r| r.is_err()) {
+            return Err(e.clone());
+        }
+        if results.iter().any(|r| !matches!(r, Ok(true))) {
+            return Err("Export가 취소되었습니다".to_string());
         }
+        Ok(())
+    }
 
-        // 6. 헤더 작성 (비디오+오디오 스트림 모두 등록 후)
+    /// 세그먼트 1개 인코딩 (비디오만)
+    /// 반환: 취소되면 false
+    #[allow(clippy::too_many_arguments)]
+    fn encode_segment(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        settings: &VideoEncoderSettings,
+        range: (i64, i64),
+        frames: (i64, i64),
+        path: &Path,
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<bool, String> {
+        let mut encoder = VideoEncoder::new(&path.to_string_lossy(), settings)
+            .map_err(|e| format!("세그먼트 인코더 생성 실패: {}", e))?;
         encoder.write_header()?;
 
-        // 7. 프레임 단위로 렌더링 → 인코딩
+        let completed = Self::encode_timeline(
+            timeline, config, FrameSink::Encoder(&mut encoder), None, range, frames, (0, 100), job, subtitles,
+        )?;
+        encoder.finish()?;
+        Ok(completed)
+    }
+
+    /// 세그먼트 통계 합산 → 작업 진행률(0~90%)/통계
+    fn aggregate_segment_stats(job: &JobShared, segment_jobs: &[JobShared], total_frames: u64) {
+        let mut stats = ExportStats { total_frames, ..ExportStats::default() };
+        let (mut bitrate_sum, mut cache_sum) = (0.0, 0.0);
+        for segment_job in segment_jobs {
+            let segment = segment_job.stats.lock().map(|s| *s).unwrap_or_default();
+            stats.frames_done += segment.frames_done;
+            stats.encode_fps += segment.encode_fps;
+            stats.dropped_frames += segment.dropped_frames;
+            bitrate_sum += segment.average_bitrate_kbps * segment.frames_done as f64;
+            cache_sum += segment.cache_hit_rate * segment.frames_done as f64;
+        }
+        if stats.frames_done > 0 {
+            stats.average_bitrate_kbps = bitrate_sum / stats.frames_done as f64;
+            stats.cache_hit_rate = cache_sum / stats.frames_done as f64;
+        }
+
+        let progress = stats.frames_done as f64 / total_frames.max(1) as f64 * 0.9;
+        stats.elapsed_ms = job.started.elapsed().as_millis() as u64;
+        stats.eta_ms = estimate_eta_ms(stats.elapsed_ms, progress);
+        job.progress.store(((progress * 100.0) as u32).min(99), Ordering::SeqCst);
+        if let Ok(mut job_stats) = job.stats.lock() {
+            *job_stats = stats;
+        }
+    }
+
+    /// 세그먼트 병합: 비디오 패킷 복사 + 오디오 믹싱/인코딩 → 최종 출력
+    fn merge_segments(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        settings: &VideoEncoderSettings,
+        range: (i64, i64),
+        segments: &[(i64, i64)],
+        segment_paths: &[PathBuf],
+        job: &JobShared,
+    ) -> Result<(), String> {
+        let (mut encoder, encoder_path, needs_move) = Self::create_output_encoder(config, settings)?;
+        // 스트림 파라미터는 실제 패킷을 만든 세그먼트 인코더 기준
+        encoder.copy_video_parameters(&segment_paths[0])?;
+        encoder.add_chapters(&Self::export_chapters(timeline, range)?)?;
+        encoder.write_header()?;
+
+        let mut audio_mixer = Self::create_audio_mixer(timeline, config, range, job)?;
         let frame_duration_ms = 1000.0 / config.fps;
-        let total_frames = ((duration_ms as f64) / frame_duration_ms).ceil() as i64;
-        let mut frame_index: i64 = 0;
+        let total_frames = frame_count(range, config.fps);
+        let mut audio_frame: i64 = 0;
 
-        eprintln!("[EXPORT] 총 프레임: {}", total_frames);
+        // 비디오 패킷 시각까지 오디오 인코딩 (프레임 단위 믹싱 — encode_timeline과 동일)
+        let mut encode_audio_until = |encoder: &mut VideoEncoder, until_ms: i64| -> Result<(), String> {
+            while audio_frame < total_frames {
+                let offset_ms = frame_to_ms(audio_frame, config.fps);
+                if offset_ms > until_ms {
+                    break;
+                }
+                let samples = Self::mix_frame_audio(timeline, &mut audio_mixer, range.0 + offset_ms, frame_duration_ms)?;
+                encoder.encode_audio_samples(&samples)?;
+                audio_frame += 1;
+            }
+            Ok(())
+        };
 
-        loop {
-            // 취소 확인
-            if cancelled.load(Ordering::SeqCst) {
-                eprintln!("[EXPORT] 취소됨 (frame {}/{})", frame_index, total_frames);
+        for (index, (&frames, path)) in segments.iter().zip(segment_paths).enumerate() {
+            let completed = encoder.append_video_segment(path, frames.0, |encoder, time_ms| {
+                encode_audio_until(encoder, time_ms)?;
+                Ok(!job.cancelled.load(Ordering::SeqCst))
+            })?;
+            if !completed {
                 let _ = encoder.finish();
                 if needs_move {
                     let _ = std::fs::remove_file(&encoder_path);
                 }
                 return Err("Export가 취소되었습니다".to_string());
             }
+            job.progress.store(90 + (index as u32 + 1) * 9 / segments.len() as u32, Ordering::SeqCst);
+        }
+        encode_audio_until(&mut encoder, i64::MAX)?;
+        encoder.finish()?;
+
+        if needs_move {
+            log_debug!("[EXPORT] 임시 파일 이동: {} → {}", encoder_path, config.output_path);
+            Self::move_file(&encoder_path, &config.output_path)?;
+        }
+        Ok(())
+    }
+
+    /// 세그먼트 임시 파일 경로 (임시 디렉토리, 프로세스/시각별 고유)
+    fn segment_path(index: usize, ext: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        std::env::temp_dir().join(format!("vortex_segment_{}_{}_{}.{}", std::process::id(), nanos, index, ext))
+    }
+
+    /// Export용 오디오 믹서 (라우드니스 목표가 있으면 구간 믹스를 먼저 측정해 마스터 게인 설정)
+    fn create_audio_mixer(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        range: (i64, i64),
+        job: &JobShared,
+    ) -> Result<AudioMixer, String> {
+        let mut audio_mixer = AudioMixer::new();
+        if config.loudness_target_lufs != 0.0 {
+            let report = loudness::measure_timeline(timeline, range, &job.cancelled)?
+                .ok_or_else(|| "Export가 취소되었습니다".to_string())?;
+            let gain = report.normalization_gain(config.loudness_target_lufs);
+            log_info!(
+                "[EXPORT] 라우드니스 {:.1} LUFS, 트루 피크 {:.1} dBTP → 게인 {:.2}dB (목표 {:.1} LUFS)",
+                report.integrated_lufs, report.true_peak_dbtp, 20.0 * gain.log10(), config.loudness_target_lufs
+            );
+            audio_mixer.set_master_gain(gain);
+        }
+        Ok(audio_mixer)
+    }
+
+    /// 프레임 구간 오디오 믹싱 (타임라인의 오디오 소스 전체)
+    fn mix_frame_audio(
+        timeline: &Arc<Mutex<Timeline>>,
+        audio_mixer: &mut AudioMixer,
+        timestamp_ms: i64,
+        frame_duration_ms: f64,
+    ) -> Result<Vec<f32>, String> {
+        let audio_clips = {
+            let tl = timeline.lock()
+                .map_err(|e| format!("Timeline lock failed: {}", e))?;
+            tl.get_all_audio_sources_at_time(timestamp_ms)
+        };
+        Ok(audio_mixer.mix_range(&audio_clips, timestamp_ms, frame_duration_ms))
+    }
 
-            let timestamp_ms = (frame_index as f64 * frame_duration_ms) as i64;
-            if timestamp_ms >= duration_ms {
-                break;
+    /// 스트림 복사 가능 여부 (타임라인 조건 + 소스 코덱/해상도/fps 일치 + 시작 키프레임)
+    fn stream_copy_plan(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        range: (i64, i64),
+        container: Container,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<Option<(StreamCopyPlan, bool)>, String> {
+        if !config.smart_render
+            || subtitles.is_some()
+            || config.loudness_target_lufs != 0.0
+            || RateControlMode::from_u32(config.rate_control) != RateControlMode::Crf
+        {
+            return Ok(None);
+        }
+        let plan = {
+            let tl = timeline.lock().map_err(|e| format!("Timeline lock failed: {}", e))?;
+            match smart_render::plan_stream_copy(&tl, range) {
+                Some(plan) => plan,
+                None => return Ok(None),
             }
+        };
+
+        let source = remux::probe_copy_source(&plan.source_path, container)?;
+        if !smart_render::source_matches(&plan, &source, config, container) {
+            log_info!("[EXPORT] 스트림 복사 불가 (소스 {:?})", source);
+            return Ok(None);
+        }
+        if !remux::starts_on_keyframe(&plan.source_path, plan.source_start_ms, source.fps)? {
+            log_info!("[EXPORT] 스트림 복사 불가 (시작 {}ms가 키프레임 아님)", plan.source_start_ms);
+            return Ok(None);
+        }
+        Ok(Some((plan, source.has_audio)))
+    }
+
+    /// 스마트 렌더링 패스 (소스 패킷 복사, 무손실)
+    /// 반환: false면 복사 조건 불충족 → 일반 렌더링으로 진행
+    fn stream_copy_pass(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        range: (i64, i64),
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<bool, String> {
+        let container = Container::from_path(&config.output_path)?;
+        let (plan, with_audio) = match Self::stream_copy_plan(timeline, config, range, container, subtitles)? {
+            Some(copy) => copy,
+            None => return Ok(false),
+        };
+        log_info!(
+            "[EXPORT] 스트림 복사: {} ({}ms ~ {}ms)",
+            plan.source_path.display(), plan.source_start_ms, plan.source_end_ms
+        );
 
-            // 비디오 프레임 렌더링
-            let frame = renderer.render_frame(timestamp_ms)
-                .map_err(|e| format!("렌더링 실패 ({}ms): {}", timestamp_ms, e))?;
-
-            if frame_index == 0 {
-                eprintln!(
-                    "[EXPORT] 첫 프레임: rendered={}x{}, encoder={}x{}, data={}bytes",
-                    frame.width, frame.height,
-                    encoder.width(), encoder.height(),
-                    frame.data.len()
-                );
+        let chapters = Self::export_chapters(timeline, range)?;
+        let (output_path, needs_move) = Self::safe_encoder_path(&config.output_path);
+        let total_frames = frame_count(range, config.fps) as u64;
+        let completed = remux::remux_range(
+            &plan.source_path,
+            &output_path,
+            container,
+            (plan.source_start_ms, plan.source_end_ms),
+            with_audio,
+            &chapters,
+            |progress, bytes_written| {
+                job.progress.store(((progress * 100.0) as u32).min(99), Ordering::SeqCst);
+                let frames_done = (progress * total_frames as f64) as u64;
+                let elapsed_ms = job.started.elapsed().as_millis() as u64;
+                if let Ok(mut stats) = job.stats.lock() {
+                    *stats = ExportStats {
+                        frames_done,
+                        total_frames,
+                        encode_fps: frames_done as f64 * 1000.0 / elapsed_ms.max(1) as f64,
+                        average_bitrate_kbps: average_bitrate_kbps(bytes_written, frames_done, config.fps),
+                        elapsed_ms,
+                        eta_ms: estimate_eta_ms(elapsed_ms, progress),
+                        ..ExportStats::default()
+                    };
+                }
+                !job.cancelled.load(Ordering::SeqCst)
+            },
+        );
+        let completed = match completed {
+            Ok(completed) => completed,
+            Err(e) => {
+                let _ = std::fs::remove_file(&output_path);
+                return Err(e);
+            }
+        };
+        if !completed {
+            if needs_move {
+                let _ = std::fs::remove_file(&output_path);
             }
+            return Err("Export가 취소되었습니다".to_string());
+        }
 
-            // 자막 오버레이 합성 (있을 때만 RGBA 경로)
-            let has_subtitle = subtitles
-                .and_then(|s| s.get_active(timestamp_ms))
-                .is_some();
+        if needs_move {
+            log_debug!("[EXPORT] 임시 파일 이동: {} → {}", output_path, config.output_path);
+            Self::move_file(&output_path, &config.output_path)?;
+        }
+        Ok(true)
+    }
 
-            if has_subtitle {
-                // 자막 프레임: YUV→RGBA 변환 → 알파 블렌딩 → RGBA 인코딩
-                let overlay = subtitles.unwrap().get_active(timestamp_ms).unwrap();
-                let mut rgba = if frame.is_yuv {
-                    yuv420p_to_rgba(&frame.data, frame.width, frame.height)
-                } else {
-                    frame.data.clone()
+    /// 이미지 시퀀스 Export (프레임마다 번호 붙은 PNG/JPEG 파일)
+    fn image_sequence_pass(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        format: ImageFormat,
+        range: (i64, i64),
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<(), String> {
+        let mut writer = ImageSequenceWriter::new(&config.output_path, format, config.width, config.height)?;
+
+        let frames = (0, frame_count(range, config.fps));
+        let completed = Self::encode_timeline(
+            timeline, config, FrameSink::Images(&mut writer), None, range, frames, (0, 100), job, subtitles,
+        )?;
+        log_info!("[EXPORT] 이미지 시퀀스 {}장 저장", writer.frames_written());
+        if !completed {
+            return Err("Export가 취소되었습니다".to_string());
+        }
+        Ok(())
+    }
+
+    /// 타임라인 구간을 프레임 단위로 렌더링 → 인코딩/이미지 저장 (출력 PTS는 첫 프레임 = 0)
+    /// 렌더링 / 오디오 믹싱 / 인코딩을 각각의 스레드에서 겹쳐 실행 (단계 사이는 PIPELINE_DEPTH 크기 채널)
+    /// - range: (시작 ms, 끝 ms) 타임라인 기준 — 프레임 i의 시각 = 시작 + i * 프레임 길이
+    /// - frames: (첫 프레임, 끝 프레임) — 전체 구간은 (0, frame_count), 세그먼트는 그 일부
+    /// - audio_mixer: None이면 비디오만 (2-pass 분석, 이미지 시퀀스는 무시)
+    /// - progress_span: (시작 %, 구간 %) — 2-pass는 패스별로 절반씩
+    /// - 반환: 취소되면 false (인코더 정리는 호출자 담당)
+    #[allow(clippy::too_many_arguments)]
+    fn encode_timeline(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        mut sink: FrameSink,
+        audio_mixer: Option<&mut AudioMixer>,
+        range: (i64, i64),
+        frames: (i64, i64),
+        progress_span: (u32, u32),
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+    ) -> Result<bool, String> {
+        let timestamps = frame_timestamps(range, frames, config.fps);
+        let total_frames = frames.1 - frames.0;
+        let (progress_start, progress_range) = progress_span;
+        let images = matches!(sink, FrameSink::Images(_));
+        // 이미지 시퀀스는 오디오 없음
+        let audio_mixer = audio_mixer.filter(|_| !images);
+
+        log_info!("[EXPORT] 총 프레임: {}", total_frames);
+
+        std::thread::scope(|scope| {
+            // 렌더링 스레드: 프레임 렌더링 + 자막 블렌딩 + 출력 포맷 변환
+            let (video_tx, video_rx) = mpsc::sync_channel(PIPELINE_DEPTH);
+            let render_timestamps = timestamps.clone();
+            scope.spawn(move || {
+                Self::render_stage(timeline, config, images, render_timestamps, job, subtitles, video_tx)
+            });
+
+            // 오디오 스레드: 프레임 구간별 믹싱
+            let audio_rx = audio_mixer.map(|audio_mixer| {
+                let (audio_tx, audio_rx) = mpsc::sync_channel(PIPELINE_DEPTH);
+                let audio_timestamps = timestamps.clone();
+                scope.spawn(move || {
+                    Self::audio_stage(timeline, audio_mixer, audio_timestamps, config.fps, job, audio_tx)
+                });
+                audio_rx
+            });
+
+            // 인코딩 (현재 스레드) — 반환 시 수신 채널이 닫혀 다른 단계도 멈춤
+            let mut fps_window = (Instant::now(), 0u64);
+            let mut encode_fps = 0.0;
+            let mut frames_done = 0u64;
+            for frame in video_rx.iter() {
+                // 취소 확인 (렌더링/오디오 스레드도 각자 확인 후 종료)
+                if job.cancelled.load(Ordering::SeqCst) {
+                    break;
+                }
+                let frame = frame?;
+
+                if frames_done == 0 {
+                    log_debug!(
+                        "[EXPORT] 첫 프레임: rendered={}x{}, output={}x{}, data={}bytes",
+                        frame.width, frame.height,
+                        config.width, config.height,
+                        frame.data.len()
+                    );
+                }
+
+                match &mut sink {
+                    FrameSink::Encoder(encoder) => {
+                        if frame.is_yuv {
+                            encoder.encode_frame_yuv(&frame.data, frame.width, frame.height)?;
+                        } else {
+                            encoder.encode_frame(&frame.data, frame.width, frame.height)?;
+                        }
+
+                        // 같은 프레임 구간의 오디오
+                        if let Some(audio_rx) = &audio_rx {
+                            let audio_samples = match audio_rx.recv() {
+                                Ok(samples) => samples?,
+                                Err(_) if job.cancelled.load(Ordering::SeqCst) => break,
+                                Err(_) => return Err("오디오 믹싱 스레드가 중단되었습니다".to_string()),
+                            };
+                            encoder.encode_audio_samples(&audio_samples)?;
+                        }
+                    }
+                    FrameSink::Images(writer) => {
+                        writer.write_frame(&frame.data, frame.width, frame.height)?;
+                    }
+                }
+
+                // 진행률 업데이트
+                frames_done += 1;
+                let pct = (progress_start + (frames_done as i64 * progress_range as i64 / total_frames) as u32).min(99);
+                job.progress.store(pct, Ordering::SeqCst);
+
+                // 통계 업데이트 (FPS는 최근 FPS_WINDOW_FRAMES 구간 기준)
+                if frames_done - fps_window.1 >= FPS_WINDOW_FRAMES {
+                    let window_secs = fps_window.0.elapsed().as_secs_f64();
+                    if window_secs > 0.0 {
+                        encode_fps = (frames_done - fps_window.1) as f64 / window_secs;
+                    }
+                    fps_window = (Instant::now(), frames_done);
+                }
+                let bytes_written = match &sink {
+                    FrameSink::Encoder(encoder) => encoder.bytes_written(),
+                    FrameSink::Images(writer) => writer.bytes_written(),
                 };
-                blend_overlay_rgba(&mut rgba, frame.width, frame.height, overlay);
-                // RGBA→YUV420P 변환 후 인코딩 (YUV 직접 경로 유지)
-                let yuv = rgba_to_yuv420p(&rgba, frame.width, frame.height);
-                encoder.encode_frame_yuv(&yuv, frame.width, frame.height)?;
-            } else {
-                // 자막 없는 프레임: 기존 직접 경로 (변환 손실 없음)
-                if frame.is_yuv {
-                    encoder.encode_frame_yuv(&frame.data, frame.width, frame.height)?;
-                } else {
-                    encoder.encode_frame(&frame.data, frame.width, frame.height)?;
+                let elapsed_ms = job.started.elapsed().as_millis() as u64;
+                let overall = (progress_start as f64
+                    + frames_done as f64 * progress_range as f64 / total_frames as f64) / 100.0;
+                if let Ok(mut stats) = job.stats.lock() {
+                    *stats = ExportStats {
+                        frames_done,
+                        total_frames: total_frames as u64,
+                        encode_fps,
+                        average_bitrate_kbps: average_bitrate_kbps(bytes_written, frames_done, config.fps),
+                        elapsed_ms,
+                        eta_ms: estimate_eta_ms(elapsed_ms, overall),
+                        dropped_frames: frame.dropped_frames,
+                        cache_hit_rate: frame.cache_hit_rate,
+                    };
                 }
+
+                // 매 300프레임(~10초)마다 로그
+                if frames_done.is_multiple_of(300) {
+                    log_debug!("[EXPORT] 진행: {}/{} ({}%)", frames_done, total_frames, pct);
+                }
+            }
+
+            if job.cancelled.load(Ordering::SeqCst) {
+                log_info!("[EXPORT] 취소됨 (frame {}/{})", frames_done, total_frames);
+                return Ok(false);
             }
+            Ok(true)
+        })
+    }
+
+    /// 렌더링 단계 — 프레임을 순서대로 렌더링해 인코딩 가능한 형태로 전송
+    /// 취소되거나 수신 측이 닫히면 종료, 렌더링 실패는 Err로 전달 후 종료
+    fn render_stage(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        images: bool,
+        timestamps: Vec<i64>,
+        job: &JobShared,
+        subtitles: Option<&SubtitleOverlayList>,
+        tx: mpsc::SyncSender<Result<PipelineFrame, String>>,
+    ) {
+        // Export용 전용 Renderer (패스마다 새로 생성 → 디코더 상태 초기화)
+        let mut renderer = Renderer::new_for_export(timeline.clone(), config.width, config.height);
 
-            // 오디오 믹싱 + 인코딩
-            let audio_clips = {
-                let tl = timeline.lock()
-                    .map_err(|e| format!("Timeline lock failed: {}", e))?;
-                tl.get_all_audio_sources_at_time(timestamp_ms)
+        for timestamp_ms in timestamps {
+            if job.cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let frame = match renderer.render_frame(timestamp_ms) {
+                Ok(frame) => frame,
+                Err(e) => {
+                    let _ = tx.send(Err(format!("렌더링 실패 ({}ms): {}", timestamp_ms, e)));
+                    return;
+                }
             };
-            let audio_samples = audio_mixer.mix_range(
-                &audio_clips,
-                timestamp_ms,
-                frame_duration_ms,
-            );
-            encoder.encode_audio_samples(&audio_samples)?;
 
-            // 진행률 업데이트
-            let pct = ((frame_index + 1) * 100 / total_frames).min(99) as u32;
-            progress.store(pct, Ordering::SeqCst);
+            // 자막 오버레이 (있을 때만 RGBA 경로, z 순서대로 블렌딩)
+            let overlays = subtitles.map(|s| s.get_active(timestamp_ms)).unwrap_or_default();
+            let (width, height) = (frame.width, frame.height);
+            let (data, is_yuv) = if images {
+                // 이미지 시퀀스: 항상 RGBA (자막 블렌딩 후 저장)
+                let mut rgba = if frame.is_yuv {
+                    yuv420p_to_rgba(&frame.data, width, height, YuvColor::BT709)
+                } else {
+                    Arc::unwrap_or_clone(frame.data)
+                };
+                blend_overlays_rgba(&mut rgba, width, height, &overlays);
+                (Arc::new(rgba), false)
+            } else if !overlays.is_empty() {
+                // 자막 프레임: YUV→RGBA 변환 → 알파 블렌딩 → YUV420P (YUV 직접 인코딩 경로 유지)
+                let mut rgba = if frame.is_yuv {
+                    yuv420p_to_rgba(&frame.data, width, height, YuvColor::BT709)
+                } else {
+                    Arc::unwrap_or_clone(frame.data)
+                };
+                blend_overlays_rgba(&mut rgba, width, height, &overlays);
+                (Arc::new(rgba_to_yuv420p(&rgba, width, height, YuvColor::BT709)), true)
+            } else {
+                // 자막 없는 프레임: 기존 직접 경로 (변환 손실 없음)
+                (frame.data, frame.is_yuv)
+            };
 
-            frame_index += 1;
+            let frame = PipelineFrame {
+                data,
+                width,
+                height,
+                is_yuv,
+                dropped_frames: renderer.dropped_frame_count(),
+                cache_hit_rate: renderer.cache_hit_rate(),
+            };
+            if tx.send(Ok(frame)).is_err() {
+                return;
+            }
+        }
+    }
 
-            // 매 300프레임(~10초)마다 로그
-            if frame_index % 300 == 0 {
-                eprintln!("[EXPORT] 진행: {}/{} ({}%)", frame_index, total_frames, pct);
+    /// 오디오 단계 — 비디오 프레임과 같은 구간 단위로 믹싱해 전송
+    fn audio_stage(
+        timeline: &Arc<Mutex<Timeline>>,
+        audio_mixer: &mut AudioMixer,
+        timestamps: Vec<i64>,
+        fps: f64,
+        job: &JobShared,
+        tx: mpsc::SyncSender<Result<Vec<f32>, String>>,
+    ) {
+        let frame_duration_ms = 1000.0 / fps;
+        for timestamp_ms in timestamps {
+            if job.cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+            let samples = Self::mix_frame_audio(timeline, audio_mixer, timestamp_ms, frame_duration_ms);
+            let failed = samples.is_err();
+            if tx.send(samples).is_err() || failed {
+                return;
             }
         }
+    }
 
-        // 8. 인코딩 완료 (flush + trailer)
-        encoder.finish()?;
+    /// 2-pass 통계 파일 경로 (임시 디렉토리, 프로세스/시각별 고유)
+    fn stats_path() -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        std::env::temp_dir().join(format!("vortex_2pass_{}_{}.log", std::process::id(), nanos))
+    }
 
-        // 9. 임시 파일을 최종 경로로 이동 (비ASCII 경로)
-        if needs_move {
-            eprintln!("[EXPORT] 임시 파일 이동: {} → {}", encoder_path, config.output_path);
-            Self::move_file(&encoder_path, &config.output_path)?;
+    /// 2-pass 통계 파일 정리 (x264 .mbtree, x265 .cutree 부가 파일 포함)
+    fn remove_stats_files(stats_path: &Path) {
+        for suffix in ["", ".mbtree", ".cutree", ".temp", ".mbtree.temp", ".cutree.temp"] {
+            let mut path = stats_path.as_os_str().to_owned();
+            path.push(suffix);
+            let _ = std::fs::remove_file(PathBuf::from(path));
         }
-
-        Ok(())
     }
 
     /// 진행률 가져오기 (0~100)
     pub fn get_progress(&self) -> u32 {
-        self.progress.load(Ordering::SeqCst)
+        self.shared.progress.load(Ordering::SeqCst)
     }
 
     /// 취소 요청
     pub fn cancel(&self) {
-        self.cancelled.store(true, Ordering::SeqCst);
+        self.shared.cancelled.store(true, Ordering::SeqCst);
     }
 
     /// 완료 여부
     pub fn is_finished(&self) -> bool {
-        self.finished.load(Ordering::SeqCst)
+        self.shared.finished.load(Ordering::SeqCst)
     }
 
     /// 에러 메시지 가져오기 (None이면 성공 또는 진행 중)
     pub fn get_error(&self) -> Option<String> {
-        self.error.lock().ok().and_then(|e| e.clone())
+        self.shared.error.lock().ok().and_then(|e| e.clone())
+    }
+
+    /// 에러 분류 (디스크 부족/권한 등, None이면 성공 또는 진행 중)
+    pub fn error_category(&self) -> Option<ErrorCategory> {
+        self.get_error().map(|msg| error::classify(&msg))
+    }
+
+    /// Export 통계 스냅샷
+    pub fn get_stats(&self) -> ExportStats {
+        let mut stats = self.shared.stats.lock().map(|s| *s).unwrap_or_default();
+        if !self.is_finished() {
+            stats.elapsed_ms = self.shared.started.elapsed().as_millis() as u64;
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitrate_for_target_size() {
+        // 60초, 100MB → 약 13.07Mbps - 오디오 192kbps
+        let bitrate = bitrate_for_target_size(100_000_000, 60_000, DEFAULT_AUDIO_BITRATE);
+        assert_eq!(bitrate, 12_874_666);
+
+        // 너무 작은 목표 크기는 하한 적용
+        assert_eq!(bitrate_for_target_size(1_000, 60_000, DEFAULT_AUDIO_BITRATE), MIN_TARGET_BITRATE);
+        assert_eq!(bitrate_for_target_size(1_000, 0, DEFAULT_AUDIO_BITRATE), 0);
+    }
+
+    #[test]
+    fn test_export_config_rate_control() {
+        let mut config = ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23);
+        assert_eq!(config.rate_control(60_000).unwrap(), RateControl::crf(23));
+
+        // 비트레이트 모드인데 목표가 없으면 에러
+        config.rate_control = RateControlMode::Vbr as u32;
+        assert!(config.rate_control(60_000).is_err());
+
+        config.target_bitrate_kbps = 8000;
+        config.max_bitrate_kbps = 12000;
+        let rate = config.rate_control(60_000).unwrap();
+        assert_eq!((rate.target_bitrate, rate.max_bitrate), (8_000_000, 12_000_000));
+
+        // 목표 크기가 목표 비트레이트보다 우선
+        config.target_size_bytes = 100_000_000;
+        assert_eq!(config.rate_control(60_000).unwrap().target_bitrate, 12_874_666);
+    }
+
+    #[test]
+    fn test_export_range() {
+        let mut config = ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23);
+        assert_eq!(config.export_range(60_000).unwrap(), (0, 60_000));
+
+        config.start_ms = 10_000;
+        config.end_ms = 20_000;
+        assert_eq!(config.export_range(60_000).unwrap(), (10_000, 20_000));
+
+        // 끝이 타임라인을 넘으면 클램프
+        config.end_ms = 90_000;
+        assert_eq!(config.export_range(60_000).unwrap(), (10_000, 60_000));
+
+        // 빈 구간
+        config.start_ms = 70_000;
+        assert!(config.export_range(60_000).is_err());
+    }
+
+    #[test]
+    fn test_segment_frames() {
+        // 10초 @ 29.97fps → 300프레임
+        assert_eq!(frame_count((0, 10_000), 29.97), 300);
+        assert_eq!(frame_count((1_000, 2_000), 30.0), 30);
+
+        // 균등 분할, 경계 연속
+        let segments = segment_frames(1_000, 4);
+        assert_eq!(segments, vec![(0, 250), (250, 500), (500, 750), (750, 1_000)]);
+
+        // 세그먼트 최소 길이 미만이면 개수 축소 / 분할 안 함
+        assert_eq!(segment_frames(400, 8), vec![(0, 200), (200, 400)]);
+        assert_eq!(segment_frames(100, 4), vec![(0, 100)]);
+        assert_eq!(segment_frames(1_000, 0), vec![(0, 1_000)]);
+    }
+
+    #[test]
+    fn test_frame_timestamps() {
+        assert_eq!(frame_timestamps((1_000, 2_000), (0, 3), 30.0), vec![1_000, 1_033, 1_066]);
+        // 세그먼트: 프레임 번호 기준 시각 (구간 시작 + i * 프레임 길이)
+        assert_eq!(frame_timestamps((0, 10_000), (30, 32), 30.0), vec![1_000, 1_033]);
+        // 구간 끝을 넘는 프레임 제외
+        assert_eq!(frame_timestamps((0, 100), (0, 10), 25.0), vec![0, 40, 80]);
+    }
+
+    #[test]
+    fn test_export_stats_helpers() {
+        assert_eq!(estimate_eta_ms(10_000, 0.0), -1);
+        assert_eq!(estimate_eta_ms(10_000, 0.25), 30_000);
+        assert_eq!(estimate_eta_ms(10_000, 1.0), 0);
+
+        // 30프레임(1초) 1MB → 8000kbps
+        assert_eq!(average_bitrate_kbps(1_000_000, 30, 30.0), 8000.0);
+        assert_eq!(average_bitrate_kbps(1_000_000, 0, 30.0), 0.0);
+    }
+
+    #[test]
+    fn test_export_job_stats_on_failure() {
+        // 빈 타임라인 → 즉시 실패, 통계는 초기값
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let job = ExportJob::start(timeline, ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23));
+        while !job.is_finished() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(job.get_error().is_some());
+        let stats = job.get_stats();
+        assert_eq!(stats.frames_done, 0);
+        assert_eq!(stats.eta_ms, -1);
+    }
+
+    #[test]
+    fn test_export_job_error_category() {
+        let job = ExportJob { shared: Arc::new(JobShared::new()) };
+        assert_eq!(job.error_category(), None);
+
+        let enospc = std::io::Error::from_raw_os_error(28);
+        *job.shared.error.lock().unwrap() = Some(format!("출력 파일 쓰기 실패: {}", enospc));
+        assert_eq!(job.error_category(), Some(ErrorCategory::DiskFull));
+
+        let eacces = std::io::Error::from_raw_os_error(13);
+        *job.shared.error.lock().unwrap() = Some(format!("출력 파일 열기 실패: {}", eacces));
+        assert_eq!(job.error_category(), Some(ErrorCategory::PermissionDenied));
+    }
+
+    #[test]
+    fn test_export_target() {
+        let mut config = ExportConfig::new("frames/shot.png".to_string(), 1920, 1080, 30.0, 23);
+        assert_eq!(config.image_format(), None);
+        config.target = 1;
+        assert_eq!(config.image_format(), Some(ImageFormat::Png));
+        config.target = 2;
+        assert_eq!(config.image_format(), Some(ImageFormat::Jpeg));
     }
 }
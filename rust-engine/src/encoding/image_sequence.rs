@@ -0,0 +1,254 @@
+// 이미지 시퀀스 출력 - 렌더링 프레임을 번호 붙은 PNG/JPEG 파일로 저장
+// 외부 합성 툴(After Effects, Nuke 등) 전달용 + 단일 스틸 프레임 저장 (write_image)
+// RGBA → PNG (무손실) / RGBA → YUVJ420P → JPEG
+
+use ffmpeg_next as ffmpeg;
+use ffmpeg::format::Pixel;
+use ffmpeg::codec;
+use ffmpeg::software::scaling;
+use crate::utils::log::log_info;
+use std::path::{Path, PathBuf};
+
+/// 이미지 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+        }
+    }
+
+    fn codec_id(self) -> codec::Id {
+        match self {
+            ImageFormat::Png => codec::Id::PNG,
+            ImageFormat::Jpeg => codec::Id::MJPEG,
+        }
+    }
+
+    fn pixel_format(self) -> Pixel {
+        match self {
+            ImageFormat::Png => Pixel::RGBA,
+            ImageFormat::Jpeg => Pixel::YUVJ420P,
+        }
+    }
+}
+
+/// 시퀀스 프레임 파일 경로
+/// output_path의 디렉토리 + 파일명(확장자 제외)에 6자리 번호: "out/shot.png" → "out/shot_000012.png"
+pub fn sequence_frame_path(output_path: &str, index: u64, format: ImageFormat) -> PathBuf {
+    let path = Path::new(output_path);
+    let stem = path.file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("frame");
+    let file_name = format!("{}_{:06}.{}", stem, index, format.extension());
+    match path.parent() {
+        Some(parent) => parent.join(file_name),
+        None => PathBuf::from(file_name),
+    }
+}
+
+/// JPEG qscale (2 = 최고 품질, 31 = 최저) — 이미지 시퀀스 기본값
+const JPEG_QSCALE: i32 = 2;
+
+/// JPEG 품질 (1~100, 높을수록 고화질) → qscale (31~2)
+pub fn jpeg_qscale(quality: u32) -> i32 {
+    let quality = quality.clamp(1, 100) as i32;
+    31 - (quality - 1) * 29 / 99
+}
+
+/// FFmpeg FF_QP2LAMBDA (qscale → lambda)
+const QP2LAMBDA: i32 = 118;
+
+/// 이미지 시퀀스 작성기 (인코더 1회 생성 후 프레임마다 재사용)
+pub struct ImageSequenceWriter {
+    output_path: String,
+    format: ImageFormat,
+    encoder: ffmpeg::encoder::Video,
+    /// RGBA → YUVJ420P (JPEG만)
+    scaler: Option<scaling::Context>,
+    jpeg_qscale: i32,
+    width: u32,
+    height: u32,
+    frames_written: u64,
+    bytes_written: u64,
+}
+
+impl ImageSequenceWriter {
+    pub fn new(output_path: &str, format: ImageFormat, width: u32, height: u32) -> Result<Self, String> {
+        Self::with_qscale(output_path, format, width, height, JPEG_QSCALE)
+    }
+
+    fn with_qscale(output_path: &str, format: ImageFormat, width: u32, height: u32, jpeg_qscale: i32) -> Result<Self, String> {
+        ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+        let codec = ffmpeg::encoder::find(format.codec_id())
+            .ok_or_else(|| format!("{:?} 인코더를 찾을 수 없습니다", format))?;
+
+        let mut encoder = codec::context::Context::new_with_codec(codec)
+            .encoder()
+            .video()
+            .map_err(|e| format!("Failed to get image encoder: {}", e))?;
+
+        encoder.set_width(width);
+        encoder.set_height(height);
+        encoder.set_format(format.pixel_format());
+        encoder.set_time_base(ffmpeg::Rational::new(1, 25));
+
+        if format == ImageFormat::Jpeg {
+            // 고정 qscale (프레임 quality와 함께 설정해야 적용됨)
+            encoder.set_flags(codec::flag::Flags::QSCALE);
+            unsafe {
+                (*encoder.as_mut_ptr()).global_quality = jpeg_qscale * QP2LAMBDA;
+            }
+        }
+
+        let encoder = encoder.open_as_with(codec, ffmpeg::Dictionary::new())
+            .map_err(|e| format!("Failed to open image encoder: {}", e))?;
+
+        let scaler = if format.pixel_format() != Pixel::RGBA {
+            let scaler = scaling::Context::get(
+                Pixel::RGBA,
+                width,
+                height,
+                format.pixel_format(),
+                width,
+                height,
+                scaling::Flags::BICUBIC,
+            )
+            .map_err(|e| format!("Failed to create scaler: {}", e))?;
+            Some(scaler)
+        } else {
+            None
+        };
+
+        log_info!("[IMAGE_SEQ] {:?} {}x{} → {}", format, width, height, output_path);
+
+        Ok(Self {
+            output_path: output_path.to_string(),
+            format,
+            encoder,
+            scaler,
+            jpeg_qscale,
+            width,
+            height,
+            frames_written: 0,
+            bytes_written: 0,
+        })
+    }
+
+    /// RGBA 프레임 1장 → 다음 번호 파일로 저장
+    pub fn write_frame(&mut self, rgba_data: &[u8], width: u32, height: u32) -> Result<PathBuf, String> {
+        let data = self.encode(rgba_data, width, height)?;
+
+        // std::fs로 저장 (비ASCII 경로도 안전)
+        let path = sequence_frame_path(&self.output_path, self.frames_written, self.format);
+        std::fs::write(&path, &data)
+            .map_err(|e| format!("이미지 저장 실패 ({}): {}", path.display(), e))?;
+
+        self.frames_written += 1;
+        self.bytes_written += data.len() as u64;
+        Ok(path)
+    }
+
+    /// RGBA 프레임 1장 → 이미지 파일 바이트
+    fn encode(&mut self, rgba_data: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+        if width != self.width || height != self.height {
+            return Err(format!(
+                "Frame dimensions mismatch: got {}x{}, expected {}x{}",
+                width, height, self.width, self.height
+            ));
+        }
+        let row_size = width as usize * 4;
+        if rgba_data.len() != row_size * height as usize {
+            return Err(format!("Invalid frame data size: {}", rgba_data.len()));
+        }
+
+        let mut rgba_frame = ffmpeg::frame::Video::new(Pixel::RGBA, width, height);
+        {
+            let stride = rgba_frame.stride(0);
+            let dst = rgba_frame.data_mut(0);
+            for (y, src_row) in rgba_data.chunks_exact(row_size).enumerate() {
+                dst[y * stride..y * stride + row_size].copy_from_slice(src_row);
+            }
+        }
+
+        let mut frame = match self.scaler.as_mut() {
+            Some(scaler) => {
+                let mut converted = ffmpeg::frame::Video::empty();
+                scaler.run(&rgba_frame, &mut converted)
+                    .map_err(|e| format!("Scaler failed: {}", e))?;
+                converted
+            }
+            None => rgba_frame,
+        };
+        frame.set_pts(Some(self.frames_written as i64));
+        if self.format == ImageFormat::Jpeg {
+            unsafe {
+                (*frame.as_mut_ptr()).quality = self.jpeg_qscale * QP2LAMBDA;
+            }
+        }
+
+        self.encoder.send_frame(&frame)
+            .map_err(|e| format!("Failed to send image frame: {}", e))?;
+
+        // 이미지 코덱은 프레임당 패킷 1개
+        let mut packet = ffmpeg::Packet::empty();
+        self.encoder.receive_packet(&mut packet)
+            .map_err(|e| format!("Failed to receive image packet: {}", e))?;
+        packet.data()
+            .map(|data| data.to_vec())
+            .ok_or_else(|| "Empty image packet".to_string())
+    }
+
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+}
+
+/// RGBA 프레임 1장 → 이미지 파일 1개 저장 (번호 없이 path 그대로)
+/// - quality: JPEG 품질 1~100 (PNG는 무손실이라 무시)
+/// - 반환: 저장한 바이트 수
+pub fn write_image(path: &str, format: ImageFormat, quality: u32, rgba_data: &[u8], width: u32, height: u32) -> Result<u64, String> {
+    let mut writer = ImageSequenceWriter::with_qscale(path, format, width, height, jpeg_qscale(quality))?;
+    let data = writer.encode(rgba_data, width, height)?;
+    std::fs::write(path, &data)
+        .map_err(|e| format!("이미지 저장 실패 ({}): {}", path, e))?;
+    Ok(data.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_frame_path() {
+        let path = sequence_frame_path("out/shot.png", 12, ImageFormat::Png);
+        assert_eq!(path, Path::new("out").join("shot_000012.png"));
+
+        // 확장자는 형식 기준
+        let path = sequence_frame_path("out/shot.png", 0, ImageFormat::Jpeg);
+        assert_eq!(path, Path::new("out").join("shot_000000.jpg"));
+
+        assert_eq!(sequence_frame_path("shot", 3, ImageFormat::Png), PathBuf::from("shot_000003.png"));
+    }
+
+    #[test]
+    fn test_jpeg_qscale() {
+        assert_eq!(jpeg_qscale(100), JPEG_QSCALE);
+        assert_eq!(jpeg_qscale(1), 31);
+        assert_eq!(jpeg_qscale(0), 31);
+        assert_eq!(jpeg_qscale(500), 2);
+        assert!(jpeg_qscale(90) < jpeg_qscale(50));
+    }
+}
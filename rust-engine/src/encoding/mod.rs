@@ -1,7 +1,13 @@
 // 인코딩 & 내보내기 모듈
-// H.264 비디오 + AAC 오디오 → MP4 컨테이너
+// H.264/H.265/VP9/AV1/ProRes 비디오 + AAC/Opus 오디오 → MP4/MOV/MKV/WebM 컨테이너
 
 pub mod encoder;
+pub mod container;
 pub mod exporter;
+pub mod export_queue;
+pub mod presets;
+pub mod image_sequence;
+pub mod smart_render;
+pub mod remux;
 pub mod audio_decoder;
 pub mod audio_mixer;
@@ -0,0 +1,219 @@
+// Export 프리셋 - 용도별 해상도/코덱/비트레이트/오디오 설정 묶음
+// 호스트(C#)와 엔진 기본값을 한 곳에서 관리 (exporter_list_presets로 조회)
+
+use crate::encoding::encoder::{RateControlMode, VideoCodec};
+use crate::encoding::exporter::ExportConfig;
+
+/// Export 프리셋
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportPreset {
+    /// FFI 식별자 (exporter_start_with_preset)
+    pub name: &'static str,
+    /// 표시 이름
+    pub label: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    pub codec: VideoCodec,
+    pub rate_control: RateControlMode,
+    pub crf: u32,
+    pub target_bitrate_kbps: u32,
+    pub max_bitrate_kbps: u32,
+    pub audio_bitrate_kbps: u32,
+    /// 권장 출력 확장자 (컨테이너)
+    pub extension: &'static str,
+}
+
+/// 내장 프리셋 목록 (표시 순서)
+pub const PRESETS: &[ExportPreset] = &[
+    ExportPreset {
+        name: "youtube_1080p",
+        label: "YouTube 1080p",
+        width: 1920,
+        height: 1080,
+        fps: 30.0,
+        codec: VideoCodec::H264,
+        rate_control: RateControlMode::Vbr,
+        crf: 20,
+        target_bitrate_kbps: 8000,
+        max_bitrate_kbps: 12000,
+        audio_bitrate_kbps: 384,
+        extension: "mp4",
+    },
+    ExportPreset {
+        name: "youtube_1080p60",
+        label: "YouTube 1080p 60fps",
+        width: 1920,
+        height: 1080,
+        fps: 60.0,
+        codec: VideoCodec::H264,
+        rate_control: RateControlMode::Vbr,
+        crf: 20,
+        target_bitrate_kbps: 12000,
+        max_bitrate_kbps: 18000,
+        audio_bitrate_kbps: 384,
+        extension: "mp4",
+    },
+    ExportPreset {
+        name: "youtube_4k",
+        label: "YouTube 4K",
+        width: 3840,
+        height: 2160,
+        fps: 30.0,
+        codec: VideoCodec::H265,
+        rate_control: RateControlMode::Vbr,
+        crf: 20,
+        target_bitrate_kbps: 35000,
+        max_bitrate_kbps: 50000,
+        audio_bitrate_kbps: 384,
+        extension: "mp4",
+    },
+    ExportPreset {
+        name: "vertical_1080x1920",
+        label: "Vertical 9:16 (Shorts/Reels/TikTok)",
+        width: 1080,
+        height: 1920,
+        fps: 30.0,
+        codec: VideoCodec::H264,
+        rate_control: RateControlMode::Vbr,
+        crf: 20,
+        target_bitrate_kbps: 8000,
+        max_bitrate_kbps: 12000,
+        audio_bitrate_kbps: 192,
+        extension: "mp4",
+    },
+    ExportPreset {
+        name: "square_1080",
+        label: "Square 1:1",
+        width: 1080,
+        height: 1080,
+        fps: 30.0,
+        codec: VideoCodec::H264,
+        rate_control: RateControlMode::Vbr,
+        crf: 20,
+        target_bitrate_kbps: 5000,
+        max_bitrate_kbps: 8000,
+        audio_bitrate_kbps: 192,
+        extension: "mp4",
+    },
+    ExportPreset {
+        name: "web_720p",
+        label: "Web 720p",
+        width: 1280,
+        height: 720,
+        fps: 30.0,
+        codec: VideoCodec::H264,
+        rate_control: RateControlMode::Crf,
+        crf: 23,
+        target_bitrate_kbps: 0,
+        max_bitrate_kbps: 0,
+        audio_bitrate_kbps: 128,
+        extension: "mp4",
+    },
+    ExportPreset {
+        name: "hevc_1080p_archive",
+        label: "H.265 1080p (Archive)",
+        width: 1920,
+        height: 1080,
+        fps: 30.0,
+        codec: VideoCodec::H265,
+        rate_control: RateControlMode::Crf,
+        crf: 20,
+        target_bitrate_kbps: 0,
+        max_bitrate_kbps: 0,
+        audio_bitrate_kbps: 256,
+        extension: "mp4",
+    },
+    ExportPreset {
+        name: "prores_master",
+        label: "ProRes 422 HQ Master",
+        width: 1920,
+        height: 1080,
+        fps: 30.0,
+        codec: VideoCodec::ProRes,
+        rate_control: RateControlMode::Crf,
+        crf: 0,
+        target_bitrate_kbps: 0,
+        max_bitrate_kbps: 0,
+        audio_bitrate_kbps: 320,
+        extension: "mov",
+    },
+];
+
+/// 이름으로 프리셋 찾기
+pub fn find_preset(name: &str) -> Option<&'static ExportPreset> {
+    PRESETS.iter().find(|p| p.name == name)
+}
+
+impl ExportPreset {
+    /// 프리셋 → ExportConfig (인코더 타입은 Auto)
+    pub fn to_config(&self, output_path: String) -> ExportConfig {
+        ExportConfig {
+            codec: self.codec as u32,
+            rate_control: self.rate_control as u32,
+            target_bitrate_kbps: self.target_bitrate_kbps,
+            max_bitrate_kbps: self.max_bitrate_kbps,
+            audio_bitrate_kbps: self.audio_bitrate_kbps,
+            ..ExportConfig::new(output_path, self.width, self.height, self.fps, self.crf)
+        }
+    }
+
+    /// JSON 객체 (codec/rate_control은 FFI u32 값)
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"name\":\"{}\",\"label\":\"{}\",\"width\":{},\"height\":{},\"fps\":{},\"codec\":{},\"rate_control\":{},\"crf\":{},\"target_bitrate_kbps\":{},\"max_bitrate_kbps\":{},\"audio_bitrate_kbps\":{},\"extension\":\"{}\"}}",
+            self.name,
+            self.label,
+            self.width,
+            self.height,
+            self.fps,
+            self.codec as u32,
+            self.rate_control as u32,
+            self.crf,
+            self.target_bitrate_kbps,
+            self.max_bitrate_kbps,
+            self.audio_bitrate_kbps,
+            self.extension,
+        )
+    }
+}
+
+/// 전체 프리셋 목록 JSON 배열
+pub fn presets_json() -> String {
+    let items: Vec<String> = PRESETS.iter().map(|p| p.to_json()).collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_lookup_and_config() {
+        // 이름 중복 없음
+        for (i, preset) in PRESETS.iter().enumerate() {
+            assert!(PRESETS[i + 1..].iter().all(|p| p.name != preset.name));
+        }
+        assert!(find_preset("unknown").is_none());
+
+        let preset = find_preset("vertical_1080x1920").unwrap();
+        let config = preset.to_config("out.mp4".to_string());
+        assert_eq!((config.width, config.height), (1080, 1920));
+        assert_eq!(config.rate_control, RateControlMode::Vbr as u32);
+        assert_eq!(config.target_bitrate_kbps, 8000);
+        assert_eq!(config.audio_bitrate_kbps, 192);
+        assert_eq!(config.encoder_type, 0);
+
+        let master = find_preset("prores_master").unwrap();
+        assert_eq!(master.to_config("master.mov".to_string()).codec, VideoCodec::ProRes as u32);
+        assert_eq!(master.extension, "mov");
+    }
+
+    #[test]
+    fn test_presets_json() {
+        let json = presets_json();
+        assert!(json.starts_with("[{\"name\":\"youtube_1080p\",\"label\":\"YouTube 1080p\",\"width\":1920,\"height\":1080,\"fps\":30,"));
+        assert_eq!(json.matches("\"name\":").count(), PRESETS.len());
+        assert!(json.ends_with("\"extension\":\"mov\"}]"));
+    }
+}
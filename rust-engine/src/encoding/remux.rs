@@ -0,0 +1,257 @@
+// 스트림 복사 (remux) - 디코딩/인코딩 없이 소스 패킷을 출력 컨테이너로 복사
+// 스마트 렌더링용: 원본 구간을 그대로 잘라 붙임 (시작점은 키프레임이어야 함)
+
+use crate::encoding::container::Container;
+use crate::encoding::encoder::{add_output_chapters, VideoCodec};
+use crate::ffmpeg::probe::stream_rotation;
+use crate::timeline::Chapter;
+use ffmpeg_next as ffmpeg;
+use ffmpeg::codec;
+use ffmpeg::media::Type;
+use std::path::Path;
+
+/// 복사 대상 소스 스트림 정보
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopySource {
+    /// 비디오 코덱 (Export 코덱이 아니면 None)
+    pub codec: Option<VideoCodec>,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    /// 컴포넌트당 비트 수 (알 수 없으면 8)
+    pub bit_depth: u32,
+    /// PQ/HLG 전달 함수 (일반 Export는 SDR로 톤 매핑)
+    pub hdr: bool,
+    /// display matrix 회전 (0/90/180/270, 복사하면 플레이어마다 다르게 표시)
+    pub rotation_deg: i32,
+    /// 알파 채널 (알파 픽셀 포맷 또는 WebM alpha_mode)
+    pub has_alpha: bool,
+    /// 컨테이너 길이 (알 수 없으면 0)
+    pub duration_ms: i64,
+    pub has_audio: bool,
+    /// 오디오 스트림을 출력 컨테이너에 그대로 넣을 수 있는지
+    pub audio_copyable: bool,
+}
+
+fn video_codec_of(id: codec::Id) -> Option<VideoCodec> {
+    match id {
+        codec::Id::H264 => Some(VideoCodec::H264),
+        codec::Id::HEVC => Some(VideoCodec::H265),
+        codec::Id::VP9 => Some(VideoCodec::Vp9),
+        codec::Id::AV1 => Some(VideoCodec::Av1),
+        codec::Id::PRORES => Some(VideoCodec::ProRes),
+        _ => None,
+    }
+}
+
+/// 컨테이너별 복사 가능한 오디오 코덱
+fn audio_copy_supported(container: Container, id: codec::Id) -> bool {
+    match container {
+        Container::Mp4 => matches!(id, codec::Id::AAC | codec::Id::MP3 | codec::Id::AC3 | codec::Id::EAC3),
+        Container::Mov => matches!(id, codec::Id::AAC | codec::Id::MP3 | codec::Id::AC3 | codec::Id::ALAC),
+        Container::Mkv => true,
+        Container::WebM => matches!(id, codec::Id::OPUS | codec::Id::VORBIS),
+    }
+}
+
+/// ms → 스트림 time_base
+fn ms_to_ts(ms: i64, time_base: ffmpeg::Rational) -> i64 {
+    ms * i64::from(time_base.denominator()) / (i64::from(time_base.numerator()) * 1000)
+}
+
+/// 스트림 time_base → ms
+fn ts_to_ms(ts: i64, time_base: ffmpeg::Rational) -> i64 {
+    ts * i64::from(time_base.numerator()) * 1000 / i64::from(time_base.denominator())
+}
+
+/// 소스 파일의 비디오/오디오 스트림 정보 조회
+pub fn probe_copy_source(path: &Path, container: Container) -> Result<CopySource, String> {
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+    let input_ctx = ffmpeg::format::input(&path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    let video_stream = input_ctx.streams().best(Type::Video).ok_or("No video stream found")?;
+    let params = video_stream.parameters();
+    // SAFETY: codecpar는 stream 수명 동안 유효, format 값은 FFmpeg이 기록한 enum 값
+    let (width, height, bit_depth, alpha_format, hdr) = unsafe {
+        let par = params.as_ptr();
+        let desc = if (*par).format >= 0 {
+            let pix_fmt: ffmpeg::ffi::AVPixelFormat = std::mem::transmute((*par).format);
+            ffmpeg::ffi::av_pix_fmt_desc_get(pix_fmt)
+        } else {
+            std::ptr::null()
+        };
+        let (bit_depth, alpha_format) = if desc.is_null() {
+            (8, false)
+        } else {
+            (
+                (*desc).comp[0].depth.max(0) as u32,
+                (*desc).flags & ffmpeg::ffi::AV_PIX_FMT_FLAG_ALPHA as u64 != 0,
+            )
+        };
+        let hdr = matches!(
+            (*par).color_trc,
+            ffmpeg::ffi::AVColorTransferCharacteristic::AVCOL_TRC_SMPTE2084
+                | ffmpeg::ffi::AVColorTransferCharacteristic::AVCOL_TRC_ARIB_STD_B67
+        );
+        ((*par).width as u32, (*par).height as u32, bit_depth, alpha_format, hdr)
+    };
+    let has_alpha = alpha_format || video_stream.metadata().get("alpha_mode") == Some("1");
+
+    let audio_id = input_ctx.streams().best(Type::Audio).map(|s| s.parameters().id());
+
+    Ok(CopySource {
+        codec: video_codec_of(params.id()),
+        width,
+        height,
+        fps: f64::from(video_stream.avg_frame_rate()),
+        bit_depth,
+        hdr,
+        rotation_deg: stream_rotation(&video_stream),
+        has_alpha,
+        duration_ms: input_ctx.duration().max(0) / 1000,
+        has_audio: audio_id.is_some(),
+        audio_copyable: audio_id.is_some_and(|id| audio_copy_supported(container, id)),
+    })
+}
+
+/// time_ms 위치에 비디오 키프레임이 있는지 (반 프레임 오차 허용)
+pub fn starts_on_keyframe(path: &Path, time_ms: i64, fps: f64) -> Result<bool, String> {
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+    let mut input_ctx = ffmpeg::format::input(&path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let video_index = input_ctx.streams().best(Type::Video).ok_or("No video stream found")?.index();
+
+    // time_ms 이전의 가장 가까운 키프레임으로 seek (AV_TIME_BASE = us)
+    let target_us = time_ms * 1000;
+    input_ctx.seek(target_us, ..=target_us)
+        .map_err(|e| format!("Seek failed: {}", e))?;
+
+    let tolerance_ms = if fps > 0.0 { (500.0 / fps) as i64 } else { 0 };
+    for (stream, packet) in input_ctx.packets() {
+        if stream.index() != video_index {
+            continue;
+        }
+        let pts = match packet.pts().or(packet.dts()) {
+            Some(pts) => pts,
+            None => return Ok(false),
+        };
+        let pts_ms = ts_to_ms(pts, stream.time_base());
+        return Ok(packet.is_key() && (pts_ms - time_ms).abs() <= tolerance_ms);
+    }
+    Ok(false)
+}
+
+/// 소스 구간 [start_ms, end_ms)을 출력 파일로 패킷 복사 (출력 타임스탬프는 start = 0)
+/// - with_audio: 소스 오디오 스트림도 복사
+/// - chapters: 출력 챕터 (출력 시간 기준)
+/// - on_progress(진행률 0.0~1.0, 출력 바이트): false 반환 시 중단
+/// - 반환: 중단되면 false (트레일러는 작성됨, 파일 정리는 호출자 담당)
+pub fn remux_range<F: FnMut(f64, u64) -> bool>(
+    source: &Path,
+    output_path: &str,
+    container: Container,
+    range: (i64, i64),
+    with_audio: bool,
+    chapters: &[Chapter],
+    mut on_progress: F,
+) -> Result<bool, String> {
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+    let (start_ms, end_ms) = range;
+    let mut input_ctx = ffmpeg::format::input(&source)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut output_ctx = ffmpeg::format::output_as(output_path, container.format_name())
+        .map_err(|e| format!("Failed to create output: {}", e))?;
+
+    // 입력 스트림 인덱스 → 출력 스트림 인덱스
+    let video_index = input_ctx.streams().best(Type::Video).ok_or("No video stream found")?.index();
+    let audio_index = if with_audio {
+        input_ctx.streams().best(Type::Audio).map(|s| s.index())
+    } else {
+        None
+    };
+    let mut stream_map = vec![None; input_ctx.nb_streams() as usize];
+    for (out_index, in_index) in std::iter::once(video_index).chain(audio_index).enumerate() {
+        let in_stream = input_ctx.stream(in_index).ok_or("Input stream not found")?;
+        let mut out_stream = output_ctx.add_stream(ffmpeg::encoder::find(codec::Id::None))
+            .map_err(|e| format!("Failed to add stream: {}", e))?;
+        out_stream.set_parameters(in_stream.parameters());
+        // 소스 컨테이너 태그는 출력 컨테이너에서 무효일 수 있음 → 먹서가 다시 결정
+        let tag = if in_stream.parameters().id() == codec::Id::HEVC && container.needs_hvc1_tag() {
+            u32::from_le_bytes(*b"hvc1")
+        } else {
+            0
+        };
+        unsafe {
+            (*(*out_stream.as_mut_ptr()).codecpar).codec_tag = tag;
+        }
+        stream_map[in_index] = Some(out_index);
+    }
+
+    add_output_chapters(&mut output_ctx, chapters)?;
+    output_ctx.write_header()
+        .map_err(|e| format!("Failed to write header: {}", e))?;
+
+    input_ctx.seek(start_ms * 1000, ..=start_ms * 1000)
+        .map_err(|e| format!("Seek failed: {}", e))?;
+
+    let mut video_done = false;
+    let mut audio_done = audio_index.is_none();
+    let mut bytes_written = 0u64;
+    let mut completed = true;
+
+    for (stream, mut packet) in input_ctx.packets() {
+        let out_index = match stream_map.get(stream.index()).copied().flatten() {
+            Some(index) => index,
+            None => continue,
+        };
+        let in_time_base = stream.time_base();
+        let start_ts = ms_to_ts(start_ms, in_time_base);
+        let end_ts = ms_to_ts(end_ms, in_time_base);
+        let is_video = stream.index() == video_index;
+
+        let (pts, dts) = match (packet.pts(), packet.dts()) {
+            (Some(pts), dts) => (pts, dts.unwrap_or(pts)),
+            (None, Some(dts)) => (dts, dts),
+            (None, None) => continue,
+        };
+
+        // DTS는 단조 증가 → DTS가 끝을 넘으면 이후 패킷은 모두 구간 밖
+        if dts >= end_ts {
+            if is_video { video_done = true; } else { audio_done = true; }
+            if video_done && audio_done {
+                break;
+            }
+            continue;
+        }
+        // 구간 밖 (B-프레임 재정렬로 끝 이후 PTS, 시작 이전 오디오)
+        if pts < start_ts || pts >= end_ts {
+            continue;
+        }
+
+        let out_time_base = output_ctx.stream(out_index).ok_or("Output stream not found")?.time_base();
+        packet.set_pts(Some(pts - start_ts));
+        packet.set_dts(Some(dts - start_ts));
+        packet.rescale_ts(in_time_base, out_time_base);
+        packet.set_position(-1);
+        packet.set_stream(out_index);
+        bytes_written += packet.size() as u64;
+        packet.write_interleaved(&mut output_ctx)
+            .map_err(|e| format!("Failed to write packet: {}", e))?;
+
+        if is_video {
+            let progress = (pts - start_ts) as f64 / (end_ts - start_ts).max(1) as f64;
+            if !on_progress(progress.clamp(0.0, 1.0), bytes_written) {
+                completed = false;
+                break;
+            }
+        }
+    }
+
+    output_ctx.write_trailer()
+        .map_err(|e| format!("Failed to write trailer: {}", e))?;
+    Ok(completed)
+}
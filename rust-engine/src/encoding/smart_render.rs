@@ -0,0 +1,258 @@
+// 스마트 렌더링 - 손대지 않은 단일 클립 구간은 디코딩/재인코딩 없이 패킷 복사 (무손실, 고속)
+// 여기서는 타임라인/소스 조건만 판단하고, 소스 조회/키프레임 확인과 복사는 remux 모듈 담당
+
+use crate::encoding::container::Container;
+use crate::encoding::encoder::VideoCodec;
+use crate::encoding::exporter::ExportConfig;
+use crate::encoding::remux::CopySource;
+use crate::timeline::{FitMode, Timeline, VideoClip};
+use std::path::PathBuf;
+
+/// 패킷 복사 계획 (소스 파일 구간)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamCopyPlan {
+    pub source_path: PathBuf,
+    pub source_start_ms: i64,
+    pub source_end_ms: i64,
+}
+
+/// 원본 그대로 출력되는 클립인지 (소스 파일 클립 + 속도/트랜스폼/키프레임/LUT/이펙트/보간 없음)
+/// 스트림을 직접 고른 클립은 제외 (패킷 복사는 기본 비디오 스트림만 다룸)
+/// 소스 오디오를 끈 클립도 제외 (패킷 복사는 소스 오디오 스트림을 그대로 포함)
+/// 배치는 기본 Fit만 (해상도 일치는 source_matches, end_behavior는 소스 길이로 판단)
+fn is_untouched(clip: &VideoClip) -> bool {
+    clip.stream_index.is_none()
+        && clip.fit_mode == FitMode::Fit
+        && !clip.is_image()
+        && !clip.is_generated()
+        && clip.speed == 1.0
+        && clip.transform.is_identity()
+        && clip.keyframes.is_empty()
+        && clip.lut_path.is_none()
+        && clip.effects.is_default()
+        && !clip.frame_interpolation
+        && clip.use_source_audio
+}
+
+/// Export 구간이 패킷 복사 가능한지 판단
+/// - 활성 비디오 트랙에서 구간과 겹치는 클립이 정확히 1개이고 구간 전체를 덮음
+/// - 클립이 원본 그대로 (is_untouched) + 트랜지션 없음 + 트랙/마스터 그레이딩 없음
+/// - 구간과 겹치는 오디오 트랙 클립 없음 (소스 오디오 스트림만 그대로 복사)
+/// - 솔로 오디오 트랙 없음 (솔로 중에는 비디오 클립 오디오가 믹스에서 빠짐)
+pub fn plan_stream_copy(timeline: &Timeline, range: (i64, i64)) -> Option<StreamCopyPlan> {
+    let (start_ms, end_ms) = range;
+    let overlaps = |clip_start: i64, clip_end: i64| clip_start < end_ms && clip_end > start_ms;
+
+    let mut found = None;
+    for track in timeline.video_tracks.iter().filter(|t| t.enabled) {
+        for clip in track.clips.iter().filter(|c| overlaps(c.start_time_ms, c.end_time_ms())) {
+            if found.is_some() {
+                return None;
+            }
+            let in_transition = track.transitions.iter()
+                .any(|t| t.from_clip_id == clip.id || t.to_clip_id == clip.id);
+            if in_transition || track.has_grade() {
+                return None;
+            }
+            found = Some(clip);
+        }
+    }
+
+    let clip = found?;
+    if timeline.has_master_grade() || clip.start_time_ms > start_ms || clip.end_time_ms() < end_ms || !is_untouched(clip) {
+        return None;
+    }
+
+    let any_solo = timeline.has_solo_audio_track();
+    let has_audio_clips = timeline.audio_tracks.iter()
+        .filter(|t| t.is_audible(any_solo))
+        .flat_map(|t| t.clips.iter())
+        .any(|c| overlaps(c.start_time_ms, c.end_time_ms()));
+    if any_solo || has_audio_clips {
+        return None;
+    }
+
+    let source_start_ms = clip.trim_start_ms + (start_ms - clip.start_time_ms);
+    Some(StreamCopyPlan {
+        source_path: clip.file_path.clone(),
+        source_start_ms,
+        source_end_ms: source_start_ms + (end_ms - start_ms),
+    })
+}
+
+/// 소스 스트림을 그대로 복사해도 일반 렌더링과 같은 결과인지
+/// - 코덱/해상도/fps가 Export 설정과 같고 소스 오디오는 출력 컨테이너에 복사 가능
+/// - 복사 구간이 소스 길이 안 (넘어서면 클립 end_behavior로 만든 프레임이 필요)
+/// - SDR 8bit 소스 + 8bit 출력 (HDR은 톤 매핑, 10bit 소스/출력은 비트 깊이 변환으로 결과가 달라짐)
+pub fn source_matches(plan: &StreamCopyPlan, source: &CopySource, config: &ExportConfig, container: Container) -> bool {
+    let codec = VideoCodec::from_u32(config.codec);
+    source.codec == Some(codec)
+        && container.supports_video(codec)
+        && (source.width, source.height) == (config.width, config.height)
+        && (source.fps - config.fps).abs() < 0.01
+        && (!source.has_audio || source.audio_copyable)
+        && plan.source_end_ms <= source.duration_ms
+        && !source.hdr
+        && source.bit_depth <= 8
+        && source.rotation_deg == 0
+        && !source.has_alpha
+        && !config.high_bit_depth_container
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeline::{Interpolation, Keyframe, KeyframeProperty};
+
+    /// 1000ms부터 10초 클립 (원본 2000ms부터 트림)
+    fn timeline_with_clip() -> (Timeline, u64) {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        let clip_id = timeline.add_video_clip(track_id, PathBuf::from("a.mp4"), 1000, 10_000).unwrap();
+        clip_mut(&mut timeline, clip_id).trim_start_ms = 2000;
+        (timeline, clip_id)
+    }
+
+    fn clip_mut(timeline: &mut Timeline, clip_id: u64) -> &mut VideoClip {
+        timeline.video_tracks[0].get_clip_by_id_mut(clip_id).unwrap()
+    }
+
+    #[test]
+    fn test_plan_single_untouched_clip() {
+        let (timeline, _) = timeline_with_clip();
+
+        let plan = plan_stream_copy(&timeline, (3000, 5000)).unwrap();
+        assert_eq!(plan.source_path, PathBuf::from("a.mp4"));
+        assert_eq!((plan.source_start_ms, plan.source_end_ms), (4000, 6000));
+
+        // 클립 바깥(빈 구간)이 포함되면 복사 불가
+        assert!(plan_stream_copy(&timeline, (0, 5000)).is_none());
+    }
+
+    #[test]
+    fn test_plan_rejects_modified_or_layered() {
+        let (mut timeline, clip_id) = timeline_with_clip();
+        clip_mut(&mut timeline, clip_id).keyframes
+            .set(KeyframeProperty::Opacity, Keyframe::new(0, 0.5, Interpolation::Linear));
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+
+        let (mut timeline, clip_id) = timeline_with_clip();
+        clip_mut(&mut timeline, clip_id).speed = 2.0;
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+
+        // 색보정 이펙트 (재인코딩 필요)
+        let (mut timeline, clip_id) = timeline_with_clip();
+        clip_mut(&mut timeline, clip_id).effects.saturation = -1.0;
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+
+        // 트랙/마스터 그레이딩
+        let (mut timeline, _) = timeline_with_clip();
+        timeline.video_tracks[0].lut_path = Some(PathBuf::from("look.cube"));
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+        let (mut timeline, _) = timeline_with_clip();
+        timeline.master_effects.contrast = 0.2;
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+
+        // Fit 이외 배치
+        let (mut timeline, clip_id) = timeline_with_clip();
+        clip_mut(&mut timeline, clip_id).fit_mode = FitMode::Fill;
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+
+        // 소스 오디오 끈 클립 (복사하면 소스 오디오가 포함됨)
+        let (mut timeline, clip_id) = timeline_with_clip();
+        clip_mut(&mut timeline, clip_id).use_source_audio = false;
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+
+        // 다른 트랙에 겹치는 클립 (오버레이)
+        let (mut timeline, _) = timeline_with_clip();
+        let overlay_track = timeline.add_video_track();
+        timeline.add_video_clip(overlay_track, PathBuf::from("b.mp4"), 4000, 500).unwrap();
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+        assert!(plan_stream_copy(&timeline, (5000, 8000)).is_some());
+
+        // 오디오 트랙 클립 (믹싱 필요)
+        let (mut timeline, _) = timeline_with_clip();
+        let audio_track = timeline.add_audio_track();
+        timeline.add_audio_clip(audio_track, PathBuf::from("music.mp3"), 0, 4000).unwrap();
+        assert!(plan_stream_copy(&timeline, (3000, 5000)).is_none());
+        assert!(plan_stream_copy(&timeline, (4000, 5000)).is_some());
+
+        // 솔로 트랙 (비디오 클립 오디오가 빠지므로 소스 오디오 복사 불가)
+        timeline.audio_tracks[0].solo = true;
+        assert!(plan_stream_copy(&timeline, (4000, 5000)).is_none());
+    }
+
+    /// 1080p30 H.264 SDR 8bit, 10초 소스
+    fn sdr_source() -> CopySource {
+        CopySource {
+            codec: Some(VideoCodec::H264),
+            width: 1920,
+            height: 1080,
+            fps: 30.0,
+            bit_depth: 8,
+            hdr: false,
+            rotation_deg: 0,
+            has_alpha: false,
+            duration_ms: 10_000,
+            has_audio: true,
+            audio_copyable: true,
+        }
+    }
+
+    fn plan(source_start_ms: i64, source_end_ms: i64) -> StreamCopyPlan {
+        StreamCopyPlan { source_path: PathBuf::from("a.mp4"), source_start_ms, source_end_ms }
+    }
+
+    #[test]
+    fn test_source_matches() {
+        let config = ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23);
+        assert!(source_matches(&plan(2000, 6000), &sdr_source(), &config, Container::Mp4));
+
+        let mut source = sdr_source();
+        source.width = 1280;
+        assert!(!source_matches(&plan(2000, 6000), &source, &config, Container::Mp4));
+    }
+
+    #[test]
+    fn test_source_matches_rejects_past_source_end() {
+        // 소스 끝 이후 구간은 end_behavior(정지/반복/검정) 프레임이 필요
+        let config = ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23);
+        assert!(source_matches(&plan(8000, 10_000), &sdr_source(), &config, Container::Mp4));
+        assert!(!source_matches(&plan(8000, 12_000), &sdr_source(), &config, Container::Mp4));
+    }
+
+    #[test]
+    fn test_source_matches_rejects_hdr_source() {
+        let config = ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23);
+        let source = CopySource { hdr: true, ..sdr_source() };
+        assert!(!source_matches(&plan(2000, 6000), &source, &config, Container::Mp4));
+    }
+
+    #[test]
+    fn test_source_matches_rejects_high_bit_depth() {
+        // 10bit 소스 (일반 렌더링은 8bit로 변환)
+        let config = ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23);
+        let source = CopySource { bit_depth: 10, ..sdr_source() };
+        assert!(!source_matches(&plan(2000, 6000), &source, &config, Container::Mp4));
+
+        // 10bit 컨테이너 출력 (8bit 값을 10bit 포맷으로 재인코딩)
+        let config = ExportConfig { high_bit_depth_container: true, ..config };
+        assert!(!source_matches(&plan(2000, 6000), &sdr_source(), &config, Container::Mp4));
+    }
+
+    #[test]
+    fn test_source_matches_rejects_rotation() {
+        // 세로 촬영 소스 (렌더링은 회전을 적용해 픽셀로 출력)
+        let config = ExportConfig::new("out.mp4".to_string(), 1920, 1080, 30.0, 23);
+        let source = CopySource { rotation_deg: 90, ..sdr_source() };
+        assert!(!source_matches(&plan(2000, 6000), &source, &config, Container::Mp4));
+    }
+
+    #[test]
+    fn test_source_matches_rejects_alpha() {
+        // 알파 소스 (렌더링은 배경 위에 합성)
+        let config = ExportConfig::new("out.mov".to_string(), 1920, 1080, 30.0, 23);
+        let source = CopySource { has_alpha: true, ..sdr_source() };
+        assert!(!source_matches(&plan(2000, 6000), &source, &config, Container::Mov));
+    }
+}
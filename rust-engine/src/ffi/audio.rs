@@ -1,9 +1,28 @@
 // 오디오 파형 피크 추출 FFI
 // FFmpeg으로 오디오 디코딩 → f32 PCM → 블록별 최대 절대값 계산
+// 스트리밍 세션: 백그라운드 스레드에서 계산된 피크를 poll로 조금씩 전달 (취소 가능)
+// 디스크 캐시 (utils::peak_cache): 같은 파일/samples_per_peak는 재디코딩 생략
+// 피크 피라미드 (utils::peak_pyramid): 한 번 디코딩으로 모든 줌 레벨 구간 조회
+//
+// 파형 API 선택:
+// - 타임라인/클립 파형 (줌/스크롤): 피크 피라미드 audio_peak_pyramid_* 권장 (디코딩 1회 + 캐시, 조회는 메모리만)
+// - 가져오기 직후 점진 표시: 스트리밍 세션 audio_peaks_session_* (완료 후 피라미드로 전환)
+// - 믹서 기준(48kHz) 클립 트림/속도 반영 구간: 파형 세션 audio_peak_session_* (요청마다 디코딩)
+// - extract_audio_peaks*: 파일 전체 일괄 추출 (호환용)
+// 타임라인 믹스 라우드니스 측정
 
-use crate::ffi::types::ErrorCode;
+use crate::audio::{loudness, sync};
+use crate::encoding::audio_decoder::{select_audio_stream, AudioDecoder};
+use crate::ffi::handle::TIMELINES;
+use crate::ffi::types::{report_error, ErrorCode};
+use crate::ffmpeg::network;
+use crate::utils::peak_cache::{self, CachedPeaks};
+use crate::utils::peak_pyramid::{PeakPyramid, BASE_SAMPLES_PER_PEAK};
+use crate::utils::log::log_warn;
 use std::ffi::{c_char, CStr};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use ffmpeg_next as ffmpeg;
 
@@ -57,7 +76,7 @@ pub extern "C" fn extract_audio_peaks(
         let file_path_str = match c_str.to_str() {
             Ok(s) => s,
             Err(e) => {
-                eprintln!("❌ extract_audio_peaks: Invalid UTF-8: {}", e);
+                report_error(format!("extract_audio_peaks: Invalid UTF-8: {}", e));
                 return ErrorCode::InvalidParam as i32;
             }
         };
@@ -65,7 +84,7 @@ pub extern "C" fn extract_audio_peaks(
         let path = PathBuf::from(file_path_str);
 
         // 피크 추출 실행
-        match extract_peaks_internal(&path, samples_per_peak) {
+        match extract_peaks_internal(&path, None, samples_per_peak) {
             Ok(result) => {
                 *out_channels = result.channels;
                 *out_sample_rate = result.sample_rate;
@@ -79,13 +98,112 @@ pub extern "C" fn extract_audio_peaks(
                 ErrorCode::Success as i32
             }
             Err(e) => {
-                eprintln!("❌ extract_audio_peaks: {}", e);
+                report_error(format!("extract_audio_peaks: {}", e));
                 ErrorCode::Ffmpeg as i32
             }
         }
     }
 }
 
+/// 캐시를 사용하는 오디오 피크 추출 (프로젝트 재오픈 시 파형 재디코딩 생략)
+///
+/// 캐시가 있으면 즉시 반환, 없으면 extract_audio_peaks와 동일하게 디코딩 후 캐시 저장.
+/// 캐시 저장 실패(읽기 전용 폴더 등)는 무시하고 결과만 반환한다.
+///
+/// # 파라미터
+/// - cache_dir: UTF-8 캐시 디렉토리 (NULL이면 미디어 파일 옆에 저장)
+/// - 나머지: extract_audio_peaks와 동일
+#[no_mangle]
+pub extern "C" fn extract_audio_peaks_cached(
+    file_path: *const c_char,
+    samples_per_peak: u32,
+    cache_dir: *const c_char,
+    out_peaks: *mut *mut f32,
+    out_peak_count: *mut u32,
+    out_channels: *mut u32,
+    out_sample_rate: *mut u32,
+    out_duration_ms: *mut i64,
+) -> i32 {
+    extract_audio_peaks_stream(
+        file_path, -1, samples_per_peak, cache_dir,
+        out_peaks, out_peak_count, out_channels, out_sample_rate, out_duration_ms,
+    )
+}
+
+/// 지정한 오디오 스트림의 피크 추출 (캐시 사용, 다국어/멀티트랙 파일의 오디오 클립 파형)
+///
+/// # 파라미터
+/// - stream_index: 컨테이너 스트림 번호 (media_probe의 streams[].index), -1=기본 오디오 스트림
+/// - 나머지: extract_audio_peaks_cached와 동일 (캐시는 스트림별로 저장)
+#[no_mangle]
+pub extern "C" fn extract_audio_peaks_stream(
+    file_path: *const c_char,
+    stream_index: i32,
+    samples_per_peak: u32,
+    cache_dir: *const c_char,
+    out_peaks: *mut *mut f32,
+    out_peak_count: *mut u32,
+    out_channels: *mut u32,
+    out_sample_rate: *mut u32,
+    out_duration_ms: *mut i64,
+) -> i32 {
+    if file_path.is_null() || out_peaks.is_null() || out_peak_count.is_null()
+        || out_channels.is_null() || out_sample_rate.is_null() || out_duration_ms.is_null()
+    {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let stream_index = match ffi_stream_index(stream_index) {
+        Some(stream_index) if samples_per_peak > 0 => stream_index,
+        _ => return ErrorCode::InvalidParam as i32,
+    };
+
+    unsafe {
+        *out_peaks = std::ptr::null_mut();
+        *out_peak_count = 0;
+        *out_channels = 0;
+        *out_sample_rate = 0;
+        *out_duration_ms = 0;
+
+        let path = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => PathBuf::from(s),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let cache_dir = if cache_dir.is_null() {
+            None
+        } else {
+            match CStr::from_ptr(cache_dir).to_str() {
+                Ok(s) => Some(PathBuf::from(s)),
+                Err(_) => return ErrorCode::InvalidParam as i32,
+            }
+        };
+
+        let result = match peak_cache::load(&path, stream_index, samples_per_peak, cache_dir.as_deref()) {
+            Some(cached) => cached,
+            None => match extract_peaks_internal(&path, stream_index, samples_per_peak) {
+                Ok(result) => {
+                    if let Err(e) = peak_cache::store(&path, stream_index, samples_per_peak, &result, cache_dir.as_deref()) {
+                        log_warn!("extract_audio_peaks_cached: cache write failed: {}", e);
+                    }
+                    result
+                }
+                Err(e) => {
+                    report_error(format!("extract_audio_peaks_cached: {}", e));
+                    return ErrorCode::Ffmpeg as i32;
+                }
+            },
+        };
+
+        *out_channels = result.channels;
+        *out_sample_rate = result.sample_rate;
+        *out_duration_ms = result.duration_ms;
+        *out_peak_count = result.peaks.len() as u32;
+        *out_peaks = Box::into_raw(result.peaks.into_boxed_slice()) as *mut f32;
+    }
+
+    ErrorCode::Success as i32
+}
+
 /// 피크 데이터 메모리 해제 (C#에서 호출)
 #[no_mangle]
 pub extern "C" fn free_audio_peaks(peaks: *mut f32, count: u32) -> i32 {
@@ -101,100 +219,127 @@ pub extern "C" fn free_audio_peaks(peaks: *mut f32, count: u32) -> i32 {
     ErrorCode::Success as i32
 }
 
-/// 내부 피크 추출 결과
-struct AudioPeakResult {
-    peaks: Vec<f32>,
-    channels: u32,
-    sample_rate: u32,
-    duration_ms: i64,
+/// FFI 스트림 번호 변환 (-1 = 기본 오디오 스트림, 그 외 음수는 None = 잘못된 값)
+fn ffi_stream_index(stream_index: i32) -> Option<Option<usize>> {
+    match stream_index {
+        -1 => Some(None),
+        index if index >= 0 => Some(Some(index as usize)),
+        _ => None,
+    }
 }
 
 /// FFmpeg으로 오디오 디코딩 + 피크 계산 (내부 함수)
 fn extract_peaks_internal(
     file_path: &PathBuf,
+    stream_index: Option<usize>,
     samples_per_peak: u32,
-) -> Result<AudioPeakResult, String> {
-    // FFmpeg 초기화
-    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
-
-    // 파일 열기
-    let mut input_ctx = ffmpeg::format::input(file_path)
-        .map_err(|e| format!("Failed to open file: {}", e))?;
-
-    // 오디오 스트림 찾기
-    let audio_stream = input_ctx
-        .streams()
-        .best(ffmpeg::media::Type::Audio)
-        .ok_or("No audio stream found")?;
-
-    let audio_stream_index = audio_stream.index();
-    let codec_params = audio_stream.parameters();
-
-    // Duration 계산
-    let duration_ms = if audio_stream.duration() > 0 {
-        let tb = audio_stream.time_base();
-        (audio_stream.duration() * i64::from(tb.numerator()) * 1000)
-            / i64::from(tb.denominator())
-    } else if input_ctx.duration() > 0 {
-        input_ctx.duration() / 1000
-    } else {
-        0
-    };
+) -> Result<CachedPeaks, String> {
+    let mut source = PeakSource::open(file_path, stream_index)?;
+    let mut accumulator = PeakAccumulator::new(samples_per_peak);
+    while source.decode_next(&mut accumulator)? {}
 
-    // 오디오 디코더 생성
-    let mut context = ffmpeg::codec::context::Context::from_parameters(codec_params)
-        .map_err(|e| format!("Failed to create audio context: {}", e))?;
+    Ok(CachedPeaks {
+        peaks: accumulator.finish(),
+        channels: source.channels,
+        sample_rate: source.sample_rate,
+        duration_ms: source.duration_ms,
+    })
+}
 
-    // 멀티스레딩
-    if let Ok(parallelism) = std::thread::available_parallelism() {
-        context.set_threading(ffmpeg::threading::Config {
-            kind: ffmpeg::threading::Type::Frame,
-            count: parallelism.get(),
-        });
-    }
+/// 원본 포맷(샘플레이트/채널) 피크 디코딩 소스 — extract_audio_peaks와 스트리밍 세션 공용
+struct PeakSource {
+    input_ctx: ffmpeg::format::context::Input,
+    audio_stream_index: usize,
+    decoder: ffmpeg::codec::decoder::Audio,
+    resampler: ffmpeg::software::resampling::Context,
+    channels: u32,
+    sample_rate: u32,
+    duration_ms: i64,
+}
 
-    let mut decoder = context
-        .decoder()
-        .audio()
-        .map_err(|e| format!("Failed to get audio decoder: {}", e))?;
+impl PeakSource {
+    /// 파일 열기 + 오디오 디코더/리샘플러(f32 packed) 준비 (stream_index: None이면 기본 오디오 스트림)
+    fn open(file_path: &PathBuf, stream_index: Option<usize>) -> Result<Self, String> {
+        // FFmpeg 초기화
+        ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
 
-    let sample_rate = decoder.rate();
-    let channels = decoder.channels() as u32;
+        // 파일 열기
+        let input_ctx = network::open_input(file_path)
+            .map_err(|e| format!("Failed to open file: {}", e))?;
 
-    // 리샘플러: 원본 포맷 → f32 planar
-    let mut resampler = ffmpeg::software::resampling::Context::get(
-        decoder.format(),
-        decoder.channel_layout(),
-        decoder.rate(),
-        ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
-        decoder.channel_layout(),
-        decoder.rate(),
-    )
-    .map_err(|e| format!("Failed to create resampler: {}", e))?;
+        // 오디오 스트림 찾기
+        let audio_stream = select_audio_stream(&input_ctx, stream_index)?;
 
-    // 피크 계산 버퍼
-    let mut peaks: Vec<f32> = Vec::new();
-    let mut block_max: f32 = 0.0;
-    let mut block_sample_count: u32 = 0;
+        let audio_stream_index = audio_stream.index();
+        let codec_params = audio_stream.parameters();
 
-    // 패킷 처리
-    for (stream, packet) in input_ctx.packets() {
-        if stream.index() != audio_stream_index {
-            continue;
+        // Duration 계산
+        let duration_ms = if audio_stream.duration() > 0 {
+            let tb = audio_stream.time_base();
+            (audio_stream.duration() * i64::from(tb.numerator()) * 1000)
+                / i64::from(tb.denominator())
+        } else if input_ctx.duration() > 0 {
+            input_ctx.duration() / 1000
+        } else {
+            0
+        };
+
+        // 오디오 디코더 생성
+        let mut context = ffmpeg::codec::context::Context::from_parameters(codec_params)
+            .map_err(|e| format!("Failed to create audio context: {}", e))?;
+
+        // 멀티스레딩
+        if let Ok(parallelism) = std::thread::available_parallelism() {
+            context.set_threading(ffmpeg::threading::Config {
+                kind: ffmpeg::threading::Type::Frame,
+                count: parallelism.get(),
+            });
         }
 
-        if decoder.send_packet(&packet).is_err() {
-            continue;
+        let decoder = context
+            .decoder()
+            .audio()
+            .map_err(|e| format!("Failed to get audio decoder: {}", e))?;
+
+        let sample_rate = decoder.rate();
+        let channels = decoder.channels() as u32;
+
+        // 리샘플러: 원본 포맷 → f32 packed
+        let resampler = ffmpeg::software::resampling::Context::get(
+            decoder.format(),
+            decoder.channel_layout(),
+            decoder.rate(),
+            ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
+            decoder.channel_layout(),
+            decoder.rate(),
+        )
+        .map_err(|e| format!("Failed to create resampler: {}", e))?;
+
+        Ok(Self { input_ctx, audio_stream_index, decoder, resampler, channels, sample_rate, duration_ms })
+    }
+
+    /// 다음 패킷 하나 디코딩 → 피크 누산 (파일 끝이면 false)
+    /// 손상된 패킷은 건너뛰고, 디코더/리샘플러 오류는 에러
+    fn decode_next(&mut self, accumulator: &mut PeakAccumulator) -> Result<bool, String> {
+        let packet = match self.input_ctx.packets().next() {
+            Some((stream, packet)) if stream.index() == self.audio_stream_index => packet,
+            Some(_) => return Ok(true),
+            None => return Ok(false),
+        };
+
+        match self.decoder.send_packet(&packet) {
+            Ok(()) => {}
+            Err(ffmpeg::Error::InvalidData) => return Ok(true),
+            Err(e) => return Err(format!("Audio decode failed: {}", e)),
         }
 
         // 디코딩된 프레임 수신
         let mut decoded_frame = ffmpeg::frame::Audio::empty();
-        while decoder.receive_frame(&mut decoded_frame).is_ok() {
+        while self.decoder.receive_frame(&mut decoded_frame).is_ok() {
             // 리샘플링 (f32 packed)
             let mut resampled = ffmpeg::frame::Audio::empty();
-            if resampler.run(&decoded_frame, &mut resampled).is_err() {
-                continue;
-            }
+            self.resampler.run(&decoded_frame, &mut resampled)
+                .map_err(|e| format!("Audio resample failed: {}", e))?;
 
             let data = resampled.data(0);
             let sample_count = resampled.samples();
@@ -203,42 +348,789 @@ fn extract_peaks_internal(
             let f32_slice = unsafe {
                 std::slice::from_raw_parts(
                     data.as_ptr() as *const f32,
-                    sample_count * channels as usize,
+                    sample_count * self.channels as usize,
                 )
             };
 
             // 채널 믹스다운 + 블록별 피크 계산
-            for chunk in f32_slice.chunks(channels as usize) {
-                // 모노 믹스다운: 모든 채널의 max(abs)
-                let sample_abs = chunk.iter()
-                    .map(|s| s.abs())
-                    .fold(0.0f32, f32::max);
-
-                if sample_abs > block_max {
-                    block_max = sample_abs;
+            accumulator.push_interleaved(f32_slice, self.channels as usize);
+        }
+        Ok(true)
+    }
+}
+
+/// 블록별 피크 누산기 (interleaved PCM → samples_per_peak 단위 max(abs))
+struct PeakAccumulator {
+    samples_per_peak: u32,
+    peaks: Vec<f32>,
+    block_max: f32,
+    block_sample_count: u32,
+}
+
+impl PeakAccumulator {
+    fn new(samples_per_peak: u32) -> Self {
+        Self {
+            samples_per_peak,
+            peaks: Vec::new(),
+            block_max: 0.0,
+            block_sample_count: 0,
+        }
+    }
+
+    /// interleaved 샘플 추가 (채널 믹스다운: 모든 채널의 max(abs))
+    fn push_interleaved(&mut self, samples: &[f32], channels: usize) {
+        for chunk in samples.chunks(channels.max(1)) {
+            let sample_abs = chunk.iter()
+                .map(|s| s.abs())
+                .fold(0.0f32, f32::max);
+
+            if sample_abs > self.block_max {
+                self.block_max = sample_abs;
+            }
+
+            self.block_sample_count += 1;
+
+            if self.block_sample_count >= self.samples_per_peak {
+                // 피크 값 클램핑 (0.0~1.0)
+                self.peaks.push(self.block_max.min(1.0));
+                self.block_max = 0.0;
+                self.block_sample_count = 0;
+            }
+        }
+    }
+
+    /// 지금까지 완성된 피크 꺼내기 (진행 중인 블록은 유지)
+    fn take_peaks(&mut self) -> Vec<f32> {
+        std::mem::take(&mut self.peaks)
+    }
+
+    /// 마지막 블록 처리 후 피크 배열 반환
+    fn finish(mut self) -> Vec<f32> {
+        if self.block_sample_count > 0 {
+            self.peaks.push(self.block_max.min(1.0));
+        }
+        self.peaks
+    }
+}
+
+// ==================== 파형 세션 API ====================
+
+/// 세션 디코딩 청크 크기 (ms) — 긴 구간도 메모리 사용량 일정
+const PEAK_SESSION_CHUNK_MS: i64 = 1000;
+
+/// 오디오 파형 세션 (AudioDecoder를 유지하며 구간/줌 레벨별 피크 추출)
+/// ThumbnailSession과 동일한 구조: 파일 Open 1회, 요청마다 필요한 구간만 디코딩
+/// 출력 PCM은 AudioDecoder 기준 48kHz stereo → samples_per_peak도 48kHz 기준
+pub struct AudioPeakSession {
+    decoder: AudioDecoder,
+}
+
+impl AudioPeakSession {
+    /// [start_ms, end_ms) 구간의 피크 추출
+    fn extract_range(&mut self, start_ms: i64, end_ms: i64, samples_per_peak: u32) -> Result<Vec<f32>, String> {
+        let duration_ms = self.decoder.duration_ms();
+        let end_ms = if duration_ms > 0 { end_ms.min(duration_ms) } else { end_ms };
+        let channels = self.decoder.channels() as usize;
+
+        let mut accumulator = PeakAccumulator::new(samples_per_peak);
+        let mut pos_ms = start_ms.max(0);
+
+        // 청크 단위 순차 디코딩 (첫 청크만 seek, 이후 AudioDecoder의 순차 경로)
+        while pos_ms < end_ms {
+            let chunk_ms = PEAK_SESSION_CHUNK_MS.min(end_ms - pos_ms);
+            let samples = self.decoder.decode_range(pos_ms, chunk_ms as f64)?;
+            accumulator.push_interleaved(&samples, channels);
+            pos_ms += chunk_ms;
+        }
+
+        Ok(accumulator.finish())
+    }
+}
+
+/// 파형 세션 생성
+/// - out_sample_rate/out_channels: 세션 출력 PCM 포맷 (48000Hz, 2ch)
+/// - out_duration_ms: 오디오 총 길이 (ms)
+#[no_mangle]
+pub extern "C" fn audio_peak_session_create(
+    file_path: *const c_char,
+    out_session: *mut *mut AudioPeakSession,
+    out_duration_ms: *mut i64,
+    out_sample_rate: *mut u32,
+    out_channels: *mut u32,
+) -> i32 {
+    audio_peak_session_create_stream(file_path, -1, out_session, out_duration_ms, out_sample_rate, out_channels)
+}
+
+/// 지정한 오디오 스트림의 파형 세션 생성
+/// - stream_index: 컨테이너 스트림 번호, -1=기본 오디오 스트림
+/// - 나머지: audio_peak_session_create와 동일
+#[no_mangle]
+pub extern "C" fn audio_peak_session_create_stream(
+    file_path: *const c_char,
+    stream_index: i32,
+    out_session: *mut *mut AudioPeakSession,
+    out_duration_ms: *mut i64,
+    out_sample_rate: *mut u32,
+    out_channels: *mut u32,
+) -> i32 {
+    if file_path.is_null() || out_session.is_null() || out_duration_ms.is_null()
+        || out_sample_rate.is_null() || out_channels.is_null()
+    {
+        return ErrorCode::NullPointer as i32;
+    }
+    let stream_index = match ffi_stream_index(stream_index) {
+        Some(stream_index) => stream_index,
+        None => return ErrorCode::InvalidParam as i32,
+    };
+
+    unsafe {
+        let c_str = CStr::from_ptr(file_path);
+        let file_path_str = match c_str.to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let path = PathBuf::from(file_path_str);
+
+        let decoder = match AudioDecoder::open_stream(&path, stream_index) {
+            Ok(d) => d,
+            Err(e) => {
+                report_error(format!("audio_peak_session_create: {}", e));
+                return ErrorCode::Ffmpeg as i32;
+            }
+        };
+
+        *out_duration_ms = decoder.duration_ms();
+        *out_sample_rate = decoder.sample_rate();
+        *out_channels = decoder.channels();
+
+        let session = Box::new(AudioPeakSession { decoder });
+        *out_session = Box::into_raw(session);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 세션에서 [start_ms, end_ms) 구간의 피크 추출
+/// - samples_per_peak: 줌 레벨 (48kHz 기준 샘플 수)
+/// - out_peaks: 피크 배열 (caller가 free_audio_peaks로 해제)
+#[no_mangle]
+pub extern "C" fn audio_peak_session_extract(
+    session: *mut AudioPeakSession,
+    start_ms: i64,
+    end_ms: i64,
+    samples_per_peak: u32,
+    out_peaks: *mut *mut f32,
+    out_peak_count: *mut u32,
+) -> i32 {
+    if session.is_null() || out_peaks.is_null() || out_peak_count.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    if samples_per_peak == 0 || end_ms <= start_ms {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        *out_peaks = std::ptr::null_mut();
+        *out_peak_count = 0;
+
+        let session = &mut *session;
+
+        match session.extract_range(start_ms, end_ms, samples_per_peak) {
+            Ok(peaks) => {
+                *out_peak_count = peaks.len() as u32;
+                let peaks_box = peaks.into_boxed_slice();
+                *out_peaks = Box::into_raw(peaks_box) as *mut f32;
+                ErrorCode::Success as i32
+            }
+            Err(e) => {
+                report_error(format!("audio_peak_session_extract: {}ms~{}ms: {}", start_ms, end_ms, e));
+                ErrorCode::Ffmpeg as i32
+            }
+        }
+    }
+}
+
+/// 오디오 클립 뷰용 피크 추출 (클립 트림/속도 반영 — 믹서와 같은 원본 구간)
+/// - session: 클립 원본 파일/스트림으로 만든 세션
+/// - start_ms/end_ms: 보이는 타임라인 구간 (클립 범위로 잘림)
+/// - samples_per_peak: 타임라인 기준 48kHz 샘플 수 (속도 변경 클립은 원본 샘플 수로 환산)
+/// - out_peaks: 피크 배열 (caller가 free_audio_peaks로 해제, 구간이 클립 밖이면 NULL/0)
+#[no_mangle]
+pub extern "C" fn audio_peak_session_extract_clip(
+    session: *mut AudioPeakSession,
+    timeline: *mut std::ffi::c_void,
+    clip_id: u64,
+    start_ms: i64,
+    end_ms: i64,
+    samples_per_peak: u32,
+    out_peaks: *mut *mut f32,
+    out_peak_count: *mut u32,
+) -> i32 {
+    if session.is_null() || timeline.is_null() || out_peaks.is_null() || out_peak_count.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    if samples_per_peak == 0 || end_ms <= start_ms {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        *out_peaks = std::ptr::null_mut();
+        *out_peak_count = 0;
+
+        let timeline = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        // 클립 복사 후 lock 해제 (디코딩 중 타임라인 편집 차단 방지)
+        let clip = match timeline.lock() {
+            Ok(tl) => tl.audio_tracks.iter()
+                .flat_map(|track| &track.clips)
+                .find(|clip| clip.id == clip_id)
+                .cloned(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let clip = match clip {
+            Some(clip) => clip,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let (source_start, source_end) = match clip.source_range(start_ms, end_ms) {
+            Some(range) => range,
+            None => return ErrorCode::Success as i32,
+        };
+        let source_samples_per_peak = (samples_per_peak as f64 * clip.speed).round().max(1.0) as u32;
+
+        let session = &mut *session;
+        match session.extract_range(source_start, source_end, source_samples_per_peak) {
+            Ok(peaks) => {
+                if !peaks.is_empty() {
+                    *out_peak_count = peaks.len() as u32;
+                    *out_peaks = Box::into_raw(peaks.into_boxed_slice()) as *mut f32;
                 }
+                ErrorCode::Success as i32
+            }
+            Err(e) => {
+                report_error(format!("audio_peak_session_extract_clip: clip {}: {}", clip_id, e));
+                ErrorCode::Ffmpeg as i32
+            }
+        }
+    }
+}
 
-                block_sample_count += 1;
+/// 파형 세션 파괴
+#[no_mangle]
+pub extern "C" fn audio_peak_session_destroy(session: *mut AudioPeakSession) -> i32 {
+    if session.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(session);
+    }
 
-                if block_sample_count >= samples_per_peak {
-                    // 피크 값 클램핑 (0.0~1.0)
-                    peaks.push(block_max.min(1.0));
-                    block_max = 0.0;
-                    block_sample_count = 0;
+    ErrorCode::Success as i32
+}
+
+// ==================== 스트리밍 피크 추출 API ====================
+
+/// 스트리밍 피크 세션 상태 (audio_peaks_session_poll의 out_status)
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PeakStreamStatus {
+    #[default]
+    Running = 0,
+    /// 모든 피크 계산 완료 (파형 전체)
+    Completed = 1,
+    /// audio_peaks_session_cancel로 중단 (파형 앞부분만)
+    Cancelled = 2,
+    /// 디코딩 실패로 중단 (파형 앞부분만, 메시지는 engine_get_last_error)
+    Failed = 3,
+}
+
+/// 스트리밍 피크 세션 공유 상태 (워커 → poll)
+#[derive(Default)]
+struct PeakStreamState {
+    /// 아직 poll로 가져가지 않은 피크
+    pending: Vec<f32>,
+    status: PeakStreamStatus,
+    /// Failed 에러 메시지 (poll에서 호출 스레드 last error로 보고 후 비움)
+    error: Option<String>,
+}
+
+/// 스트리밍 피크 세션 (extract_audio_peaks와 같은 결과를 백그라운드 스레드에서 조금씩 전달)
+/// 긴 파일도 import UI를 막지 않고 계산된 앞부분부터 파형 표시
+pub struct AudioPeaksStreamSession {
+    state: Arc<Mutex<PeakStreamState>>,
+    cancelled: Arc<AtomicBool>,
+    worker: Option<std::thread::JoinHandle<()>>,
+}
+
+impl AudioPeaksStreamSession {
+    fn start(source: PeakSource, samples_per_peak: u32) -> Self {
+        let state = Arc::new(Mutex::new(PeakStreamState::default()));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_state = state.clone();
+        let worker_cancelled = cancelled.clone();
+        let worker = std::thread::spawn(move || {
+            run_peak_stream(source, samples_per_peak, &worker_state, &worker_cancelled);
+        });
+        Self { state, cancelled, worker: Some(worker) }
+    }
+}
+
+impl Drop for AudioPeaksStreamSession {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// 워커: 패킷 단위 디코딩, 완성된 피크를 pending에 추가 (취소 시 즉시 종료)
+fn run_peak_stream(
+    mut source: PeakSource,
+    samples_per_peak: u32,
+    state: &Mutex<PeakStreamState>,
+    cancelled: &AtomicBool,
+) {
+    let mut accumulator = PeakAccumulator::new(samples_per_peak);
+    let mut result = Ok(true);
+    while matches!(result, Ok(true)) && !cancelled.load(Ordering::Relaxed) {
+        result = source.decode_next(&mut accumulator);
+        let peaks = accumulator.take_peaks();
+        if peaks.is_empty() {
+            continue;
+        }
+        match state.lock() {
+            Ok(mut state) => state.pending.extend(peaks),
+            Err(_) => return,
+        }
+    }
+
+    if let Ok(mut state) = state.lock() {
+        state.status = match result {
+            Ok(false) => {
+                // 마지막 미완성 블록
+                state.pending.extend(accumulator.finish());
+                PeakStreamStatus::Completed
+            }
+            Ok(true) => PeakStreamStatus::Cancelled,
+            Err(e) => {
+                state.error = Some(e);
+                PeakStreamStatus::Failed
+            }
+        };
+    }
+}
+
+/// 스트리밍 피크 세션 생성 (파일 열기까지만 동기, 디코딩은 백그라운드)
+/// - samples_per_peak / out_channels / out_sample_rate / out_duration_ms: extract_audio_peaks와 동일 (원본 샘플레이트 기준)
+/// - 이후 audio_peaks_session_poll로 피크를 순서대로 받고, audio_peaks_session_destroy로 해제
+#[no_mangle]
+pub extern "C" fn audio_peaks_session_create(
+    file_path: *const c_char,
+    samples_per_peak: u32,
+    out_session: *mut *mut AudioPeaksStreamSession,
+    out_channels: *mut u32,
+    out_sample_rate: *mut u32,
+    out_duration_ms: *mut i64,
+) -> i32 {
+    audio_peaks_session_create_stream(
+        file_path, -1, samples_per_peak, out_session, out_channels, out_sample_rate, out_duration_ms,
+    )
+}
+
+/// 지정한 오디오 스트림의 스트리밍 피크 세션 생성
+/// - stream_index: 컨테이너 스트림 번호, -1=기본 오디오 스트림
+/// - 나머지: audio_peaks_session_create와 동일
+#[no_mangle]
+pub extern "C" fn audio_peaks_session_create_stream(
+    file_path: *const c_char,
+    stream_index: i32,
+    samples_per_peak: u32,
+    out_session: *mut *mut AudioPeaksStreamSession,
+    out_channels: *mut u32,
+    out_sample_rate: *mut u32,
+    out_duration_ms: *mut i64,
+) -> i32 {
+    if file_path.is_null() || out_session.is_null() || out_channels.is_null()
+        || out_sample_rate.is_null() || out_duration_ms.is_null()
+    {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let stream_index = match ffi_stream_index(stream_index) {
+        Some(stream_index) if samples_per_peak > 0 => stream_index,
+        _ => return ErrorCode::InvalidParam as i32,
+    };
+
+    unsafe {
+        *out_session = std::ptr::null_mut();
+
+        let path = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => PathBuf::from(s),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let source = match PeakSource::open(&path, stream_index) {
+            Ok(source) => source,
+            Err(e) => {
+                report_error(format!("audio_peaks_session_create: {}", e));
+                return ErrorCode::Ffmpeg as i32;
+            }
+        };
+
+        *out_channels = source.channels;
+        *out_sample_rate = source.sample_rate;
+        *out_duration_ms = source.duration_ms;
+
+        let session = Box::new(AudioPeaksStreamSession::start(source, samples_per_peak));
+        *out_session = Box::into_raw(session);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 지난 poll 이후 계산된 피크 가져오기 (없으면 out_peaks = NULL, count = 0)
+/// - out_peaks: 피크 배열 (caller가 free_audio_peaks로 해제), 이전 poll 결과 뒤에 이어붙이면 전체 파형
+/// - out_status: PeakStreamStatus (0=계산 중, 1=완료, 2=취소, 3=디코딩 실패) — 0이 아니면 더 이상 poll 불필요
+///   완료만 전체 파형, 취소/실패는 앞부분만 (캐시/피라미드로 넘기지 말 것)
+#[no_mangle]
+pub extern "C" fn audio_peaks_session_poll(
+    session: *mut AudioPeaksStreamSession,
+    out_peaks: *mut *mut f32,
+    out_peak_count: *mut u32,
+    out_status: *mut i32,
+) -> i32 {
+    if session.is_null() || out_peaks.is_null() || out_peak_count.is_null() || out_status.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_peaks = std::ptr::null_mut();
+        *out_peak_count = 0;
+        *out_status = PeakStreamStatus::Running as i32;
+
+        let session = &*session;
+        let (peaks, status, error) = match session.state.lock() {
+            Ok(mut state) => (std::mem::take(&mut state.pending), state.status, state.error.take()),
+            Err(_) => return ErrorCode::Unknown as i32,
+        };
+
+        if let Some(error) = error {
+            report_error(format!("audio_peaks_session_poll: {}", error));
+        }
+        *out_status = status as i32;
+        if !peaks.is_empty() {
+            *out_peak_count = peaks.len() as u32;
+            *out_peaks = Box::into_raw(peaks.into_boxed_slice()) as *mut f32;
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 피크 계산 중단 요청 (즉시 반환, 워커는 다음 패킷에서 종료 → poll이 Cancelled)
+#[no_mangle]
+pub extern "C" fn audio_peaks_session_cancel(session: *mut AudioPeaksStreamSession) -> i32 {
+    if session.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        (*session).cancelled.store(true, Ordering::SeqCst);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 스트리밍 피크 세션 파괴 (진행 중이면 취소 후 워커 종료 대기)
+#[no_mangle]
+pub extern "C" fn audio_peaks_session_destroy(session: *mut AudioPeaksStreamSession) -> i32 {
+    if session.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(session);
+    }
+
+    ErrorCode::Success as i32
+}
+
+// ==================== 피크 피라미드 API ====================
+
+/// 줌 레벨 피크 피라미드 생성 (원본 샘플레이트 기준, 레벨 0 = 256 samples/peak)
+/// 레벨 0 피크는 extract_audio_peaks_stream과 같은 디스크 캐시 사용 → 재오픈 시 디코딩 없음
+/// - stream_index: 컨테이너 스트림 번호, -1=기본 오디오 스트림
+/// - cache_dir: UTF-8 캐시 디렉토리 (NULL이면 미디어 파일 옆에 저장)
+/// - out_pyramid: audio_peak_pyramid_destroy로 해제
+#[no_mangle]
+pub extern "C" fn audio_peak_pyramid_create(
+    file_path: *const c_char,
+    stream_index: i32,
+    cache_dir: *const c_char,
+    out_pyramid: *mut *mut PeakPyramid,
+    out_channels: *mut u32,
+    out_sample_rate: *mut u32,
+    out_duration_ms: *mut i64,
+) -> i32 {
+    if file_path.is_null() || out_pyramid.is_null() || out_channels.is_null()
+        || out_sample_rate.is_null() || out_duration_ms.is_null()
+    {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let stream_index = match ffi_stream_index(stream_index) {
+        Some(stream_index) => stream_index,
+        None => return ErrorCode::InvalidParam as i32,
+    };
+
+    unsafe {
+        *out_pyramid = std::ptr::null_mut();
+
+        let path = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => PathBuf::from(s),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let cache_dir = if cache_dir.is_null() {
+            None
+        } else {
+            match CStr::from_ptr(cache_dir).to_str() {
+                Ok(s) => Some(PathBuf::from(s)),
+                Err(_) => return ErrorCode::InvalidParam as i32,
+            }
+        };
+
+        let base = match peak_cache::load(&path, stream_index, BASE_SAMPLES_PER_PEAK, cache_dir.as_deref()) {
+            Some(cached) => cached,
+            None => match extract_peaks_internal(&path, stream_index, BASE_SAMPLES_PER_PEAK) {
+                Ok(result) => {
+                    if let Err(e) = peak_cache::store(&path, stream_index, BASE_SAMPLES_PER_PEAK, &result, cache_dir.as_deref()) {
+                        log_warn!("audio_peak_pyramid_create: cache write failed: {}", e);
+                    }
+                    result
+                }
+                Err(e) => {
+                    report_error(format!("audio_peak_pyramid_create: {}", e));
+                    return ErrorCode::Ffmpeg as i32;
+                }
+            },
+        };
+
+        *out_channels = base.channels;
+        *out_sample_rate = base.sample_rate;
+        *out_duration_ms = base.duration_ms;
+        *out_pyramid = Box::into_raw(Box::new(PeakPyramid::build(base.peaks, base.sample_rate)));
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 피라미드에서 [start_ms, end_ms) 구간 피크 조회 (디코딩 없음, 줌 변경마다 호출 가능)
+/// - samples_per_peak: 줌 레벨 (원본 샘플레이트 기준, 임의 값)
+/// - out_peaks: 피크 배열 (caller가 free_audio_peaks로 해제, 구간이 오디오 밖이면 NULL/0)
+#[no_mangle]
+pub extern "C" fn audio_peak_pyramid_query(
+    pyramid: *mut PeakPyramid,
+    start_ms: i64,
+    end_ms: i64,
+    samples_per_peak: u32,
+    out_peaks: *mut *mut f32,
+    out_peak_count: *mut u32,
+) -> i32 {
+    if pyramid.is_null() || out_peaks.is_null() || out_peak_count.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    if samples_per_peak == 0 || end_ms <= start_ms {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        *out_peaks = std::ptr::null_mut();
+        *out_peak_count = 0;
+
+        let peaks = (*pyramid).query(start_ms, end_ms, samples_per_peak);
+        if !peaks.is_empty() {
+            *out_peak_count = peaks.len() as u32;
+            *out_peaks = Box::into_raw(peaks.into_boxed_slice()) as *mut f32;
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 피크 피라미드 파괴
+#[no_mangle]
+pub extern "C" fn audio_peak_pyramid_destroy(pyramid: *mut PeakPyramid) -> i32 {
+    if pyramid.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(pyramid);
+    }
+
+    ErrorCode::Success as i32
+}
+
+// ==================== 멀티캠 오디오 동기화 ====================
+
+/// 동기화 분석 기본 구간 (ms) — analysis_ms <= 0일 때 사용
+const SYNC_DEFAULT_ANALYSIS_MS: i64 = 60_000;
+
+/// 파일 앞부분 analysis_ms 구간의 오디오 엔벨로프 추출
+fn extract_sync_envelope(path: &std::path::Path, start_ms: i64, analysis_ms: i64) -> Result<Vec<f32>, String> {
+    let mut decoder = AudioDecoder::open(path)?;
+    let duration_ms = decoder.duration_ms();
+    let start_ms = start_ms.max(0);
+    let end_ms = if duration_ms > 0 {
+        (start_ms + analysis_ms).min(duration_ms)
+    } else {
+        start_ms + analysis_ms
+    };
+    let channels = decoder.channels() as usize;
+    let sample_rate = decoder.sample_rate();
+
+    // 1초 청크 = 엔벨로프 블록(5ms)의 정수배 → 청크 경계에서 블록이 잘리지 않음
+    let mut envelope = Vec::new();
+    let mut pos_ms = start_ms;
+    while pos_ms < end_ms {
+        let chunk_ms = PEAK_SESSION_CHUNK_MS.min(end_ms - pos_ms);
+        let samples = decoder.decode_range(pos_ms, chunk_ms as f64)?;
+        envelope.extend(sync::envelope(&samples, channels, sample_rate));
+        pos_ms += chunk_ms;
+    }
+
+    Ok(envelope)
+}
+
+/// 여러 클립의 오디오를 상호상관하여 동기화 오프셋 계산
+///
+/// 첫 번째 파일을 기준(reference)으로, 각 파일의 시작 위치 오프셋을 반환한다.
+/// 양수 오프셋: 해당 파일이 기준보다 늦게 녹화 시작 → 타임라인에서 그만큼 뒤에 배치
+///
+/// # 파라미터
+/// - file_paths: UTF-8 파일 경로 배열 (count개, 2개 이상)
+/// - source_starts_ms: 파일별 분석 시작 위치 배열 (NULL이면 모두 0, 예: 클립 trim_start)
+/// - analysis_ms: 분석 구간 길이 (0 이하면 60초)
+/// - max_offset_ms: 탐색할 최대 오프셋 (±)
+/// - out_offsets_ms: 출력 오프셋 배열 (호출자 할당, count개, [0]=0)
+/// - out_confidences: 출력 신뢰도 배열 (호출자 할당, count개, 0.0~1.0)
+#[no_mangle]
+pub extern "C" fn audio_sync_compute_offsets(
+    file_paths: *const *const c_char,
+    source_starts_ms: *const i64,
+    count: u32,
+    analysis_ms: i64,
+    max_offset_ms: i64,
+    out_offsets_ms: *mut i64,
+    out_confidences: *mut f32,
+) -> i32 {
+    if file_paths.is_null() || out_offsets_ms.is_null() || out_confidences.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    if count < 2 || max_offset_ms <= 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    let analysis_ms = if analysis_ms > 0 { analysis_ms } else { SYNC_DEFAULT_ANALYSIS_MS };
+
+    unsafe {
+        let paths = std::slice::from_raw_parts(file_paths, count as usize);
+        let offsets = std::slice::from_raw_parts_mut(out_offsets_ms, count as usize);
+        let confidences = std::slice::from_raw_parts_mut(out_confidences, count as usize);
+
+        let mut envelopes = Vec::with_capacity(paths.len());
+        for (i, &path_ptr) in paths.iter().enumerate() {
+            if path_ptr.is_null() {
+                return ErrorCode::NullPointer as i32;
+            }
+            let path = match CStr::from_ptr(path_ptr).to_str() {
+                Ok(s) => PathBuf::from(s),
+                Err(_) => return ErrorCode::InvalidParam as i32,
+            };
+            let start_ms = if source_starts_ms.is_null() { 0 } else { *source_starts_ms.add(i) };
+
+            match extract_sync_envelope(&path, start_ms, analysis_ms) {
+                Ok(env) => envelopes.push(env),
+                Err(e) => {
+                    report_error(format!("audio_sync_compute_offsets: {:?}: {}", path, e));
+                    return ErrorCode::Ffmpeg as i32;
+                }
+            }
+        }
+
+        offsets[0] = 0;
+        confidences[0] = 1.0;
+
+        for (i, envelope) in envelopes.iter().enumerate().skip(1) {
+            match sync::compute_offset(&envelopes[0], envelope, max_offset_ms) {
+                Some(result) => {
+                    offsets[i] = result.offset_ms;
+                    confidences[i] = result.confidence;
+                }
+                None => {
+                    // 무음/빈 오디오 → 상관 계산 불가
+                    offsets[i] = 0;
+                    confidences[i] = 0.0;
                 }
             }
         }
     }
 
-    // 마지막 블록 처리
-    if block_sample_count > 0 {
-        peaks.push(block_max.min(1.0));
+    ErrorCode::Success as i32
+}
+
+/// 타임라인 믹스 라우드니스 측정 (EBU R128, 트랙/클립 볼륨·페이드·엔벌로프 반영)
+/// - start_ms/end_ms: 타임라인 구간 (end_ms <= 0이면 끝까지)
+/// - out_integrated_lufs: 통합 라우드니스 (무음이면 -Infinity)
+/// - out_true_peak_dbtp: 트루 피크 (무음이면 -Infinity)
+/// 구간 전체를 디코딩하므로 UI 스레드가 아닌 곳에서 호출
+#[no_mangle]
+pub extern "C" fn timeline_measure_loudness(
+    timeline: *mut std::ffi::c_void,
+    start_ms: i64,
+    end_ms: i64,
+    out_integrated_lufs: *mut f64,
+    out_true_peak_dbtp: *mut f64,
+) -> i32 {
+    if timeline.is_null() || out_integrated_lufs.is_null() || out_true_peak_dbtp.is_null() {
+        return ErrorCode::NullPointer as i32;
     }
 
-    Ok(AudioPeakResult {
-        peaks,
-        channels,
-        sample_rate,
-        duration_ms,
-    })
+    unsafe {
+        // Timeline Arc 복제 (원본은 핸들 레지스트리가 소유)
+        let timeline = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+
+        let duration_ms = match timeline.lock() {
+            Ok(tl) => tl.duration_ms(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let end_ms = if end_ms <= 0 { duration_ms } else { end_ms.min(duration_ms) };
+        if start_ms < 0 || start_ms >= end_ms {
+            return ErrorCode::InvalidParam as i32;
+        }
+
+        match loudness::measure_timeline(&timeline, (start_ms, end_ms), &AtomicBool::new(false)) {
+            Ok(Some(report)) => {
+                *out_integrated_lufs = report.integrated_lufs;
+                *out_true_peak_dbtp = report.true_peak_dbtp;
+                ErrorCode::Success as i32
+            }
+            Ok(None) => ErrorCode::Unknown as i32,
+            Err(e) => {
+                report_error(format!("[LOUDNESS] 측정 실패: {}", e));
+                ErrorCode::RenderFailed as i32
+            }
+        }
+    }
 }
@@ -1,14 +1,15 @@
 // 오디오 재생 FFI - C# P/Invoke 연동
-// AudioPlayback 생성/정지/일시정지/재개/파괴
+// AudioPlayback 생성/정지/일시정지/재개/파괴, 비디오 재생(playback_*)의 마스터 클럭 연결
+// AudioEngine: 호스트가 PCM을 직접 요청하는 pull 방식 (WASAPI/NAudio 공급용)
 
+use crate::audio::engine::AudioEngine;
 use crate::audio::playback::AudioPlayback;
-use crate::ffi::types::ErrorCode;
-use crate::timeline::Timeline;
+use crate::ffi::handle::{PLAYBACKS, TIMELINES};
+use crate::ffi::types::{report_error, ErrorCode};
 use std::ffi::c_void;
-use std::sync::{Arc, Mutex};
 
 /// 오디오 재생 시작
-/// timeline: Arc<Mutex<Timeline>>의 raw pointer (소유권 변경 없음)
+/// timeline: timeline_create 핸들 (소유권 변경 없음)
 /// start_time_ms: 재생 시작 위치
 /// out_handle: AudioPlayback 핸들 반환
 #[no_mangle]
@@ -22,10 +23,11 @@ pub extern "C" fn audio_playback_start(
     }
 
     unsafe {
-        // Timeline Arc 복제 (원본 소유권 유지)
-        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
-        let timeline_clone = Arc::clone(&timeline_arc);
-        let _ = Arc::into_raw(timeline_arc); // 원본 유지
+        // Timeline Arc 복제 (원본은 핸들 레지스트리가 소유)
+        let timeline_clone = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
 
         match AudioPlayback::start(timeline_clone, start_time_ms) {
             Ok(playback) => {
@@ -34,7 +36,7 @@ pub extern "C" fn audio_playback_start(
                 ErrorCode::Success as i32
             }
             Err(e) => {
-                eprintln!("[AUDIO_FFI] 재생 시작 실패: {}", e);
+                report_error(format!("[AUDIO_FFI] 재생 시작 실패: {}", e));
                 *out_handle = std::ptr::null_mut();
                 ErrorCode::Unknown as i32
             }
@@ -87,6 +89,24 @@ pub extern "C" fn audio_playback_resume(handle: *mut c_void) -> i32 {
     ErrorCode::Success as i32
 }
 
+/// 오디오 재생 위치를 비디오 재생(playback_create 핸들)의 마스터 클럭으로 연결
+/// 1배속 재생 중 비디오 프레임이 오디오 출력 위치를 따라감 (해제: playback_detach_audio)
+/// 오디오 재생을 파괴/재시작하면 이전 클럭은 보고가 끊겨 무시됨 → 새 핸들로 다시 연결
+#[no_mangle]
+pub extern "C" fn audio_playback_attach_video(handle: *mut c_void, playback: *mut c_void) -> i32 {
+    if handle.is_null() || playback.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let controller = match PLAYBACKS.get(playback) {
+        Some(c) => c,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let audio = unsafe { &*(handle as *mut AudioPlayback) };
+    controller.set_audio_clock(Some(audio.clock()));
+    ErrorCode::Success as i32
+}
+
 /// 오디오 재생 객체 파괴 (메모리 해제)
 #[no_mangle]
 pub extern "C" fn audio_playback_destroy(handle: *mut c_void) -> i32 {
@@ -101,3 +121,86 @@ pub extern "C" fn audio_playback_destroy(handle: *mut c_void) -> i32 {
 
     ErrorCode::Success as i32
 }
+
+/// Pull 방식 오디오 엔진 생성
+/// timeline: timeline_create 핸들 (소유권 변경 없음)
+/// sample_rate/channels: 호스트 출력 포맷 (8000~192000Hz, 1~8ch)
+/// out_handle: AudioEngine 핸들 반환 (audio_engine_destroy로 해제)
+#[no_mangle]
+pub extern "C" fn audio_engine_create(
+    timeline: *mut c_void,
+    sample_rate: u32,
+    channels: u32,
+    out_handle: *mut *mut c_void,
+) -> i32 {
+    if timeline.is_null() || out_handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_handle = std::ptr::null_mut();
+
+        // Timeline Arc 복제 (원본은 핸들 레지스트리가 소유)
+
+        let timeline_clone = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+
+        match AudioEngine::new(timeline_clone, sample_rate, channels) {
+            Ok(engine) => {
+                *out_handle = Box::into_raw(Box::new(engine)) as *mut c_void;
+                ErrorCode::Success as i32
+            }
+            Err(e) => {
+                report_error(format!("[AUDIO_FFI] 엔진 생성 실패: {}", e));
+                ErrorCode::InvalidParam as i32
+            }
+        }
+    }
+}
+
+/// 타임라인 오디오 PCM 요청 (디코딩 + 믹스 + 리샘플)
+/// timestamp_ms: 요청 시작 위치 (직전 요청 끝과 가까우면 끊김 없이 이어서 출력)
+/// sample_count: 채널당 샘플 수
+/// out_buf: f32 interleaved 버퍼 (sample_count * channels 이상, 호출자 할당)
+#[no_mangle]
+pub extern "C" fn audio_engine_pull_samples(
+    handle: *mut c_void,
+    timestamp_ms: i64,
+    sample_count: u32,
+    out_buf: *mut f32,
+) -> i32 {
+    if handle.is_null() || out_buf.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let engine = &mut *(handle as *mut AudioEngine);
+        let len = sample_count as usize * engine.channels() as usize;
+        let out = std::slice::from_raw_parts_mut(out_buf, len);
+
+        match engine.pull_samples(timestamp_ms, sample_count as usize, out) {
+            Ok(()) => ErrorCode::Success as i32,
+            Err(e) => {
+                report_error(format!("[AUDIO_FFI] 샘플 요청 실패: {}", e));
+                out.fill(0.0);
+                ErrorCode::Unknown as i32
+            }
+        }
+    }
+}
+
+/// 오디오 엔진 파괴 (메모리 해제)
+#[no_mangle]
+pub extern "C" fn audio_engine_destroy(handle: *mut c_void) -> i32 {
+    if handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(handle as *mut AudioEngine);
+    }
+
+    ErrorCode::Success as i32
+}
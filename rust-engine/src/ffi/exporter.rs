@@ -1,15 +1,17 @@
 // Exporter FFI - C# P/Invoke 연동
-// Export 작업 생성/진행률/취소/파괴
+// Export 작업 생성/진행률/취소/파괴 + Export 큐
 
+use crate::encoding::export_queue::ExportQueue;
 use crate::encoding::exporter::{ExportConfig, ExportJob};
-use crate::ffi::types::ErrorCode;
+use crate::encoding::presets;
+use crate::ffi::handle::{EXPORT_JOBS, TIMELINES};
+use crate::ffi::types::{report_error, CExportOptions, CExportStats, ErrorCode};
 use crate::subtitle::overlay::{SubtitleOverlay, SubtitleOverlayList};
-use crate::timeline::Timeline;
 use std::ffi::{c_void, c_char, CStr, CString};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 /// Export 시작 (백그라운드 스레드에서 실행)
-/// timeline: Arc<Mutex<Timeline>>의 raw pointer
+/// timeline: timeline_create 핸들
 /// output_path: UTF-8 인코딩된 출력 파일 경로
 /// out_job: ExportJob 핸들 반환
 #[no_mangle]
@@ -27,34 +29,13 @@ pub extern "C" fn exporter_start(
     }
 
     unsafe {
-        // output_path → Rust String
-        let c_str = CStr::from_ptr(output_path);
-        let output_path_str = match c_str.to_str() {
-            Ok(s) => s.to_string(),
-            Err(_) => return ErrorCode::InvalidParam as i32,
-        };
-
-        // Timeline Arc 복제 (원본 소유권 유지)
-        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
-        let timeline_clone = Arc::clone(&timeline_arc);
-        let _ = Arc::into_raw(timeline_arc); // 원본 유지
-
-        let config = ExportConfig {
-            output_path: output_path_str,
-            width,
-            height,
-            fps,
-            crf,
-            encoder_type: 0, // Auto
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
         };
-
-        // ExportJob 시작 (백그라운드 스레드)
-        let job = ExportJob::start(timeline_clone, config);
-        let job_box = Box::new(job);
-        *out_job = Box::into_raw(job_box) as *mut c_void;
+        let config = ExportConfig::new(output_path, width, height, fps, crf);
+        start_export(timeline, config, None, out_job)
     }
-
-    ErrorCode::Success as i32
 }
 
 /// Export 진행률 가져오기 (0~100)
@@ -64,10 +45,39 @@ pub extern "C" fn exporter_get_progress(job: *mut c_void) -> u32 {
         return 0;
     }
 
+    match EXPORT_JOBS.get(job) {
+        Some(job_ref) => job_ref.get_progress(),
+        None => 0,
+    }
+}
+
+/// Export 통계 조회 (처리 프레임, 인코딩 FPS, 평균 비트레이트, 남은 시간, 드롭 프레임, 캐시 적중률)
+/// 프레임 수/비트레이트는 현재 패스 기준 (2-pass는 패스마다 초기화)
+#[no_mangle]
+pub extern "C" fn exporter_get_stats(job: *mut c_void, out_stats: *mut CExportStats) -> i32 {
+    if job.is_null() || out_stats.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
     unsafe {
-        let job_ref = &*(job as *const ExportJob);
-        job_ref.get_progress()
+        let job_ref = match EXPORT_JOBS.get(job) {
+            Some(j) => j,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let stats = job_ref.get_stats();
+        *out_stats = CExportStats {
+            frames_done: stats.frames_done,
+            total_frames: stats.total_frames,
+            encode_fps: stats.encode_fps,
+            average_bitrate_kbps: stats.average_bitrate_kbps,
+            elapsed_ms: stats.elapsed_ms,
+            eta_ms: stats.eta_ms,
+            dropped_frames: stats.dropped_frames,
+            cache_hit_rate: stats.cache_hit_rate,
+        };
     }
+
+    ErrorCode::Success as i32
 }
 
 /// Export 완료 여부 확인
@@ -78,14 +88,15 @@ pub extern "C" fn exporter_is_finished(job: *mut c_void) -> i32 {
         return 1; // null이면 완료로 처리
     }
 
-    unsafe {
-        let job_ref = &*(job as *const ExportJob);
-        if job_ref.is_finished() { 1 } else { 0 }
+    match EXPORT_JOBS.get(job) {
+        Some(job_ref) => if job_ref.is_finished() { 1 } else { 0 },
+        None => 1, // 무효 핸들도 완료로 처리 (폴링 종료)
     }
 }
 
 /// Export 에러 메시지 가져오기
 /// out_error: 에러 문자열 포인터 (없으면 null)
+/// 에러가 있으면 마지막 에러로도 기록 (engine_get_last_error로 분류 조회)
 /// 반환 후 string_free()로 해제 필요
 #[no_mangle]
 pub extern "C" fn exporter_get_error(
@@ -97,10 +108,14 @@ pub extern "C" fn exporter_get_error(
     }
 
     unsafe {
-        let job_ref = &*(job as *const ExportJob);
+        let job_ref = match EXPORT_JOBS.get(job) {
+            Some(j) => j,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
 
         match job_ref.get_error() {
             Some(msg) => {
+                crate::utils::error::set_last_error(msg.clone());
                 match CString::new(msg) {
                     Ok(c_str) => {
                         *out_error = c_str.into_raw();
@@ -119,6 +134,29 @@ pub extern "C" fn exporter_get_error(
     ErrorCode::Success as i32
 }
 
+/// Export 에러 분류 (ErrorCategory, 0 = 에러 없음/진행 중)
+/// 6 = 디스크 공간 부족, 3 = 권한 없음 등 → 호스트가 원인별 안내 표시
+#[no_mangle]
+pub extern "C" fn exporter_get_error_category(
+    job: *mut c_void,
+    out_category: *mut i32,
+) -> i32 {
+    if job.is_null() || out_category.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let job_ref = match EXPORT_JOBS.get(job) {
+            Some(j) => j,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        *out_category = job_ref.error_category()
+            .map_or(crate::utils::error::ErrorCategory::None as i32, |c| c as i32);
+    }
+
+    ErrorCode::Success as i32
+}
+
 /// Export 취소
 #[no_mangle]
 pub extern "C" fn exporter_cancel(job: *mut c_void) -> i32 {
@@ -126,9 +164,9 @@ pub extern "C" fn exporter_cancel(job: *mut c_void) -> i32 {
         return ErrorCode::NullPointer as i32;
     }
 
-    unsafe {
-        let job_ref = &*(job as *const ExportJob);
-        job_ref.cancel();
+    match EXPORT_JOBS.get(job) {
+        Some(job_ref) => job_ref.cancel(),
+        None => return ErrorCode::InvalidHandle as i32,
     }
 
     ErrorCode::Success as i32
@@ -142,8 +180,9 @@ pub extern "C" fn exporter_destroy(job: *mut c_void) -> i32 {
         return ErrorCode::NullPointer as i32;
     }
 
-    unsafe {
-        let _ = Box::from_raw(job as *mut ExportJob);
+    // 진행 중인 호출이 Arc를 가지고 있으면 그 호출이 끝날 때 해제
+    if EXPORT_JOBS.remove(job).is_none() {
+        return ErrorCode::InvalidHandle as i32;
     }
 
     ErrorCode::Success as i32
@@ -159,7 +198,7 @@ pub extern "C" fn exporter_create_subtitle_list() -> *mut c_void {
     Box::into_raw(list) as *mut c_void
 }
 
-/// 자막 오버레이 추가
+/// 자막 오버레이 추가 (z_index 0, 추가 순서대로 위에 쌓임)
 /// rgba_ptr: RGBA 비트맵 데이터 포인터 (width * height * 4 bytes)
 /// rgba_len: 바이트 수
 #[no_mangle]
@@ -173,6 +212,24 @@ pub extern "C" fn exporter_subtitle_list_add(
     height: u32,
     rgba_ptr: *const u8,
     rgba_len: u32,
+) -> i32 {
+    exporter_subtitle_list_add_v2(list, start_ms, end_ms, x, y, width, height, rgba_ptr, rgba_len, 0)
+}
+
+/// 자막 오버레이 추가 (v2: z_index 지정)
+/// 같은 시간에 여러 오버레이가 활성이면 z_index 오름차순으로 블렌딩 (큰 값이 위)
+#[no_mangle]
+pub extern "C" fn exporter_subtitle_list_add_v2(
+    list: *mut c_void,
+    start_ms: i64,
+    end_ms: i64,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    rgba_ptr: *const u8,
+    rgba_len: u32,
+    z_index: i32,
 ) -> i32 {
     if list.is_null() || rgba_ptr.is_null() {
         return ErrorCode::NullPointer as i32;
@@ -195,6 +252,7 @@ pub extern "C" fn exporter_subtitle_list_add(
             width,
             height,
             rgba_data: data,
+            z_index,
         });
     }
 
@@ -215,50 +273,148 @@ pub extern "C" fn exporter_start_v2(
     subtitle_list: *mut c_void,
     out_job: *mut *mut c_void,
 ) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
     if timeline.is_null() || output_path.is_null() || out_job.is_null() {
         return ErrorCode::NullPointer as i32;
     }
 
     unsafe {
-        let c_str = CStr::from_ptr(output_path);
-        let output_path_str = match c_str.to_str() {
-            Ok(s) => s.to_string(),
-            Err(_) => return ErrorCode::InvalidParam as i32,
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
         };
+        let config = ExportConfig::new(output_path, width, height, fps, crf);
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
+/// 자막 포함 Export 시작 (v3) — 인코더 타입 선택 지원
+/// encoder_type: 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+/// subtitle_list: null이면 자막 없음, 소유권 Rust로 이전
+#[no_mangle]
+pub extern "C" fn exporter_start_v3(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
 
-        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
-        let timeline_clone = Arc::clone(&timeline_arc);
-        let _ = Arc::into_raw(timeline_arc);
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
 
+    unsafe {
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
         let config = ExportConfig {
-            output_path: output_path_str,
-            width,
-            height,
-            fps,
-            crf,
-            encoder_type: 0, // Auto
+            encoder_type,
+            ..ExportConfig::new(output_path, width, height, fps, crf)
         };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
 
-        // 자막 목록 소유권 이전 (null이면 None)
-        let subtitles = if subtitle_list.is_null() {
-            None
-        } else {
-            Some(*Box::from_raw(subtitle_list as *mut SubtitleOverlayList))
+/// 자막 포함 Export 시작 (v4) — 비디오 코덱 선택 지원
+/// encoder_type: 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+/// codec: 0=H.264, 1=H.265, 2=VP9, 3=AV1, 4=ProRes (인코더 없으면 H.264로 폴백)
+/// subtitle_list: null이면 자막 없음, 소유권 Rust로 이전
+#[no_mangle]
+pub extern "C" fn exporter_start_v4(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    codec: u32,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
         };
+        let config = ExportConfig {
+            encoder_type,
+            codec,
+            ..ExportConfig::new(output_path, width, height, fps, crf)
+        };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
+/// 자막 포함 Export 시작 (v5) — 비트레이트 제어 모드 지원
+/// rate_control: 0=CRF, 1=CBR, 2=VBR, 3=2-pass VBR (CRF 외에는 target_bitrate_kbps 또는 target_size_bytes 필요)
+/// max_bitrate_kbps / buffer_size_kbits: 0이면 자동
+/// target_size_bytes: 0보다 크면 목표 파일 크기로 비트레이트 계산 (target_bitrate_kbps 무시)
+/// 2-pass는 진행률 0~50% 분석 패스, 50~100% 인코딩 패스
+#[no_mangle]
+pub extern "C" fn exporter_start_v5(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    codec: u32,
+    rate_control: u32,
+    target_bitrate_kbps: u32,
+    max_bitrate_kbps: u32,
+    buffer_size_kbits: u32,
+    target_size_bytes: u64,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
 
-        let job = ExportJob::start_with_subtitles(timeline_clone, config, subtitles);
-        let job_box = Box::new(job);
-        *out_job = Box::into_raw(job_box) as *mut c_void;
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
     }
 
-    ErrorCode::Success as i32
+    unsafe {
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let config = ExportConfig {
+            encoder_type,
+            codec,
+            rate_control,
+            target_bitrate_kbps,
+            max_bitrate_kbps,
+            buffer_size_kbits,
+            target_size_bytes,
+            ..ExportConfig::new(output_path, width, height, fps, crf)
+        };
+        start_export(timeline, config, subtitles, out_job)
+    }
 }
 
-/// 자막 포함 Export 시작 (v3) — 인코더 타입 선택 지원
-/// encoder_type: 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
-/// subtitle_list: null이면 자막 없음, 소유권 Rust로 이전
+/// 자막 포함 Export 시작 (v6) — Export 구간 지원 (v5 + start_ms/end_ms)
+/// rate_control: 0=CRF, 1=CBR, 2=VBR, 3=2-pass VBR (CRF 외에는 target_bitrate_kbps 또는 target_size_bytes 필요)
+/// max_bitrate_kbps / buffer_size_kbits: 0이면 자동
+/// target_size_bytes: 0보다 크면 목표 파일 크기로 비트레이트 계산 (target_bitrate_kbps 무시)
+/// start_ms/end_ms: 타임라인 기준 Export 구간 (end_ms <= 0이면 끝까지), 진행률은 구간 기준
 #[no_mangle]
-pub extern "C" fn exporter_start_v3(
+pub extern "C" fn exporter_start_v6(
     timeline: *mut c_void,
     output_path: *const c_char,
     width: u32,
@@ -266,47 +422,439 @@ pub extern "C" fn exporter_start_v3(
     fps: f64,
     crf: u32,
     encoder_type: u32,
+    codec: u32,
+    rate_control: u32,
+    target_bitrate_kbps: u32,
+    max_bitrate_kbps: u32,
+    buffer_size_kbits: u32,
+    target_size_bytes: u64,
+    start_ms: i64,
+    end_ms: i64,
     subtitle_list: *mut c_void,
     out_job: *mut *mut c_void,
 ) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
     if timeline.is_null() || output_path.is_null() || out_job.is_null() {
         return ErrorCode::NullPointer as i32;
     }
 
     unsafe {
-        let c_str = CStr::from_ptr(output_path);
-        let output_path_str = match c_str.to_str() {
-            Ok(s) => s.to_string(),
-            Err(_) => return ErrorCode::InvalidParam as i32,
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
         };
+        let config = ExportConfig {
+            encoder_type,
+            codec,
+            rate_control,
+            target_bitrate_kbps,
+            max_bitrate_kbps,
+            buffer_size_kbits,
+            target_size_bytes,
+            start_ms,
+            end_ms,
+            ..ExportConfig::new(output_path, width, height, fps, crf)
+        };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
+/// 자막 포함 Export 시작 (v7) — 스마트 렌더링/세그먼트 병렬 옵션 (v6 + smart_render/parallel_segments)
+/// rate_control: 0=CRF, 1=CBR, 2=VBR, 3=2-pass VBR (CRF 외에는 target_bitrate_kbps 또는 target_size_bytes 필요)
+/// max_bitrate_kbps / buffer_size_kbits: 0이면 자동
+/// target_size_bytes: 0보다 크면 목표 파일 크기로 비트레이트 계산 (target_bitrate_kbps 무시)
+/// start_ms/end_ms: 타임라인 기준 Export 구간 (end_ms <= 0이면 끝까지), 진행률은 구간 기준
+/// smart_render: 0이 아니면 손대지 않은 단일 클립 구간을 재인코딩 없이 복사 (v6 이하는 사용 안 함)
+/// parallel_segments: 세그먼트 병렬 인코딩 스레드 수 (0/1 = 사용 안 함, 2-pass/HW 인코더는 무시)
+#[no_mangle]
+pub extern "C" fn exporter_start_v7(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    codec: u32,
+    rate_control: u32,
+    target_bitrate_kbps: u32,
+    max_bitrate_kbps: u32,
+    buffer_size_kbits: u32,
+    target_size_bytes: u64,
+    start_ms: i64,
+    end_ms: i64,
+    smart_render: u32,
+    parallel_segments: u32,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
 
-        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
-        let timeline_clone = Arc::clone(&timeline_arc);
-        let _ = Arc::into_raw(timeline_arc);
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
 
+    unsafe {
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
         let config = ExportConfig {
-            output_path: output_path_str,
-            width,
-            height,
-            fps,
-            crf,
             encoder_type,
+            codec,
+            rate_control,
+            target_bitrate_kbps,
+            max_bitrate_kbps,
+            buffer_size_kbits,
+            target_size_bytes,
+            start_ms,
+            end_ms,
+            smart_render: smart_render != 0,
+            parallel_segments,
+            ..ExportConfig::new(output_path, width, height, fps, crf)
         };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
 
-        let subtitles = if subtitle_list.is_null() {
-            None
-        } else {
-            Some(*Box::from_raw(subtitle_list as *mut SubtitleOverlayList))
+/// 자막 포함 Export 시작 (v8) — 라우드니스 정규화 (v7 + loudness_target_lufs)
+/// rate_control: 0=CRF, 1=CBR, 2=VBR, 3=2-pass VBR (CRF 외에는 target_bitrate_kbps 또는 target_size_bytes 필요)
+/// max_bitrate_kbps / buffer_size_kbits: 0이면 자동
+/// target_size_bytes: 0보다 크면 목표 파일 크기로 비트레이트 계산 (target_bitrate_kbps 무시)
+/// start_ms/end_ms: 타임라인 기준 Export 구간 (end_ms <= 0이면 끝까지), 진행률은 구간 기준
+/// smart_render: 0이 아니면 손대지 않은 단일 클립 구간을 재인코딩 없이 복사 (v6 이하는 사용 안 함)
+/// parallel_segments: 세그먼트 병렬 인코딩 스레드 수 (0/1 = 사용 안 함, 2-pass/HW 인코더는 무시)
+/// loudness_target_lufs: 0 = 사용 안 함, 음수면 믹스 측정 후 목표 LUFS로 정규화 (예: -14.0, 트루 피크 -1 dBTP 제한)
+#[no_mangle]
+pub extern "C" fn exporter_start_v8(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    codec: u32,
+    rate_control: u32,
+    target_bitrate_kbps: u32,
+    max_bitrate_kbps: u32,
+    buffer_size_kbits: u32,
+    target_size_bytes: u64,
+    start_ms: i64,
+    end_ms: i64,
+    smart_render: u32,
+    parallel_segments: u32,
+    loudness_target_lufs: f64,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let config = ExportConfig {
+            encoder_type,
+            codec,
+            rate_control,
+            target_bitrate_kbps,
+            max_bitrate_kbps,
+            buffer_size_kbits,
+            target_size_bytes,
+            start_ms,
+            end_ms,
+            smart_render: smart_render != 0,
+            parallel_segments,
+            loudness_target_lufs,
+            ..ExportConfig::new(output_path, width, height, fps, crf)
+        };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
+/// 자막 포함 Export 시작 (v9) — 10bit 픽셀 포맷 출력 (v8 + high_bit_depth_container)
+/// rate_control: 0=CRF, 1=CBR, 2=VBR, 3=2-pass VBR (CRF 외에는 target_bitrate_kbps 또는 target_size_bytes 필요)
+/// max_bitrate_kbps / buffer_size_kbits: 0이면 자동
+/// target_size_bytes: 0보다 크면 목표 파일 크기로 비트레이트 계산 (target_bitrate_kbps 무시)
+/// start_ms/end_ms: 타임라인 기준 Export 구간 (end_ms <= 0이면 끝까지), 진행률은 구간 기준
+/// smart_render: 0이 아니면 손대지 않은 단일 클립 구간을 재인코딩 없이 복사 (v6 이하는 사용 안 함)
+/// parallel_segments: 세그먼트 병렬 인코딩 스레드 수 (0/1 = 사용 안 함, 2-pass/HW 인코더는 무시)
+/// loudness_target_lufs: 0 = 사용 안 함, 음수면 믹스 측정 후 목표 LUFS로 정규화 (예: -14.0, 트루 피크 -1 dBTP 제한)
+/// high_bit_depth_container: 0이 아니면 10bit 4:2:0 픽셀 포맷으로 인코딩 (인코더 미지원 시 8bit, 스마트 렌더링 비활성)
+///   디코딩/합성은 8bit 그대로 — 10bit 스트림이 필요한 납품 규격용이며 소스 10bit 정밀도는 유지되지 않음
+#[no_mangle]
+pub extern "C" fn exporter_start_v9(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    codec: u32,
+    rate_control: u32,
+    target_bitrate_kbps: u32,
+    max_bitrate_kbps: u32,
+    buffer_size_kbits: u32,
+    target_size_bytes: u64,
+    start_ms: i64,
+    end_ms: i64,
+    smart_render: u32,
+    parallel_segments: u32,
+    loudness_target_lufs: f64,
+    high_bit_depth_container: u32,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let config = ExportConfig {
+            encoder_type,
+            codec,
+            rate_control,
+            target_bitrate_kbps,
+            max_bitrate_kbps,
+            buffer_size_kbits,
+            target_size_bytes,
+            start_ms,
+            end_ms,
+            smart_render: smart_render != 0,
+            parallel_segments,
+            loudness_target_lufs,
+            high_bit_depth_container: high_bit_depth_container != 0,
+            ..ExportConfig::new(output_path, width, height, fps, crf)
+        };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
+/// 자막 포함 Export 시작 (옵션 구조체) — v9 이후 옵션은 CExportOptions 끝에 필드를 추가
+/// options: struct_size = sizeof(CExportOptions), 호출자가 모르는 뒤쪽 필드는 0 (기본값)
+/// 필드 의미는 exporter_start_v9 인자와 동일 (0이 아닌 u32 플래그 = 사용)
+/// subtitle_list: null이면 자막 없음, 소유권 Rust로 이전
+#[no_mangle]
+pub extern "C" fn exporter_start_with_options(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    options: *const CExportOptions,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if timeline.is_null() || output_path.is_null() || options.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let options = match read_export_options(options) {
+            Some(options) => options,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let config = ExportConfig {
+            encoder_type: options.encoder_type,
+            codec: options.codec,
+            rate_control: options.rate_control,
+            target_bitrate_kbps: options.target_bitrate_kbps,
+            max_bitrate_kbps: options.max_bitrate_kbps,
+            buffer_size_kbits: options.buffer_size_kbits,
+            target_size_bytes: options.target_size_bytes,
+            start_ms: options.start_ms,
+            end_ms: options.end_ms,
+            audio_bitrate_kbps: options.audio_bitrate_kbps,
+            smart_render: options.smart_render != 0,
+            parallel_segments: options.parallel_segments,
+            loudness_target_lufs: options.loudness_target_lufs,
+            high_bit_depth_container: options.high_bit_depth_container != 0,
+            ..ExportConfig::new(output_path, options.width, options.height, options.fps, options.crf)
+        };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
+/// 호출자 구조체 크기만큼만 복사 (이전 버전 구조체면 뒤쪽 필드는 0)
+/// 출력 크기/fps/crf까지는 필수
+unsafe fn read_export_options(options: *const CExportOptions) -> Option<CExportOptions> {
+    let size = ((*options).struct_size as usize).min(std::mem::size_of::<CExportOptions>());
+    if size < std::mem::offset_of!(CExportOptions, encoder_type) {
+        return None;
+    }
+    let mut copy = std::mem::zeroed::<CExportOptions>();
+    std::ptr::copy_nonoverlapping(options as *const u8, &mut copy as *mut CExportOptions as *mut u8, size);
+    Some(copy)
+}
+
+/// 이미지 시퀀스 Export 시작 (프레임마다 PNG/JPEG 파일, 오디오 없음)
+/// output_path: 파일명 기준 경로 — "out/shot.png" → out/shot_000000.png, out/shot_000001.png, ...
+/// image_format: 0=PNG, 1=JPEG
+/// start_ms/end_ms: 타임라인 기준 구간 (end_ms <= 0이면 끝까지)
+/// subtitle_list: null이면 자막 없음, 소유권 Rust로 이전
+#[no_mangle]
+pub extern "C" fn exporter_start_image_sequence(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    image_format: u32,
+    start_ms: i64,
+    end_ms: i64,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if image_format > 1 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let config = ExportConfig {
+            target: image_format + 1,
+            start_ms,
+            end_ms,
+            ..ExportConfig::new(output_path, width, height, fps, 0)
         };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
+/// 출력 경로 (UTF-8이 아니면 None)
+unsafe fn output_path_string(output_path: *const c_char) -> Option<String> {
+    match CStr::from_ptr(output_path).to_str() {
+        Ok(path) => Some(path.to_string()),
+        Err(e) => {
+            report_error(format!("exporter_start: Invalid UTF-8 output path: {}", e));
+            None
+        }
+    }
+}
 
-        let job = ExportJob::start_with_subtitles(timeline_clone, config, subtitles);
-        let job_box = Box::new(job);
-        *out_job = Box::into_raw(job_box) as *mut c_void;
+/// 자막 목록 소유권 이전 (null이면 None)
+/// subtitle_list를 받는 함수는 인자 검증 전에 호출 → 실패로 반환해도 목록이 해제됨
+unsafe fn take_subtitles(subtitle_list: *mut c_void) -> Option<SubtitleOverlayList> {
+    if subtitle_list.is_null() {
+        None
+    } else {
+        Some(*Box::from_raw(subtitle_list as *mut SubtitleOverlayList))
+    }
+}
+
+/// Export 시작 공통 (설정 검증 + 백그라운드 작업 시작)
+/// exporter_start_* 는 인자로 ExportConfig만 만들어 넘김
+unsafe fn start_export(
+    timeline: *mut c_void,
+    config: ExportConfig,
+    subtitles: Option<SubtitleOverlayList>,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    if !config.loudness_target_lufs.is_finite() || config.loudness_target_lufs > 0.0 {
+        report_error(format!("exporter_start: Invalid loudness target: {}", config.loudness_target_lufs));
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    // Timeline Arc 복제 (원본은 핸들 레지스트리가 소유)
+    let timeline_clone = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => {
+            report_error("exporter_start: Invalid timeline handle".to_string());
+            return ErrorCode::InvalidHandle as i32;
+        }
+    };
+
+    let job = ExportJob::start_with_subtitles(timeline_clone, config, subtitles);
+    *out_job = EXPORT_JOBS.insert(Arc::new(job));
+
+    ErrorCode::Success as i32
+}
+
+/// Export 프리셋 목록 (JSON 배열)
+/// out_json: [{"name":"youtube_1080p","label":"YouTube 1080p","width":1920,"height":1080,"fps":30,
+///   "codec":0,"rate_control":2,"crf":20,"target_bitrate_kbps":8000,"max_bitrate_kbps":12000,
+///   "audio_bitrate_kbps":384,"extension":"mp4"}, ...]
+/// 반환 후 string_free()로 해제 필요
+#[no_mangle]
+pub extern "C" fn exporter_list_presets(out_json: *mut *mut c_char) -> i32 {
+    if out_json.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        match CString::new(presets::presets_json()) {
+            Ok(c_str) => *out_json = c_str.into_raw(),
+            Err(_) => {
+                *out_json = std::ptr::null_mut();
+                return ErrorCode::InvalidParam as i32;
+            }
+        }
     }
 
     ErrorCode::Success as i32
 }
 
+/// 프리셋으로 Export 시작
+/// preset_name: exporter_list_presets의 name (없으면 InvalidParam)
+/// encoder_type: 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+/// subtitle_list: null이면 자막 없음, 소유권 Rust로 이전
+#[no_mangle]
+pub extern "C" fn exporter_start_with_preset(
+    timeline: *mut c_void,
+    preset_name: *const c_char,
+    output_path: *const c_char,
+    encoder_type: u32,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if timeline.is_null() || preset_name.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let preset = match CStr::from_ptr(preset_name).to_str().ok().and_then(presets::find_preset) {
+            Some(p) => p,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let output_path = match output_path_string(output_path) {
+            Some(path) => path,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+        let config = ExportConfig {
+            encoder_type,
+            ..preset.to_config(output_path)
+        };
+        start_export(timeline, config, subtitles, out_job)
+    }
+}
+
 /// 사용 가능한 인코더 탐지 (비트마스크 반환)
 /// bit 0 = libx264 (1), bit 1 = NVENC (2), bit 2 = QSV (4), bit 3 = AMF (8)
 #[no_mangle]
@@ -327,3 +875,280 @@ pub extern "C" fn exporter_free_subtitle_list(list: *mut c_void) -> i32 {
 
     ErrorCode::Success as i32
 }
+
+// ==================== Export 큐 FFI ====================
+
+/// Export 큐 생성
+/// max_parallel: 동시 실행 작업 수 (0/1 = 순차)
+/// 반환: ExportQueue 핸들 (export_queue_destroy로 해제)
+#[no_mangle]
+pub extern "C" fn export_queue_create(max_parallel: u32) -> *mut c_void {
+    let queue = Box::new(ExportQueue::new(max_parallel as usize));
+    Box::into_raw(queue) as *mut c_void
+}
+
+/// 큐에 작업 추가 (현재 타임라인 상태로 스냅샷)
+/// encoder_type: 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+/// codec: 0=H.264, 1=H.265, 2=VP9, 3=AV1, 4=ProRes
+/// start_ms/end_ms: 타임라인 기준 구간 (end_ms <= 0이면 끝까지)
+/// subtitle_list: null이면 자막 없음, 소유권 Rust로 이전
+/// out_index: 작업 인덱스
+#[no_mangle]
+pub extern "C" fn export_queue_add(
+    queue: *mut c_void,
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    codec: u32,
+    start_ms: i64,
+    end_ms: i64,
+    subtitle_list: *mut c_void,
+    out_index: *mut u32,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if queue.is_null() || timeline.is_null() || output_path.is_null() || out_index.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let c_str = CStr::from_ptr(output_path);
+        let output_path_str = match c_str.to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let config = ExportConfig {
+            encoder_type,
+            codec,
+            start_ms,
+            end_ms,
+            ..ExportConfig::new(output_path_str, width, height, fps, crf)
+        };
+
+        queue_add(queue, timeline, config, subtitles, out_index)
+    }
+}
+
+/// 큐에 프리셋 작업 추가
+/// preset_name: exporter_list_presets의 name (없으면 InvalidParam)
+/// start_ms/end_ms: 타임라인 기준 구간 (end_ms <= 0이면 끝까지)
+#[no_mangle]
+pub extern "C" fn export_queue_add_preset(
+    queue: *mut c_void,
+    timeline: *mut c_void,
+    preset_name: *const c_char,
+    output_path: *const c_char,
+    start_ms: i64,
+    end_ms: i64,
+    subtitle_list: *mut c_void,
+    out_index: *mut u32,
+) -> i32 {
+    let subtitles = unsafe { take_subtitles(subtitle_list) };
+
+    if queue.is_null() || timeline.is_null() || preset_name.is_null() || output_path.is_null() || out_index.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let preset = match CStr::from_ptr(preset_name).to_str().ok().and_then(presets::find_preset) {
+            Some(p) => p,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+
+        let c_str = CStr::from_ptr(output_path);
+        let output_path_str = match c_str.to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let config = ExportConfig {
+            start_ms,
+            end_ms,
+            ..preset.to_config(output_path_str)
+        };
+
+        queue_add(queue, timeline, config, subtitles, out_index)
+    }
+}
+
+/// 큐 작업 추가 공통 (타임라인 스냅샷)
+unsafe fn queue_add(
+    queue: *mut c_void,
+    timeline: *mut c_void,
+    config: ExportConfig,
+    subtitles: Option<SubtitleOverlayList>,
+    out_index: *mut u32,
+) -> i32 {
+    let queue_ref = &*(queue as *const ExportQueue);
+    let timeline_mutex = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+
+    let index = match timeline_mutex.lock() {
+        Ok(t) => queue_ref.add(&t, config, subtitles),
+        Err(_) => return ErrorCode::InvalidParam as i32,
+    };
+    *out_index = index as u32;
+
+    ErrorCode::Success as i32
+}
+
+/// 큐 실행 시작 (실행 중 추가된 작업도 이어서 처리)
+#[no_mangle]
+pub extern "C" fn export_queue_start(queue: *mut c_void) -> i32 {
+    if queue.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        queue_ref.start();
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 큐 작업 수
+#[no_mangle]
+pub extern "C" fn export_queue_get_count(queue: *mut c_void) -> u32 {
+    if queue.is_null() {
+        return 0;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        queue_ref.job_count() as u32
+    }
+}
+
+/// 작업 상태/진행률 조회
+/// out_status: 0=대기, 1=실행 중, 2=완료, 3=실패, 4=취소
+/// out_progress: 0~100 (종료된 작업은 100)
+#[no_mangle]
+pub extern "C" fn export_queue_get_job(
+    queue: *mut c_void,
+    index: u32,
+    out_status: *mut u32,
+    out_progress: *mut u32,
+) -> i32 {
+    if queue.is_null() || out_status.is_null() || out_progress.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        let index = index as usize;
+        match (queue_ref.job_status(index), queue_ref.job_progress(index)) {
+            (Some(status), Some(progress)) => {
+                *out_status = status as u32;
+                *out_progress = progress;
+            }
+            _ => return ErrorCode::InvalidParam as i32,
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 작업 에러 메시지 (없으면 null, 있으면 마지막 에러로도 기록)
+/// 반환 후 string_free()로 해제 필요
+#[no_mangle]
+pub extern "C" fn export_queue_get_job_error(
+    queue: *mut c_void,
+    index: u32,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    if queue.is_null() || out_error.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        *out_error = queue_ref.job_error(index as usize)
+            .inspect(|msg| crate::utils::error::set_last_error(msg.as_str()))
+            .and_then(|msg| CString::new(msg).ok())
+            .map(|c_str| c_str.into_raw())
+            .unwrap_or(std::ptr::null_mut());
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 전체 진행률 (작업 진행률 평균, 0~100)
+#[no_mangle]
+pub extern "C" fn export_queue_get_progress(queue: *mut c_void) -> u32 {
+    if queue.is_null() {
+        return 0;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        queue_ref.overall_progress()
+    }
+}
+
+/// 모든 작업 종료 여부
+/// 반환: 1=종료, 0=진행 중
+#[no_mangle]
+pub extern "C" fn export_queue_is_finished(queue: *mut c_void) -> i32 {
+    if queue.is_null() {
+        return 1;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        if queue_ref.is_finished() { 1 } else { 0 }
+    }
+}
+
+/// 작업 취소 (대기 중이면 건너뜀)
+#[no_mangle]
+pub extern "C" fn export_queue_cancel_job(queue: *mut c_void, index: u32) -> i32 {
+    if queue.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        if !queue_ref.cancel_job(index as usize) {
+            return ErrorCode::InvalidParam as i32;
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 전체 작업 취소
+#[no_mangle]
+pub extern "C" fn export_queue_cancel_all(queue: *mut c_void) -> i32 {
+    if queue.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let queue_ref = &*(queue as *const ExportQueue);
+        queue_ref.cancel_all();
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// ExportQueue 파괴 (실행 중 작업은 취소)
+#[no_mangle]
+pub extern "C" fn export_queue_destroy(queue: *mut c_void) -> i32 {
+    if queue.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(queue as *mut ExportQueue);
+    }
+
+    ErrorCode::Success as i32
+}
@@ -0,0 +1,59 @@
+// FFI 핸들 레지스트리 - Timeline/Renderer/ExportJob/ThumbnailSession/Playback 핸들 검증
+// C#에 넘기는 핸들은 메모리 주소가 아닌 HandleTable id (포인터 크기 값, C#은 그대로 IntPtr로 보관)
+// 파괴된/다른 종류의/잘못된 핸들은 역참조 없이 ERROR_INVALID_HANDLE 반환
+// 조회는 Arc 복제 → 호출 중에 다른 스레드가 destroy해도 호출이 끝날 때까지 객체 유지
+
+use super::thumbnail::ThumbnailSession;
+use super::types::report_error;
+use crate::encoding::exporter::ExportJob;
+use crate::playback::PlaybackController;
+use crate::rendering::Renderer;
+use crate::timeline::Timeline;
+use crate::utils::handle_table::HandleTable;
+use std::ffi::c_void;
+use std::sync::{Arc, Mutex};
+
+/// 종류별 핸들 레지스트리
+pub struct Registry<T> {
+    name: &'static str,
+    table: Mutex<HandleTable<T>>,
+}
+
+impl<T> Registry<T> {
+    const fn new(name: &'static str, tag: u8) -> Self {
+        Self { name, table: Mutex::new(HandleTable::new(tag)) }
+    }
+
+    /// 객체 등록 → C#에 넘길 핸들
+    pub fn insert(&self, value: Arc<T>) -> *mut c_void {
+        let id = match self.table.lock() {
+            Ok(mut table) => table.insert(value),
+            Err(_) => 0,
+        };
+        std::ptr::without_provenance_mut(id as usize)
+    }
+
+    /// 핸들 → 객체 (무효 핸들이면 에러 기록 후 None)
+    pub fn get(&self, handle: *const c_void) -> Option<Arc<T>> {
+        let value = self.table.lock().ok()?.get(handle.addr() as u64);
+        if value.is_none() {
+            report_error(format!("Invalid {} handle: {:p}", self.name, handle));
+        }
+        value
+    }
+
+    /// 핸들 등록 해제 (무효 핸들이면 None)
+    pub fn remove(&self, handle: *const c_void) -> Option<Arc<T>> {
+        let value = self.table.lock().ok()?.remove(handle.addr() as u64);
+        if value.is_none() {
+            report_error(format!("Invalid {} handle: {:p}", self.name, handle));
+        }
+        value
+    }
+}
+
+pub static TIMELINES: Registry<Mutex<Timeline>> = Registry::new("Timeline", 1);
+pub static RENDERERS: Registry<Mutex<Renderer>> = Registry::new("Renderer", 2);
+pub static EXPORT_JOBS: Registry<ExportJob> = Registry::new("ExportJob", 3);
+pub static THUMBNAIL_SESSIONS: Registry<ThumbnailSession> = Registry::new("ThumbnailSession", 4);
+pub static PLAYBACKS: Registry<PlaybackController> = Registry::new("Playback", 5);
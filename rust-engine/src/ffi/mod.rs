@@ -2,14 +2,20 @@
 // C# P/Invoke와 연동되는 C ABI 함수들
 
 pub mod types;
+pub mod handle;
 pub mod timeline;
 pub mod renderer;
 pub mod exporter;
 pub mod audio;
 pub mod thumbnail;
 pub mod audio_playback;
+pub mod subtitle;
+pub mod proxy;
+pub mod playback;
 
-use std::ffi::CString;
+use crate::utils::error::{self, ErrorCategory};
+use crate::utils::log::{self, LogCallback, LogLevel};
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 
 /// 문자열 메모리 해제
@@ -22,6 +28,62 @@ pub extern "C" fn string_free(ptr: *mut c_char) {
     }
 }
 
+/// 엔진 로그 콜백 등록 (null이면 해제 → stderr 출력)
+/// - callback(level, message): level 0=Debug, 1=Info, 2=Warn, 3=Error, message는 UTF-8 (콜백 안에서만 유효)
+/// - 렌더/Export/디코딩 워커 등 메시지를 낸 스레드에서 호출되므로 UI 갱신은 호스트가 마샬링
+#[no_mangle]
+pub extern "C" fn engine_set_log_callback(callback: Option<LogCallback>) -> i32 {
+    log::set_callback(callback);
+    types::ErrorCode::Success as i32
+}
+
+/// 엔진 로그 최소 레벨 설정 (0=Debug ~ 3=Error, 기본: 릴리스 빌드 Info / 디버그 빌드 Debug)
+#[no_mangle]
+pub extern "C" fn engine_set_log_level(level: i32) -> i32 {
+    log::set_level(LogLevel::from_i32(level));
+    types::ErrorCode::Success as i32
+}
+
+/// 호출 스레드의 마지막 에러 조회 (FFI 함수가 실패를 반환한 직후, 같은 스레드에서 호출)
+/// - out_error.category: ErrorCategory (0=없음, 1=열기 실패, 2=파일 없음, 3=권한, 4=코덱 미지원,
+///   5=잘못된 데이터, 6=디스크 공간 부족, 7=네트워크, 8=메모리 부족, 9=취소, 99=기타)
+/// - out_error.message: 전체 메시지 (string_free로 해제, 없으면 null)
+/// - clear=1이면 조회 후 지움 (다음 실패와 구분)
+#[no_mangle]
+pub extern "C" fn engine_get_last_error(out_error: *mut types::CError, clear: i32) -> i32 {
+    if out_error.is_null() {
+        return types::ErrorCode::NullPointer as i32;
+    }
+
+    let (category, message) = match error::last_error() {
+        Some((category, message)) => (category, CString::new(message).ok()),
+        None => (ErrorCategory::None, None),
+    };
+    if clear != 0 {
+        error::clear_last_error();
+    }
+
+    unsafe {
+        *out_error = types::CError {
+            category: category as i32,
+            message: message.map_or(std::ptr::null_mut(), CString::into_raw),
+        };
+    }
+
+    types::ErrorCode::Success as i32
+}
+
+/// 에러 메시지 분류 (exporter_get_error 등 백그라운드 작업 에러 → ErrorCategory)
+#[no_mangle]
+pub extern "C" fn engine_classify_error(message: *const c_char) -> i32 {
+    if message.is_null() {
+        return ErrorCategory::None as i32;
+    }
+
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    error::classify(&message) as i32
+}
+
 /// Hello World 테스트 함수
 #[no_mangle]
 pub extern "C" fn hello_world() -> *mut c_char {
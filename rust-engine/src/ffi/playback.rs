@@ -0,0 +1,257 @@
+// Playback FFI - 엔진 프레임 클럭 기반 프리뷰 재생
+// 호스트 UI 타이머 대신 Rust 워커가 프레임 경계마다 렌더링 (늦은 프레임은 드롭)
+// 프레임 수신: 콜백(워커 스레드에서 즉시) 또는 playback_acquire_frame(트리플 버퍼, UI 렌더 루프에서 poll)
+// A/V 동기화: audio_playback_attach_video 또는 playback_report_audio_position으로 오디오 클럭 연결
+
+use crate::ffi::handle::{PLAYBACKS, RENDERERS};
+use crate::ffi::types::{report_error, ErrorCode};
+use crate::playback::{FrameSink, PlaybackController};
+use std::ffi::c_void;
+use std::sync::Arc;
+
+/// 재생 프레임 콜백 (재생 워커 스레드에서 호출)
+/// - data: RGBA width x height (콜백 안에서만 유효 → 필요하면 복사)
+/// - timestamp_ms: 프로젝트 fps 격자에 맞춘 프레임 시간
+pub type PlaybackFrameCallback = extern "C" fn(
+    user_data: *mut c_void,
+    timestamp_ms: i64,
+    width: u32,
+    height: u32,
+    data: *const u8,
+    data_size: usize,
+);
+
+/// 콜백 user_data (C# GCHandle 등, 워커 스레드로 전달만 하고 역참조하지 않음)
+struct UserData(*mut c_void);
+
+// SAFETY: 포인터는 콜백 인자로 되돌려줄 뿐 Rust에서 접근하지 않음
+unsafe impl Send for UserData {}
+
+/// 재생 컨트롤러 생성 (정지 상태, 0ms, 1배속)
+/// - callback: null이면 콜백 없이 playback_acquire_frame으로만 수신
+/// - Renderer 핸들과 별개 수명 — Renderer가 먼저 파괴되면 재생 워커는 멈춤
+/// - 재생 중 같은 Renderer로 renderer_render_frame을 호출하면 Renderer lock을 번갈아 사용
+#[no_mangle]
+pub extern "C" fn playback_create(
+    renderer: *mut c_void,
+    callback: Option<PlaybackFrameCallback>,
+    user_data: *mut c_void,
+    out_playback: *mut *mut c_void,
+) -> i32 {
+    if renderer.is_null() || out_playback.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_arc = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let timeline = match renderer_arc.lock() {
+        Ok(r) => r.timeline(),
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+
+    let sink = callback.map(|callback| {
+        let user_data = UserData(user_data);
+        Box::new(move |frame: &crate::rendering::RenderedFrame, _index: i64| {
+            let user_data = &user_data;
+            callback(user_data.0, frame.timestamp_ms, frame.width, frame.height, frame.data.as_ptr(), frame.data.len());
+        }) as FrameSink
+    });
+
+    // 워커는 Renderer Weak만 보관 (핸들 Arc의 소유권은 레지스트리)
+    let controller = PlaybackController::start(Arc::downgrade(&renderer_arc), timeline, sink);
+    unsafe {
+        *out_playback = PLAYBACKS.insert(Arc::new(controller));
+    }
+    ErrorCode::Success as i32
+}
+
+/// 재생 컨트롤러 파괴 (워커 종료 대기 → 반환 후 콜백 호출 없음)
+/// 콜백 안에서 호출하지 말 것 (워커 자신은 기다리지 않으므로 종료 시점 보장 안 됨)
+#[no_mangle]
+pub extern "C" fn playback_destroy(playback: *mut c_void) -> i32 {
+    if playback.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if PLAYBACKS.remove(playback).is_none() {
+        return ErrorCode::InvalidHandle as i32;
+    }
+    ErrorCode::Success as i32
+}
+
+/// 핸들 조회 후 명령 실행
+fn with_playback(playback: *mut c_void, f: impl FnOnce(&PlaybackController) -> i32) -> i32 {
+    if playback.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    match PLAYBACKS.get(playback) {
+        Some(controller) => f(&controller),
+        None => ErrorCode::InvalidHandle as i32,
+    }
+}
+
+/// 재생 시작 (끝에서 멈춘 상태면 처음부터)
+#[no_mangle]
+pub extern "C" fn playback_play(playback: *mut c_void) -> i32 {
+    with_playback(playback, |c| {
+        c.play();
+        ErrorCode::Success as i32
+    })
+}
+
+/// 일시정지 (현재 위치 유지)
+#[no_mangle]
+pub extern "C" fn playback_pause(playback: *mut c_void) -> i32 {
+    with_playback(playback, |c| {
+        c.pause();
+        ErrorCode::Success as i32
+    })
+}
+
+/// 위치 이동 (재생 상태 유지, 정지 중이면 해당 프레임 1회 렌더링)
+#[no_mangle]
+pub extern "C" fn playback_seek(playback: *mut c_void, timestamp_ms: i64) -> i32 {
+    with_playback(playback, |c| {
+        c.seek(timestamp_ms);
+        ErrorCode::Success as i32
+    })
+}
+
+/// 배속 설정 (0 < |rate| ≤ 16, 음수 = 역방향 재생 — J-K-L 셔틀은 -1/-2/-4 등)
+#[no_mangle]
+pub extern "C" fn playback_set_rate(playback: *mut c_void, rate: f64) -> i32 {
+    with_playback(playback, |c| match c.set_rate(rate) {
+        Ok(()) => ErrorCode::Success as i32,
+        Err(e) => {
+            report_error(e);
+            ErrorCode::InvalidParam as i32
+        }
+    })
+}
+
+/// 현재 재생 위치/상태 조회 (out_state: 0=Paused, 1=Playing, 2=Ended)
+#[no_mangle]
+pub extern "C" fn playback_get_position(
+    playback: *mut c_void,
+    out_position_ms: *mut i64,
+    out_state: *mut i32,
+) -> i32 {
+    if out_position_ms.is_null() || out_state.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    with_playback(playback, |c| {
+        unsafe {
+            *out_position_ms = c.position_ms();
+            *out_state = c.state() as i32;
+        }
+        ErrorCode::Success as i32
+    })
+}
+
+/// 재생 통계 (표시 프레임 수, 렌더링이 늦어 건너뛴 프레임 수)
+#[no_mangle]
+pub extern "C" fn playback_get_stats(
+    playback: *mut c_void,
+    out_presented_frames: *mut u64,
+    out_dropped_frames: *mut u64,
+) -> i32 {
+    if out_presented_frames.is_null() || out_dropped_frames.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    with_playback(playback, |c| {
+        let stats = c.stats();
+        unsafe {
+            *out_presented_frames = stats.presented_frames;
+            *out_dropped_frames = stats.dropped_frames;
+        }
+        ErrorCode::Success as i32
+    })
+}
+
+/// 호스트 오디오 출력 위치 보고 (pull 방식 audio_engine 사용 시 — 출력 장치 콜백마다 호출)
+/// position_ms: 지금 스피커에서 들리는 타임라인 위치 (출력 지연 반영), playing: 0이면 정지 중
+/// 보고하면 비디오 재생이 이 위치에 종속 (1배속 재생 중에만, 다른 오디오 클럭 연결은 대체)
+#[no_mangle]
+pub extern "C" fn playback_report_audio_position(playback: *mut c_void, position_ms: f64, playing: i32) -> i32 {
+    if !position_ms.is_finite() {
+        return ErrorCode::InvalidParam as i32;
+    }
+    with_playback(playback, |c| {
+        c.report_audio_position(position_ms, playing != 0);
+        ErrorCode::Success as i32
+    })
+}
+
+/// 오디오 클럭 연결 해제 (비디오 자체 클럭으로 재생)
+#[no_mangle]
+pub extern "C" fn playback_detach_audio(playback: *mut c_void) -> i32 {
+    with_playback(playback, |c| {
+        c.set_audio_clock(None);
+        ErrorCode::Success as i32
+    })
+}
+
+/// A/V 동기화 통계
+/// - out_drift_ms: 마지막 측정 drift (비디오 - 오디오, 양수 = 비디오가 앞섬)
+/// - out_max_drift_ms: drift 절댓값 최대
+/// - out_corrections / out_resyncs: 점진 보정 / 즉시 맞춤 횟수
+#[no_mangle]
+pub extern "C" fn playback_get_sync_stats(
+    playback: *mut c_void,
+    out_drift_ms: *mut f64,
+    out_max_drift_ms: *mut f64,
+    out_corrections: *mut u64,
+    out_resyncs: *mut u64,
+) -> i32 {
+    if out_drift_ms.is_null() || out_max_drift_ms.is_null() || out_corrections.is_null() || out_resyncs.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    with_playback(playback, |c| {
+        let stats = c.sync_stats();
+        unsafe {
+            *out_drift_ms = stats.drift_ms;
+            *out_max_drift_ms = stats.max_drift_ms;
+            *out_corrections = stats.corrections;
+            *out_resyncs = stats.resyncs;
+        }
+        ErrorCode::Success as i32
+    })
+}
+
+/// 최신 재생 프레임 수신 (새 프레임이 없으면 out_handle = null)
+/// 버퍼는 renderer_poll_frame과 동일 — out_handle을 renderer_release_frame으로 해제
+#[no_mangle]
+pub extern "C" fn playback_acquire_frame(
+    playback: *mut c_void,
+    out_timestamp_ms: *mut i64,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *const u8,
+    out_data_size: *mut usize,
+    out_handle: *mut *mut c_void,
+) -> i32 {
+    if out_timestamp_ms.is_null() || out_width.is_null() || out_height.is_null()
+        || out_data.is_null() || out_data_size.is_null() || out_handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    with_playback(playback, |c| unsafe {
+        *out_timestamp_ms = 0;
+        *out_width = 0;
+        *out_height = 0;
+        *out_data = std::ptr::null();
+        *out_data_size = 0;
+        *out_handle = std::ptr::null_mut();
+
+        if let Some(frame) = c.acquire_frame() {
+            *out_timestamp_ms = frame.timestamp_ms;
+            *out_width = frame.width;
+            *out_height = frame.height;
+            *out_data_size = frame.data.len();
+            *out_data = frame.data.as_ptr();
+            *out_handle = Box::into_raw(Box::new(frame.data)) as *mut c_void;
+        }
+        ErrorCode::Success as i32
+    })
+}
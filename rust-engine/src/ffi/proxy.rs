@@ -0,0 +1,195 @@
+// 프록시 FFI - C# P/Invoke 연동
+// 프록시 생성 작업(시작/진행률/취소/파괴) + 프록시 경로 계산/등록
+// 프리뷰 전환은 renderer_set_use_proxies
+
+use crate::ffi::types::{report_error, ErrorCode};
+use crate::utils::proxy::{self, ProxyJob, DEFAULT_PROXY_HEIGHT};
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::PathBuf;
+
+/// UTF-8 C 문자열 → 경로
+unsafe fn path_arg(s: *const c_char) -> Option<PathBuf> {
+    CStr::from_ptr(s).to_str().ok().map(PathBuf::from)
+}
+
+/// 프록시 생성 시작 (백그라운드 스레드)
+/// - source_path: 원본 미디어 경로
+/// - proxy_dir: 프록시 저장 디렉토리 (없으면 생성, 파일 이름은 원본 내용 해시 기준)
+/// - height: 프록시 높이 (0이면 540, 원본보다 크면 원본 크기)
+/// - out_job: ProxyJob 핸들 (proxy_job_destroy로 해제)
+///
+/// 완료되면 원본 → 프록시가 자동 등록됨
+#[no_mangle]
+pub extern "C" fn proxy_job_start(
+    source_path: *const c_char,
+    proxy_dir: *const c_char,
+    height: u32,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    if source_path.is_null() || proxy_dir.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let (source, dir) = match (path_arg(source_path), path_arg(proxy_dir)) {
+            (Some(source), Some(dir)) => (source, dir),
+            _ => return ErrorCode::InvalidParam as i32,
+        };
+        let height = if height == 0 { DEFAULT_PROXY_HEIGHT } else { height };
+        let output = match proxy::proxy_path(&source, &dir, height) {
+            Ok(path) => path,
+            Err(e) => {
+                report_error(format!("proxy_job_start: {}: {}", source.display(), e));
+                return ErrorCode::Io as i32;
+            }
+        };
+
+        let job = ProxyJob::start(source, output, height);
+        *out_job = Box::into_raw(Box::new(job)) as *mut c_void;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 프록시 생성 진행률 (0~100)
+#[no_mangle]
+pub extern "C" fn proxy_job_get_progress(job: *mut c_void) -> u32 {
+    if job.is_null() {
+        return 0;
+    }
+
+    unsafe {
+        let job_ref = &*(job as *const ProxyJob);
+        job_ref.progress()
+    }
+}
+
+/// 프록시 생성 완료 여부
+/// 반환: 1=완료(성공/실패/취소), 0=진행중
+#[no_mangle]
+pub extern "C" fn proxy_job_is_finished(job: *mut c_void) -> i32 {
+    if job.is_null() {
+        return 1; // null이면 완료로 처리
+    }
+
+    unsafe {
+        let job_ref = &*(job as *const ProxyJob);
+        if job_ref.is_finished() { 1 } else { 0 }
+    }
+}
+
+/// 프록시 생성 에러 메시지
+/// out_error: 에러 문자열 포인터 (없으면 null), string_free()로 해제
+#[no_mangle]
+pub extern "C" fn proxy_job_get_error(job: *mut c_void, out_error: *mut *mut c_char) -> i32 {
+    if job.is_null() || out_error.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let job_ref = &*(job as *const ProxyJob);
+        *out_error = job_ref.error()
+            .and_then(|msg| CString::new(msg).ok())
+            .map_or(std::ptr::null_mut(), CString::into_raw);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 프록시 생성 취소 (작성 중인 파일 삭제)
+#[no_mangle]
+pub extern "C" fn proxy_job_cancel(job: *mut c_void) -> i32 {
+    if job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let job_ref = &*(job as *const ProxyJob);
+        job_ref.cancel();
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// ProxyJob 파괴 (진행 중이면 백그라운드 생성은 계속됨, 중단하려면 먼저 proxy_job_cancel)
+#[no_mangle]
+pub extern "C" fn proxy_job_destroy(job: *mut c_void) -> i32 {
+    if job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(job as *mut ProxyJob);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 원본의 프록시 파일 경로 계산 (프로젝트 재오픈 시 기존 프록시 확인용, 파일 존재 여부는 확인 안 함)
+/// - height: 0이면 540
+/// - out_path: string_free()로 해제
+#[no_mangle]
+pub extern "C" fn proxy_get_path(
+    source_path: *const c_char,
+    proxy_dir: *const c_char,
+    height: u32,
+    out_path: *mut *mut c_char,
+) -> i32 {
+    if source_path.is_null() || proxy_dir.is_null() || out_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let (source, dir) = match (path_arg(source_path), path_arg(proxy_dir)) {
+            (Some(source), Some(dir)) => (source, dir),
+            _ => return ErrorCode::InvalidParam as i32,
+        };
+        let height = if height == 0 { DEFAULT_PROXY_HEIGHT } else { height };
+        let path = match proxy::proxy_path(&source, &dir, height) {
+            Ok(path) => path,
+            Err(e) => {
+                report_error(format!("proxy_get_path: {}: {}", source.display(), e));
+                return ErrorCode::Io as i32;
+            }
+        };
+        match CString::new(path.to_string_lossy().into_owned()) {
+            Ok(s) => *out_path = s.into_raw(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 이미 있는 프록시 파일 등록 (프로젝트 재오픈 시 재생성 없이 사용)
+#[no_mangle]
+pub extern "C" fn proxy_register(source_path: *const c_char, proxy_path: *const c_char) -> i32 {
+    if source_path.is_null() || proxy_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        match (path_arg(source_path), path_arg(proxy_path)) {
+            (Some(source), Some(proxy)) => proxy::register(&source, &proxy),
+            _ => return ErrorCode::InvalidParam as i32,
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 프록시 등록 해제 (프록시 파일 삭제 전 호출, 이후 프리뷰는 원본 디코딩)
+/// 반환: 등록되어 있지 않으면 InvalidParam
+#[no_mangle]
+pub extern "C" fn proxy_unregister(source_path: *const c_char) -> i32 {
+    if source_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        match path_arg(source_path) {
+            Some(source) if proxy::unregister(&source) => ErrorCode::Success as i32,
+            _ => ErrorCode::InvalidParam as i32,
+        }
+    }
+}
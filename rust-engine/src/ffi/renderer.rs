@@ -1,11 +1,19 @@
 // Renderer FFI - C# 연동
 
-use crate::rendering::Renderer;
-use crate::timeline::Timeline;
-use crate::ffmpeg::Decoder;
-use crate::ffi::types::ErrorCode;
-use std::ffi::{c_void, c_char, CStr};
-use std::sync::{Arc, Mutex};
+use crate::rendering::{PreviewResolution, Renderer};
+use crate::rendering::frame_request::FrameRequester;
+#[cfg(all(windows, feature = "d3d11"))]
+use crate::rendering::shared_texture::SharedTextureWriter;
+use crate::rendering::effects::EffectParams;
+use crate::ffmpeg::{network, probe, Decoder, DecodeLimits, SeekMode};
+use crate::encoding::image_sequence::{write_image, ImageFormat};
+use crate::ffi::handle::{RENDERERS, TIMELINES};
+use crate::ffi::types::{report_error, ErrorCode};
+use crate::subtitle::overlay::SubtitleOverlayList;
+use crate::utils::thumbnail_fit::ThumbnailFit;
+use std::ffi::{c_void, c_char, CStr, CString};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::path::PathBuf;
 
 /// Renderer 생성 (Mutex로 감싸서 thread-safe 보장)
@@ -15,20 +23,18 @@ pub extern "C" fn renderer_create(timeline: *mut c_void, out_renderer: *mut *mut
         return ErrorCode::NullPointer as i32;
     }
 
-    unsafe {
-        // Arc::into_raw()는 *const Mutex<Timeline>을 반환함
-        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
-        let timeline_clone = Arc::clone(&timeline_arc);
-
-        // 원본 Arc의 소유권 유지 (C#이 관리)
-        let _ = Arc::into_raw(timeline_arc);
+    // Timeline Arc 복제 (원본은 핸들 레지스트리가 소유)
+    let timeline_clone = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
 
-        let renderer = Renderer::new(timeline_clone);
-        // CRITICAL: Renderer를 Mutex로 감싸서 동시 접근 방지
-        let renderer_mutex = Box::new(Mutex::new(renderer));
-        *out_renderer = Box::into_raw(renderer_mutex) as *mut c_void;
-
-        // 생성 완료
+    let renderer = Renderer::new(timeline_clone);
+    // CRITICAL: Renderer를 Mutex로 감싸서 동시 접근 방지
+    // Arc: 비동기 프레임 요청 워커가 Weak로 참조
+    let renderer_mutex = Arc::new(Mutex::new(renderer));
+    unsafe {
+        *out_renderer = RENDERERS.insert(renderer_mutex);
     }
 
     ErrorCode::Success as i32
@@ -41,10 +47,14 @@ pub extern "C" fn renderer_destroy(renderer: *mut c_void) -> i32 {
         return ErrorCode::NullPointer as i32;
     }
 
-    unsafe {
-        // Mutex<Renderer>를 Box로 다시 감싸서 drop
-        let _ = Box::from_raw(renderer as *mut Mutex<Renderer>);
-        // 파괴 완료
+    // 핸들 해제 (렌더링 중인 워커/호출이 있으면 끝날 때 해제)
+    if RENDERERS.remove(renderer).is_none() {
+        return ErrorCode::InvalidHandle as i32;
+    }
+
+    // 비동기 프레임 요청 워커 종료
+    if let Ok(mut requesters) = frame_requesters().lock() {
+        requesters.remove(&(renderer as usize));
     }
 
     ErrorCode::Success as i32
@@ -67,7 +77,10 @@ pub extern "C" fn renderer_render_frame(
     }
 
     unsafe {
-        let renderer_mutex = &*(renderer as *const Mutex<Renderer>);
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
 
         let mut renderer_ref = match renderer_mutex.try_lock() {
             Ok(r) => r,
@@ -87,7 +100,8 @@ pub extern "C" fn renderer_render_frame(
                 *out_height = frame.height;
                 *out_data_size = frame.data.len();
 
-                let data_box = frame.data.into_boxed_slice();
+                // 캐시와 공유 중이면 복사, 단독 소유면 그대로 이전
+                let data_box = Arc::unwrap_or_clone(frame.data).into_boxed_slice();
                 *out_data = Box::into_raw(data_box) as *mut u8;
 
                 ErrorCode::Success as i32
@@ -97,7 +111,7 @@ pub extern "C" fn renderer_render_frame(
                 // render_frame Err는 Timeline lock poison 등 심각한 상황이지만,
                 // C#에서 Exception throw → 재생 영구 정지보다는
                 // 프레임 스킵(null) 반환이 더 안전
-                eprintln!("renderer_render_frame error at {}ms: {}", timestamp_ms, e);
+                report_error(format!("renderer_render_frame error at {}ms: {}", timestamp_ms, e));
                 *out_width = 0;
                 *out_height = 0;
                 *out_data = std::ptr::null_mut();
@@ -118,16 +132,15 @@ pub extern "C" fn renderer_set_playback_mode(renderer: *mut c_void, playback: i3
         return ErrorCode::NullPointer as i32;
     }
 
-    unsafe {
-        let renderer_mutex = &*(renderer as *const Mutex<Renderer>);
-        match renderer_mutex.try_lock() {
-            Ok(mut r) => {
-                r.set_playback_mode(playback != 0);
-                ErrorCode::Success as i32
-            }
-            Err(_) => ErrorCode::Success as i32, // busy면 무시 (다음 프레임에서 적용)
-        }
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    // busy면 무시 (다음 프레임에서 적용)
+    if let Ok(mut r) = renderer_mutex.try_lock() {
+        r.set_playback_mode(playback != 0);
     }
+    ErrorCode::Success as i32
 }
 
 /// 프레임 캐시 클리어 (클립 편집 시 C#에서 호출)
@@ -137,15 +150,59 @@ pub extern "C" fn renderer_clear_cache(renderer: *mut c_void) -> i32 {
         return ErrorCode::NullPointer as i32;
     }
 
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    // busy면 무시
+    if let Ok(mut r) = renderer_mutex.try_lock() {
+        r.clear_cache();
+    }
+    ErrorCode::Success as i32
+}
+
+/// 클립 하나의 프레임 캐시 무효화 (LUT 변경 등 클립 단위 편집 시 C#에서 호출)
+#[no_mangle]
+pub extern "C" fn renderer_invalidate_clip(renderer: *mut c_void, clip_id: u64) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    // 무효화가 유실되면 오래된 프레임이 남으므로 lock 대기
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.invalidate_clip(clip_id);
+    ErrorCode::Success as i32
+}
+
+/// 파일 하나의 프레임 캐시 무효화 (미디어 교체/재연결 시 C#에서 호출)
+#[no_mangle]
+pub extern "C" fn renderer_invalidate_file(renderer: *mut c_void, file_path: *const c_char) -> i32 {
+    if renderer.is_null() || file_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
     unsafe {
-        let renderer_mutex = &*(renderer as *const Mutex<Renderer>);
-        match renderer_mutex.try_lock() {
-            Ok(mut r) => {
-                r.clear_cache();
-                ErrorCode::Success as i32
-            }
-            Err(_) => ErrorCode::Success as i32, // busy면 무시
-        }
+        let file_path = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let mut r = match renderer_mutex.lock() {
+            Ok(r) => r,
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        };
+        r.invalidate_file(file_path);
+        ErrorCode::Success as i32
     }
 }
 
@@ -161,25 +218,45 @@ pub extern "C" fn renderer_get_cache_stats(
     }
 
     unsafe {
-        let renderer_mutex = &*(renderer as *const Mutex<Renderer>);
-        match renderer_mutex.try_lock() {
-            Ok(r) => {
-                let (frames, bytes) = r.cache_stats();
-                *out_cached_frames = frames;
-                *out_cache_bytes = bytes;
-                ErrorCode::Success as i32
-            }
-            Err(_) => {
-                *out_cached_frames = 0;
-                *out_cache_bytes = 0;
-                ErrorCode::Success as i32
-            }
-        }
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let (frames, bytes) = match renderer_mutex.try_lock() {
+            Ok(r) => r.cache_stats(),
+            Err(_) => (0, 0),
+        };
+        *out_cached_frames = frames;
+        *out_cache_bytes = bytes;
+        ErrorCode::Success as i32
+    }
+}
+
+/// 클립 이펙트 저장 공통 (타임라인 클립에 저장 → Export에도 적용)
+/// 유실되면 Export 결과가 달라지므로 렌더링 중이어도 lock 대기
+fn set_clip_effects(renderer: *mut c_void, clip_id: u64, params: EffectParams) -> i32 {
+    if params.values().iter().any(|v| !v.is_finite()) {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    if r.set_clip_effects(clip_id, params) {
+        ErrorCode::Success as i32
+    } else {
+        ErrorCode::InvalidParam as i32
     }
 }
 
 /// 클립 이펙트 설정 (C# Inspector Color 탭 Slider에서 호출)
 /// brightness, contrast, saturation, temperature: -1.0 ~ 1.0 (0=원본)
+/// 나머지 파라미터(v2)는 0으로 초기화, 타임라인에 없는 클립이면 InvalidParam
 #[no_mangle]
 pub extern "C" fn renderer_set_clip_effects(
     renderer: *mut c_void,
@@ -193,20 +270,453 @@ pub extern "C" fn renderer_set_clip_effects(
         return ErrorCode::NullPointer as i32;
     }
 
+    set_clip_effects(renderer, clip_id, EffectParams {
+        brightness,
+        contrast,
+        saturation,
+        temperature,
+        ..EffectParams::default()
+    })
+}
+
+/// 클립 이펙트 설정 v2 (기본 4종 + 감마/하이라이트/섀도/틴트/비네팅)
+/// 모든 값: -1.0 ~ 1.0 (0=원본), 한 번의 픽셀 순회로 적용
+#[no_mangle]
+pub extern "C" fn renderer_set_clip_effects_v2(
+    renderer: *mut c_void,
+    clip_id: u64,
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    temperature: f32,
+    gamma: f32,
+    highlights: f32,
+    shadows: f32,
+    tint: f32,
+    vignette: f32,
+) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    set_clip_effects(renderer, clip_id, EffectParams {
+        brightness,
+        contrast,
+        saturation,
+        temperature,
+        gamma,
+        highlights,
+        shadows,
+        tint,
+        vignette,
+    })
+}
+
+/// 클립 이펙트 제거 (모든 파라미터 0, 키프레임 애니메이션은 유지)
+#[no_mangle]
+pub extern "C" fn renderer_clear_clip_effects(renderer: *mut c_void, clip_id: u64) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    set_clip_effects(renderer, clip_id, EffectParams::default())
+}
+
+/// 전체 이펙트/LUT 바이패스 (원본 A/B 비교)
+/// bypass=1: 모든 클립 원본 표시, bypass=0: 이펙트 복원 (파라미터는 유지)
+#[no_mangle]
+pub extern "C" fn renderer_set_effects_bypass(renderer: *mut c_void, bypass: i32) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    // 토글이 누락되면 A/B 상태가 어긋나므로 busy여도 lock 대기
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.set_effects_bypass(bypass != 0);
+    ErrorCode::Success as i32
+}
+
+/// 특정 클립 이펙트/LUT 바이패스
+#[no_mangle]
+pub extern "C" fn renderer_set_clip_effects_bypass(renderer: *mut c_void, clip_id: u64, bypass: i32) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.set_clip_effects_bypass(clip_id, bypass != 0);
+    ErrorCode::Success as i32
+}
+
+/// 디코더 탐색 한도 설정 (스크럽 응답성 vs 정확도 조정)
+/// - max_packets: decode 1회당 최대 패킷 수 (0 = 무제한, 기본 3000)
+/// - max_decode_ms: decode 1회당 최대 소요 시간 (0 = 무제한, 기본 0)
+/// - seek_retries: seek 실패 시 재시도 횟수 (기본 1)
+///
+/// 한도 초과 시 해당 프레임은 스킵되고 이전 프레임 유지
+#[no_mangle]
+pub extern "C" fn renderer_set_decode_limits(
+    renderer: *mut c_void,
+    max_packets: u32,
+    max_decode_ms: u32,
+    seek_retries: u32,
+) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    // 설정 누락 방지 → busy여도 lock 대기
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.set_decode_limits(DecodeLimits {
+        max_packets,
+        max_decode_ms,
+        seek_retries,
+    });
+    ErrorCode::Success as i32
+}
+
+/// 프리뷰 하드웨어 디코딩 on/off (NVDEC/D3D11VA/QSV, 실패 시 소프트웨어 폴백)
+/// enabled=1: 다음 렌더링부터 HW 디코더로 다시 열림
+#[no_mangle]
+pub extern "C" fn renderer_set_hw_decode(renderer: *mut c_void, enabled: i32) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.set_hw_decode(enabled != 0);
+    ErrorCode::Success as i32
+}
+
+/// 프리뷰 해상도 변경 (다음 렌더링부터 새 크기로 디코딩, 캐시/디코더 초기화)
+/// resolution: 0=기본 960x540, 1=프로젝트 1/4, 2=프로젝트 1/2, 3=프로젝트 해상도
+/// out_width/out_height: 적용된 프리뷰 프레임 크기 (자막 오버레이 래스터화 기준)
+#[no_mangle]
+pub extern "C" fn renderer_set_preview_resolution(
+    renderer: *mut c_void,
+    resolution: i32,
+    out_width: *mut u32,
+    out_height: *mut u32,
+) -> i32 {
+    if renderer.is_null() || out_width.is_null() || out_height.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    let resolution = match PreviewResolution::from_i32(resolution) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidParam as i32,
+    };
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    match r.set_preview_resolution(resolution) {
+        Ok((width, height)) => {
+            unsafe {
+                *out_width = width;
+                *out_height = height;
+            }
+            ErrorCode::Success as i32
+        }
+        Err(e) => {
+            report_error(e);
+            ErrorCode::RenderFailed as i32
+        }
+    }
+}
+
+/// 프리뷰 프록시 사용 on/off (등록된 프록시가 있는 클립만, Export는 항상 원본)
+/// 켜져 있는 동안 프록시가 새로 등록/해제되면 다음 렌더링부터 반영
+#[no_mangle]
+pub extern "C" fn renderer_set_use_proxies(renderer: *mut c_void, enabled: i32) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.set_use_proxies(enabled != 0);
+    ErrorCode::Success as i32
+}
+
+/// 하드웨어 디코딩 상태 조회
+/// - out_enabled: HW 디코딩 설정 (1/0)
+/// - out_active_decoders: 실제 HW로 디코딩 중인 디코더 수 (0이면 소프트웨어 폴백)
+#[no_mangle]
+pub extern "C" fn renderer_get_hw_decode_status(
+    renderer: *mut c_void,
+    out_enabled: *mut i32,
+    out_active_decoders: *mut u32,
+) -> i32 {
+    if renderer.is_null() || out_enabled.is_null() || out_active_decoders.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let r = match renderer_mutex.lock() {
+            Ok(r) => r,
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        };
+        let (enabled, active) = r.hw_decode_status();
+        *out_enabled = enabled as i32;
+        *out_active_decoders = active;
+        ErrorCode::Success as i32
+    }
+}
+
+/// 디코더 예산 설정 (동시에 열어 두는 최대 디코더 수, 기본 8)
+/// 초과 시 가장 오래 전에 사용한 파일의 디코더부터 닫고, 다시 필요하면 재오픈
+/// 한 프레임에 보이는 클립 수보다 작으면 매 프레임 재오픈이 발생하므로 그 이상으로 설정
+#[no_mangle]
+pub extern "C" fn renderer_set_decoder_budget(renderer: *mut c_void, max_decoders: u32) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if max_decoders == 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.set_max_decoders(max_decoders as usize);
+    ErrorCode::Success as i32
+}
+
+/// 디코더 예산 조회
+/// - out_max_decoders: 최대 디코더 수
+/// - out_open_decoders: 현재 열린 디코더 수
+#[no_mangle]
+pub extern "C" fn renderer_get_decoder_budget(
+    renderer: *mut c_void,
+    out_max_decoders: *mut u32,
+    out_open_decoders: *mut u32,
+) -> i32 {
+    if renderer.is_null() || out_max_decoders.is_null() || out_open_decoders.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let r = match renderer_mutex.lock() {
+            Ok(r) => r,
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        };
+        *out_max_decoders = r.max_decoders() as u32;
+        *out_open_decoders = r.open_decoder_count() as u32;
+        ErrorCode::Success as i32
+    }
+}
+
+/// 프리뷰 자막 설정
+/// subtitle_list: exporter_create_subtitle_list()로 만든 핸들, 소유권 Rust로 이전 (null이면 자막 제거)
+/// 오버레이는 프리뷰 프레임 크기(기본 960x540, renderer_set_preview_resolution 결과) 기준으로 래스터화해야 함
+#[no_mangle]
+pub extern "C" fn renderer_set_subtitles(renderer: *mut c_void, subtitle_list: *mut c_void) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let subtitles = if subtitle_list.is_null() {
+            None
+        } else {
+            Some(*Box::from_raw(subtitle_list as *mut SubtitleOverlayList))
+        };
+
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let mut r = match renderer_mutex.lock() {
+            Ok(r) => r,
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        };
+        r.set_subtitles(subtitles);
+        ErrorCode::Success as i32
+    }
+}
+
+/// 재생 미리 읽기 프레임 수 설정 (클립당, 기본 8)
+/// frames=0: 미리 읽기 끔 — 재생 중 백그라운드 디코딩 없이 렌더링 시점에 디코딩
+#[no_mangle]
+pub extern "C" fn renderer_set_prefetch_frames(renderer: *mut c_void, frames: u32) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut r = match renderer_mutex.lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    r.set_prefetch_frames(frames);
+    ErrorCode::Success as i32
+}
+
+/// 타임라인 필름스트립 렌더링 (프로젝트 브라우저 썸네일/Export 다이얼로그 미리보기)
+/// frame_count개 타임라인 프레임을 tile_width x tile_height로 축소하여 가로로 이어붙인 RGBA 버퍼
+/// out_data는 renderer_free_frame_data로 해제
+#[no_mangle]
+pub extern "C" fn renderer_render_filmstrip(
+    renderer: *mut c_void,
+    frame_count: u32,
+    tile_width: u32,
+    tile_height: u32,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *mut u8,
+    out_data_size: *mut usize,
+) -> i32 {
+    if renderer.is_null() || out_width.is_null() || out_height.is_null()
+        || out_data.is_null() || out_data_size.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    if frame_count == 0 || tile_width == 0 || tile_height == 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
     unsafe {
-        let renderer_mutex = &*(renderer as *const Mutex<Renderer>);
-        match renderer_mutex.try_lock() {
-            Ok(mut r) => {
-                use crate::rendering::effects::EffectParams;
-                r.set_clip_effects(clip_id, EffectParams {
-                    brightness,
-                    contrast,
-                    saturation,
-                    temperature,
-                });
+        *out_width = 0;
+        *out_height = 0;
+        *out_data = std::ptr::null_mut();
+        *out_data_size = 0;
+
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        // 일회성 요청 → 재생 중이어도 lock 대기 (프레임 스킵 대상 아님)
+        let mut renderer_ref = match renderer_mutex.lock() {
+            Ok(r) => r,
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        };
+
+        match renderer_ref.render_filmstrip(frame_count, tile_width, tile_height) {
+            Ok(strip) => {
+                *out_width = strip.width;
+                *out_height = strip.height;
+                *out_data_size = strip.data.len();
+
+                let data_box = Arc::unwrap_or_clone(strip.data).into_boxed_slice();
+                *out_data = Box::into_raw(data_box) as *mut u8;
+
                 ErrorCode::Success as i32
             }
-            Err(_) => ErrorCode::Success as i32, // busy면 무시 (다음 프레임에서 적용)
+            Err(e) => {
+                report_error(format!("renderer_render_filmstrip error: {}", e));
+                ErrorCode::RenderFailed as i32
+            }
+        }
+    }
+}
+
+/// 현재 프레임을 이미지 파일로 저장 (포스터/썸네일 프레임)
+/// 프리뷰(960x540)가 아닌 프로젝트 해상도로 합성 (이펙트/LUT 포함, 프리뷰 자막 제외)
+/// - format: 0=PNG, 1=JPEG
+/// - quality: JPEG 품질 1~100 (PNG는 무시)
+/// - 별도 Export 렌더러로 렌더링 → 프리뷰 Renderer lock은 타임라인 조회 동안만 사용
+#[no_mangle]
+pub extern "C" fn renderer_export_frame(
+    renderer: *mut c_void,
+    timestamp_ms: i64,
+    path: *const c_char,
+    format: i32,
+    quality: i32,
+) -> i32 {
+    if renderer.is_null() || path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let format = match format {
+        0 => ImageFormat::Png,
+        1 => ImageFormat::Jpeg,
+        _ => return ErrorCode::InvalidParam as i32,
+    };
+    if !(1..=100).contains(&quality) || timestamp_ms < 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    let path = match unsafe { CStr::from_ptr(path) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return ErrorCode::InvalidParam as i32,
+    };
+
+    let timeline = match RENDERERS.get(renderer) {
+        Some(r) => match r.lock() {
+            Ok(r) => r.timeline(),
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        },
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+
+    let frame = match Renderer::render_still(timeline, timestamp_ms) {
+        Ok(frame) => frame,
+        Err(e) => {
+            report_error(format!("renderer_export_frame error at {}ms: {}", timestamp_ms, e));
+            return ErrorCode::RenderFailed as i32;
+        }
+    };
+
+    match write_image(path, format, quality as u32, &frame.data, frame.width, frame.height) {
+        Ok(_) => ErrorCode::Success as i32,
+        Err(e) => {
+            report_error(e);
+            ErrorCode::Io as i32
         }
     }
 }
@@ -226,6 +736,357 @@ pub extern "C" fn renderer_free_frame_data(data: *mut u8, size: usize) -> i32 {
     ErrorCode::Success as i32
 }
 
+/// 프레임 렌더링 (복사 없는 공유 버퍼)
+/// out_data는 캐시와 공유되는 읽기 전용 버퍼 — out_handle을 renderer_release_frame으로 해제하기 전까지 유효
+/// busy/에러 시 renderer_render_frame과 동일하게 빈 프레임 (out_handle = null)
+#[no_mangle]
+pub extern "C" fn renderer_render_frame_shared(
+    renderer: *mut c_void,
+    timestamp_ms: i64,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *const u8,
+    out_data_size: *mut usize,
+    out_handle: *mut *mut c_void,
+) -> i32 {
+    if renderer.is_null() || out_width.is_null() || out_height.is_null()
+        || out_data.is_null() || out_data_size.is_null() || out_handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_width = 0;
+        *out_height = 0;
+        *out_data = std::ptr::null();
+        *out_data_size = 0;
+        *out_handle = std::ptr::null_mut();
+
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let mut renderer_ref = match renderer_mutex.try_lock() {
+            Ok(r) => r,
+            // Mutex busy → 프레임 스킵
+            Err(_) => return ErrorCode::Success as i32,
+        };
+
+        match renderer_ref.render_frame(timestamp_ms) {
+            Ok(frame) => {
+                *out_width = frame.width;
+                *out_height = frame.height;
+                *out_data_size = frame.data.len();
+                *out_data = frame.data.as_ptr();
+                // Arc 참조 하나를 핸들로 보관 (해제 전까지 버퍼 유지)
+                *out_handle = Box::into_raw(Box::new(frame.data)) as *mut c_void;
+            }
+            Err(e) => report_error(format!("renderer_render_frame_shared error at {}ms: {}", timestamp_ms, e)),
+        }
+        ErrorCode::Success as i32
+    }
+}
+
+/// 프레임 스텝 (방향키 1회 = 정확히 1프레임, direction: +1 다음 / -1 이전, 여러 칸도 가능)
+/// 마지막 렌더링 프레임(재생/스크럽 포함) 기준, [0, 마지막 프레임]으로 제한
+/// 버퍼는 renderer_render_frame_shared와 동일 — out_handle을 renderer_release_frame으로 해제
+#[no_mangle]
+pub extern "C" fn renderer_step_frame(
+    renderer: *mut c_void,
+    direction: i32,
+    out_frame_index: *mut i64,
+    out_timestamp_ms: *mut i64,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *const u8,
+    out_data_size: *mut usize,
+    out_handle: *mut *mut c_void,
+) -> i32 {
+    render_frame_at_index(renderer, FrameTarget::Step(direction as i64), out_frame_index, out_timestamp_ms,
+        out_width, out_height, out_data, out_data_size, out_handle)
+}
+
+/// 프레임 번호로 렌더링 (프레임 N = 프로젝트 fps 격자의 N번째, ms 반올림 없음)
+/// 버퍼는 renderer_render_frame_shared와 동일 — out_handle을 renderer_release_frame으로 해제
+#[no_mangle]
+pub extern "C" fn renderer_render_frame_index(
+    renderer: *mut c_void,
+    frame_index: i64,
+    out_frame_index: *mut i64,
+    out_timestamp_ms: *mut i64,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *const u8,
+    out_data_size: *mut usize,
+    out_handle: *mut *mut c_void,
+) -> i32 {
+    render_frame_at_index(renderer, FrameTarget::Index(frame_index), out_frame_index, out_timestamp_ms,
+        out_width, out_height, out_data, out_data_size, out_handle)
+}
+
+/// 프레임 번호 기반 렌더링 대상
+enum FrameTarget {
+    /// 현재 프레임에서 상대 이동
+    Step(i64),
+    /// 절대 프레임 번호
+    Index(i64),
+}
+
+/// renderer_step_frame / renderer_render_frame_index 공통
+/// 사용자 입력이므로 try_lock 스킵 없이 대기 (재생 워커와 lock 번갈아 사용)
+#[allow(clippy::too_many_arguments)]
+fn render_frame_at_index(
+    renderer: *mut c_void,
+    target: FrameTarget,
+    out_frame_index: *mut i64,
+    out_timestamp_ms: *mut i64,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *const u8,
+    out_data_size: *mut usize,
+    out_handle: *mut *mut c_void,
+) -> i32 {
+    if renderer.is_null() || out_frame_index.is_null() || out_timestamp_ms.is_null()
+        || out_width.is_null() || out_height.is_null()
+        || out_data.is_null() || out_data_size.is_null() || out_handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_frame_index = 0;
+        *out_timestamp_ms = 0;
+        *out_width = 0;
+        *out_height = 0;
+        *out_data = std::ptr::null();
+        *out_data_size = 0;
+        *out_handle = std::ptr::null_mut();
+
+        let renderer_mutex = match RENDERERS.get(renderer) {
+            Some(r) => r,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let mut renderer_ref = match renderer_mutex.lock() {
+            Ok(r) => r,
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        };
+
+        let result = match target {
+            FrameTarget::Step(direction) => renderer_ref.step_frame(direction),
+            FrameTarget::Index(index) => renderer_ref.render_frame_index(index),
+        };
+        match result {
+            Ok(frame) => {
+                *out_frame_index = renderer_ref.current_frame();
+                *out_timestamp_ms = frame.timestamp_ms;
+                *out_width = frame.width;
+                *out_height = frame.height;
+                *out_data_size = frame.data.len();
+                *out_data = frame.data.as_ptr();
+                *out_handle = Box::into_raw(Box::new(frame.data)) as *mut c_void;
+                ErrorCode::Success as i32
+            }
+            Err(e) => {
+                report_error(format!("renderer frame index render error: {}", e));
+                ErrorCode::RenderFailed as i32
+            }
+        }
+    }
+}
+
+/// renderer_render_frame_shared 프레임 해제
+#[no_mangle]
+pub extern "C" fn renderer_release_frame(handle: *mut c_void) -> i32 {
+    if handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(handle as *mut Arc<Vec<u8>>);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// GPU 프리뷰 합성 on/off (enabled=1 켜기, 0 끄기)
+/// GPU 어댑터가 없거나 gpu feature 없이 빌드되면 RenderFailed (CPU 합성 유지)
+#[no_mangle]
+pub extern "C" fn renderer_set_gpu_compositing(renderer: *mut c_void, enabled: i32) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_mutex = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let result = match renderer_mutex.lock() {
+        Ok(mut r) => r.set_gpu_compositing(enabled != 0),
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    match result {
+        Ok(()) => ErrorCode::Success as i32,
+        Err(e) => {
+            report_error(format!("renderer_set_gpu_compositing: {}", e));
+            ErrorCode::RenderFailed as i32
+        }
+    }
+}
+
+/// 비동기 프레임 요청 워커 (Renderer 핸들별, 첫 요청 시 생성, renderer_destroy에서 종료)
+fn frame_requesters() -> &'static Mutex<HashMap<usize, FrameRequester>> {
+    static REQUESTERS: OnceLock<Mutex<HashMap<usize, FrameRequester>>> = OnceLock::new();
+    REQUESTERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 스크럽 프레임 비동기 요청 (디코딩을 기다리지 않고 즉시 반환)
+/// 렌더링 시작 전에 새 요청이 오면 이전 요청은 버림 — 결과는 renderer_poll_frame으로 수신
+#[no_mangle]
+pub extern "C" fn renderer_request_frame_async(renderer: *mut c_void, timestamp_ms: i64) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let renderer_arc = match RENDERERS.get(renderer) {
+        Some(r) => r,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    let mut requesters = match frame_requesters().lock() {
+        Ok(r) => r,
+        Err(_) => return ErrorCode::RenderFailed as i32,
+    };
+    // 핸들 Arc의 소유권은 레지스트리가 유지, 워커는 Weak만 보관
+    let requester = requesters.entry(renderer as usize)
+        .or_insert_with(|| FrameRequester::start(Arc::downgrade(&renderer_arc)));
+    requester.request(timestamp_ms);
+    ErrorCode::Success as i32
+}
+
+/// 비동기 요청 프레임 수신 (완료된 최신 프레임, 없으면 out_handle = null)
+/// 버퍼는 renderer_render_frame_shared와 동일 — out_handle을 renderer_release_frame으로 해제
+#[no_mangle]
+pub extern "C" fn renderer_poll_frame(
+    renderer: *mut c_void,
+    out_timestamp_ms: *mut i64,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *const u8,
+    out_data_size: *mut usize,
+    out_handle: *mut *mut c_void,
+) -> i32 {
+    if renderer.is_null() || out_timestamp_ms.is_null() || out_width.is_null() || out_height.is_null()
+        || out_data.is_null() || out_data_size.is_null() || out_handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_timestamp_ms = 0;
+        *out_width = 0;
+        *out_height = 0;
+        *out_data = std::ptr::null();
+        *out_data_size = 0;
+        *out_handle = std::ptr::null_mut();
+
+        let frame = match frame_requesters().lock() {
+            Ok(requesters) => requesters.get(&(renderer as usize)).and_then(|r| r.poll()),
+            Err(_) => return ErrorCode::RenderFailed as i32,
+        };
+        if let Some(frame) = frame {
+            *out_timestamp_ms = frame.timestamp_ms;
+            *out_width = frame.width;
+            *out_height = frame.height;
+            *out_data_size = frame.data.len();
+            *out_data = frame.data.as_ptr();
+            *out_handle = Box::into_raw(Box::new(frame.data)) as *mut c_void;
+        }
+        ErrorCode::Success as i32
+    }
+}
+
+/// 프레임을 호스트 소유 공유 D3D11 텍스처에 직접 렌더링 (RGBA 바이트 복사/C# 비트맵 업로드 생략)
+/// shared_handle: IDXGIResource::GetSharedHandle 또는 IDXGIResource1::CreateSharedHandle 핸들
+/// 텍스처: B8G8R8A8/R8G8B8A8_UNORM, 프리뷰 출력 크기 (960x540), 키드 뮤텍스면 키 0
+/// busy면 Success + out_written = 0 (renderer_render_frame의 프레임 스킵과 동일)
+#[no_mangle]
+pub extern "C" fn renderer_render_frame_to_texture(
+    renderer: *mut c_void,
+    timestamp_ms: i64,
+    shared_handle: *mut c_void,
+    out_written: *mut i32,
+) -> i32 {
+    if renderer.is_null() || shared_handle.is_null() || out_written.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_written = 0;
+
+        #[cfg(all(windows, feature = "d3d11"))]
+        {
+            let renderer_mutex = match RENDERERS.get(renderer) {
+                Some(r) => r,
+                None => return ErrorCode::InvalidHandle as i32,
+            };
+            let frame = match renderer_mutex.try_lock() {
+                Ok(mut r) => match r.render_frame(timestamp_ms) {
+                    Ok(frame) => frame,
+                    Err(e) => {
+                        report_error(format!("renderer_render_frame_to_texture error at {}ms: {}", timestamp_ms, e));
+                        return ErrorCode::Success as i32;
+                    }
+                },
+                // Mutex busy → 프레임 스킵
+                Err(_) => return ErrorCode::Success as i32,
+            };
+
+            let writer = match SharedTextureWriter::global() {
+                Ok(writer) => writer,
+                Err(e) => {
+                    report_error(format!("renderer_render_frame_to_texture: {}", e));
+                    return ErrorCode::RenderFailed as i32;
+                }
+            };
+            let result = match writer.lock() {
+                Ok(mut w) => w.write(shared_handle, &frame),
+                Err(_) => return ErrorCode::RenderFailed as i32,
+            };
+            match result {
+                Ok(()) => {
+                    *out_written = 1;
+                    ErrorCode::Success as i32
+                }
+                Err(e) => {
+                    report_error(format!("renderer_render_frame_to_texture: {}", e));
+                    ErrorCode::RenderFailed as i32
+                }
+            }
+        }
+
+        #[cfg(not(all(windows, feature = "d3d11")))]
+        {
+            report_error(format!(
+                "renderer_render_frame_to_texture: built without D3D11 output (feature \"d3d11\", Windows only), t={}ms",
+                timestamp_ms
+            ));
+            ErrorCode::RenderFailed as i32
+        }
+    }
+}
+
+/// 공유 텍스처 해제 전 호출 (엔진이 열어둔 참조 정리, 호스트 리사이즈 시)
+#[no_mangle]
+pub extern "C" fn renderer_release_texture(shared_handle: *mut c_void) -> i32 {
+    if shared_handle.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    #[cfg(all(windows, feature = "d3d11"))]
+    if let Ok(writer) = SharedTextureWriter::global() {
+        if let Ok(mut w) = writer.lock() {
+            w.forget(shared_handle);
+        }
+    }
+    ErrorCode::Success as i32
+}
+
 /// 비디오 파일 정보 조회 (duration, width, height, fps)
 #[no_mangle]
 pub extern "C" fn get_video_info(
@@ -252,7 +1113,7 @@ pub extern "C" fn get_video_info(
         let decoder = match Decoder::open(&path) {
             Ok(d) => d,
             Err(e) => {
-                eprintln!("get_video_info: Failed to open: {}", e);
+                report_error(format!("get_video_info: Failed to open: {}", e));
                 return ErrorCode::Ffmpeg as i32;
             }
         };
@@ -266,6 +1127,63 @@ pub extern "C" fn get_video_info(
     ErrorCode::Success as i32
 }
 
+/// 미디어 파일 전체 스트림 메타데이터 조회 (디코더를 열지 않아 빠름)
+/// file_path: 로컬 경로 또는 URL (http/https/rtmp/rtsp 등, network_set_timeouts 적용)
+/// out_json: {"format":"mov,mp4,m4a,3gp,3g2,mj2","duration_ms":..,"bitrate":..,"streams":[
+///   {"index":0,"type":"video","codec":"h264","width":1920,"height":1080,"pixel_format":"yuv420p","bit_depth":8,
+///    "fps":29.97,"duration_ms":..,"bitrate":..,"sample_rate":0,"channels":0,"channel_layout":null,"language":"und","rotation":90,
+///    "title":null,"default":true}, ...]}
+/// rotation: 표시 시 시계 방향 회전 (0/90/180/270), 문자열 항목은 없으면 null
+/// 라이브 스트림은 duration_ms = 0
+/// 반환 후 string_free()로 해제 필요
+#[no_mangle]
+pub extern "C" fn media_probe(file_path: *const c_char, out_json: *mut *mut c_char) -> i32 {
+    if file_path.is_null() || out_json.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_json = std::ptr::null_mut();
+
+        let path = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => PathBuf::from(s),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let info = match probe::probe(&path) {
+            Ok(info) => info,
+            Err(e) => {
+                report_error(format!("media_probe: {}", e));
+                return ErrorCode::Ffmpeg as i32;
+            }
+        };
+
+        match CString::new(info.to_json()) {
+            Ok(c_str) => *out_json = c_str.into_raw(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 네트워크 소스(URL) 타임아웃 설정 (이후 여는 소스부터 적용, 0이면 기본값 열기 10초/읽기 15초)
+/// - open_timeout_ms: 연결 + 스트림 정보 분석 제한 시간
+/// - read_timeout_ms: 데이터 수신이 멈춘 뒤 읽기를 실패 처리하기까지의 시간
+#[no_mangle]
+pub extern "C" fn network_set_timeouts(open_timeout_ms: u32, read_timeout_ms: u32) -> i32 {
+    network::set_timeouts(open_timeout_ms, read_timeout_ms);
+    ErrorCode::Success as i32
+}
+
+/// 열려 있는 모든 네트워크 소스의 블로킹 읽기 중단 (다른 스레드에서 호출 가능)
+/// 응답 없는 스트림에 묶인 렌더/썸네일 호출이 즉시 에러로 반환되고, 다음 요청 때 소스를 다시 연다
+#[no_mangle]
+pub extern "C" fn network_interrupt_all() -> i32 {
+    network::interrupt_all();
+    ErrorCode::Success as i32
+}
+
 /// 비디오 썸네일 생성 (스탠드얼론 함수 - 레거시, 단일 프레임용)
 /// NOTE: 다수 썸네일 생성 시 thumbnail_session_* API 사용 권장
 #[no_mangle]
@@ -278,12 +1196,43 @@ pub extern "C" fn generate_video_thumbnail(
     out_height: *mut u32,
     out_data: *mut *mut u8,
     out_data_size: *mut usize,
+) -> i32 {
+    generate_video_thumbnail_with_fit(
+        file_path,
+        timestamp_ms,
+        thumb_width,
+        thumb_height,
+        ThumbnailFit::Stretch as u32,
+        out_width,
+        out_height,
+        out_data,
+        out_data_size,
+    )
+}
+
+/// 비디오 썸네일 생성 (종횡비 맞춤 지정)
+/// - fit: 0=Stretch, 1=Letterbox (검정 패딩), 2=Crop (중앙 크롭)
+#[no_mangle]
+pub extern "C" fn generate_video_thumbnail_with_fit(
+    file_path: *const c_char,
+    timestamp_ms: i64,
+    thumb_width: u32,
+    thumb_height: u32,
+    fit: u32,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *mut u8,
+    out_data_size: *mut usize,
 ) -> i32 {
     if file_path.is_null() || out_width.is_null() || out_height.is_null()
         || out_data.is_null() || out_data_size.is_null() {
         return ErrorCode::NullPointer as i32;
     }
 
+    if thumb_width == 0 || thumb_height == 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
     unsafe {
         let c_str = CStr::from_ptr(file_path);
         let file_path_str = match c_str.to_str() {
@@ -297,12 +1246,14 @@ pub extern "C" fn generate_video_thumbnail(
         let mut decoder = match Decoder::open(&path) {
             Ok(d) => d,
             Err(e) => {
-                eprintln!("generate_video_thumbnail: Failed to open: {}", e);
+                report_error(format!("generate_video_thumbnail: Failed to open: {}", e));
                 return ErrorCode::Ffmpeg as i32;
             }
         };
+        // 단일 프레임 추출 → 스크럽용 빠른 seek 대신 정확한 프레임
+        decoder.set_seek_mode(SeekMode::Exact);
 
-        match decoder.generate_thumbnail(timestamp_ms, thumb_width, thumb_height) {
+        match decoder.generate_thumbnail_fit(timestamp_ms, thumb_width, thumb_height, ThumbnailFit::from_u32(fit)) {
             Ok(frame) => {
                 *out_width = frame.width;
                 *out_height = frame.height;
@@ -314,7 +1265,7 @@ pub extern "C" fn generate_video_thumbnail(
                 ErrorCode::Success as i32
             }
             Err(e) => {
-                eprintln!("generate_video_thumbnail: Failed at {}ms: {}", timestamp_ms, e);
+                report_error(format!("generate_video_thumbnail: Failed at {}ms: {}", timestamp_ms, e));
                 ErrorCode::Ffmpeg as i32
             }
         }
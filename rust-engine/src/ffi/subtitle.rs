@@ -0,0 +1,174 @@
+// Subtitle FFI - 자막 파일 파싱 + 텍스트 래스터화 (C# P/Invoke 연동)
+// 텍스트 자막은 엔진에서 RGBA 오버레이로 변환 → exporter_create_subtitle_list 목록에 추가
+
+use crate::ffi::types::{report_error, CSubtitleStyle, ErrorCode};
+use crate::subtitle::overlay::SubtitleOverlayList;
+use crate::subtitle::parser::{self, SubtitleAlignment, SubtitleStyle};
+use crate::subtitle::text;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::Path;
+
+/// 0xRRGGBBAA → [R, G, B, A]
+fn rgba_from_u32(v: u32) -> [u8; 4] {
+    v.to_be_bytes()
+}
+
+/// C 스타일 → SubtitleStyle (null이면 기본 스타일)
+pub(crate) unsafe fn style_from_c(style: *const CSubtitleStyle) -> SubtitleStyle {
+    if style.is_null() {
+        return SubtitleStyle::default();
+    }
+    let style = &*style;
+    SubtitleStyle {
+        font_size: style.font_size,
+        color: rgba_from_u32(style.color),
+        outline_color: rgba_from_u32(style.outline_color),
+        outline_width: style.outline_width,
+        alignment: SubtitleAlignment::from_numpad(style.alignment),
+        margin_h: style.margin_h,
+        margin_v: style.margin_v,
+    }
+}
+
+/// 폰트 경로 (null이면 시스템 기본 폰트)
+pub(crate) unsafe fn font_path_from_c<'a>(font_path: *const c_char) -> Result<Option<&'a Path>, ()> {
+    if font_path.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(font_path).to_str().map(|s| Some(Path::new(s))).map_err(|_| ())
+}
+
+/// 자막 파일 파싱 (.srt / .vtt / .ass, 확장자 없으면 내용으로 판단)
+/// out_json: [{"start_ms":1000,"end_ms":2500,"text":"첫 줄\n둘째 줄"}, ...] (시작 시간 순, 서식 태그 제거)
+/// 반환 후 string_free()로 해제 필요
+#[no_mangle]
+pub extern "C" fn subtitle_parse_file(file_path: *const c_char, out_json: *mut *mut c_char) -> i32 {
+    if file_path.is_null() || out_json.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_json = std::ptr::null_mut();
+
+        let path_str = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let cues = match parser::load_subtitle_file(Path::new(path_str)) {
+            Ok(cues) => cues,
+            Err(e) => {
+                report_error(format!("[SUBTITLE] 파싱 실패: {}", e));
+                return ErrorCode::Io as i32;
+            }
+        };
+
+        match CString::new(parser::cues_json(&cues)) {
+            Ok(c_str) => *out_json = c_str.into_raw(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 텍스트 자막을 래스터화하여 자막 목록에 추가
+/// list: exporter_create_subtitle_list()로 생성한 핸들
+/// text: UTF-8 텍스트 (\n 줄바꿈, 프레임 폭 - 좌우 여백을 넘으면 자동 줄바꿈)
+/// font_path: 폰트 파일 경로 (null이면 시스템 기본 폰트), style: null이면 기본 스타일
+/// frame_width/frame_height: 출력 해상도 (배치 기준)
+#[no_mangle]
+pub extern "C" fn subtitle_list_add_text(
+    list: *mut c_void,
+    start_ms: i64,
+    end_ms: i64,
+    text_utf8: *const c_char,
+    font_path: *const c_char,
+    style: *const CSubtitleStyle,
+    frame_width: u32,
+    frame_height: u32,
+) -> i32 {
+    if list.is_null() || text_utf8.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let text_str = match CStr::from_ptr(text_utf8).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let font_path = match font_path_from_c(font_path) {
+            Ok(p) => p,
+            Err(()) => return ErrorCode::InvalidParam as i32,
+        };
+        let renderer = match text::shared_renderer(font_path) {
+            Ok(r) => r,
+            Err(e) => {
+                report_error(format!("[SUBTITLE] 폰트 로드 실패: {}", e));
+                return ErrorCode::Io as i32;
+            }
+        };
+
+        let style = style_from_c(style);
+        let list_ref = &mut *(list as *mut SubtitleOverlayList);
+        // 빈 텍스트는 추가할 오버레이 없음 (성공)
+        if let Some(overlay) = renderer.render_overlay(text_str, &style, start_ms, end_ms, frame_width, frame_height) {
+            list_ref.overlays.push(overlay);
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 자막 파일(.srt/.vtt/.ass)을 파싱 + 래스터화하여 자막 목록에 추가
+/// style: 기본 스타일 (ASS 스타일이 있는 큐는 파일 스타일 우선), null이면 기본 스타일
+/// out_count: 추가된 오버레이 수
+#[no_mangle]
+pub extern "C" fn subtitle_list_load_file(
+    list: *mut c_void,
+    file_path: *const c_char,
+    font_path: *const c_char,
+    style: *const CSubtitleStyle,
+    frame_width: u32,
+    frame_height: u32,
+    out_count: *mut u32,
+) -> i32 {
+    if list.is_null() || file_path.is_null() || out_count.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_count = 0;
+
+        let path_str = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let font_path = match font_path_from_c(font_path) {
+            Ok(p) => p,
+            Err(()) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let cues = match parser::load_subtitle_file(Path::new(path_str)) {
+            Ok(cues) => cues,
+            Err(e) => {
+                report_error(format!("[SUBTITLE] 파싱 실패: {}", e));
+                return ErrorCode::Io as i32;
+            }
+        };
+        let renderer = match text::shared_renderer(font_path) {
+            Ok(r) => r,
+            Err(e) => {
+                report_error(format!("[SUBTITLE] 폰트 로드 실패: {}", e));
+                return ErrorCode::Io as i32;
+            }
+        };
+
+        let overlays = text::rasterize_cues(&renderer, &cues, &style_from_c(style), frame_width, frame_height);
+        *out_count = overlays.len() as u32;
+        let list_ref = &mut *(list as *mut SubtitleOverlayList);
+        list_ref.overlays.extend(overlays);
+    }
+
+    ErrorCode::Success as i32
+}
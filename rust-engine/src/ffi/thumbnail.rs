@@ -3,15 +3,79 @@
 // 매번 Decoder를 새로 생성하던 기존 방식 대비:
 //   - 파일 Open/Close 1회 (기존: N회)
 //   - 스케일러가 직접 썸네일 해상도로 출력 (기존: 960x540 → nearest-neighbor 다운스케일)
+// 구간 생성(thumbnail_session_generate_range): Rust 워커 스레드가 같은 디코더로 디코딩 → 콜백 전달
 
-use crate::ffmpeg::decoder::{Decoder, DecodeResult};
-use crate::ffi::types::ErrorCode;
-use std::ffi::{c_char, CStr};
+use crate::ffmpeg::decoder::{Decoder, DecodeLimits, DecodeResult, Frame};
+use crate::ffi::handle::THUMBNAIL_SESSIONS;
+use crate::ffi::types::{report_error, ErrorCode};
+use crate::utils::thumbnail_fit::ThumbnailFit;
+use crate::utils::log::log_warn;
+use std::ffi::{c_char, c_void, CStr};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// 썸네일 디코딩 모드 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThumbnailMode {
+    /// 정확 모드: 목표 PTS까지 forward decode (최종 filmstrip용)
+    Exact = 0,
+    /// 키프레임 전용: 가장 가까운 키프레임만 디코딩 (스크롤 중 즉시 표시용)
+    KeyframeOnly = 1,
+}
+
+impl ThumbnailMode {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => ThumbnailMode::KeyframeOnly,
+            _ => ThumbnailMode::Exact,
+        }
+    }
+}
 
 /// 썸네일 세션 (Decoder를 유지하며 여러 프레임 생성)
 pub struct ThumbnailSession {
-    decoder: Decoder,
+    /// 구간 생성 워커와 공유 (단일 생성과 워커 디코딩은 번갈아 진행)
+    decoder: Arc<Mutex<Decoder>>,
+    /// ThumbnailMode (핸들로 공유되므로 원자적으로 변경)
+    mode: AtomicU32,
+    /// 요청 썸네일 크기 (Letterbox/Crop 시 디코더 출력을 이 크기로 패딩/크롭)
+    thumb_width: u32,
+    thumb_height: u32,
+}
+
+impl ThumbnailSession {
+    /// 현재 썸네일 모드
+    fn mode(&self) -> ThumbnailMode {
+        ThumbnailMode::from_u32(self.mode.load(Ordering::Relaxed))
+    }
+
+    /// timestamp의 썸네일 디코딩 (None: seek 실패/끝 이후 → 빈 썸네일)
+    /// 스케일러가 이미 thumb(또는 비율 유지) 해상도이므로 추가 다운스케일 불필요
+    fn decode(&self, timestamp_ms: i64) -> Result<Option<Frame>, String> {
+        decode_thumbnail(&self.decoder, self.mode(), self.thumb_width, self.thumb_height, timestamp_ms)
+    }
+}
+
+fn decode_thumbnail(
+    decoder: &Mutex<Decoder>,
+    mode: ThumbnailMode,
+    thumb_width: u32,
+    thumb_height: u32,
+    timestamp_ms: i64,
+) -> Result<Option<Frame>, String> {
+    let mut decoder = decoder.lock().map_err(|_| "Decoder lock poisoned".to_string())?;
+    let result = match mode {
+        ThumbnailMode::Exact => decoder.decode_frame(timestamp_ms),
+        ThumbnailMode::KeyframeOnly => decoder.decode_keyframe(timestamp_ms),
+    };
+    match result? {
+        // Letterbox/Crop: 비율 유지 크기 → 요청 크기로 패딩/크롭
+        DecodeResult::Frame(f) | DecodeResult::EndOfStream(f) => Ok(Some(Decoder::fit_thumbnail(f, thumb_width, thumb_height))),
+        DecodeResult::FrameSkipped | DecodeResult::EndOfStreamEmpty => Ok(None),
+    }
 }
 
 /// 썸네일 세션 생성
@@ -25,7 +89,31 @@ pub extern "C" fn thumbnail_session_create(
     file_path: *const c_char,
     thumb_width: u32,
     thumb_height: u32,
-    out_session: *mut *mut ThumbnailSession,
+    out_session: *mut *mut c_void,
+    out_duration_ms: *mut i64,
+    out_fps: *mut f64,
+) -> i32 {
+    thumbnail_session_create_with_fit(
+        file_path,
+        thumb_width,
+        thumb_height,
+        ThumbnailFit::Stretch as u32,
+        out_session,
+        out_duration_ms,
+        out_fps,
+    )
+}
+
+/// 썸네일 세션 생성 (종횡비 맞춤 지정)
+/// - fit: 0=Stretch (요청 크기로 늘림), 1=Letterbox (비율 유지 + 검정 패딩), 2=Crop (비율 유지 + 중앙 크롭)
+/// - 출력 썸네일은 항상 thumb_width x thumb_height
+#[no_mangle]
+pub extern "C" fn thumbnail_session_create_with_fit(
+    file_path: *const c_char,
+    thumb_width: u32,
+    thumb_height: u32,
+    fit: u32,
+    out_session: *mut *mut c_void,
     out_duration_ms: *mut i64,
     out_fps: *mut f64,
 ) -> i32 {
@@ -35,6 +123,10 @@ pub extern "C" fn thumbnail_session_create(
         return ErrorCode::NullPointer as i32;
     }
 
+    if thumb_width == 0 || thumb_height == 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
     unsafe {
         let c_str = CStr::from_ptr(file_path);
         let file_path_str = match c_str.to_str() {
@@ -45,10 +137,11 @@ pub extern "C" fn thumbnail_session_create(
         let path = PathBuf::from(file_path_str);
 
         // 썸네일 해상도로 직접 디코딩 (960x540 거치지 않음)
-        let mut decoder = match Decoder::open_with_resolution(&path, thumb_width, thumb_height) {
+        // Letterbox/Crop이면 원본 비율에 맞춘 크기로 디코딩
+        let mut decoder = match Decoder::open_for_thumbnail(&path, thumb_width, thumb_height, ThumbnailFit::from_u32(fit)) {
             Ok(d) => d,
             Err(e) => {
-                eprintln!("thumbnail_session_create: Failed to open decoder: {}", e);
+                report_error(format!("thumbnail_session_create: Failed to open decoder: {}", e));
                 return ErrorCode::Ffmpeg as i32;
             }
         };
@@ -60,11 +153,14 @@ pub extern "C" fn thumbnail_session_create(
         *out_duration_ms = decoder.duration_ms();
         *out_fps = decoder.fps();
 
-        let session = Box::new(ThumbnailSession {
-            decoder,
+        let session = Arc::new(ThumbnailSession {
+            decoder: Arc::new(Mutex::new(decoder)),
+            mode: AtomicU32::new(ThumbnailMode::Exact as u32),
+            thumb_width,
+            thumb_height,
         });
 
-        *out_session = Box::into_raw(session);
+        *out_session = THUMBNAIL_SESSIONS.insert(session);
     }
 
     ErrorCode::Success as i32
@@ -76,7 +172,7 @@ pub extern "C" fn thumbnail_session_create(
 /// - out_data: RGBA 바이트 배열 (caller가 renderer_free_frame_data로 해제)
 #[no_mangle]
 pub extern "C" fn thumbnail_session_generate(
-    session: *mut ThumbnailSession,
+    session: *mut c_void,
     timestamp_ms: i64,
     out_width: *mut u32,
     out_height: *mut u32,
@@ -90,21 +186,15 @@ pub extern "C" fn thumbnail_session_generate(
     }
 
     unsafe {
-        let session = &mut *session;
-
-        // decode_frame → 스케일러가 이미 thumb 해상도이므로 추가 다운스케일 불필요
-        let frame = match session.decoder.decode_frame(timestamp_ms) {
-            Ok(DecodeResult::Frame(f)) => f,
-            Ok(DecodeResult::EndOfStream(f)) => f,
-            Ok(DecodeResult::FrameSkipped) => {
-                // seek 실패 → 빈 프레임 반환 (C# 측에서 스킵 처리)
-                *out_width = 0;
-                *out_height = 0;
-                *out_data = std::ptr::null_mut();
-                *out_data_size = 0;
-                return ErrorCode::Success as i32;
-            }
-            Ok(DecodeResult::EndOfStreamEmpty) => {
+        let session = match THUMBNAIL_SESSIONS.get(session) {
+            Some(s) => s,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+
+        let frame = match session.decode(timestamp_ms) {
+            Ok(Some(frame)) => frame,
+            // seek 실패/끝 이후 → 빈 프레임 반환 (C# 측에서 스킵 처리)
+            Ok(None) => {
                 *out_width = 0;
                 *out_height = 0;
                 *out_data = std::ptr::null_mut();
@@ -112,7 +202,7 @@ pub extern "C" fn thumbnail_session_generate(
                 return ErrorCode::Success as i32;
             }
             Err(e) => {
-                eprintln!("thumbnail_session_generate: decode failed at {}ms: {}", timestamp_ms, e);
+                report_error(format!("thumbnail_session_generate: decode failed at {}ms: {}", timestamp_ms, e));
                 *out_width = 0;
                 *out_height = 0;
                 *out_data = std::ptr::null_mut();
@@ -133,15 +223,267 @@ pub extern "C" fn thumbnail_session_generate(
     ErrorCode::Success as i32
 }
 
+/// 필름스트립 생성 (타임라인 클립 썸네일 줄)
+/// [start_ms, end_ms)를 count 등분한 구간 중앙 시점의 썸네일을 가로로 이어붙인 하나의 RGBA 버퍼
+/// - out_width = count * thumb_width, out_height = thumb_height
+/// - 디코딩 실패/끝 이후 타일은 투명(0)으로 채움
+/// - out_data: caller가 renderer_free_frame_data로 해제
+#[no_mangle]
+pub extern "C" fn thumbnail_session_generate_strip(
+    session: *mut c_void,
+    start_ms: i64,
+    end_ms: i64,
+    count: u32,
+    out_width: *mut u32,
+    out_height: *mut u32,
+    out_data: *mut *mut u8,
+    out_data_size: *mut usize,
+) -> i32 {
+    if session.is_null() || out_width.is_null() || out_height.is_null()
+        || out_data.is_null() || out_data_size.is_null()
+    {
+        return ErrorCode::NullPointer as i32;
+    }
+    if count == 0 || start_ms < 0 || end_ms < start_ms {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let session = match THUMBNAIL_SESSIONS.get(session) {
+            Some(s) => s,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let (tile_width, tile_height) = (session.thumb_width as usize, session.thumb_height as usize);
+        let strip_width = tile_width * count as usize;
+        let mut data = vec![0u8; strip_width * tile_height * 4];
+
+        for i in 0..count as usize {
+            let timestamp_ms = start_ms + ((end_ms - start_ms) as f64 * (i as f64 + 0.5) / count as f64) as i64;
+            let frame = match session.decode(timestamp_ms) {
+                Ok(Some(frame)) if frame.width as usize == tile_width && frame.height as usize == tile_height => frame,
+                Ok(_) => continue,
+                Err(e) => {
+                    log_warn!("thumbnail_session_generate_strip: decode failed at {}ms: {}", timestamp_ms, e);
+                    continue;
+                }
+            };
+            let row_bytes = tile_width * 4;
+            for (y, row) in frame.data.chunks_exact(row_bytes).enumerate() {
+                let offset = (y * strip_width + i * tile_width) * 4;
+                data[offset..offset + row_bytes].copy_from_slice(row);
+            }
+        }
+
+        *out_width = strip_width as u32;
+        *out_height = tile_height as u32;
+        *out_data_size = data.len();
+        *out_data = Box::into_raw(data.into_boxed_slice()) as *mut u8;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 썸네일 디코딩 모드 설정
+/// - mode: 0=Exact (정확한 프레임), 1=KeyframeOnly (키프레임만, 스크롤 중 빠른 표시)
+/// - 스크롤 중에는 KeyframeOnly로 대략적인 filmstrip을 채우고, 멈추면 Exact로 최종 패스
+#[no_mangle]
+pub extern "C" fn thumbnail_session_set_mode(session: *mut c_void, mode: u32) -> i32 {
+    if session.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    match THUMBNAIL_SESSIONS.get(session) {
+        Some(session) => session.mode.store(ThumbnailMode::from_u32(mode) as u32, Ordering::Relaxed),
+        None => return ErrorCode::InvalidHandle as i32,
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 썸네일 디코더 탐색 한도 설정
+/// - max_packets / max_decode_ms: 0 = 무제한 (한도 초과 시 빈 썸네일 반환 → C# 측 스킵)
+/// - seek_retries: seek 실패 시 재시도 횟수
+#[no_mangle]
+pub extern "C" fn thumbnail_session_set_decode_limits(
+    session: *mut c_void,
+    max_packets: u32,
+    max_decode_ms: u32,
+    seek_retries: u32,
+) -> i32 {
+    if session.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let session = match THUMBNAIL_SESSIONS.get(session) {
+        Some(s) => s,
+        None => return ErrorCode::InvalidHandle as i32,
+    };
+    if let Ok(mut decoder) = session.decoder.lock() {
+        decoder.set_limits(DecodeLimits {
+            max_packets,
+            max_decode_ms,
+            seek_retries,
+        });
+    }
+
+    ErrorCode::Success as i32
+}
+
 /// 썸네일 세션 파괴
 #[no_mangle]
-pub extern "C" fn thumbnail_session_destroy(session: *mut ThumbnailSession) -> i32 {
+pub extern "C" fn thumbnail_session_destroy(session: *mut c_void) -> i32 {
     if session.is_null() {
         return ErrorCode::NullPointer as i32;
     }
 
+    // 진행 중인 호출/구간 생성 워커가 디코더를 공유 중이면 끝날 때 해제
+    if THUMBNAIL_SESSIONS.remove(session).is_none() {
+        return ErrorCode::InvalidHandle as i32;
+    }
+
+    ErrorCode::Success as i32
+}
+
+// ==================== 구간 생성 (워커 스레드 + 콜백) ====================
+
+/// 썸네일 전달 콜백 (워커 스레드에서 호출)
+/// - data: RGBA width x height (콜백 안에서만 유효 → 필요하면 복사)
+/// - 디코딩 실패/끝 이후 시간은 width=height=0, data=null
+/// - 반환: 0이면 계속, 0이 아니면 중단
+pub type ThumbnailCallback = extern "C" fn(
+    user_data: *mut c_void,
+    timestamp_ms: i64,
+    width: u32,
+    height: u32,
+    data: *const u8,
+    data_size: usize,
+) -> i32;
+
+/// 콜백 user_data (C# GCHandle 등, 워커 스레드로 전달만 하고 역참조하지 않음)
+struct UserData(*mut c_void);
+
+// SAFETY: 포인터는 콜백 인자로 되돌려줄 뿐 Rust에서 접근하지 않음
+unsafe impl Send for UserData {}
+
+/// 구간 생성 작업 핸들
+pub struct ThumbnailRangeJob {
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl ThumbnailRangeJob {
+    /// 취소 후 워커 종료 대기 (반환 후에는 콜백이 호출되지 않음)
+    fn stop(&mut self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for ThumbnailRangeJob {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// 구간 썸네일 생성 시작 (Rust 워커 스레드, start_ms부터 interval_ms 간격으로 end_ms 전까지)
+/// - 시간순으로 timestamp마다 callback 1회 (세션 모드/디코딩 한도 적용, 시작 시점의 모드 사용)
+/// - 세션 디코더를 공유하므로 진행 중에도 thumbnail_session_generate 호출 가능 (번갈아 디코딩)
+/// - out_job: thumbnail_range_destroy로 해제 (세션보다 먼저 파괴하지 않아도 됨)
+/// - 콜백 안에서 thumbnail_range_cancel/destroy를 기다리는 스레드를 동기 대기하면 교착
+#[no_mangle]
+pub extern "C" fn thumbnail_session_generate_range(
+    session: *mut c_void,
+    start_ms: i64,
+    end_ms: i64,
+    interval_ms: i64,
+    callback: Option<ThumbnailCallback>,
+    user_data: *mut c_void,
+    out_job: *mut *mut ThumbnailRangeJob,
+) -> i32 {
+    let callback = match callback {
+        Some(callback) if !session.is_null() && !out_job.is_null() => callback,
+        _ => return ErrorCode::NullPointer as i32,
+    };
+    if interval_ms <= 0 || start_ms < 0 || end_ms < start_ms {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let session = match THUMBNAIL_SESSIONS.get(session) {
+            Some(s) => s,
+            None => return ErrorCode::InvalidHandle as i32,
+        };
+        let decoder = session.decoder.clone();
+        let (mode, thumb_width, thumb_height) = (session.mode(), session.thumb_width, session.thumb_height);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let finished = Arc::new(AtomicBool::new(false));
+        let user_data = UserData(user_data);
+
+        let (worker_cancelled, worker_finished) = (cancelled.clone(), finished.clone());
+        let worker = std::thread::spawn(move || {
+            let user_data = user_data;
+            let mut timestamp_ms = start_ms;
+            while timestamp_ms < end_ms && !worker_cancelled.load(Ordering::SeqCst) {
+                let frame = decode_thumbnail(&decoder, mode, thumb_width, thumb_height, timestamp_ms)
+                    .unwrap_or_else(|e| {
+                        log_warn!("thumbnail_session_generate_range: decode failed at {}ms: {}", timestamp_ms, e);
+                        None
+                    });
+                let (width, height, data) = frame.as_ref().map_or((0, 0, &[][..]), |f| (f.width, f.height, &f.data[..]));
+                let data_ptr = if data.is_empty() { std::ptr::null() } else { data.as_ptr() };
+                if callback(user_data.0, timestamp_ms, width, height, data_ptr, data.len()) != 0 {
+                    break;
+                }
+                timestamp_ms += interval_ms;
+            }
+            worker_finished.store(true, Ordering::SeqCst);
+        });
+
+        *out_job = Box::into_raw(Box::new(ThumbnailRangeJob { cancelled, finished, worker: Some(worker) }));
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 구간 생성 완료 여부
+/// 반환: 1=완료(끝/취소/콜백 중단), 0=진행중
+#[no_mangle]
+pub extern "C" fn thumbnail_range_is_finished(job: *mut ThumbnailRangeJob) -> i32 {
+    if job.is_null() {
+        return 1; // null이면 완료로 처리
+    }
+
+    unsafe {
+        if (*job).finished.load(Ordering::SeqCst) { 1 } else { 0 }
+    }
+}
+
+/// 구간 생성 취소 (즉시 반환, 진행 중인 한 프레임은 콜백까지 완료될 수 있음)
+#[no_mangle]
+pub extern "C" fn thumbnail_range_cancel(job: *mut ThumbnailRangeJob) -> i32 {
+    if job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        (*job).cancelled.store(true, Ordering::SeqCst);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 구간 생성 작업 파괴 (취소 + 워커 종료 대기 → 반환 후 콜백 없음, user_data 해제 가능)
+#[no_mangle]
+pub extern "C" fn thumbnail_range_destroy(job: *mut ThumbnailRangeJob) -> i32 {
+    if job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
     unsafe {
-        let _ = Box::from_raw(session);
+        let _ = Box::from_raw(job);
     }
 
     ErrorCode::Success as i32
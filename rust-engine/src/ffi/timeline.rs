@@ -1,17 +1,28 @@
 // Timeline FFI 함수
 // C#에서 Timeline을 생성/관리하기 위한 FFI 인터페이스
+// Undo/Redo 대상: 트랙/클립 추가·제거·분할, 이동·트림·속도(겹침 처리 포함), 리플 편집
+// 클립/트랙 속성 setter(LUT/이펙트/트랜스폼/크롭/키프레임/보간/스트림/끝 동작/맞춤 모드/
+// 타이틀·컬러 내용/오디오 볼륨·페이드 등)는 히스토리 밖 — Undo해도 현재 값 유지
 
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-use crate::timeline::Timeline;
-use super::types::{ERROR_SUCCESS, ERROR_NULL_PTR, ERROR_INVALID_PARAM};
+use crate::timeline::{Timeline, EndOfMediaBehavior, FitMode, TrackStats, CropRect, OverlapPolicy, ClipType, AudioClip, AudioTrack, VideoTrack, MAX_CLIP_VOLUME, MAX_TRACK_VOLUME};
+use crate::timeline::{ClipKeyframes, Interpolation, Keyframe, KeyframeProperty, KeyframeTrack, Transition, TransitionKind};
+use crate::timeline::{ColorClip, TitleAnimation, TitleClip};
+use crate::rendering::effects::EffectParams;
+use crate::audio::effects::{AudioEffectParams, MAX_COMPRESSOR_RATIO, MAX_EFFECT_GAIN_DB, MAX_EQ_GAIN_DB};
+use crate::utils::json_escape;
+use crate::utils::scene_detect::{self, SceneDetectParams};
+use super::subtitle::{font_path_from_c, style_from_c};
+use super::handle::{RENDERERS, TIMELINES};
+use super::types::{report_error, CClip, CSubtitleStyle, ERROR_SUCCESS, ERROR_NULL_PTR, ERROR_INVALID_PARAM, ERROR_INVALID_HANDLE};
 
 type TimelineArc = Arc<Mutex<Timeline>>;
 
-/// Timeline 생성 (Arc<Mutex> 래핑)
+/// Timeline 생성 (Arc<Mutex> 래핑, 핸들 레지스트리에 등록)
 #[no_mangle]
 pub extern "C" fn timeline_create(
     width: u32,
@@ -30,21 +41,21 @@ pub extern "C" fn timeline_create(
     let timeline = Arc::new(Mutex::new(Timeline::new(width, height, fps)));
 
     unsafe {
-        *out_timeline = Arc::into_raw(timeline) as *mut std::ffi::c_void;
+        *out_timeline = TIMELINES.insert(timeline);
     }
 
     ERROR_SUCCESS
 }
 
-/// Timeline 파괴 (메모리 해제)
+/// Timeline 파괴 (핸들 해제, Renderer/Export가 공유 중이면 마지막 사용자가 해제)
 #[no_mangle]
 pub extern "C" fn timeline_destroy(timeline: *mut std::ffi::c_void) -> i32 {
     if timeline.is_null() {
         return ERROR_NULL_PTR;
     }
 
-    unsafe {
-        let _ = Arc::from_raw(timeline as *const Mutex<Timeline>);
+    if TIMELINES.remove(timeline).is_none() {
+        return ERROR_INVALID_HANDLE;
     }
 
     ERROR_SUCCESS
@@ -61,7 +72,10 @@ pub extern "C" fn timeline_add_video_track(
     }
 
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
         let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
@@ -84,7 +98,10 @@ pub extern "C" fn timeline_add_audio_track(
     }
 
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
         let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
@@ -96,6 +113,153 @@ pub extern "C" fn timeline_add_audio_track(
     ERROR_SUCCESS
 }
 
+/// 트랙 제거 (비디오/오디오 공통, 클립 포함, Undo 가능)
+/// 없거나 잠긴 트랙이면 ERROR_INVALID_PARAM
+#[no_mangle]
+pub extern "C" fn timeline_remove_track(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.remove_track(track_id) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+
+/// 트랙 순서 변경 (new_index: 같은 종류 트랙 안의 새 위치, 0 = 최하단, Undo 가능)
+/// 다른 트랙들의 index도 목록 위치에 맞게 갱신
+#[no_mangle]
+pub extern "C" fn timeline_move_track(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    new_index: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.move_track(track_id, new_index as usize) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+
+/// 트랙 이름 변경 (name: UTF-8)
+#[no_mangle]
+pub extern "C" fn timeline_set_track_name(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    name: *const c_char,
+) -> i32 {
+    if timeline.is_null() || name.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let name = match CStr::from_ptr(name).to_str() {
+            Ok(s) => s,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        if timeline.set_track_name(track_id, name) {
+            ERROR_SUCCESS
+        } else {
+            ERROR_INVALID_PARAM
+        }
+    }
+}
+
+/// 트랙 잠금 설정 (locked: 0=해제, 그 외=잠금)
+/// 잠긴 트랙의 클립 추가/제거/편집/속성 변경은 ERROR_INVALID_PARAM (트랙 볼륨/뮤트 등 트랙 설정은 허용)
+#[no_mangle]
+pub extern "C" fn timeline_set_track_locked(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    locked: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.set_track_locked(track_id, locked != 0) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+
+/// 트랙 on/off (enabled: 0=끔, 그 외=켬, 비디오/오디오 트랙 공통, 잠긴 트랙도 허용)
+/// 꺼진 비디오 트랙은 합성, 오디오 트랙은 믹스에서 제외
+/// Renderer는 다음 렌더링에서 변경을 감지해 해당 트랙 레이어만 무효화 (디코딩 프레임 캐시 유지)
+/// 트랙 설정이므로 Undo 대상이 아니며, 이전 편집을 Undo해도 on/off 상태는 유지
+#[no_mangle]
+pub extern "C" fn timeline_set_track_enabled(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    enabled: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.set_track_enabled(track_id, enabled != 0) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+
 /// 비디오 클립 추가
 #[no_mangle]
 pub extern "C" fn timeline_add_video_clip(
@@ -124,7 +288,10 @@ pub extern "C" fn timeline_add_video_clip(
     let path = PathBuf::from(path_str);
 
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
         let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
@@ -168,7 +335,10 @@ pub extern "C" fn timeline_add_audio_clip(
     let path = PathBuf::from(path_str);
 
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
         let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
@@ -195,18 +365,19 @@ pub extern "C" fn timeline_remove_video_clip(
         return ERROR_NULL_PTR;
     }
 
-    unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
-        let mut timeline = match timeline_arc.lock() {
-            Ok(t) => t,
-            Err(_) => return ERROR_INVALID_PARAM,
-        };
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
 
-        if timeline.remove_video_clip(track_id, clip_id) {
-            ERROR_SUCCESS
-        } else {
-            ERROR_INVALID_PARAM
-        }
+    if timeline.remove_video_clip(track_id, clip_id) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
     }
 }
 
@@ -221,145 +392,2706 @@ pub extern "C" fn timeline_remove_audio_clip(
         return ERROR_NULL_PTR;
     }
 
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.remove_audio_clip(track_id, clip_id) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+
+/// 비디오 클립 분할 (split_time_ms: 타임라인 시간, 클립 내부여야 함)
+/// out_new_clip_id: 새로 생성된 뒷부분 클립 ID
+#[no_mangle]
+pub extern "C" fn timeline_split_video_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    split_time_ms: i64,
+    out_new_clip_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || out_new_clip_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
         let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
         };
 
-        if timeline.remove_audio_clip(track_id, clip_id) {
-            ERROR_SUCCESS
-        } else {
-            ERROR_INVALID_PARAM
+        match timeline.split_video_clip(track_id, clip_id, split_time_ms) {
+            Some(new_id) => {
+                *out_new_clip_id = new_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM,
         }
     }
 }
 
-/// 타임라인 총 길이 가져오기 (ms)
+/// 오디오 클립 분할 (split_time_ms: 타임라인 시간, 클립 내부여야 함)
+/// out_new_clip_id: 새로 생성된 뒷부분 클립 ID
 #[no_mangle]
-pub extern "C" fn timeline_get_duration(
-    timeline: *const std::ffi::c_void,
-    out_duration_ms: *mut i64,
+pub extern "C" fn timeline_split_audio_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    split_time_ms: i64,
+    out_new_clip_id: *mut u64,
 ) -> i32 {
-    if timeline.is_null() || out_duration_ms.is_null() {
+    if timeline.is_null() || out_new_clip_id.is_null() {
         return ERROR_NULL_PTR;
     }
 
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
-        let timeline = match timeline_arc.lock() {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
         };
 
-        *out_duration_ms = timeline.duration_ms();
+        match timeline.split_audio_clip(track_id, clip_id, split_time_ms) {
+            Some(new_id) => {
+                *out_new_clip_id = new_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM,
+        }
     }
-
-    ERROR_SUCCESS
 }
 
-/// 비디오 트랙 개수 가져오기
+/// 클립 이동 (비디오/오디오 트랙 공통, 클립 ID와 이펙트 유지)
+/// - new_start_ms: 새 타임라인 시작 위치
+/// - policy: 겹침 처리 0=거부, 1=뒤 클립 밀기, 2=덮어쓰기
 #[no_mangle]
-pub extern "C" fn timeline_get_video_track_count(
-    timeline: *const std::ffi::c_void,
-    out_count: *mut usize,
+pub extern "C" fn timeline_move_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    new_start_ms: i64,
+    policy: u32,
 ) -> i32 {
-    if timeline.is_null() || out_count.is_null() {
+    if timeline.is_null() {
         return ERROR_NULL_PTR;
     }
 
-    unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
-        let timeline = match timeline_arc.lock() {
-            Ok(t) => t,
-            Err(_) => return ERROR_INVALID_PARAM,
-        };
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
 
-        *out_count = timeline.video_tracks.len();
+    if timeline.move_clip(track_id, clip_id, new_start_ms, OverlapPolicy::from_u32(policy)) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
     }
+}
 
-    ERROR_SUCCESS
+/// 클립 트림 (원본 기준 새 in/out 지점, 비디오/오디오 트랙 공통)
+/// 앞부분 트림 시 타임라인 시작 위치도 같이 이동 (원본 프레임 정렬 유지)
+/// - policy: 겹침 처리 0=거부, 1=뒤 클립 밀기, 2=덮어쓰기
+#[no_mangle]
+pub extern "C" fn timeline_trim_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    new_trim_start_ms: i64,
+    new_trim_end_ms: i64,
+    policy: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    let policy = OverlapPolicy::from_u32(policy);
+    if timeline.trim_clip(track_id, clip_id, new_trim_start_ms, new_trim_end_ms, policy) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
 }
 
-/// 오디오 트랙 개수 가져오기
+
+
+/// 클립 재생 속도 변경 (비디오/오디오 트랙 공통, 0.1~16.0)
+/// 원본 구간은 유지되고 타임라인 길이가 바뀜 (0.5 = 2배 슬로우 → 길이 2배)
+/// - policy: 겹침 처리 0=거부, 1=뒤 클립 밀기, 2=덮어쓰기
 #[no_mangle]
-pub extern "C" fn timeline_get_audio_track_count(
-    timeline: *const std::ffi::c_void,
-    out_count: *mut usize,
+pub extern "C" fn timeline_set_clip_speed(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    speed: f64,
+    policy: u32,
 ) -> i32 {
-    if timeline.is_null() || out_count.is_null() {
+    if timeline.is_null() {
         return ERROR_NULL_PTR;
     }
 
-    unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
-        let timeline = match timeline_arc.lock() {
-            Ok(t) => t,
-            Err(_) => return ERROR_INVALID_PARAM,
-        };
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
 
-        *out_count = timeline.audio_tracks.len();
+    if timeline.set_clip_speed(track_id, clip_id, speed, OverlapPolicy::from_u32(policy)) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+/// 리플 삭제: 클립 제거 후 뒤따르는 클립을 당겨 빈 공간 제거 (비디오/오디오 트랙 공통)
+/// - all_tracks: 1이면 모든 트랙의 이후 클립을 함께 이동 (트랙 간 싱크 유지)
+#[no_mangle]
+pub extern "C" fn timeline_ripple_remove_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    all_tracks: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
     }
 
-    ERROR_SUCCESS
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.ripple_remove_clip(track_id, clip_id, all_tracks != 0) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
 }
 
-/// 특정 비디오 트랙의 클립 개수 가져오기
+/// 리플 삽입: start_time_ms 이후 클립을 duration_ms만큼 밀고 새 클립 추가 (비디오/오디오 트랙 공통)
+/// 삽입 지점에 걸친 클립은 분할됨
+/// - all_tracks: 1이면 모든 트랙을 함께 밀기
 #[no_mangle]
-pub extern "C" fn timeline_get_video_clip_count(
-    timeline: *const std::ffi::c_void,
+pub extern "C" fn timeline_ripple_insert_clip(
+    timeline: *mut std::ffi::c_void,
     track_id: u64,
-    out_count: *mut usize,
+    file_path: *const c_char,
+    start_time_ms: i64,
+    duration_ms: i64,
+    all_tracks: i32,
+    out_clip_id: *mut u64,
 ) -> i32 {
-    if timeline.is_null() || out_count.is_null() {
+    if timeline.is_null() || file_path.is_null() || out_clip_id.is_null() {
         return ERROR_NULL_PTR;
     }
 
+    let path_str = unsafe {
+        match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    };
+
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
-        let timeline = match timeline_arc.lock() {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
         };
 
-        if let Some(track) = timeline.video_tracks.iter().find(|t| t.id == track_id) {
-            *out_count = track.clips.len();
-            ERROR_SUCCESS
-        } else {
-            ERROR_INVALID_PARAM
+        let path = PathBuf::from(path_str);
+        match timeline.ripple_insert_clip(track_id, path, start_time_ms, duration_ms, all_tracks != 0) {
+            Some(clip_id) => {
+                *out_clip_id = clip_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM,
         }
     }
 }
 
-/// 비디오 클립의 trim_start_ms 설정 (Razor 분할용)
+/// 마지막 편집 되돌리기 (구조/배치 편집만 기록, 속성 setter는 대상 아님 — 파일 상단 참고)
+/// out_applied: 1 = 되돌림, 0 = 되돌릴 편집 없음
 #[no_mangle]
-pub extern "C" fn timeline_set_video_clip_trim(
+pub extern "C" fn timeline_undo(
     timeline: *mut std::ffi::c_void,
-    track_id: u64,
-    clip_id: u64,
-    trim_start_ms: i64,
-    trim_end_ms: i64,
+    out_applied: *mut i32,
 ) -> i32 {
-    if timeline.is_null() {
+    if timeline.is_null() || out_applied.is_null() {
         return ERROR_NULL_PTR;
     }
 
     unsafe {
-        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
         let mut timeline = match timeline_arc.lock() {
             Ok(t) => t,
             Err(_) => return ERROR_INVALID_PARAM,
         };
+        *out_applied = timeline.undo() as i32;
+    }
 
-        if let Some(track) = timeline.video_tracks.iter_mut().find(|t| t.id == track_id) {
-            if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
-                clip.trim_start_ms = trim_start_ms;
-                clip.trim_end_ms = trim_end_ms;
-                return ERROR_SUCCESS;
-            }
-        }
+    ERROR_SUCCESS
+}
+
+/// 되돌린 편집 다시 실행
+/// out_applied: 1 = 다시 실행, 0 = 다시 실행할 편집 없음
+#[no_mangle]
+pub extern "C" fn timeline_redo(
+    timeline: *mut std::ffi::c_void,
+    out_applied: *mut i32,
+) -> i32 {
+    if timeline.is_null() || out_applied.is_null() {
+        return ERROR_NULL_PTR;
     }
 
-    ERROR_INVALID_PARAM
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+        *out_applied = timeline.redo() as i32;
+    }
+
+    ERROR_SUCCESS
+}
+
+/// Undo/Redo 가능 횟수 조회 (메뉴 활성화 상태 갱신용)
+#[no_mangle]
+pub extern "C" fn timeline_history_depth(
+    timeline: *const std::ffi::c_void,
+    out_undo_depth: *mut u32,
+    out_redo_depth: *mut u32,
+) -> i32 {
+    if timeline.is_null() || out_undo_depth.is_null() || out_redo_depth.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+        *out_undo_depth = timeline.history().undo_depth() as u32;
+        *out_redo_depth = timeline.history().redo_depth() as u32;
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 히스토리 초기화 (프로젝트 로드 직후 복원 과정이 Undo 대상이 되지 않도록)
+#[no_mangle]
+pub extern "C" fn timeline_clear_history(timeline: *mut std::ffi::c_void) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    match timeline_arc.lock() {
+        Ok(mut t) => t.clear_history(),
+        Err(_) => return ERROR_INVALID_PARAM,
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 타임라인 총 길이 가져오기 (ms)
+#[no_mangle]
+pub extern "C" fn timeline_get_duration(
+    timeline: *const std::ffi::c_void,
+    out_duration_ms: *mut i64,
+) -> i32 {
+    if timeline.is_null() || out_duration_ms.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        *out_duration_ms = timeline.duration_ms();
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 타임라인 프레임 수 (프로젝트 fps 격자, Export 프레임 수와 동일)
+#[no_mangle]
+pub extern "C" fn timeline_get_frame_count(
+    timeline: *const std::ffi::c_void,
+    out_frame_count: *mut i64,
+) -> i32 {
+    if timeline.is_null() || out_frame_count.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        *out_frame_count = timeline.frame_count();
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 프레임 번호 → 프레임 시작 시간 (ms, 프로젝트 fps 격자)
+#[no_mangle]
+pub extern "C" fn timeline_frame_to_ms(
+    timeline: *const std::ffi::c_void,
+    frame_index: i64,
+    out_timestamp_ms: *mut i64,
+) -> i32 {
+    if timeline.is_null() || out_timestamp_ms.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        *out_timestamp_ms = timeline.frame_time_ms(frame_index);
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 시간(ms) → 그 시간이 속한 프레임 번호
+#[no_mangle]
+pub extern "C" fn timeline_ms_to_frame(
+    timeline: *const std::ffi::c_void,
+    timestamp_ms: i64,
+    out_frame_index: *mut i64,
+) -> i32 {
+    if timeline.is_null() || out_frame_index.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        *out_frame_index = timeline.frame_at(timestamp_ms);
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 트랙의 빈 구간 조회 (JSON, 0 ~ 마지막 클립 끝, Export 전 검정 프레임 경고용)
+/// - out_json: [{"start_ms":1000,"end_ms":2000}, ...] (string_free로 해제)
+#[no_mangle]
+pub extern "C" fn timeline_find_gaps(
+    timeline: *const std::ffi::c_void,
+    track_id: u64,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if timeline.is_null() || out_json.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        *out_json = std::ptr::null_mut();
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let gaps = match timeline.find_gaps(track_id) {
+            Some(gaps) => gaps,
+            None => return ERROR_INVALID_PARAM,
+        };
+        let items: Vec<String> = gaps.iter()
+            .map(|(start_ms, end_ms)| format!("{{\"start_ms\":{},\"end_ms\":{}}}", start_ms, end_ms))
+            .collect();
+
+        match CString::new(format!("[{}]", items.join(","))) {
+            Ok(s) => *out_json = s.into_raw(),
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 같은 트랙에서 겹치는 클립 조회 (JSON, 전체 트랙)
+/// - out_json: [{"track_id":2,"clip_id":3,"other_clip_id":4,"start_ms":1200,"end_ms":1500}, ...] (string_free로 해제)
+#[no_mangle]
+pub extern "C" fn timeline_find_overlaps(
+    timeline: *const std::ffi::c_void,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if timeline.is_null() || out_json.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        *out_json = std::ptr::null_mut();
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let items: Vec<String> = timeline.find_overlaps().iter()
+            .map(|o| format!(
+                "{{\"track_id\":{},\"clip_id\":{},\"other_clip_id\":{},\"start_ms\":{},\"end_ms\":{}}}",
+                o.track_id, o.clip_id, o.other_clip_id, o.start_ms, o.end_ms
+            ))
+            .collect();
+
+        match CString::new(format!("[{}]", items.join(","))) {
+            Ok(s) => *out_json = s.into_raw(),
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 모든 트랙의 빈 구간 제거 (트랙별로 클립을 앞으로 당김, 잠긴 트랙 제외, Undo 1회로 복원)
+/// - out_closed_count: 제거한 빈 구간 수 (null이면 무시)
+#[no_mangle]
+pub extern "C" fn timeline_close_all_gaps(
+    timeline: *mut std::ffi::c_void,
+    out_closed_count: *mut u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let closed = timeline.close_all_gaps();
+        if !out_closed_count.is_null() {
+            *out_closed_count = closed as u32;
+        }
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 스냅 (드래그/트림/재생 헤드 이동 시)
+/// candidate_ms에서 tolerance_ms 이내의 가장 가까운 지점: 0, 모든 트랙의 클립 시작/끝, 마커, 재생 헤드
+/// - playhead_ms: 재생 헤드 위치 (음수면 제외)
+/// - exclude_clip_id: 드래그 중인 클립 (0이면 제외 없음)
+/// - out_time_ms: 스냅된 시간 (스냅 지점이 없으면 candidate_ms 그대로)
+/// - out_snapped: 1=스냅됨, 0=스냅 지점 없음 (null이면 무시)
+#[no_mangle]
+pub extern "C" fn timeline_snap_time(
+    timeline: *const std::ffi::c_void,
+    candidate_ms: i64,
+    tolerance_ms: i64,
+    playhead_ms: i64,
+    exclude_clip_id: u64,
+    out_time_ms: *mut i64,
+    out_snapped: *mut i32,
+) -> i32 {
+    if timeline.is_null() || out_time_ms.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if tolerance_ms < 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let playhead_ms = (playhead_ms >= 0).then_some(playhead_ms);
+        let exclude_clip_id = (exclude_clip_id != 0).then_some(exclude_clip_id);
+        let snapped = timeline.snap_time(candidate_ms, tolerance_ms, playhead_ms, exclude_clip_id);
+
+        *out_time_ms = snapped.unwrap_or(candidate_ms);
+        if !out_snapped.is_null() {
+            *out_snapped = i32::from(snapped.is_some());
+        }
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 타임라인 배경색 설정 (클립이 없는 구간/레터박스 영역, 단색 배경 채우기로 저장)
+#[no_mangle]
+pub extern "C" fn timeline_set_background_color(
+    timeline: *mut std::ffi::c_void,
+    r: u8,
+    g: u8,
+    b: u8,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    timeline.set_background(ColorClip::solid([r, g, b, 255]));
+
+    ERROR_SUCCESS
+}
+
+/// 타임라인 배경 채우기 설정 (단색 또는 그라디언트, timeline_set_background_color를 대체)
+/// - color / end_color: 0xRRGGBBAA (배경이므로 알파는 무시), gradient != 0이면 color → end_color
+/// - angle_deg: 그라디언트 방향 (0 = 왼쪽 → 오른쪽, 90 = 위 → 아래)
+#[no_mangle]
+pub extern "C" fn timeline_set_background_fill(
+    timeline: *mut std::ffi::c_void,
+    color: u32,
+    end_color: u32,
+    gradient: i32,
+    angle_deg: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let background = match color_clip_from_c(color, end_color, gradient, angle_deg) {
+        Some(background) => background,
+        None => return ERROR_INVALID_PARAM,
+    };
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    timeline.set_background(background);
+
+    ERROR_SUCCESS
+}
+
+/// 타임라인 배경 채우기 가져오기 (프로젝트 저장용, 인자는 timeline_set_background_fill과 동일)
+/// 단색이면 out_gradient = 0, out_end_color = out_color
+#[no_mangle]
+pub extern "C" fn timeline_get_background_fill(
+    timeline: *const std::ffi::c_void,
+    out_color: *mut u32,
+    out_end_color: *mut u32,
+    out_gradient: *mut i32,
+    out_angle_deg: *mut f32,
+) -> i32 {
+    if timeline.is_null() || out_color.is_null() || out_end_color.is_null()
+        || out_gradient.is_null() || out_angle_deg.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let background = timeline.background();
+        *out_color = u32::from_be_bytes(background.color);
+        *out_end_color = u32::from_be_bytes(background.end_color.unwrap_or(background.color));
+        *out_gradient = i32::from(background.solid_rgb().is_none());
+        *out_angle_deg = background.angle_deg;
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 타임라인 배경색 가져오기 (그라디언트 배경이면 시작 색)
+#[no_mangle]
+pub extern "C" fn timeline_get_background_color(
+    timeline: *const std::ffi::c_void,
+    out_r: *mut u8,
+    out_g: *mut u8,
+    out_b: *mut u8,
+) -> i32 {
+    if timeline.is_null() || out_r.is_null() || out_g.is_null() || out_b.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let [r, g, b, _] = timeline.background().color;
+        *out_r = r;
+        *out_g = g;
+        *out_b = b;
+    }
+
+    ERROR_SUCCESS
+}
+
+/// LUT 경로 인자 (NULL/빈 문자열이면 해제 = None, UTF-8이 아니면 Err)
+unsafe fn lut_path_arg(lut_path: *const c_char) -> Result<Option<PathBuf>, ()> {
+    if lut_path.is_null() {
+        return Ok(None);
+    }
+    match CStr::from_ptr(lut_path).to_str() {
+        Ok(s) if !s.is_empty() => Ok(Some(PathBuf::from(s))),
+        Ok(_) => Ok(None),
+        Err(_) => Err(()),
+    }
+}
+
+/// 그레이딩 이펙트 파라미터 (값 하나라도 NaN/무한대면 None)
+#[allow(clippy::too_many_arguments)]
+fn grade_effect_params(
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    temperature: f32,
+    gamma: f32,
+    highlights: f32,
+    shadows: f32,
+    tint: f32,
+    vignette: f32,
+) -> Option<EffectParams> {
+    let params = EffectParams { brightness, contrast, saturation, temperature, gamma, highlights, shadows, tint, vignette };
+    params.values().iter().all(|v| v.is_finite()).then_some(params)
+}
+
+/// 마스터 이펙트 설정 (모든 트랙 합성 후 출력 프레임 전체에 적용, 자막 제외)
+/// 파라미터는 renderer_set_clip_effects_v2와 동일 (-1.0 ~ 1.0, 모두 0이면 해제)
+#[no_mangle]
+pub extern "C" fn timeline_set_master_effects(
+    timeline: *mut std::ffi::c_void,
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    temperature: f32,
+    gamma: f32,
+    highlights: f32,
+    shadows: f32,
+    tint: f32,
+    vignette: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    let params = match grade_effect_params(brightness, contrast, saturation, temperature, gamma, highlights, shadows, tint, vignette) {
+        Some(params) => params,
+        None => return ERROR_INVALID_PARAM,
+    };
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    timeline.master_effects = params;
+
+    ERROR_SUCCESS
+}
+
+/// 마스터 LUT 설정 (.cube 파일, NULL이면 해제, 마스터 이펙트보다 먼저 적용)
+#[no_mangle]
+pub extern "C" fn timeline_set_master_lut(timeline: *mut std::ffi::c_void, lut_path: *const c_char) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    let lut_path = match unsafe { lut_path_arg(lut_path) } {
+        Ok(path) => path,
+        Err(()) => return ERROR_INVALID_PARAM,
+    };
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    timeline.master_lut_path = lut_path;
+
+    ERROR_SUCCESS
+}
+
+/// 비디오 트랙 조회 후 작업 실행 (트랙이 없으면 ERROR_INVALID_PARAM, 잠긴 트랙도 허용)
+fn with_video_track(timeline: *mut std::ffi::c_void, track_id: u64, f: impl FnOnce(&mut VideoTrack)) -> i32 {
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    match timeline.video_tracks.iter_mut().find(|t| t.id == track_id) {
+        Some(track) => {
+            f(track);
+            ERROR_SUCCESS
+        }
+        None => ERROR_INVALID_PARAM,
+    }
+}
+
+/// 비디오 트랙 이펙트 설정 (트랙 클립의 이펙트/트랜스폼/트랜지션 이후 트랙 레이어 전체에 적용)
+/// 파라미터는 renderer_set_clip_effects_v2와 동일 (-1.0 ~ 1.0, 모두 0이면 해제)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_track_effects(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+    temperature: f32,
+    gamma: f32,
+    highlights: f32,
+    shadows: f32,
+    tint: f32,
+    vignette: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    let params = match grade_effect_params(brightness, contrast, saturation, temperature, gamma, highlights, shadows, tint, vignette) {
+        Some(params) => params,
+        None => return ERROR_INVALID_PARAM,
+    };
+
+    with_video_track(timeline, track_id, |track| track.effects = params)
+}
+
+/// 비디오 트랙 LUT 설정 (.cube 파일, NULL이면 해제, 트랙 이펙트보다 먼저 적용)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_track_lut(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    lut_path: *const c_char,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    let lut_path = match unsafe { lut_path_arg(lut_path) } {
+        Ok(path) => path,
+        Err(()) => return ERROR_INVALID_PARAM,
+    };
+
+    with_video_track(timeline, track_id, |track| track.lut_path = lut_path)
+}
+
+/// 마커 추가 (탐색용, Export 시 챕터로 기록)
+/// - name: UTF-8 마커 이름 (챕터 제목)
+/// - out_marker_id: 생성된 마커 ID
+#[no_mangle]
+pub extern "C" fn timeline_add_marker(
+    timeline: *mut std::ffi::c_void,
+    time_ms: i64,
+    name: *const c_char,
+    r: u8,
+    g: u8,
+    b: u8,
+    out_marker_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || name.is_null() || out_marker_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if time_ms < 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        let name = match CStr::from_ptr(name).to_str() {
+            Ok(s) => s,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        *out_marker_id = timeline.add_marker(time_ms, name, [r, g, b]);
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 마커 제거
+#[no_mangle]
+pub extern "C" fn timeline_remove_marker(
+    timeline: *mut std::ffi::c_void,
+    marker_id: u64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.remove_marker(marker_id) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+
+/// 마커 목록 조회 (JSON, 시간 순)
+/// - out_json: [{"id":1,"time_ms":0,"name":"Intro","color":[255,0,0]}, ...] (string_free로 해제)
+#[no_mangle]
+pub extern "C" fn timeline_get_markers(
+    timeline: *const std::ffi::c_void,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if timeline.is_null() || out_json.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        *out_json = std::ptr::null_mut();
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let items: Vec<String> = timeline.markers().iter()
+            .map(|m| format!(
+                "{{\"id\":{},\"time_ms\":{},\"name\":\"{}\",\"color\":[{},{},{}]}}",
+                m.id, m.time_ms, json_escape(&m.name), m.color[0], m.color[1], m.color[2]
+            ))
+            .collect();
+
+        match CString::new(format!("[{}]", items.join(","))) {
+            Ok(s) => *out_json = s.into_raw(),
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 비디오 트랙 개수 가져오기
+#[no_mangle]
+pub extern "C" fn timeline_get_video_track_count(
+    timeline: *const std::ffi::c_void,
+    out_count: *mut usize,
+) -> i32 {
+    if timeline.is_null() || out_count.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        *out_count = timeline.video_tracks.len();
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 오디오 트랙 개수 가져오기
+#[no_mangle]
+pub extern "C" fn timeline_get_audio_track_count(
+    timeline: *const std::ffi::c_void,
+    out_count: *mut usize,
+) -> i32 {
+    if timeline.is_null() || out_count.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        *out_count = timeline.audio_tracks.len();
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 오디오 트랙 조회 후 작업 실행 (트랙이 없거나 f가 false면 ERROR_INVALID_PARAM)
+unsafe fn with_audio_track(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    f: impl FnOnce(&mut AudioTrack) -> bool,
+) -> i32 {
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.audio_tracks.iter_mut().find(|t| t.id == track_id) {
+        return if f(track) { ERROR_SUCCESS } else { ERROR_INVALID_PARAM };
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 오디오 트랙 볼륨 설정 (0.0 ~ 2.0, 범위 밖은 클램프, 1.0 = 원본)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_track_volume(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    volume: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if !volume.is_finite() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        with_audio_track(timeline, track_id, |track| {
+            track.volume = volume.clamp(0.0, MAX_TRACK_VOLUME);
+            true
+        })
+    }
+}
+
+/// 오디오 트랙 팬 설정 (-1.0 = 왼쪽, 0.0 = 중앙, 1.0 = 오른쪽)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_track_pan(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    pan: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if !pan.is_finite() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        with_audio_track(timeline, track_id, |track| {
+            track.pan = pan.clamp(-1.0, 1.0);
+            true
+        })
+    }
+}
+
+/// 오디오 트랙 뮤트 설정 (muted: 0=해제, 그 외=뮤트)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_track_muted(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    muted: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        with_audio_track(timeline, track_id, |track| {
+            track.muted = muted != 0;
+            true
+        })
+    }
+}
+
+/// 오디오 트랙 솔로 설정 (solo: 0=해제, 그 외=솔로)
+/// 솔로 트랙이 하나라도 있으면 솔로 트랙만 믹스 (비디오 클립 오디오 포함 나머지 무음)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_track_solo(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    solo: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        with_audio_track(timeline, track_id, |track| {
+            track.solo = solo != 0;
+            true
+        })
+    }
+}
+
+/// 오디오 클립 페이드 설정 (ms, 0 = 없음, 클립보다 길면 클립 길이로 제한)
+/// 같은 트랙에서 겹친 클립은 겹친 구간만큼 자동 크로스페이드 (지정 값이 더 길면 지정 값 사용)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_clip_fade(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    fade_in_ms: i64,
+    fade_out_ms: i64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if fade_in_ms < 0 || fade_out_ms < 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        with_audio_clip(timeline, track_id, clip_id, |clip| {
+            clip.fade_in_ms = fade_in_ms;
+            clip.fade_out_ms = fade_out_ms;
+            true
+        })
+    }
+}
+
+/// 오디오 클립 조회 후 작업 실행 (클립이 없거나, 트랙이 잠겼거나, f가 false면 ERROR_INVALID_PARAM)
+unsafe fn with_audio_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    f: impl FnOnce(&mut AudioClip) -> bool,
+) -> i32 {
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    let clip = timeline.editable_audio_track_mut(track_id)
+        .and_then(|track| track.clips.iter_mut().find(|c| c.id == clip_id));
+    match clip {
+        Some(clip) => if f(clip) { ERROR_SUCCESS } else { ERROR_INVALID_PARAM },
+        None => ERROR_INVALID_PARAM,
+    }
+}
+
+/// 오디오 클립 볼륨 엔벌로프 포인트 조회 후 작업 실행 (클립이 없거나 f가 false면 ERROR_INVALID_PARAM)
+unsafe fn with_audio_clip_envelope(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    f: impl FnOnce(&mut KeyframeTrack) -> bool,
+) -> i32 {
+    with_audio_clip(timeline, track_id, clip_id, |clip| f(&mut clip.volume_envelope))
+}
+
+/// 오디오 클립 볼륨 엔벌로프 포인트 추가 (같은 시간이 있으면 교체)
+/// - time_ms: 클립 시작 기준 시간
+/// - gain: 게인 배율 (0.0 ~ 2.0, 클립/트랙 볼륨과 곱해짐)
+/// - interpolation: 0=Linear, 1=Bezier, 2=EaseIn, 3=EaseOut, 4=EaseInOut, 5=Hold
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_envelope_point(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    time_ms: i64,
+    gain: f32,
+    interpolation: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if time_ms < 0 || !gain.is_finite() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let point = Keyframe::new(time_ms, gain.clamp(0.0, MAX_CLIP_VOLUME), Interpolation::from_u32(interpolation));
+    unsafe {
+        with_audio_clip_envelope(timeline, track_id, clip_id, |envelope| {
+            envelope.set(point);
+            true
+        })
+    }
+}
+
+/// 오디오 클립 볼륨 엔벌로프 포인트 제거 (해당 시간에 포인트가 없으면 ERROR_INVALID_PARAM)
+#[no_mangle]
+pub extern "C" fn timeline_remove_audio_envelope_point(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    time_ms: i64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe { with_audio_clip_envelope(timeline, track_id, clip_id, |envelope| envelope.remove(time_ms)) }
+}
+
+/// 오디오 클립 볼륨 엔벌로프 포인트 이동 (보간 방식 유지, new_time_ms의 기존 포인트는 교체)
+#[no_mangle]
+pub extern "C" fn timeline_move_audio_envelope_point(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    time_ms: i64,
+    new_time_ms: i64,
+    new_gain: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if new_time_ms < 0 || !new_gain.is_finite() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        with_audio_clip_envelope(timeline, track_id, clip_id, |envelope| {
+            let mut point = match envelope.get_mut(time_ms) {
+                Some(point) => *point,
+                None => return false,
+            };
+            envelope.remove(time_ms);
+            point.time_ms = new_time_ms;
+            point.value = new_gain.clamp(0.0, MAX_CLIP_VOLUME);
+            envelope.set(point);
+            true
+        })
+    }
+}
+
+/// 오디오 클립 피치 유지 설정 (enabled: 0=속도에 따라 피치 변경, 그 외=피치 유지)
+/// 피치 유지는 WSOLA 시간 늘이기 (speed = 1.0이면 영향 없음)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_clip_preserve_pitch(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    enabled: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        with_audio_clip(timeline, track_id, clip_id, |clip| {
+            clip.preserve_pitch = enabled != 0;
+            true
+        })
+    }
+}
+
+/// 오디오 클립 소스 오디오 스트림 선택 (다국어 더빙, 해설 트랙 등)
+/// stream_index: 컨테이너 스트림 번호 (media_probe의 streams[].index), -1=기본 오디오 스트림
+/// 파형은 extract_audio_peaks_stream에 같은 번호를 넘겨 추출
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_clip_stream(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    stream_index: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    let stream_index = match stream_index {
+        -1 => None,
+        index if index >= 0 => Some(index as usize),
+        _ => return ERROR_INVALID_PARAM,
+    };
+
+    unsafe {
+        with_audio_clip(timeline, track_id, clip_id, |clip| {
+            clip.stream_index = stream_index;
+            true
+        })
+    }
+}
+
+/// 오디오 클립 볼륨 설정 (0.0 ~ 2.0, 트랙 볼륨과 곱해짐)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_clip_volume(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    volume: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if !volume.is_finite() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        with_audio_clip(timeline, track_id, clip_id, |clip| {
+            clip.volume = volume.clamp(0.0, MAX_CLIP_VOLUME);
+            true
+        })
+    }
+}
+
+/// 오디오 이펙트 파라미터 검증 + 범위 클램프 (NaN/무한대면 None)
+#[allow(clippy::too_many_arguments)]
+fn audio_effect_params(
+    gain_db: f32,
+    eq_low_db: f32,
+    eq_mid_db: f32,
+    eq_high_db: f32,
+    compressor_threshold_db: f32,
+    compressor_ratio: f32,
+    compressor_attack_ms: f32,
+    compressor_release_ms: f32,
+    compressor_makeup_db: f32,
+    limiter_enabled: i32,
+    limiter_ceiling_db: f32,
+) -> Option<AudioEffectParams> {
+    let values = [
+        gain_db, eq_low_db, eq_mid_db, eq_high_db, compressor_threshold_db, compressor_ratio,
+        compressor_attack_ms, compressor_release_ms, compressor_makeup_db, limiter_ceiling_db,
+    ];
+    if values.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+
+    Some(AudioEffectParams {
+        gain_db: gain_db.clamp(-MAX_EFFECT_GAIN_DB, MAX_EFFECT_GAIN_DB),
+        eq_low_db: eq_low_db.clamp(-MAX_EQ_GAIN_DB, MAX_EQ_GAIN_DB),
+        eq_mid_db: eq_mid_db.clamp(-MAX_EQ_GAIN_DB, MAX_EQ_GAIN_DB),
+        eq_high_db: eq_high_db.clamp(-MAX_EQ_GAIN_DB, MAX_EQ_GAIN_DB),
+        compressor_threshold_db: compressor_threshold_db.clamp(-60.0, 0.0),
+        compressor_ratio: compressor_ratio.clamp(1.0, MAX_COMPRESSOR_RATIO),
+        compressor_attack_ms: compressor_attack_ms.clamp(0.1, 500.0),
+        compressor_release_ms: compressor_release_ms.clamp(1.0, 5000.0),
+        compressor_makeup_db: compressor_makeup_db.clamp(0.0, MAX_EFFECT_GAIN_DB),
+        limiter_enabled: limiter_enabled != 0,
+        limiter_ceiling_db: limiter_ceiling_db.clamp(-24.0, 0.0),
+    })
+}
+
+/// 오디오 클립 이펙트 설정 (프리뷰 재생/Export 믹스에 적용, 볼륨/페이드 전 원본 신호 기준)
+/// - gain_db: 입력 게인 (±24dB)
+/// - eq_low/mid/high_db: 3밴드 EQ (200Hz 셸프 / 1kHz 피크 / 5kHz 셸프, ±18dB)
+/// - compressor_threshold_db: -60 ~ 0, compressor_ratio: 1.0(꺼짐) ~ 20.0
+/// - compressor_attack_ms / release_ms / makeup_db: 어택, 릴리스, 메이크업 게인 (0 ~ 24dB)
+/// - limiter_enabled: 0=꺼짐, 그 외=켜짐, limiter_ceiling_db: -24 ~ 0
+///
+/// 범위 밖 값은 클램프, 모두 기본값(0dB, 비율 1, 리미터 꺼짐)이면 이펙트 없음
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_clip_effects(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    gain_db: f32,
+    eq_low_db: f32,
+    eq_mid_db: f32,
+    eq_high_db: f32,
+    compressor_threshold_db: f32,
+    compressor_ratio: f32,
+    compressor_attack_ms: f32,
+    compressor_release_ms: f32,
+    compressor_makeup_db: f32,
+    limiter_enabled: i32,
+    limiter_ceiling_db: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let params = match audio_effect_params(
+        gain_db, eq_low_db, eq_mid_db, eq_high_db, compressor_threshold_db, compressor_ratio,
+        compressor_attack_ms, compressor_release_ms, compressor_makeup_db, limiter_enabled, limiter_ceiling_db,
+    ) {
+        Some(params) => params,
+        None => return ERROR_INVALID_PARAM,
+    };
+    unsafe {
+        with_audio_clip(timeline, track_id, clip_id, |clip| {
+            clip.effects = params;
+            true
+        })
+    }
+}
+
+/// 오디오 트랙 이펙트 설정 (트랙 클립을 합산한 버스에 볼륨/팬 이후 적용, 파라미터는 클립 이펙트와 동일)
+#[no_mangle]
+pub extern "C" fn timeline_set_audio_track_effects(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    gain_db: f32,
+    eq_low_db: f32,
+    eq_mid_db: f32,
+    eq_high_db: f32,
+    compressor_threshold_db: f32,
+    compressor_ratio: f32,
+    compressor_attack_ms: f32,
+    compressor_release_ms: f32,
+    compressor_makeup_db: f32,
+    limiter_enabled: i32,
+    limiter_ceiling_db: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let params = match audio_effect_params(
+        gain_db, eq_low_db, eq_mid_db, eq_high_db, compressor_threshold_db, compressor_ratio,
+        compressor_attack_ms, compressor_release_ms, compressor_makeup_db, limiter_enabled, limiter_ceiling_db,
+    ) {
+        Some(params) => params,
+        None => return ERROR_INVALID_PARAM,
+    };
+    unsafe {
+        with_audio_track(timeline, track_id, |track| {
+            track.effects = params;
+            true
+        })
+    }
+}
+
+/// 특정 비디오 트랙의 클립 개수 가져오기
+#[no_mangle]
+pub extern "C" fn timeline_get_video_clip_count(
+    timeline: *const std::ffi::c_void,
+    track_id: u64,
+    out_count: *mut usize,
+) -> i32 {
+    if timeline.is_null() || out_count.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        if let Some(track) = timeline.video_tracks.iter().find(|t| t.id == track_id) {
+            *out_count = track.clips.len();
+            ERROR_SUCCESS
+        } else {
+            ERROR_INVALID_PARAM
+        }
+    }
+}
+
+/// 비디오 트랙의 index번째 클립 정보 (시작 시간 순, 0 ≤ index < timeline_get_video_clip_count)
+/// out_clip.file_path는 string_free로 해제 (타이틀 클립은 null)
+#[no_mangle]
+pub extern "C" fn timeline_get_video_clip_info(
+    timeline: *const std::ffi::c_void,
+    track_id: u64,
+    index: u32,
+    out_clip: *mut CClip,
+) -> i32 {
+    if timeline.is_null() || out_clip.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let track = match timeline.video_tracks.iter().find(|t| t.id == track_id) {
+            Some(track) => track,
+            None => return ERROR_INVALID_PARAM,
+        };
+        let clip = match track.clips.get(index as usize) {
+            Some(clip) => clip,
+            None => return ERROR_INVALID_PARAM,
+        };
+
+        let file_path = if clip.is_generated() {
+            std::ptr::null_mut()
+        } else {
+            match CString::new(clip.file_path.to_string_lossy().as_bytes()) {
+                Ok(s) => s.into_raw(),
+                Err(_) => return ERROR_INVALID_PARAM,
+            }
+        };
+
+        *out_clip = CClip {
+            id: clip.id,
+            start_time_ms: clip.start_time_ms,
+            duration_ms: clip.duration_ms,
+            track_index: track.index as i32,
+            clip_type: clip_type_code(clip.clip_type),
+            file_path,
+            trim_start_ms: clip.trim_start_ms,
+            trim_end_ms: clip.trim_end_ms,
+            speed: clip.speed,
+            stream_index: clip.stream_index.map_or(-1, |index| index as i32),
+        };
+    }
+
+    ERROR_SUCCESS
+}
+
+/// CClip.clip_type 값 (0=Video, 1=Audio, 2=Image, 3=Title, 4=Color)
+fn clip_type_code(clip_type: ClipType) -> i32 {
+    match clip_type {
+        ClipType::Video => 0,
+        ClipType::Audio => 1,
+        ClipType::Image => 2,
+        ClipType::Title => 3,
+        ClipType::Color => 4,
+    }
+}
+
+fn stream_index_json(stream_index: Option<usize>) -> String {
+    stream_index.map_or("-1".to_string(), |index| index.to_string())
+}
+
+fn video_track_json(track: &VideoTrack) -> String {
+    let clips: Vec<String> = track.clips.iter()
+        .map(|c| format!(
+            "{{\"id\":{},\"type\":{},\"file_path\":\"{}\",\"stream_index\":{},\"start_time_ms\":{},\"duration_ms\":{},\"trim_start_ms\":{},\"trim_end_ms\":{},\"speed\":{},\"use_source_audio\":{},\"title\":\"{}\"}}",
+            c.id,
+            clip_type_code(c.clip_type),
+            json_escape(&c.file_path.to_string_lossy()),
+            stream_index_json(c.stream_index),
+            c.start_time_ms,
+            c.duration_ms,
+            c.trim_start_ms,
+            c.trim_end_ms,
+            c.speed,
+            c.use_source_audio,
+            json_escape(c.title.as_ref().map_or("", |t| t.text.as_str())),
+        ))
+        .collect();
+    let transitions: Vec<String> = track.transitions.iter()
+        .map(|t| format!(
+            "{{\"from_clip_id\":{},\"to_clip_id\":{},\"kind\":{},\"duration_ms\":{}}}",
+            t.from_clip_id, t.to_clip_id, t.kind as u32, t.duration_ms
+        ))
+        .collect();
+    format!(
+        "{{\"id\":{},\"index\":{},\"name\":\"{}\",\"enabled\":{},\"locked\":{},\"clips\":[{}],\"transitions\":[{}]}}",
+        track.id, track.index, json_escape(&track.name), track.enabled, track.locked,
+        clips.join(","), transitions.join(",")
+    )
+}
+
+fn audio_track_json(track: &AudioTrack) -> String {
+    let clips: Vec<String> = track.clips.iter()
+        .map(|c| format!(
+            "{{\"id\":{},\"file_path\":\"{}\",\"stream_index\":{},\"start_time_ms\":{},\"duration_ms\":{},\"trim_start_ms\":{},\"trim_end_ms\":{},\"speed\":{},\"preserve_pitch\":{},\"volume\":{},\"fade_in_ms\":{},\"fade_out_ms\":{}}}",
+            c.id,
+            json_escape(&c.file_path.to_string_lossy()),
+            stream_index_json(c.stream_index),
+            c.start_time_ms,
+            c.duration_ms,
+            c.trim_start_ms,
+            c.trim_end_ms,
+            c.speed,
+            c.preserve_pitch,
+            c.volume,
+            c.fade_in_ms,
+            c.fade_out_ms,
+        ))
+        .collect();
+    format!(
+        "{{\"id\":{},\"index\":{},\"name\":\"{}\",\"enabled\":{},\"locked\":{},\"muted\":{},\"solo\":{},\"volume\":{},\"pan\":{},\"clips\":[{}]}}",
+        track.id, track.index, json_escape(&track.name), track.enabled, track.locked,
+        track.muted, track.solo, track.volume, track.pan, clips.join(",")
+    )
+}
+
+/// 전체 트랙/클립 조회 (JSON, 프로젝트 로드/Undo 후 호스트 뷰 재동기화용)
+/// - out_json: string_free로 해제
+///
+/// {"video_tracks":[{"id":1,"index":0,"name":"","enabled":true,"locked":false,
+///   "clips":[{"id":3,"type":0,"file_path":"a.mp4","stream_index":-1,"start_time_ms":0,"duration_ms":5000,
+///             "trim_start_ms":0,"trim_end_ms":5000,"speed":1,"use_source_audio":true,"title":""}],
+///   "transitions":[{"from_clip_id":3,"to_clip_id":4,"kind":0,"duration_ms":500}]}],
+///  "audio_tracks":[{"id":2,"index":0,"name":"","enabled":true,"locked":false,"muted":false,"solo":false,"volume":1,"pan":0,
+///   "clips":[{"id":5,"file_path":"a.wav","stream_index":-1,"start_time_ms":0,"duration_ms":5000,"trim_start_ms":0,
+///             "trim_end_ms":5000,"speed":1,"preserve_pitch":false,"volume":1,"fade_in_ms":0,"fade_out_ms":0}]}]}
+/// type: 0=Video, 2=Image, 3=Title / kind: TransitionKind 값 / 트랙은 index 순, 클립은 시작 시간 순
+#[no_mangle]
+pub extern "C" fn timeline_get_all_clips_json(
+    timeline: *const std::ffi::c_void,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if timeline.is_null() || out_json.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        *out_json = std::ptr::null_mut();
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let video: Vec<String> = timeline.video_tracks.iter().map(video_track_json).collect();
+        let audio: Vec<String> = timeline.audio_tracks.iter().map(audio_track_json).collect();
+        let json = format!("{{\"video_tracks\":[{}],\"audio_tracks\":[{}]}}", video.join(","), audio.join(","));
+
+        match CString::new(json) {
+            Ok(s) => *out_json = s.into_raw(),
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 비디오 클립의 원본 in/out 지점 설정 (Razor 분할/프로젝트 복원용, Undo 가능)
+/// 트림 값만 변경 — 타임라인 시작 위치/길이는 유지 (시작 위치를 같이 옮기는 트림은 timeline_trim_clip)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_trim(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    trim_start_ms: i64,
+    trim_end_ms: i64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if timeline.set_video_clip_trim(track_id, clip_id, trim_start_ms, trim_end_ms) {
+        ERROR_SUCCESS
+    } else {
+        ERROR_INVALID_PARAM
+    }
+}
+
+/// 비디오 클립 프레임 보간 설정 (슬로우 모션 시 프레임 복제 대신 모션 보상 보간)
+/// enabled: 0=끄기, 그 외=켜기
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_interpolation(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    enabled: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            clip.frame_interpolation = enabled != 0;
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 소스 오디오 사용 설정 (enabled: 0=끄기, 그 외=켜기, 기본 켜짐)
+/// 켜져 있으면 비디오 파일의 오디오가 믹서/Export에 포함 (트랙 볼륨 1.0, 중앙)
+/// 오디오를 별도 오디오 클립으로 분리해 편집할 때는 끄기 (이중 재생 방지)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_use_source_audio(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    enabled: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            clip.use_source_audio = enabled != 0;
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 소스 비디오 스트림 선택 (멀티 앵글, 화면+카메라 녹화 등)
+/// stream_index: 컨테이너 스트림 번호 (media_probe의 streams[].index), -1=기본 비디오 스트림
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_stream(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    stream_index: i32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    let stream_index = match stream_index {
+        -1 => None,
+        index if index >= 0 => Some(index as usize),
+        _ => return ERROR_INVALID_PARAM,
+    };
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            clip.stream_index = stream_index;
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 LUT 설정 (.cube 파일, NULL이면 해제)
+/// 파싱된 LUT는 Renderer에서 파일별로 캐싱되어 클립 간 공유됨
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_lut(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    lut_path: *const c_char,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let lut_path = match lut_path_arg(lut_path) {
+            Ok(path) => path,
+            Err(()) => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        if let Some(track) = timeline.editable_video_track_mut(track_id) {
+            if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+                clip.lut_path = lut_path;
+                return ERROR_SUCCESS;
+            }
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 트랜스폼 설정 (PIP/모션 레이아웃)
+/// - offset_x/offset_y: 출력 크기 대비 이동 비율 (0 = 가운데, 0.25 = 1/4만큼 오른쪽/아래)
+/// - scale: 배율 (1.0 = 출력 전체, 0보다 커야 함)
+/// - rotation_deg: 회전 각도 (시계 방향 +)
+///
+/// 합성 순서는 트랙 순서 (뒤 트랙이 위)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_transform(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    offset_x: f32,
+    offset_y: f32,
+    scale: f32,
+    rotation_deg: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let values_finite = offset_x.is_finite() && offset_y.is_finite()
+        && scale.is_finite() && rotation_deg.is_finite();
+    if !values_finite || scale <= 0.0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            clip.transform.offset_x = offset_x;
+            clip.transform.offset_y = offset_y;
+            clip.transform.scale = scale;
+            clip.transform.rotation_deg = rotation_deg;
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 크롭 설정 (각 가장자리에서 잘라낼 비율 0.0~1.0, 전부 0이면 해제)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_crop(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    left: f32,
+    top: f32,
+    right: f32,
+    bottom: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let crop = CropRect { left, top, right, bottom };
+    if !crop.is_valid() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            clip.transform.crop = crop;
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 키프레임 조회 후 작업 실행 (클립이 없으면 ERROR_INVALID_PARAM)
+unsafe fn with_clip_keyframes(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    f: impl FnOnce(&mut ClipKeyframes) -> bool,
+) -> i32 {
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            return if f(&mut clip.keyframes) { ERROR_SUCCESS } else { ERROR_INVALID_PARAM };
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 클립 속성 키프레임 추가 (같은 시간이 있으면 교체)
+/// - property: 0=OffsetX, 1=OffsetY, 2=Scale, 3=Rotation, 4=Opacity,
+///   5=Brightness, 6=Contrast, 7=Saturation, 8=Temperature,
+///   9=Gamma, 10=Highlights, 11=Shadows, 12=Tint, 13=Vignette
+/// - time_ms: 클립 시작 기준 시간
+/// - interpolation: 0=Linear, 1=Bezier, 2=EaseIn, 3=EaseOut, 4=EaseInOut, 5=Hold
+#[no_mangle]
+pub extern "C" fn timeline_set_clip_keyframe(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    property: u32,
+    time_ms: i64,
+    value: f32,
+    interpolation: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let property = match KeyframeProperty::from_u32(property) {
+        Some(p) => p,
+        None => return ERROR_INVALID_PARAM,
+    };
+    if time_ms < 0 || !value.is_finite() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let keyframe = Keyframe::new(time_ms, value, Interpolation::from_u32(interpolation));
+    unsafe {
+        with_clip_keyframes(timeline, track_id, clip_id, |keyframes| {
+            keyframes.set(property, keyframe);
+            true
+        })
+    }
+}
+
+/// 클립 속성 키프레임 제거 (해당 시간에 키프레임이 없으면 ERROR_INVALID_PARAM)
+#[no_mangle]
+pub extern "C" fn timeline_remove_clip_keyframe(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    property: u32,
+    time_ms: i64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let property = match KeyframeProperty::from_u32(property) {
+        Some(p) => p,
+        None => return ERROR_INVALID_PARAM,
+    };
+
+    unsafe { with_clip_keyframes(timeline, track_id, clip_id, |keyframes| keyframes.remove(property, time_ms)) }
+}
+
+/// 클립 키프레임 전체 제거 (property가 유효 범위 밖이면 모든 속성)
+#[no_mangle]
+pub extern "C" fn timeline_clear_clip_keyframes(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    property: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let property = KeyframeProperty::from_u32(property);
+    unsafe {
+        with_clip_keyframes(timeline, track_id, clip_id, |keyframes| {
+            keyframes.clear(property);
+            true
+        })
+    }
+}
+
+/// 베지어 키프레임 핸들 설정 (키프레임 값 기준 오프셋, NaN이면 기본 핸들)
+#[no_mangle]
+pub extern "C" fn timeline_set_clip_keyframe_handles(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    property: u32,
+    time_ms: i64,
+    in_value_offset: f32,
+    out_value_offset: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let property = match KeyframeProperty::from_u32(property) {
+        Some(p) => p,
+        None => return ERROR_INVALID_PARAM,
+    };
+    let handle = |offset: f32| offset.is_finite().then_some(offset);
+
+    unsafe {
+        with_clip_keyframes(timeline, track_id, clip_id, |keyframes| {
+            match keyframes.get_mut(property, time_ms) {
+                Some(keyframe) => {
+                    keyframe.in_value_offset = handle(in_value_offset);
+                    keyframe.out_value_offset = handle(out_value_offset);
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+/// 맞닿은 두 비디오 클립 사이 트랜지션 설정 (같은 경계의 기존 트랜지션은 교체)
+/// - kind: 0=Crossfade, 1=DipToBlack, 2=Wipe, 3=Slide
+/// - duration_ms: 컷 지점 중심 전환 길이 (짧은 클립 길이로 제한)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_transition(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    from_clip_id: u64,
+    to_clip_id: u64,
+    kind: u32,
+    duration_ms: i64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        let transition = Transition::new(from_clip_id, to_clip_id, TransitionKind::from_u32(kind), duration_ms);
+        if track.set_transition(transition) {
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 뒤쪽 트랜지션 제거
+#[no_mangle]
+pub extern "C" fn timeline_remove_video_transition(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    from_clip_id: u64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if track.remove_transition(from_clip_id) {
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// FFI 인자 → 장면 감지 파라미터 (0 이하면 기본값)
+fn scene_detect_params(threshold: f32, min_scene_ms: i64) -> SceneDetectParams {
+    let mut params = SceneDetectParams::default();
+    if threshold > 0.0 {
+        params.threshold = threshold;
+    }
+    if min_scene_ms > 0 {
+        params.min_scene_ms = min_scene_ms;
+    }
+    params
+}
+
+/// 비디오 파일 장면 컷 위치 감지 (분할 없이 타임스탬프만 반환, 마커/미리보기용)
+/// - start_ms/end_ms: 분석할 소스 구간 (end_ms 0 이하면 파일 끝까지)
+/// - threshold / min_scene_ms: timeline_auto_split_video_clip과 동일
+/// - out_cuts: 컷 소스 시간 배열 (ms, 오름차순, free_scene_cuts로 해제), 컷이 없으면 NULL
+#[no_mangle]
+pub extern "C" fn detect_scene_cuts(
+    file_path: *const c_char,
+    start_ms: i64,
+    end_ms: i64,
+    threshold: f32,
+    min_scene_ms: i64,
+    out_cuts: *mut *mut i64,
+    out_count: *mut u32,
+) -> i32 {
+    if file_path.is_null() || out_cuts.is_null() || out_count.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        *out_cuts = std::ptr::null_mut();
+        *out_count = 0;
+
+        let path = match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => PathBuf::from(s),
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+        let end_ms = if end_ms > 0 { end_ms } else { i64::MAX };
+        if start_ms < 0 || start_ms >= end_ms {
+            return ERROR_INVALID_PARAM;
+        }
+
+        let cuts = match scene_detect::detect_scenes(&path, start_ms, end_ms, scene_detect_params(threshold, min_scene_ms)) {
+            Ok(c) => c,
+            Err(e) => {
+                report_error(format!("detect_scene_cuts: {}", e));
+                return ERROR_INVALID_PARAM;
+            }
+        };
+
+        if !cuts.is_empty() {
+            *out_count = cuts.len() as u32;
+            *out_cuts = Box::into_raw(cuts.into_boxed_slice()) as *mut i64;
+        }
+    }
+
+    ERROR_SUCCESS
+}
+
+/// detect_scene_cuts 결과 해제
+#[no_mangle]
+pub extern "C" fn free_scene_cuts(cuts: *mut i64, count: u32) -> i32 {
+    if cuts.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let slice = std::slice::from_raw_parts_mut(cuts, count as usize);
+        let _ = Box::from_raw(slice as *mut [i64]);
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 장면 전환 위치에서 비디오 클립 자동 분할
+/// 클립의 트림 구간을 분석하여 컷마다 분할 (분석 중에는 timeline lock을 잡지 않음)
+/// - threshold: 히스토그램 차이 임계값 (0 이하면 기본값 0.35)
+/// - min_scene_ms: 컷 사이 최소 간격 (0 이하면 기본값 1000ms)
+/// - out_split_count: 실제 분할 횟수 (생성된 새 클립 수)
+#[no_mangle]
+pub extern "C" fn timeline_auto_split_video_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    threshold: f32,
+    min_scene_ms: i64,
+    out_split_count: *mut u32,
+) -> i32 {
+    if timeline.is_null() || out_split_count.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let params = scene_detect_params(threshold, min_scene_ms);
+
+    unsafe {
+        *out_split_count = 0;
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+
+        // 1. 클립 정보 복사 후 lock 해제
+        let clip = {
+            let timeline = match timeline_arc.lock() {
+                Ok(t) => t,
+                Err(_) => return ERROR_INVALID_PARAM,
+            };
+            match timeline.video_tracks.iter()
+                .find(|t| t.id == track_id)
+                .and_then(|t| t.get_clip_by_id(clip_id))
+            {
+                Some(c) => c.clone(),
+                None => return ERROR_INVALID_PARAM,
+            }
+        };
+
+        // 2. 트림 구간 장면 분석 (오래 걸림)
+        let source_end_ms = clip.trim_start_ms + clip.source_offset(clip.duration_ms);
+        let cuts = match scene_detect::detect_scenes(&clip.file_path, clip.trim_start_ms, source_end_ms, params) {
+            Ok(c) => c,
+            Err(e) => {
+                report_error(format!("timeline_auto_split_video_clip: {}", e));
+                return ERROR_INVALID_PARAM;
+            }
+        };
+
+        // 3. 컷 위치(소스 시간) → 타임라인 시간으로 변환하여 순서대로 분할
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let mut current_id = clip_id;
+        let mut split_count = 0u32;
+        for cut_ms in cuts {
+            let split_time_ms = clip.source_to_timeline_time(cut_ms);
+            if let Some(new_id) = timeline.split_video_clip(track_id, current_id, split_time_ms) {
+                current_id = new_id;
+                split_count += 1;
+            }
+        }
+
+        *out_split_count = split_count;
+    }
+
+    ERROR_SUCCESS
+}
+
+/// 비디오 클립의 소스 끝 이후 동작 설정
+/// behavior: 0=Freeze(마지막 프레임 유지), 1=Black, 2=Loop
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_end_behavior(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    behavior: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            clip.end_behavior = EndOfMediaBehavior::from_u32(behavior);
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 비디오 클립 맞춤 모드 설정 (소스와 프로젝트 종횡비가 다를 때, 프리뷰/Export 공통)
+/// mode: 0=Fit(레터박스/필러박스), 1=Fill(중앙 크롭), 2=Stretch(늘림), 3=Custom(원본 픽셀 크기)
+#[no_mangle]
+pub extern "C" fn timeline_set_video_clip_fit_mode(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    mode: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+    if mode > FitMode::Custom as u32 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+            clip.fit_mode = FitMode::from_u32(mode);
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// C 인자 → 타이틀 내용 (text: UTF-8, font_path/style: null이면 시스템 폰트/기본 스타일)
+unsafe fn title_from_c(
+    text: *const c_char,
+    font_path: *const c_char,
+    style: *const CSubtitleStyle,
+) -> Option<TitleClip> {
+    let text = CStr::from_ptr(text).to_str().ok()?;
+    let font_path = font_path_from_c(font_path).ok()?;
+    Some(TitleClip {
+        font_path: font_path.map(|p| p.to_path_buf()),
+        style: style_from_c(style),
+        ..TitleClip::new(text)
+    })
+}
+
+/// 타이틀 클립 추가 (엔진 텍스트 래스터라이저로 렌더링, 소스 파일 없음)
+/// - text: UTF-8 텍스트 (\n 줄바꿈)
+/// - font_path: 폰트 파일 (NULL이면 시스템 기본 폰트)
+/// - style: NULL이면 기본 스타일, 크기/여백은 프로젝트 해상도 기준 픽셀
+///
+/// 이동/트림/분할/트랜스폼/키프레임/트랜지션은 일반 비디오 클립과 동일
+#[no_mangle]
+pub extern "C" fn timeline_add_title_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    text: *const c_char,
+    font_path: *const c_char,
+    style: *const CSubtitleStyle,
+    start_time_ms: i64,
+    duration_ms: i64,
+    out_clip_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || text.is_null() || out_clip_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    if duration_ms <= 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        let title = match title_from_c(text, font_path, style) {
+            Some(title) => title,
+            None => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match timeline.add_title_clip(track_id, title, start_time_ms, duration_ms) {
+            Some(clip_id) => {
+                *out_clip_id = clip_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM, // 트랙을 찾을 수 없음
+        }
+    }
+}
+
+/// 타이틀 클립 텍스트/폰트/스타일 변경 (애니메이션은 유지)
+#[no_mangle]
+pub extern "C" fn timeline_set_title_clip_text(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    text: *const c_char,
+    font_path: *const c_char,
+    style: *const CSubtitleStyle,
+) -> i32 {
+    if timeline.is_null() || text.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let new_title = match title_from_c(text, font_path, style) {
+            Some(title) => title,
+            None => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        if let Some(track) = timeline.editable_video_track_mut(track_id) {
+            if let Some(title) = track.get_clip_by_id_mut(clip_id).and_then(|c| c.title.as_mut()) {
+                title.text = new_title.text;
+                title.font_path = new_title.font_path;
+                title.style = new_title.style;
+                return ERROR_SUCCESS;
+            }
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 타이틀 클립 인/아웃 애니메이션 설정
+/// animation: 0=None, 1=Fade, 2=SlideUp, 3=SlideDown, 4=SlideLeft, 5=SlideRight, 6=Zoom
+/// in_ms/out_ms: 애니메이션 길이 (0이면 없음, 클립 시작/끝 기준)
+#[no_mangle]
+pub extern "C" fn timeline_set_title_clip_animation(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    animation_in: u32,
+    in_ms: i64,
+    animation_out: u32,
+    out_ms: i64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    if in_ms < 0 || out_ms < 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(title) = track.get_clip_by_id_mut(clip_id).and_then(|c| c.title.as_mut()) {
+            title.animation_in = TitleAnimation::from_u32(animation_in);
+            title.animation_in_ms = in_ms;
+            title.animation_out = TitleAnimation::from_u32(animation_out);
+            title.animation_out_ms = out_ms;
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// C 인자 → ColorClip (색은 0xRRGGBBAA, gradient != 0이면 color → end_color 선형 그라디언트)
+fn color_clip_from_c(color: u32, end_color: u32, gradient: i32, angle_deg: f32) -> Option<ColorClip> {
+    if !angle_deg.is_finite() {
+        return None;
+    }
+    Some(match gradient {
+        0 => ColorClip::solid(color.to_be_bytes()),
+        _ => ColorClip::gradient(color.to_be_bytes(), end_color.to_be_bytes(), angle_deg),
+    })
+}
+
+/// 컬러 클립 추가 (단색 또는 2색 선형 그라디언트, 소스 파일 없음)
+/// - color / end_color: 0xRRGGBBAA (end_color는 gradient != 0일 때만 사용)
+/// - angle_deg: 그라디언트 방향 (0 = 왼쪽 → 오른쪽, 90 = 위 → 아래)
+///
+/// 이동/트림/분할/트랜스폼/키프레임/트랜지션은 일반 비디오 클립과 동일
+#[no_mangle]
+pub extern "C" fn timeline_add_color_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    color: u32,
+    end_color: u32,
+    gradient: i32,
+    angle_deg: f32,
+    start_time_ms: i64,
+    duration_ms: i64,
+    out_clip_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || out_clip_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    if duration_ms <= 0 {
+        return ERROR_INVALID_PARAM;
+    }
+    let color = match color_clip_from_c(color, end_color, gradient, angle_deg) {
+        Some(color) => color,
+        None => return ERROR_INVALID_PARAM,
+    };
+
+    unsafe {
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match timeline.add_color_clip(track_id, color, start_time_ms, duration_ms) {
+            Some(clip_id) => {
+                *out_clip_id = clip_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM, // 트랙을 찾을 수 없음
+        }
+    }
+}
+
+/// 컬러 클립 색 변경 (인자는 timeline_add_color_clip과 동일)
+#[no_mangle]
+pub extern "C" fn timeline_set_color_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    clip_id: u64,
+    color: u32,
+    end_color: u32,
+    gradient: i32,
+    angle_deg: f32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let new_color = match color_clip_from_c(color, end_color, gradient, angle_deg) {
+        Some(color) => color,
+        None => return ERROR_INVALID_PARAM,
+    };
+
+    let timeline_arc = match TIMELINES.get(timeline) {
+        Some(t) => t,
+        None => return ERROR_INVALID_HANDLE,
+    };
+    let mut timeline = match timeline_arc.lock() {
+        Ok(t) => t,
+        Err(_) => return ERROR_INVALID_PARAM,
+    };
+
+    if let Some(track) = timeline.editable_video_track_mut(track_id) {
+        if let Some(color) = track.get_clip_by_id_mut(clip_id).and_then(|c| c.color.as_mut()) {
+            *color = new_color;
+            return ERROR_SUCCESS;
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 트랙 통계 배열 → JSON
+fn track_stats_json(tracks: &[TrackStats]) -> String {
+    let items: Vec<String> = tracks.iter()
+        .map(|t| format!(
+            "{{\"id\":{},\"clip_count\":{},\"enabled\":{}}}",
+            t.track_id, t.clip_count, t.enabled
+        ))
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+/// 타임라인 통계 조회 (JSON, 프로젝트 정보 패널/Export 전 점검용)
+/// - renderer: 이펙트 사용 통계용 (NULL이면 이펙트 항목은 0)
+/// - out_json: UTF-8 JSON 문자열 (string_free로 해제)
+///
+/// {"video_tracks":[{"id":1,"clip_count":2,"enabled":true}],"audio_tracks":[...],
+///  "source_file_count":2,"media_duration_ms":9000,"timeline_duration_ms":7000,
+///  "effects":{"color_clips":1,"brightness":1,"contrast":0,"saturation":0,"temperature":0,
+///   "gamma":0,"highlights":0,"shadows":0,"tint":0,"vignette":0,"interpolated_clips":0}}
+#[no_mangle]
+pub extern "C" fn timeline_get_stats(
+    timeline: *const std::ffi::c_void,
+    renderer: *mut std::ffi::c_void,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if timeline.is_null() || out_json.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        *out_json = std::ptr::null_mut();
+
+        let timeline_arc = match TIMELINES.get(timeline) {
+            Some(t) => t,
+            None => return ERROR_INVALID_HANDLE,
+        };
+        let stats = match timeline_arc.lock() {
+            Ok(t) => t.stats(),
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        // 이펙트 사용 통계 (파라미터별 0이 아닌 클립 수)
+        let mut effect_counts = [0usize; 10];
+        if !renderer.is_null() {
+            let renderer_mutex = match RENDERERS.get(renderer) {
+                Some(r) => r,
+                None => return ERROR_INVALID_HANDLE,
+            };
+            let r = match renderer_mutex.lock() {
+                Ok(r) => r,
+                Err(_) => return ERROR_INVALID_PARAM,
+            };
+            for params in r.clip_effects().values() {
+                effect_counts[0] += 1;
+                for (count, value) in effect_counts[1..].iter_mut().zip(params.values()) {
+                    if value.abs() >= 0.001 {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        let json = format!(
+            "{{\"video_tracks\":{},\"audio_tracks\":{},\"source_file_count\":{},\"media_duration_ms\":{},\"timeline_duration_ms\":{},\"effects\":{{\"color_clips\":{},\"brightness\":{},\"contrast\":{},\"saturation\":{},\"temperature\":{},\"gamma\":{},\"highlights\":{},\"shadows\":{},\"tint\":{},\"vignette\":{},\"interpolated_clips\":{}}}}}",
+            track_stats_json(&stats.video_tracks),
+            track_stats_json(&stats.audio_tracks),
+            stats.source_file_count,
+            stats.media_duration_ms,
+            stats.timeline_duration_ms,
+            effect_counts[0],
+            effect_counts[1],
+            effect_counts[2],
+            effect_counts[3],
+            effect_counts[4],
+            effect_counts[5],
+            effect_counts[6],
+            effect_counts[7],
+            effect_counts[8],
+            effect_counts[9],
+            stats.interpolated_clip_count,
+        );
+
+        match CString::new(json) {
+            Ok(s) => *out_json = s.into_raw(),
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    }
+
+    ERROR_SUCCESS
 }
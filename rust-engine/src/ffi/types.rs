@@ -1,6 +1,8 @@
 // C-compatible 타입 정의
 // C#과 공유되는 데이터 구조
 
+use crate::utils::error;
+use crate::utils::log::log_error;
 use std::os::raw::c_char;
 
 /// 에러 코드
@@ -10,6 +12,8 @@ pub const ERROR_INVALID_PARAM: i32 = 2;
 pub const ERROR_FFMPEG: i32 = 3;
 pub const ERROR_IO: i32 = 4;
 pub const ERROR_RENDER_FAILED: i32 = 5;
+/// 파괴되었거나 다른 종류의 핸들 (ffi::handle)
+pub const ERROR_INVALID_HANDLE: i32 = 6;
 pub const ERROR_UNKNOWN: i32 = 99;
 
 /// 에러 코드 Enum
@@ -21,25 +25,39 @@ pub enum ErrorCode {
     Ffmpeg = 3,
     Io = 4,
     RenderFailed = 5,
+    InvalidHandle = 6,
     Unknown = 99,
 }
 
-/// C-compatible 에러 구조체
+/// C-compatible 에러 구조체 (engine_get_last_error)
 #[repr(C)]
 pub struct CError {
-    pub code: i32,
-    pub message: *const c_char,
+    /// utils::error::ErrorCategory (0 = 기록된 에러 없음)
+    pub category: i32,
+    /// 전체 에러 메시지 (string_free로 해제, 에러 없으면 null)
+    pub message: *mut c_char,
 }
 
-/// C-compatible 클립 구조체
+/// FFI 실패 보고: 에러 로그 + 호출 스레드의 마지막 에러 기록
+pub fn report_error(message: String) {
+    log_error!("{}", message);
+    error::set_last_error(message);
+}
+
+/// C-compatible 클립 구조체 (timeline_get_video_clip_info)
+/// file_path는 string_free로 해제 (타이틀/컬러 클립은 null)
 #[repr(C)]
 pub struct CClip {
     pub id: u64,
     pub start_time_ms: i64,
     pub duration_ms: i64,
     pub track_index: i32,
-    pub clip_type: i32,  // 0=Video, 1=Audio, 2=Image
-    pub file_path: *const c_char,
+    pub clip_type: i32,  // 0=Video, 1=Audio, 2=Image, 3=Title, 4=Color
+    pub file_path: *mut c_char,
+    pub trim_start_ms: i64,
+    pub trim_end_ms: i64,
+    pub speed: f64,
+    pub stream_index: i32,  // -1 = 기본 스트림
 }
 
 /// C-compatible 렌더 프레임 구조체
@@ -51,3 +69,55 @@ pub struct CRenderFrame {
     pub data: *mut u8,
     pub data_len: usize,
 }
+
+/// C-compatible Export 통계 구조체 (exporter_get_stats)
+#[repr(C)]
+pub struct CExportStats {
+    pub frames_done: u64,
+    pub total_frames: u64,
+    pub encode_fps: f64,
+    pub average_bitrate_kbps: f64,
+    pub elapsed_ms: u64,
+    pub eta_ms: i64,  // -1 = 추정 불가
+    pub dropped_frames: u64,
+    pub cache_hit_rate: f64,  // 0.0~1.0
+}
+
+/// C-compatible Export 옵션 구조체 (exporter_start_with_options)
+/// struct_size에 호출자 기준 sizeof를 기록 — 새 옵션은 끝에만 추가 (모르는 필드는 0으로 처리)
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CExportOptions {
+    pub struct_size: u32,
+    pub width: u32,
+    pub height: u32,
+    pub crf: u32,
+    pub fps: f64,
+    pub encoder_type: u32,         // 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+    pub codec: u32,                // 0=H.264, 1=H.265, 2=VP9, 3=AV1, 4=ProRes
+    pub rate_control: u32,         // 0=CRF, 1=CBR, 2=VBR, 3=2-pass VBR
+    pub target_bitrate_kbps: u32,
+    pub max_bitrate_kbps: u32,     // 0 = 자동
+    pub buffer_size_kbits: u32,    // 0 = 자동
+    pub target_size_bytes: u64,    // 0보다 크면 target_bitrate_kbps 대신 사용
+    pub start_ms: i64,
+    pub end_ms: i64,               // 0 이하 = 타임라인 끝까지
+    pub audio_bitrate_kbps: u32,   // 0 = 기본 192kbps
+    pub smart_render: u32,
+    pub parallel_segments: u32,
+    pub high_bit_depth_container: u32, // 10bit 픽셀 포맷만 (합성 정밀도는 8bit)
+    pub loudness_target_lufs: f64, // 0 = 사용 안 함
+}
+
+/// C-compatible 자막 스타일 구조체 (subtitle_list_add_text / subtitle_list_load_file)
+/// 색상: 0xRRGGBBAA
+#[repr(C)]
+pub struct CSubtitleStyle {
+    pub font_size: f32,
+    pub color: u32,
+    pub outline_color: u32,
+    pub outline_width: f32,
+    pub alignment: u32,  // 1~9 숫자패드 배치 (2 = 하단 중앙)
+    pub margin_h: i32,
+    pub margin_v: i32,
+}
@@ -0,0 +1,144 @@
+// YUV 색공간 (행렬 + 범위) - RGBA ↔ YUV420P 변환 계수
+// 디코더가 소스 색공간을 Frame에 기록하고, 변환 함수는 같은 행렬로 되돌림 (자막/이펙트 합성 시 색 틀어짐 방지)
+// Export 출력은 BT.709 limited 고정 (스케일러/인코더 태그와 일치)
+
+/// YUV 행렬 계수 (ITU-R 권고)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YuvMatrix {
+    /// SD (BT.601 / SMPTE 170M)
+    Bt601,
+    /// HD
+    Bt709,
+    /// UHD/HDR (non-constant luminance)
+    Bt2020,
+}
+
+impl YuvMatrix {
+    /// (Kr, Kb) 휘도 가중치
+    fn luma_weights(self) -> (f64, f64) {
+        match self {
+            YuvMatrix::Bt601 => (0.299, 0.114),
+            YuvMatrix::Bt709 => (0.2126, 0.0722),
+            YuvMatrix::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+/// YUV 데이터의 색 정보 (행렬 + 범위)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YuvColor {
+    pub matrix: YuvMatrix,
+    /// true: 0~255 (JPEG), false: Y 16~235 / UV 16~240 (방송 규격)
+    pub full_range: bool,
+}
+
+impl YuvColor {
+    /// Export 출력 / 기본값: BT.709 limited
+    pub const BT709: YuvColor = YuvColor { matrix: YuvMatrix::Bt709, full_range: false };
+
+    /// 색공간 태그가 없는 소스 추정 (FFmpeg/플레이어 관례: SD 높이는 BT.601, 그 외 BT.709)
+    pub fn guess(height: u32) -> YuvColor {
+        let matrix = if height < 720 { YuvMatrix::Bt601 } else { YuvMatrix::Bt709 };
+        YuvColor { matrix, full_range: false }
+    }
+
+    /// 정수 변환 계수 (8bit 고정소수점, ×256)
+    pub fn coefficients(self) -> YuvCoefficients {
+        let (kr, kb) = self.matrix.luma_weights();
+        let kg = 1.0 - kr - kb;
+        let (y_range, c_range) = if self.full_range { (255.0, 255.0) } else { (219.0, 224.0) };
+        let fixed = |v: f64| (v * 256.0).round() as i32;
+
+        // RGB → YUV: 가운데 계수는 합이 맞도록 역산 (무채색 → U/V 정확히 128)
+        let y_total = fixed(y_range / 255.0);
+        let (yr, yb) = (fixed(kr * y_range / 255.0), fixed(kb * y_range / 255.0));
+        let c_scale = c_range / 255.0;
+        let (ur, ub) = (fixed(-kr / (2.0 * (1.0 - kb)) * c_scale), fixed(0.5 * c_scale));
+        let (vr, vb) = (fixed(0.5 * c_scale), fixed(-kb / (2.0 * (1.0 - kr)) * c_scale));
+
+        // YUV → RGB
+        let inv_c = 255.0 / c_range;
+        let (y_min, y_max, c_min, c_max) = if self.full_range { (0, 255, 0, 255) } else { (16, 235, 16, 240) };
+
+        YuvCoefficients {
+            y_offset: y_min,
+            y_scale: fixed(255.0 / y_range),
+            rv: fixed(2.0 * (1.0 - kr) * inv_c),
+            gu: fixed(2.0 * (1.0 - kb) * kb / kg * inv_c),
+            gv: fixed(2.0 * (1.0 - kr) * kr / kg * inv_c),
+            bu: fixed(2.0 * (1.0 - kb) * inv_c),
+            yr,
+            yg: y_total - yr - yb,
+            yb,
+            ur,
+            ug: -(ur + ub),
+            ub,
+            vr,
+            vg: -(vr + vb),
+            vb,
+            y_min,
+            y_max,
+            c_min,
+            c_max,
+        }
+    }
+}
+
+impl Default for YuvColor {
+    fn default() -> Self {
+        YuvColor::BT709
+    }
+}
+
+/// 정수 변환 계수 (스칼라/SIMD 공용, 모두 ×256)
+/// YUV → RGB: r = (y_scale·(Y - y_offset) + rv·V' + 128) >> 8 (U' = U - 128, V' = V - 128)
+/// RGB → YUV: Y = ((yr·R + yg·G + yb·B + 128) >> 8) + y_offset, U/V는 + 128
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YuvCoefficients {
+    pub y_offset: i32,
+    pub y_scale: i32,
+    pub rv: i32,
+    pub gu: i32,
+    pub gv: i32,
+    pub bu: i32,
+    pub yr: i32,
+    pub yg: i32,
+    pub yb: i32,
+    pub ur: i32,
+    pub ug: i32,
+    pub ub: i32,
+    pub vr: i32,
+    pub vg: i32,
+    pub vb: i32,
+    pub y_min: i32,
+    pub y_max: i32,
+    pub c_min: i32,
+    pub c_max: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coefficients() {
+        // BT.601 limited: 널리 쓰이는 정수 근사와 동일
+        let c = YuvColor { matrix: YuvMatrix::Bt601, full_range: false }.coefficients();
+        assert_eq!((c.yr, c.yg, c.yb), (66, 129, 25));
+        assert_eq!((c.ur, c.ug, c.ub), (-38, -74, 112));
+        assert_eq!((c.vr, c.vg, c.vb), (112, -94, -18));
+        assert_eq!((c.y_scale, c.rv, c.gu, c.gv, c.bu), (298, 409, 100, 208, 516));
+
+        // BT.709 limited
+        let c = YuvColor::BT709.coefficients();
+        assert_eq!((c.yr, c.yg, c.yb), (47, 157, 16));
+        assert_eq!((c.y_scale, c.rv, c.gu, c.gv, c.bu), (298, 459, 55, 136, 541));
+
+        // full range: Y 그대로
+        let c = YuvColor { matrix: YuvMatrix::Bt2020, full_range: true }.coefficients();
+        assert_eq!((c.y_offset, c.y_scale, c.yr + c.yg + c.yb), (0, 256, 256));
+
+        assert_eq!(YuvColor::guess(480).matrix, YuvMatrix::Bt601);
+        assert_eq!(YuvColor::guess(1080), YuvColor::BT709);
+    }
+}
@@ -2,8 +2,19 @@
 // 아키텍처: 상태 머신 기반 디코더 + EOF/에러 안전 처리
 
 use ffmpeg_next as ffmpeg;
+use ffmpeg::ffi;
+use crate::ffmpeg::colorspace::{YuvColor, YuvMatrix};
+use crate::ffmpeg::frame_index::FrameIndex;
+use crate::ffmpeg::hdr::{ToneMapper, TransferFunction};
+use crate::ffmpeg::{hwaccel, network, probe};
+use crate::utils::thumbnail_fit::{ThumbnailFit, fitted_size, fit_rgba};
+use crate::utils::log::{log_info, log_warn};
+use std::collections::VecDeque;
 use std::path::Path;
 
+/// 역방향 디코딩 링 버퍼 크기 (GOP 전진 디코딩 1회에 목표 직전 프레임을 이만큼 보관)
+const REVERSE_RING_FRAMES: usize = 8;
+
 /// 비디오 프레임 데이터
 #[derive(Debug, Clone)]
 pub struct Frame {
@@ -12,6 +23,10 @@ pub struct Frame {
     pub format: PixelFormat,
     pub data: Vec<u8>,
     pub timestamp_ms: i64,
+    /// YUV420P 데이터의 행렬/범위 (RGBA/RGB 프레임에서는 사용하지 않음)
+    pub color: YuvColor,
+    /// 소스 프레임 표시 길이 (PTS 기준, VFR에서는 프레임마다 다름, 0 = 모름)
+    pub duration_ms: i64,
 }
 
 /// 픽셀 포맷
@@ -22,6 +37,51 @@ pub enum PixelFormat {
     YUV420P,
 }
 
+/// 디코딩 탐색 한도 (스크럽: 응답성 우선, Export: 정확도 우선)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// decode 호출 1회당 최대 패킷 수 (0 = 무제한). 초과 시 FrameSkipped
+    pub max_packets: u32,
+    /// decode 호출 1회당 최대 소요 시간 ms (0 = 무제한). 초과 시 FrameSkipped
+    pub max_decode_ms: u32,
+    /// seek 실패 시 재시도 횟수
+    pub seek_retries: u32,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_packets: 3000,
+            max_decode_ms: 0,
+            seek_retries: 1,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// 탐색 한도 초과 여부 (packet_count: 지금까지 읽은 패킷 수)
+    pub fn exceeded(&self, packet_count: u32, started: std::time::Instant) -> bool {
+        (self.max_packets > 0 && packet_count > self.max_packets)
+            || (self.max_decode_ms > 0
+                && started.elapsed().as_millis() >= self.max_decode_ms as u128)
+    }
+}
+
+/// 랜덤 접근(seek) 정확도
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekMode {
+    /// 스크럽/재생: seek 후 처음 얻은 구간 프레임 수락 (키프레임이 목표 이후면 그 프레임)
+    Fast,
+    /// Export/단일 프레임 추출: 목표 ms에서 시작하는 프레임까지 포함해 PTS 구간으로 확정,
+    /// seek가 목표를 지나치면 더 앞에서 다시 seek
+    Exact,
+}
+
+/// 정확 모드 재-seek: 목표보다 이만큼 앞에서 시작 (실패할 때마다 2배)
+const EXACT_SEEK_BACKOFF_MS: i64 = 1000;
+/// 정확 모드 재-seek 최대 간격 (1s → 2s → 4s → 8s)
+const EXACT_SEEK_MAX_BACKOFF_MS: i64 = 8000;
+
 /// 디코더 상태 머신
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DecoderState {
@@ -48,15 +108,38 @@ pub struct Decoder {
     video_stream_index: usize,
     decoder: ffmpeg::codec::decoder::Video,
     scaler: ffmpeg::software::scaling::Context,
+    /// 스케일러 플래그 (HW 프레임 전송 후 입력 포맷이 바뀌면 같은 플래그로 재생성)
+    scaler_flags: ffmpeg::software::scaling::Flags,
+    /// 스케일러 색공간 (입력 YUV 행렬/범위, 출력 YUV 행렬) — 재생성 시 다시 적용
+    scaler_colorspace: (YuvColor, YuvMatrix),
+    /// YUV 출력 프레임의 색 정보 (소스 행렬, limited range)
+    yuv_color: YuvColor,
+    /// HDR(PQ/HLG) 소스: 스케일러는 RGBA64로 출력하고 SDR RGBA로 톤 매핑
+    tone_mapper: Option<ToneMapper>,
+    /// 알파 채널 소스 (ProRes 4444, VP9 알파 등): 출력 RGBA에 실제 투명도
+    has_alpha: bool,
     width: u32,
     height: u32,
+    /// 표시 기준 원본 크기 (SAR 반영, 썸네일 종횡비 계산용)
+    display_width: u32,
+    display_height: u32,
+    /// 표시 회전 (시계 방향 0/90/180/270, display matrix) — 출력 프레임은 회전 적용 후 방향
+    rotation_deg: i32,
     fps: f64,
+    /// 가변 프레임레이트 소스 (화면 녹화/폰 영상) — fps는 평균값일 뿐, 프레임 매핑은 FrameIndex 사용
+    variable_frame_rate: bool,
     duration_ms: i64,
     last_timestamp_ms: i64,
     is_hardware: bool,
     state: DecoderState,
     /// 마지막 성공 디코딩 프레임 (EOF/에러 시 fallback용)
     last_decoded_frame: Option<Frame>,
+    /// 마지막 반환 프레임의 표시 구간 [시작, 끝) (PTS 단위) — 구간 내 재요청은 디코딩 없이 재사용
+    last_frame_span: Option<(i64, i64)>,
+    /// 목표 확정을 위해 디코더에서 미리 꺼낸 다음 프레임
+    pending_frame: Option<ffmpeg::frame::Video>,
+    /// 패킷 소진 후 EOF 신호를 보내 지연 프레임 배출 중 (순차 디코딩)
+    draining: bool,
     /// Forward decode 임계값 (ms)
     /// - 기본값: frame_duration * 2 (프리뷰 재생용)
     /// - 썸네일 세션: 10000ms (GOP 내 불필요한 seek 방지)
@@ -69,83 +152,225 @@ pub struct Decoder {
     /// true: 디코더 → YUV420P → 인코더 (색공간 변환 없이 최고 품질)
     /// false: 디코더 → RGBA → 프리뷰/썸네일/인코더
     yuv_output: bool,
+    /// 다음 decode_frame에서 강제 seek 여부
+    /// 키프레임 전용 디코딩 후에는 디코더 내부 버퍼가 목표 위치와 어긋나므로 재동기화 필요
+    needs_resync: bool,
+    /// 패킷/시간/seek 재시도 한도
+    limits: DecodeLimits,
+    /// 랜덤 접근 정확도 (Export 디코더는 Exact)
+    seek_mode: SeekMode,
+    /// 역방향 재생용 프레임 링 버퍼 (PTS 오름차순, 연속 구간)
+    reverse_ring: VecDeque<Frame>,
+    /// 링 버퍼 마지막 프레임의 표시 끝 (ms)
+    reverse_ring_end_ms: i64,
 }
 
 impl Decoder {
     /// Decoder 생성 (Multi-threading 최적화)
+    /// - hwaccel: true면 HW 장치(NVDEC/D3D11VA/QSV 등) 연결 시도, 실패 시 소프트웨어
+    /// - codec: 기본 디코더 대신 사용할 디코더 (VP8/VP9 알파 → libvpx)
+    /// - 반환: (디코더, HW 장치 연결 여부)
     fn try_create_decoder(
-        _codec_id: ffmpeg::codec::Id,
+        codec_id: ffmpeg::codec::Id,
         codec_params: ffmpeg::codec::Parameters,
+        hwaccel: bool,
+        codec: Option<ffmpeg::Codec>,
     ) -> Result<(ffmpeg::codec::decoder::Video, bool), String> {
         // Create decoder context
-        let mut context = ffmpeg::codec::context::Context::from_parameters(codec_params)
+        let mut context = ffmpeg::codec::context::Context::from_parameters(codec_params.clone())
             .map_err(|e| format!("Failed to create context: {}", e))?;
 
+        let mut is_hardware = false;
+        if hwaccel {
+            if let Some(codec) = ffmpeg::decoder::find(codec_id) {
+                // SAFETY: context/codec 모두 유효, avcodec_open2 이전에만 호출
+                if let Some(device_type) = unsafe { hwaccel::attach(context.as_mut_ptr(), codec.as_ptr()) } {
+                    log_info!("[HWACCEL] {:?} 장치 연결 ({:?})", device_type, codec_id);
+                    is_hardware = true;
+                }
+            }
+        }
+
         // OPTIMIZATION: Multi-threading
-        if let Ok(parallelism) = std::thread::available_parallelism() {
-            let thread_count = parallelism.get();
-            // Multi-threading 활성화
-            context.set_threading(ffmpeg::threading::Config {
-                kind: ffmpeg::threading::Type::Frame,
-                count: thread_count,
-            });
+        // HW 디코딩은 GPU가 처리하므로 프레임 스레딩 불필요 (지연만 증가)
+        if !is_hardware {
+            if let Ok(parallelism) = std::thread::available_parallelism() {
+                let thread_count = parallelism.get();
+                // Multi-threading 활성화
+                context.set_threading(ffmpeg::threading::Config {
+                    kind: ffmpeg::threading::Type::Frame,
+                    count: thread_count,
+                });
+            }
         }
 
         // Open decoder
-        let decoder = context
-            .decoder()
-            .video()
-            .map_err(|e| format!("Failed to get video decoder: {}", e))?;
-
-        // Hardware acceleration is set at input level (input_with_dictionary)
-        // We'll detect it based on decoder format later
-        Ok((decoder, false))  // is_hardware will be updated based on actual usage
+        let opened = match codec {
+            Some(codec) => context.decoder().open_as(codec).and_then(|opened| opened.video()),
+            None => context.decoder().video(),
+        };
+        match opened {
+            Ok(decoder) => Ok((decoder, is_hardware)),
+            Err(e) if is_hardware => {
+                // HW 장치로 열기 실패 → 소프트웨어 폴백
+                log_warn!("[HWACCEL] HW 디코더 열기 실패 ({}) → 소프트웨어", e);
+                Self::try_create_decoder(codec_id, codec_params, false, codec)
+            }
+            Err(e) => Err(format!("Failed to get video decoder: {}", e)),
+        }
     }
 
     /// 비디오 파일 열기 (프리뷰용 960x540 고정 해상도)
     pub fn open(file_path: &Path) -> Result<Self, String> {
-        Self::open_internal(file_path, 960, 540, false, false)
+        Self::open_internal(file_path, 960, 540, ThumbnailFit::Stretch, false, false, false, None)
+    }
+
+    /// 비디오 파일 열기 (프리뷰용 960x540, 하드웨어 가속 디코딩)
+    /// NVDEC/D3D11VA/QSV 순으로 시도하고 모두 실패하면 소프트웨어 디코딩
+    pub fn open_with_hwaccel(file_path: &Path) -> Result<Self, String> {
+        Self::open_internal(file_path, 960, 540, ThumbnailFit::Stretch, false, false, true, None)
     }
 
     /// 비디오 파일 열기 (커스텀 출력 해상도 지정)
     /// 썸네일 세션에서는 직접 썸네일 크기로 디코딩하여 불필요한 다운스케일 방지
     pub fn open_with_resolution(file_path: &Path, target_width: u32, target_height: u32) -> Result<Self, String> {
-        Self::open_internal(file_path, target_width, target_height, false, false)
+        Self::open_internal(file_path, target_width, target_height, ThumbnailFit::Stretch, false, false, false, None)
+    }
+
+    /// 썸네일용 디코더 (원본 비율 유지)
+    /// 스케일러는 fit 모드에 맞춘 크기로 출력하고, 박스 패딩/크롭은 fit_thumbnail에서 처리
+    pub fn open_for_thumbnail(file_path: &Path, box_width: u32, box_height: u32, fit: ThumbnailFit) -> Result<Self, String> {
+        Self::open_internal(file_path, box_width, box_height, fit, false, false, false, None)
     }
 
     /// Export용 고품질 디코더 (YUV420P 직접 출력 + LANCZOS 리사이즈)
-    /// RGBA 변환을 건너뛰어 색공간 변환 손실 제거
+    /// RGBA 변환을 건너뛰어 색공간 변환 손실 제거 (HDR 소스는 톤 매핑된 RGBA 출력)
     pub fn open_for_export(file_path: &Path, target_width: u32, target_height: u32) -> Result<Self, String> {
-        Self::open_internal(file_path, target_width, target_height, true, true)
+        Self::open_internal(file_path, target_width, target_height, ThumbnailFit::Stretch, true, true, false, None)
+    }
+
+    /// 지정한 비디오 스트림으로 열기 (멀티 앵글, 화면+카메라 녹화 등)
+    /// - stream_index: 컨테이너 스트림 번호 (None이면 기본 비디오 스트림, media_probe로 목록 조회)
+    /// - export_resolution: Some이면 open_for_export와 같은 Export용, None이면 preview_size RGBA 프리뷰
+    /// - hwaccel: 프리뷰 하드웨어 디코딩 시도
+    pub fn open_stream(
+        file_path: &Path,
+        stream_index: Option<usize>,
+        export_resolution: Option<(u32, u32)>,
+        preview_size: (u32, u32),
+        hwaccel: bool,
+    ) -> Result<Self, String> {
+        match export_resolution {
+            Some((w, h)) => Self::open_internal(file_path, w, h, ThumbnailFit::Stretch, true, true, false, stream_index),
+            None => {
+                let (w, h) = preview_size;
+                Self::open_internal(file_path, w, h, ThumbnailFit::Stretch, false, false, hwaccel, stream_index)
+            }
+        }
+    }
+
+    /// 소스 프레임 인덱스 (디코딩 없이 패킷 PTS만 읽음)
+    /// PTS는 ms 올림 → 인덱스의 프레임 시작 시간으로 decode_frame을 요청하면 해당 프레임이 선택됨
+    pub fn scan_frame_index(file_path: &Path, stream_index: Option<usize>) -> Result<FrameIndex, String> {
+        ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+        let mut input_ctx = network::open_input(file_path)
+            .map_err(|e| format!("Failed to open file: {}", e))?;
+        let video_stream = select_video_stream(&input_ctx, stream_index)?;
+        let stream_index = video_stream.index();
+        let tb = video_stream.time_base();
+
+        // decode_frame과 같은 기준 (PTS 그대로, 시작 오프셋 보정 없음)
+        let ceil_ms = |pts: i64| {
+            let num = pts * i64::from(tb.numerator()) * 1000;
+            let den = i64::from(tb.denominator()).max(1);
+            num.div_euclid(den) + i64::from(num.rem_euclid(den) != 0)
+        };
+
+        let mut starts_ms = Vec::new();
+        let mut end_ms = 0;
+        for (stream, packet) in input_ctx.packets() {
+            if stream.index() != stream_index {
+                continue;
+            }
+            if let Some(pts) = packet.pts().or(packet.dts()) {
+                starts_ms.push(ceil_ms(pts));
+                if packet.duration() > 0 {
+                    end_ms = end_ms.max(ceil_ms(pts + packet.duration()));
+                }
+            }
+        }
+        Ok(FrameIndex::new(starts_ms, end_ms))
     }
 
     /// 내부 디코더 생성
+    /// - fit: 출력 크기 결정 방식 (Stretch면 target 크기 그대로)
     /// - high_quality: LANCZOS(Export) vs FAST_BILINEAR(프리뷰)
     /// - yuv_output: YUV420P 직접 출력(Export) vs RGBA(프리뷰)
-    fn open_internal(file_path: &Path, target_width: u32, target_height: u32, high_quality: bool, yuv_output: bool) -> Result<Self, String> {
+    /// - hwaccel: 하드웨어 디코딩 시도 (실패 시 소프트웨어)
+    /// - stream_index: 디코딩할 비디오 스트림 (None이면 기본 스트림)
+    #[allow(clippy::too_many_arguments)]
+    fn open_internal(
+        file_path: &Path,
+        target_width: u32,
+        target_height: u32,
+        fit: ThumbnailFit,
+        high_quality: bool,
+        yuv_output: bool,
+        hwaccel: bool,
+        stream_index: Option<usize>,
+    ) -> Result<Self, String> {
         ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
 
-        let input_ctx = ffmpeg::format::input(&file_path)
+        let input_ctx = network::open_input(file_path)
             .map_err(|e| format!("Failed to open file: {}", e))?;
 
-        let video_stream = input_ctx
-            .streams()
-            .best(ffmpeg::media::Type::Video)
-            .ok_or("No video stream found")?;
+        let video_stream = select_video_stream(&input_ctx, stream_index)?;
 
         let video_stream_index = video_stream.index();
         let codec_params = video_stream.parameters();
         let codec_id = codec_params.id();
 
-        let (decoder, is_hardware) = Self::try_create_decoder(codec_id, codec_params)?;
+        // 알파 채널 소스: HW 디코더는 알파를 버리므로 소프트웨어 전용
+        let alpha_codec = vpx_alpha_decoder(&video_stream, codec_id);
+        let (decoder, is_hardware) =
+            Self::try_create_decoder(codec_id, codec_params, hwaccel && alpha_codec.is_none(), alpha_codec)?;
+        let has_alpha = alpha_codec.is_some() || pixel_format_has_flag(decoder.format(), AV_PIX_FMT_FLAG_ALPHA);
 
         let src_width = decoder.width();
         let src_height = decoder.height();
 
-        let decode_width = target_width;
-        let decode_height = target_height;
+        // 비정방 픽셀(SAR) 소스는 표시 폭으로 보정
+        let sar = decoder.aspect_ratio();
+        let display_width = if sar.numerator() > 0 && sar.denominator() > 0 {
+            ((src_width as i64 * sar.numerator() as i64) / sar.denominator() as i64).max(1) as u32
+        } else {
+            src_width
+        };
+        let display_height = src_height;
+
+        // 세로 촬영 폰 영상 등: 90/270도 회전이면 표시 크기 가로/세로 교환
+        let rotation_deg = probe::stream_rotation(&video_stream);
+        let quarter_turn = rotation_deg % 180 != 0;
+        let (display_width, display_height) = if quarter_turn {
+            (display_height, display_width)
+        } else {
+            (display_width, display_height)
+        };
+
+        // 출력(회전 후) 크기, 스케일러는 회전 전 방향으로 출력
+        let (decode_width, decode_height) =
+            fitted_size(display_width, display_height, target_width, target_height, fit);
+        let (scaled_width, scaled_height) = if quarter_turn {
+            (decode_height, decode_width)
+        } else {
+            (decode_width, decode_height)
+        };
 
         let fps = f64::from(video_stream.avg_frame_rate());
+        // VFR 추정: 타임스탬프 기준 프레임레이트(r_frame_rate)가 평균과 다르면 프레임 간격이 일정하지 않음
+        let base_rate = f64::from(video_stream.rate());
+        let variable_frame_rate = fps > 0.0 && base_rate > 0.0 && (base_rate - fps).abs() > fps * 0.01;
 
         let duration_ms = if video_stream.duration() > 0 {
             let time_base = video_stream.time_base();
@@ -157,31 +382,64 @@ impl Decoder {
             0
         };
 
+        // HDR(PQ/HLG): 16bit RGBA로 변환 후 SDR 톤 매핑 (프리뷰/Export 모두 RGBA 출력)
+        let transfer = match decoder.color_transfer_characteristic() {
+            ffmpeg::color::TransferCharacteristic::SMPTE2084 => TransferFunction::Pq,
+            ffmpeg::color::TransferCharacteristic::ARIB_STD_B67 => TransferFunction::Hlg,
+            _ => TransferFunction::Sdr,
+        };
+        let tone_mapper = transfer.is_hdr().then(|| ToneMapper::new(transfer));
+
+        // 소스 행렬/범위를 스케일러에 지정 (swscale 기본값은 항상 BT.601 → HD 소스 색 틀어짐)
+        // YUV 출력은 소스 행렬 유지 + limited range, RGB 소스(PNG 등)는 BT.709로 변환
+        let source_color = source_yuv_color(&decoder);
+        let source_color = if tone_mapper.is_some() {
+            YuvColor { matrix: YuvMatrix::Bt2020, ..source_color }
+        } else {
+            source_color
+        };
+        let output_matrix = if pixel_format_has_flag(decoder.format(), AV_PIX_FMT_FLAG_RGB) {
+            YuvMatrix::Bt709
+        } else {
+            source_color.matrix
+        };
+        let scaler_colorspace = (source_color, output_matrix);
+        let yuv_color = YuvColor { matrix: output_matrix, full_range: false };
+
         // Export: LANCZOS (최고 품질), 프리뷰: FAST_BILINEAR (속도 우선)
-        let scaler_flags = if high_quality {
+        let mut scaler_flags = if high_quality {
             ffmpeg::software::scaling::Flags::LANCZOS
         } else {
             ffmpeg::software::scaling::Flags::FAST_BILINEAR
         };
+        // 10bit 이상 소스: 정확한 반올림 + 디더링으로 8bit 변환 시 밴딩 방지
+        if source_bit_depth(decoder.format()) > 8 {
+            scaler_flags |= ffmpeg::software::scaling::Flags::ACCURATE_RND;
+        }
 
         // YUV 직접 출력: 색공간 변환 없이 YUV420P로 리사이즈만
-        // RGBA 출력: 프리뷰/썸네일용 색공간 변환
-        let output_pixel_format = if yuv_output {
+        // RGBA 출력: 프리뷰/썸네일용 색공간 변환, 알파 소스는 Export에서도 RGBA (투명도 유지 → 합성)
+        let yuv_output = yuv_output && !has_alpha;
+        let output_pixel_format = if tone_mapper.is_some() {
+            ffmpeg::format::Pixel::RGBA64LE
+        } else if yuv_output {
             ffmpeg::format::Pixel::YUV420P
         } else {
             ffmpeg::format::Pixel::RGBA
         };
 
-        let scaler = ffmpeg::software::scaling::Context::get(
+        let mut scaler = ffmpeg::software::scaling::Context::get(
             decoder.format(),
             src_width,
             src_height,
             output_pixel_format,
-            decode_width,
-            decode_height,
+            scaled_width,
+            scaled_height,
             scaler_flags,
         )
         .map_err(|e| format!("Failed to create scaler: {}", e))?;
+        // SAFETY: 방금 생성한 유효한 스케일러
+        unsafe { set_scaler_colorspace(&mut scaler, scaler_colorspace) };
 
         let _frame_duration_ms = (1000.0 / fps).max(1.0) as i64;
 
@@ -190,17 +448,34 @@ impl Decoder {
             video_stream_index,
             decoder,
             scaler,
+            scaler_flags,
+            scaler_colorspace,
+            yuv_color,
+            tone_mapper,
+            has_alpha,
             width: decode_width,
             height: decode_height,
+            display_width,
+            display_height,
+            rotation_deg,
             fps,
+            variable_frame_rate,
             duration_ms,
             last_timestamp_ms: -1,
             is_hardware,
             state: DecoderState::Ready,
             last_decoded_frame: None,
+            last_frame_span: None,
+            pending_frame: None,
+            draining: false,
             forward_threshold_ms: 100,
             eof_timestamp_ms: None,
             yuv_output,
+            needs_resync: false,
+            limits: DecodeLimits::default(),
+            seek_mode: if high_quality { SeekMode::Exact } else { SeekMode::Fast },
+            reverse_ring: VecDeque::new(),
+            reverse_ring_end_ms: 0,
         })
     }
 
@@ -210,6 +485,24 @@ impl Decoder {
         self.forward_threshold_ms = threshold_ms;
     }
 
+    /// 디코딩 탐색 한도 설정
+    pub fn set_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits;
+    }
+
+    /// 랜덤 접근 정확도 설정 (Export용 디코더는 기본 Exact, 나머지는 Fast)
+    pub fn set_seek_mode(&mut self, mode: SeekMode) {
+        self.seek_mode = mode;
+    }
+
+    pub fn seek_mode(&self) -> SeekMode {
+        self.seek_mode
+    }
+
+    pub fn limits(&self) -> DecodeLimits {
+        self.limits
+    }
+
     /// 비디오 정보 가져오기
     pub fn width(&self) -> u32 {
         self.width
@@ -219,10 +512,25 @@ impl Decoder {
         self.height
     }
 
+    /// 표시 기준 원본 크기 (SAR/회전 반영)
+    pub fn display_size(&self) -> (u32, u32) {
+        (self.display_width, self.display_height)
+    }
+
+    /// 소스 표시 회전 (시계 방향 0/90/180/270, 디코딩 프레임에는 이미 적용됨)
+    pub fn rotation_deg(&self) -> i32 {
+        self.rotation_deg
+    }
+
     pub fn fps(&self) -> f64 {
         self.fps
     }
 
+    /// 가변 프레임레이트 소스 여부
+    pub fn is_vfr(&self) -> bool {
+        self.variable_frame_rate
+    }
+
     pub fn duration_ms(&self) -> i64 {
         self.duration_ms
     }
@@ -231,10 +539,38 @@ impl Decoder {
         self.state
     }
 
+    /// HDR(PQ/HLG) 소스 여부 (출력 프레임은 SDR로 톤 매핑된 RGBA)
+    pub fn is_hdr(&self) -> bool {
+        self.tone_mapper.is_some()
+    }
+
+    /// 알파 채널 소스 여부 (출력은 항상 RGBA)
+    pub fn has_alpha(&self) -> bool {
+        self.has_alpha
+    }
+
+    /// 하드웨어 디코딩 중 여부 (HW 포맷 협상 실패로 소프트웨어 폴백 시 false)
+    pub fn is_hardware(&self) -> bool {
+        self.is_hardware
+    }
+
+    /// seek 없이 forward decode로 도달 가능한 위치인지
+    /// (현재 위치 이후, forward_threshold 또는 2프레임 이내, 재동기화 불필요)
+    pub fn can_continue_to(&self, timestamp_ms: i64) -> bool {
+        let frame_duration_ms = (1000.0 / self.fps).max(1.0) as i64;
+        let gap_ms = timestamp_ms - self.last_timestamp_ms;
+        self.state == DecoderState::Ready
+            && !self.needs_resync
+            && gap_ms >= 0
+            && gap_ms <= self.forward_threshold_ms.max(frame_duration_ms * 2)
+    }
+
     /// 특정 시간의 프레임 디코딩 (상태 머신 기반)
-    /// - 즉시 순차 (1프레임 이내): seek 없이, PTS 확인 없이 다음 프레임 반환
-    /// - Forward decode (threshold 이내): seek 없이, PTS 확인하며 전진
-    /// - 랜덤 접근 (threshold 초과 또는 역방향): seek + PTS 확인
+    /// - 선택 기준: PTS만 사용 — 표시 구간 [pts, 다음 pts)가 timestamp를 포함하는 프레임 (VFR 안전)
+    /// - 같은 구간 재요청: 디코딩 없이 마지막 프레임 재사용
+    /// - Forward decode (threshold 이내): seek 없이 전진
+    /// - 랜덤 접근 (threshold 초과 또는 역방향): seek 후 전진
+    /// - 정확 모드(SeekMode::Exact): ms 내림된 프레임 시간도 해당 프레임으로, seek가 지나치면 재-seek
     /// - EOF/에러: DecodeResult로 구분하여 안전 처리
     pub fn decode_frame(&mut self, timestamp_ms: i64) -> Result<DecodeResult, String> {
         // Error 상태에서는 마지막 프레임 반환
@@ -261,21 +597,32 @@ impl Decoder {
 
         let frame_duration_ms = (1000.0 / self.fps).max(1.0) as i64;
 
-        // 3단계 판정: 즉시순차 / forward decode / 랜덤접근
-        let is_ahead = self.state == DecoderState::Ready
-            && timestamp_ms >= self.last_timestamp_ms;
-        let gap_ms = timestamp_ms - self.last_timestamp_ms;
+        let stream = self.input_ctx.stream(self.video_stream_index)
+            .ok_or("Video stream not found")?;
+        let tb = stream.time_base();
+        let target_pts = match self.seek_mode {
+            SeekMode::Fast => ms_to_pts(timestamp_ms, tb),
+            SeekMode::Exact => exact_target_pts(timestamp_ms, tb),
+        };
+
+        // 마지막 반환 프레임의 표시 구간 안이면 디코딩 없이 재사용
+        // (소스 fps < 타임라인 fps, VFR 소스의 긴 프레임 — 다음 프레임으로 넘어가면 재생이 빨라짐)
+        if self.state == DecoderState::Ready && !self.needs_resync {
+            if let (Some((start, end)), Some(last)) = (self.last_frame_span, &self.last_decoded_frame) {
+                if start <= target_pts && target_pts < end {
+                    self.last_timestamp_ms = timestamp_ms;
+                    return Ok(DecodeResult::Frame(Frame { timestamp_ms, ..last.clone() }));
+                }
+            }
+        }
 
-        // 즉시 순차: 다음 프레임 (1프레임 이내 차이)
-        let is_immediate = is_ahead && gap_ms <= frame_duration_ms * 2;
-        // Forward decode: threshold 이내 전진 (seek 불필요, PTS 확인 필요)
-        let is_forward = is_ahead && !is_immediate && gap_ms <= self.forward_threshold_ms;
-        // 그 외: 랜덤 접근 (seek 필요)
-        let needs_seek = !is_immediate && !is_forward;
+        // 2단계 판정: forward decode (seek 없이 전진) / 랜덤 접근 (seek)
+        // 둘 다 PTS 기준으로 목표 시간을 덮는 프레임 선택 (fps 기반 추정 없음 → VFR에서도 정확)
+        let needs_seek = !self.can_continue_to(timestamp_ms);
 
         if needs_seek {
             if let Err(e) = self.seek(timestamp_ms) {
-                eprintln!("Seek failed at {}ms: {}", timestamp_ms, e);
+                log_warn!("Seek failed at {}ms: {}", timestamp_ms, e);
                 return match &self.last_decoded_frame {
                     Some(_) => Ok(DecodeResult::FrameSkipped),
                     None => Ok(DecodeResult::EndOfStreamEmpty),
@@ -285,40 +632,83 @@ impl Decoder {
 
         self.last_timestamp_ms = timestamp_ms;
 
-        // PTS 확인 여부 결정:
-        // - 즉시 순차: PTS 확인 불필요 (다음 프레임 즉시 반환)
-        // - Forward decode: PTS 확인 필요 (목표 시간까지 전진)
-        // - 랜덤 접근: PTS 확인 필요 (키프레임에서 목표까지 전진)
-        let target_info = if is_immediate {
-            None
-        } else {
-            let stream = self.input_ctx.stream(self.video_stream_index)
-                .ok_or("Video stream not found")?;
-            let tb = stream.time_base();
-            let target_pts = (timestamp_ms * i64::from(tb.denominator()))
-                / (i64::from(tb.numerator()) * 1000);
-            let tolerance_pts = (frame_duration_ms * i64::from(tb.denominator()))
-                / (i64::from(tb.numerator()) * 1000);
-            Some((target_pts, tolerance_pts))
+        // 스트림 끝: 마지막 프레임의 표시 구간(길이 모르면 평균 프레임 길이)을 지났는지 판정
+        let nominal_duration = ms_to_pts(frame_duration_ms, tb).max(1);
+        let (mut selection, mut hit_eof) = self.select_frame(target_pts, nominal_duration);
+
+        // 정확 모드: seek 후 첫 프레임이 이미 목표 이후 (키프레임 인덱스 부정확, open GOP 등)
+        // → 목표보다 앞에서 다시 seek (간격을 늘려가며)
+        let mut backoff_ms = EXACT_SEEK_BACKOFF_MS;
+        let mut seek_ms = timestamp_ms;
+        while self.seek_mode == SeekMode::Exact && selection.overshoot && seek_ms > 0
+            && backoff_ms <= EXACT_SEEK_MAX_BACKOFF_MS
+        {
+            seek_ms = (timestamp_ms - backoff_ms).max(0);
+            backoff_ms *= 2;
+            if let Err(e) = self.seek(seek_ms) {
+                log_warn!("Exact re-seek failed at {}ms: {}", seek_ms, e);
+                break;
+            }
+            (selection, hit_eof) = self.select_frame(target_pts, nominal_duration);
+        }
+        self.pending_frame = selection.lookahead;
+
+        let (raw_frame, span) = match selection.picked {
+            Some(p) => p,
+            // EOF 처리 (이번 탐색에서 프레임을 하나도 얻지 못함)
+            None if hit_eof => {
+                self.state = DecoderState::EndOfStream;
+                // EOF 위치 기록 → 이후 같은/더 먼 timestamp에서 seek+전패킷읽기 반복 방지
+                self.eof_timestamp_ms = Some(timestamp_ms);
+                return match &self.last_decoded_frame {
+                    Some(f) => Ok(DecodeResult::EndOfStream(f.clone())),
+                    None => Ok(DecodeResult::EndOfStreamEmpty),
+                };
+            }
+            // 프레임 디코딩 실패 (EOF가 아닌 경우) → FrameSkipped
+            None => return Ok(DecodeResult::FrameSkipped),
         };
 
-        let mut decoded_frame: Option<ffmpeg::frame::Video> = None;
+        // 출력 프레임으로 변환 (RGBA 또는 YUV420P), 표시 구간 → 프레임 길이
+        let mut frame = self.convert_frame(&raw_frame, timestamp_ms)?;
+        frame.duration_ms = span.map_or(0, |(start, end)| pts_to_ms(end, tb) - pts_to_ms(start, tb));
 
-        // Step 1: 디코더 버퍼에서 프레임 확인
-        loop {
+        // 마지막 성공 프레임 저장 (EOF/에러 시 fallback, 같은 구간 재요청 시 재사용)
+        self.last_decoded_frame = Some(frame.clone());
+        self.last_frame_span = span;
+        self.state = DecoderState::Ready;
+
+        // 마지막 프레임의 표시 구간도 지난 위치 → 마지막 프레임과 함께 EOF
+        if selection.past_end {
+            self.state = DecoderState::EndOfStream;
+            self.eof_timestamp_ms = Some(timestamp_ms);
+            return Ok(DecodeResult::EndOfStream(frame));
+        }
+
+        Ok(DecodeResult::Frame(frame))
+    }
+
+    /// 현재 디코더 위치부터 목표 PTS를 덮는 프레임 선택
+    /// 반환: (선택 결과, 패킷 소진 = EOF 여부)
+    fn select_frame(&mut self, target_pts: i64, nominal_duration: i64) -> (PtsSelection<ffmpeg::frame::Video>, bool) {
+        let mut selector = PtsSelector::new(target_pts);
+
+        // Step 1: 이전 요청에서 넘친 프레임 → 디코더 버퍼
+        if let Some(frame) = self.pending_frame.take() {
+            selector.offer_frame(frame);
+        }
+        while !selector.is_done() {
             let mut frame = ffmpeg::frame::Video::empty();
             if self.decoder.receive_frame(&mut frame).is_err() {
                 break;
             }
-            if is_pts_at_target(target_info, &frame) {
-                decoded_frame = Some(frame);
-                break;
-            }
+            selector.offer_frame(frame);
         }
 
-        // Step 2: 패킷 읽으며 디코딩 (목표 PTS 도달까지)
+        // Step 2: 패킷 읽으며 디코딩 (목표 프레임 확정까지)
         let mut hit_eof = false;
-        if decoded_frame.is_none() {
+        if !selector.is_done() {
+            let started = std::time::Instant::now();
             let mut packet_count = 0;
             let mut packets_exhausted = true; // for 루프가 끝까지 소진되면 EOF
 
@@ -329,34 +719,28 @@ impl Decoder {
 
                 // send_packet (EAGAIN 시 drain 후 재시도)
                 if self.decoder.send_packet(&packet).is_err() {
-                    loop {
+                    while !selector.is_done() {
                         let mut frame = ffmpeg::frame::Video::empty();
                         if self.decoder.receive_frame(&mut frame).is_err() { break; }
-                        if is_pts_at_target(target_info, &frame) {
-                            decoded_frame = Some(frame);
-                            break;
-                        }
+                        selector.offer_frame(frame);
                     }
-                    if decoded_frame.is_some() { packets_exhausted = false; break; }
+                    if selector.is_done() { packets_exhausted = false; break; }
                     let _ = self.decoder.send_packet(&packet);
                 }
 
                 // 디코딩된 프레임 수신 (B-frame 재정렬 대응)
-                loop {
+                while !selector.is_done() {
                     let mut frame = ffmpeg::frame::Video::empty();
                     if self.decoder.receive_frame(&mut frame).is_err() { break; }
-                    if is_pts_at_target(target_info, &frame) {
-                        decoded_frame = Some(frame);
-                        break;
-                    }
+                    selector.offer_frame(frame);
                 }
 
-                if decoded_frame.is_some() { packets_exhausted = false; break; }
+                if selector.is_done() { packets_exhausted = false; break; }
 
                 packet_count += 1;
-                if packet_count > 3000 {
-                    // 안전장치: 3000패킷 소진 → FrameSkipped (에러가 아님)
-                    // (타임라인 썸네일 생성 등 랜덤 접근 시 긴 GOP에서도
+                if self.limits.exceeded(packet_count, started) {
+                    // 안전장치: 패킷/시간 한도 소진 → FrameSkipped (에러가 아님)
+                    // (기본 3000패킷: 타임라인 썸네일 생성 등 랜덤 접근 시 긴 GOP에서도
                     // 더 먼 위치까지 탐색할 수 있도록 상한을 상향 조정)
                     packets_exhausted = false;
                     break;
@@ -364,100 +748,319 @@ impl Decoder {
             }
 
             // for 루프가 자연종료 = 패킷 소진 = EOF
-            if packets_exhausted && decoded_frame.is_none() {
+            if packets_exhausted && !selector.is_done() {
                 hit_eof = true;
             }
         }
 
-        // EOF 처리
-        if hit_eof {
-            self.state = DecoderState::EndOfStream;
-            // EOF 위치 기록 → 이후 같은/더 먼 timestamp에서 seek+전패킷읽기 반복 방지
-            self.eof_timestamp_ms = Some(timestamp_ms);
+        (selector.finish(hit_eof.then_some(nominal_duration)), hit_eof)
+    }
+
+    /// 키프레임 전용 디코딩 (스크롤 중 빠른 filmstrip용)
+    /// - timestamp 이전의 가장 가까운 키프레임으로 seek 후 첫 프레임만 반환
+    /// - 목표 PTS까지 forward decode 하지 않음 → GOP 길이와 무관하게 즉시 반환
+    /// - 같은 GOP 안의 timestamp는 모두 같은 키프레임으로 떨어짐 (정확한 프레임은 decode_frame)
+    /// - 반환 프레임의 timestamp_ms는 요청 시간이 아닌 키프레임 PTS (≤ timestamp)
+    pub fn decode_keyframe(&mut self, timestamp_ms: i64) -> Result<DecodeResult, String> {
+        if self.state == DecoderState::Error {
             return match &self.last_decoded_frame {
                 Some(f) => Ok(DecodeResult::EndOfStream(f.clone())),
                 None => Ok(DecodeResult::EndOfStreamEmpty),
             };
         }
 
-        // 프레임 디코딩 실패 (EOF가 아닌 경우) → FrameSkipped
+        if let Err(e) = self.seek(timestamp_ms) {
+            log_warn!("Keyframe seek failed at {}ms: {}", timestamp_ms, e);
+            return match &self.last_decoded_frame {
+                Some(_) => Ok(DecodeResult::FrameSkipped),
+                None => Ok(DecodeResult::EndOfStreamEmpty),
+            };
+        }
+
+        // 비키프레임 패킷은 디코딩하지 않음 (프레임 스레딩 지연 동안에도 디코딩 비용 없음)
+        self.decoder.skip_frame(ffmpeg::codec::discard::Discard::NonKey);
+
+        let mut decoded_frame: Option<ffmpeg::frame::Video> = None;
+        let started = std::time::Instant::now();
+        let mut packet_count = 0;
+        let mut packets_exhausted = true;
+
+        for (stream, packet) in self.input_ctx.packets() {
+            if stream.index() != self.video_stream_index {
+                continue;
+            }
+
+            let _ = self.decoder.send_packet(&packet);
+
+            // seek 직후 첫 출력 프레임 = 키프레임 (PTS 확인 없음)
+            let mut frame = ffmpeg::frame::Video::empty();
+            if self.decoder.receive_frame(&mut frame).is_ok() {
+                decoded_frame = Some(frame);
+                packets_exhausted = false;
+                break;
+            }
+
+            packet_count += 1;
+            if self.limits.exceeded(packet_count, started) {
+                packets_exhausted = false;
+                break;
+            }
+        }
+
+        self.decoder.skip_frame(ffmpeg::codec::discard::Discard::Default);
+        // 디코더 버퍼에 비키프레임이 빠진 상태 → 다음 정확 디코딩은 반드시 seek
+        self.needs_resync = true;
+
         let raw_frame = match decoded_frame {
             Some(f) => f,
+            None if packets_exhausted => {
+                self.state = DecoderState::EndOfStream;
+                return match &self.last_decoded_frame {
+                    Some(f) => Ok(DecodeResult::EndOfStream(f.clone())),
+                    None => Ok(DecodeResult::EndOfStreamEmpty),
+                };
+            }
             None => return Ok(DecodeResult::FrameSkipped),
         };
 
-        // 출력 프레임으로 변환 (RGBA 또는 YUV420P)
-        let frame = self.convert_frame(&raw_frame, timestamp_ms)?;
+        let tb = self.input_ctx.stream(self.video_stream_index)
+            .ok_or("Video stream not found")?
+            .time_base();
+        let keyframe_ms = raw_frame.pts().or(raw_frame.timestamp())
+            .map_or(timestamp_ms, |pts| pts_to_ms(pts, tb));
+        let frame = self.convert_frame(&raw_frame, keyframe_ms)?;
 
-        // 마지막 성공 프레임 저장 (EOF/에러 시 fallback)
+        self.last_timestamp_ms = keyframe_ms;
         self.last_decoded_frame = Some(frame.clone());
         self.state = DecoderState::Ready;
 
         Ok(DecodeResult::Frame(frame))
     }
 
+    /// 역방향 재생 디코딩 (J-K-L 셔틀, 시간이 줄어드는 순서로 요청)
+    /// - 링 버퍼에 timestamp를 덮는 프레임이 있으면 디코딩 없이 반환
+    /// - 없으면 timestamp 이전 키프레임으로 seek → timestamp까지 순차 디코딩하며 마지막 N프레임만 보관
+    /// - 연속 역방향 요청은 N프레임마다 한 번만 GOP를 다시 디코딩 (프레임마다 seek+GOP 디코딩 대비)
+    pub fn decode_frame_reverse(&mut self, timestamp_ms: i64) -> Result<DecodeResult, String> {
+        if self.state == DecoderState::Error {
+            return match &self.last_decoded_frame {
+                Some(f) => Ok(DecodeResult::EndOfStream(f.clone())),
+                None => Ok(DecodeResult::EndOfStreamEmpty),
+            };
+        }
+
+        if let Some(frame) = self.reverse_ring_lookup(timestamp_ms) {
+            return Ok(DecodeResult::Frame(frame));
+        }
+
+        if let Err(e) = self.seek(timestamp_ms) {
+            log_warn!("Reverse seek failed at {}ms: {}", timestamp_ms, e);
+            return match &self.last_decoded_frame {
+                Some(_) => Ok(DecodeResult::FrameSkipped),
+                None => Ok(DecodeResult::EndOfStreamEmpty),
+            };
+        }
+
+        // 키프레임부터 목표를 지나는 프레임까지 전진 (목표 이후 첫 프레임 = 링 버퍼 끝 경계)
+        self.reverse_ring.clear();
+        let frame_duration_ms = (1000.0 / self.fps).max(1.0) as i64;
+        let started = std::time::Instant::now();
+        let mut decoded_count = 0;
+        let mut ring_end_ms = None;
+        while let Some(frame) = self.decode_next_frame()? {
+            if frame.timestamp_ms > timestamp_ms && !self.reverse_ring.is_empty() {
+                ring_end_ms = Some(frame.timestamp_ms);
+                break;
+            }
+            let past_target = frame.timestamp_ms > timestamp_ms;
+            if self.reverse_ring.len() == REVERSE_RING_FRAMES {
+                self.reverse_ring.pop_front();
+            }
+            self.reverse_ring.push_back(frame);
+            // 키프레임이 목표 이후 (소스 시작 전 시간) → 첫 프레임만 보관
+            if past_target {
+                break;
+            }
+
+            decoded_count += 1;
+            if self.limits.exceeded(decoded_count, started) {
+                self.reverse_ring.clear();
+                return Ok(DecodeResult::FrameSkipped);
+            }
+        }
+
+        let (last_ms, last_duration_ms) = match self.reverse_ring.back() {
+            Some(last) => (last.timestamp_ms, last.duration_ms),
+            None => {
+                self.state = DecoderState::EndOfStream;
+                return match &self.last_decoded_frame {
+                    Some(f) => Ok(DecodeResult::EndOfStream(f.clone())),
+                    None => Ok(DecodeResult::EndOfStreamEmpty),
+                };
+            }
+        };
+        // 스트림 끝까지 읽음 → 마지막 프레임 길이 (모르면 평균 프레임 길이)
+        self.reverse_ring_end_ms = ring_end_ms.unwrap_or(
+            last_ms + if last_duration_ms > 0 { last_duration_ms } else { frame_duration_ms },
+        );
+
+        match self.reverse_ring_lookup(timestamp_ms) {
+            Some(frame) => Ok(DecodeResult::Frame(frame)),
+            // 마지막 프레임 표시 구간도 지난 위치 (소스 길이 밖)
+            None if timestamp_ms >= self.reverse_ring_end_ms => {
+                let last = self.reverse_ring.back().cloned().ok_or("Reverse ring is empty")?;
+                Ok(DecodeResult::EndOfStream(Frame { timestamp_ms, ..last }))
+            }
+            // 목표가 첫 프레임 이전 → 첫 프레임
+            None => {
+                let first = self.reverse_ring.front().cloned().ok_or("Reverse ring is empty")?;
+                Ok(DecodeResult::Frame(Frame { timestamp_ms, ..first }))
+            }
+        }
+    }
+
+    /// 링 버퍼에서 timestamp를 표시 구간에 포함하는 프레임 (요청 시간으로 반환)
+    fn reverse_ring_lookup(&mut self, timestamp_ms: i64) -> Option<Frame> {
+        let first_ms = self.reverse_ring.front()?.timestamp_ms;
+        if timestamp_ms < first_ms || timestamp_ms >= self.reverse_ring_end_ms {
+            return None;
+        }
+        let frame = self.reverse_ring.iter().rev().find(|f| f.timestamp_ms <= timestamp_ms)?;
+        let frame = Frame { timestamp_ms, ..frame.clone() };
+        self.last_decoded_frame = Some(frame.clone());
+        Some(frame)
+    }
+
     /// 디코딩된 ffmpeg Video 프레임을 출력 형식으로 변환
     /// - yuv_output=false: RGBA (프리뷰/썸네일용)
     /// - yuv_output=true: YUV420P 직접 출력 (Export용 — 색공간 변환 손실 제거)
     /// bounds check 추가: FFmpeg이 손상된 프레임을 반환해도 panic 대신 Err 반환
     fn convert_frame(&mut self, raw_frame: &ffmpeg::frame::Video, timestamp_ms: i64) -> Result<Frame, String> {
+        // HW 프레임(GPU 메모리) → 시스템 메모리 (보통 NV12/P010)
+        let transferred = hwaccel::transfer(raw_frame)?;
+        self.is_hardware = transferred.is_some();
+        let src_frame = transferred.as_ref().unwrap_or(raw_frame);
+
+        // 입력 포맷/크기가 스케일러와 다르면 재생성 (HW 전송 포맷, 해상도 변경 스트림)
+        let input = *self.scaler.input();
+        if input.format != src_frame.format()
+            || input.width != src_frame.width()
+            || input.height != src_frame.height()
+        {
+            let output = *self.scaler.output();
+            self.scaler.cached(
+                src_frame.format(),
+                src_frame.width(),
+                src_frame.height(),
+                output.format,
+                output.width,
+                output.height,
+                self.scaler_flags,
+            );
+            // SAFETY: cached()가 반환한 유효한 스케일러
+            unsafe { set_scaler_colorspace(&mut self.scaler, self.scaler_colorspace) };
+        }
+
         let mut scaled_frame = ffmpeg::frame::Video::empty();
-        self.scaler.run(raw_frame, &mut scaled_frame)
+        self.scaler.run(src_frame, &mut scaled_frame)
             .map_err(|e| format!("Failed to scale frame: {}", e))?;
 
-        if self.yuv_output {
-            self.extract_yuv_frame(&scaled_frame, timestamp_ms)
+        let frame = if let Some(mapper) = &self.tone_mapper {
+            Self::extract_tone_mapped_frame(&scaled_frame, mapper, timestamp_ms)?
+        } else if self.yuv_output {
+            Self::extract_yuv_frame(&scaled_frame, self.yuv_color, timestamp_ms)?
         } else {
-            self.extract_rgba_frame(&scaled_frame, timestamp_ms)
-        }
+            Self::extract_rgba_frame(&scaled_frame, timestamp_ms)?
+        };
+        Ok(rotate_frame(frame, self.rotation_deg))
     }
 
-    /// RGBA 프레임 추출 (프리뷰/썸네일용)
-    fn extract_rgba_frame(&self, frame: &ffmpeg::frame::Video, timestamp_ms: i64) -> Result<Frame, String> {
-        let size = (self.width * self.height * 4) as usize;
+    /// RGBA 프레임 추출 (프리뷰/썸네일용, 스케일러 출력 크기 = 회전 전)
+    fn extract_rgba_frame(frame: &ffmpeg::frame::Video, timestamp_ms: i64) -> Result<Frame, String> {
+        let width = frame.width();
+        let height = frame.height();
+        let size = (width * height * 4) as usize;
         let mut data = vec![0u8; size];
 
         let src_data = frame.data(0);
         let linesize = frame.stride(0);
 
         // 안전성 검증
-        let required_src_size = (self.height as usize - 1) * linesize + (self.width as usize * 4);
+        let required_src_size = (height as usize - 1) * linesize + (width as usize * 4);
         if src_data.len() < required_src_size {
             return Err(format!(
                 "Frame data too small: got {} bytes, need {} ({}x{}, stride={})",
-                src_data.len(), required_src_size, self.width, self.height, linesize
+                src_data.len(), required_src_size, width, height, linesize
             ));
         }
 
-        if linesize < self.width as usize * 4 {
+        if linesize < width as usize * 4 {
             return Err(format!(
                 "Invalid stride: {} < {} (width * 4)",
-                linesize, self.width as usize * 4
+                linesize, width as usize * 4
             ));
         }
 
-        for y in 0..self.height as usize {
+        for y in 0..height as usize {
             let src_offset = y * linesize;
-            let dst_offset = y * (self.width as usize * 4);
-            let row_size = self.width as usize * 4;
+            let dst_offset = y * (width as usize * 4);
+            let row_size = width as usize * 4;
             data[dst_offset..dst_offset + row_size]
                 .copy_from_slice(&src_data[src_offset..src_offset + row_size]);
         }
 
         Ok(Frame {
-            width: self.width,
-            height: self.height,
+            width,
+            height,
             format: PixelFormat::RGBA,
             data,
             timestamp_ms,
+            color: YuvColor::default(),
+            duration_ms: 0,
         })
     }
 
-    /// YUV420P 프레임 추출 (Export용 — 색공간 변환 없이 직접 전달)
+    /// HDR RGBA64 프레임 → SDR 톤 매핑 RGBA 프레임
+    fn extract_tone_mapped_frame(
+        frame: &ffmpeg::frame::Video,
+        mapper: &ToneMapper,
+        timestamp_ms: i64,
+    ) -> Result<Frame, String> {
+        let width = frame.width() as usize;
+        let height = frame.height() as usize;
+        let src_data = frame.data(0);
+        let linesize = frame.stride(0);
+
+        // 안전성 검증 (RGBA64 = 픽셀당 8바이트)
+        if height == 0 || linesize < width * 8 || src_data.len() < (height - 1) * linesize + width * 8 {
+            return Err(format!(
+                "Invalid HDR frame: {}x{}, stride={}, {} bytes",
+                width, height, linesize, src_data.len()
+            ));
+        }
+
+        let mut data = vec![0u8; width * height * 4];
+        for (y, dst_row) in data.chunks_exact_mut(width * 4).enumerate() {
+            let src_offset = y * linesize;
+            mapper.map_row(&src_data[src_offset..src_offset + width * 8], dst_row);
+        }
+
+        Ok(Frame {
+            width: width as u32,
+            height: height as u32,
+            format: PixelFormat::RGBA,
+            data,
+            timestamp_ms,
+            color: YuvColor::default(),
+            duration_ms: 0,
+        })
+    }
+
+    /// YUV420P 프레임 추출 (Export용 — 색공간 변환 없이 직접 전달, color: 스케일러 출력 행렬/범위)
     /// 데이터 레이아웃: [Y plane: w*h][U plane: w/2*h/2][V plane: w/2*h/2]
-    fn extract_yuv_frame(&self, frame: &ffmpeg::frame::Video, timestamp_ms: i64) -> Result<Frame, String> {
-        let w = self.width as usize;
-        let h = self.height as usize;
+    fn extract_yuv_frame(frame: &ffmpeg::frame::Video, color: YuvColor, timestamp_ms: i64) -> Result<Frame, String> {
+        let w = frame.width() as usize;
+        let h = frame.height() as usize;
         let y_size = w * h;
         let half_w = w / 2;
         let half_h = h / 2;
@@ -502,18 +1105,98 @@ impl Decoder {
         }
 
         Ok(Frame {
-            width: self.width,
-            height: self.height,
+            width: w as u32,
+            height: h as u32,
             format: PixelFormat::YUV420P,
             data,
             timestamp_ms,
+            color,
+            duration_ms: 0,
         })
     }
 
-    /// 다음 프레임 디코딩
+    /// 다음 프레임 순차 디코딩 (목표 시간 없음 — seek/PTS 탐색 없이 표시 순서대로)
+    /// - timestamp_ms: 프레임 PTS (ms), duration_ms: 프레임 길이 (모르면 0)
+    /// - 스트림 끝이면 디코더 내부 지연 프레임까지 모두 꺼낸 뒤 None
+    /// - decode_frame과 섞어 써도 됨 (decode_frame이 미리 꺼낸 다음 프레임부터 이어짐)
     pub fn decode_next_frame(&mut self) -> Result<Option<Frame>, String> {
-        // TODO: 구현
-        Ok(None)
+        if self.state == DecoderState::Error {
+            return Err("Decoder is in error state".to_string());
+        }
+        // 키프레임 전용 디코딩 직후: 디코더 버퍼가 어긋나 있으므로 마지막 위치로 재동기화
+        if self.needs_resync {
+            self.seek(self.last_timestamp_ms.max(0))?;
+        }
+        if self.state == DecoderState::EndOfStream {
+            return Ok(None);
+        }
+
+        let raw_frame = match self.pending_frame.take() {
+            Some(frame) => frame,
+            None => match self.receive_next_frame() {
+                Some(frame) => frame,
+                None => {
+                    self.state = DecoderState::EndOfStream;
+                    return Ok(None);
+                }
+            },
+        };
+
+        let tb = self.input_ctx.stream(self.video_stream_index)
+            .ok_or("Video stream not found")?
+            .time_base();
+        let pts = raw_frame.pts().or(raw_frame.timestamp());
+        // SAFETY: 유효한 AVFrame (duration은 디코더가 패킷 길이로 채움, 모르면 0)
+        let duration = unsafe { (*raw_frame.as_ptr()).duration }.max(0);
+        let timestamp_ms = pts.map_or(self.last_timestamp_ms.max(0), |pts| pts_to_ms(pts, tb));
+
+        let mut frame = self.convert_frame(&raw_frame, timestamp_ms)?;
+        frame.duration_ms = pts_to_ms(duration, tb);
+
+        self.last_timestamp_ms = timestamp_ms;
+        self.last_frame_span = pts.filter(|_| duration > 0).map(|pts| (pts, pts + duration));
+        self.last_decoded_frame = Some(frame.clone());
+        Ok(Some(frame))
+    }
+
+    /// 순차 디코딩 반복자 (decode_next_frame 래퍼)
+    /// 스트림 끝에서 None, 에러는 Some(Err) 한 번 반환 후 종료
+    pub fn frames(&mut self) -> Frames<'_> {
+        Frames { decoder: self, failed: false }
+    }
+
+    /// 디코더에서 다음 원시 프레임 (패킷 소진 시 EOF 신호로 지연 프레임 배출, 모두 꺼내면 None)
+    fn receive_next_frame(&mut self) -> Option<ffmpeg::frame::Video> {
+        loop {
+            let mut frame = ffmpeg::frame::Video::empty();
+            if self.decoder.receive_frame(&mut frame).is_ok() {
+                return Some(frame);
+            }
+            if self.draining {
+                return None;
+            }
+
+            let video_stream_index = self.video_stream_index;
+            let packet = self.input_ctx.packets()
+                .find(|(stream, _)| stream.index() == video_stream_index)
+                .map(|(_, packet)| packet);
+
+            match packet {
+                Some(packet) => {
+                    // EAGAIN: 출력 프레임을 먼저 꺼낸 뒤 재전송 (손상 패킷은 건너뜀)
+                    if self.decoder.send_packet(&packet).is_err()
+                        && self.decoder.receive_frame(&mut frame).is_ok()
+                    {
+                        let _ = self.decoder.send_packet(&packet);
+                        return Some(frame);
+                    }
+                }
+                None => {
+                    self.draining = true;
+                    let _ = self.decoder.send_eof();
+                }
+            }
+        }
     }
 
     /// 썸네일 프레임 생성 (작은 해상도로 디코딩)
@@ -530,6 +1213,18 @@ impl Decoder {
         timestamp_ms: i64,
         thumb_width: u32,
         thumb_height: u32,
+    ) -> Result<Frame, String> {
+        self.generate_thumbnail_fit(timestamp_ms, thumb_width, thumb_height, ThumbnailFit::Stretch)
+    }
+
+    /// 썸네일 프레임 생성 (종횡비 맞춤 지정)
+    /// Letterbox/Crop: 원본 비율로 축소한 뒤 thumb 박스 중앙에 패딩/크롭
+    pub fn generate_thumbnail_fit(
+        &mut self,
+        timestamp_ms: i64,
+        thumb_width: u32,
+        thumb_height: u32,
+        fit: ThumbnailFit,
     ) -> Result<Frame, String> {
         // 1) decode_frame으로 해당 timestamp의 RGBA 프레임 얻기
         let base_frame = match self.decode_frame(timestamp_ms)? {
@@ -546,93 +1241,401 @@ impl Decoder {
             }
         };
 
-        // 2) 크기가 이미 원하는 썸네일 크기라면 그대로 반환
-        //    (open_with_resolution으로 열었으면 스케일러가 이미 thumb 크기)
-        if base_frame.width == thumb_width && base_frame.height == thumb_height {
-            return Ok(base_frame);
-        }
+        // 2) 원본 비율 기준 스케일 크기 (Stretch면 thumb 크기)
+        let (scaled_width, scaled_height) = fitted_size(
+            self.display_width, self.display_height, thumb_width, thumb_height, fit,
+        );
 
-        // 3) 크기 불일치 시 Nearest-Neighbor 다운스케일 (fallback)
-        let src_w = base_frame.width as usize;
-        let src_h = base_frame.height as usize;
-        let dst_w = thumb_width as usize;
-        let dst_h = thumb_height as usize;
+        // 3) 크기가 이미 원하는 크기라면 그대로 사용
+        //    (open_with_resolution/open_for_thumbnail로 열었으면 스케일러가 이미 해당 크기)
+        let scaled = if base_frame.width == scaled_width && base_frame.height == scaled_height {
+            base_frame
+        } else {
+            // 크기 불일치 시 Nearest-Neighbor 다운스케일 (fallback)
+            let src_w = base_frame.width as usize;
+            let src_h = base_frame.height as usize;
+            let dst_w = scaled_width as usize;
+            let dst_h = scaled_height as usize;
 
-        let mut data = vec![0u8; dst_w * dst_h * 4];
+            let mut data = vec![0u8; dst_w * dst_h * 4];
 
-        for y in 0..dst_h {
-            let src_y = y * src_h / dst_h;
-            for x in 0..dst_w {
-                let src_x = x * src_w / dst_w;
+            for y in 0..dst_h {
+                let src_y = y * src_h / dst_h;
+                for x in 0..dst_w {
+                    let src_x = x * src_w / dst_w;
 
-                let src_index = (src_y * src_w + src_x) * 4;
-                let dst_index = (y * dst_w + x) * 4;
+                    let src_index = (src_y * src_w + src_x) * 4;
+                    let dst_index = (y * dst_w + x) * 4;
 
-                data[dst_index..dst_index + 4]
-                    .copy_from_slice(&base_frame.data[src_index..src_index + 4]);
+                    data[dst_index..dst_index + 4]
+                        .copy_from_slice(&base_frame.data[src_index..src_index + 4]);
+                }
             }
-        }
 
-        Ok(Frame {
-            width: thumb_width,
-            height: thumb_height,
-            format: PixelFormat::RGBA,
-            data,
-            timestamp_ms,
-        })
+            Frame {
+                width: scaled_width,
+                height: scaled_height,
+                format: PixelFormat::RGBA,
+                data,
+                timestamp_ms,
+                color: YuvColor::default(),
+                duration_ms: base_frame.duration_ms,
+            }
+        };
+
+        // 4) thumb 박스에 패딩/크롭
+        Ok(Self::fit_thumbnail(scaled, thumb_width, thumb_height))
+    }
+
+    /// 스케일된 RGBA 프레임을 thumb 박스 크기로 패딩/크롭 (이미 박스 크기면 그대로)
+    pub fn fit_thumbnail(frame: Frame, box_width: u32, box_height: u32) -> Frame {
+        if frame.width == box_width && frame.height == box_height {
+            return frame;
+        }
+        Frame {
+            data: fit_rgba(&frame.data, frame.width, frame.height, box_width, box_height),
+            width: box_width,
+            height: box_height,
+            format: frame.format,
+            timestamp_ms: frame.timestamp_ms,
+            color: frame.color,
+            duration_ms: frame.duration_ms,
+        }
     }
 
     /// 특정 시간으로 seek (EOF/Error 상태에서 자동 복구)
     pub fn seek(&mut self, timestamp_ms: i64) -> Result<(), String> {
-        let stream = self.input_ctx.stream(self.video_stream_index)
-            .ok_or("Video stream not found")?;
+        // avformat_seek_file(stream_index = -1)은 AV_TIME_BASE(µs) 단위 — 스트림 time_base가 아님
+        // (스트림 단위를 넘기면 목표보다 훨씬 앞으로 seek → 불필요한 forward decode)
+        let timestamp = timestamp_ms.saturating_mul(1000);
+
+        // seek 실패 → flush 후 limits.seek_retries회 재시도
+        let mut last_error = None;
+        for attempt in 0..=self.limits.seek_retries {
+            if attempt > 0 {
+                self.decoder.flush();
+            }
+            match self.input_ctx.seek(timestamp, ..timestamp) {
+                Ok(_) => {
+                    self.decoder.flush();
+                    // seek 성공 → Ready 상태로 복구 (EOF/Error에서 복구)
+                    self.state = DecoderState::Ready;
+                    self.eof_timestamp_ms = None; // EOF 마커 초기화
+                    self.needs_resync = false;
+                    // 디코더 버퍼와 함께 선택 상태도 무효
+                    self.pending_frame = None;
+                    self.last_frame_span = None;
+                    self.draining = false;
+                    return Ok(());
+                }
+                Err(e) => {
+                    last_error.get_or_insert(e);
+                }
+            }
+        }
+
+        self.state = DecoderState::Error;
+        match last_error {
+            Some(e) => Err(format!("Seek failed after {} retries: {}", self.limits.seek_retries, e)),
+            None => Err("Seek failed".to_string()),
+        }
+    }
+}
 
-        let time_base = stream.time_base();
+/// 순차 디코딩 반복자 (Decoder::frames)
+pub struct Frames<'a> {
+    decoder: &'a mut Decoder,
+    failed: bool,
+}
 
-        // milliseconds to stream time base
-        let timestamp = (timestamp_ms * i64::from(time_base.denominator()))
-            / (i64::from(time_base.numerator()) * 1000);
+impl Iterator for Frames<'_> {
+    type Item = Result<Frame, String>;
 
-        match self.input_ctx.seek(timestamp, ..timestamp) {
-            Ok(_) => {
-                self.decoder.flush();
-                // seek 성공 → Ready 상태로 복구 (EOF/Error에서 복구)
-                self.state = DecoderState::Ready;
-                self.eof_timestamp_ms = None; // EOF 마커 초기화
-                Ok(())
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let result = self.decoder.decode_next_frame().transpose();
+        self.failed = matches!(result, Some(Err(_)));
+        result
+    }
+}
+
+/// 디코딩할 비디오 스트림 (지정 번호가 없거나 비디오가 아니면 에러, None이면 기본 스트림)
+fn select_video_stream(
+    input_ctx: &ffmpeg::format::context::Input,
+    stream_index: Option<usize>,
+) -> Result<ffmpeg::Stream<'_>, String> {
+    match stream_index {
+        Some(index) => {
+            let stream = input_ctx.stream(index).ok_or_else(|| format!("Stream {} not found", index))?;
+            if stream.parameters().medium() != ffmpeg::media::Type::Video {
+                return Err(format!("Stream {} is not a video stream", index));
             }
-            Err(e) => {
-                // seek 실패 → flush 후 재시도 1회
-                self.decoder.flush();
-                match self.input_ctx.seek(timestamp, ..timestamp) {
-                    Ok(_) => {
-                        self.decoder.flush();
-                        self.state = DecoderState::Ready;
-                        Ok(())
-                    }
-                    Err(_) => {
-                        self.state = DecoderState::Error;
-                        Err(format!("Seek failed after retry: {}", e))
-                    }
+            Ok(stream)
+        }
+        None => input_ctx
+            .streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or_else(|| "No video stream found".to_string()),
+    }
+}
+
+/// ms → 스트림 time_base 단위 PTS
+fn ms_to_pts(ms: i64, tb: ffmpeg::Rational) -> i64 {
+    (ms * i64::from(tb.denominator())) / (i64::from(tb.numerator()) * 1000).max(1)
+}
+
+/// 정확 모드 목표 PTS: 시작 시간(ms 내림)이 ms 이하인 마지막 PTS
+/// 타임라인 프레임 시간은 ms 내림(frame_to_ms)이라 같은 ms 안에서 시작하는 소스 프레임이 그 프레임
+/// (29.97fps 프레임 1 = 33.37ms → 33ms 요청은 프레임 0이 아닌 프레임 1)
+fn exact_target_pts(ms: i64, tb: ffmpeg::Rational) -> i64 {
+    let num = (ms + 1) * i64::from(tb.denominator());
+    let den = (i64::from(tb.numerator()) * 1000).max(1);
+    num.div_euclid(den) + i64::from(num.rem_euclid(den) != 0) - 1
+}
+
+/// 스트림 time_base 단위 PTS → ms
+fn pts_to_ms(pts: i64, tb: ffmpeg::Rational) -> i64 {
+    (pts * i64::from(tb.numerator()) * 1000) / i64::from(tb.denominator()).max(1)
+}
+
+/// PTS 기준 프레임 선택 결과
+struct PtsSelection<F> {
+    /// 선택된 프레임 + 표시 구간 [시작, 끝) (PTS 단위, 길이를 모르면 None)
+    picked: Option<(F, Option<(i64, i64)>)>,
+    /// 목표가 마지막 프레임의 표시 구간 이후 (스트림 끝)
+    past_end: bool,
+    /// 목표 다음 프레임 (이미 디코더에서 꺼냄 → 다음 요청에서 먼저 사용)
+    lookahead: Option<F>,
+    /// 첫 프레임이 이미 목표 이후라 목표를 덮지 않는 프레임을 선택 (seek가 지나침)
+    overshoot: bool,
+}
+
+/// 목표 PTS를 덮는 프레임 선택 (모듈 레벨 - borrow checker 충돌 방지)
+/// 디코딩 순서대로 프레임을 넣으면 [pts, pts + duration) 또는 [pts, 다음 pts)가 목표를 포함하는 프레임 선택
+/// fps 기반 허용 오차를 쓰지 않으므로 VFR 소스에서도 틀린 프레임을 고르지 않음
+struct PtsSelector<F> {
+    target_pts: i64,
+    /// 목표 이전 마지막 프레임 (pts, duration) — 다음 프레임 PTS로 구간 확정 대기
+    best: Option<(F, i64, i64)>,
+    result: Option<PtsSelection<F>>,
+}
+
+impl<F> PtsSelector<F> {
+    fn new(target_pts: i64) -> Self {
+        Self { target_pts, best: None, result: None }
+    }
+
+    fn is_done(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// 디코딩된 프레임 전달 (duration: PTS 단위, 0 = 모름)
+    fn offer(&mut self, frame: F, pts: Option<i64>, duration: i64) {
+        if self.result.is_some() {
+            return;
+        }
+        let target = self.target_pts;
+        let done = |picked, lookahead, overshoot| Some(PtsSelection { picked: Some(picked), past_end: false, lookahead, overshoot });
+
+        let pts = match pts {
+            Some(pts) => pts,
+            // PTS 정보 없으면 수락 (구간 모름)
+            None => {
+                self.result = done((frame, None), None, false);
+                return;
+            }
+        };
+
+        if pts > target {
+            self.result = match self.best.take() {
+                // 목표 이전 프레임의 구간이 이 프레임 PTS에서 끝남
+                Some((best, best_pts, _)) => done((best, Some((best_pts, pts))), Some(frame), false),
+                // seek 후 첫 프레임이 이미 목표 이후 (목표가 스트림 시작 전 등)
+                None => done((frame, (duration > 0).then_some((pts, pts + duration))), None, true),
+            };
+        } else if duration > 0 && target < pts + duration {
+            self.result = done((frame, Some((pts, pts + duration))), None, false);
+        } else {
+            self.best = Some((frame, pts, duration));
+        }
+    }
+
+    /// 선택 종료
+    /// eof_duration: 스트림 끝이면 Some(길이를 모를 때 쓸 프레임 길이) — 마지막 프레임 구간으로 판정
+    /// 스트림 끝이 아닌데 미확정이면 후보 프레임은 lookahead로 보존 (다음 요청에서 계속)
+    fn finish(mut self, eof_duration: Option<i64>) -> PtsSelection<F> {
+        if let Some(result) = self.result.take() {
+            return result;
+        }
+        match (self.best.take(), eof_duration) {
+            (Some((best, pts, duration)), Some(nominal)) => {
+                let end = pts + if duration > 0 { duration } else { nominal };
+                PtsSelection {
+                    picked: Some((best, Some((pts, end)))),
+                    past_end: self.target_pts >= end,
+                    lookahead: None,
+                    overshoot: false,
                 }
             }
+            (best, _) => PtsSelection {
+                picked: None,
+                past_end: false,
+                lookahead: best.map(|(frame, _, _)| frame),
+                overshoot: false,
+            },
         }
     }
 }
 
-/// PTS가 목표에 도달했는지 확인 (모듈 레벨 함수 - borrow checker 충돌 방지)
-/// target_info: None이면 순차 재생 → 항상 true (첫 프레임 즉시 수락)
-/// target_info: Some((target_pts, tolerance_pts)) → PTS >= target - tolerance 이면 true
-fn is_pts_at_target(target_info: Option<(i64, i64)>, frame: &ffmpeg::frame::Video) -> bool {
-    match target_info {
-        None => true, // 순차 재생: 다음 프레임 무조건 사용
-        Some((target_pts, tolerance_pts)) => {
-            match frame.pts() {
-                Some(pts) => pts >= target_pts - tolerance_pts,
-                None => true, // PTS 정보 없으면 수락
-            }
+impl PtsSelector<ffmpeg::frame::Video> {
+    fn offer_frame(&mut self, frame: ffmpeg::frame::Video) {
+        let pts = frame.pts().or(frame.timestamp());
+        // SAFETY: 유효한 AVFrame (duration은 디코더가 패킷 길이로 채움, 모르면 0)
+        let duration = unsafe { (*frame.as_ptr()).duration }.max(0);
+        self.offer(frame, pts, duration);
+    }
+}
+
+/// swscale 색공간 (swscale.h SWS_CS_*)
+const SWS_CS_ITU709: i32 = 1;
+const SWS_CS_ITU601: i32 = 5;
+const SWS_CS_BT2020: i32 = 9;
+
+fn sws_colorspace(matrix: YuvMatrix) -> i32 {
+    match matrix {
+        YuvMatrix::Bt601 => SWS_CS_ITU601,
+        YuvMatrix::Bt709 => SWS_CS_ITU709,
+        YuvMatrix::Bt2020 => SWS_CS_BT2020,
+    }
+}
+
+/// 소스 YUV 행렬/범위 (태그 없으면 높이로 추정, yuvj* 포맷은 full range)
+fn source_yuv_color(decoder: &ffmpeg::codec::decoder::Video) -> YuvColor {
+    use ffmpeg::color::Space;
+    use ffmpeg::format::Pixel;
+
+    let matrix = match decoder.color_space() {
+        Space::BT709 => YuvMatrix::Bt709,
+        Space::BT470BG | Space::SMPTE170M | Space::FCC => YuvMatrix::Bt601,
+        Space::BT2020NCL | Space::BT2020CL => YuvMatrix::Bt2020,
+        _ => YuvColor::guess(decoder.height()).matrix,
+    };
+    let full_range = decoder.color_range() == ffmpeg::color::Range::JPEG
+        || matches!(decoder.format(), Pixel::YUVJ420P | Pixel::YUVJ422P | Pixel::YUVJ444P);
+    YuvColor { matrix, full_range }
+}
+
+/// 픽셀 포맷 플래그 (pixdesc.h AV_PIX_FMT_FLAG_*)
+const AV_PIX_FMT_FLAG_RGB: u64 = 1 << 5;
+const AV_PIX_FMT_FLAG_ALPHA: u64 = 1 << 7;
+
+fn pixel_format_has_flag(format: ffmpeg::format::Pixel, flag: u64) -> bool {
+    format.descriptor()
+        // SAFETY: 디스크립터는 FFmpeg 정적 테이블
+        .map(|desc| unsafe { (*desc.as_ptr()).flags } & flag != 0)
+        .unwrap_or(false)
+}
+
+/// VP8/VP9 알파 스트림용 디코더 (WebM alpha_mode=1)
+/// 알파는 BlockAdditional에 별도 저장되어 FFmpeg 내장 디코더는 버림 → libvpx 디코더만 yuva420p 출력
+fn vpx_alpha_decoder(stream: &ffmpeg::Stream, codec_id: ffmpeg::codec::Id) -> Option<ffmpeg::Codec> {
+    if stream.metadata().get("alpha_mode") != Some("1") {
+        return None;
+    }
+    let name = match codec_id {
+        ffmpeg::codec::Id::VP9 => "libvpx-vp9",
+        ffmpeg::codec::Id::VP8 => "libvpx",
+        _ => return None,
+    };
+    let codec = ffmpeg::decoder::find_by_name(name);
+    if codec.is_none() {
+        log_warn!("[DECODER] {} 디코더 없음 → 알파 채널 없이 디코딩", name);
+    }
+    codec
+}
+
+/// 픽셀 포맷의 컴포넌트당 비트 수 (알 수 없으면 8)
+fn source_bit_depth(format: ffmpeg::format::Pixel) -> i32 {
+    format.descriptor()
+        // SAFETY: 디스크립터는 FFmpeg 정적 테이블
+        .map(|desc| unsafe { (*desc.as_ptr()).comp[0].depth })
+        .unwrap_or(8)
+}
+
+/// 스케일러 색공간 설정 (swscale 기본값은 입력/출력 모두 BT.601)
+/// source: YUV 입력 행렬/범위 (YUV→RGB/YUV), output: YUV 출력 행렬 (RGB→YUV), 출력 범위는 포맷 기본값 유지
+/// SAFETY: scaler는 유효한 SwsContext
+pub(crate) unsafe fn set_scaler_colorspace(
+    scaler: &mut ffmpeg::software::scaling::Context,
+    (source, output): (YuvColor, YuvMatrix),
+) {
+    let ctx = scaler.as_mut_ptr();
+    let (mut inv_table, mut table) = (std::ptr::null_mut(), std::ptr::null_mut());
+    let (mut src_range, mut dst_range) = (0, 0);
+    let (mut brightness, mut contrast, mut saturation) = (0, 0, 0);
+    if ffi::sws_getColorspaceDetails(
+        ctx, &mut inv_table, &mut src_range, &mut table, &mut dst_range,
+        &mut brightness, &mut contrast, &mut saturation,
+    ) < 0 {
+        return;
+    }
+    ffi::sws_setColorspaceDetails(
+        ctx,
+        ffi::sws_getCoefficients(sws_colorspace(source.matrix)),
+        source.full_range as i32,
+        ffi::sws_getCoefficients(sws_colorspace(output)),
+        dst_range,
+        brightness, contrast, saturation,
+    );
+}
+
+/// 평면 하나를 시계 방향으로 회전 (bpp: 픽셀당 바이트, 90/270이면 가로/세로 교환)
+fn rotate_plane(src: &[u8], width: usize, height: usize, bpp: usize, rotation_deg: i32) -> Vec<u8> {
+    let mut dst = vec![0u8; width * height * bpp];
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy, dst_width) = match rotation_deg {
+                90 => (height - 1 - y, x, height),
+                180 => (width - 1 - x, height - 1 - y, width),
+                270 => (y, width - 1 - x, height),
+                _ => (x, y, width),
+            };
+            let s = (y * width + x) * bpp;
+            let d = (dy * dst_width + dx) * bpp;
+            dst[d..d + bpp].copy_from_slice(&src[s..s + bpp]);
         }
     }
+    dst
+}
+
+/// 디코딩 프레임에 소스 회전 적용 (RGBA/RGB/YUV420P, 0도면 그대로)
+pub fn rotate_frame(frame: Frame, rotation_deg: i32) -> Frame {
+    if !matches!(rotation_deg, 90 | 180 | 270) {
+        return frame;
+    }
+    let w = frame.width as usize;
+    let h = frame.height as usize;
+    let data = match frame.format {
+        PixelFormat::RGBA => rotate_plane(&frame.data, w, h, 4, rotation_deg),
+        PixelFormat::RGB => rotate_plane(&frame.data, w, h, 3, rotation_deg),
+        PixelFormat::YUV420P => {
+            let y_size = w * h;
+            let uv_size = (w / 2) * (h / 2);
+            let mut data = rotate_plane(&frame.data[..y_size], w, h, 1, rotation_deg);
+            for plane in frame.data[y_size..y_size + uv_size * 2].chunks_exact(uv_size.max(1)) {
+                data.extend(rotate_plane(plane, w / 2, h / 2, 1, rotation_deg));
+            }
+            data
+        }
+    };
+    let (width, height) = if rotation_deg == 180 {
+        (frame.width, frame.height)
+    } else {
+        (frame.height, frame.width)
+    };
+    Frame { width, height, data, ..frame }
 }
 
 // 실제 비디오 파일이 필요하므로 테스트는 주석 처리
@@ -641,6 +1644,108 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_decode_limits_exceeded() {
+        let started = std::time::Instant::now();
+        let limits = DecodeLimits::default();
+        assert!(!limits.exceeded(3000, started));
+        assert!(limits.exceeded(3001, started));
+
+        // 0 = 무제한
+        let unlimited = DecodeLimits { max_packets: 0, max_decode_ms: 0, seek_retries: 0 };
+        assert!(!unlimited.exceeded(u32::MAX, started));
+
+        let timed = DecodeLimits { max_packets: 0, max_decode_ms: 1, seek_retries: 0 };
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert!(timed.exceeded(0, started));
+    }
+
+    #[test]
+    fn test_pts_selector() {
+        // VFR: 프레임 2(pts 100)가 400 동안 표시 — 평균 fps 허용 오차가 아닌 PTS 구간으로 선택
+        let select = |target, frames: &[(i32, i64, i64)], eof: Option<i64>| {
+            let mut selector = PtsSelector::new(target);
+            for &(frame, pts, duration) in frames {
+                selector.offer(frame, Some(pts), duration);
+            }
+            let selection = selector.finish(eof);
+            (selection.picked, selection.past_end, selection.lookahead)
+        };
+
+        // 길이를 알면 구간 안에서 즉시 선택
+        assert_eq!(select(300, &[(1, 67, 33), (2, 100, 400)], None), (Some((2, Some((100, 500)))), false, None));
+        // 길이를 모르면 다음 PTS로 구간 확정, 다음 프레임은 lookahead로 보존
+        assert_eq!(select(300, &[(1, 67, 0), (2, 100, 0), (3, 500, 0)], None), (Some((2, Some((100, 500)))), false, Some(3)));
+        // 미확정 + 탐색 중단 → 선택 없음, 후보 보존
+        assert_eq!(select(300, &[(2, 100, 0)], None), (None, false, Some(2)));
+        // 첫 프레임이 목표 이후
+        assert_eq!(select(0, &[(1, 40, 0)], None), (Some((1, None)), false, None));
+        // 스트림 끝: 마지막 프레임 구간 안 / 밖
+        assert_eq!(select(520, &[(3, 500, 0)], Some(33)), (Some((3, Some((500, 533)))), false, None));
+        assert_eq!(select(600, &[(3, 500, 33)], Some(33)), (Some((3, Some((500, 533)))), true, None));
+        assert_eq!(select(600, &[], Some(33)), (None, false, None));
+
+        // seek가 목표를 지나친 경우만 overshoot (정확 모드 재-seek 판정)
+        let overshoot = |target, frames: &[(i32, i64, i64)]| {
+            let mut selector = PtsSelector::new(target);
+            for &(frame, pts, duration) in frames {
+                selector.offer(frame, Some(pts), duration);
+            }
+            selector.finish(None).overshoot
+        };
+        assert!(overshoot(0, &[(1, 40, 0)]));
+        assert!(!overshoot(300, &[(1, 67, 0), (2, 100, 0), (3, 500, 0)]));
+        assert!(!overshoot(120, &[(2, 100, 33)]));
+    }
+
+    #[test]
+    fn test_exact_target_pts() {
+        // 29.97fps (90kHz): 프레임 1 = pts 3003 (33.37ms) → 타임라인 33ms 요청은 프레임 1을 덮음
+        let tb = ffmpeg::Rational::new(1, 90000);
+        assert_eq!(ms_to_pts(33, tb), 2970);
+        assert_eq!(exact_target_pts(33, tb), 3059);
+        assert_eq!(exact_target_pts(32, tb), 2969);
+        // 거친 time_base (1/30): 프레임 1 = 33.33ms
+        let coarse = ffmpeg::Rational::new(1, 30);
+        assert_eq!(exact_target_pts(33, coarse), 1);
+        assert_eq!(exact_target_pts(32, coarse), 0);
+        assert_eq!(exact_target_pts(0, coarse), 0);
+    }
+
+    #[test]
+    fn test_rotate_frame() {
+        // 2x1 RGBA [A B] → 90도: 1x2 [A; B], 180도: [B A], 270도: [B; A]
+        let frame = Frame {
+            width: 2,
+            height: 1,
+            format: PixelFormat::RGBA,
+            data: vec![1, 1, 1, 1, 2, 2, 2, 2],
+            timestamp_ms: 0,
+            color: YuvColor::default(),
+            duration_ms: 0,
+        };
+        let rotated = rotate_frame(frame.clone(), 90);
+        assert_eq!((rotated.width, rotated.height), (1, 2));
+        assert_eq!(rotated.data, vec![1, 1, 1, 1, 2, 2, 2, 2]);
+        assert_eq!(rotate_frame(frame.clone(), 180).data, vec![2, 2, 2, 2, 1, 1, 1, 1]);
+        assert_eq!(rotate_frame(frame.clone(), 270).data, vec![2, 2, 2, 2, 1, 1, 1, 1]);
+        assert_eq!(rotate_frame(frame.clone(), 0).data, frame.data);
+
+        // 4x2 YUV420P: Y 평면 회전 + U/V 평면(2x1) 각각 회전
+        let yuv = Frame {
+            width: 4,
+            height: 2,
+            format: PixelFormat::YUV420P,
+            data: vec![0, 1, 2, 3, 4, 5, 6, 7, 10, 11, 20, 21],
+            timestamp_ms: 0,
+            color: YuvColor::default(),
+            duration_ms: 0,
+        };
+        let rotated = rotate_frame(yuv, 90);
+        assert_eq!((rotated.width, rotated.height), (2, 4));
+        assert_eq!(rotated.data, vec![4, 0, 5, 1, 6, 2, 7, 3, 10, 11, 20, 21]);
+    }
+
     #[test]
     #[ignore] // 실제 비디오 파일 필요
     fn test_decoder_open() {
@@ -669,6 +1774,71 @@ mod tests {
         assert!(!frame.data.is_empty());
     }
 
+    #[test]
+    #[ignore] // 실제 비디오 파일 필요
+    fn test_decode_next_frame() {
+        let path = PathBuf::from("test.mp4");
+        let mut decoder = Decoder::open(&path).unwrap();
+
+        // 표시 순서 (PTS 증가), 끝까지 읽으면 None
+        let timestamps: Vec<i64> = decoder.frames().map(|f| f.unwrap().timestamp_ms).collect();
+        assert!(!timestamps.is_empty());
+        assert!(timestamps.windows(2).all(|w| w[0] < w[1]));
+        assert!(decoder.decode_next_frame().unwrap().is_none());
+
+        // decode_frame 이후에는 그 다음 프레임부터 이어짐 (timestamp_ms는 PTS 내림 → +1ms가 프레임 1 구간)
+        decoder.decode_frame(timestamps[1] + 1).unwrap();
+        let next = decoder.decode_next_frame().unwrap().unwrap();
+        assert_eq!(next.timestamp_ms, timestamps[2]);
+    }
+
+    #[test]
+    #[ignore] // 실제 비디오 파일 필요
+    fn test_decode_frame_reverse() {
+        let path = PathBuf::from("test.mp4");
+        let mut forward = Decoder::open(&path).unwrap();
+        let mut reverse = Decoder::open(&path).unwrap();
+
+        // 역방향 연속 요청도 정방향 정확 디코딩과 같은 프레임
+        for timestamp_ms in (0..=2000).rev().step_by(33) {
+            let expected = match forward.decode_frame(timestamp_ms).unwrap() {
+                DecodeResult::Frame(f) | DecodeResult::EndOfStream(f) => f,
+                _ => panic!("forward decode failed at {}ms", timestamp_ms),
+            };
+            let actual = match reverse.decode_frame_reverse(timestamp_ms).unwrap() {
+                DecodeResult::Frame(f) | DecodeResult::EndOfStream(f) => f,
+                _ => panic!("reverse decode failed at {}ms", timestamp_ms),
+            };
+            assert_eq!(actual.timestamp_ms, timestamp_ms);
+            assert_eq!(actual.data, expected.data, "frame mismatch at {}ms", timestamp_ms);
+        }
+    }
+
+    #[test]
+    #[ignore] // 실제 비디오 파일 필요
+    fn test_decode_keyframe_timestamp() {
+        let path = PathBuf::from("test.mp4");
+
+        // 소스 키프레임 PTS (ms)
+        let mut input_ctx = ffmpeg::format::input(&path).unwrap();
+        let video_index = input_ctx.streams().best(ffmpeg::media::Type::Video).unwrap().index();
+        let keyframes_ms: Vec<i64> = input_ctx.packets()
+            .filter(|(stream, packet)| stream.index() == video_index && packet.is_key())
+            .filter_map(|(stream, packet)| packet.pts().map(|pts| pts_to_ms(pts, stream.time_base())))
+            .collect();
+        assert!(!keyframes_ms.is_empty());
+
+        let mut decoder = Decoder::open(&path).unwrap();
+        for timestamp_ms in (0..=5000).step_by(250) {
+            let frame = match decoder.decode_keyframe(timestamp_ms).unwrap() {
+                DecodeResult::Frame(f) => f,
+                _ => continue,
+            };
+            assert!(frame.timestamp_ms <= timestamp_ms, "{}ms → {}ms", timestamp_ms, frame.timestamp_ms);
+            assert!(keyframes_ms.contains(&frame.timestamp_ms), "{}ms is not a keyframe", frame.timestamp_ms);
+        }
+    }
+
     #[test]
     fn test_decoder_with_real_file() {
         // 실제 비디오 파일로 테스트
@@ -0,0 +1,119 @@
+// 프레임 인덱스 - 소스 프레임 PTS 목록으로 시간 → 프레임 매핑 (VFR 소스용)
+// 화면 녹화/폰 영상은 프레임 간격이 일정하지 않아 평균 fps로 계산한 프레임 번호/경계가 어긋남
+// 디먹싱만으로 모은 PTS(ms)로 각 프레임의 표시 구간 [시작, 다음 프레임 시작)을 계산
+
+/// 평균 대비 이 이상(ms) 차이 나는 프레임 길이가 있으면 가변 프레임레이트
+const VARIABLE_TOLERANCE_MS: i64 = 2;
+
+/// 소스 프레임 표시 구간 목록
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameIndex {
+    /// 프레임 시작 시간 (ms, 오름차순, 중복 없음)
+    starts_ms: Vec<i64>,
+    /// 마지막 프레임 끝 (ms)
+    end_ms: i64,
+}
+
+impl FrameIndex {
+    /// pts_ms: 프레임 시작 시간 (순서 무관 — B-frame 디코딩 순서 그대로 가능)
+    /// end_ms: 마지막 프레임 끝 (모르면 0 → 직전 프레임 길이로 추정)
+    pub fn new(mut pts_ms: Vec<i64>, end_ms: i64) -> Self {
+        pts_ms.sort_unstable();
+        pts_ms.dedup();
+
+        let last = pts_ms.last().copied().unwrap_or(0);
+        let end_ms = if end_ms > last {
+            end_ms
+        } else {
+            match pts_ms.len() {
+                0 => 0,
+                1 => last + 1,
+                n => last + (last - pts_ms[n - 2]),
+            }
+        };
+        Self { starts_ms: pts_ms, end_ms }
+    }
+
+    pub fn len(&self) -> usize {
+        self.starts_ms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts_ms.is_empty()
+    }
+
+    /// 마지막 프레임 끝 (ms)
+    pub fn end_ms(&self) -> i64 {
+        self.end_ms
+    }
+
+    /// 시간에 표시되는 프레임 번호 (첫 프레임 이전은 0, 끝 이후는 마지막 프레임)
+    pub fn frame_at(&self, time_ms: i64) -> Option<usize> {
+        if self.starts_ms.is_empty() {
+            return None;
+        }
+        Some(self.starts_ms.partition_point(|&start| start <= time_ms).saturating_sub(1))
+    }
+
+    /// 프레임 표시 구간 [시작, 끝) (ms)
+    pub fn frame_span(&self, index: usize) -> Option<(i64, i64)> {
+        let start = *self.starts_ms.get(index)?;
+        let end = self.starts_ms.get(index + 1).copied().unwrap_or(self.end_ms);
+        Some((start, end))
+    }
+
+    /// 시간에 표시되는 프레임의 구간
+    pub fn span_at(&self, time_ms: i64) -> Option<(i64, i64)> {
+        self.frame_at(time_ms).and_then(|index| self.frame_span(index))
+    }
+
+    /// 프레임 표시 길이 (ms, 없는 프레임은 0)
+    pub fn frame_duration_ms(&self, index: usize) -> i64 {
+        self.frame_span(index).map_or(0, |(start, end)| end - start)
+    }
+
+    /// 프레임 길이가 일정하지 않은지 (평균 대비 VARIABLE_TOLERANCE_MS 초과 차이)
+    pub fn is_variable(&self) -> bool {
+        let count = self.starts_ms.len();
+        if count < 2 {
+            return false;
+        }
+        let average = (self.end_ms - self.starts_ms[0]) / count as i64;
+        (0..count).any(|i| (self.frame_duration_ms(i) - average).abs() > VARIABLE_TOLERANCE_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_lookup() {
+        // 화면 녹화: 변화 없는 구간에서 프레임 간격이 늘어남 (디코딩 순서로 입력)
+        let index = FrameIndex::new(vec![0, 100, 33, 67, 500, 533], 566);
+        assert_eq!(index.len(), 6);
+        assert!(index.is_variable());
+
+        assert_eq!(index.frame_at(-10), Some(0));
+        assert_eq!(index.frame_at(66), Some(1));
+        assert_eq!(index.frame_at(67), Some(2));
+        // 평균 fps로는 프레임 9 근처지만 실제로는 100ms 프레임이 500ms까지 표시
+        assert_eq!(index.frame_at(300), Some(3));
+        assert_eq!(index.span_at(300), Some((100, 500)));
+        assert_eq!(index.frame_duration_ms(3), 400);
+        assert_eq!(index.frame_at(10_000), Some(5));
+        assert_eq!(index.frame_span(5), Some((533, 566)));
+        assert_eq!(index.frame_span(6), None);
+    }
+
+    #[test]
+    fn test_constant_rate_and_unknown_end() {
+        // 끝을 모르면 직전 프레임 길이로 추정, ms 반올림 차이는 고정 프레임레이트로 취급
+        let index = FrameIndex::new(vec![0, 34, 67, 100], 0);
+        assert_eq!(index.end_ms(), 133);
+        assert!(!index.is_variable());
+
+        assert_eq!(FrameIndex::new(Vec::new(), 0).frame_at(0), None);
+        assert!(!FrameIndex::new(vec![0], 0).is_variable());
+    }
+}
@@ -0,0 +1,194 @@
+// HDR(PQ/HLG) → SDR 톤 매핑 - 프리뷰/합성용 RGBA 출력
+// 입력: 스케일러 출력 RGBA64LE (BT.2020 원색, 전달 함수가 적용된 비선형 값, 16bit라 10bit 소스도 밴딩 없음)
+// 처리: EOTF(LUT) → 선형 BT.2020 → BT.709 색역 변환 → 최대 채널 기준 무릎 곡선 → BT.709 감마(LUT) → RGBA8
+// 최대 채널 기준 압축이라 밝은 영역의 색상(hue)이 유지됨
+
+/// SDR 기준 백색 (nits, ITU-R BT.2408) — 선형 값 1.0
+const SDR_WHITE_NITS: f32 = 203.0;
+/// HLG 공칭 최대 휘도 (nits, 시스템 감마 1.2 기준)
+const HLG_PEAK_NITS: f32 = 1000.0;
+/// HLG 시스템 감마
+const HLG_SYSTEM_GAMMA: f32 = 1.2;
+/// 무릎 시작점 (SDR 백색 대비) — 이하 구간은 그대로, 이상은 1.0으로 수렴하도록 압축
+const KNEE_START: f32 = 0.75;
+/// EOTF LUT 인덱스 비트 (16bit 입력의 상위 12bit)
+const EOTF_LUT_BITS: u32 = 12;
+/// 감마 LUT 크기 (선형 0~1)
+const OETF_LUT_SIZE: usize = 4096;
+
+/// BT.2020 → BT.709 선형 RGB 변환 행렬
+const BT2020_TO_BT709: [[f32; 3]; 3] = [
+    [1.660_491, -0.587_641, -0.072_85],
+    [-0.124_55, 1.132_9, -0.008_349],
+    [-0.018_151, -0.100_579, 1.118_73],
+];
+
+/// 소스 전달 함수 (color_trc)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferFunction {
+    /// BT.709/BT.601/sRGB 등 SDR
+    Sdr,
+    /// SMPTE ST 2084 (HDR10)
+    Pq,
+    /// ARIB STD-B67 (방송/폰 HDR)
+    Hlg,
+}
+
+impl TransferFunction {
+    pub fn is_hdr(self) -> bool {
+        self != TransferFunction::Sdr
+    }
+}
+
+/// PQ 비선형 값(0~1) → 절대 휘도 (nits)
+pub fn pq_eotf_nits(value: f32) -> f32 {
+    const M1: f32 = 2610.0 / 16384.0;
+    const M2: f32 = 2523.0 / 4096.0 * 128.0;
+    const C1: f32 = 3424.0 / 4096.0;
+    const C2: f32 = 2413.0 / 4096.0 * 32.0;
+    const C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+    let p = value.clamp(0.0, 1.0).powf(1.0 / M2);
+    ((p - C1).max(0.0) / (C2 - C3 * p)).powf(1.0 / M1) * 10000.0
+}
+
+/// HLG 비선형 값(0~1) → 장면 선형 값(0~1) (역 OETF)
+pub fn hlg_inverse_oetf(value: f32) -> f32 {
+    const A: f32 = 0.178_832_77;
+    const B: f32 = 0.284_668_92;
+    const C: f32 = 0.559_910_7;
+
+    let v = value.clamp(0.0, 1.0);
+    if v <= 0.5 {
+        v * v / 3.0
+    } else {
+        (((v - C) / A).exp() + B) / 12.0
+    }
+}
+
+/// SDR 백색 대비 선형 값 → 출력 범위(0~1) 무릎 곡선 (KNEE_START에서 기울기 1로 연속)
+fn knee(x: f32) -> f32 {
+    if x <= KNEE_START {
+        x
+    } else {
+        let range = 1.0 - KNEE_START;
+        KNEE_START + range * (1.0 - (-(x - KNEE_START) / range).exp())
+    }
+}
+
+/// HDR → SDR 톤 매퍼 (전달 함수별 LUT 캐시)
+pub struct ToneMapper {
+    transfer: TransferFunction,
+    /// 비선형 입력(상위 12bit) → 선형 값 (PQ: SDR 백색 대비, HLG: 장면 선형)
+    eotf: Vec<f32>,
+    /// 선형 0~1 → 8bit BT.709 감마 (BT.1886 역함수)
+    oetf: Vec<u8>,
+}
+
+impl ToneMapper {
+    pub fn new(transfer: TransferFunction) -> Self {
+        let eotf_size = 1usize << EOTF_LUT_BITS;
+        let eotf = (0..eotf_size)
+            .map(|i| {
+                let v = i as f32 / (eotf_size - 1) as f32;
+                match transfer {
+                    TransferFunction::Pq => pq_eotf_nits(v) / SDR_WHITE_NITS,
+                    TransferFunction::Hlg => hlg_inverse_oetf(v),
+                    TransferFunction::Sdr => v.powf(2.4),
+                }
+            })
+            .collect();
+        let oetf = (0..OETF_LUT_SIZE)
+            .map(|i| ((i as f32 / (OETF_LUT_SIZE - 1) as f32).powf(1.0 / 2.4) * 255.0).round() as u8)
+            .collect();
+        Self { transfer, eotf, oetf }
+    }
+
+    pub fn transfer(&self) -> TransferFunction {
+        self.transfer
+    }
+
+    /// RGBA64LE 행 → RGBA8 행 (픽셀 수 = dst.len() / 4, 알파는 상위 8bit)
+    pub fn map_row(&self, src: &[u8], dst: &mut [u8]) {
+        let shift = 16 - EOTF_LUT_BITS;
+        let sample = |i: usize| u16::from_le_bytes([src[i], src[i + 1]]);
+
+        for (px, out) in dst.chunks_exact_mut(4).enumerate() {
+            let base = px * 8;
+            let mut rgb = [
+                self.eotf[(sample(base) >> shift) as usize],
+                self.eotf[(sample(base + 2) >> shift) as usize],
+                self.eotf[(sample(base + 4) >> shift) as usize],
+            ];
+
+            // HLG: 장면 선형 → 디스플레이 선형 (OOTF, 휘도 기반 시스템 감마)
+            if self.transfer == TransferFunction::Hlg {
+                let luma = 0.2627 * rgb[0] + 0.6780 * rgb[1] + 0.0593 * rgb[2];
+                let gain = HLG_PEAK_NITS / SDR_WHITE_NITS * luma.max(1e-6).powf(HLG_SYSTEM_GAMMA - 1.0);
+                rgb.iter_mut().for_each(|c| *c *= gain);
+            }
+
+            // BT.2020 → BT.709 (색역 밖 음수는 0으로)
+            let mut mapped = BT2020_TO_BT709.map(|row| (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).max(0.0));
+
+            // 최대 채널 기준 압축 (색상 유지)
+            let peak = mapped[0].max(mapped[1]).max(mapped[2]);
+            if peak > KNEE_START {
+                let scale = knee(peak) / peak;
+                mapped.iter_mut().for_each(|c| *c *= scale);
+            }
+
+            for (o, c) in out.iter_mut().zip(mapped) {
+                *o = self.oetf[(c.min(1.0) * (OETF_LUT_SIZE - 1) as f32).round() as usize];
+            }
+            out[3] = (sample(base + 6) >> 8) as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba64(r: f32, g: f32, b: f32) -> Vec<u8> {
+        [r, g, b, 1.0]
+            .iter()
+            .flat_map(|v| ((v * 65535.0).round() as u16).to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_transfer_functions() {
+        // PQ: 1.0 = 10000 nits, 0.5807 ≈ 203 nits (HDR 기준 백색)
+        assert!((pq_eotf_nits(1.0) - 10000.0).abs() < 1.0);
+        assert!((pq_eotf_nits(0.5807) - 203.0).abs() < 2.0);
+        assert_eq!(pq_eotf_nits(0.0), 0.0);
+
+        // HLG: 0.5 = 1/12 (제곱근 구간 경계), 1.0 ≈ 1.0
+        assert!((hlg_inverse_oetf(0.5) - 1.0 / 12.0).abs() < 1e-6);
+        assert!((hlg_inverse_oetf(1.0) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_tone_map_pq() {
+        let mapper = ToneMapper::new(TransferFunction::Pq);
+        let map = |r, g, b| {
+            let mut out = [0u8; 4];
+            mapper.map_row(&rgba64(r, g, b), &mut out);
+            out
+        };
+
+        assert_eq!(map(0.0, 0.0, 0.0), [0, 0, 0, 255]);
+
+        // 기준 백색(무채색)은 무채색 유지, 밝게 표시되지만 포화되지 않음
+        let white = map(0.5807, 0.5807, 0.5807);
+        assert!(white[0] == white[1] && white[1] == white[2]);
+        assert!(white[0] > 200 && white[0] < 255, "{:?}", white);
+
+        // 하이라이트는 압축되어 1.0 근처로 수렴, 밝기 순서 유지
+        let bright = map(0.75, 0.75, 0.75);
+        let peak = map(1.0, 1.0, 1.0);
+        assert!(bright[0] > white[0] && peak[0] >= bright[0]);
+        assert_eq!(peak[0], 255);
+    }
+}
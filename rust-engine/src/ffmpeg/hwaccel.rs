@@ -0,0 +1,125 @@
+// 하드웨어 가속 디코딩 (NVDEC/D3D11VA/QSV 등)
+// ffmpeg-next에 hwaccel 래퍼가 없어 ffmpeg-sys 직접 호출
+// 흐름: 코덱 HW config 조회 → HW device 생성 → 코덱 컨텍스트에 연결 (open 전)
+//       → 디코딩된 HW 프레임을 av_hwframe_transfer_data로 시스템 메모리로 복사 → 기존 스케일러
+
+use ffmpeg_next as ffmpeg;
+use ffmpeg::ffi;
+use crate::utils::log::log_warn;
+use std::ffi::c_void;
+use std::ptr;
+
+/// AV_CODEC_HW_CONFIG_METHOD_HW_DEVICE_CTX (codec.h 익명 enum)
+const HW_CONFIG_METHOD_HW_DEVICE_CTX: i32 = 0x01;
+
+/// 플랫폼별 시도 순서 (앞에서부터 성공하는 첫 장치 사용)
+fn candidate_device_types() -> &'static [ffi::AVHWDeviceType] {
+    use ffi::AVHWDeviceType::*;
+
+    #[cfg(target_os = "windows")]
+    {
+        &[AV_HWDEVICE_TYPE_CUDA, AV_HWDEVICE_TYPE_D3D11VA, AV_HWDEVICE_TYPE_QSV]
+    }
+    #[cfg(target_os = "macos")]
+    {
+        &[AV_HWDEVICE_TYPE_VIDEOTOOLBOX]
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        &[AV_HWDEVICE_TYPE_CUDA, AV_HWDEVICE_TYPE_VAAPI, AV_HWDEVICE_TYPE_QSV]
+    }
+}
+
+/// 코덱이 해당 장치 타입을 device context 방식으로 지원하면 HW 픽셀 포맷 반환
+unsafe fn find_hw_pix_fmt(
+    codec: *const ffi::AVCodec,
+    device_type: ffi::AVHWDeviceType,
+) -> Option<ffi::AVPixelFormat> {
+    let mut index = 0;
+    loop {
+        let config = ffi::avcodec_get_hw_config(codec, index);
+        if config.is_null() {
+            return None;
+        }
+        if (*config).methods & HW_CONFIG_METHOD_HW_DEVICE_CTX != 0 && (*config).device_type == device_type {
+            return Some((*config).pix_fmt);
+        }
+        index += 1;
+    }
+}
+
+/// get_format 콜백: opaque에 저장한 HW 포맷 선택, 없으면 첫 소프트웨어 포맷 (자동 SW 폴백)
+unsafe extern "C" fn get_hw_format(
+    ctx: *mut ffi::AVCodecContext,
+    formats: *const ffi::AVPixelFormat,
+) -> ffi::AVPixelFormat {
+    let wanted = (*ctx).opaque as isize as i32;
+
+    let mut p = formats;
+    while *p != ffi::AVPixelFormat::AV_PIX_FMT_NONE {
+        if *p as i32 == wanted {
+            return *p;
+        }
+        p = p.add(1);
+    }
+
+    log_warn!("[HWACCEL] HW 포맷 협상 실패 → 소프트웨어 디코딩");
+    let mut p = formats;
+    while *p != ffi::AVPixelFormat::AV_PIX_FMT_NONE {
+        let desc = ffi::av_pix_fmt_desc_get(*p);
+        if !desc.is_null() && (*desc).flags & ffi::AV_PIX_FMT_FLAG_HWACCEL as u64 == 0 {
+            return *p;
+        }
+        p = p.add(1);
+    }
+    ffi::AVPixelFormat::AV_PIX_FMT_NONE
+}
+
+/// 코덱 컨텍스트에 HW 장치 연결 (avcodec_open2 이전에 호출)
+/// 성공 시 사용한 장치 타입, 지원 장치가 없으면 None (컨텍스트는 변경 없음)
+pub(crate) unsafe fn attach(
+    ctx: *mut ffi::AVCodecContext,
+    codec: *const ffi::AVCodec,
+) -> Option<ffi::AVHWDeviceType> {
+    for &device_type in candidate_device_types() {
+        let pix_fmt = match find_hw_pix_fmt(codec, device_type) {
+            Some(f) => f,
+            None => continue,
+        };
+
+        let mut device_ref: *mut ffi::AVBufferRef = ptr::null_mut();
+        let ret = ffi::av_hwdevice_ctx_create(&mut device_ref, device_type, ptr::null(), ptr::null_mut(), 0);
+        if ret < 0 || device_ref.is_null() {
+            continue;
+        }
+
+        // 컨텍스트가 자체 참조를 보유 → 로컬 참조는 즉시 해제
+        (*ctx).hw_device_ctx = ffi::av_buffer_ref(device_ref);
+        ffi::av_buffer_unref(&mut device_ref);
+        if (*ctx).hw_device_ctx.is_null() {
+            continue;
+        }
+
+        (*ctx).opaque = pix_fmt as i32 as isize as *mut c_void;
+        (*ctx).get_format = Some(get_hw_format);
+        return Some(device_type);
+    }
+    None
+}
+
+/// HW 프레임이면 시스템 메모리로 복사한 프레임 반환, 소프트웨어 프레임이면 None
+pub(crate) fn transfer(frame: &ffmpeg::frame::Video) -> Result<Option<ffmpeg::frame::Video>, String> {
+    unsafe {
+        if (*frame.as_ptr()).hw_frames_ctx.is_null() {
+            return Ok(None);
+        }
+
+        let mut sw_frame = ffmpeg::frame::Video::empty();
+        let ret = ffi::av_hwframe_transfer_data(sw_frame.as_mut_ptr(), frame.as_ptr(), 0);
+        if ret < 0 {
+            return Err(format!("HW frame transfer failed: {}", ffmpeg::Error::from(ret)));
+        }
+        sw_frame.set_pts(frame.pts());
+        Ok(Some(sw_frame))
+    }
+}
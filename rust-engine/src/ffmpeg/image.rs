@@ -0,0 +1,26 @@
+// 정지 이미지 로더 (PNG/JPEG/BMP)
+// FFmpeg image2 디먹서로 단일 프레임을 디코딩 → 비디오 클립과 같은 출력 포맷/해상도로 변환
+
+use crate::ffmpeg::decoder::{Decoder, DecodeResult, Frame};
+use std::path::Path;
+
+/// 이미지 파일을 한 프레임으로 디코딩
+/// - export_resolution: Some이면 Export용 YUV420P(해당 해상도), None이면 프리뷰 RGBA preview_size
+/// - 반환: (출력 크기 프레임, 표시 기준 원본 크기 — 맞춤 모드 배치용)
+pub fn load_image(
+    file_path: &Path,
+    export_resolution: Option<(u32, u32)>,
+    preview_size: (u32, u32),
+) -> Result<(Frame, (u32, u32)), String> {
+    let mut decoder = match export_resolution {
+        Some((w, h)) => Decoder::open_for_export(file_path, w, h)?,
+        None => Decoder::open_with_resolution(file_path, preview_size.0, preview_size.1)?,
+    };
+
+    match decoder.decode_frame(0)? {
+        DecodeResult::Frame(frame) | DecodeResult::EndOfStream(frame) => Ok((frame, decoder.display_size())),
+        DecodeResult::FrameSkipped | DecodeResult::EndOfStreamEmpty => {
+            Err(format!("No image frame decoded: {}", file_path.display()))
+        }
+    }
+}
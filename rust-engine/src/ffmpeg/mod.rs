@@ -1,6 +1,15 @@
 // FFmpeg 래퍼 모듈
-// 비디오/오디오 디코딩/인코딩
+// 비디오/오디오/이미지 디코딩/인코딩
 
+pub mod colorspace;
 pub mod decoder;
+pub mod frame_index;
+pub mod hdr;
+pub mod hwaccel;
+pub mod image;
+pub mod network;
+pub mod probe;
 
-pub use decoder::{Decoder, Frame, PixelFormat, DecoderState, DecodeResult};
+pub use colorspace::{YuvColor, YuvMatrix};
+pub use frame_index::FrameIndex;
+pub use decoder::{Decoder, Frame, Frames, PixelFormat, DecoderState, DecodeResult, DecodeLimits, SeekMode};
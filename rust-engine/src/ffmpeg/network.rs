@@ -0,0 +1,156 @@
+// 네트워크 소스 - HTTP/HTTPS/RTMP/RTSP 등 FFmpeg 프로토콜 URL 입력
+// URL도 로컬 파일과 같은 Path로 전달 (Decoder/probe/썸네일/오디오 디코더가 open_input 공용)
+// 멈춘 스트림이 렌더 스레드를 붙잡지 않도록: 열기 타임아웃 + 읽기 타임아웃(rw_timeout) + 인터럽트 콜백
+// 인터럽트 콜백: 열기 마감 초과, 또는 interrupt_all 호출 전에 연 네트워크 입력의 블로킹 I/O 중단 (AVERROR_EXIT)
+
+use ffmpeg_next as ffmpeg;
+use ffmpeg::ffi;
+use std::cell::Cell;
+use std::ffi::{c_int, c_void, CString};
+use std::path::Path;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+/// 기본 열기 타임아웃 (연결 + 헤더/스트림 정보 분석)
+pub const DEFAULT_OPEN_TIMEOUT_MS: u32 = 10_000;
+/// 기본 읽기 타임아웃 (데이터가 이 시간 동안 오지 않으면 읽기 실패)
+pub const DEFAULT_READ_TIMEOUT_MS: u32 = 15_000;
+
+/// socket "timeout" 옵션(μs)으로 연결 타임아웃을 지정하는 프로토콜
+/// (rtmp의 timeout은 listen 대기 시간이라 지정하면 서버 모드가 되므로 제외)
+const SOCKET_TIMEOUT_SCHEMES: [&str; 6] = ["http", "https", "tcp", "tls", "rtsp", "rtsps"];
+
+static OPEN_TIMEOUT_MS: AtomicU32 = AtomicU32::new(DEFAULT_OPEN_TIMEOUT_MS);
+static READ_TIMEOUT_MS: AtomicU32 = AtomicU32::new(DEFAULT_READ_TIMEOUT_MS);
+/// interrupt_all 호출 횟수 (입력을 열 때의 값을 콜백 opaque에 기록, 달라지면 중단)
+static INTERRUPT_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// 이 스레드에서 진행 중인 열기의 마감 시각 (인터럽트 콜백은 I/O를 수행하는 스레드에서 호출됨)
+    static OPEN_DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// URL 스킴 ("https://host/a.mp4" → "https", 로컬 경로는 None)
+/// 한 글자 스킴은 제외 (Windows 드라이브 문자 "C:/")
+pub fn url_scheme(path: &Path) -> Option<&str> {
+    let (scheme, _) = path.to_str()?.split_once("://")?;
+    let valid = scheme.len() > 1
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some(scheme)
+}
+
+/// 네트워크 URL 소스인지 (file:// 제외)
+pub fn is_url(path: &Path) -> bool {
+    url_scheme(path).is_some_and(|scheme| !scheme.eq_ignore_ascii_case("file"))
+}
+
+/// 네트워크 타임아웃 설정 (이후 여는 입력부터 적용, 0이면 기본값)
+pub fn set_timeouts(open_timeout_ms: u32, read_timeout_ms: u32) {
+    let or_default = |value: u32, default: u32| if value == 0 { default } else { value };
+    OPEN_TIMEOUT_MS.store(or_default(open_timeout_ms, DEFAULT_OPEN_TIMEOUT_MS), Ordering::Relaxed);
+    READ_TIMEOUT_MS.store(or_default(read_timeout_ms, DEFAULT_READ_TIMEOUT_MS), Ordering::Relaxed);
+}
+
+/// 현재 네트워크 타임아웃 (열기, 읽기)
+pub fn timeouts() -> (u32, u32) {
+    (OPEN_TIMEOUT_MS.load(Ordering::Relaxed), READ_TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+/// 지금까지 연 모든 네트워크 입력의 블로킹 I/O 중단
+/// 중단된 디코더는 Error 상태가 되고, Renderer는 다음 요청 때 새로 연다
+pub fn interrupt_all() {
+    INTERRUPT_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// AVIOInterruptCB 콜백 (1 반환 시 FFmpeg이 블로킹 I/O를 AVERROR_EXIT로 중단)
+extern "C" fn interrupt_callback(opaque: *mut c_void) -> c_int {
+    let generation = opaque as usize;
+    let interrupted = INTERRUPT_GENERATION.load(Ordering::Relaxed) != generation
+        || OPEN_DEADLINE.with(|deadline| deadline.get()).is_some_and(|d| Instant::now() >= d);
+    c_int::from(interrupted)
+}
+
+/// 열기 마감 설정 (drop 시 해제 → 열기 이후 읽기에는 적용 안 됨)
+struct OpenDeadline;
+
+impl OpenDeadline {
+    fn start(timeout_ms: u32) -> Self {
+        let deadline = Instant::now() + Duration::from_millis(u64::from(timeout_ms));
+        OPEN_DEADLINE.with(|d| d.set(Some(deadline)));
+        OpenDeadline
+    }
+}
+
+impl Drop for OpenDeadline {
+    fn drop(&mut self) {
+        OPEN_DEADLINE.with(|d| d.set(None));
+    }
+}
+
+/// 입력 열기 (로컬 파일은 ffmpeg::format::input 그대로, URL은 타임아웃 + 인터럽트 콜백 설정)
+pub fn open_input(path: &Path) -> Result<ffmpeg::format::context::Input, ffmpeg::Error> {
+    if !is_url(path) {
+        return ffmpeg::format::input(path);
+    }
+
+    static NETWORK_INIT: Once = Once::new();
+    NETWORK_INIT.call_once(ffmpeg::format::network::init);
+
+    let (open_timeout_ms, read_timeout_ms) = timeouts();
+    let mut options = ffmpeg::Dictionary::new();
+    options.set("rw_timeout", &(u64::from(read_timeout_ms) * 1000).to_string());
+    let scheme = url_scheme(path).unwrap_or_default().to_ascii_lowercase();
+    if SOCKET_TIMEOUT_SCHEMES.contains(&scheme.as_str()) {
+        options.set("timeout", &(u64::from(open_timeout_ms) * 1000).to_string());
+    }
+
+    let url = CString::new(path.to_string_lossy().as_bytes()).map_err(|_| ffmpeg::Error::InvalidData)?;
+    let _deadline = OpenDeadline::start(open_timeout_ms);
+
+    // SAFETY: ps는 avformat_open_input 실패 시 FFmpeg이 해제, 성공 후에는 Input이 소유
+    unsafe {
+        let mut ps = ffi::avformat_alloc_context();
+        if ps.is_null() {
+            return Err(ffmpeg::Error::Other { errno: ffmpeg::util::error::ENOMEM });
+        }
+        (*ps).interrupt_callback = ffi::AVIOInterruptCB {
+            callback: Some(interrupt_callback),
+            opaque: INTERRUPT_GENERATION.load(Ordering::SeqCst) as *mut c_void,
+        };
+
+        let mut opts = options.disown();
+        let result = ffi::avformat_open_input(&mut ps, url.as_ptr(), ptr::null_mut(), &mut opts);
+        ffmpeg::Dictionary::own(opts);
+        if result < 0 {
+            return Err(ffmpeg::Error::from(result));
+        }
+
+        let result = ffi::avformat_find_stream_info(ps, ptr::null_mut());
+        if result < 0 {
+            ffi::avformat_close_input(&mut ps);
+            return Err(ffmpeg::Error::from(result));
+        }
+        Ok(ffmpeg::format::context::Input::wrap(ps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_detection() {
+        assert_eq!(url_scheme(Path::new("https://cdn.example.com/a.mp4")), Some("https"));
+        assert!(is_url(Path::new("rtmp://live.example.com/app/stream")));
+        assert!(is_url(Path::new("HTTP://example.com/a.mp4")));
+
+        // 로컬 경로 (Windows 드라이브, file://, 스킴 없는 경로)
+        assert!(!is_url(Path::new("C://videos/a.mp4")));
+        assert!(!is_url(Path::new("file:///home/user/a.mp4")));
+        assert!(!is_url(Path::new("/home/user/a.mp4")));
+        assert!(!is_url(Path::new("my clip://x.mp4")));
+    }
+}
@@ -0,0 +1,286 @@
+// 미디어 프로브 - 컨테이너의 모든 스트림 메타데이터 조회 (디코더를 열지 않음)
+// import UI 표시용: 코덱, 해상도, 픽셀 포맷/비트 깊이, fps, 길이, 비트레이트, 채널 레이아웃, 언어, 회전
+// 회전은 display matrix side data 우선, 없으면 구형 "rotate" 메타데이터 태그
+
+use crate::ffmpeg::network;
+use crate::utils::json_escape;
+use ffmpeg_next as ffmpeg;
+use ffmpeg::ffi;
+use std::path::Path;
+
+/// 스트림 정보 (해당 없는 항목은 0 / None)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamInfo {
+    pub index: usize,
+    /// "video" / "audio" / "subtitle" / "data" / "attachment" / "unknown"
+    pub kind: &'static str,
+    pub codec: String,
+    pub width: u32,
+    pub height: u32,
+    pub pixel_format: Option<String>,
+    /// 컴포넌트당 비트 수 (8 / 10 / 12 ...)
+    pub bit_depth: u32,
+    pub fps: f64,
+    pub duration_ms: i64,
+    /// bits/s
+    pub bitrate: i64,
+    pub sample_rate: u32,
+    pub channels: u32,
+    pub channel_layout: Option<String>,
+    pub language: Option<String>,
+    /// 표시 시 시계 방향 회전 (0 / 90 / 180 / 270)
+    pub rotation_deg: i32,
+    /// 스트림 제목 (멀티 앵글/트랙 구분용 메타데이터)
+    pub title: Option<String>,
+    /// 컨테이너 기본 스트림 표시 (disposition default)
+    pub is_default: bool,
+}
+
+/// 컨테이너 정보 + 전체 스트림
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaInfo {
+    pub format: String,
+    pub duration_ms: i64,
+    /// bits/s
+    pub bitrate: i64,
+    pub streams: Vec<StreamInfo>,
+}
+
+/// display matrix (3x3, 16.16 고정소수점) → 시계 방향 회전 각도 (0~359)
+/// av_display_rotation_get은 반시계 방향 각도를 반환하므로 부호 반전
+pub fn display_matrix_rotation(matrix: &[i32; 9]) -> i32 {
+    let scale0 = (matrix[0] as f64).hypot(matrix[3] as f64);
+    let scale1 = (matrix[1] as f64).hypot(matrix[4] as f64);
+    if scale0 == 0.0 || scale1 == 0.0 {
+        return 0;
+    }
+    let ccw = -(matrix[1] as f64 / scale1).atan2(matrix[0] as f64 / scale0).to_degrees();
+    (-ccw.round() as i32).rem_euclid(360)
+}
+
+/// 스트림 회전 각도 (display matrix → "rotate" 태그 → 0)
+pub fn stream_rotation(stream: &ffmpeg::Stream) -> i32 {
+    // SAFETY: codecpar/side data는 stream 수명 동안 유효, display matrix는 i32 9개 (36바이트)
+    let matrix = unsafe {
+        let par = (*stream.as_ptr()).codecpar;
+        if par.is_null() {
+            None
+        } else {
+            let side_data = ffi::av_packet_side_data_get(
+                (*par).coded_side_data,
+                (*par).nb_coded_side_data,
+                ffi::AVPacketSideDataType::AV_PKT_DATA_DISPLAYMATRIX,
+            );
+            if side_data.is_null() || (*side_data).size < 36 {
+                None
+            } else {
+                let values = std::slice::from_raw_parts((*side_data).data as *const i32, 9);
+                <[i32; 9]>::try_from(values).ok()
+            }
+        }
+    };
+
+    match matrix {
+        Some(m) => display_matrix_rotation(&m),
+        None => stream.metadata().get("rotate")
+            .and_then(|r| r.trim().parse::<i32>().ok())
+            .map(|r| r.rem_euclid(360))
+            .unwrap_or(0),
+    }
+}
+
+/// 파일의 모든 스트림 메타데이터 조회
+pub fn probe(file_path: &Path) -> Result<MediaInfo, String> {
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+    let input_ctx = network::open_input(file_path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    let container_duration_ms = if input_ctx.duration() > 0 {
+        input_ctx.duration() / 1000
+    } else {
+        0
+    };
+
+    let streams = input_ctx.streams().map(|stream| {
+        let params = stream.parameters();
+        let kind = match params.medium() {
+            ffmpeg::media::Type::Video => "video",
+            ffmpeg::media::Type::Audio => "audio",
+            ffmpeg::media::Type::Subtitle => "subtitle",
+            ffmpeg::media::Type::Data => "data",
+            ffmpeg::media::Type::Attachment => "attachment",
+            _ => "unknown",
+        };
+
+        let duration_ms = if stream.duration() > 0 {
+            let tb = stream.time_base();
+            stream.duration() * i64::from(tb.numerator()) * 1000 / i64::from(tb.denominator()).max(1)
+        } else {
+            container_duration_ms
+        };
+
+        let mut info = StreamInfo {
+            index: stream.index(),
+            kind,
+            codec: params.id().name().to_string(),
+            duration_ms,
+            language: stream.metadata().get("language").map(|s| s.to_string()),
+            title: stream.metadata().get("title").map(|s| s.to_string()),
+            is_default: stream.disposition().contains(ffmpeg::format::stream::Disposition::DEFAULT),
+            ..Default::default()
+        };
+
+        // SAFETY: codecpar는 stream 수명 동안 유효, format 값은 FFmpeg이 기록한 enum 값
+        unsafe {
+            let par = params.as_ptr();
+            info.bitrate = (*par).bit_rate;
+
+            match kind {
+                "video" => {
+                    info.width = (*par).width.max(0) as u32;
+                    info.height = (*par).height.max(0) as u32;
+                    if (*par).format >= 0 {
+                        let pix_fmt: ffi::AVPixelFormat = std::mem::transmute((*par).format);
+                        let desc = ffi::av_pix_fmt_desc_get(pix_fmt);
+                        if !desc.is_null() {
+                            info.pixel_format = Some(
+                                std::ffi::CStr::from_ptr((*desc).name).to_string_lossy().into_owned(),
+                            );
+                            info.bit_depth = (*desc).comp[0].depth.max(0) as u32;
+                        }
+                    }
+                    let rate = stream.avg_frame_rate();
+                    let rate = if rate.numerator() > 0 && rate.denominator() > 0 { rate } else { stream.rate() };
+                    if rate.numerator() > 0 && rate.denominator() > 0 {
+                        info.fps = f64::from(rate);
+                    }
+                    info.rotation_deg = stream_rotation(&stream);
+                }
+                "audio" => {
+                    info.sample_rate = (*par).sample_rate.max(0) as u32;
+                    info.channels = (*par).ch_layout.nb_channels.max(0) as u32;
+                    let mut buf = [0 as std::ffi::c_char; 64];
+                    if ffi::av_channel_layout_describe(&(*par).ch_layout, buf.as_mut_ptr(), buf.len()) > 0 {
+                        info.channel_layout = Some(
+                            std::ffi::CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned(),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        info
+    }).collect();
+
+    Ok(MediaInfo {
+        format: input_ctx.format().name().to_string(),
+        duration_ms: container_duration_ms,
+        bitrate: input_ctx.bit_rate(),
+        streams,
+    })
+}
+
+/// 선택 문자열 → JSON 값 (없으면 null)
+fn json_opt_string(value: &Option<String>) -> String {
+    match value {
+        Some(s) => format!("\"{}\"", json_escape(s)),
+        None => "null".to_string(),
+    }
+}
+
+impl StreamInfo {
+    /// JSON 객체 (모든 필드 포함, 해당 없는 문자열은 null)
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"index\":{},\"type\":\"{}\",\"codec\":\"{}\",\"width\":{},\"height\":{},\"pixel_format\":{},\"bit_depth\":{},\"fps\":{},\"duration_ms\":{},\"bitrate\":{},\"sample_rate\":{},\"channels\":{},\"channel_layout\":{},\"language\":{},\"rotation\":{},\"title\":{},\"default\":{}}}",
+            self.index,
+            self.kind,
+            json_escape(&self.codec),
+            self.width,
+            self.height,
+            json_opt_string(&self.pixel_format),
+            self.bit_depth,
+            if self.fps.is_finite() { self.fps } else { 0.0 },
+            self.duration_ms,
+            self.bitrate,
+            self.sample_rate,
+            self.channels,
+            json_opt_string(&self.channel_layout),
+            json_opt_string(&self.language),
+            self.rotation_deg,
+            json_opt_string(&self.title),
+            self.is_default,
+        )
+    }
+}
+
+impl MediaInfo {
+    /// JSON 객체 {"format":..,"duration_ms":..,"bitrate":..,"streams":[..]}
+    pub fn to_json(&self) -> String {
+        let streams: Vec<String> = self.streams.iter().map(|s| s.to_json()).collect();
+        format!(
+            "{{\"format\":\"{}\",\"duration_ms\":{},\"bitrate\":{},\"streams\":[{}]}}",
+            json_escape(&self.format),
+            self.duration_ms,
+            self.bitrate,
+            streams.join(","),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_matrix_rotation() {
+        const ONE: i32 = 1 << 16;
+        let w = 1 << 30;
+        assert_eq!(display_matrix_rotation(&[ONE, 0, 0, 0, ONE, 0, 0, 0, w]), 0);
+        // 세로 촬영 폰 영상 (ffprobe rotation=-90) → 시계 방향 90도
+        assert_eq!(display_matrix_rotation(&[0, ONE, 0, -ONE, 0, 0, 0, 0, w]), 90);
+        assert_eq!(display_matrix_rotation(&[-ONE, 0, 0, 0, -ONE, 0, 0, 0, w]), 180);
+        assert_eq!(display_matrix_rotation(&[0, -ONE, 0, ONE, 0, 0, 0, 0, w]), 270);
+        assert_eq!(display_matrix_rotation(&[0; 9]), 0);
+    }
+
+    #[test]
+    fn test_media_info_json() {
+        let info = MediaInfo {
+            format: "mov,mp4".to_string(),
+            duration_ms: 1000,
+            bitrate: 8000000,
+            streams: vec![
+                StreamInfo {
+                    kind: "video",
+                    codec: "h264".to_string(),
+                    width: 1920,
+                    height: 1080,
+                    pixel_format: Some("yuv420p".to_string()),
+                    bit_depth: 8,
+                    fps: 30.0,
+                    rotation_deg: 90,
+                    is_default: true,
+                    ..Default::default()
+                },
+                StreamInfo {
+                    index: 1,
+                    kind: "audio",
+                    codec: "aac".to_string(),
+                    sample_rate: 48000,
+                    channels: 2,
+                    channel_layout: Some("stereo".to_string()),
+                    language: Some("kor".to_string()),
+                    title: Some("Commentary".to_string()),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let json = info.to_json();
+        assert!(json.starts_with("{\"format\":\"mov,mp4\",\"duration_ms\":1000,\"bitrate\":8000000,\"streams\":[{\"index\":0,\"type\":\"video\",\"codec\":\"h264\",\"width\":1920,\"height\":1080,\"pixel_format\":\"yuv420p\",\"bit_depth\":8,\"fps\":30,"));
+        assert!(json.contains("\"language\":null,\"rotation\":90,\"title\":null,\"default\":true}"));
+        assert!(json.ends_with("\"channel_layout\":\"stereo\",\"language\":\"kor\",\"rotation\":0,\"title\":\"Commentary\",\"default\":false}]}"));
+    }
+}
@@ -9,6 +9,7 @@ pub mod encoding;
 pub mod subtitle;
 pub mod utils;
 pub mod audio;
+pub mod playback;
 
 // FFI 함수들을 최상위에서 재export
 pub use ffi::*;
@@ -0,0 +1,154 @@
+// 재생 프레임 클럭 - 기준 시각과 배속으로 타임라인 위치 계산
+// 누적 덧셈 대신 (기준 위치 + 경과 시간 × 배속) → 장시간 재생에도 오차 누적 없음
+// 프레임 번호는 프로젝트 fps 격자 (timeline::frame_to_ms — Export/프레임 스텝과 동일)
+// 배속이 음수면 역방향 진행 (J-K-L 셔틀)
+
+use crate::timeline::{frame_to_ms, ms_to_frame};
+use std::time::{Duration, Instant};
+
+/// 기본 fps (타임라인 fps가 0/NaN일 때)
+const FALLBACK_FPS: f64 = 30.0;
+
+/// 재생 클럭 (시각은 호출 측이 전달 → 테스트에서 시간 고정 가능)
+#[derive(Debug, Clone)]
+pub struct FrameClock {
+    fps: f64,
+    rate: f64,
+    /// 기준 위치 (타임라인 ms)
+    origin_ms: f64,
+    /// 재생 중이면 기준 시각, 정지 중이면 None
+    started: Option<Instant>,
+}
+
+impl FrameClock {
+    pub fn new(fps: f64) -> Self {
+        Self { fps: valid_fps(fps), rate: 1.0, origin_ms: 0.0, started: None }
+    }
+
+    pub fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// fps 변경 (위치 유지)
+    pub fn set_fps(&mut self, fps: f64) {
+        self.fps = valid_fps(fps);
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// now 시점의 타임라인 위치 (ms)
+    pub fn position_ms(&self, now: Instant) -> f64 {
+        match self.started {
+            Some(started) => self.origin_ms + now.saturating_duration_since(started).as_secs_f64() * 1000.0 * self.rate,
+            None => self.origin_ms,
+        }
+    }
+
+    /// 진행 시작 (이미 진행 중이면 무시)
+    pub fn start(&mut self, now: Instant) {
+        if self.started.is_none() {
+            self.started = Some(now);
+        }
+    }
+
+    /// 현재 위치에서 멈춤
+    pub fn stop(&mut self, now: Instant) {
+        self.origin_ms = self.position_ms(now);
+        self.started = None;
+    }
+
+    /// 위치 이동 (진행 상태 유지)
+    pub fn seek(&mut self, position_ms: f64, now: Instant) {
+        self.origin_ms = position_ms;
+        if self.started.is_some() {
+            self.started = Some(now);
+        }
+    }
+
+    /// 배속 변경 (현재 위치 기준으로 이후 진행 속도만 변경)
+    pub fn set_rate(&mut self, rate: f64, now: Instant) {
+        self.seek(self.position_ms(now), now);
+        self.rate = rate;
+    }
+
+    /// 위치 → 프레임 번호
+    pub fn frame_at(&self, position_ms: f64) -> i64 {
+        ms_to_frame(position_ms.floor() as i64, self.fps)
+    }
+
+    /// 프레임 번호 → 프레임 시작 시간 (ms)
+    pub fn frame_time_ms(&self, index: i64) -> i64 {
+        frame_to_ms(index, self.fps)
+    }
+
+    /// now부터 index번 프레임 표시 시작까지 남은 실제 시간 (정지 중이거나 이미 지났으면 0)
+    /// 정방향은 프레임 시작, 역방향은 프레임 끝(= 다음 프레임 시작) 경계에 도달하는 시점
+    pub fn time_until_frame(&self, index: i64, now: Instant) -> Duration {
+        if self.started.is_none() || self.rate == 0.0 {
+            return Duration::ZERO;
+        }
+        let boundary = if self.rate > 0.0 { index } else { index + 1 };
+        let remaining_ms = (frame_to_ms(boundary, self.fps) as f64 - self.position_ms(now)) / self.rate;
+        Duration::from_secs_f64((remaining_ms / 1000.0).max(0.0))
+    }
+}
+
+fn valid_fps(fps: f64) -> f64 {
+    if fps.is_finite() && fps > 0.0 { fps } else { FALLBACK_FPS }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_position_rate_and_frames() {
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        let mut clock = FrameClock::new(30.0);
+
+        // 정지 중에는 시간이 흘러도 위치 고정
+        assert_eq!(clock.position_ms(at(500)), 0.0);
+        clock.start(t0);
+        assert!((clock.position_ms(at(1000)) - 1000.0).abs() < 1e-6);
+
+        // 2배속: 변경 시점 위치부터 2배로 진행
+        clock.set_rate(2.0, at(1000));
+        assert!((clock.position_ms(at(1500)) - 2000.0).abs() < 1e-6);
+
+        clock.stop(at(1500));
+        assert!((clock.position_ms(at(9000)) - 2000.0).abs() < 1e-6);
+        clock.seek(100.0, at(9000));
+        assert_eq!(clock.position_ms(at(9999)), 100.0);
+
+        // 프레임 격자: 프레임 시작 시간(ms 내림)은 같은 프레임
+        assert_eq!(clock.frame_time_ms(1), 33);
+        assert_eq!(clock.frame_at(33.0), 1);
+        assert_eq!(clock.frame_at(32.0), 0);
+        assert_eq!(clock.frame_at(clock.frame_time_ms(299) as f64), 299);
+
+        // 다음 프레임까지 대기 시간 (2배속이면 실제 시간은 절반)
+        clock.seek(0.0, t0);
+        clock.start(t0);
+        let wait = clock.time_until_frame(1, t0);
+        assert!((wait.as_secs_f64() * 1000.0 - 16.5).abs() < 0.01);
+        assert_eq!(clock.time_until_frame(0, at(10)), Duration::ZERO);
+
+        // 역방향: 위치가 줄어들고, 이전 프레임은 현재 프레임 시작 경계에서 표시
+        clock.seek(1000.0, t0);
+        clock.set_rate(-1.0, t0);
+        assert!((clock.position_ms(at(100)) - 900.0).abs() < 1e-6);
+        assert_eq!(clock.frame_at(1000.0), 30);
+        let wait = clock.time_until_frame(29, t0);
+        assert!((wait.as_secs_f64() * 1000.0).abs() < 0.01);
+        // 프레임 29 시작 = 966ms → 34ms 뒤
+        let wait = clock.time_until_frame(28, t0);
+        assert!((wait.as_secs_f64() * 1000.0 - 34.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,547 @@
+// 재생 컨트롤러 - 엔진 프레임 클럭으로 재생 타이밍 구동 (호스트 UI 타이머 지터 배제)
+// 워커 스레드가 프레임 경계마다 렌더링 → 트리플 버퍼 게시 + 프레임 콜백
+// 렌더링이 밀리면 클럭 기준 현재 프레임으로 건너뜀 (늦은 프레임은 그리지 않고 드롭으로 집계)
+// 재생/정지에 맞춰 Renderer 재생 모드 전환 → 미리 읽기 워커/forward decode 구동
+// 오디오 클럭이 연결되면 1배속 재생 중 비디오 클럭을 오디오 위치에 종속 (sync 모듈)
+// 음수 배속 = 역방향 재생 (Renderer 역방향 디코딩 모드, 0ms에서 끝)
+
+use super::clock::FrameClock;
+use super::sync::{AudioClock, AvSync, SyncStats};
+use super::triple_buffer::TripleBuffer;
+use crate::rendering::{Renderer, RenderedFrame};
+use crate::timeline::Timeline;
+use crate::utils::log::log_error;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 최대 배속 (절댓값, 역방향도 같은 한도)
+pub const MAX_PLAYBACK_RATE: f64 = 16.0;
+
+/// 정지 중 타임라인 변경(fps/길이) 확인 주기
+const IDLE_POLL: Duration = Duration::from_millis(100);
+
+/// 재생 상태 (FFI i32 매핑)
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackState {
+    Paused = 0,
+    Playing = 1,
+    /// 타임라인 끝(역방향이면 처음)에 도달해 멈춤 (play 시 반대쪽 끝부터)
+    Ended = 2,
+}
+
+/// Renderer 디코딩 모드 (워커가 바뀔 때만 Renderer에 적용)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DecodeMode {
+    /// 정지/스크럽: 즉시 seek
+    Scrub,
+    /// 정방향 재생: forward decode + 미리 읽기
+    Forward,
+    /// 역방향 재생: GOP 단위 역방향 디코딩
+    Reverse,
+}
+
+/// 프레임 전달 콜백 (워커 스레드에서 호출, 인자: 프레임, 프레임 번호)
+pub type FrameSink = Box<dyn FnMut(&RenderedFrame, i64) + Send>;
+
+/// 재생 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlaybackStats {
+    /// 표시(게시)한 프레임 수
+    pub presented_frames: u64,
+    /// 렌더링이 늦어 건너뛴 프레임 수
+    pub dropped_frames: u64,
+}
+
+/// 명령으로 바뀌는 재생 상태
+struct Control {
+    clock: FrameClock,
+    state: PlaybackState,
+    /// 아직 표시하지 않은 seek 프레임 (정지 중에도 1회 렌더링)
+    pending_seek: Option<i64>,
+    /// seek마다 증가 (렌더링 중에 seek되면 결과 폐기)
+    generation: u64,
+    /// 명령마다 증가 (워커가 대기 직전 변경 여부 확인 → 알림 유실 방지)
+    revision: u64,
+    /// 마스터 오디오 클럭 (None이면 비디오 자체 클럭)
+    audio_clock: Option<Arc<AudioClock>>,
+    sync: AvSync,
+    /// 마지막 seek 시각 (이전 오디오 보고는 seek 전 위치라 무시)
+    sync_since: Instant,
+    /// 워커가 마지막으로 확인한 타임라인 길이 (역방향 재생을 끝에서 시작할 때 사용)
+    duration_ms: i64,
+}
+
+impl Control {
+    fn touch(&mut self) {
+        self.revision += 1;
+    }
+}
+
+/// 워커가 다음에 할 일
+enum Step {
+    Render { index: i64, generation: u64 },
+    /// 대기 (None: 명령이 올 때까지)
+    Wait { timeout: Option<Duration>, revision: u64 },
+}
+
+struct Shared {
+    control: Mutex<Control>,
+    wake: Condvar,
+    stopped: AtomicBool,
+    frames: TripleBuffer<RenderedFrame>,
+    presented: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl Shared {
+    /// 현재 클럭 기준 다음 할 일 결정 (반환: 할 일, Renderer 디코딩 모드)
+    fn next_step(&self, fps: f64, duration_ms: i64, last_index: Option<i64>) -> Option<(Step, DecodeMode)> {
+        let mut control = self.control.lock().ok()?;
+        let now = Instant::now();
+        control.clock.set_fps(fps);
+        control.duration_ms = duration_ms;
+        let reverse = control.clock.rate() < 0.0;
+        let mode = match control.state {
+            PlaybackState::Playing if reverse => DecodeMode::Reverse,
+            PlaybackState::Playing => DecodeMode::Forward,
+            _ => DecodeMode::Scrub,
+        };
+
+        if let Some(index) = control.pending_seek.take() {
+            return Some((Step::Render { index, generation: control.generation }, mode));
+        }
+        if mode == DecodeMode::Scrub {
+            return Some((Step::Wait { timeout: Some(IDLE_POLL), revision: control.revision }, mode));
+        }
+
+        let mut position_ms = control.clock.position_ms(now);
+        // 오디오가 같은 속도로 나오는 1배속에서만 오디오 클럭 종속
+        let audio_ms = control.audio_clock.as_ref()
+            .filter(|_| control.clock.rate() == 1.0)
+            .and_then(|clock| clock.position_ms(now, control.sync_since));
+        if let Some(audio_ms) = audio_ms {
+            let correction = control.sync.correction(position_ms, audio_ms);
+            if correction != 0.0 {
+                position_ms = (position_ms + correction).max(0.0);
+                control.clock.seek(position_ms, now);
+            }
+        }
+
+        let ended = if reverse { position_ms <= 0.0 } else { position_ms >= duration_ms as f64 };
+        if ended {
+            // 끝 도달 → 끝 위치에서 멈춤
+            let end_ms = if reverse { 0 } else { duration_ms.max(0) };
+            control.clock.stop(now);
+            control.clock.seek(end_ms as f64, now);
+            control.state = PlaybackState::Ended;
+            control.touch();
+            return Some((Step::Wait { timeout: None, revision: control.revision }, DecodeMode::Scrub));
+        }
+
+        let index = control.clock.frame_at(position_ms);
+        if last_index == Some(index) {
+            let next = if reverse { index - 1 } else { index + 1 };
+            let timeout = control.clock.time_until_frame(next, now);
+            return Some((Step::Wait { timeout: Some(timeout), revision: control.revision }, mode));
+        }
+        Some((Step::Render { index, generation: control.generation }, mode))
+    }
+
+    /// 명령 또는 timeout까지 대기 (대기 전 revision이 바뀌었으면 즉시 반환)
+    fn wait(&self, timeout: Option<Duration>, revision: u64) {
+        let control = match self.control.lock() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        if control.revision != revision || self.stopped.load(Ordering::SeqCst) {
+            return;
+        }
+        match timeout {
+            Some(timeout) => {
+                drop(self.wake.wait_timeout(control, timeout));
+            }
+            None => {
+                drop(self.wake.wait(control));
+            }
+        }
+    }
+
+    fn generation(&self) -> Option<u64> {
+        self.control.lock().ok().map(|c| c.generation)
+    }
+
+    fn frame_time_ms(&self, index: i64) -> i64 {
+        match self.control.lock() {
+            Ok(c) => c.clock.frame_time_ms(index),
+            Err(_) => 0,
+        }
+    }
+}
+
+/// 재생 컨트롤러 (drop 시 워커 종료 대기 → 이후 콜백 호출 없음)
+pub struct PlaybackController {
+    shared: Arc<Shared>,
+    /// 읽는 쪽 버퍼 (트리플 버퍼 front)
+    front: Mutex<Option<RenderedFrame>>,
+    /// 호스트가 위치를 직접 보고하는 오디오 클럭 (pull 방식 오디오 엔진용)
+    host_audio_clock: Arc<AudioClock>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl PlaybackController {
+    /// 워커 시작 (정지 상태, 0ms) - Renderer가 해제되면 워커도 종료
+    pub fn start(renderer: Weak<Mutex<Renderer>>, timeline: Arc<Mutex<Timeline>>, sink: Option<FrameSink>) -> Self {
+        let fps = timeline.lock().map(|t| t.fps).unwrap_or(0.0);
+        let shared = Arc::new(Shared {
+            control: Mutex::new(Control {
+                clock: FrameClock::new(fps),
+                state: PlaybackState::Paused,
+                pending_seek: None,
+                generation: 0,
+                revision: 0,
+                audio_clock: None,
+                sync: AvSync::new(),
+                sync_since: Instant::now(),
+                duration_ms: 0,
+            }),
+            wake: Condvar::new(),
+            stopped: AtomicBool::new(false),
+            frames: TripleBuffer::new(),
+            presented: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        });
+        let worker_shared = shared.clone();
+        let worker = std::thread::spawn(move || run_worker(&worker_shared, &renderer, &timeline, sink));
+        Self {
+            shared,
+            front: Mutex::new(None),
+            host_audio_clock: Arc::new(AudioClock::new()),
+            worker: Some(worker),
+        }
+    }
+
+    /// 명령 적용 후 워커 깨우기
+    fn command(&self, f: impl FnOnce(&mut Control, Instant)) {
+        if let Ok(mut control) = self.shared.control.lock() {
+            f(&mut control, Instant::now());
+            control.touch();
+            self.shared.wake.notify_all();
+        }
+    }
+
+    /// 재생 (끝에서 멈춘 상태면 반대쪽 끝부터 — 정방향은 처음, 역방향은 끝)
+    pub fn play(&self) {
+        self.command(|c, now| {
+            if c.state == PlaybackState::Ended {
+                let start_ms = if c.clock.rate() < 0.0 { c.duration_ms } else { 0 };
+                c.clock.seek(start_ms as f64, now);
+                c.sync_since = now;
+            }
+            c.state = PlaybackState::Playing;
+            c.clock.start(now);
+        });
+    }
+
+    /// 일시정지 (현재 위치 유지)
+    pub fn pause(&self) {
+        self.command(|c, now| {
+            if c.state == PlaybackState::Playing {
+                c.state = PlaybackState::Paused;
+            }
+            c.clock.stop(now);
+        });
+    }
+
+    /// 위치 이동 (재생 상태 유지, 정지 중이면 해당 프레임 1회 렌더링)
+    pub fn seek(&self, timestamp_ms: i64) {
+        self.command(|c, now| {
+            let timestamp_ms = timestamp_ms.max(0);
+            c.clock.seek(timestamp_ms as f64, now);
+            c.pending_seek = Some(c.clock.frame_at(timestamp_ms as f64));
+            c.generation += 1;
+            c.sync_since = now;
+            if c.state == PlaybackState::Ended {
+                c.state = PlaybackState::Paused;
+            }
+        });
+    }
+
+    /// 배속 설정 (0 < |rate| ≤ MAX_PLAYBACK_RATE, 음수 = 역방향, 현재 위치 기준)
+    pub fn set_rate(&self, rate: f64) -> Result<(), String> {
+        if !(rate != 0.0 && rate.abs() <= MAX_PLAYBACK_RATE) {
+            return Err(format!("Playback rate must be non-zero within ±{}: {}", MAX_PLAYBACK_RATE, rate));
+        }
+        self.command(|c, now| c.clock.set_rate(rate, now));
+        Ok(())
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.shared.control.lock().map(|c| c.clock.rate()).unwrap_or(1.0)
+    }
+
+    pub fn state(&self) -> PlaybackState {
+        self.shared.control.lock().map(|c| c.state).unwrap_or(PlaybackState::Paused)
+    }
+
+    /// 현재 클럭 위치 (ms)
+    pub fn position_ms(&self) -> i64 {
+        self.shared.control.lock()
+            .map(|c| c.clock.position_ms(Instant::now()).round() as i64)
+            .unwrap_or(0)
+    }
+
+    /// 최신 표시 프레임 (트리플 버퍼 교환, 새 프레임이 없으면 None)
+    pub fn acquire_frame(&self) -> Option<RenderedFrame> {
+        let mut front = self.front.lock().ok()?;
+        if self.shared.frames.acquire(&mut front) {
+            front.clone()
+        } else {
+            None
+        }
+    }
+
+    /// 마스터 오디오 클럭 연결 (None: 해제 → 비디오 자체 클럭)
+    pub fn set_audio_clock(&self, clock: Option<Arc<AudioClock>>) {
+        self.command(|c, _| c.audio_clock = clock);
+    }
+
+    /// 호스트 오디오 출력 위치 보고 (지금 들리는 타임라인 위치, 장치 지연 반영)
+    /// 보고하면 호스트 클럭이 마스터 오디오 클럭이 됨
+    pub fn report_audio_position(&self, position_ms: f64, running: bool) {
+        self.host_audio_clock.update(position_ms, running, Instant::now());
+        let attached = self.shared.control.lock()
+            .map(|c| c.audio_clock.as_ref().is_some_and(|a| Arc::ptr_eq(a, &self.host_audio_clock)))
+            .unwrap_or(true);
+        if !attached {
+            self.set_audio_clock(Some(self.host_audio_clock.clone()));
+        }
+    }
+
+    /// A/V 동기화 통계 (오디오 클럭이 없으면 측정 없음)
+    pub fn sync_stats(&self) -> SyncStats {
+        self.shared.control.lock().map(|c| c.sync.stats()).unwrap_or_default()
+    }
+
+    pub fn stats(&self) -> PlaybackStats {
+        PlaybackStats {
+            presented_frames: self.shared.presented.load(Ordering::Relaxed),
+            dropped_frames: self.shared.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Drop for PlaybackController {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::SeqCst);
+        if let Ok(_control) = self.shared.control.lock() {
+            self.shared.wake.notify_all();
+        }
+        if let Some(worker) = self.worker.take() {
+            // 콜백 안에서 해제된 경우 자기 자신은 join하지 않음 (루프는 stopped로 종료)
+            if worker.thread().id() != std::thread::current().id() {
+                let _ = worker.join();
+            }
+        }
+    }
+}
+
+/// 워커 루프 (클럭 프레임마다 Renderer lock → 렌더링 → 게시)
+fn run_worker(
+    shared: &Shared,
+    renderer: &Weak<Mutex<Renderer>>,
+    timeline: &Mutex<Timeline>,
+    mut sink: Option<FrameSink>,
+) {
+    let mut last_index: Option<i64> = None;
+    let mut last_generation = 0;
+    let mut renderer_mode = DecodeMode::Scrub;
+
+    while !shared.stopped.load(Ordering::SeqCst) {
+        let (fps, duration_ms) = match timeline.lock() {
+            Ok(t) => (t.fps, t.duration_ms()),
+            Err(_) => break,
+        };
+        let (step, mode) = match shared.next_step(fps, duration_ms, last_index) {
+            Some(next) => next,
+            None => break,
+        };
+        let renderer = match renderer.upgrade() {
+            Some(renderer) => renderer,
+            None => break,
+        };
+        if mode != renderer_mode {
+            if let Ok(mut r) = renderer.lock() {
+                apply_decode_mode(&mut r, mode);
+            }
+            renderer_mode = mode;
+        }
+
+        let (index, generation) = match step {
+            Step::Render { index, generation } => (index, generation),
+            Step::Wait { timeout, revision } => {
+                drop(renderer);
+                shared.wait(timeout, revision);
+                continue;
+            }
+        };
+
+        let timestamp_ms = shared.frame_time_ms(index);
+        let result = match renderer.lock() {
+            Ok(mut r) => r.render_frame(timestamp_ms),
+            Err(_) => break,
+        };
+        drop(renderer);
+
+        // 렌더링 중에 seek → 이전 위치 프레임은 표시하지 않음
+        if shared.generation() != Some(generation) {
+            continue;
+        }
+        match result {
+            Ok(mut frame) => {
+                frame.timestamp_ms = timestamp_ms;
+                // 같은 구간 연속 재생에서 건너뛴 프레임 = 드롭
+                if let Some(last) = last_index.filter(|_| generation == last_generation && mode != DecodeMode::Scrub) {
+                    let skipped = (index - last).unsigned_abs().saturating_sub(1);
+                    shared.dropped.fetch_add(skipped, Ordering::Relaxed);
+                }
+                if let Some(sink) = sink.as_mut() {
+                    sink(&frame, index);
+                }
+                // 돌려받은 이전 버퍼는 다른 쪽과 공유 중일 수 있으므로 재사용하지 않고 해제
+                let mut back = Some(frame);
+                shared.frames.publish(&mut back);
+                shared.presented.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => log_error!("[PLAYBACK] render error at {}ms: {}", timestamp_ms, e),
+        }
+        last_index = Some(index);
+        last_generation = generation;
+    }
+
+    if renderer_mode != DecodeMode::Scrub {
+        if let Some(renderer) = renderer.upgrade() {
+            if let Ok(mut r) = renderer.lock() {
+                apply_decode_mode(&mut r, DecodeMode::Scrub);
+            }
+        }
+    }
+}
+
+fn apply_decode_mode(renderer: &mut Renderer, mode: DecodeMode) {
+    renderer.set_reverse_playback(mode == DecodeMode::Reverse);
+    renderer.set_playback_mode(mode != DecodeMode::Scrub);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 렌더링 결과가 배경색인 클립 1개짜리 타임라인 (파일 없음 → fallback 프레임)
+    fn timeline_with_duration(duration_ms: i64) -> Arc<Mutex<Timeline>> {
+        let mut timeline = Timeline::new(64, 36, 30.0);
+        let track_id = timeline.add_video_track();
+        timeline.add_video_clip(track_id, "missing.mp4".into(), 0, duration_ms);
+        Arc::new(Mutex::new(timeline))
+    }
+
+    fn wait_for(condition: impl Fn() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if condition() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    #[test]
+    fn test_seek_while_paused_renders_once() {
+        let timeline = timeline_with_duration(1000);
+        let renderer = Arc::new(Mutex::new(Renderer::new(timeline.clone())));
+        let controller = PlaybackController::start(Arc::downgrade(&renderer), timeline, None);
+
+        controller.seek(250);
+        assert!(wait_for(|| controller.stats().presented_frames == 1));
+        // 프레임 격자에 맞춘 시간 (30fps 7번 프레임 = 233ms)
+        let frame = controller.acquire_frame().expect("seek frame");
+        assert_eq!(frame.timestamp_ms, 233);
+        assert!(controller.acquire_frame().is_none());
+        assert_eq!(controller.state(), PlaybackState::Paused);
+        assert_eq!(controller.position_ms(), 250);
+    }
+
+    #[test]
+    fn test_reverse_play_presents_decreasing_frames_until_start() {
+        let timeline = timeline_with_duration(1000);
+        let renderer = Arc::new(Mutex::new(Renderer::new(timeline.clone())));
+        let indices = Arc::new(Mutex::new(Vec::new()));
+        let sink_indices = indices.clone();
+        let sink: FrameSink = Box::new(move |_, index| sink_indices.lock().unwrap().push(index));
+        let controller = PlaybackController::start(Arc::downgrade(&renderer), timeline, Some(sink));
+
+        controller.seek(300);
+        assert!(controller.set_rate(-2.0).is_ok());
+        controller.play();
+        assert!(wait_for(|| controller.state() == PlaybackState::Ended));
+        assert_eq!(controller.position_ms(), 0);
+        assert!(!renderer.lock().unwrap().reverse_playback());
+
+        // seek 프레임(9) 이후 역방향으로 감소
+        let indices = indices.lock().unwrap().clone();
+        assert_eq!(indices.first(), Some(&9));
+        assert!(indices.len() > 1);
+        assert!(indices.windows(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_video_clock_follows_audio_clock() {
+        let timeline = timeline_with_duration(10_000);
+        let renderer = Arc::new(Mutex::new(Renderer::new(timeline.clone())));
+        let controller = PlaybackController::start(Arc::downgrade(&renderer), timeline, None);
+
+        // 오디오가 2초 앞에서 재생 중 → 비디오가 즉시 맞춤
+        let audio_start = Instant::now();
+        controller.play();
+        let synced = wait_for(|| {
+            let audio_ms = 2000.0 + audio_start.elapsed().as_secs_f64() * 1000.0;
+            controller.report_audio_position(audio_ms, true);
+            (controller.position_ms() as f64 - audio_ms).abs() < 50.0
+        });
+        assert!(synced);
+        let stats = controller.sync_stats();
+        assert!(stats.resyncs >= 1);
+        assert!(stats.max_drift_ms >= 1900.0);
+    }
+
+    #[test]
+    fn test_play_presents_increasing_frames_until_end() {
+        let timeline = timeline_with_duration(300);
+        let renderer = Arc::new(Mutex::new(Renderer::new(timeline.clone())));
+        let indices = Arc::new(Mutex::new(Vec::new()));
+        let sink_indices = indices.clone();
+        let sink: FrameSink = Box::new(move |_, index| sink_indices.lock().unwrap().push(index));
+        let controller = PlaybackController::start(Arc::downgrade(&renderer), timeline, Some(sink));
+
+        assert!(controller.set_rate(0.0).is_err());
+        assert!(controller.set_rate(-17.0).is_err());
+        assert!(controller.set_rate(2.0).is_ok());
+        controller.play();
+        assert!(wait_for(|| controller.state() == PlaybackState::Ended));
+        assert_eq!(controller.position_ms(), 300);
+
+        let indices = indices.lock().unwrap().clone();
+        assert!(!indices.is_empty());
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        // 표시 + 드롭 = 재생한 프레임 구간
+        let stats = controller.stats();
+        assert_eq!(stats.presented_frames, indices.len() as u64);
+        assert_eq!(
+            stats.presented_frames + stats.dropped_frames,
+            (indices.last().unwrap() - indices.first().unwrap() + 1) as u64
+        );
+    }
+}
@@ -0,0 +1,14 @@
+// 재생 모듈 - 엔진 내부 프레임 클럭 기반 프리뷰 재생
+// clock: 배속/seek 가능한 프레임 클럭, triple_buffer: 호스트와 최신 프레임 교환
+// controller: play/pause/seek/set_rate + 늦은 프레임 드롭 + 프레임 콜백
+// sync: 오디오 클럭 기준 A/V 동기화 (drift 보정/통계)
+
+pub mod clock;
+pub mod triple_buffer;
+pub mod controller;
+pub mod sync;
+
+pub use clock::FrameClock;
+pub use controller::{FrameSink, PlaybackController, PlaybackState, PlaybackStats};
+pub use sync::{AudioClock, AvSync, SyncStats};
+pub use triple_buffer::TripleBuffer;
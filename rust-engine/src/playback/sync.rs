@@ -0,0 +1,167 @@
+// A/V 동기화 - 프리뷰 재생의 비디오 프레임 선택을 오디오 클럭에 종속
+// 오디오 출력이 실제로 내보내는 타임라인 위치를 AudioClock에 보고 → 비디오 클럭과 차이(drift) 측정
+// 작은 차이는 점진 보정 (프레임 반복/건너뜀 없이 수렴), 큰 차이(seek/언더런 후)는 즉시 맞춤
+// Export는 같은 타임라인 시간으로 오디오/비디오를 만들므로 오디오 기준 프리뷰 = Export 립싱크
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 이 이하 차이는 무시 (1ms 단위 반올림/콜백 지터)
+const SYNC_TOLERANCE_MS: f64 = 10.0;
+
+/// 이 이상 벌어지면 즉시 오디오 위치로 맞춤
+const RESYNC_THRESHOLD_MS: f64 = 200.0;
+
+/// 한 번에 보정하는 drift 비율 (프레임마다 적용 → 약 1초에 걸쳐 수렴)
+const CORRECTION_GAIN: f64 = 0.1;
+
+/// 보고가 이보다 오래되면 오디오 클럭 무효 (출력 정지/언더런 → 비디오 자체 클럭으로 진행)
+const STALE_AFTER: Duration = Duration::from_millis(500);
+
+/// 오디오 출력 위치 보고 (출력 스레드가 쓰고 재생 워커가 읽음)
+#[derive(Debug, Clone, Copy)]
+struct AudioReport {
+    /// 보고 시점에 들리는 타임라인 위치 (ms)
+    position_ms: f64,
+    updated: Instant,
+    running: bool,
+}
+
+/// 오디오 마스터 클럭
+pub struct AudioClock {
+    report: Mutex<Option<AudioReport>>,
+}
+
+impl AudioClock {
+    pub fn new() -> Self {
+        Self { report: Mutex::new(None) }
+    }
+
+    /// now 시점에 들리는 위치 보고 (출력 장치 지연은 보고 측이 빼서 전달)
+    /// 오디오 콜백에서는 update_nonblocking 사용
+    pub fn update(&self, position_ms: f64, running: bool, now: Instant) {
+        if let Ok(mut report) = self.report.lock() {
+            *report = Some(AudioReport { position_ms, updated: now, running });
+        }
+    }
+
+    /// 오디오 콜백용 (lock 경합 시 이번 보고는 건너뜀 — 오디오 스레드는 블로킹 금지)
+    pub fn update_nonblocking(&self, position_ms: f64, running: bool, now: Instant) {
+        if let Ok(mut report) = self.report.try_lock() {
+            *report = Some(AudioReport { position_ms, updated: now, running });
+        }
+    }
+
+    /// 진행 중지 (일시정지 → 외삽 중단)
+    pub fn set_running(&self, running: bool) {
+        if let Ok(mut report) = self.report.lock() {
+            if let Some(report) = report.as_mut() {
+                report.running = running;
+            }
+        }
+    }
+
+    /// now 시점 오디오 위치 (마지막 보고에서 1배속 외삽)
+    /// None: 보고 없음 / since 이전 보고 (seek 전 위치) / 오래된 보고 / 정지 중
+    pub fn position_ms(&self, now: Instant, since: Instant) -> Option<f64> {
+        let report = (*self.report.lock().ok()?)?;
+        if !report.running || report.updated < since {
+            return None;
+        }
+        let elapsed = now.checked_duration_since(report.updated).unwrap_or(Duration::ZERO);
+        if elapsed > STALE_AFTER {
+            return None;
+        }
+        Some(report.position_ms + elapsed.as_secs_f64() * 1000.0)
+    }
+}
+
+impl Default for AudioClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 동기화 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SyncStats {
+    /// 마지막 측정 drift (비디오 - 오디오, ms, 양수 = 비디오가 앞섬)
+    pub drift_ms: f64,
+    /// 측정한 drift 절댓값 최대
+    pub max_drift_ms: f64,
+    /// 점진 보정 횟수
+    pub corrections: u64,
+    /// 즉시 맞춤 횟수
+    pub resyncs: u64,
+}
+
+/// drift 측정/보정량 계산
+#[derive(Debug, Default)]
+pub struct AvSync {
+    stats: SyncStats,
+}
+
+impl AvSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 비디오/오디오 위치 비교 → 비디오 클럭에 더할 보정량 (ms)
+    pub fn correction(&mut self, video_ms: f64, audio_ms: f64) -> f64 {
+        let drift = video_ms - audio_ms;
+        self.stats.drift_ms = drift;
+        self.stats.max_drift_ms = self.stats.max_drift_ms.max(drift.abs());
+
+        if drift.abs() <= SYNC_TOLERANCE_MS {
+            0.0
+        } else if drift.abs() >= RESYNC_THRESHOLD_MS {
+            self.stats.resyncs += 1;
+            -drift
+        } else {
+            self.stats.corrections += 1;
+            -drift * CORRECTION_GAIN
+        }
+    }
+
+    pub fn stats(&self) -> SyncStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correction_tolerance_gradual_and_resync() {
+        let mut sync = AvSync::new();
+        assert_eq!(sync.correction(1005.0, 1000.0), 0.0);
+        // 비디오가 50ms 앞섬 → 10%씩 뒤로
+        assert!((sync.correction(1050.0, 1000.0) + 5.0).abs() < 1e-9);
+        // 500ms 뒤처짐 → 즉시 맞춤
+        assert_eq!(sync.correction(500.0, 1000.0), 500.0);
+
+        let stats = sync.stats();
+        assert_eq!((stats.corrections, stats.resyncs), (1, 1));
+        assert_eq!(stats.drift_ms, -500.0);
+        assert_eq!(stats.max_drift_ms, 500.0);
+    }
+
+    #[test]
+    fn test_audio_clock_extrapolates_and_expires() {
+        let clock = AudioClock::new();
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+        assert_eq!(clock.position_ms(t0, t0), None);
+
+        clock.update(1000.0, true, at(10));
+        assert_eq!(clock.position_ms(at(110), t0).map(f64::round), Some(1100.0));
+        // seek 이후 기준이면 seek 전 보고는 무시
+        assert_eq!(clock.position_ms(at(110), at(50)), None);
+        // 보고가 끊기면 무효
+        assert_eq!(clock.position_ms(at(1000), t0), None);
+
+        clock.set_running(false);
+        assert_eq!(clock.position_ms(at(110), t0), None);
+    }
+}
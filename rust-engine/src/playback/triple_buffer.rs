@@ -0,0 +1,90 @@
+// 트리플 버퍼 - 작성자(재생 스레드)와 읽는 쪽(호스트)이 서로 기다리지 않고 최신 값 교환
+// 버퍼 3개: 작성 중(back, 작성자 소유) / 가운데(middle, 교환 슬롯) / 표시 중(front, 읽는 쪽 소유)
+// lock은 가운데 슬롯과 swap하는 동안만 → 렌더링/표시 중에는 서로 막지 않음
+
+use std::sync::Mutex;
+
+/// 가운데 슬롯 + 읽지 않은 새 값 여부
+struct Middle<T> {
+    value: Option<T>,
+    fresh: bool,
+}
+
+pub struct TripleBuffer<T> {
+    middle: Mutex<Middle<T>>,
+}
+
+impl<T> TripleBuffer<T> {
+    pub fn new() -> Self {
+        Self { middle: Mutex::new(Middle { value: None, fresh: false }) }
+    }
+
+    /// 작성 완료한 back을 가운데와 교환 (back에는 이전 가운데 값 → 작성자가 재사용/폐기)
+    /// 반환: 읽히지 않고 밀려난 값이 있었는지
+    pub fn publish(&self, back: &mut Option<T>) -> bool {
+        let mut middle = match self.middle.lock() {
+            Ok(m) => m,
+            Err(_) => return false,
+        };
+        std::mem::swap(&mut middle.value, back);
+        let overwritten = middle.fresh;
+        middle.fresh = middle.value.is_some();
+        overwritten
+    }
+
+    /// 새 값이 있으면 front와 가운데 교환 (front에는 최신 값, 이전 front는 작성자에게 돌아감)
+    /// 반환: front가 바뀌었는지
+    pub fn acquire(&self, front: &mut Option<T>) -> bool {
+        let mut middle = match self.middle.lock() {
+            Ok(m) => m,
+            Err(_) => return false,
+        };
+        if !middle.fresh {
+            return false;
+        }
+        std::mem::swap(&mut middle.value, front);
+        middle.fresh = false;
+        true
+    }
+}
+
+impl<T> Default for TripleBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triple_buffer_latest_value() {
+        let buffer = TripleBuffer::new();
+        let mut front = None;
+        assert!(!buffer.acquire(&mut front));
+
+        // 읽기 전에 두 번 게시 → 최신 값만 읽힘, 밀려난 값은 작성자에게 반환
+        let mut back = Some(1);
+        assert!(!buffer.publish(&mut back));
+        assert_eq!(back, None);
+        back = Some(2);
+        assert!(buffer.publish(&mut back));
+        assert_eq!(back, Some(1));
+
+        assert!(buffer.acquire(&mut front));
+        assert_eq!(front, Some(2));
+        // 새 값이 없으면 front 유지
+        assert!(!buffer.acquire(&mut front));
+        assert_eq!(front, Some(2));
+
+        // 읽은 뒤 게시 → 덮어쓴 것 아님, 이전 front(2)가 작성자 버퍼로 돌아옴
+        back = Some(3);
+        assert!(!buffer.publish(&mut back));
+        assert!(buffer.acquire(&mut front));
+        assert_eq!(front, Some(3));
+        back = Some(4);
+        buffer.publish(&mut back);
+        assert_eq!(back, Some(2));
+    }
+}
@@ -0,0 +1,292 @@
+// 레이어 합성 - 클립 트랜스폼(위치/크기/회전/크롭) 적용 후 캔버스에 알파 블렌딩
+// 트랙 순서대로 아래 → 위 (V1 위에 V2) 합성
+// 맞춤 모드(FitMode): 트랜스폼 전에 소스 종횡비대로 다시 배치 (디코더/캐시 프레임은 출력 크기로 늘어난 상태)
+
+use crate::timeline::{ClipTransform, FitMode};
+
+/// 레이어가 캔버스 전체를 불투명하게 덮는지 (합성 없이 교체 가능)
+pub fn covers_canvas(layer: &[u8], is_yuv: bool, transform: &ClipTransform) -> bool {
+    // YUV는 알파가 없으므로 항상 불투명
+    transform.is_identity() && (is_yuv || layer.chunks_exact(4).all(|p| p[3] == 255))
+}
+
+/// RGBA 레이어를 트랜스폼 적용하여 RGBA 캔버스 위에 합성 (source-over)
+/// - 레이어는 변환 전 캔버스 전체를 채우는 것으로 간주 (크기가 달라도 늘려서 매핑)
+/// - 크롭 영역 밖/레이어 밖은 캔버스 유지
+/// - opacity는 레이어 알파에 곱해짐
+pub fn composite_rgba(
+    canvas: &mut [u8],
+    canvas_width: u32,
+    canvas_height: u32,
+    layer: &[u8],
+    layer_width: u32,
+    layer_height: u32,
+    transform: &ClipTransform,
+) {
+    let (cw, ch) = (canvas_width as usize, canvas_height as usize);
+    let (lw, lh) = (layer_width as usize, layer_height as usize);
+    if cw == 0 || ch == 0 || lw == 0 || lh == 0
+        || canvas.len() < cw * ch * 4 || layer.len() < lw * lh * 4
+        || transform.scale <= 0.0 || !transform.scale.is_finite()
+        || transform.opacity <= 0.0
+    {
+        return;
+    }
+    let opacity = transform.opacity.min(1.0);
+
+    let cwf = cw as f32;
+    let chf = ch as f32;
+    let center_x = cwf / 2.0 + transform.offset_x * cwf;
+    let center_y = chf / 2.0 + transform.offset_y * chf;
+    let (sin, cos) = transform.rotation_deg.to_radians().sin_cos();
+    let inv_scale = 1.0 / transform.scale;
+
+    // 크롭 후 남는 영역 (변환 전 캔버스 좌표)
+    let crop = &transform.crop;
+    let min_u = crop.left * cwf;
+    let max_u = (1.0 - crop.right) * cwf;
+    let min_v = crop.top * chf;
+    let max_v = (1.0 - crop.bottom) * chf;
+
+    let sx_ratio = lw as f32 / cwf;
+    let sy_ratio = lh as f32 / chf;
+
+    for y in 0..ch {
+        let dy = y as f32 + 0.5 - center_y;
+        for x in 0..cw {
+            let dx = x as f32 + 0.5 - center_x;
+
+            // 역변환: 회전(-θ) → 스케일(1/s) → 변환 전 캔버스 좌표
+            let u = (dx * cos + dy * sin) * inv_scale + cwf / 2.0;
+            let v = (-dx * sin + dy * cos) * inv_scale + chf / 2.0;
+            if u < min_u || u >= max_u || v < min_v || v >= max_v {
+                continue;
+            }
+
+            let mut src = sample_bilinear(layer, lw, lh, u * sx_ratio - 0.5, v * sy_ratio - 0.5);
+            src[3] *= opacity;
+            let di = (y * cw + x) * 4;
+            blend_over(&mut canvas[di..di + 4], src);
+        }
+    }
+}
+
+/// 맞춤 모드의 소스 배치 영역 [x, y, 폭, 높이] (캔버스 픽셀, 캔버스 밖으로 나갈 수 있음)
+/// - source_size: 표시 기준 원본 크기 (SAR/회전 반영)
+/// - native_scale: Custom 모드에서 원본 1픽셀의 캔버스 크기 (캔버스 폭 / 프로젝트 폭)
+/// - None: 캔버스 전체 (Stretch, 또는 비율/크기가 같아 다시 배치할 필요 없음)
+pub fn fit_rect(source_size: (u32, u32), canvas_width: u32, canvas_height: u32, mode: FitMode, native_scale: f32) -> Option<[f32; 4]> {
+    let (sw, sh) = (source_size.0 as f32, source_size.1 as f32);
+    let (cw, ch) = (canvas_width as f32, canvas_height as f32);
+    if sw <= 0.0 || sh <= 0.0 || cw <= 0.0 || ch <= 0.0 {
+        return None;
+    }
+
+    let scale = match mode {
+        FitMode::Stretch => return None,
+        FitMode::Fit => (cw / sw).min(ch / sh),
+        FitMode::Fill => (cw / sw).max(ch / sh),
+        FitMode::Custom if native_scale > 0.0 && native_scale.is_finite() => native_scale,
+        FitMode::Custom => return None,
+    };
+    let (width, height) = (sw * scale, sh * scale);
+    // 반 픽셀 미만 차이는 같은 크기로 간주 (반올림 오차로 가장자리가 비지 않도록)
+    if (width - cw).abs() < 0.5 && (height - ch).abs() < 0.5 {
+        return None;
+    }
+    Some([(cw - width) / 2.0, (ch - height) / 2.0, width, height])
+}
+
+/// 출력 크기로 늘어난 RGBA 레이어를 rect 영역에 다시 배치한 캔버스 크기 RGBA (rect 밖은 투명)
+pub fn fit_layer_rgba(
+    layer: &[u8],
+    layer_width: u32,
+    layer_height: u32,
+    canvas_width: u32,
+    canvas_height: u32,
+    rect: [f32; 4],
+) -> Vec<u8> {
+    let (cw, ch) = (canvas_width as usize, canvas_height as usize);
+    let (lw, lh) = (layer_width as usize, layer_height as usize);
+    let mut out = vec![0u8; cw * ch * 4];
+    let [rx, ry, rw, rh] = rect;
+    if lw == 0 || lh == 0 || layer.len() < lw * lh * 4 || rw <= 0.0 || rh <= 0.0 {
+        return out;
+    }
+
+    let sx_ratio = lw as f32 / rw;
+    let sy_ratio = lh as f32 / rh;
+    let x_range = (rx.max(0.0).floor() as usize).min(cw)..((rx + rw).ceil().max(0.0) as usize).min(cw);
+    let y_range = (ry.max(0.0).floor() as usize).min(ch)..((ry + rh).ceil().max(0.0) as usize).min(ch);
+    for y in y_range {
+        let v = y as f32 + 0.5 - ry;
+        if v < 0.0 || v >= rh {
+            continue;
+        }
+        for x in x_range.clone() {
+            let u = x as f32 + 0.5 - rx;
+            if u < 0.0 || u >= rw {
+                continue;
+            }
+            let src = sample_bilinear(layer, lw, lh, u * sx_ratio - 0.5, v * sy_ratio - 0.5);
+            let di = (y * cw + x) * 4;
+            for c in 0..4 {
+                out[di + c] = src[c].round() as u8;
+            }
+        }
+    }
+    out
+}
+
+/// 바이리니어 샘플링 (가장자리 클램프)
+#[inline]
+fn sample_bilinear(data: &[u8], width: usize, height: usize, x: f32, y: f32) -> [f32; 4] {
+    let x = x.clamp(0.0, (width - 1) as f32);
+    let y = y.clamp(0.0, (height - 1) as f32);
+    let x0 = x.floor() as usize;
+    let y0 = y.floor() as usize;
+    let x1 = (x0 + 1).min(width - 1);
+    let y1 = (y0 + 1).min(height - 1);
+    let fx = x - x0 as f32;
+    let fy = y - y0 as f32;
+
+    let mut out = [0.0f32; 4];
+    for (c, value) in out.iter_mut().enumerate() {
+        let p00 = data[(y0 * width + x0) * 4 + c] as f32;
+        let p10 = data[(y0 * width + x1) * 4 + c] as f32;
+        let p01 = data[(y1 * width + x0) * 4 + c] as f32;
+        let p11 = data[(y1 * width + x1) * 4 + c] as f32;
+        let top = p00 + (p10 - p00) * fx;
+        let bottom = p01 + (p11 - p01) * fx;
+        *value = top + (bottom - top) * fy;
+    }
+    out
+}
+
+/// source-over 알파 블렌딩 (straight alpha)
+#[inline]
+fn blend_over(dst: &mut [u8], src: [f32; 4]) {
+    let sa = src[3] / 255.0;
+    if sa >= 1.0 {
+        for c in 0..4 {
+            dst[c] = src[c].round() as u8;
+        }
+        return;
+    }
+    if sa <= 0.0 {
+        return;
+    }
+
+    let da = dst[3] as f32 / 255.0;
+    let out_a = sa + da * (1.0 - sa);
+    for c in 0..3 {
+        let value = (src[c] * sa + dst[c] as f32 * da * (1.0 - sa)) / out_a;
+        dst[c] = value.round().clamp(0.0, 255.0) as u8;
+    }
+    dst[3] = (out_a * 255.0).round() as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeline::CropRect;
+
+    fn solid(width: usize, height: usize, rgba: [u8; 4]) -> Vec<u8> {
+        rgba.iter().copied().cycle().take(width * height * 4).collect()
+    }
+
+    fn pixel(data: &[u8], width: usize, x: usize, y: usize) -> &[u8] {
+        let i = (y * width + x) * 4;
+        &data[i..i + 4]
+    }
+
+    #[test]
+    fn test_identity_replaces_canvas() {
+        let mut canvas = solid(8, 4, [0, 0, 0, 0]);
+        let layer = solid(8, 4, [200, 100, 50, 255]);
+        let transform = ClipTransform::default();
+        assert!(covers_canvas(&layer, false, &transform));
+
+        composite_rgba(&mut canvas, 8, 4, &layer, 8, 4, &transform);
+        assert_eq!(canvas, layer);
+    }
+
+    #[test]
+    fn test_scaled_offset_pip() {
+        // 절반 크기 + 오른쪽 아래로 1/4 이동 → 오른쪽 아래 사분면만 레이어
+        let mut canvas = solid(16, 8, [0, 0, 255, 255]);
+        let layer = solid(16, 8, [255, 0, 0, 255]);
+        let transform = ClipTransform { offset_x: 0.25, offset_y: 0.25, scale: 0.5, ..ClipTransform::default() };
+        assert!(!covers_canvas(&layer, false, &transform));
+
+        composite_rgba(&mut canvas, 16, 8, &layer, 16, 8, &transform);
+        assert_eq!(pixel(&canvas, 16, 12, 6), &[255, 0, 0, 255]);
+        assert_eq!(pixel(&canvas, 16, 2, 1), &[0, 0, 255, 255]);
+        assert_eq!(pixel(&canvas, 16, 12, 1), &[0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_crop_and_rotation() {
+        // 왼쪽 절반 크롭 → 오른쪽 절반만 레이어
+        let mut canvas = solid(8, 8, [0, 0, 0, 255]);
+        let layer = solid(8, 8, [255, 255, 255, 255]);
+        let transform = ClipTransform {
+            crop: CropRect { left: 0.5, top: 0.0, right: 0.0, bottom: 0.0 },
+            ..ClipTransform::default()
+        };
+        composite_rgba(&mut canvas, 8, 8, &layer, 8, 8, &transform);
+        assert_eq!(pixel(&canvas, 8, 1, 4), &[0, 0, 0, 255]);
+        assert_eq!(pixel(&canvas, 8, 6, 4), &[255, 255, 255, 255]);
+
+        // 180도 회전 → 크롭된 오른쪽 절반이 왼쪽으로
+        let mut canvas = solid(8, 8, [0, 0, 0, 255]);
+        let rotated = ClipTransform { rotation_deg: 180.0, ..transform };
+        composite_rgba(&mut canvas, 8, 8, &layer, 8, 8, &rotated);
+        assert_eq!(pixel(&canvas, 8, 1, 4), &[255, 255, 255, 255]);
+        assert_eq!(pixel(&canvas, 8, 6, 4), &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_alpha_blend() {
+        let mut dst = [0u8, 0, 255, 255];
+        blend_over(&mut dst, [255.0, 0.0, 0.0, 127.5]);
+        assert_eq!(dst, [128, 0, 128, 255]);
+
+        // 투명 배경 위: 레이어 색 그대로, 알파만 반영
+        let mut dst = [0u8, 0, 0, 0];
+        blend_over(&mut dst, [255.0, 0.0, 0.0, 127.5]);
+        assert_eq!(dst, [255, 0, 0, 128]);
+
+        // 불투명도 50% 레이어
+        let mut canvas = solid(2, 2, [0, 0, 255, 255]);
+        let layer = solid(2, 2, [255, 0, 0, 255]);
+        let transform = ClipTransform { opacity: 0.5, ..ClipTransform::default() };
+        assert!(!covers_canvas(&layer, false, &transform));
+        composite_rgba(&mut canvas, 2, 2, &layer, 2, 2, &transform);
+        assert_eq!(pixel(&canvas, 2, 1, 1), &[128, 0, 128, 255]);
+    }
+    #[test]
+    fn test_fit_modes() {
+        // 4:3 소스 → 16:9 캔버스
+        assert_eq!(fit_rect((640, 480), 16, 9, FitMode::Fit, 1.0), Some([2.0, 0.0, 12.0, 9.0]));
+        assert_eq!(fit_rect((640, 480), 16, 9, FitMode::Fill, 1.0), Some([0.0, -1.5, 16.0, 12.0]));
+        assert_eq!(fit_rect((640, 480), 16, 9, FitMode::Stretch, 1.0), None);
+        // 원본 크기 (캔버스가 프로젝트의 1/40)
+        assert_eq!(fit_rect((640, 480), 16, 9, FitMode::Custom, 1.0 / 40.0), Some([0.0, -1.5, 16.0, 12.0]));
+        // 같은 비율은 다시 배치하지 않음
+        assert_eq!(fit_rect((1920, 1080), 16, 9, FitMode::Fit, 1.0), None);
+        assert_eq!(fit_rect((1920, 1080), 16, 9, FitMode::Fill, 1.0), None);
+
+        // 필러박스: 양옆 2픽셀 투명
+        let layer = solid(16, 9, [255, 0, 0, 255]);
+        let out = fit_layer_rgba(&layer, 16, 9, 16, 9, [2.0, 0.0, 12.0, 9.0]);
+        assert_eq!(pixel(&out, 16, 1, 4), &[0, 0, 0, 0]);
+        assert_eq!(pixel(&out, 16, 2, 4), &[255, 0, 0, 255]);
+        assert_eq!(pixel(&out, 16, 13, 8), &[255, 0, 0, 255]);
+        assert_eq!(pixel(&out, 16, 14, 0), &[0, 0, 0, 0]);
+
+        // 채우기: 위아래가 잘려 전체 불투명
+        let out = fit_layer_rgba(&layer, 16, 9, 16, 9, [0.0, -1.5, 16.0, 12.0]);
+        assert!(covers_canvas(&out, false, &ClipTransform::default()));
+    }
+}
@@ -0,0 +1,185 @@
+// 디코드 스케줄러 - 프리뷰/Export 동시 디코딩 우선순위 조정
+// 프리뷰(높음)와 Export(낮음)가 같은 파일을 동시에 디코딩할 때 디스크/CPU 경합 방지
+// Export는 프리뷰 디코딩이 진행/대기 중이면 양보하되, 최대 대기 시간 후에는 진행 (기아 방지)
+
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// 동시 디코딩 최대 개수 기본값
+const DEFAULT_MAX_CONCURRENT: u32 = 2;
+
+/// Export가 프리뷰에 양보하는 최대 시간 — 재생 중에도 Export가 멈추지 않도록
+const EXPORT_YIELD_MAX: Duration = Duration::from_millis(50);
+
+/// 디코딩 우선순위
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodePriority {
+    /// 프리뷰 (재생/스크럽) — 즉시 실행
+    Preview,
+    /// Export — 프리뷰 디코딩 사이 빈 구간에 실행
+    Export,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    active_preview: u32,
+    active_export: u32,
+    waiting_preview: u32,
+}
+
+impl SchedulerState {
+    fn active_total(&self) -> u32 {
+        self.active_preview + self.active_export
+    }
+}
+
+/// 프로세스 전역 디코드 스케줄러
+pub struct DecodeScheduler {
+    state: Mutex<SchedulerState>,
+    cond: Condvar,
+    max_concurrent: u32,
+}
+
+/// 디코딩 허가 (drop 시 슬롯 반환)
+pub struct DecodePermit<'a> {
+    scheduler: &'a DecodeScheduler,
+    priority: DecodePriority,
+}
+
+impl Drop for DecodePermit<'_> {
+    fn drop(&mut self) {
+        self.scheduler.release(self.priority);
+    }
+}
+
+impl DecodeScheduler {
+    pub fn new(max_concurrent: u32) -> Self {
+        Self {
+            state: Mutex::new(SchedulerState::default()),
+            cond: Condvar::new(),
+            max_concurrent: max_concurrent.max(1),
+        }
+    }
+
+    /// 전역 인스턴스 (Renderer 프리뷰/Export 공유)
+    pub fn global() -> &'static DecodeScheduler {
+        static GLOBAL: OnceLock<DecodeScheduler> = OnceLock::new();
+        GLOBAL.get_or_init(|| DecodeScheduler::new(DEFAULT_MAX_CONCURRENT))
+    }
+
+    /// 디코딩 슬롯 획득 (필요 시 대기)
+    pub fn acquire(&self, priority: DecodePriority) -> DecodePermit<'_> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        match priority {
+            DecodePriority::Preview => {
+                state.waiting_preview += 1;
+                while state.active_total() >= self.max_concurrent {
+                    state = self.cond.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+                state.waiting_preview -= 1;
+                state.active_preview += 1;
+            }
+            DecodePriority::Export => {
+                let yield_deadline = Instant::now() + EXPORT_YIELD_MAX;
+                loop {
+                    let preview_busy = state.active_preview > 0 || state.waiting_preview > 0;
+                    let yielding = preview_busy && Instant::now() < yield_deadline;
+                    if !yielding && state.active_total() < self.max_concurrent {
+                        break;
+                    }
+                    // 양보 중이면 남은 양보 시간만큼, 아니면 슬롯 반환까지 대기
+                    state = if yielding {
+                        let remaining = yield_deadline.saturating_duration_since(Instant::now());
+                        self.cond.wait_timeout(state, remaining)
+                            .unwrap_or_else(|e| e.into_inner())
+                            .0
+                    } else {
+                        self.cond.wait(state).unwrap_or_else(|e| e.into_inner())
+                    };
+                }
+                state.active_export += 1;
+            }
+        }
+
+        DecodePermit { scheduler: self, priority }
+    }
+
+    /// 대기 없이 슬롯 획득 시도 (Export는 프리뷰 진행 중이면 실패)
+    pub fn try_acquire(&self, priority: DecodePriority) -> Option<DecodePermit<'_>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.active_total() >= self.max_concurrent {
+            return None;
+        }
+
+        match priority {
+            DecodePriority::Preview => state.active_preview += 1,
+            DecodePriority::Export => {
+                if state.active_preview > 0 || state.waiting_preview > 0 {
+                    return None;
+                }
+                state.active_export += 1;
+            }
+        }
+
+        Some(DecodePermit { scheduler: self, priority })
+    }
+
+    /// 현재 실행 중인 (프리뷰, Export) 디코딩 수
+    pub fn active_counts(&self) -> (u32, u32) {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        (state.active_preview, state.active_export)
+    }
+
+    fn release(&self, priority: DecodePriority) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match priority {
+            DecodePriority::Preview => state.active_preview = state.active_preview.saturating_sub(1),
+            DecodePriority::Export => state.active_export = state.active_export.saturating_sub(1),
+        }
+        drop(state);
+        self.cond.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_yields_to_preview() {
+        let scheduler = DecodeScheduler::new(2);
+
+        let preview = scheduler.try_acquire(DecodePriority::Preview).unwrap();
+        // 슬롯이 남아도 프리뷰 진행 중이면 Export는 즉시 획득 불가
+        assert!(scheduler.try_acquire(DecodePriority::Export).is_none());
+        assert_eq!(scheduler.active_counts(), (1, 0));
+
+        drop(preview);
+        let export = scheduler.try_acquire(DecodePriority::Export).unwrap();
+        // 프리뷰는 Export 진행 중에도 획득 가능
+        let _preview = scheduler.try_acquire(DecodePriority::Preview).unwrap();
+        assert_eq!(scheduler.active_counts(), (1, 1));
+        drop(export);
+        assert_eq!(scheduler.active_counts(), (1, 0));
+    }
+
+    #[test]
+    fn test_export_not_starved() {
+        let scheduler = DecodeScheduler::new(2);
+        let _preview = scheduler.acquire(DecodePriority::Preview);
+
+        // 프리뷰가 계속 점유해도 양보 시간 후 Export 진행
+        let start = Instant::now();
+        let _export = scheduler.acquire(DecodePriority::Export);
+        assert!(start.elapsed() >= EXPORT_YIELD_MAX);
+        assert_eq!(scheduler.active_counts(), (1, 1));
+    }
+
+    #[test]
+    fn test_max_concurrent() {
+        let scheduler = DecodeScheduler::new(1);
+        let _preview = scheduler.try_acquire(DecodePriority::Preview).unwrap();
+        assert!(scheduler.try_acquire(DecodePriority::Preview).is_none());
+    }
+}
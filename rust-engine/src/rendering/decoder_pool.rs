@@ -0,0 +1,140 @@
+// 디코더 풀 - 파일별 디코더 LRU 캐시
+// 디코더마다 FFmpeg 컨텍스트 + 디코딩 스레드 풀 + 프레임 버퍼를 잡고 있어 파일 수만큼 열어 두면 메모리가 계속 증가
+// 한도(동시에 열어 두는 디코더 수)를 넘으면 가장 오래 전에 사용한 디코더부터 닫음 (다시 필요하면 재오픈)
+
+use std::collections::HashMap;
+
+/// 기본 최대 디코더 수 (동시에 보이는 클립 + 최근 스크럽한 파일)
+pub const DEFAULT_MAX_DECODERS: usize = 8;
+
+/// LRU 디코더 풀 (D: 디코더 타입)
+pub struct DecoderPool<D> {
+    /// 키 → (디코더, 마지막 사용 시각)
+    entries: HashMap<String, (D, u64)>,
+    /// 사용 시각 카운터 (조회/삽입마다 증가)
+    clock: u64,
+    max_decoders: usize,
+}
+
+impl<D> DecoderPool<D> {
+    /// max_decoders: 최대 디코더 수 (최소 1)
+    pub fn new(max_decoders: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            clock: 0,
+            max_decoders: max_decoders.max(1),
+        }
+    }
+
+    pub fn max_decoders(&self) -> usize {
+        self.max_decoders
+    }
+
+    /// 최대 디코더 수 변경 (줄이면 즉시 LRU부터 닫음)
+    /// 반환: 닫힌 디코더 키
+    pub fn set_max_decoders(&mut self, max_decoders: usize) -> Vec<String> {
+        self.max_decoders = max_decoders.max(1);
+        self.evict(None)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// 조회 (사용 시각 갱신)
+    pub fn get(&mut self, key: &str) -> Option<&D> {
+        self.get_mut(key).map(|decoder| &*decoder)
+    }
+
+    /// 가변 조회 (사용 시각 갱신)
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut D> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(key).map(|(decoder, last_used)| {
+            *last_used = clock;
+            decoder
+        })
+    }
+
+    /// 삽입 (가장 최근 사용으로 기록, 한도 초과 시 LRU부터 닫음)
+    /// 반환: 닫힌 디코더 키 (새로 넣은 키는 제외)
+    pub fn insert(&mut self, key: String, decoder: D) -> Vec<String> {
+        self.clock += 1;
+        self.entries.insert(key.clone(), (decoder, self.clock));
+        self.evict(Some(&key))
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<D> {
+        self.entries.remove(key).map(|(decoder, _)| decoder)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &D)> {
+        self.entries.iter().map(|(key, (decoder, _))| (key, decoder))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &D> {
+        self.entries.values().map(|(decoder, _)| decoder)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut D> {
+        self.entries.values_mut().map(|(decoder, _)| decoder)
+    }
+
+    /// 한도까지 LRU 디코더 제거 (keep은 제거 대상에서 제외)
+    fn evict(&mut self, keep: Option<&str>) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.entries.len() > self.max_decoders {
+            let oldest = self.entries.iter()
+                .filter(|(key, _)| Some(key.as_str()) != keep)
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => {
+                    self.entries.remove(&key);
+                    evicted.push(key);
+                }
+                None => break,
+            }
+        }
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut pool = DecoderPool::new(2);
+        assert!(pool.insert("a".to_string(), 1).is_empty());
+        assert!(pool.insert("b".to_string(), 2).is_empty());
+
+        // a 사용 → b가 가장 오래됨
+        assert_eq!(pool.get("a"), Some(&1));
+        assert_eq!(pool.insert("c".to_string(), 3), vec!["b".to_string()]);
+        assert!(pool.contains_key("a") && pool.contains_key("c"));
+
+        // 한도 축소 → 즉시 LRU 제거 (최소 1)
+        assert_eq!(pool.set_max_decoders(0), vec!["a".to_string()]);
+        assert_eq!(pool.max_decoders(), 1);
+        assert_eq!(pool.len(), 1);
+
+        // 같은 키 재삽입은 교체 (제거 없음)
+        assert!(pool.insert("c".to_string(), 4).is_empty());
+        assert_eq!(pool.remove("c"), Some(4));
+        assert!(pool.is_empty());
+    }
+}
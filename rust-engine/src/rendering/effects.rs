@@ -1,5 +1,6 @@
-// 이펙트 엔진 — RGBA 픽셀 연산 (Brightness, Contrast, Saturation, Temperature)
+// 이펙트 엔진 — RGBA 픽셀 연산 (Brightness, Contrast, Gamma, Highlights/Shadows, Saturation, Temperature/Tint, Vignette)
 
+use crate::rendering::simd;
 use std::collections::HashMap;
 
 /// 클립별 이펙트 파라미터 (-1.0 ~ 1.0, 0=원본)
@@ -9,6 +10,16 @@ pub struct EffectParams {
     pub contrast: f32,
     pub saturation: f32,
     pub temperature: f32,
+    /// 감마 (+: 중간톤 밝게, -: 어둡게)
+    pub gamma: f32,
+    /// 하이라이트 (밝은 영역만 조정)
+    pub highlights: f32,
+    /// 섀도 (어두운 영역만 조정)
+    pub shadows: f32,
+    /// 틴트 (+: 마젠타, -: 그린)
+    pub tint: f32,
+    /// 비네팅 (+: 가장자리 어둡게, -: 밝게)
+    pub vignette: f32,
 }
 
 impl Default for EffectParams {
@@ -18,82 +29,271 @@ impl Default for EffectParams {
             contrast: 0.0,
             saturation: 0.0,
             temperature: 0.0,
+            gamma: 0.0,
+            highlights: 0.0,
+            shadows: 0.0,
+            tint: 0.0,
+            vignette: 0.0,
         }
     }
 }
 
 impl EffectParams {
+    /// 파라미터 값 배열 (brightness, contrast, saturation, temperature, gamma, highlights, shadows, tint, vignette 순)
+    pub fn values(&self) -> [f32; 9] {
+        [
+            self.brightness, self.contrast, self.saturation, self.temperature,
+            self.gamma, self.highlights, self.shadows, self.tint, self.vignette,
+        ]
+    }
+
     /// 모든 값이 기본값(0)인지 확인 — true이면 이펙트 연산 건너뜀
     pub fn is_default(&self) -> bool {
-        self.brightness.abs() < 0.001
-            && self.contrast.abs() < 0.001
-            && self.saturation.abs() < 0.001
-            && self.temperature.abs() < 0.001
+        self.values().iter().all(|v| v.abs() < 0.001)
     }
 }
 
 /// 클립별 이펙트 저장소
 pub type EffectStore = HashMap<u64, EffectParams>;
 
-/// RGBA 버퍼에 이펙트 적용 (in-place)
-/// data: RGBA 픽셀 배열 (4 bytes per pixel)
-pub fn apply_effects(data: &mut [u8], width: u32, height: u32, params: &EffectParams) {
-    if params.is_default() {
-        return;
-    }
+/// 비네팅 시작 반경 (중심~모서리 거리 비율, 안쪽은 영향 없음)
+pub(crate) const VIGNETTE_INNER_RADIUS: f32 = 0.4;
 
-    let pixel_count = (width * height) as usize;
-    if data.len() < pixel_count * 4 {
-        return;
+/// 감마 보정 테이블 (exponent = 2^-gamma, +1이면 0.5승)
+fn gamma_table(gamma: f32) -> [u8; 256] {
+    let exponent = 2f32.powf(-gamma);
+    let mut table = [0u8; 256];
+    for (i, v) in table.iter_mut().enumerate() {
+        *v = (255.0 * (i as f32 / 255.0).powf(exponent)).round() as u8;
     }
+    table
+}
 
-    let brightness_offset = params.brightness * 255.0;
-    let contrast_factor = 1.0 + params.contrast;
-    let saturation_factor = 1.0 + params.saturation;
+/// 가장자리 가중치 (0 = 안쪽 원, 1 = 모서리), 부드러운 감쇠
+fn vignette_weight(x: usize, y: usize, width: usize, height: usize) -> f32 {
+    let dx = (x as f32 + 0.5) / width as f32 * 2.0 - 1.0;
+    let dy = (y as f32 + 0.5) / height as f32 * 2.0 - 1.0;
+    let d = ((dx * dx + dy * dy) / 2.0).sqrt();
+    let t = ((d - VIGNETTE_INNER_RADIUS) / (1.0 - VIGNETTE_INNER_RADIUS)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
 
-    // Temperature: warm(+) = R+, B-, cool(-) = R-, B+
-    let temp_r = params.temperature * 30.0;
-    let temp_b = -params.temperature * 30.0;
+/// 파라미터에서 미리 계산한 픽셀 연산 상수 (스칼라/SIMD 커널 공용, None = 단계 건너뜀)
+pub(crate) struct EffectKernel {
+    pub(crate) brightness: Option<f32>,
+    pub(crate) contrast: Option<f32>,
+    pub(crate) gamma: Option<[u8; 256]>,
+    /// (highlights, shadows)
+    pub(crate) tone: Option<(f32, f32)>,
+    pub(crate) saturation: Option<f32>,
+    /// (R, B 오프셋)
+    pub(crate) temperature: Option<(f32, f32)>,
+    /// (R/B, G 오프셋)
+    pub(crate) tint: Option<(f32, f32)>,
+    /// (강도, 목표값 0/255)
+    pub(crate) vignette: Option<(f32, f32)>,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+}
 
-    for i in 0..pixel_count {
-        let idx = i * 4;
-        let mut r = data[idx] as f32;
-        let mut g = data[idx + 1] as f32;
-        let mut b = data[idx + 2] as f32;
-        // Alpha (idx+3) 는 변경하지 않음
+impl EffectKernel {
+    fn new(params: &EffectParams, width: usize, height: usize) -> Self {
+        let active = |v: f32| v.abs() >= 0.001;
+        let brightness_offset = params.brightness * 255.0;
+        let contrast_factor = 1.0 + params.contrast;
+        let saturation_factor = 1.0 + params.saturation;
+
+        // Temperature: warm(+) = R+, B-, cool(-) = R-, B+
+        let temp_r = params.temperature * 30.0;
+        let temp_b = -params.temperature * 30.0;
+        // Tint: magenta(+) = R+, G-, B+, green(-) = 반대
+        let tint_g = -params.tint * 30.0;
+        let tint_rb = params.tint * 15.0;
+
+        Self {
+            brightness: (brightness_offset.abs() > 0.1).then_some(brightness_offset),
+            contrast: ((contrast_factor - 1.0).abs() > 0.001).then_some(contrast_factor),
+            gamma: active(params.gamma).then(|| gamma_table(params.gamma)),
+            tone: (active(params.highlights) || active(params.shadows))
+                .then_some((params.highlights, params.shadows)),
+            saturation: ((saturation_factor - 1.0).abs() > 0.001).then_some(saturation_factor),
+            temperature: (temp_r.abs() > 0.1).then_some((temp_r, temp_b)),
+            tint: (tint_g.abs() > 0.1).then_some((tint_rb, tint_g)),
+            vignette: active(params.vignette)
+                .then(|| (params.vignette.abs(), if params.vignette > 0.0 { 0.0 } else { 255.0 })),
+            width,
+            height,
+        }
+    }
+
+    /// 픽셀 1개 적용 (x, y: 비네팅 위치), Alpha (px[3]) 는 변경하지 않음
+    pub(crate) fn apply_pixel(&self, px: &mut [u8], x: usize, y: usize) {
+        let mut r = px[0] as f32;
+        let mut g = px[1] as f32;
+        let mut b = px[2] as f32;
 
         // 1. Brightness: 단순 오프셋
-        if brightness_offset.abs() > 0.1 {
-            r += brightness_offset;
-            g += brightness_offset;
-            b += brightness_offset;
+        if let Some(offset) = self.brightness {
+            r += offset;
+            g += offset;
+            b += offset;
         }
 
         // 2. Contrast: 128 기준 스케일링
-        if (contrast_factor - 1.0).abs() > 0.001 {
-            r = 128.0 + (r - 128.0) * contrast_factor;
-            g = 128.0 + (g - 128.0) * contrast_factor;
-            b = 128.0 + (b - 128.0) * contrast_factor;
+        if let Some(factor) = self.contrast {
+            r = 128.0 + (r - 128.0) * factor;
+            g = 128.0 + (g - 128.0) * factor;
+            b = 128.0 + (b - 128.0) * factor;
+        }
+
+        // 3. Gamma: 중간톤 곡선 (테이블 조회)
+        if let Some(table) = &self.gamma {
+            r = table[r.clamp(0.0, 255.0) as usize] as f32;
+            g = table[g.clamp(0.0, 255.0) as usize] as f32;
+            b = table[b.clamp(0.0, 255.0) as usize] as f32;
+        }
+
+        // 4. Highlights/Shadows: 밝기 가중 오프셋 (밝은 영역 lum², 어두운 영역 (1-lum)²)
+        if let Some((highlights, shadows)) = self.tone {
+            let lum = ((0.2126 * r + 0.7152 * g + 0.0722 * b) / 255.0).clamp(0.0, 1.0);
+            let offset = (highlights * lum * lum + shadows * (1.0 - lum) * (1.0 - lum)) * 128.0;
+            r += offset;
+            g += offset;
+            b += offset;
         }
 
-        // 3. Saturation: luminance 기준 조정
-        if (saturation_factor - 1.0).abs() > 0.001 {
+        // 5. Saturation: luminance 기준 조정
+        if let Some(factor) = self.saturation {
             // BT.709 가중치
             let lum = 0.2126 * r + 0.7152 * g + 0.0722 * b;
-            r = lum + (r - lum) * saturation_factor;
-            g = lum + (g - lum) * saturation_factor;
-            b = lum + (b - lum) * saturation_factor;
+            r = lum + (r - lum) * factor;
+            g = lum + (g - lum) * factor;
+            b = lum + (b - lum) * factor;
         }
 
-        // 4. Temperature: R/B 채널 오프셋
-        if temp_r.abs() > 0.1 {
-            r += temp_r;
-            b += temp_b;
+        // 6. Temperature/Tint: 채널 오프셋
+        if let Some((dr, db)) = self.temperature {
+            r += dr;
+            b += db;
+        }
+        if let Some((drb, dg)) = self.tint {
+            r += drb;
+            g += dg;
+            b += drb;
+        }
+
+        // 7. Vignette: 가장자리로 갈수록 검정(+)/흰색(-) 쪽으로
+        if let Some((amount, target)) = self.vignette {
+            let weight = vignette_weight(x, y, self.width, self.height) * amount;
+            r += (target - r) * weight;
+            g += (target - g) * weight;
+            b += (target - b) * weight;
         }
 
         // Clamp 0-255
-        data[idx] = r.clamp(0.0, 255.0) as u8;
-        data[idx + 1] = g.clamp(0.0, 255.0) as u8;
-        data[idx + 2] = b.clamp(0.0, 255.0) as u8;
+        px[0] = r.clamp(0.0, 255.0) as u8;
+        px[1] = g.clamp(0.0, 255.0) as u8;
+        px[2] = b.clamp(0.0, 255.0) as u8;
+    }
+}
+
+/// RGBA 버퍼에 이펙트 적용 (in-place, 한 번의 픽셀 순회, AVX2 가능하면 8픽셀 단위)
+/// data: RGBA 픽셀 배열 (4 bytes per pixel)
+pub fn apply_effects(data: &mut [u8], width: u32, height: u32, params: &EffectParams) {
+    apply_effects_impl(data, width, height, params, true);
+}
+
+/// 스칼라 전용 apply_effects (SIMD 결과 비교/벤치마크 기준)
+pub fn apply_effects_scalar(data: &mut [u8], width: u32, height: u32, params: &EffectParams) {
+    apply_effects_impl(data, width, height, params, false);
+}
+
+fn apply_effects_impl(data: &mut [u8], width: u32, height: u32, params: &EffectParams, use_simd: bool) {
+    if params.is_default() {
+        return;
+    }
+
+    let (w, h) = (width as usize, height as usize);
+    if w == 0 || h == 0 || data.len() < w * h * 4 {
+        return;
+    }
+
+    let kernel = EffectKernel::new(params, w, h);
+    for (y, row) in data[..w * h * 4].chunks_exact_mut(w * 4).enumerate() {
+        let done = if use_simd { simd::apply_effects_row(row, y, &kernel) } else { 0 };
+        for (x, px) in row.chunks_exact_mut(4).enumerate().skip(done) {
+            kernel.apply_pixel(px, x, y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray_frame(width: u32, height: u32, value: u8) -> Vec<u8> {
+        [value, value, value, 255].repeat((width * height) as usize)
+    }
+
+    #[test]
+    fn test_gamma_and_tone() {
+        let mut data = gray_frame(1, 1, 128);
+        apply_effects(&mut data, 1, 1, &EffectParams { gamma: 1.0, ..EffectParams::default() });
+        assert_eq!(data[0], 181); // (128/255)^0.5 * 255
+        assert_eq!(data[3], 255);
+
+        // 섀도는 어두운 픽셀을 더 많이 올림
+        let mut dark = gray_frame(1, 1, 30);
+        let mut bright = gray_frame(1, 1, 220);
+        let shadows = EffectParams { shadows: 0.5, ..EffectParams::default() };
+        apply_effects(&mut dark, 1, 1, &shadows);
+        apply_effects(&mut bright, 1, 1, &shadows);
+        assert!(dark[0] - 30 > bright[0] - 220);
+
+        // 틴트 +: 마젠타 (G 감소)
+        let mut data = gray_frame(1, 1, 128);
+        apply_effects(&mut data, 1, 1, &EffectParams { tint: 1.0, ..EffectParams::default() });
+        assert!(data[1] < 128 && data[0] > 128 && data[2] > 128);
+    }
+
+    #[test]
+    fn test_vignette() {
+        let mut data = gray_frame(9, 9, 200);
+        apply_effects(&mut data, 9, 9, &EffectParams { vignette: 1.0, ..EffectParams::default() });
+        let pixel = |x: usize, y: usize| data[(y * 9 + x) * 4];
+        assert_eq!(pixel(4, 4), 200); // 중심은 그대로
+        assert!(pixel(0, 0) < 20);    // 모서리는 거의 검정
+        assert!(pixel(4, 0) < 200 && pixel(4, 0) > pixel(0, 0));
+    }
+
+    #[test]
+    fn test_simd_matches_scalar() {
+        let all = EffectParams {
+            brightness: 0.1, contrast: 0.3, saturation: -0.4, temperature: 0.2,
+            gamma: 0.5, highlights: -0.3, shadows: 0.4, tint: -0.2, vignette: 0.7,
+        };
+        let cases = [
+            all.clone(),
+            EffectParams { vignette: -0.6, ..EffectParams::default() },
+            EffectParams { brightness: -0.5, contrast: 0.8, ..EffectParams::default() },
+            EffectParams { gamma: -1.0, saturation: 1.0, ..EffectParams::default() },
+        ];
+        for params in &cases {
+            for (w, h) in [(37u32, 5u32), (16, 3), (3, 2)] {
+                // 재현 가능한 의사 난수 픽셀 (LCG)
+                let mut state = w * h;
+                let source: Vec<u8> = (0..w * h * 4)
+                    .map(|_| {
+                        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                        (state >> 24) as u8
+                    })
+                    .collect();
+                let mut simd = source.clone();
+                let mut scalar = source;
+                apply_effects(&mut simd, w, h, params);
+                apply_effects_scalar(&mut scalar, w, h, params);
+                assert_eq!(simd, scalar, "{:?} {}x{}", params, w, h);
+            }
+        }
     }
 }
@@ -0,0 +1,146 @@
+// 비동기 프레임 요청 (스크럽) - UI 스레드가 디코딩을 기다리지 않도록 워커 스레드에서 렌더링
+// 최신 요청 우선: 워커가 가져가기 전에 새 요청이 오면 이전 요청은 버림
+// 완료 프레임도 마지막 것 하나만 보관 → 호스트는 poll로 가장 최근 결과만 받음
+
+use crate::rendering::{Renderer, RenderedFrame};
+use crate::utils::log::log_error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+
+/// 워커 공유 상태
+struct RequestShared {
+    /// 대기 중인 요청 타임스탬프 (워커가 가져가면 None)
+    pending: Mutex<Option<i64>>,
+    /// 렌더링 완료 프레임 (poll로 꺼내면 None)
+    completed: Mutex<Option<RenderedFrame>>,
+    wake: Condvar,
+    stopped: AtomicBool,
+}
+
+impl RequestShared {
+    /// 다음 요청 대기 (중지되면 None)
+    fn next_request(&self) -> Option<i64> {
+        let mut pending = self.pending.lock().ok()?;
+        loop {
+            if self.stopped.load(Ordering::SeqCst) {
+                return None;
+            }
+            if let Some(timestamp_ms) = pending.take() {
+                return Some(timestamp_ms);
+            }
+            pending = self.wake.wait(pending).ok()?;
+        }
+    }
+}
+
+/// 비동기 프레임 요청 워커 핸들 (drop 시 워커 종료)
+pub struct FrameRequester {
+    shared: Arc<RequestShared>,
+}
+
+impl FrameRequester {
+    /// 워커 시작 (Renderer가 해제되면 워커도 종료)
+    pub fn start(renderer: Weak<Mutex<Renderer>>) -> Self {
+        let shared = Arc::new(RequestShared {
+            pending: Mutex::new(None),
+            completed: Mutex::new(None),
+            wake: Condvar::new(),
+            stopped: AtomicBool::new(false),
+        });
+        let worker = shared.clone();
+        std::thread::spawn(move || run_worker(&worker, &renderer));
+        Self { shared }
+    }
+
+    /// 프레임 요청 (아직 렌더링을 시작하지 않은 이전 요청은 교체)
+    pub fn request(&self, timestamp_ms: i64) {
+        if let Ok(mut pending) = self.shared.pending.lock() {
+            *pending = Some(timestamp_ms);
+            self.shared.wake.notify_one();
+        }
+    }
+
+    /// 완료된 최신 프레임 꺼내기 (없으면 None, timestamp_ms = 요청 시간)
+    pub fn poll(&self) -> Option<RenderedFrame> {
+        self.shared.completed.lock().ok()?.take()
+    }
+}
+
+impl Drop for FrameRequester {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::SeqCst);
+        // 대기 중인 워커 깨우기 (lock을 잡아 wait 진입 전 알림 유실 방지)
+        let _pending = self.shared.pending.lock();
+        self.shared.wake.notify_all();
+    }
+}
+
+/// 워커 루프 (요청마다 Renderer lock → 렌더링 → 완료 슬롯 교체)
+fn run_worker(shared: &RequestShared, renderer: &Weak<Mutex<Renderer>>) {
+    while let Some(timestamp_ms) = shared.next_request() {
+        let renderer = match renderer.upgrade() {
+            Some(renderer) => renderer,
+            None => break,
+        };
+        let result = match renderer.lock() {
+            Ok(mut r) => r.render_frame(timestamp_ms),
+            Err(_) => break,
+        };
+        match result {
+            Ok(mut frame) => {
+                frame.timestamp_ms = timestamp_ms;
+                if let Ok(mut completed) = shared.completed.lock() {
+                    *completed = Some(frame);
+                }
+            }
+            Err(e) => log_error!("[FRAME_REQUEST] render error at {}ms: {}", timestamp_ms, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeline::Timeline;
+    use std::time::{Duration, Instant};
+
+    fn poll_until(requester: &FrameRequester, timestamp_ms: i64) -> Option<RenderedFrame> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if let Some(frame) = requester.poll().filter(|f| f.timestamp_ms == timestamp_ms) {
+                return Some(frame);
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        None
+    }
+
+    #[test]
+    fn test_latest_request_wins() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let renderer = Arc::new(Mutex::new(Renderer::new(timeline)));
+        // 워커가 Renderer를 잡지 못하게 막은 상태에서 연속 요청 → 이미 가져간 요청 외에는 마지막 요청만 렌더링
+        let guard = renderer.lock().unwrap();
+        let requester = FrameRequester::start(Arc::downgrade(&renderer));
+        requester.request(100);
+        requester.request(200);
+        requester.request(300);
+        drop(guard);
+
+        let frame = poll_until(&requester, 300).expect("latest frame");
+        assert_eq!((frame.width, frame.height), (960, 540));
+        assert!(requester.poll().is_none());
+    }
+
+    #[test]
+    fn test_worker_stops_with_renderer() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let renderer = Arc::new(Mutex::new(Renderer::new(timeline)));
+        let requester = FrameRequester::start(Arc::downgrade(&renderer));
+        drop(renderer);
+        // Renderer 해제 후 요청은 렌더링 없이 무시
+        requester.request(0);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(requester.poll().is_none());
+    }
+}
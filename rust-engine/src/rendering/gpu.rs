@@ -0,0 +1,465 @@
+// GPU 합성 백엔드 (wgpu, feature = "gpu") - 프리뷰 레이어를 텍스처로 업로드 후 셰이더에서 스케일/이펙트/블렌딩
+// 수식은 CPU 경로(compositor.rs, effects.rs)와 동일 → 결과 차이는 반올림 수준
+// 결과: 출력 텍스처 (호스트 공유용, output_texture) 또는 RGBA readback (read_rgba)
+
+use crate::rendering::effects::{EffectParams, VIGNETTE_INNER_RADIUS};
+use crate::timeline::ClipTransform;
+use std::sync::mpsc;
+
+/// 출력/레이어 텍스처 포맷 (CPU 경로와 같은 감마 공간 연산을 위해 sRGB 변환 없음)
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+/// 레이어 uniform (vec4 8개, 셰이더 Layer 구조체와 같은 순서)
+const UNIFORM_SIZE: u64 = 8 * 16;
+
+/// 합성할 레이어 (RGBA, 트랙 순서대로 아래 → 위)
+pub struct GpuLayer<'a> {
+    pub data: &'a [u8],
+    pub width: u32,
+    pub height: u32,
+    pub transform: ClipTransform,
+    /// 레이어 좌표 기준 이펙트 (None = 원본)
+    pub effects: Option<EffectParams>,
+}
+
+/// 레이어 텍스처 슬롯 (크기가 같으면 프레임 간 재사용)
+struct LayerSlot {
+    texture: wgpu::Texture,
+    uniform: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    width: u32,
+    height: u32,
+}
+
+/// 출력 텍스처 + readback 버퍼
+struct OutputTarget {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    readback: wgpu::Buffer,
+    width: u32,
+    height: u32,
+}
+
+/// GPU 합성기 (프리뷰 Renderer당 1개)
+pub struct GpuCompositor {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    slots: Vec<LayerSlot>,
+    output: Option<OutputTarget>,
+    adapter_name: String,
+}
+
+impl GpuCompositor {
+    /// 기본 GPU 어댑터로 초기화 (어댑터/디바이스 없으면 Err → CPU 합성 유지)
+    pub fn new() -> Result<Self, String> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))
+        .ok_or_else(|| "No GPU adapter available".to_string())?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("vortexcut-compositor"),
+                required_features: wgpu::Features::empty(),
+                // 4K 텍스처를 위해 어댑터 최대 해상도 사용
+                required_limits: wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))
+        .map_err(|e| format!("Failed to create GPU device: {}", e))?;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("gpu_composite"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("gpu_composite.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("layer"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(UNIFORM_SIZE),
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("gpu_composite"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("gpu_composite"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: TEXTURE_FORMAT,
+                    // 셰이더가 premultiplied 출력 → source-over
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // 바이리니어 + 가장자리 클램프 (CPU sample_bilinear와 동일)
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("layer"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            sampler,
+            slots: Vec::new(),
+            output: None,
+            adapter_name: adapter.get_info().name,
+        })
+    }
+
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    /// 마지막 합성 결과 텍스처 (호스트가 같은 디바이스에서 직접 표시, readback 생략)
+    pub fn output_texture(&self) -> Option<&wgpu::Texture> {
+        self.output.as_ref().map(|o| &o.texture)
+    }
+
+    /// 배경색 위에 레이어를 아래 → 위 순서로 합성 (결과는 출력 텍스처에 유지)
+    pub fn composite(&mut self, width: u32, height: u32, background: [u8; 3], layers: &[GpuLayer]) -> Result<(), String> {
+        if width == 0 || height == 0 {
+            return Err(format!("Invalid canvas size {}x{}", width, height));
+        }
+        if let Some(layer) = layers.iter().find(|l| l.data.len() < (l.width * l.height * 4) as usize) {
+            return Err(format!("Layer data too small for {}x{}", layer.width, layer.height));
+        }
+        self.ensure_output(width, height);
+
+        // 그릴 레이어만 슬롯에 업로드 (크기 0/투명/잘못된 배율은 CPU와 같이 건너뜀)
+        let mut draws = 0;
+        for layer in layers {
+            let t = &layer.transform;
+            if layer.width == 0 || layer.height == 0 || t.opacity <= 0.0 || t.scale <= 0.0 || !t.scale.is_finite() {
+                continue;
+            }
+            self.upload_layer(draws, layer, width, height);
+            draws += 1;
+        }
+
+        let output = self.output.as_ref().expect("output target");
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("gpu_composite") });
+        {
+            let [r, g, b] = background.map(|c| c as f64 / 255.0);
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("gpu_composite"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &output.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color { r, g, b, a: 1.0 }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            for slot in &self.slots[..draws] {
+                pass.set_bind_group(0, &slot.bind_group, &[]);
+                pass.draw(0..3, 0..1);
+            }
+        }
+        self.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+
+    /// 마지막 합성 결과를 RGBA로 읽기 (GPU 완료 대기)
+    pub fn read_rgba(&self) -> Result<Vec<u8>, String> {
+        let output = self.output.as_ref().ok_or_else(|| "Nothing composited yet".to_string())?;
+        let row_bytes = output.width * 4;
+        let padded_row_bytes = padded_bytes_per_row(output.width);
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("gpu_readback") });
+        encoder.copy_texture_to_buffer(
+            output.texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &output.readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(output.height),
+                },
+            },
+            texture_size(output.width, output.height),
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = output.readback.slice(..);
+        let (tx, rx) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .map_err(|_| "GPU readback aborted".to_string())?
+            .map_err(|e| format!("GPU readback failed: {}", e))?;
+
+        // 256바이트 행 정렬 패딩 제거
+        let mut rgba = Vec::with_capacity((row_bytes * output.height) as usize);
+        {
+            let mapped = slice.get_mapped_range();
+            for row in mapped.chunks_exact(padded_row_bytes as usize).take(output.height as usize) {
+                rgba.extend_from_slice(&row[..row_bytes as usize]);
+            }
+        }
+        output.readback.unmap();
+        Ok(rgba)
+    }
+
+    /// 합성 + readback (CPU compositor 대체용)
+    pub fn composite_rgba(&mut self, width: u32, height: u32, background: [u8; 3], layers: &[GpuLayer]) -> Result<Vec<u8>, String> {
+        self.composite(width, height, background, layers)?;
+        self.read_rgba()
+    }
+
+    /// 출력 텍스처/readback 버퍼 준비 (크기 변경 시에만 재생성)
+    fn ensure_output(&mut self, width: u32, height: u32) {
+        if self.output.as_ref().is_some_and(|o| o.width == width && o.height == height) {
+            return;
+        }
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("gpu_composite_output"),
+            size: texture_size(width, height),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("gpu_composite_readback"),
+            size: padded_bytes_per_row(width) as u64 * height as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        self.output = Some(OutputTarget { texture, view, readback, width, height });
+    }
+
+    /// 레이어 텍스처 + uniform 업로드 (슬롯 크기가 다르면 텍스처 재생성)
+    fn upload_layer(&mut self, index: usize, layer: &GpuLayer, canvas_width: u32, canvas_height: u32) {
+        let reusable = self.slots.get(index).is_some_and(|s| s.width == layer.width && s.height == layer.height);
+        if !reusable {
+            let slot = self.create_slot(layer.width, layer.height);
+            if index < self.slots.len() {
+                self.slots[index] = slot;
+            } else {
+                self.slots.push(slot);
+            }
+        }
+        let slot = &self.slots[index];
+
+        self.queue.write_texture(
+            slot.texture.as_image_copy(),
+            &layer.data[..(layer.width * layer.height * 4) as usize],
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(layer.width * 4),
+                rows_per_image: Some(layer.height),
+            },
+            texture_size(layer.width, layer.height),
+        );
+        let uniforms = layer_uniforms(&layer.transform, layer.effects.as_ref(), canvas_width, canvas_height);
+        let bytes: Vec<u8> = uniforms.iter().flatten().flat_map(|v| v.to_le_bytes()).collect();
+        self.queue.write_buffer(&slot.uniform, 0, &bytes);
+    }
+
+    fn create_slot(&self, width: u32, height: u32) -> LayerSlot {
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("gpu_composite_layer"),
+            size: texture_size(width, height),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let uniform = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("gpu_composite_layer"),
+            size: UNIFORM_SIZE,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("gpu_composite_layer"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&self.sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: uniform.as_entire_binding() },
+            ],
+        });
+        LayerSlot { texture, uniform, bind_group, width, height }
+    }
+}
+
+fn texture_size(width: u32, height: u32) -> wgpu::Extent3d {
+    wgpu::Extent3d { width, height, depth_or_array_layers: 1 }
+}
+
+/// readback 행 크기 (COPY_BYTES_PER_ROW_ALIGNMENT 배수)
+fn padded_bytes_per_row(width: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    (width * 4).div_ceil(align) * align
+}
+
+/// 셰이더 Layer uniform 값 (트랜스폼은 compositor.rs, 이펙트 상수는 EffectKernel::new와 같은 계산)
+fn layer_uniforms(transform: &ClipTransform, effects: Option<&EffectParams>, canvas_width: u32, canvas_height: u32) -> [[f32; 4]; 8] {
+    let cwf = canvas_width as f32;
+    let chf = canvas_height as f32;
+    let (sin, cos) = transform.rotation_deg.to_radians().sin_cos();
+    let crop = &transform.crop;
+
+    let default = EffectParams::default();
+    let params = effects.unwrap_or(&default);
+    let active = |v: f32| if v.abs() >= 0.001 { 1.0 } else { 0.0 };
+
+    [
+        [cwf, chf, 0.0, 0.0],
+        [cwf / 2.0 + transform.offset_x * cwf, chf / 2.0 + transform.offset_y * chf, cos, sin],
+        [1.0 / transform.scale, transform.opacity.min(1.0), 0.0, 0.0],
+        [crop.left * cwf, (1.0 - crop.right) * cwf, crop.top * chf, (1.0 - crop.bottom) * chf],
+        [params.brightness * 255.0, 1.0 + params.contrast, 2f32.powf(-params.gamma), params.highlights],
+        [params.shadows, 1.0 + params.saturation, params.temperature * 30.0, -params.temperature * 30.0],
+        [
+            params.tint * 15.0,
+            -params.tint * 30.0,
+            params.vignette.abs(),
+            if params.vignette > 0.0 { 0.0 } else { 255.0 },
+        ],
+        [
+            active(params.gamma),
+            active(params.highlights).max(active(params.shadows)),
+            active(params.vignette),
+            VIGNETTE_INNER_RADIUS,
+        ],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rendering::compositor::composite_rgba;
+    use crate::rendering::effects::apply_effects;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Vec<u8> {
+        rgba.iter().copied().cycle().take((width * height * 4) as usize).collect()
+    }
+
+    /// 채널별 최대 차이 (GPU 보간/반올림 차이 허용용)
+    fn max_diff(a: &[u8], b: &[u8]) -> u8 {
+        a.iter().zip(b).map(|(x, y)| x.abs_diff(*y)).max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_padded_bytes_per_row() {
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+        assert_eq!(padded_bytes_per_row(960), 3840);
+    }
+
+    #[test]
+    fn test_gpu_matches_cpu_composite() {
+        // GPU 어댑터가 없는 환경은 건너뜀
+        let mut gpu = match GpuCompositor::new() {
+            Ok(gpu) => gpu,
+            Err(_) => return,
+        };
+        let (width, height) = (64, 32);
+        let background = [0, 0, 255];
+        let base = solid(width, height, [200, 100, 50, 255]);
+        let pip = solid(width, height, [20, 220, 120, 255]);
+        let pip_transform = ClipTransform { scale: 0.5, offset_x: 0.25, opacity: 0.5, ..ClipTransform::default() };
+        let effects = EffectParams { brightness: 0.1, saturation: -0.5, ..EffectParams::default() };
+
+        let gpu_rgba = gpu.composite_rgba(width, height, background, &[
+            GpuLayer { data: &base, width, height, transform: ClipTransform::default(), effects: Some(effects.clone()) },
+            GpuLayer { data: &pip, width, height, transform: pip_transform, effects: None },
+        ]).unwrap();
+
+        let mut cpu_rgba = solid(width, height, [0, 0, 255, 255]);
+        let mut graded = base.clone();
+        apply_effects(&mut graded, width, height, &effects);
+        composite_rgba(&mut cpu_rgba, width, height, &graded, width, height, &ClipTransform::default());
+        composite_rgba(&mut cpu_rgba, width, height, &pip, width, height, &pip_transform);
+
+        assert_eq!(gpu_rgba.len(), cpu_rgba.len());
+        assert!(max_diff(&gpu_rgba, &cpu_rgba) <= 2);
+        assert!(gpu.output_texture().is_some());
+    }
+}
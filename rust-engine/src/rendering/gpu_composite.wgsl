@@ -0,0 +1,85 @@
+// 레이어 합성 셰이더 - compositor.rs (트랜스폼/크롭/바이리니어/source-over), effects.rs (픽셀 연산)와 같은 수식
+// 레이어 하나 = 캔버스 전체 삼각형 1회 그리기, 역변환으로 레이어 좌표 계산 (CPU composite_rgba와 동일)
+// 출력은 premultiplied alpha (배경이 불투명하므로 결과 알파는 항상 1)
+
+struct Layer {
+    // 캔버스 폭, 높이
+    canvas: vec4<f32>,
+    // 중심 x, y (px), cos, sin
+    placement: vec4<f32>,
+    // 1/scale, opacity
+    scale: vec4<f32>,
+    // 크롭 후 남는 영역 min_u, max_u, min_v, max_v (변환 전 캔버스 px)
+    crop: vec4<f32>,
+    // brightness 오프셋, contrast 배율, gamma 지수, highlights
+    fx0: vec4<f32>,
+    // shadows, saturation 배율, temperature R, temperature B
+    fx1: vec4<f32>,
+    // tint R/B, tint G, vignette 강도, vignette 목표값
+    fx2: vec4<f32>,
+    // gamma, tone, vignette 사용 여부 (1 = 사용), vignette 시작 반경
+    flags: vec4<f32>,
+}
+
+@group(0) @binding(0) var layer_texture: texture_2d<f32>;
+@group(0) @binding(1) var layer_sampler: sampler;
+@group(0) @binding(2) var<uniform> layer: Layer;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // 화면 전체를 덮는 삼각형
+    let x = f32((index << 1u) & 2u) * 2.0 - 1.0;
+    let y = f32(index & 2u) * 2.0 - 1.0;
+    return vec4<f32>(x, y, 0.0, 1.0);
+}
+
+fn luminance(c: vec3<f32>) -> f32 {
+    // BT.709 가중치
+    return dot(c, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// effects.rs EffectKernel::apply_pixel과 같은 순서 (0~255 값 기준)
+fn apply_effects(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    var c = color + vec3<f32>(layer.fx0.x);
+    c = 128.0 + (c - 128.0) * layer.fx0.y;
+    if (layer.flags.x > 0.5) {
+        c = 255.0 * pow(clamp(c, vec3<f32>(0.0), vec3<f32>(255.0)) / 255.0, vec3<f32>(layer.fx0.z));
+    }
+    if (layer.flags.y > 0.5) {
+        let lum = clamp(luminance(c) / 255.0, 0.0, 1.0);
+        c = c + vec3<f32>((layer.fx0.w * lum * lum + layer.fx1.x * (1.0 - lum) * (1.0 - lum)) * 128.0);
+    }
+    let lum = luminance(c);
+    c = lum + (c - lum) * layer.fx1.y;
+    c = c + vec3<f32>(layer.fx1.z + layer.fx2.x, layer.fx2.y, layer.fx1.w + layer.fx2.x);
+    if (layer.flags.z > 0.5) {
+        let d = uv * 2.0 - 1.0;
+        let dist = sqrt(dot(d, d) / 2.0);
+        let inner = layer.flags.w;
+        let t = clamp((dist - inner) / (1.0 - inner), 0.0, 1.0);
+        let weight = t * t * (3.0 - 2.0 * t) * layer.fx2.z;
+        c = c + (layer.fx2.w - c) * weight;
+    }
+    return clamp(c, vec3<f32>(0.0), vec3<f32>(255.0));
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let canvas = layer.canvas.xy;
+    let d = position.xy - layer.placement.xy;
+    let cos_t = layer.placement.z;
+    let sin_t = layer.placement.w;
+
+    // 역변환: 회전(-θ) → 스케일(1/s) → 변환 전 캔버스 좌표
+    let u = (d.x * cos_t + d.y * sin_t) * layer.scale.x + canvas.x / 2.0;
+    let v = (-d.x * sin_t + d.y * cos_t) * layer.scale.x + canvas.y / 2.0;
+    if (u < layer.crop.x || u >= layer.crop.y || v < layer.crop.z || v >= layer.crop.w) {
+        discard;
+    }
+
+    let uv = vec2<f32>(u, v) / canvas;
+    let texel = textureSampleLevel(layer_texture, layer_sampler, uv, 0.0);
+    let rgb = apply_effects(texel.rgb * 255.0, uv) / 255.0;
+    let alpha = texel.a * layer.scale.y;
+    return vec4<f32>(rgb * alpha, alpha);
+}
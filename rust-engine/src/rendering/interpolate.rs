@@ -0,0 +1,256 @@
+// 프레임 보간 - 양방향 블록 매칭 모션 추정 + 모션 보상 블렌드
+// 슬로우 모션 등 소스 프레임 사이 시점이 필요할 때 프레임 복제 대신 중간 프레임 생성
+
+/// 모션 블록 크기 (원본 해상도 기준 픽셀)
+const BLOCK_SIZE: usize = 16;
+
+/// 탐색 범위 (원본 해상도 기준 ±픽셀)
+const SEARCH_RANGE: i32 = 8;
+
+/// 모션 추정 다운샘플 비율 (2 → 1/2 해상도에서 탐색)
+const ME_SCALE: usize = 2;
+
+/// 출력 프레임 블록별 모션 벡터 (A → B 총 이동량, 원본 해상도 픽셀 단위)
+struct MotionField {
+    cols: usize,
+    rows: usize,
+    vectors: Vec<(i32, i32)>,
+}
+
+impl MotionField {
+    /// 픽셀 좌표의 모션 벡터 (최근접 블록)
+    #[inline]
+    fn vector_at(&self, x: usize, y: usize) -> (i32, i32) {
+        let col = (x / BLOCK_SIZE).min(self.cols - 1);
+        let row = (y / BLOCK_SIZE).min(self.rows - 1);
+        self.vectors[row * self.cols + col]
+    }
+}
+
+/// 1/ME_SCALE 다운샘플 (박스 필터)
+fn downsample(luma: &[u8], width: usize, height: usize) -> (Vec<u8>, usize, usize) {
+    let w = (width / ME_SCALE).max(1);
+    let h = (height / ME_SCALE).max(1);
+    let mut out = vec![0u8; w * h];
+
+    for y in 0..h {
+        for x in 0..w {
+            let mut sum = 0u32;
+            let mut count = 0u32;
+            for dy in 0..ME_SCALE {
+                for dx in 0..ME_SCALE {
+                    let sx = (x * ME_SCALE + dx).min(width - 1);
+                    let sy = (y * ME_SCALE + dy).min(height - 1);
+                    sum += luma[sy * width + sx] as u32;
+                    count += 1;
+                }
+            }
+            out[y * w + x] = (sum / count) as u8;
+        }
+    }
+
+    (out, w, h)
+}
+
+/// 양방향 블록 매칭 모션 추정 (다운샘플 해상도에서 전역 탐색)
+/// 출력(t 시점) 블록마다 A[p - t·v]와 B[p + (1-t)·v]의 SAD가 최소인 v 선택
+/// → A 기준 단방향 탐색과 달리 새로 드러나는 영역에도 구멍이 생기지 않음
+fn estimate_motion(luma_a: &[u8], luma_b: &[u8], width: usize, height: usize, t: f32) -> MotionField {
+    let cols = width.div_ceil(BLOCK_SIZE).max(1);
+    let rows = height.div_ceil(BLOCK_SIZE).max(1);
+
+    let (a, w, h) = downsample(luma_a, width, height);
+    let (b, _, _) = downsample(luma_b, width, height);
+
+    let block = BLOCK_SIZE / ME_SCALE;
+    let range = SEARCH_RANGE / ME_SCALE as i32;
+
+    let mut vectors = Vec::with_capacity(cols * rows);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let bx = col * block;
+            let by = row * block;
+            let bw = block.min(w.saturating_sub(bx));
+            let bh = block.min(h.saturating_sub(by));
+            if bw == 0 || bh == 0 {
+                vectors.push((0, 0));
+                continue;
+            }
+
+            let mut best = (0i32, 0i32);
+            let mut best_sad = u32::MAX;
+
+            for vy in -range..=range {
+                for vx in -range..=range {
+                    let sad = block_sad(&a, &b, w, h, bx, by, bw, bh, vx, vy, t);
+                    // 동점이면 작은 벡터 우선 (정지 영역 안정화)
+                    if sad < best_sad
+                        || (sad == best_sad && vx.abs() + vy.abs() < best.0.abs() + best.1.abs())
+                    {
+                        best_sad = sad;
+                        best = (vx, vy);
+                    }
+                }
+            }
+
+            vectors.push((best.0 * ME_SCALE as i32, best.1 * ME_SCALE as i32));
+        }
+    }
+
+    MotionField { cols, rows, vectors }
+}
+
+/// 출력 블록 기준 A[p - t·v]와 B[p + (1-t)·v] 간 SAD
+#[allow(clippy::too_many_arguments)]
+#[inline]
+fn block_sad(
+    a: &[u8], b: &[u8], w: usize, h: usize,
+    bx: usize, by: usize, bw: usize, bh: usize,
+    vx: i32, vy: i32, t: f32,
+) -> u32 {
+    // 총 이동량이 정확히 v가 되도록 A쪽만 반올림
+    let ax_off = -(t * vx as f32).round() as i32;
+    let ay_off = -(t * vy as f32).round() as i32;
+    let bx_off = vx + ax_off;
+    let by_off = vy + ay_off;
+
+    let mut sad = 0u32;
+    for y in by..by + bh {
+        let ay = (y as i32 + ay_off).clamp(0, h as i32 - 1) as usize;
+        let sy = (y as i32 + by_off).clamp(0, h as i32 - 1) as usize;
+        for x in bx..bx + bw {
+            let ax = (x as i32 + ax_off).clamp(0, w as i32 - 1) as usize;
+            let sx = (x as i32 + bx_off).clamp(0, w as i32 - 1) as usize;
+            sad += (a[ay * w + ax] as i32 - b[sy * w + sx] as i32).unsigned_abs();
+        }
+    }
+    sad
+}
+
+/// 모션 보상 블렌드 (한 평면): 출력 p = (1-t)·A[p - t·v] + t·B[p + (1-t)·v]
+/// - bpp: 픽셀당 바이트 (RGBA=4, YUV 평면=1)
+/// - scale: 평면 해상도 축소 비율 (Y/RGBA=1, YUV420 크로마=2)
+#[allow(clippy::too_many_arguments)]
+fn blend_plane(
+    a: &[u8], b: &[u8], out: &mut [u8],
+    width: usize, height: usize, bpp: usize, scale: usize,
+    field: &MotionField, t: f32,
+) {
+    let wa = 1.0 - t;
+    for y in 0..height {
+        for x in 0..width {
+            let (vx, vy) = field.vector_at(x * scale, y * scale);
+            let vx = vx as f32 / scale as f32;
+            let vy = vy as f32 / scale as f32;
+
+            let ax = (x as f32 - t * vx).round().clamp(0.0, (width - 1) as f32) as usize;
+            let ay = (y as f32 - t * vy).round().clamp(0.0, (height - 1) as f32) as usize;
+            let bx = (x as f32 + wa * vx).round().clamp(0.0, (width - 1) as f32) as usize;
+            let by = (y as f32 + wa * vy).round().clamp(0.0, (height - 1) as f32) as usize;
+
+            let o = (y * width + x) * bpp;
+            let ia = (ay * width + ax) * bpp;
+            let ib = (by * width + bx) * bpp;
+            for c in 0..bpp {
+                out[o + c] = (a[ia + c] as f32 * wa + b[ib + c] as f32 * t).round() as u8;
+            }
+        }
+    }
+}
+
+/// RGBA 두 프레임 사이 t(0.0~1.0) 시점의 보간 프레임 생성
+/// 크기가 맞지 않으면 None
+pub fn interpolate_rgba(a: &[u8], b: &[u8], width: u32, height: u32, t: f32) -> Option<Vec<u8>> {
+    let (w, h) = (width as usize, height as usize);
+    let len = w * h * 4;
+    if w == 0 || h == 0 || a.len() < len || b.len() < len {
+        return None;
+    }
+    let t = t.clamp(0.0, 1.0);
+
+    // BT.709 근사 luma (정수 연산)
+    let luma = |data: &[u8]| -> Vec<u8> {
+        data[..len]
+            .chunks_exact(4)
+            .map(|p| ((p[0] as u32 * 54 + p[1] as u32 * 183 + p[2] as u32 * 19) >> 8) as u8)
+            .collect()
+    };
+    let field = estimate_motion(&luma(a), &luma(b), w, h, t);
+
+    let mut out = vec![0u8; len];
+    blend_plane(&a[..len], &b[..len], &mut out, w, h, 4, 1, &field, t);
+    Some(out)
+}
+
+/// YUV420P 두 프레임 사이 t(0.0~1.0) 시점의 보간 프레임 생성 (Export 경로)
+/// 모션은 Y 평면에서 추정하고 크로마는 절반 해상도로 같은 벡터 적용
+pub fn interpolate_yuv420p(a: &[u8], b: &[u8], width: u32, height: u32, t: f32) -> Option<Vec<u8>> {
+    let (w, h) = (width as usize, height as usize);
+    let y_size = w * h;
+    let (cw, ch) = (w / 2, h / 2);
+    let uv_size = cw * ch;
+    let len = y_size + uv_size * 2;
+    if w < 2 || h < 2 || a.len() < len || b.len() < len {
+        return None;
+    }
+    let t = t.clamp(0.0, 1.0);
+
+    let field = estimate_motion(&a[..y_size], &b[..y_size], w, h, t);
+
+    let mut out = vec![0u8; len];
+    blend_plane(&a[..y_size], &b[..y_size], &mut out[..y_size], w, h, 1, 1, &field, t);
+    for plane in 0..2 {
+        let start = y_size + plane * uv_size;
+        let end = start + uv_size;
+        blend_plane(&a[start..end], &b[start..end], &mut out[start..end], cw, ch, 1, 2, &field, t);
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 검은 배경 위 흰 사각형 (x 위치 지정)
+    fn square_frame(width: usize, height: usize, square_x: usize) -> Vec<u8> {
+        let mut data = vec![0u8; width * height * 4];
+        for y in 16..32 {
+            for x in square_x..square_x + 16 {
+                let i = (y * width + x) * 4;
+                data[i..i + 4].copy_from_slice(&[255, 255, 255, 255]);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_interpolate_identical_frames() {
+        let frame = square_frame(64, 48, 16);
+        let out = interpolate_rgba(&frame, &frame, 64, 48, 0.5).unwrap();
+        assert_eq!(out, frame);
+    }
+
+    #[test]
+    fn test_interpolate_moving_square() {
+        // 사각형이 8px 이동 → 중간 프레임에서는 4px 이동 위치
+        let a = square_frame(64, 48, 16);
+        let b = square_frame(64, 48, 24);
+        let out = interpolate_rgba(&a, &b, 64, 48, 0.5).unwrap();
+        let expected = square_frame(64, 48, 20);
+
+        // 단순 블렌드라면 중앙 행이 회색(128)이 되지만, 모션 보상 시 대부분 원색 유지
+        let mismatched = out
+            .chunks_exact(4)
+            .zip(expected.chunks_exact(4))
+            .filter(|(o, e)| (o[0] as i32 - e[0] as i32).abs() > 32)
+            .count();
+        assert!(mismatched < 32, "mismatched pixels: {}", mismatched);
+    }
+
+    #[test]
+    fn test_interpolate_size_mismatch() {
+        assert!(interpolate_rgba(&[0u8; 16], &[0u8; 8], 2, 2, 0.5).is_none());
+        assert!(interpolate_yuv420p(&[0u8; 6], &[0u8; 6], 2, 2, 0.5).is_some());
+    }
+}
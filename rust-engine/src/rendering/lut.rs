@@ -0,0 +1,218 @@
+// 3D LUT - .cube 파일 파싱 + RGBA 트라이리니어 적용
+// 같은 LUT 파일을 쓰는 클립들은 LutCache를 통해 파싱 결과(Arc)를 공유
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// LUT 격자 최대 크기 (일반적으로 17/33/65)
+const MAX_LUT_SIZE: usize = 256;
+
+/// 3D LUT (R이 가장 빠르게 변하는 .cube 순서)
+#[derive(Debug, Clone)]
+pub struct Lut3D {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    table: Vec<[f32; 3]>,
+}
+
+impl Lut3D {
+    /// .cube 텍스트 파싱 (Adobe/Resolve 3D LUT 포맷)
+    pub fn parse_cube(text: &str) -> Result<Self, String> {
+        let mut size = 0usize;
+        let mut domain_min = [0.0f32; 3];
+        let mut domain_max = [1.0f32; 3];
+        let mut table = Vec::new();
+
+        for (line_no, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let key = parts.next().unwrap_or_default();
+
+            match key {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => return Err("1D LUT is not supported".to_string()),
+                "LUT_3D_SIZE" => {
+                    size = parts.next()
+                        .and_then(|v| v.parse().ok())
+                        .filter(|v| (2..=MAX_LUT_SIZE).contains(v))
+                        .ok_or_else(|| format!("Invalid LUT_3D_SIZE at line {}", line_no + 1))?;
+                    table.reserve(size * size * size);
+                }
+                "DOMAIN_MIN" => domain_min = parse_triplet(parts, line_no)?,
+                "DOMAIN_MAX" => domain_max = parse_triplet(parts, line_no)?,
+                _ => {
+                    // 데이터 행 (키워드가 아닌 숫자 3개)
+                    let values = parse_triplet(line.split_whitespace(), line_no)?;
+                    table.push(values);
+                }
+            }
+        }
+
+        if size == 0 {
+            return Err("Missing LUT_3D_SIZE".to_string());
+        }
+        if table.len() != size * size * size {
+            return Err(format!(
+                "LUT data count mismatch: expected {}, got {}",
+                size * size * size,
+                table.len()
+            ));
+        }
+
+        Ok(Self { size, domain_min, domain_max, table })
+    }
+
+    /// .cube 파일 로드
+    pub fn load_cube(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read LUT {:?}: {}", path, e))?;
+        Self::parse_cube(&text)
+    }
+
+    #[inline]
+    fn entry(&self, r: usize, g: usize, b: usize) -> [f32; 3] {
+        self.table[(b * self.size + g) * self.size + r]
+    }
+
+    /// 정규화 RGB(0.0~1.0) → LUT 적용 결과 (트라이리니어 보간)
+    fn lookup(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let max_index = (self.size - 1) as f32;
+        let mut idx = [0usize; 3];
+        let mut frac = [0.0f32; 3];
+
+        for c in 0..3 {
+            let range = (self.domain_max[c] - self.domain_min[c]).max(f32::EPSILON);
+            let pos = ((rgb[c] - self.domain_min[c]) / range).clamp(0.0, 1.0) * max_index;
+            let i = (pos.floor() as usize).min(self.size - 2);
+            idx[c] = i;
+            frac[c] = pos - i as f32;
+        }
+
+        let [r, g, b] = idx;
+        let [fr, fg, fb] = frac;
+        let mut out = [0.0f32; 3];
+
+        for (c, value) in out.iter_mut().enumerate() {
+            let c000 = self.entry(r, g, b)[c];
+            let c100 = self.entry(r + 1, g, b)[c];
+            let c010 = self.entry(r, g + 1, b)[c];
+            let c110 = self.entry(r + 1, g + 1, b)[c];
+            let c001 = self.entry(r, g, b + 1)[c];
+            let c101 = self.entry(r + 1, g, b + 1)[c];
+            let c011 = self.entry(r, g + 1, b + 1)[c];
+            let c111 = self.entry(r + 1, g + 1, b + 1)[c];
+
+            let c00 = c000 + (c100 - c000) * fr;
+            let c10 = c010 + (c110 - c010) * fr;
+            let c01 = c001 + (c101 - c001) * fr;
+            let c11 = c011 + (c111 - c011) * fr;
+            let c0 = c00 + (c10 - c00) * fg;
+            let c1 = c01 + (c11 - c01) * fg;
+            *value = c0 + (c1 - c0) * fb;
+        }
+
+        out
+    }
+
+    /// RGBA 버퍼에 LUT 적용 (in-place, Alpha 유지)
+    pub fn apply_rgba(&self, data: &mut [u8]) {
+        for px in data.chunks_exact_mut(4) {
+            let rgb = [
+                px[0] as f32 / 255.0,
+                px[1] as f32 / 255.0,
+                px[2] as f32 / 255.0,
+            ];
+            let out = self.lookup(rgb);
+            px[0] = (out[0] * 255.0).round().clamp(0.0, 255.0) as u8;
+            px[1] = (out[1] * 255.0).round().clamp(0.0, 255.0) as u8;
+            px[2] = (out[2] * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+fn parse_triplet<'a>(mut parts: impl Iterator<Item = &'a str>, line_no: usize) -> Result<[f32; 3], String> {
+    let mut values = [0.0f32; 3];
+    for v in values.iter_mut() {
+        *v = parts.next()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| format!("Invalid LUT line {}", line_no + 1))?;
+    }
+    Ok(values)
+}
+
+/// 파싱된 LUT 캐시 (파일 경로 → 공유 LUT)
+#[derive(Default)]
+pub struct LutCache {
+    entries: HashMap<PathBuf, Arc<Lut3D>>,
+}
+
+impl LutCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 캐시에서 조회, 없으면 로드 후 저장
+    pub fn get_or_load(&mut self, path: &Path) -> Result<Arc<Lut3D>, String> {
+        if let Some(lut) = self.entries.get(path) {
+            return Ok(lut.clone());
+        }
+
+        let lut = Arc::new(Lut3D::load_cube(path)?);
+        self.entries.insert(path.to_path_buf(), lut.clone());
+        Ok(lut)
+    }
+
+    /// 캐시 비우기 (LUT 파일이 디스크에서 바뀐 경우)
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2x2x2 LUT: 색 반전
+    const INVERT_CUBE: &str = "\
+TITLE \"invert\"
+# comment
+LUT_3D_SIZE 2
+1 1 1
+0 1 1
+1 0 1
+0 0 1
+1 1 0
+0 1 0
+1 0 0
+0 0 0
+";
+
+    #[test]
+    fn test_parse_and_apply_cube() {
+        let lut = Lut3D::parse_cube(INVERT_CUBE).unwrap();
+        let mut data = vec![0u8, 128, 255, 200];
+        lut.apply_rgba(&mut data);
+        assert_eq!(data, vec![255, 127, 0, 200]);
+    }
+
+    #[test]
+    fn test_parse_cube_errors() {
+        assert!(Lut3D::parse_cube("LUT_3D_SIZE 2\n0 0 0\n").is_err());
+        assert!(Lut3D::parse_cube("LUT_1D_SIZE 4\n").is_err());
+        assert!(Lut3D::parse_cube("0 0 0\n").is_err());
+    }
+}
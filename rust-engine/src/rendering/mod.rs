@@ -2,5 +2,18 @@
 
 pub mod renderer;
 pub mod effects;
+pub mod simd;
+pub mod interpolate;
+pub mod decode_scheduler;
+pub mod decoder_pool;
+pub mod lut;
+pub mod prefetch;
+pub mod frame_request;
+pub mod compositor;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+#[cfg(all(windows, feature = "d3d11"))]
+pub mod shared_texture;
+pub mod transition;
 
-pub use renderer::{Renderer, RenderedFrame};
+pub use renderer::{PreviewResolution, Renderer, RenderedFrame};
@@ -0,0 +1,285 @@
+// 재생 미리 읽기 (read-ahead) - 재생 헤드 앞쪽 소스 프레임을 백그라운드 스레드에서 디코딩
+// Renderer가 재생 중 매 프레임 다음 구간 소스 시간을 요청 → 워커가 전용 디코더로 디코딩 → 공유 저장소
+// Renderer는 FrameCache 미스 시 저장소 프레임을 꺼내 LUT/이펙트 적용 후 FrameCache에 저장 (디코딩 생략)
+
+use crate::ffmpeg::{Decoder, DecodeResult, Frame};
+use crate::rendering::decode_scheduler::{DecodePriority, DecodeScheduler};
+use crate::rendering::decoder_pool::{DecoderPool, DEFAULT_MAX_DECODERS};
+use crate::timeline::clip::source_key;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// 재생 헤드 앞으로 미리 디코딩할 기본 프레임 수 (클립당)
+pub const DEFAULT_PREFETCH_FRAMES: u32 = 8;
+
+/// 저장소 크기 = 프레임 수 × 동시 클립 수 (PiP 등 여러 트랙 재생)
+const STORE_CLIPS: usize = 3;
+
+/// 워커 디코더 forward decode 임계값 (재생 모드와 동일)
+const PREFETCH_FORWARD_THRESHOLD_MS: i64 = 5000;
+
+/// 미리 읽기 요청 (파일 하나의 소스 시간 목록, 재생 순서)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefetchRequest {
+    pub file_path: PathBuf,
+    /// 비디오 스트림 (None이면 기본 스트림)
+    pub stream_index: Option<usize>,
+    /// 원본 대신 디코딩할 프록시 (저장소 키는 원본 소스 키 그대로)
+    pub proxy_path: Option<PathBuf>,
+    pub source_times: Vec<i64>,
+}
+
+/// 디코딩 완료 프레임 (frame.timestamp_ms = 요청한 소스 시간)
+struct PrefetchedFrame {
+    /// 소스 키 (경로 + 스트림, timeline::source_key)
+    file_path: String,
+    frame: Frame,
+    /// 소스 프레임 길이 (조회 허용 오차 계산용)
+    frame_duration_ms: i64,
+}
+
+impl PrefetchedFrame {
+    /// 같은 파일 + 소스 시간이 반 프레임 이내
+    fn matches(&self, file_path: &str, source_time_ms: i64) -> bool {
+        self.file_path == file_path
+            && (source_time_ms - self.frame.timestamp_ms).abs() * 2 < self.frame_duration_ms.max(1)
+    }
+}
+
+/// 워커와 Renderer가 공유하는 프레임 저장소 (가득 차면 오래된 것부터 제거)
+struct PrefetchStore {
+    frames: VecDeque<PrefetchedFrame>,
+    max_frames: usize,
+}
+
+impl PrefetchStore {
+    fn new(max_frames: usize) -> Self {
+        Self { frames: VecDeque::new(), max_frames: max_frames.max(1) }
+    }
+
+    fn contains(&self, file_path: &str, source_time_ms: i64) -> bool {
+        self.frames.iter().any(|f| f.matches(file_path, source_time_ms))
+    }
+
+    fn push(&mut self, frame: PrefetchedFrame) {
+        while self.frames.len() >= self.max_frames {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// 프레임 꺼내기 (꺼낸 프레임은 FrameCache로 이동하므로 저장소에서 제거)
+    fn take(&mut self, file_path: &str, source_time_ms: i64) -> Option<Frame> {
+        let index = self.frames.iter().position(|f| f.matches(file_path, source_time_ms))?;
+        self.frames.remove(index).map(|f| f.frame)
+    }
+}
+
+/// 워커 공유 상태
+struct PrefetchShared {
+    store: Mutex<PrefetchStore>,
+    /// 최신 요청 (워커가 가져가면 None, 처리 중 새 요청이 오면 이전 요청은 버림)
+    pending: Mutex<Option<Vec<PrefetchRequest>>>,
+    wake: Condvar,
+    stopped: AtomicBool,
+}
+
+impl PrefetchShared {
+    /// 다음 요청 대기 (중지되면 None)
+    fn next_batch(&self) -> Option<Vec<PrefetchRequest>> {
+        let mut pending = self.pending.lock().ok()?;
+        loop {
+            if self.stopped.load(Ordering::SeqCst) {
+                return None;
+            }
+            if let Some(batch) = pending.take() {
+                return Some(batch);
+            }
+            pending = self.wake.wait(pending).ok()?;
+        }
+    }
+
+    /// 현재 요청 처리 중단 여부 (중지 또는 재생 헤드 이동으로 새 요청 도착)
+    fn interrupted(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+            || self.pending.lock().map(|p| p.is_some()).unwrap_or(true)
+    }
+}
+
+/// 미리 읽기 워커 핸들 (drop 시 워커 종료)
+pub struct Prefetcher {
+    shared: Arc<PrefetchShared>,
+    frames: u32,
+}
+
+impl Prefetcher {
+    /// 워커 시작
+    /// - frames: 클립당 재생 헤드 앞으로 디코딩할 프레임 수
+    /// - preview_size: 디코딩 출력 크기 (Renderer 프리뷰 해상도, 바뀌면 워커를 다시 시작)
+    pub fn start(frames: u32, preview_size: (u32, u32)) -> Self {
+        let shared = Arc::new(PrefetchShared {
+            store: Mutex::new(PrefetchStore::new(frames as usize * STORE_CLIPS)),
+            pending: Mutex::new(None),
+            wake: Condvar::new(),
+            stopped: AtomicBool::new(false),
+        });
+        let worker = shared.clone();
+        std::thread::spawn(move || run_worker(&worker, preview_size));
+        Self { shared, frames }
+    }
+
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// 다음 구간 요청 (아직 처리하지 못한 이전 요청은 교체)
+    pub fn request(&self, requests: Vec<PrefetchRequest>) {
+        if let Ok(mut pending) = self.shared.pending.lock() {
+            *pending = Some(requests);
+            self.shared.wake.notify_one();
+        }
+    }
+
+    /// 미리 디코딩된 프레임 꺼내기 (없으면 None → 호출자가 직접 디코딩)
+    pub fn take(&self, file_path: &str, source_time_ms: i64) -> Option<Frame> {
+        self.shared.store.lock().ok()?.take(file_path, source_time_ms)
+    }
+}
+
+impl Drop for Prefetcher {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::SeqCst);
+        // 대기 중인 워커 깨우기 (lock을 잡아 wait 진입 전 알림 유실 방지)
+        let _pending = self.shared.pending.lock();
+        self.shared.wake.notify_all();
+    }
+}
+
+/// 워커 루프 (파일별 전용 디코더 LRU, 프리뷰 디코딩에 양보)
+fn run_worker(shared: &PrefetchShared, preview_size: (u32, u32)) {
+    let mut decoders = DecoderPool::new(DEFAULT_MAX_DECODERS);
+    while let Some(batch) = shared.next_batch() {
+        for request in batch {
+            if !prefetch_file(shared, &mut decoders, request, preview_size) {
+                break;
+            }
+        }
+    }
+}
+
+/// 파일 하나의 요청 처리 (반환: false면 중단됨)
+fn prefetch_file(
+    shared: &PrefetchShared,
+    decoders: &mut DecoderPool<Decoder>,
+    request: PrefetchRequest,
+    preview_size: (u32, u32),
+) -> bool {
+    let file_path = source_key(&request.file_path, request.stream_index);
+    for source_time_ms in request.source_times {
+        if shared.interrupted() {
+            return false;
+        }
+        let cached = shared.store.lock().map(|s| s.contains(&file_path, source_time_ms)).unwrap_or(true);
+        if cached {
+            continue;
+        }
+
+        if !decoders.contains_key(&file_path) {
+            let opened = match &request.proxy_path {
+                Some(proxy) => Decoder::open_stream(proxy, None, None, preview_size, false),
+                None => Decoder::open_stream(&request.file_path, request.stream_index, None, preview_size, false),
+            };
+            match opened {
+                Ok(mut decoder) => {
+                    decoder.set_forward_threshold(PREFETCH_FORWARD_THRESHOLD_MS);
+                    decoders.insert(file_path.clone(), decoder);
+                }
+                // 열기 실패 → 이 파일은 Renderer가 직접 디코딩
+                Err(_) => return true,
+            }
+        }
+        let decoder = match decoders.get_mut(&file_path) {
+            Some(decoder) => decoder,
+            None => return true,
+        };
+
+        // 미리 읽기는 Export와 같은 낮은 우선순위 (프리뷰 디코딩 대기/진행 중이면 양보)
+        let result = {
+            let _permit = DecodeScheduler::global().acquire(DecodePriority::Export);
+            decoder.decode_frame(source_time_ms)
+        };
+        let frame = match result {
+            Ok(DecodeResult::Frame(frame)) => frame,
+            // EOF/스킵/에러 → 이후 시간도 의미 없음
+            _ => return true,
+        };
+        // 프레임 자체 길이 우선 (VFR 소스는 평균 fps와 다름)
+        let frame_duration_ms = if frame.duration_ms > 0 {
+            frame.duration_ms
+        } else if decoder.fps() > 0.0 {
+            (1000.0 / decoder.fps()).max(1.0) as i64
+        } else {
+            33
+        };
+        if let Ok(mut store) = shared.store.lock() {
+            store.push(PrefetchedFrame { file_path: file_path.clone(), frame, frame_duration_ms });
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffmpeg::{PixelFormat, YuvColor};
+
+    fn prefetched(file_path: &str, timestamp_ms: i64) -> PrefetchedFrame {
+        PrefetchedFrame {
+            file_path: file_path.to_string(),
+            frame: Frame {
+                width: 1, height: 1, format: PixelFormat::RGBA, data: vec![0; 4], timestamp_ms,
+                color: YuvColor::default(), duration_ms: 0,
+            },
+            frame_duration_ms: 40,
+        }
+    }
+
+    #[test]
+    fn test_prefetch_store() {
+        let mut store = PrefetchStore::new(2);
+        store.push(prefetched("a.mp4", 1000));
+        store.push(prefetched("a.mp4", 1040));
+
+        // 반 프레임 이내면 같은 프레임
+        assert!(store.contains("a.mp4", 1019));
+        assert!(!store.contains("a.mp4", 1020 + 40));
+        assert!(!store.contains("b.mp4", 1000));
+
+        // 꺼내면 제거
+        assert_eq!(store.take("a.mp4", 1041).map(|f| f.timestamp_ms), Some(1040));
+        assert!(store.take("a.mp4", 1041).is_none());
+
+        // 용량 초과 시 오래된 것부터 제거
+        store.push(prefetched("a.mp4", 1080));
+        store.push(prefetched("a.mp4", 1120));
+        assert!(!store.contains("a.mp4", 1000));
+        assert!(store.contains("a.mp4", 1080) && store.contains("a.mp4", 1120));
+    }
+
+    #[test]
+    fn test_prefetcher_missing_file() {
+        // 열 수 없는 파일은 저장 없이 건너뜀, drop 시 워커 종료
+        let prefetcher = Prefetcher::start(4, (960, 540));
+        prefetcher.request(vec![PrefetchRequest {
+            file_path: PathBuf::from("/nonexistent/prefetch.mp4"),
+            stream_index: None,
+            proxy_path: None,
+            source_times: vec![0, 33, 66],
+        }]);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(prefetcher.take("/nonexistent/prefetch.mp4", 0).is_none());
+        assert_eq!(prefetcher.frames(), 4);
+    }
+}
@@ -1,26 +1,57 @@
 // 렌더링 엔진 - Timeline을 실제 프레임으로 렌더링
 // 아키텍처: FrameCache + DecodeResult 기반 안전 렌더링
 
-use crate::timeline::{Timeline, VideoClip};
-use crate::ffmpeg::{Decoder, DecodeResult};
-use crate::rendering::effects::{EffectParams, apply_effects};
-use std::collections::{HashMap, VecDeque};
+use crate::timeline::clip::is_source_of;
+use crate::timeline::{frame_to_ms, ms_to_frame, ClipTransform, ColorClip, EndOfMediaBehavior, FitMode, KeyframeProperty, Timeline, TitleClip, Transition, VideoClip};
+use crate::ffmpeg::{Decoder, DecodeLimits, DecodeResult, Frame, FrameIndex, YuvColor};
+use crate::ffmpeg::image::load_image;
+use crate::rendering::effects::{EffectParams, EffectStore, apply_effects};
+use crate::rendering::interpolate::{interpolate_rgba, interpolate_yuv420p};
+use crate::rendering::decode_scheduler::{DecodeScheduler, DecodePriority};
+use crate::rendering::decoder_pool::{DecoderPool, DEFAULT_MAX_DECODERS};
+use crate::rendering::lut::{Lut3D, LutCache};
+use crate::rendering::prefetch::{Prefetcher, PrefetchRequest, DEFAULT_PREFETCH_FRAMES};
+use crate::rendering::compositor::{composite_rgba, covers_canvas, fit_layer_rgba, fit_rect};
+#[cfg(feature = "gpu")]
+use crate::rendering::gpu::{GpuCompositor, GpuLayer};
+use crate::rendering::transition::blend_transition;
+use crate::subtitle::overlay::{SubtitleOverlayList, blend_overlays_rgba, yuv420p_to_rgba, rgba_to_yuv420p};
+use crate::subtitle::text;
+use crate::utils::proxy;
+use crate::utils::log::{log_debug, log_warn};
+#[cfg(feature = "gpu")]
+use crate::utils::log::log_info;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 // ============================================================
 // 프레임 캐시 (LRU)
 // ============================================================
 
-/// 캐시 엔트리
-struct CacheEntry {
+/// 캐시 노드 (슬랩 인덱스로 연결된 LRU 이중 연결 리스트)
+struct CacheNode {
     file_path: String,
     source_time_ms: i64,
     frame: RenderedFrame,
+    /// 더 오래 전에 사용된 노드
+    prev: Option<usize>,
+    /// 더 최근에 사용된 노드
+    next: Option<usize>,
 }
 
-/// LRU 프레임 캐시
+/// LRU 프레임 캐시 (HashMap 인덱스 O(1) 조회 + 개수/바이트 한도 evict)
 struct FrameCache {
-    entries: VecDeque<CacheEntry>,
+    /// 파일 경로 → 소스 시간 → 노드 슬롯 (조회 시 &str로 검색, 키 할당 없음)
+    index: HashMap<String, HashMap<i64, usize>>,
+    /// 노드 슬랩 (None = 빈 슬롯, free 목록에서 재사용)
+    nodes: Vec<Option<CacheNode>>,
+    free: Vec<usize>,
+    /// 가장 오래 전에 사용된 노드 (evict 대상)
+    oldest: Option<usize>,
+    /// 가장 최근에 사용된 노드
+    newest: Option<usize>,
+    len: usize,
     max_entries: usize,
     max_bytes: usize,
     current_bytes: usize,
@@ -31,7 +62,12 @@ struct FrameCache {
 impl FrameCache {
     fn new(max_entries: usize, max_bytes: usize) -> Self {
         Self {
-            entries: VecDeque::new(),
+            index: HashMap::new(),
+            nodes: Vec::new(),
+            free: Vec::new(),
+            oldest: None,
+            newest: None,
+            len: 0,
             max_entries,
             max_bytes,
             current_bytes: 0,
@@ -40,22 +76,72 @@ impl FrameCache {
         }
     }
 
+    fn slot(&self, file_path: &str, source_time_ms: i64) -> Option<usize> {
+        self.index.get(file_path)?.get(&source_time_ms).copied()
+    }
+
+    fn node_mut(&mut self, slot: usize) -> &mut CacheNode {
+        self.nodes[slot].as_mut().expect("linked cache slot")
+    }
+
+    /// 리스트에서 노드 분리 (슬롯은 유지)
+    fn unlink(&mut self, slot: usize) {
+        let (prev, next) = {
+            let node = self.node_mut(slot);
+            (node.prev.take(), node.next.take())
+        };
+        match prev {
+            Some(p) => self.node_mut(p).next = next,
+            None => self.oldest = next,
+        }
+        match next {
+            Some(n) => self.node_mut(n).prev = prev,
+            None => self.newest = prev,
+        }
+    }
+
+    /// 가장 최근 위치에 연결
+    fn link_newest(&mut self, slot: usize) {
+        let newest = self.newest;
+        {
+            let node = self.node_mut(slot);
+            node.prev = newest;
+            node.next = None;
+        }
+        match newest {
+            Some(n) => self.node_mut(n).next = Some(slot),
+            None => self.oldest = Some(slot),
+        }
+        self.newest = Some(slot);
+    }
+
+    /// 노드 제거 (인덱스/슬롯/바이트 정리)
+    fn remove_slot(&mut self, slot: usize) {
+        self.unlink(slot);
+        if let Some(node) = self.nodes[slot].take() {
+            if let Some(times) = self.index.get_mut(&node.file_path) {
+                times.remove(&node.source_time_ms);
+                if times.is_empty() {
+                    self.index.remove(&node.file_path);
+                }
+            }
+            self.current_bytes -= node.frame.data.len();
+            self.len -= 1;
+            self.free.push(slot);
+        }
+    }
+
     /// 캐시에서 프레임 조회 (히트 시 LRU 갱신)
     fn get(&mut self, file_path: &str, source_time_ms: i64) -> Option<&RenderedFrame> {
-        // 캐시 검색
-        let idx = self.entries.iter().position(|e| {
-            e.file_path == file_path && e.source_time_ms == source_time_ms
-        });
-
-        match idx {
-            Some(i) => {
+        match self.slot(file_path, source_time_ms) {
+            Some(slot) => {
                 self.hit_count += 1;
-                // LRU: 히트된 항목을 뒤로 이동 (가장 최근 사용)
-                if i < self.entries.len() - 1 {
-                    let entry = self.entries.remove(i).unwrap();
-                    self.entries.push_back(entry);
+                // LRU: 히트된 항목을 가장 최근으로 이동
+                if self.newest != Some(slot) {
+                    self.unlink(slot);
+                    self.link_newest(slot);
                 }
-                self.entries.back().map(|e| &e.frame)
+                self.nodes[slot].as_ref().map(|n| &n.frame)
             }
             None => {
                 self.miss_count += 1;
@@ -69,39 +155,77 @@ impl FrameCache {
         let frame_bytes = frame.data.len();
 
         // 이미 존재하면 갱신
-        if let Some(i) = self.entries.iter().position(|e| {
-            e.file_path == file_path && e.source_time_ms == source_time_ms
-        }) {
-            let old = self.entries.remove(i).unwrap();
-            self.current_bytes -= old.frame.data.len();
+        if let Some(slot) = self.slot(&file_path, source_time_ms) {
+            self.remove_slot(slot);
         }
 
         // 용량 초과 시 LRU evict (가장 오래된 것부터)
-        while (self.entries.len() >= self.max_entries || self.current_bytes + frame_bytes > self.max_bytes)
-            && !self.entries.is_empty()
-        {
-            if let Some(evicted) = self.entries.pop_front() {
-                self.current_bytes -= evicted.frame.data.len();
+        while self.len >= self.max_entries || self.current_bytes + frame_bytes > self.max_bytes {
+            match self.oldest {
+                Some(slot) => self.remove_slot(slot),
+                None => break,
             }
         }
 
+        let node = CacheNode { file_path: file_path.clone(), source_time_ms, frame, prev: None, next: None };
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = Some(node);
+                slot
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.link_newest(slot);
+        self.index.entry(file_path).or_default().insert(source_time_ms, slot);
         self.current_bytes += frame_bytes;
-        self.entries.push_back(CacheEntry {
-            file_path,
-            source_time_ms,
-            frame,
-        });
+        self.len += 1;
+    }
+
+    /// 캐시 포함 여부 (LRU/통계 변경 없음)
+    fn contains(&self, file_path: &str, source_time_ms: i64) -> bool {
+        self.slot(file_path, source_time_ms).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 파일의 모든 프레임 제거 (파일 교체/재연결 시, 모든 스트림)
+    fn invalidate_file(&mut self, file_path: &str) {
+        self.invalidate_range(file_path, i64::MIN, i64::MAX);
+    }
+
+    /// 파일의 소스 시간 범위 [start_ms, end_ms] 프레임 제거 (클립 이펙트/LUT 변경 시)
+    /// file_path: 파일 경로(모든 스트림) 또는 소스 키(해당 스트림만)
+    fn invalidate_range(&mut self, file_path: &str, start_ms: i64, end_ms: i64) {
+        let slots: Vec<usize> = self.index.iter()
+            .filter(|(key, _)| is_source_of(key, file_path))
+            .flat_map(|(_, times)| times.iter())
+            .filter(|(&t, _)| t >= start_ms && t <= end_ms)
+            .map(|(_, &slot)| slot)
+            .collect();
+        for slot in slots {
+            self.remove_slot(slot);
+        }
     }
 
     /// 캐시 전체 클리어
     fn clear(&mut self) {
-        self.entries.clear();
+        self.index.clear();
+        self.nodes.clear();
+        self.free.clear();
+        self.oldest = None;
+        self.newest = None;
+        self.len = 0;
         self.current_bytes = 0;
     }
 
     /// 통계 조회
     fn stats(&self) -> (u32, usize) {
-        (self.entries.len() as u32, self.current_bytes)
+        (self.len as u32, self.current_bytes)
     }
 }
 
@@ -114,9 +238,10 @@ impl FrameCache {
 pub struct RenderedFrame {
     pub width: u32,
     pub height: u32,
-    pub data: Vec<u8>, // RGBA 또는 YUV420P
+    /// RGBA 또는 YUV420P (캐시/fallback/FFI가 복사 없이 공유, 수정은 Arc::make_mut — 공유 중일 때만 복사)
+    pub data: Arc<Vec<u8>>,
     pub timestamp_ms: i64,
-    /// Export 시 true: data는 YUV420P (색공간 변환 손실 없음)
+    /// Export 시 true: data는 YUV420P (BT.709 limited, 인코더 출력 색공간과 동일)
     /// 프리뷰 시 false: data는 RGBA
     pub is_yuv: bool,
 }
@@ -125,20 +250,162 @@ pub struct RenderedFrame {
 // 렌더러
 // ============================================================
 
+/// 보간 최소 위상 — 소스 프레임 경계에서 이 비율 이내면 보간 없이 원본 프레임 사용
+const INTERPOLATION_MIN_PHASE: f32 = 0.05;
+
+/// 기본 프리뷰 해상도 (PreviewResolution::Standard)
+pub const DEFAULT_PREVIEW_SIZE: (u32, u32) = (960, 540);
+
+/// 프리뷰 해상도 (Standard 외에는 프로젝트 해상도 기준 비율)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewResolution {
+    /// 960x540 고정 (기본)
+    Standard = 0,
+    /// 프로젝트 해상도 1/4
+    Quarter = 1,
+    /// 프로젝트 해상도 1/2
+    Half = 2,
+    /// 프로젝트 해상도 그대로 (4K 모니터에서 포커스/텍스트 선명도 확인용)
+    Full = 3,
+}
+
+impl PreviewResolution {
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Standard),
+            1 => Some(Self::Quarter),
+            2 => Some(Self::Half),
+            3 => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    /// 프리뷰 프레임 크기 (YUV 변환/스케일러를 위해 짝수, 최소 2x2)
+    pub fn size(self, project_width: u32, project_height: u32) -> (u32, u32) {
+        let divisor = match self {
+            Self::Standard => return DEFAULT_PREVIEW_SIZE,
+            Self::Quarter => 4,
+            Self::Half => 2,
+            Self::Full => 1,
+        };
+        let even = |v: u32| ((v / divisor) & !1).max(2);
+        (even(project_width), even(project_height))
+    }
+}
+
+/// Export 순차 디코딩 커서의 최대 전진 폭 — 더 먼 점프(트림 경계, 같은 파일의 다른 클립)는 seek
+const SEQUENTIAL_MAX_GAP_MS: i64 = 5000;
+
+/// Export 순차 디코딩 커서 (디코더별)
+/// 소스 시간이 전진하는 동안 seek/PTS 탐색 없이 다음 프레임만 읽어 표시 시간이 된 프레임으로 교체
+struct SequentialCursor {
+    /// 현재 표시 프레임
+    current: Frame,
+    /// 현재 프레임 표시 시작 (ms) — 이보다 앞선 요청은 커서로 답할 수 없음
+    current_start_ms: i64,
+    /// 미리 읽은 다음 프레임 (timestamp_ms = PTS, 아직 표시 시간이 아님)
+    next: Option<Frame>,
+    /// 마지막 요청 소스 시간
+    last_source_ms: i64,
+    /// 스트림 끝 도달
+    ended: bool,
+}
+
+/// 클립 레이어 렌더링 결과
+enum LayerResult {
+    /// 합성할 프레임
+    Frame(RenderedFrame),
+    /// 표시할 내용 없음 (EndOfMediaBehavior::Black)
+    Empty,
+    /// 디코딩 스킵/실패 + 직전 프레임 없음
+    Failed,
+}
+
+/// 트랙별 렌더링 대상 (단일 클립 또는 트랜지션 구간의 두 클립)
+enum TrackLayer {
+    Clip(VideoClip, i64),
+    Transition {
+        transition: Transition,
+        from: VideoClip,
+        to: Box<VideoClip>,
+        progress: f32,
+    },
+}
+
+/// 트랙/타임라인 그레이딩 (합성 단계에서 적용, 프레임 캐시에는 포함되지 않음)
+#[derive(Clone)]
+struct Grade {
+    lut_path: Option<PathBuf>,
+    effects: Option<EffectParams>,
+}
+
+impl Grade {
+    /// LUT/이펙트가 모두 없으면 None
+    fn new(lut_path: &Option<PathBuf>, effects: &EffectParams) -> Option<Self> {
+        let effects = (!effects.is_default()).then(|| effects.clone());
+        (lut_path.is_some() || effects.is_some()).then(|| Self { lut_path: lut_path.clone(), effects })
+    }
+}
+
 /// 비디오 렌더러 (캐시 + DecodeResult 기반)
 pub struct Renderer {
     timeline: Arc<Mutex<Timeline>>,
-    decoder_cache: HashMap<String, Decoder>,
+    /// 클립별 디코더 (키: decoder_key, LRU, 최대 개수 초과 시 오래 안 쓴 디코더부터 닫음)
+    /// 같은 파일을 쓰는 클립도 각자 디코더를 가져 떨어진 위치를 번갈아 디코딩해도 seek 반복 없음
+    decoder_cache: DecoderPool<Decoder>,
+    /// VFR 소스 프레임 인덱스 (소스 키당 1회 스캔, 디코더를 다시 열어도 유지)
+    frame_indexes: HashMap<String, FrameIndex>,
+    /// Export 순차 디코딩 커서 (디코더 키별, 프리뷰에서는 사용하지 않음)
+    sequential_cursors: HashMap<String, SequentialCursor>,
+    /// 이미지 클립 디코딩 결과 (파일당 1회 디코딩, 렌더러 출력 포맷/해상도)
+    image_cache: HashMap<String, Frame>,
+    /// 소스 표시 크기 (소스 키 → SAR/회전 반영 크기, 디코더/이미지를 처음 열 때 기록, 맞춤 모드 배치용)
+    source_sizes: HashMap<String, (u32, u32)>,
     frame_cache: FrameCache,
     /// 마지막 성공 렌더링 프레임 (fallback용)
     last_rendered_frame: Option<RenderedFrame>,
+    /// 클립별 마지막 레이어 프레임 (레이어 단위 fallback, 현재 화면의 클립만 유지)
+    last_clip_frames: HashMap<u64, RenderedFrame>,
+    /// 마지막 렌더링 시점의 비디오 트랙 on/off (바뀐 트랙은 렌더링 전에 무효화)
+    track_enabled: HashMap<u64, bool>,
     /// 재생 모드: true일 때 forward_threshold를 5초로 올려 seek 대신 forward decode
     /// false(스크럽)일 때는 기본값(66ms) 유지 → 즉시 seek으로 정확한 위치 도달
     playback_mode: bool,
-    /// Export용 출력 해상도 (None이면 프리뷰 960x540)
+    /// 역방향 재생: 디코더 역방향 모드 (GOP 단위 링 버퍼), 정방향 미리 읽기 끔
+    reverse_playback: bool,
+    /// 마지막 render_frame의 프레임 번호 (프로젝트 fps 격자, step_frame 기준)
+    current_frame: i64,
+    /// Export용 출력 해상도 (None이면 프리뷰 preview_size)
     export_resolution: Option<(u32, u32)>,
-    /// 클립별 이펙트 파라미터
-    clip_effects: HashMap<u64, EffectParams>,
+    /// 프리뷰 프레임 크기 (set_preview_resolution, 기본 960x540)
+    preview_size: (u32, u32),
+    /// 파싱된 LUT 캐시 (같은 파일을 쓰는 클립끼리 공유)
+    lut_cache: LutCache,
+    /// 전체 이펙트/LUT 바이패스 (클립/트랙/마스터 그레이딩 모두, 원본 A/B 비교용, 파라미터는 유지)
+    effects_bypassed: bool,
+    /// 이펙트/LUT 바이패스 클립 ID
+    bypassed_clips: HashSet<u64>,
+    /// 디코더 탐색 한도 (새로 여는 디코더에도 적용)
+    decode_limits: DecodeLimits,
+    /// 프리뷰 하드웨어 디코딩 사용 여부 (Export는 항상 소프트웨어 YUV 경로)
+    hw_decode: bool,
+    /// 프리뷰에서 등록된 프록시 사용 (Export는 항상 원본)
+    use_proxies: bool,
+    /// 마지막으로 반영한 프록시 등록 변경 횟수 (utils::proxy::generation)
+    proxy_generation: u64,
+    /// 프리뷰 자막 오버레이 (합성 결과 위에 블렌딩, 프레임 캐시에는 포함되지 않음)
+    subtitles: Option<SubtitleOverlayList>,
+    /// 타이틀 클립 래스터화 결과 (클립 ID → 내용, 레이어), 내용/해상도가 같으면 재사용
+    title_cache: HashMap<u64, (TitleClip, RenderedFrame)>,
+    /// 컬러 클립/배경 채우기 래스터화 결과 (클립 ID, None = 타임라인 배경 → 내용, 레이어)
+    color_cache: HashMap<Option<u64>, (ColorClip, RenderedFrame)>,
+    /// 재생 미리 읽기 워커 (프리뷰 재생 모드에서만 동작)
+    prefetcher: Option<Prefetcher>,
+    /// 클립당 미리 읽을 프레임 수 (0 = 끔)
+    prefetch_frames: u32,
+    /// GPU 합성기 (프리뷰 전용, 사용 중이면 캐시 프레임은 이펙트 적용 전 상태)
+    #[cfg(feature = "gpu")]
+    gpu: Option<GpuCompositor>,
     /// 진단 카운터 (매 30프레임마다 출력)
     diag_total: u64,
     diag_cache_hit: u64,
@@ -149,52 +416,231 @@ pub struct Renderer {
     diag_error: u64,
 }
 
-/// 검은색 프레임 생성 (기본 960x540, Export 시 지정 해상도)
-fn black_frame(timestamp_ms: i64) -> RenderedFrame {
-    black_frame_with_size(960, 540, timestamp_ms)
+/// 클립 디코더 풀 키 ("소스키#클립ID", 소스키는 경로 + 선택한 스트림)
+fn decoder_key(clip: &VideoClip) -> String {
+    format!("{}#{}", clip.source_key(), clip.id)
+}
+
+/// 같은 소스(파일 + 스트림)의 클립 디코더 키인지
+fn is_same_file_key(key: &str, source_key: &str) -> bool {
+    key.strip_prefix(source_key)
+        .and_then(|rest| rest.strip_prefix('#'))
+        .is_some_and(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
 }
 
-/// 지정 크기의 검은색 프레임 생성
-fn black_frame_with_size(width: u32, height: u32, timestamp_ms: i64) -> RenderedFrame {
+/// 단색 RGBA 프레임 생성 (타임라인 배경색)
+fn solid_frame_rgba(width: u32, height: u32, color: [u8; 3], timestamp_ms: i64) -> RenderedFrame {
+    let pixel_count = (width * height) as usize;
+    // 검정은 기존 black frame과 동일하게 alpha 포함 전부 0
+    let alpha = if color == [0, 0, 0] { 0 } else { 255 };
+    let mut data = Vec::with_capacity(pixel_count * 4);
+    for _ in 0..pixel_count {
+        data.extend_from_slice(&[color[0], color[1], color[2], alpha]);
+    }
     RenderedFrame {
         width,
         height,
-        data: vec![0u8; (width * height * 4) as usize],
+        data: Arc::new(data),
         timestamp_ms,
         is_yuv: false,
     }
 }
 
-/// Export용 검은색 YUV420P 프레임 생성
-fn black_frame_yuv(width: u32, height: u32, timestamp_ms: i64) -> RenderedFrame {
+/// 단색 YUV420P 프레임 생성 (Export용 타임라인 배경색)
+fn solid_frame_yuv(width: u32, height: u32, color: [u8; 3], timestamp_ms: i64) -> RenderedFrame {
     let y_size = (width * height) as usize;
     let uv_size = ((width / 2) * (height / 2)) as usize;
-    // YUV420P: Y=0 (검정), U=V=128 (무채색)
-    let mut data = vec![0u8; y_size + uv_size * 2];
-    for i in y_size..y_size + uv_size * 2 {
-        data[i] = 128;
-    }
+    let (y, u, v) = rgb_to_yuv(color);
+    // YUV420P: Y 평면 → U 평면 → V 평면
+    let mut data = vec![y; y_size + uv_size * 2];
+    data[y_size..y_size + uv_size].fill(u);
+    data[y_size + uv_size..].fill(v);
     RenderedFrame {
         width,
         height,
-        data,
+        data: Arc::new(data),
         timestamp_ms,
         is_yuv: true,
     }
 }
 
+/// RGB → YUV (Export 색공간 BT.709 limited, 검정 = Y16/U128/V128)
+fn rgb_to_yuv(color: [u8; 3]) -> (u8, u8, u8) {
+    let block = [color[0], color[1], color[2], 255].repeat(4);
+    let yuv = rgba_to_yuv420p(&block, 2, 2, YuvColor::BT709);
+    (yuv[0], yuv[4], yuv[5])
+}
+
+/// 레이어를 트랜스폼 적용하여 캔버스에 합성 (YUV는 RGBA로 변환 후 합성, 캔버스 포맷 유지)
+fn composite_layer(canvas: &mut RenderedFrame, layer: &RenderedFrame, transform: &ClipTransform) {
+    let mut canvas_rgba = if canvas.is_yuv {
+        yuv420p_to_rgba(&canvas.data, canvas.width, canvas.height, YuvColor::BT709)
+    } else {
+        Arc::unwrap_or_clone(std::mem::take(&mut canvas.data))
+    };
+
+    let converted;
+    let layer_rgba: &[u8] = if layer.is_yuv {
+        converted = yuv420p_to_rgba(&layer.data, layer.width, layer.height, YuvColor::BT709);
+        &converted
+    } else {
+        &layer.data
+    };
+
+    composite_rgba(
+        &mut canvas_rgba, canvas.width, canvas.height,
+        layer_rgba, layer.width, layer.height,
+        transform,
+    );
+
+    canvas.data = Arc::new(if canvas.is_yuv {
+        rgba_to_yuv420p(&canvas_rgba, canvas.width, canvas.height, YuvColor::BT709)
+    } else {
+        canvas_rgba
+    });
+}
+
+/// LUT → 이펙트 순으로 프레임 색 보정 (YUV는 RGBA로 한 번 변환해 적용 후 되돌림)
+fn grade_frame(frame: &mut RenderedFrame, lut: Option<&Lut3D>, effects: Option<&EffectParams>) {
+    if lut.is_none() && effects.is_none() {
+        return;
+    }
+
+    let (width, height) = (frame.width, frame.height);
+    let grade = |rgba: &mut [u8]| {
+        if let Some(lut) = lut {
+            lut.apply_rgba(rgba);
+        }
+        if let Some(params) = effects {
+            apply_effects(rgba, width, height, params);
+        }
+    };
+    if frame.is_yuv {
+        let mut rgba = yuv420p_to_rgba(&frame.data, width, height, YuvColor::BT709);
+        grade(&mut rgba);
+        frame.data = Arc::new(rgba_to_yuv420p(&rgba, width, height, YuvColor::BT709));
+    } else {
+        grade(Arc::make_mut(&mut frame.data).as_mut_slice());
+    }
+}
+
+/// 투명 RGBA 프레임 (트랜지션에서 내용 없는 쪽)
+fn transparent_frame(width: u32, height: u32, timestamp_ms: i64) -> RenderedFrame {
+    RenderedFrame {
+        width,
+        height,
+        data: Arc::new(vec![0; (width * height * 4) as usize]),
+        timestamp_ms,
+        is_yuv: false,
+    }
+}
+
+/// RGBA 비트맵을 (x, y) 위치에 그대로 복사 (알파 유지, 프레임 밖은 잘림)
+fn copy_bitmap_rgba(frame: &mut RenderedFrame, rgba: &[u8], x: i32, y: i32, width: u32, height: u32) {
+    let (fw, fh) = (frame.width as i32, frame.height as i32);
+    let x0 = x.max(0);
+    let x1 = (x + width as i32).min(fw);
+    if x0 >= x1 {
+        return;
+    }
+    let data = Arc::make_mut(&mut frame.data);
+    for row in 0..height as i32 {
+        let fy = y + row;
+        if fy < 0 || fy >= fh {
+            continue;
+        }
+        let src = ((row * width as i32 + (x0 - x)) * 4) as usize;
+        let dst = ((fy * fw + x0) * 4) as usize;
+        let len = ((x1 - x0) * 4) as usize;
+        data[dst..dst + len].copy_from_slice(&rgba[src..src + len]);
+    }
+}
+
+/// 맞춤 모드 영역에 레이어를 다시 배치 (YUV는 RGBA로 변환, 캔버스를 꽉 채우면 YUV로 되돌려 직접 인코딩 경로 유지)
+fn fit_frame(layer: RenderedFrame, width: u32, height: u32, rect: [f32; 4]) -> RenderedFrame {
+    let converted;
+    let layer_rgba: &[u8] = if layer.is_yuv {
+        converted = yuv420p_to_rgba(&layer.data, layer.width, layer.height, YuvColor::BT709);
+        &converted
+    } else {
+        &layer.data
+    };
+    let rgba = fit_layer_rgba(layer_rgba, layer.width, layer.height, width, height, rect);
+
+    let [x, y, w, h] = rect;
+    let covers = x <= 0.0 && y <= 0.0 && x + w >= width as f32 && y + h >= height as f32;
+    let (data, is_yuv) = if layer.is_yuv && covers {
+        (rgba_to_yuv420p(&rgba, width, height, YuvColor::BT709), true)
+    } else {
+        (rgba, false)
+    };
+    RenderedFrame { width, height, data: Arc::new(data), timestamp_ms: layer.timestamp_ms, is_yuv }
+}
+
+/// 트랜스폼을 같은 크기의 투명 캔버스에 미리 적용 (항등이면 그대로)
+fn place_layer(layer: RenderedFrame, transform: &ClipTransform) -> RenderedFrame {
+    if transform.is_identity() {
+        return layer;
+    }
+    let mut canvas = transparent_frame(layer.width, layer.height, layer.timestamp_ms);
+    composite_layer(&mut canvas, &layer, transform);
+    canvas
+}
+
+/// RGBA 프레임을 dst 버퍼의 (dst_x, 0) 위치에 tile 크기로 축소 복사 (최근접 샘플링)
+fn blit_scaled_rgba(src: &RenderedFrame, dst: &mut [u8], dst_stride_px: u32, dst_x: u32, tile_width: u32, tile_height: u32) {
+    let (sw, sh) = (src.width as usize, src.height as usize);
+    if src.data.len() < sw * sh * 4 {
+        return;
+    }
+
+    for y in 0..tile_height as usize {
+        let sy = ((y as f64 + 0.5) * sh as f64 / tile_height as f64) as usize;
+        let sy = sy.min(sh - 1);
+        for x in 0..tile_width as usize {
+            let sx = ((x as f64 + 0.5) * sw as f64 / tile_width as f64) as usize;
+            let sx = sx.min(sw - 1);
+            let si = (sy * sw + sx) * 4;
+            let di = (y * dst_stride_px as usize + dst_x as usize + x) * 4;
+            dst[di..di + 4].copy_from_slice(&src.data[si..si + 4]);
+        }
+    }
+}
+
 impl Renderer {
     /// 새 렌더러 생성 (프리뷰용)
     pub fn new(timeline: Arc<Mutex<Timeline>>) -> Self {
         Self {
             timeline,
-            decoder_cache: HashMap::new(),
+            decoder_cache: DecoderPool::new(DEFAULT_MAX_DECODERS),
+            frame_indexes: HashMap::new(),
+            sequential_cursors: HashMap::new(),
+            image_cache: HashMap::new(),
+            source_sizes: HashMap::new(),
             // 60프레임 캐시 (~120MB at 960x540 RGBA)
             frame_cache: FrameCache::new(60, 200 * 1024 * 1024),
             last_rendered_frame: None,
+            last_clip_frames: HashMap::new(),
+            track_enabled: HashMap::new(),
             playback_mode: false,
+            reverse_playback: false,
+            current_frame: 0,
             export_resolution: None,
-            clip_effects: HashMap::new(),
+            preview_size: DEFAULT_PREVIEW_SIZE,
+            lut_cache: LutCache::new(),
+            effects_bypassed: false,
+            bypassed_clips: HashSet::new(),
+            decode_limits: DecodeLimits::default(),
+            hw_decode: false,
+            use_proxies: false,
+            proxy_generation: 0,
+            subtitles: None,
+            title_cache: HashMap::new(),
+            color_cache: HashMap::new(),
+            prefetcher: None,
+            prefetch_frames: DEFAULT_PREFETCH_FRAMES,
+            #[cfg(feature = "gpu")]
+            gpu: None,
             diag_total: 0,
             diag_cache_hit: 0,
             diag_decoded: 0,
@@ -212,13 +658,35 @@ impl Renderer {
     pub fn new_for_export(timeline: Arc<Mutex<Timeline>>, width: u32, height: u32) -> Self {
         Self {
             timeline,
-            decoder_cache: HashMap::new(),
+            decoder_cache: DecoderPool::new(DEFAULT_MAX_DECODERS),
+            frame_indexes: HashMap::new(),
+            sequential_cursors: HashMap::new(),
+            image_cache: HashMap::new(),
+            source_sizes: HashMap::new(),
             // Export: 캐시 최소 (순차 인코딩이라 재사용 거의 없음)
             frame_cache: FrameCache::new(5, 50 * 1024 * 1024),
             last_rendered_frame: None,
+            last_clip_frames: HashMap::new(),
+            track_enabled: HashMap::new(),
             playback_mode: true, // forward decode 모드 (순차 접근)
+            reverse_playback: false,
+            current_frame: 0,
             export_resolution: Some((width, height)),
-            clip_effects: HashMap::new(),
+            preview_size: DEFAULT_PREVIEW_SIZE,
+            lut_cache: LutCache::new(),
+            effects_bypassed: false,
+            bypassed_clips: HashSet::new(),
+            decode_limits: DecodeLimits::default(),
+            hw_decode: false,
+            use_proxies: false,
+            proxy_generation: 0,
+            subtitles: None,
+            title_cache: HashMap::new(),
+            color_cache: HashMap::new(),
+            prefetcher: None,
+            prefetch_frames: DEFAULT_PREFETCH_FRAMES,
+            #[cfg(feature = "gpu")]
+            gpu: None,
             diag_total: 0,
             diag_cache_hit: 0,
             diag_decoded: 0,
@@ -229,6 +697,11 @@ impl Renderer {
         }
     }
 
+    /// 렌더링 대상 타임라인
+    pub fn timeline(&self) -> Arc<Mutex<Timeline>> {
+        self.timeline.clone()
+    }
+
     /// 재생 모드 설정: 재생 시작 시 true, 정지 시 false
     /// 재생 모드: forward_threshold=5000ms (seek 대신 forward decode → 빠름)
     /// 스크럽 모드: forward_threshold=기본값 (즉시 seek → 정확한 위치)
@@ -248,309 +721,1939 @@ impl Renderer {
                 self.decoder_cache.remove(&key);
             }
         }
+        self.update_prefetcher();
+    }
+
+    /// 역방향 재생 설정 (프리뷰 디코딩을 역방향 모드로, Export에는 영향 없음)
+    pub fn set_reverse_playback(&mut self, reverse: bool) {
+        self.reverse_playback = reverse;
+        self.update_prefetcher();
+    }
+
+    pub fn reverse_playback(&self) -> bool {
+        self.reverse_playback
+    }
+
+    /// 미리 읽기 프레임 수 설정 (0이면 끔, 재생 중이면 새 설정으로 워커 재시작)
+    pub fn set_prefetch_frames(&mut self, frames: u32) {
+        if self.prefetch_frames != frames {
+            self.prefetch_frames = frames;
+            self.prefetcher = None;
+            self.update_prefetcher();
+        }
+    }
+
+    /// 재생 모드/설정에 맞춰 미리 읽기 워커 시작/정지 (Export는 순차 디코딩이라 불필요)
+    fn update_prefetcher(&mut self) {
+        let enabled = self.playback_mode && !self.reverse_playback
+            && self.export_resolution.is_none() && self.prefetch_frames > 0;
+        if !enabled {
+            self.prefetcher = None;
+        } else if self.prefetcher.is_none() {
+            self.prefetcher = Some(Prefetcher::start(self.prefetch_frames, self.preview_size));
+        }
     }
 
     /// 특정 시간의 프레임 렌더링 (캐시 + DecodeResult 안전 처리)
+    /// 마스터 그레이딩 → 자막 순으로 타임라인 합성 후 적용 (fallback용 last_rendered_frame은 둘 다 없는 프레임)
     pub fn render_frame(&mut self, timestamp_ms: i64) -> Result<RenderedFrame, String> {
+        if self.use_proxies && self.proxy_generation != proxy::generation() {
+            self.reload_sources();
+        }
+        let mut frame = self.render_timeline_frame(timestamp_ms)?;
+        let (master, fps) = {
+            let timeline = self.timeline.lock()
+                .map_err(|e| format!("Failed to lock timeline: {}", e))?;
+            let grade = Grade::new(&timeline.master_lut_path, &timeline.master_effects)
+                .filter(|_| !self.effects_bypassed);
+            (grade, timeline.fps)
+        };
+        if let Some(grade) = master {
+            self.apply_grade(&mut frame, &grade);
+        }
+        self.blend_subtitles(&mut frame, timestamp_ms);
+        self.current_frame = ms_to_frame(timestamp_ms, fps);
+        Ok(frame)
+    }
+
+    /// 프레임 번호로 렌더링 (프레임 N = 프로젝트 fps 격자의 N번째, Export의 N번째 프레임과 동일)
+    pub fn render_frame_index(&mut self, index: i64) -> Result<RenderedFrame, String> {
+        let fps = self.timeline.lock()
+            .map_err(|e| format!("Failed to lock timeline: {}", e))?
+            .fps;
+        self.render_frame(frame_to_ms(index.max(0), fps))
+    }
+
+    /// 현재 프레임에서 direction만큼 이동해 렌더링 (방향키 스텝: +1/-1)
+    /// [0, 마지막 프레임]으로 제한 → 끝에서 누르면 같은 프레임 유지
+    pub fn step_frame(&mut self, direction: i64) -> Result<RenderedFrame, String> {
+        let last_frame = self.timeline.lock()
+            .map_err(|e| format!("Failed to lock timeline: {}", e))?
+            .frame_count() - 1;
+        let index = self.current_frame.saturating_add(direction).min(last_frame).max(0);
+        self.render_frame_index(index)
+    }
+
+    /// 마지막으로 렌더링한 프레임 번호
+    pub fn current_frame(&self) -> i64 {
+        self.current_frame
+    }
+
+    /// 타임라인 트랙 합성 (자막 제외)
+    fn render_timeline_frame(&mut self, timestamp_ms: i64) -> Result<RenderedFrame, String> {
         self.diag_total += 1;
         let render_start = std::time::Instant::now();
 
         // Timeline 데이터 복사 (lock 최소화)
-        let clips_to_render = {
+        // 트랙 순서 = 합성 순서 (앞 트랙이 아래, 뒤 트랙이 위), 트랙 그레이딩은 레이어와 함께 복사
+        let (layers_to_render, background, fps, toggled_clips) = {
             let timeline = self.timeline.lock()
                 .map_err(|e| format!("Failed to lock timeline: {}", e))?;
 
-            let mut clips = Vec::new();
+            let mut layers = Vec::new();
+
+            // on/off가 바뀐 트랙 → 해당 트랙 클립 무효화 (렌더링 시 자동, 호스트 호출 불필요)
+            let mut toggled_clips = HashSet::new();
+            for track in &timeline.video_tracks {
+                if self.track_enabled.insert(track.id, track.enabled).is_some_and(|was| was != track.enabled) {
+                    toggled_clips.extend(track.clips.iter().map(|c| c.id));
+                }
+            }
 
             for track in &timeline.video_tracks {
                 if !track.enabled {
                     continue;
                 }
+                let grade = Grade::new(&track.lut_path, &track.effects).filter(|_| !self.effects_bypassed);
+
+                // 트랜지션 구간이면 앞/뒤 클립 모두 렌더링
+                if let Some((transition, from, to, progress)) = track.transition_at(timestamp_ms) {
+                    layers.push((TrackLayer::Transition {
+                        transition: *transition,
+                        from: from.clone(),
+                        to: Box::new(to.clone()),
+                        progress,
+                    }, grade));
+                    continue;
+                }
 
                 if let Some(clip) = track.get_clip_at_time(timestamp_ms) {
                     if let Some(source_time_ms) = clip.timeline_to_source_time(timestamp_ms) {
-                        clips.push((clip.clone(), source_time_ms));
+                        layers.push((TrackLayer::Clip(clip.clone(), source_time_ms), grade));
                     }
                 }
             }
 
-            clips
+            (layers, timeline.background(), timeline.fps, toggled_clips)
         }; // timeline lock 해제
 
-        // 클립이 없으면 검은색 프레임 반환
-        if clips_to_render.is_empty() {
-            self.diag_no_clip += 1;
-            self.print_diag_if_needed(timestamp_ms);
-            return Ok(match self.export_resolution {
-                Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
-                None => black_frame(timestamp_ms),
-            });
+        if !toggled_clips.is_empty() {
+            self.invalidate_clips(&toggled_clips);
         }
 
-        // 첫 번째 클립 렌더링
-        let (clip, source_time_ms) = &clips_to_render[0];
-        let file_path = clip.file_path.to_string_lossy().to_string();
+        self.schedule_prefetch(&layers_to_render, timestamp_ms, fps);
 
-        // 1단계: 캐시 조회 (.cloned()로 즉시 소유권 획득 → 가변 참조 해제)
-        if let Some(mut frame) = self.frame_cache.get(&file_path, *source_time_ms).cloned() {
-            frame.timestamp_ms = timestamp_ms;
-            self.diag_cache_hit += 1;
+        // 클립이 없으면 배경색 프레임 반환
+        if layers_to_render.is_empty() {
+            self.diag_no_clip += 1;
             self.print_diag_if_needed(timestamp_ms);
-            return Ok(frame);
+            return Ok(self.background_fill_frame(&background, timestamp_ms));
         }
 
-        // 2단계: 디코딩
-        let decode_start = std::time::Instant::now();
-        let result = self.decode_clip_frame(clip, *source_time_ms);
-        let decode_elapsed = decode_start.elapsed().as_millis();
-
-        // 처음 10프레임 또는 50ms 이상 걸린 경우 로그
-        if self.diag_total <= 10 || decode_elapsed > 50 {
-            eprintln!(
-                "[RENDER] t={}ms src={}ms decode={}ms total_frames={}",
-                timestamp_ms, source_time_ms, decode_elapsed, self.diag_total
-            );
+        // 레이어별 렌더링 (트랙 순서 = 합성 순서)
+        // 그라디언트 배경은 맨 아래 불투명 레이어 (단색 배경은 합성 단계 background로 처리)
+        let mut layers = Vec::new();
+        if background.solid_rgb().is_none() {
+            layers.push((self.render_color_frame(None, &background, timestamp_ms), ClipTransform::default(), None));
         }
-
-        match result {
-            Ok(decode_result) => {
-                match decode_result {
-                    DecodeResult::Frame(frame) => {
-                        self.diag_decoded += 1;
-                        let is_yuv = frame.format == crate::ffmpeg::PixelFormat::YUV420P;
-                        let mut rendered = RenderedFrame {
-                            width: frame.width,
-                            height: frame.height,
-                            data: frame.data,
-                            timestamp_ms,
-                            is_yuv,
-                        };
-                        // 이펙트 적용 (RGBA 프리뷰만, YUV Export는 건너뜀)
-                        if !rendered.is_yuv {
-                            if let Some(params) = self.clip_effects.get(&clip.id) {
-                                if !params.is_default() {
-                                    apply_effects(&mut rendered.data, rendered.width, rendered.height, params);
-                                }
-                            }
+        let mut any_failed = false;
+        let mut active_clip_ids = HashSet::new();
+
+        for (track_layer, grade) in &layers_to_render {
+            let (result, transform, effects) = match track_layer {
+                TrackLayer::Clip(clip, source_time_ms) => {
+                    active_clip_ids.insert(clip.id);
+                    // 키프레임 애니메이션 반영
+                    let result = self.render_clip_layer(clip, *source_time_ms, timestamp_ms);
+                    let result = self.fit_clip_layer(clip, result);
+                    (result, clip.transform_at(timestamp_ms), self.deferred_effects(clip, timestamp_ms))
+                }
+                TrackLayer::Transition { transition, from, to, progress } => {
+                    active_clip_ids.insert(from.id);
+                    active_clip_ids.insert(to.id);
+                    // 클립별 트랜스폼/이펙트는 블렌딩 전에 적용됨
+                    let result = self.render_transition_layer(transition, from, to, *progress, timestamp_ms);
+                    (result, ClipTransform::default(), None)
+                }
+            };
+            match result {
+                LayerResult::Frame(mut frame) => match grade {
+                    // 트랙 그레이딩은 클립 이펙트(GPU로 미룬 것 포함) → 배치 이후, 트랙 레이어 전체에 적용
+                    Some(grade) => {
+                        if let Some(params) = effects.filter(|_| !frame.is_yuv) {
+                            apply_effects(Arc::make_mut(&mut frame.data).as_mut_slice(), frame.width, frame.height, &params);
                         }
-                        // 캐시에 저장
-                        self.frame_cache.put(file_path, *source_time_ms, rendered.clone());
-                        self.last_rendered_frame = Some(rendered.clone());
-                        self.print_diag_if_needed(timestamp_ms);
-                        Ok(rendered)
-                    }
-                    DecodeResult::FrameSkipped => {
-                        self.diag_skipped += 1;
-                        self.print_diag_if_needed(timestamp_ms);
-                        // 프레임 스킵 → 마지막 렌더링 프레임 반환 (재생 중단 방지)
-                        Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
-                            match self.export_resolution {
-                                Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
-                                None => black_frame(timestamp_ms),
-                            }
-                        }))
+                        let mut placed = place_layer(frame, &transform);
+                        self.apply_grade(&mut placed, grade);
+                        layers.push((placed, ClipTransform::default(), None));
                     }
-                    DecodeResult::EndOfStream(frame) => {
-                        self.diag_eof += 1;
-                        self.print_diag_if_needed(timestamp_ms);
-                        let is_yuv = frame.format == crate::ffmpeg::PixelFormat::YUV420P;
-                        let rendered = RenderedFrame {
-                            width: frame.width,
-                            height: frame.height,
-                            data: frame.data,
-                            timestamp_ms,
-                            is_yuv,
-                        };
-                        self.last_rendered_frame = Some(rendered.clone());
-                        Ok(rendered)
-                    }
-                    DecodeResult::EndOfStreamEmpty => {
-                        self.diag_eof += 1;
-                        self.print_diag_if_needed(timestamp_ms);
-                        Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
-                            match self.export_resolution {
-                                Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
-                                None => black_frame(timestamp_ms),
-                            }
-                        }))
-                    }
-                }
+                    None => layers.push((frame, transform, effects)),
+                },
+                LayerResult::Empty => {}
+                LayerResult::Failed => any_failed = true,
             }
-            Err(e) => {
-                self.diag_error += 1;
-                self.print_diag_if_needed(timestamp_ms);
-                eprintln!("Decode error at {}ms: {}", timestamp_ms, e);
-                // 에러 시에도 마지막 프레임 반환 (재생 중단 방지)
-                Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
-                    match self.export_resolution {
-                        Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
-                        None => black_frame(timestamp_ms),
+        }
+
+        self.last_clip_frames.retain(|id, _| active_clip_ids.contains(id));
+        self.title_cache.retain(|id, _| active_clip_ids.contains(id));
+        self.color_cache.retain(|id, _| id.is_none_or(|id| active_clip_ids.contains(&id)));
+        self.print_diag_if_needed(timestamp_ms);
+
+        let frame = match self.composite_layers(layers, background.solid_rgb().unwrap_or_default(), timestamp_ms) {
+            Some(frame) => frame,
+            // 디코딩 실패/스킵만 있었으면 마지막 렌더링 프레임 유지 (재생 중단 방지)
+            None if any_failed => {
+                let frame = match self.last_rendered_frame.clone() {
+                    Some(mut frame) => {
+                        frame.timestamp_ms = timestamp_ms;
+                        frame
                     }
-                }))
+                    None => self.background_fill_frame(&background, timestamp_ms),
+                };
+                return Ok(frame);
             }
-        }
-    }
+            None => self.background_fill_frame(&background, timestamp_ms),
+        };
 
-    /// 진단 통계 출력 (30프레임=~1초마다)
-    fn print_diag_if_needed(&self, last_ts: i64) {
-        if self.diag_total % 30 == 0 {
-            eprintln!(
-                "[RENDER DIAG] t={}ms | total={} cache={} decode={} eof={} skip={} noclip={} err={}",
-                last_ts,
-                self.diag_total,
-                self.diag_cache_hit,
-                self.diag_decoded,
-                self.diag_eof,
-                self.diag_skipped,
-                self.diag_no_clip,
-                self.diag_error
-            );
-        }
+        self.last_rendered_frame = Some(frame.clone());
+        Ok(frame)
     }
 
-    /// 클립의 프레임 디코딩 (DecodeResult 반환)
-    /// 에러 시 디코더 재생성 1회 재시도 (corrupted state 복구)
-    fn decode_clip_frame(&mut self, clip: &VideoClip, source_time_ms: i64) -> Result<DecodeResult, String> {
-        let file_path = clip.file_path.to_string_lossy().to_string();
-
-        // Error 상태 디코더는 제거 후 재생성 (복구 불가능 상태 탈출)
-        if let Some(decoder) = self.decoder_cache.get(&file_path) {
-            if decoder.state() == crate::ffmpeg::DecoderState::Error {
-                eprintln!("[DECODER] Error state, recreating: {}", file_path);
-                self.decoder_cache.remove(&file_path);
-            }
+    /// 레이어를 배경 위에 아래 → 위 순서로 합성 (레이어 없으면 None)
+    /// GPU 합성 중이면 GPU에서 트랜스폼/이펙트/블렌딩, 실패 시 CPU 합성으로 전환
+    fn composite_layers(
+        &mut self,
+        layers: Vec<(RenderedFrame, ClipTransform, Option<EffectParams>)>,
+        background: [u8; 3],
+        timestamp_ms: i64,
+    ) -> Option<RenderedFrame> {
+        if layers.is_empty() {
+            return None;
         }
 
-        // 디코더가 캐시에 없으면 생성 (현재 모드의 forward_threshold 적용)
-        let threshold = if self.playback_mode { 5000 } else { 100 };
-        if !self.decoder_cache.contains_key(&file_path) {
-            // Export: LANCZOS 고품질, 프리뷰: FAST_BILINEAR
-            let mut decoder = match self.export_resolution {
-                Some((w, h)) => Decoder::open_for_export(&clip.file_path, w, h)?,
-                None => Decoder::open(&clip.file_path)?,
-            };
-            decoder.set_forward_threshold(threshold);
-            self.decoder_cache.insert(file_path.clone(), decoder);
+        // 프리뷰는 RGBA 레이어만 (YUV가 섞이면 CPU 합성)
+        #[cfg(feature = "gpu")]
+        let (width, height) = self.canvas_size();
+        #[cfg(feature = "gpu")]
+        if let Some(gpu) = self.gpu.as_mut().filter(|_| layers.iter().all(|(frame, ..)| !frame.is_yuv)) {
+            let gpu_layers: Vec<GpuLayer> = layers.iter()
+                .map(|(frame, transform, effects)| GpuLayer {
+                    data: &frame.data,
+                    width: frame.width,
+                    height: frame.height,
+                    transform: *transform,
+                    effects: effects.clone(),
+                })
+                .collect();
+            match gpu.composite_rgba(width, height, background, &gpu_layers) {
+                Ok(rgba) => {
+                    return Some(RenderedFrame { width, height, data: Arc::new(rgba), timestamp_ms, is_yuv: false });
+                }
+                Err(e) => {
+                    log_warn!("[RENDER] GPU compositing failed, falling back to CPU: {}", e);
+                    self.disable_gpu();
+                }
+            }
         }
 
-        let decoder = self.decoder_cache.get_mut(&file_path)
-            .ok_or("Decoder not found in cache")?;
-
-        match decoder.decode_frame(source_time_ms) {
-            Ok(result) => Ok(result),
-            Err(e) => {
-                eprintln!("[DECODER] Decode error at {}ms: {}, recreating decoder", source_time_ms, e);
-                self.decoder_cache.remove(&file_path);
-
-                let mut new_decoder = match self.export_resolution {
-                    Some((w, h)) => Decoder::open_for_export(&clip.file_path, w, h)
-                        .map_err(|e2| format!("Decoder recreate failed: {}", e2))?,
-                    None => Decoder::open(&clip.file_path)
-                        .map_err(|e2| format!("Decoder recreate failed: {}", e2))?,
-                };
-                new_decoder.set_forward_threshold(threshold);
-                self.decoder_cache.insert(file_path.clone(), new_decoder);
-
-                let decoder = self.decoder_cache.get_mut(&file_path)
-                    .ok_or("Decoder not found after recreate")?;
+        let mut canvas: Option<RenderedFrame> = None;
+        for (mut layer, transform, effects) in layers {
+            // GPU 실패로 넘어온 경우: 지연된 이펙트를 CPU에서 적용
+            if let Some(params) = effects.filter(|_| !layer.is_yuv) {
+                apply_effects(Arc::make_mut(&mut layer.data).as_mut_slice(), layer.width, layer.height, &params);
+            }
 
-                decoder.decode_frame(source_time_ms)
+            // 불투명 전체 화면 레이어는 아래 레이어를 완전히 가림 → 합성 없이 교체
+            if covers_canvas(&layer.data, layer.is_yuv, &transform) {
+                canvas = Some(layer);
+                continue;
             }
+
+            let mut base = canvas.take()
+                .unwrap_or_else(|| self.background_frame(background, timestamp_ms));
+            composite_layer(&mut base, &layer, &transform);
+            canvas = Some(base);
         }
+        canvas
     }
 
-    /// 클립 이펙트 설정 (C# Slider 변경 시 호출)
-    pub fn set_clip_effects(&mut self, clip_id: u64, params: EffectParams) {
-        if params.is_default() {
-            self.clip_effects.remove(&clip_id);
-        } else {
-            self.clip_effects.insert(clip_id, params);
+    /// GPU 합성 on/off (프리뷰 전용)
+    /// 켜면 이펙트가 캐시 이후 GPU 단계로 이동하므로 프레임 캐시를 비움
+    #[cfg(feature = "gpu")]
+    pub fn set_gpu_compositing(&mut self, enabled: bool) -> Result<(), String> {
+        if enabled == self.gpu.is_some() {
+            return Ok(());
+        }
+        if !enabled {
+            self.disable_gpu();
+            return Ok(());
         }
-        // 캐시 클리어 — 이펙트가 변경되면 캐시된 프레임도 무효화
+        if self.export_resolution.is_some() {
+            return Err("GPU compositing is preview-only".to_string());
+        }
+        let gpu = GpuCompositor::new()?;
+        log_info!("[RENDER] GPU compositing enabled ({})", gpu.adapter_name());
+        self.gpu = Some(gpu);
         self.frame_cache.clear();
+        self.last_clip_frames.clear();
+        Ok(())
     }
 
-    /// 클립 이펙트 제거
-    pub fn clear_clip_effects(&mut self, clip_id: u64) {
-        self.clip_effects.remove(&clip_id);
-        self.frame_cache.clear();
+    /// GPU 합성 on/off (gpu feature 없이 빌드되면 켤 수 없음)
+    #[cfg(not(feature = "gpu"))]
+    pub fn set_gpu_compositing(&mut self, enabled: bool) -> Result<(), String> {
+        if enabled {
+            return Err("Built without GPU compositing (feature \"gpu\")".to_string());
+        }
+        Ok(())
     }
 
-    /// 캐시 클리어 (클립 편집 시 호출)
-    pub fn clear_cache(&mut self) {
-        self.frame_cache.clear();
+    /// GPU 합성 사용 중 여부
+    pub fn gpu_compositing(&self) -> bool {
+        #[cfg(feature = "gpu")]
+        {
+            self.gpu.is_some()
+        }
+        #[cfg(not(feature = "gpu"))]
+        {
+            false
+        }
     }
 
-    /// 캐시 통계 조회
-    pub fn cache_stats(&self) -> (u32, usize) {
-        self.frame_cache.stats()
+    /// GPU 합성 끄기 (이펙트 적용 전 프레임이 캐시에 남지 않도록 비움)
+    #[cfg(feature = "gpu")]
+    fn disable_gpu(&mut self) {
+        if self.gpu.take().is_some() {
+            self.frame_cache.clear();
+            self.last_clip_frames.clear();
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
+    /// GPU 합성 단계로 미룬 클립 이펙트 (CPU 합성이거나 타이틀/컬러/바이패스 클립이면 None)
+    fn deferred_effects(&self, clip: &VideoClip, timestamp_ms: i64) -> Option<EffectParams> {
+        if !self.gpu_compositing() || clip.is_generated() || self.is_effects_bypassed(clip.id) {
+            return None;
+        }
+        self.clip_effect_params(clip, timestamp_ms)
+    }
 
-    #[test]
-    fn test_renderer_create() {
-        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
-        let _renderer = Renderer::new(timeline);
+    /// 재생 헤드 다음 구간 미리 읽기 요청 (캐시에 이미 있는 소스 시간 제외)
+    /// 대상: 일반 비디오 클립 (이미지/타이틀/컬러/프레임 보간/이펙트 애니메이션/트랜지션 구간 제외)
+    fn schedule_prefetch(&self, layers: &[(TrackLayer, Option<Grade>)], timestamp_ms: i64, fps: f64) {
+        let prefetcher = match &self.prefetcher {
+            Some(prefetcher) => prefetcher,
+            None => return,
+        };
+        let frame_duration_ms = 1000.0 / if fps > 0.0 { fps } else { 30.0 };
+
+        let requests = layers.iter()
+            .filter_map(|(layer, _)| match layer {
+                TrackLayer::Clip(clip, _) => Some(clip),
+                TrackLayer::Transition { .. } => None,
+            })
+            .filter(|clip| {
+                !clip.is_image() && !clip.is_generated() && !clip.frame_interpolation && !clip.keyframes.animates_effects()
+            })
+            .map(|clip| {
+                let source_key = clip.source_key();
+                let source_times = (1..=prefetcher.frames() as i64)
+                    .filter_map(|i| clip.timeline_to_source_time(timestamp_ms + (i as f64 * frame_duration_ms) as i64))
+                    .filter(|&t| !self.frame_cache.contains(&source_key, t))
+                    .collect();
+                PrefetchRequest {
+                    file_path: clip.file_path.clone(),
+                    stream_index: clip.stream_index,
+                    proxy_path: self.proxy_path(clip),
+                    source_times,
+                }
+            })
+            .collect();
+        prefetcher.request(requests);
     }
 
-    #[test]
-    fn test_frame_cache_lru() {
-        let mut cache = FrameCache::new(3, 100 * 1024 * 1024);
+    /// 활성 자막을 프레임 위에 블렌딩 (YUV Export 프레임은 RGBA 변환 후 블렌딩)
+    fn blend_subtitles(&self, frame: &mut RenderedFrame, timestamp_ms: i64) {
+        let overlays = match &self.subtitles {
+            Some(subtitles) => subtitles.get_active(timestamp_ms),
+            None => return,
+        };
+        if overlays.is_empty() {
+            return;
+        }
+        if frame.is_yuv {
+            let mut rgba = yuv420p_to_rgba(&frame.data, frame.width, frame.height, YuvColor::BT709);
+            blend_overlays_rgba(&mut rgba, frame.width, frame.height, &overlays);
+            frame.data = Arc::new(rgba_to_yuv420p(&rgba, frame.width, frame.height, YuvColor::BT709));
+        } else {
+            blend_overlays_rgba(Arc::make_mut(&mut frame.data).as_mut_slice(), frame.width, frame.height, &overlays);
+        }
+    }
 
-        // 3개 프레임 추가
-        for i in 0..3 {
-            cache.put("test.mp4".to_string(), i * 33, RenderedFrame {
-                width: 960, height: 540, data: vec![0u8; 100], is_yuv: false, timestamp_ms: i * 33,
-            });
+    /// 클립 한 개의 레이어 프레임 (트랜스폼 적용 전, LUT/이펙트 적용 후)
+    /// 디코딩 스킵/실패 시 같은 클립의 직전 프레임 재사용
+    fn render_clip_layer(&mut self, clip: &VideoClip, source_time_ms: i64, timestamp_ms: i64) -> LayerResult {
+        if let Some(title) = &clip.title {
+            return self.render_title_layer(clip.id, title, timestamp_ms);
+        }
+        if let Some(color) = &clip.color {
+            return LayerResult::Frame(self.render_color_frame(Some(clip.id), color, timestamp_ms));
         }
-        assert_eq!(cache.entries.len(), 3);
 
-        // 4번째 추가 → LRU eviction (가장 오래된 0ms 제거)
-        cache.put("test.mp4".to_string(), 99, RenderedFrame {
-            width: 960, height: 540, data: vec![0u8; 100], is_yuv: false, timestamp_ms: 99,
-        });
-        assert_eq!(cache.entries.len(), 3);
-        // 0ms는 evict됨
-        assert!(cache.get("test.mp4", 0).is_none());
-        // 33ms, 66ms, 99ms는 존재
-        assert!(cache.get("test.mp4", 33).is_some());
-        assert!(cache.get("test.mp4", 66).is_some());
-        assert!(cache.get("test.mp4", 99).is_some());
-    }
+        let file_path = clip.source_key();
 
-    #[test]
-    fn test_frame_cache_hit_miss() {
+        // 소스 길이 초과 구간 처리 (Freeze/Black/Loop)
+        let source_time_ms = match self.resolve_end_of_media(clip, source_time_ms) {
+            Some(t) => t,
+            None => {
+                self.diag_no_clip += 1;
+                return LayerResult::Empty;
+            }
+        };
+
+        // 프레임 보간: 소스 프레임 사이 시점이면 앞/뒤 프레임으로 중간 프레임 생성
+        if clip.frame_interpolation {
+            if let Some(frame) = self.render_interpolated(clip, source_time_ms, timestamp_ms) {
+                self.last_clip_frames.insert(clip.id, frame.clone());
+                return LayerResult::Frame(frame);
+            }
+        }
+
+        // 이펙트 애니메이션 클립은 같은 소스 프레임이라도 시점마다 결과가 다름 → 캐시 우회
+        let cacheable = !clip.keyframes.animates_effects();
+
+        // 1단계: 캐시 조회 (.cloned()로 즉시 소유권 획득 → 가변 참조 해제)
+        if let Some(mut frame) = self.frame_cache.get(&file_path, source_time_ms).cloned().filter(|_| cacheable) {
+            frame.timestamp_ms = timestamp_ms;
+            self.diag_cache_hit += 1;
+            self.last_clip_frames.insert(clip.id, frame.clone());
+            return LayerResult::Frame(frame);
+        }
+
+        // 2단계: 미리 읽은 프레임, 없으면 디코딩
+        let decode_start = std::time::Instant::now();
+        let prefetched = self.prefetcher.as_ref().and_then(|p| p.take(&file_path, source_time_ms));
+        let result = match prefetched {
+            Some(frame) => Ok(DecodeResult::Frame(frame)),
+            None => self.decode_clip_frame(clip, source_time_ms),
+        };
+        let decode_elapsed = decode_start.elapsed().as_millis();
+
+        // 처음 10프레임 또는 50ms 이상 걸린 경우 로그
+        if self.diag_total <= 10 || decode_elapsed > 50 {
+            log_debug!(
+                "[RENDER] t={}ms src={}ms decode={}ms total_frames={}",
+                timestamp_ms, source_time_ms, decode_elapsed, self.diag_total
+            );
+        }
+
+        let rendered = match result {
+            Ok(DecodeResult::Frame(frame)) => {
+                self.diag_decoded += 1;
+                let rendered = self.frame_to_rendered(clip, frame, timestamp_ms);
+                // 캐시에 저장
+                if cacheable {
+                    self.frame_cache.put(file_path, source_time_ms, rendered.clone());
+                }
+                rendered
+            }
+            Ok(DecodeResult::EndOfStream(frame)) => {
+                self.diag_eof += 1;
+                self.frame_to_rendered(clip, frame, timestamp_ms)
+            }
+            Ok(DecodeResult::FrameSkipped) => {
+                self.diag_skipped += 1;
+                return self.previous_clip_frame(clip.id, timestamp_ms);
+            }
+            Ok(DecodeResult::EndOfStreamEmpty) => {
+                self.diag_eof += 1;
+                return self.previous_clip_frame(clip.id, timestamp_ms);
+            }
+            Err(e) => {
+                self.diag_error += 1;
+                log_warn!("Decode error at {}ms: {}", timestamp_ms, e);
+                return self.previous_clip_frame(clip.id, timestamp_ms);
+            }
+        };
+
+        self.last_clip_frames.insert(clip.id, rendered.clone());
+        LayerResult::Frame(rendered)
+    }
+
+    /// 클립 맞춤 모드 적용 (소스와 출력 종횡비가 다를 때 레터박스/크롭/원본 크기로 다시 배치, 타이틀/컬러는 무시)
+    /// 캐시/디코더 프레임은 출력 크기로 늘어난 상태 → 소스 표시 크기 비율로 배치 (Export도 같은 경로)
+    fn fit_clip_layer(&self, clip: &VideoClip, result: LayerResult) -> LayerResult {
+        let frame = match result {
+            LayerResult::Frame(frame) if !clip.is_generated() => frame,
+            other => return other,
+        };
+        let source_size = match self.source_sizes.get(&clip.source_key()) {
+            Some(&size) => size,
+            None => return LayerResult::Frame(frame),
+        };
+        let (width, height) = self.canvas_size();
+        let native_scale = match clip.fit_mode {
+            FitMode::Custom => self.timeline.lock()
+                .map(|t| width as f32 / t.width.max(1) as f32)
+                .unwrap_or(1.0),
+            _ => 1.0,
+        };
+        match fit_rect(source_size, width, height, clip.fit_mode, native_scale) {
+            Some(rect) => LayerResult::Frame(fit_frame(frame, width, height, rect)),
+            None => LayerResult::Frame(frame),
+        }
+    }
+
+    /// 타이틀 클립 레이어 (출력 크기 투명 RGBA 위에 텍스트, 애니메이션은 transform_at에서 적용)
+    /// 스타일은 프로젝트 해상도 기준 → 출력 높이 비율로 조정
+    fn render_title_layer(&mut self, clip_id: u64, title: &TitleClip, timestamp_ms: i64) -> LayerResult {
+        let (width, height) = self.canvas_size();
+        if let Some((cached_title, frame)) = self.title_cache.get(&clip_id) {
+            if cached_title == title && frame.width == width && frame.height == height {
+                let mut frame = frame.clone();
+                frame.timestamp_ms = timestamp_ms;
+                return LayerResult::Frame(frame);
+            }
+        }
+
+        let project_height = self.timeline.lock().map(|t| t.height).unwrap_or(height);
+        let style = title.style.scaled(height as f32 / project_height.max(1) as f32);
+        let overlay = text::shared_renderer(title.font_path.as_deref())
+            .map(|renderer| renderer.render_overlay(&title.text, &style, 0, 0, width, height));
+
+        let mut frame = transparent_frame(width, height, timestamp_ms);
+        match overlay {
+            Ok(Some(overlay)) => copy_bitmap_rgba(&mut frame, &overlay.rgba_data, overlay.x, overlay.y, overlay.width, overlay.height),
+            // 빈 텍스트 → 투명 레이어
+            Ok(None) => {}
+            Err(e) => {
+                log_warn!("[TITLE] {}", e);
+                return LayerResult::Empty;
+            }
+        }
+
+        self.title_cache.insert(clip_id, (title.clone(), frame.clone()));
+        LayerResult::Frame(frame)
+    }
+
+    /// 컬러 레이어 (출력 크기 단색/그라디언트, key: 클립 ID 또는 None = 타임라인 배경)
+    /// 불투명한 Export 레이어는 YUV로 보관 → 아래 레이어를 가리면 변환 없이 캔버스로 사용
+    fn render_color_frame(&mut self, key: Option<u64>, color: &ColorClip, timestamp_ms: i64) -> RenderedFrame {
+        let (width, height) = self.canvas_size();
+        if let Some((cached_color, frame)) = self.color_cache.get(&key) {
+            if cached_color == color && frame.width == width && frame.height == height {
+                let mut frame = frame.clone();
+                frame.timestamp_ms = timestamp_ms;
+                return frame;
+            }
+        }
+
+        let rgba = color.render_rgba(width, height);
+        let (data, is_yuv) = if self.export_resolution.is_some() && color.is_opaque() {
+            (rgba_to_yuv420p(&rgba, width, height, YuvColor::BT709), true)
+        } else {
+            (rgba, false)
+        };
+        let frame = RenderedFrame { width, height, data: Arc::new(data), timestamp_ms, is_yuv };
+        self.color_cache.insert(key, (*color, frame.clone()));
+        frame
+    }
+
+    /// 레이어가 없을 때의 출력 프레임 (단색 배경은 캐시 없이 생성, 그라디언트는 컬러 레이어 캐시)
+    fn background_fill_frame(&mut self, background: &ColorClip, timestamp_ms: i64) -> RenderedFrame {
+        match background.solid_rgb() {
+            Some(color) => self.background_frame(color, timestamp_ms),
+            None => self.render_color_frame(None, background, timestamp_ms),
+        }
+    }
+
+    /// 트랜지션 구간 레이어 (앞/뒤 클립을 각자 트랜스폼 적용 후 블렌딩)
+    /// 한쪽이 Empty면 투명 프레임과 블렌딩, 한쪽만 실패하면 나머지 클립만 표시
+    fn render_transition_layer(
+        &mut self,
+        transition: &Transition,
+        from: &VideoClip,
+        to: &VideoClip,
+        progress: f32,
+        timestamp_ms: i64,
+    ) -> LayerResult {
+        let from_layer = self.render_clip_layer(from, from.source_time_with_handles(timestamp_ms), timestamp_ms);
+        let from_layer = self.fit_clip_layer(from, from_layer);
+        let to_layer = self.render_clip_layer(to, to.source_time_with_handles(timestamp_ms), timestamp_ms);
+        let to_layer = self.fit_clip_layer(to, to_layer);
+        // 트랜지션 블렌딩은 CPU → GPU 합성 중이어도 클립 이펙트는 여기서 적용
+        let from_layer = self.apply_deferred_effects(from_layer, from, timestamp_ms);
+        let to_layer = self.apply_deferred_effects(to_layer, to, timestamp_ms);
+
+        let place = |layer: RenderedFrame, clip: &VideoClip| place_layer(layer, &clip.transform_at(timestamp_ms));
+        let (from_frame, to_frame) = match (from_layer, to_layer) {
+            (LayerResult::Frame(a), LayerResult::Frame(b)) => (place(a, from), place(b, to)),
+            (LayerResult::Frame(a), LayerResult::Empty) => {
+                let a = place(a, from);
+                let empty = transparent_frame(a.width, a.height, timestamp_ms);
+                (a, empty)
+            }
+            (LayerResult::Empty, LayerResult::Frame(b)) => {
+                let b = place(b, to);
+                (transparent_frame(b.width, b.height, timestamp_ms), b)
+            }
+            (LayerResult::Frame(a), LayerResult::Failed) => return LayerResult::Frame(place(a, from)),
+            (LayerResult::Failed, LayerResult::Frame(b)) => return LayerResult::Frame(place(b, to)),
+            (LayerResult::Empty, LayerResult::Empty) => return LayerResult::Empty,
+            _ => return LayerResult::Failed,
+        };
+
+        let rgba = |frame: &RenderedFrame| if frame.is_yuv {
+            yuv420p_to_rgba(&frame.data, frame.width, frame.height, YuvColor::BT709)
+        } else {
+            frame.data.to_vec()
+        };
+        let blended = blend_transition(
+            &rgba(&from_frame), &rgba(&to_frame),
+            from_frame.width, from_frame.height,
+            transition.kind, progress,
+        );
+
+        // Export: 불투명 결과는 YUV로 복원 (캔버스 교체 시 포맷 유지), 투명 영역이 있으면 RGBA로 합성
+        let is_yuv = self.export_resolution.is_some() && blended.chunks_exact(4).all(|p| p[3] == 255);
+        LayerResult::Frame(RenderedFrame {
+            width: from_frame.width,
+            height: from_frame.height,
+            data: Arc::new(if is_yuv { rgba_to_yuv420p(&blended, from_frame.width, from_frame.height, YuvColor::BT709) } else { blended }),
+            timestamp_ms,
+            is_yuv,
+        })
+    }
+
+    /// GPU 합성 단계로 미룬 이펙트를 CPU에서 적용 (트랜지션 입력용)
+    fn apply_deferred_effects(&self, layer: LayerResult, clip: &VideoClip, timestamp_ms: i64) -> LayerResult {
+        match (layer, self.deferred_effects(clip, timestamp_ms)) {
+            (LayerResult::Frame(mut frame), Some(params)) if !frame.is_yuv => {
+                apply_effects(Arc::make_mut(&mut frame.data).as_mut_slice(), frame.width, frame.height, &params);
+                LayerResult::Frame(frame)
+            }
+            (layer, _) => layer,
+        }
+    }
+
+    /// 클립의 직전 레이어 프레임 (프레임 스킵/에러 시 fallback)
+    fn previous_clip_frame(&self, clip_id: u64, timestamp_ms: i64) -> LayerResult {
+        match self.last_clip_frames.get(&clip_id) {
+            Some(frame) => {
+                let mut frame = frame.clone();
+                frame.timestamp_ms = timestamp_ms;
+                LayerResult::Frame(frame)
+            }
+            None => LayerResult::Failed,
+        }
+    }
+
+    /// 배경색 프레임 (Export: YUV420P 출력 크기, 프리뷰: RGBA 프리뷰 크기)
+    fn background_frame(&self, color: [u8; 3], timestamp_ms: i64) -> RenderedFrame {
+        let (w, h) = self.canvas_size();
+        match self.export_resolution {
+            Some(_) => solid_frame_yuv(w, h, color, timestamp_ms),
+            None => solid_frame_rgba(w, h, color, timestamp_ms),
+        }
+    }
+
+    /// 출력 프레임 크기 (Export 해상도, 프리뷰 preview_size)
+    fn canvas_size(&self) -> (u32, u32) {
+        self.export_resolution.unwrap_or(self.preview_size)
+    }
+
+    /// 디코딩 프레임 → RenderedFrame
+    /// 클립 LUT → 이펙트 순으로 프리뷰/Export 모두 적용 (grade_frame)
+    /// 바이패스 중이면 둘 다 건너뜀 (원본 프레임)
+    fn frame_to_rendered(&mut self, clip: &VideoClip, frame: Frame, timestamp_ms: i64) -> RenderedFrame {
+        let is_yuv = frame.format == crate::ffmpeg::PixelFormat::YUV420P;
+        // 소스 행렬이 Export 색공간과 다르면 (SD BT.601, BT.2020 등) 변환 → 합성/인코딩은 모두 BT.709 기준
+        let data = if is_yuv && frame.color != YuvColor::BT709 {
+            let rgba = yuv420p_to_rgba(&frame.data, frame.width, frame.height, frame.color);
+            rgba_to_yuv420p(&rgba, frame.width, frame.height, YuvColor::BT709)
+        } else {
+            frame.data
+        };
+        let mut rendered = RenderedFrame {
+            width: frame.width,
+            height: frame.height,
+            data: Arc::new(data),
+            timestamp_ms,
+            is_yuv,
+        };
+        if self.is_effects_bypassed(clip.id) {
+            return rendered;
+        }
+        let lut = clip.lut_path.as_deref().and_then(|path| self.load_lut(path));
+        // GPU 합성 중이면 RGBA 이펙트는 합성 단계에서 적용 (deferred_effects, YUV 레이어는 제외되므로 여기서)
+        let effects = if rendered.is_yuv || !self.gpu_compositing() {
+            self.clip_effect_params(clip, timestamp_ms)
+        } else {
+            None
+        };
+        grade_frame(&mut rendered, lut.as_deref(), effects.as_ref());
+        rendered
+    }
+
+    /// 트랙/마스터 그레이딩 적용
+    fn apply_grade(&mut self, frame: &mut RenderedFrame, grade: &Grade) {
+        let lut = grade.lut_path.as_deref().and_then(|path| self.load_lut(path));
+        grade_frame(frame, lut.as_deref(), grade.effects.as_ref());
+    }
+
+    /// 타임라인 시간의 클립 이펙트 파라미터 (키프레임 보간 값 우선, 기본값이면 None)
+    fn clip_effect_params(&self, clip: &VideoClip, timestamp_ms: i64) -> Option<EffectParams> {
+        let mut params = clip.effects.clone();
+        if clip.keyframes.animates_effects() {
+            let t = timestamp_ms - clip.start_time_ms;
+            let value = |property, fallback: f32| clip.keyframes.value_at(property, t).unwrap_or(fallback);
+            params.brightness = value(KeyframeProperty::Brightness, params.brightness);
+            params.contrast = value(KeyframeProperty::Contrast, params.contrast);
+            params.saturation = value(KeyframeProperty::Saturation, params.saturation);
+            params.temperature = value(KeyframeProperty::Temperature, params.temperature);
+            params.gamma = value(KeyframeProperty::Gamma, params.gamma);
+            params.highlights = value(KeyframeProperty::Highlights, params.highlights);
+            params.shadows = value(KeyframeProperty::Shadows, params.shadows);
+            params.tint = value(KeyframeProperty::Tint, params.tint);
+            params.vignette = value(KeyframeProperty::Vignette, params.vignette);
+        }
+        (!params.is_default()).then_some(params)
+    }
+
+    /// LUT 파일 (캐시, 로드 실패 시 None → 원본 유지)
+    fn load_lut(&mut self, lut_path: &std::path::Path) -> Option<Arc<Lut3D>> {
+        match self.lut_cache.get_or_load(lut_path) {
+            Ok(lut) => Some(lut),
+            Err(e) => {
+                log_warn!("[LUT] {}", e);
+                None
+            }
+        }
+    }
+
+    /// 캐시 우선 소스 프레임 조회 (정상 디코딩된 프레임만, 실패/EOF는 None)
+    fn decode_cached(&mut self, clip: &VideoClip, source_time_ms: i64, timestamp_ms: i64) -> Option<RenderedFrame> {
+        let file_path = clip.source_key();
+        let cacheable = !clip.keyframes.animates_effects();
+        if cacheable {
+            if let Some(frame) = self.frame_cache.get(&file_path, source_time_ms).cloned() {
+                self.diag_cache_hit += 1;
+                return Some(frame);
+            }
+        }
+
+        match self.decode_clip_frame(clip, source_time_ms) {
+            Ok(DecodeResult::Frame(frame)) => {
+                self.diag_decoded += 1;
+                let rendered = self.frame_to_rendered(clip, frame, timestamp_ms);
+                if cacheable {
+                    self.frame_cache.put(file_path, source_time_ms, rendered.clone());
+                }
+                Some(rendered)
+            }
+            _ => None,
+        }
+    }
+
+    /// 소스 프레임 사이 시점이면 앞/뒤 프레임을 모션 보상 보간
+    /// 프레임 경계에 가깝거나 디코딩 실패 시 None → 일반 경로로 처리
+    fn render_interpolated(&mut self, clip: &VideoClip, source_time_ms: i64, timestamp_ms: i64) -> Option<RenderedFrame> {
+        let (prev_ms, next_ms, phase) = self.source_frame_position(clip, source_time_ms)?;
+        if !(INTERPOLATION_MIN_PHASE..=1.0 - INTERPOLATION_MIN_PHASE).contains(&phase) {
+            return None;
+        }
+
+        let prev = self.decode_cached(clip, prev_ms, timestamp_ms)?;
+        let next = self.decode_cached(clip, next_ms, timestamp_ms)?;
+
+        if prev.width != next.width || prev.height != next.height || prev.is_yuv != next.is_yuv {
+            return None;
+        }
+
+        let data = if prev.is_yuv {
+            interpolate_yuv420p(&prev.data, &next.data, prev.width, prev.height, phase)?
+        } else {
+            interpolate_rgba(&prev.data, &next.data, prev.width, prev.height, phase)?
+        };
+
+        Some(RenderedFrame {
+            width: prev.width,
+            height: prev.height,
+            data: Arc::new(data),
+            timestamp_ms,
+            is_yuv: prev.is_yuv,
+        })
+    }
+
+    /// 소스 시간의 앞/뒤 소스 프레임 시작 (ms 올림 → 해당 프레임이 확실히 선택됨) + 사이 위치 (0~1)
+    /// VFR 소스는 프레임 인덱스의 실제 PTS, 그 외는 평균 fps로 계산
+    fn source_frame_position(&mut self, clip: &VideoClip, source_time_ms: i64) -> Option<(i64, i64, f32)> {
+        if let Some(index) = self.frame_index(clip) {
+            let (start, end) = index.span_at(source_time_ms)?;
+            if end <= start || source_time_ms < start {
+                return None;
+            }
+            return Some((start, end, (source_time_ms - start) as f32 / (end - start) as f32));
+        }
+
+        let fps = self.decoder_cache.get(&decoder_key(clip))?.fps();
+        if fps <= 0.0 {
+            return None;
+        }
+        let frame_duration = 1000.0 / fps;
+        let position = source_time_ms as f64 / frame_duration;
+        let index = position.floor();
+        let prev_ms = (index * frame_duration).ceil() as i64;
+        let next_ms = ((index + 1.0) * frame_duration).ceil() as i64;
+        Some((prev_ms, next_ms, (position - index) as f32))
+    }
+
+    /// VFR 소스의 프레임 인덱스 (최초 요청 시 스캔, 고정 프레임레이트 소스나 스캔 실패는 None)
+    fn frame_index(&mut self, clip: &VideoClip) -> Option<&FrameIndex> {
+        if !self.decoder_cache.get(&decoder_key(clip))?.is_vfr() {
+            return None;
+        }
+        let file_path = clip.source_key();
+        if !self.frame_indexes.contains_key(&file_path) {
+            match Decoder::scan_frame_index(&clip.file_path, clip.stream_index) {
+                Ok(index) if !index.is_empty() => {
+                    self.frame_indexes.insert(file_path.clone(), index);
+                }
+                Ok(_) => return None,
+                Err(e) => {
+                    log_warn!("[RENDER] Frame index scan failed ({}): {}", file_path, e);
+                    return None;
+                }
+            }
+        }
+        self.frame_indexes.get(&file_path)
+    }
+
+    /// 진단 통계 출력 (30프레임=~1초마다)
+    fn print_diag_if_needed(&self, last_ts: i64) {
+        if self.diag_total % 30 == 0 {
+            log_debug!(
+                "[RENDER DIAG] t={}ms | total={} cache={} decode={} eof={} skip={} noclip={} err={}",
+                last_ts,
+                self.diag_total,
+                self.diag_cache_hit,
+                self.diag_decoded,
+                self.diag_eof,
+                self.diag_skipped,
+                self.diag_no_clip,
+                self.diag_error
+            );
+        }
+    }
+
+    /// 클립용 디코더 생성 (현재 모드의 forward_threshold 적용)
+    /// Export: LANCZOS 고품질, 프리뷰: FAST_BILINEAR
+    fn open_decoder(&self, clip: &VideoClip) -> Result<Decoder, String> {
+        let threshold = if self.playback_mode { 5000 } else { 100 };
+        let mut decoder = match self.proxy_path(clip) {
+            Some(proxy) => Decoder::open_stream(&proxy, None, None, self.preview_size, self.hw_decode)?,
+            None => Decoder::open_stream(
+                &clip.file_path, clip.stream_index, self.export_resolution, self.preview_size, self.hw_decode,
+            )?,
+        };
+        decoder.set_forward_threshold(threshold);
+        decoder.set_limits(self.decode_limits);
+        Ok(decoder)
+    }
+
+    /// 디코더 풀에 추가 (한도 초과로 닫힌 디코더의 순차 커서도 제거)
+    fn insert_decoder(&mut self, key: String, decoder: Decoder) {
+        for evicted in self.decoder_cache.insert(key, decoder) {
+            self.sequential_cursors.remove(&evicted);
+        }
+    }
+
+    /// 클립 디코더 준비 (반환: 풀 키)
+    /// 없으면 같은 소스(파일 + 스트림)의 다른 클립 디코더 중 seek 없이 이어서 디코딩할 수 있는 것을 넘겨받고
+    /// (컷 편집으로 이어지는 구간), 없으면 새로 열기
+    fn ensure_decoder(&mut self, clip: &VideoClip, source_time_ms: i64) -> Result<String, String> {
+        let key = decoder_key(clip);
+        if self.decoder_cache.contains_key(&key) {
+            return Ok(key);
+        }
+
+        let source_key = clip.source_key();
+        let donor = self.decoder_cache.iter()
+            .find(|(other, decoder)| is_same_file_key(other, &source_key) && decoder.can_continue_to(source_time_ms))
+            .map(|(other, _)| other.clone());
+        let decoder = match donor.and_then(|other| self.decoder_cache.remove(&other).map(|d| (other, d))) {
+            Some((other, decoder)) => {
+                // 디코더 위치를 따르는 순차 커서도 함께 이동
+                if let Some(cursor) = self.sequential_cursors.remove(&other) {
+                    self.sequential_cursors.insert(key.clone(), cursor);
+                }
+                decoder
+            }
+            None => self.open_decoder(clip)?,
+        };
+        self.source_sizes.entry(source_key).or_insert(decoder.display_size());
+        self.insert_decoder(key.clone(), decoder);
+        Ok(key)
+    }
+
+    /// 소스 실제 길이를 기준으로 클립의 end_behavior 적용
+    /// None이면 검은 프레임 (EndOfMediaBehavior::Black)
+    /// 이미지 클립은 항상 소스 0ms (길이 제한 없음)
+    fn resolve_end_of_media(&mut self, clip: &VideoClip, source_time_ms: i64) -> Option<i64> {
+        if clip.is_image() {
+            return Some(0);
+        }
+        // 열기 실패는 디코딩 단계에서 처리 (에러 카운트/fallback 프레임)
+        let key = match self.ensure_decoder(clip, source_time_ms) {
+            Ok(key) => key,
+            Err(_) => return Some(source_time_ms),
+        };
+
+        // VFR: 마지막 프레임의 실제 시작/길이 (평균 fps로 계산하면 다른 프레임에서 정지)
+        if let Some(index) = self.frame_index(clip) {
+            let last_duration_ms = index.frame_duration_ms(index.len() - 1);
+            let end_ms = index.end_ms();
+            return clip.apply_end_behavior(source_time_ms, end_ms, last_duration_ms);
+        }
+
+        let decoder = self.decoder_cache.get(&key)?;
+        let frame_duration_ms = if decoder.fps() > 0.0 {
+            (1000.0 / decoder.fps()).max(1.0) as i64
+        } else {
+            33
+        };
+
+        clip.apply_end_behavior(source_time_ms, decoder.duration_ms(), frame_duration_ms)
+    }
+
+    /// 클립의 프레임 디코딩 (DecodeResult 반환)
+    /// 에러 시 디코더 재생성 1회 재시도 (corrupted state 복구)
+    fn decode_clip_frame(&mut self, clip: &VideoClip, source_time_ms: i64) -> Result<DecodeResult, String> {
+        let file_path = clip.file_path.to_string_lossy().to_string();
+
+        // 프리뷰/Export 동시 디코딩 조정 (Export는 프리뷰에 양보)
+        let priority = if self.export_resolution.is_some() {
+            DecodePriority::Export
+        } else {
+            DecodePriority::Preview
+        };
+        let _permit = DecodeScheduler::global().acquire(priority);
+
+        if clip.is_image() {
+            return self.load_image_frame(clip).map(DecodeResult::Frame);
+        }
+
+        // Error 상태 디코더는 제거 후 재생성 (복구 불가능 상태 탈출)
+        let key = decoder_key(clip);
+        if let Some(decoder) = self.decoder_cache.get(&key) {
+            if decoder.state() == crate::ffmpeg::DecoderState::Error {
+                log_warn!("[DECODER] Error state, recreating: {}", file_path);
+                self.decoder_cache.remove(&key);
+                self.sequential_cursors.remove(&key);
+            }
+        }
+
+        // 클립 디코더가 없으면 같은 파일 디코더를 넘겨받거나 생성
+        let key = self.ensure_decoder(clip, source_time_ms)?;
+
+        // Export: 순차 접근이면 다음 프레임만 읽음 (seek/threshold 판단 생략)
+        if self.export_resolution.is_some() {
+            if let Some(result) = self.decode_sequential(&key, source_time_ms) {
+                return Ok(result);
+            }
+        }
+
+        let decoder = self.decoder_cache.get_mut(&key)
+            .ok_or("Decoder not found in cache")?;
+
+        // 역방향 재생 프리뷰: GOP 단위 링 버퍼 디코딩
+        if self.reverse_playback && self.export_resolution.is_none() {
+            return decoder.decode_frame_reverse(source_time_ms);
+        }
+
+        let result = match decoder.decode_frame(source_time_ms) {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                log_warn!("[DECODER] Decode error at {}ms: {}, recreating decoder", source_time_ms, e);
+                self.decoder_cache.remove(&key);
+
+                let new_decoder = self.open_decoder(clip)
+                    .map_err(|e2| format!("Decoder recreate failed: {}", e2))?;
+                self.insert_decoder(key.clone(), new_decoder);
+
+                let decoder = self.decoder_cache.get_mut(&key)
+                    .ok_or("Decoder not found after recreate")?;
+
+                decoder.decode_frame(source_time_ms)
+            }
+        };
+
+        // Export: 정확 디코딩한 위치에서 순차 커서 시작 (디코더는 그 다음 프레임부터 이어짐)
+        if self.export_resolution.is_some() {
+            match &result {
+                Ok(DecodeResult::Frame(frame)) => {
+                    self.sequential_cursors.insert(key, SequentialCursor {
+                        current: frame.clone(),
+                        current_start_ms: source_time_ms,
+                        next: None,
+                        last_source_ms: source_time_ms,
+                        ended: false,
+                    });
+                }
+                _ => {
+                    self.sequential_cursors.remove(&key);
+                }
+            }
+        }
+        result
+    }
+
+    /// Export 순차 디코딩 (Decoder::frames로 다음 프레임만 읽음)
+    /// 커서가 없거나 역방향/먼 점프, 디코딩 에러면 None → decode_frame 경로 (seek + 커서 재시작)
+    fn decode_sequential(&mut self, key: &str, source_time_ms: i64) -> Option<DecodeResult> {
+        let cursor = self.sequential_cursors.get_mut(key)?;
+        if source_time_ms < cursor.current_start_ms || source_time_ms - cursor.last_source_ms > SEQUENTIAL_MAX_GAP_MS {
+            self.sequential_cursors.remove(key);
+            return None;
+        }
+        let decoder = self.decoder_cache.get_mut(key)?;
+        let mut frames = decoder.frames();
+
+        // 표시 시간이 된 프레임까지 전진 (PTS <= 소스 시간)
+        loop {
+            if cursor.next.is_none() && !cursor.ended {
+                match frames.next() {
+                    Some(Ok(frame)) => cursor.next = Some(frame),
+                    Some(Err(e)) => {
+                        log_warn!("[DECODER] Sequential decode error at {}ms: {}", source_time_ms, e);
+                        self.sequential_cursors.remove(key);
+                        return None;
+                    }
+                    None => cursor.ended = true,
+                }
+            }
+            match cursor.next.take() {
+                Some(frame) if frame.timestamp_ms <= source_time_ms => {
+                    cursor.current_start_ms = frame.timestamp_ms;
+                    cursor.current = frame;
+                }
+                next => {
+                    cursor.next = next;
+                    break;
+                }
+            }
+        }
+        cursor.last_source_ms = source_time_ms;
+
+        // 스트림 끝 + 마지막 프레임 표시 구간도 지남 → EOF (길이를 모르면 마지막 프레임 유지)
+        let past_end = cursor.ended
+            && cursor.current.duration_ms > 0
+            && source_time_ms >= cursor.current_start_ms + cursor.current.duration_ms;
+        let frame = Frame { timestamp_ms: source_time_ms, ..cursor.current.clone() };
+        Some(if past_end { DecodeResult::EndOfStream(frame) } else { DecodeResult::Frame(frame) })
+    }
+
+    /// 이미지 클립 프레임 (첫 요청 시 디코딩 후 캐시)
+    fn load_image_frame(&mut self, clip: &VideoClip) -> Result<Frame, String> {
+        let file_path = clip.file_path.to_string_lossy().to_string();
+        if let Some(frame) = self.image_cache.get(&file_path) {
+            return Ok(frame.clone());
+        }
+
+        let (frame, display_size) = load_image(&clip.file_path, self.export_resolution, self.preview_size)?;
+        self.source_sizes.insert(clip.source_key(), display_size);
+        self.image_cache.insert(file_path, frame.clone());
+        Ok(frame)
+    }
+
+    /// 타임라인 필름스트립 렌더링 (프로젝트 썸네일/Export 미리보기)
+    /// 타임라인 길이를 frame_count 등분한 구간 중앙 시점의 합성 프레임(이펙트 포함)을
+    /// 가로로 이어붙인 하나의 RGBA 버퍼 반환 (width = frame_count * tile_width)
+    pub fn render_filmstrip(&mut self, frame_count: u32, tile_width: u32, tile_height: u32) -> Result<RenderedFrame, String> {
+        if frame_count == 0 || tile_width == 0 || tile_height == 0 {
+            return Err("Invalid filmstrip size".to_string());
+        }
+        if self.export_resolution.is_some() {
+            return Err("Filmstrip is not available in export mode".to_string());
+        }
+
+        let duration_ms = self.timeline.lock()
+            .map_err(|e| format!("Failed to lock timeline: {}", e))?
+            .duration_ms();
+
+        let strip_width = frame_count * tile_width;
+        let mut data = vec![0u8; (strip_width * tile_height * 4) as usize];
+
+        for i in 0..frame_count {
+            let timestamp_ms = (duration_ms as f64 * (i as f64 + 0.5) / frame_count as f64) as i64;
+            let frame = self.render_frame(timestamp_ms)?;
+            if frame.is_yuv || frame.width == 0 || frame.height == 0 {
+                continue;
+            }
+            blit_scaled_rgba(&frame, &mut data, strip_width, i * tile_width, tile_width, tile_height);
+        }
+
+        Ok(RenderedFrame {
+            width: strip_width,
+            height: tile_height,
+            data: Arc::new(data),
+            timestamp_ms: 0,
+            is_yuv: false,
+        })
+    }
+
+    /// 프로젝트 해상도 스틸 프레임 렌더링 (포스터/썸네일 이미지 저장용, 항상 RGBA)
+    /// 프리뷰 캐시/디코더와 격리된 Export 렌더러로 정확한 프레임을 디코딩 (960x540 프리뷰 프레임 재사용 안 함)
+    /// 프리뷰 자막은 960x540 좌표 기준이므로 포함하지 않음
+    pub fn render_still(timeline: Arc<Mutex<Timeline>>, timestamp_ms: i64) -> Result<RenderedFrame, String> {
+        let (width, height) = {
+            let timeline = timeline.lock()
+                .map_err(|e| format!("Failed to lock timeline: {}", e))?;
+            (timeline.width, timeline.height)
+        };
+        if width == 0 || height == 0 {
+            return Err("Invalid project resolution".to_string());
+        }
+
+        let mut renderer = Renderer::new_for_export(timeline, width, height);
+        let mut frame = renderer.render_frame(timestamp_ms)?;
+        if frame.is_yuv {
+            frame.data = Arc::new(yuv420p_to_rgba(&frame.data, frame.width, frame.height, YuvColor::BT709));
+            frame.is_yuv = false;
+        }
+        Ok(frame)
+    }
+
+    /// 클립 이펙트 설정 (C# Slider 변경 시 호출)
+    /// 타임라인 클립에 저장 → 같은 타임라인으로 만든 Export Renderer에도 적용
+    /// 반환: 타임라인에 클립이 없으면 false
+    pub fn set_clip_effects(&mut self, clip_id: u64, params: EffectParams) -> bool {
+        let stored = self.timeline.lock()
+            .map(|mut timeline| timeline.set_clip_effects(clip_id, params))
+            .unwrap_or(false);
+        // 이 클립 소스 구간의 캐시만 무효화 (다른 클립 프레임은 유지)
+        self.invalidate_clip(clip_id);
+        stored
+    }
+
+    /// 클립 이펙트 제거
+    pub fn clear_clip_effects(&mut self, clip_id: u64) -> bool {
+        self.set_clip_effects(clip_id, EffectParams::default())
+    }
+
+    /// 클립별 이펙트 파라미터 조회 (기본값이 아닌 클립만, 통계용)
+    pub fn clip_effects(&self) -> EffectStore {
+        let timeline = match self.timeline.lock() {
+            Ok(t) => t,
+            Err(_) => return EffectStore::new(),
+        };
+        timeline.video_tracks.iter()
+            .flat_map(|track| &track.clips)
+            .filter(|clip| !clip.effects.is_default())
+            .map(|clip| (clip.id, clip.effects.clone()))
+            .collect()
+    }
+
+    /// 디코더 탐색 한도 설정 (열린 디코더 + 이후 여는 디코더 모두 적용)
+    /// 스크럽: 작은 한도로 응답성 우선, Export: 무제한으로 정확도 우선
+    pub fn set_decode_limits(&mut self, limits: DecodeLimits) {
+        self.decode_limits = limits;
+        for decoder in self.decoder_cache.values_mut() {
+            decoder.set_limits(limits);
+        }
+    }
+
+    pub fn decode_limits(&self) -> DecodeLimits {
+        self.decode_limits
+    }
+
+    /// 동시에 열어 두는 최대 디코더 수 (줄이면 오래 안 쓴 디코더부터 즉시 닫음)
+    pub fn set_max_decoders(&mut self, max_decoders: usize) {
+        for evicted in self.decoder_cache.set_max_decoders(max_decoders) {
+            self.sequential_cursors.remove(&evicted);
+        }
+    }
+
+    pub fn max_decoders(&self) -> usize {
+        self.decoder_cache.max_decoders()
+    }
+
+    /// 현재 열린 디코더 수
+    pub fn open_decoder_count(&self) -> usize {
+        self.decoder_cache.len()
+    }
+
+    /// 프리뷰 하드웨어 디코딩 on/off
+    /// 열린 디코더는 닫아서 다음 렌더링 시 새 설정으로 다시 열림
+    pub fn set_hw_decode(&mut self, enabled: bool) {
+        if self.hw_decode != enabled {
+            self.hw_decode = enabled;
+            self.decoder_cache.clear();
+        }
+    }
+
+    /// 프리뷰 프록시 사용 on/off (Export 렌더러에서는 무시)
+    /// 프록시와 원본은 해상도/화질이 달라 디코더와 프레임 캐시를 비우고 다시 열기
+    pub fn set_use_proxies(&mut self, enabled: bool) {
+        if self.use_proxies != enabled {
+            self.use_proxies = enabled;
+            self.reload_sources();
+        }
+    }
+
+    /// 클립을 디코딩할 프록시 파일 (프록시 사용 중인 프리뷰 + 기본 비디오 스트림 + 등록된 프록시)
+    fn proxy_path(&self, clip: &VideoClip) -> Option<PathBuf> {
+        if !self.use_proxies || self.export_resolution.is_some() || clip.stream_index.is_some() {
+            return None;
+        }
+        proxy::proxy_for(&clip.file_path)
+    }
+
+    /// 프리뷰 해상도 변경 (Export 렌더러에서는 무시)
+    /// 디코더 스케일러 출력 크기가 바뀌므로 디코더/캐시/미리 읽기 워커를 모두 새로 만듦
+    /// 반환: 적용된 프리뷰 프레임 크기
+    pub fn set_preview_resolution(&mut self, resolution: PreviewResolution) -> Result<(u32, u32), String> {
+        if self.export_resolution.is_some() {
+            return Ok(self.canvas_size());
+        }
+        let size = {
+            let timeline = self.timeline.lock()
+                .map_err(|e| format!("Failed to lock timeline: {}", e))?;
+            resolution.size(timeline.width, timeline.height)
+        };
+        if self.preview_size != size {
+            self.preview_size = size;
+            self.image_cache.clear();
+            self.title_cache.clear();
+            self.color_cache.clear();
+            self.last_clip_frames.clear();
+            self.last_rendered_frame = None;
+            self.reload_sources();
+        }
+        Ok(size)
+    }
+
+    /// 프리뷰 프레임 크기
+    pub fn preview_size(&self) -> (u32, u32) {
+        self.preview_size
+    }
+
+    /// 디코딩 소스(원본/프록시) 전환: 디코더/프레임 캐시/미리 읽기 워커 초기화
+    fn reload_sources(&mut self) {
+        self.proxy_generation = proxy::generation();
+        self.decoder_cache.clear();
+        self.sequential_cursors.clear();
+        self.frame_cache.clear();
+        self.prefetcher = None;
+        self.update_prefetcher();
+    }
+
+    /// (HW 디코딩 설정, 실제 HW로 디코딩 중인 디코더 수)
+    pub fn hw_decode_status(&self) -> (bool, u32) {
+        let active = self.decoder_cache.values().filter(|d| d.is_hardware()).count() as u32;
+        (self.hw_decode, active)
+    }
+
+    /// 전체 이펙트/LUT 바이패스 (파라미터는 유지, 해제 시 그대로 복원)
+    pub fn set_effects_bypass(&mut self, bypass: bool) {
+        if self.effects_bypassed != bypass {
+            self.effects_bypassed = bypass;
+            // 캐시된 프레임은 이펙트 적용 상태로 저장되어 있으므로 무효화
+            self.frame_cache.clear();
+        }
+    }
+
+    /// 특정 클립 이펙트/LUT 바이패스
+    pub fn set_clip_effects_bypass(&mut self, clip_id: u64, bypass: bool) {
+        let changed = if bypass {
+            self.bypassed_clips.insert(clip_id)
+        } else {
+            self.bypassed_clips.remove(&clip_id)
+        };
+        if changed {
+            self.invalidate_clip(clip_id);
+        }
+    }
+
+    /// 클립 이펙트/LUT 바이패스 여부 (전체 바이패스 포함)
+    pub fn is_effects_bypassed(&self, clip_id: u64) -> bool {
+        self.effects_bypassed || self.bypassed_clips.contains(&clip_id)
+    }
+
+    /// 프리뷰 자막 목록 교체 (None이면 자막 끔)
+    /// 오버레이 좌표는 렌더링 프레임 크기 기준 (preview_size — 해상도를 바꾸면 다시 래스터화해서 교체)
+    /// 자막은 캐시 이후 단계에서 블렌딩되므로 프레임 캐시는 유지
+    pub fn set_subtitles(&mut self, subtitles: Option<SubtitleOverlayList>) {
+        self.subtitles = subtitles;
+    }
+
+    /// 캐시 클리어 (클립 편집 시 호출)
+    pub fn clear_cache(&mut self) {
+        self.frame_cache.clear();
+    }
+
+    /// 클립이 캐시에 남기는 (파일, 소스 시간 범위) — 트랜지션 핸들/루프 포함
+    fn clip_cache_range(&self, clip_id: u64) -> Option<(String, i64, i64)> {
+        let timeline = self.timeline.lock().ok()?;
+        timeline.video_tracks.iter().find_map(|track| {
+            let clip = track.get_clip_by_id(clip_id)?;
+            let handle_ms = track.transitions.iter()
+                .filter(|t| t.from_clip_id == clip_id || t.to_clip_id == clip_id)
+                .map(|t| t.duration_ms)
+                .max()
+                .unwrap_or(0);
+            // Loop는 소스 끝에서 트림 시작(또는 파일 처음)으로 되감김
+            let start_ms = match clip.end_behavior {
+                EndOfMediaBehavior::Loop => 0,
+                _ => clip.source_time_with_handles(clip.start_time_ms - handle_ms),
+            };
+            let end_ms = clip.source_time_with_handles(clip.end_time_ms() + handle_ms);
+            Some((clip.source_key(), start_ms, end_ms))
+        })
+    }
+
+    /// 클립 하나의 캐시 무효화 (이펙트/LUT/바이패스 변경 시)
+    /// 캐시 키는 (소스 키, 소스 시간)이므로 이동/트림/분할은 무효화 불필요
+    pub fn invalidate_clip(&mut self, clip_id: u64) {
+        match self.clip_cache_range(clip_id) {
+            Some((file_path, start_ms, end_ms)) => self.frame_cache.invalidate_range(&file_path, start_ms, end_ms),
+            // 타임라인에 없는 클립 (lock 실패 포함) → 안전하게 전체 클리어
+            None => self.frame_cache.clear(),
+        }
+    }
+
+    /// 트랙 on/off 변경 시 무효화 — 트랙 클립의 직전 프레임/타이틀/컬러 레이어와 마지막 합성 결과만 버림
+    /// 프레임 캐시는 (소스 키, 소스 시간) 기준이라 트랙 상태와 무관 → 유지 (다시 켜도 디코딩 없이 표시)
+    fn invalidate_clips(&mut self, clip_ids: &HashSet<u64>) {
+        self.last_clip_frames.retain(|id, _| !clip_ids.contains(id));
+        self.title_cache.retain(|id, _| !clip_ids.contains(id));
+        self.color_cache.retain(|id, _| id.is_none_or(|id| !clip_ids.contains(&id)));
+        // 디코딩 실패 시 대체 프레임으로 쓰이므로 꺼진 트랙이 다시 보이지 않도록 버림
+        self.last_rendered_frame = None;
+    }
+
+    /// 파일 하나의 캐시 무효화 (미디어 교체/재연결 시)
+    pub fn invalidate_file(&mut self, file_path: &str) {
+        self.frame_cache.invalidate_file(file_path);
+    }
+
+    /// 파일의 소스 시간 범위 캐시 무효화
+    pub fn invalidate_range(&mut self, file_path: &str, start_ms: i64, end_ms: i64) {
+        self.frame_cache.invalidate_range(file_path, start_ms, end_ms);
+    }
+
+    /// 캐시 통계 조회
+    pub fn cache_stats(&self) -> (u32, usize) {
+        self.frame_cache.stats()
+    }
+
+    /// 프레임 캐시 적중률 (0.0~1.0, 조회 없으면 0)
+    pub fn cache_hit_rate(&self) -> f64 {
+        let lookups = self.frame_cache.hit_count + self.frame_cache.miss_count;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.frame_cache.hit_count as f64 / lookups as f64
+    }
+
+    /// 디코딩 스킵/실패로 이전 프레임을 재사용한 횟수 (Export 드롭 프레임 통계)
+    pub fn dropped_frame_count(&self) -> u64 {
+        self.diag_skipped + self.diag_error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_renderer_create() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let _renderer = Renderer::new(timeline);
+    }
+
+    #[test]
+    fn test_frame_cache_lru() {
+        let mut cache = FrameCache::new(3, 100 * 1024 * 1024);
+
+        // 3개 프레임 추가
+        for i in 0..3 {
+            cache.put("test.mp4".to_string(), i * 33, RenderedFrame {
+                width: 960, height: 540, data: Arc::new(vec![0u8; 100]), is_yuv: false, timestamp_ms: i * 33,
+            });
+        }
+        assert_eq!(cache.len(), 3);
+
+        // 4번째 추가 → LRU eviction (가장 오래된 0ms 제거)
+        cache.put("test.mp4".to_string(), 99, RenderedFrame {
+            width: 960, height: 540, data: Arc::new(vec![0u8; 100]), is_yuv: false, timestamp_ms: 99,
+        });
+        assert_eq!(cache.len(), 3);
+        // 0ms는 evict됨
+        assert!(cache.get("test.mp4", 0).is_none());
+        // 33ms, 66ms, 99ms는 존재
+        assert!(cache.get("test.mp4", 33).is_some());
+        assert!(cache.get("test.mp4", 66).is_some());
+        assert!(cache.get("test.mp4", 99).is_some());
+    }
+
+    #[test]
+    fn test_frame_cache_recency_and_bytes() {
+        let frame = |bytes: usize| RenderedFrame {
+            width: 1, height: 1, data: Arc::new(vec![0u8; bytes]), is_yuv: false, timestamp_ms: 0,
+        };
+        let mut cache = FrameCache::new(3, 300);
+        cache.put("a.mp4".to_string(), 0, frame(100));
+        cache.put("b.mp4".to_string(), 0, frame(100));
+        cache.put("a.mp4".to_string(), 33, frame(100));
+
+        // 조회한 항목은 최근으로 이동 → 다음 evict 대상은 b.mp4
+        assert!(cache.get("a.mp4", 0).is_some());
+        cache.put("c.mp4".to_string(), 0, frame(100));
+        assert!(!cache.contains("b.mp4", 0));
+        assert!(cache.contains("a.mp4", 0) && cache.contains("a.mp4", 33));
+
+        // 같은 키 갱신은 교체 (바이트 재계산)
+        cache.put("c.mp4".to_string(), 0, frame(50));
+        assert_eq!(cache.stats(), (3, 250));
+
+        // 바이트 한도: 250 + 200 > 300 → 오래된 두 항목 evict
+        cache.put("d.mp4".to_string(), 0, frame(200));
+        assert_eq!(cache.stats(), (2, 250));
+        assert!(cache.contains("c.mp4", 0) && cache.contains("d.mp4", 0));
+
+        // 빈 슬롯 재사용 후에도 순서 유지
+        cache.put("e.mp4".to_string(), 0, frame(10));
+        cache.put("f.mp4".to_string(), 0, frame(10));
+        assert!(!cache.contains("c.mp4", 0));
+        assert_eq!(cache.len(), 3);
+
+        cache.clear();
+        assert_eq!(cache.stats(), (0, 0));
+        assert!(cache.get("d.mp4", 0).is_none());
+    }
+
+    #[test]
+    fn test_frame_cache_invalidate() {
+        let frame = || RenderedFrame {
+            width: 1, height: 1, data: Arc::new(vec![0u8; 4]), is_yuv: false, timestamp_ms: 0,
+        };
+        let mut cache = FrameCache::new(10, 1000);
+        for t in [0, 33, 66, 100] {
+            cache.put("a.mp4".to_string(), t, frame());
+        }
+        cache.put("b.mp4".to_string(), 33, frame());
+
+        // 범위 양 끝 포함
+        cache.invalidate_range("a.mp4", 33, 66);
+        assert!(cache.contains("a.mp4", 0) && cache.contains("a.mp4", 100));
+        assert!(!cache.contains("a.mp4", 33) && !cache.contains("a.mp4", 66));
+        assert!(cache.contains("b.mp4", 33));
+        assert_eq!(cache.stats(), (3, 12));
+
+        cache.invalidate_file("a.mp4");
+        cache.invalidate_file("missing.mp4");
+        assert_eq!(cache.stats(), (1, 4));
+        assert!(cache.contains("b.mp4", 33));
+
+        // 제거된 슬롯 재사용 후에도 LRU 연결 유지
+        cache.put("c.mp4".to_string(), 0, frame());
+        assert!(cache.get("b.mp4", 33).is_some());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_frame_cache_hit_miss() {
         let mut cache = FrameCache::new(10, 100 * 1024 * 1024);
 
         cache.put("test.mp4".to_string(), 0, RenderedFrame {
-            width: 960, height: 540, data: vec![0u8; 100], is_yuv: false, timestamp_ms: 0,
+            width: 960, height: 540, data: Arc::new(vec![0u8; 100]), is_yuv: false, timestamp_ms: 0,
         });
 
-        // 히트
-        assert!(cache.get("test.mp4", 0).is_some());
-        assert_eq!(cache.hit_count, 1);
+        // 히트 (버퍼 복사 없이 공유)
+        let hit = cache.get("test.mp4", 0).cloned().unwrap();
+        assert!(Arc::ptr_eq(&hit.data, &cache.get("test.mp4", 0).unwrap().data));
+        assert_eq!(cache.hit_count, 2);
         assert_eq!(cache.miss_count, 0);
 
         // 미스
         assert!(cache.get("test.mp4", 100).is_none());
-        assert_eq!(cache.hit_count, 1);
+        assert_eq!(cache.hit_count, 2);
         assert_eq!(cache.miss_count, 1);
     }
 
+    #[test]
+    fn test_decoder_keys() {
+        // 같은 파일이라도 클립마다 다른 디코더 키
+        let a = decoder_key(&VideoClip::new(1, PathBuf::from("cut.mp4"), 0, 1000));
+        let b = decoder_key(&VideoClip::new(2, PathBuf::from("cut.mp4"), 1000, 1000));
+        assert_ne!(a, b);
+        assert!(is_same_file_key(&a, "cut.mp4") && is_same_file_key(&b, "cut.mp4"));
+        // 경로가 다른 파일의 접두사인 경우는 제외
+        assert!(!is_same_file_key(&decoder_key(&VideoClip::new(3, PathBuf::from("cut.mp4#1.mp4"), 0, 1000)), "cut.mp4"));
+        // 다른 비디오 스트림을 고른 클립은 넘겨받지 않음
+        let mut angle = VideoClip::new(4, PathBuf::from("cut.mp4"), 2000, 1000);
+        angle.stream_index = Some(1);
+        assert!(!is_same_file_key(&decoder_key(&angle), "cut.mp4"));
+        assert!(is_same_file_key(&decoder_key(&angle), &angle.source_key()));
+    }
+
     #[test]
     fn test_black_frame() {
-        let frame = black_frame(1000);
+        let frame = solid_frame_rgba(960, 540, [0, 0, 0], 1000);
         assert_eq!(frame.width, 960);
         assert_eq!(frame.height, 540);
         assert_eq!(frame.data.len(), (960 * 540 * 4) as usize);
         assert!(frame.data.iter().all(|&b| b == 0));
     }
 
+    #[test]
+    fn test_background_color_frames() {
+        let frame = solid_frame_rgba(4, 2, [255, 0, 0], 0);
+        assert_eq!(&frame.data[0..4], &[255, 0, 0, 255]);
+        assert_eq!(frame.data.len(), 4 * 2 * 4);
+
+        // 검정 YUV (limited range): Y=16, U=V=128
+        let black = solid_frame_yuv(4, 2, [0, 0, 0], 0);
+        assert!(black.is_yuv);
+        assert!(black.data[..8].iter().all(|&b| b == 16));
+        assert!(black.data[8..].iter().all(|&b| b == 128));
+
+        // 흰색 YUV: Y=235, U=V=128
+        let white = solid_frame_yuv(4, 2, [255, 255, 255], 0);
+        assert!(white.data[..8].iter().all(|&b| b == 235));
+        assert!(white.data[8..].iter().all(|&b| b == 128));
+    }
+
+    #[test]
+    fn test_effects_bypass() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let clip_id = {
+            let mut t = timeline.lock().unwrap();
+            let track_id = t.add_video_track();
+            t.add_video_clip(track_id, PathBuf::from("test.mp4"), 0, 1000).unwrap()
+        };
+        let mut renderer = Renderer::new(timeline.clone());
+        assert!(renderer.set_clip_effects(clip_id, EffectParams { brightness: 0.5, ..EffectParams::default() }));
+        assert!(!renderer.set_clip_effects(999, EffectParams { brightness: 0.5, ..EffectParams::default() }));
+        let clip = timeline.lock().unwrap().video_tracks[0].get_clip_by_id(clip_id).unwrap().clone();
+
+        let frame = || Frame {
+            width: 1, height: 1, format: crate::ffmpeg::PixelFormat::RGBA,
+            data: vec![100, 100, 100, 255], timestamp_ms: 0, color: YuvColor::default(), duration_ms: 0,
+        };
+
+        let graded = renderer.frame_to_rendered(&clip, frame(), 0);
+        assert_ne!(*graded.data, frame().data);
+        // 타임라인에 저장 → 같은 타임라인의 새 Renderer(Export)도 같은 결과
+        let mut export_renderer = Renderer::new(timeline.clone());
+        assert_eq!(export_renderer.frame_to_rendered(&clip, frame(), 0).data, graded.data);
+
+        // 클립 바이패스 → 원본, 파라미터는 유지
+        renderer.set_clip_effects_bypass(clip_id, true);
+        assert_eq!(*renderer.frame_to_rendered(&clip, frame(), 0).data, frame().data);
+        assert!(renderer.clip_effects().contains_key(&clip_id));
+
+        renderer.set_clip_effects_bypass(clip_id, false);
+        assert_eq!(renderer.frame_to_rendered(&clip, frame(), 0).data, graded.data);
+
+        // 전체 바이패스
+        renderer.set_effects_bypass(true);
+        assert!(renderer.is_effects_bypassed(clip_id));
+        assert_eq!(*renderer.frame_to_rendered(&clip, frame(), 0).data, frame().data);
+    }
+
+    #[test]
+    fn test_master_grade_applies_to_output() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        timeline.lock().unwrap().set_background(ColorClip::solid([100, 100, 100, 255]));
+        let mut renderer = Renderer::new(timeline.clone());
+        let original = renderer.render_frame(0).unwrap();
+        assert_eq!(&original.data[..4], &[100, 100, 100, 255]);
+
+        // 클립이 없는 구간(배경)도 마스터 그레이딩 대상
+        timeline.lock().unwrap().master_effects.brightness = 0.5;
+        let graded = renderer.render_frame(0).unwrap();
+        assert!(graded.data[0] > 100);
+
+        // Export 렌더러(YUV)에도 적용
+        let mut export_renderer = Renderer::new_for_export(timeline.clone(), 2, 2);
+        let export_frame = export_renderer.render_frame(0).unwrap();
+        assert!(export_frame.is_yuv);
+        assert!(export_frame.data[0] > solid_frame_yuv(2, 2, [100, 100, 100], 0).data[0]);
+
+        // 전체 바이패스 → 원본
+        renderer.set_effects_bypass(true);
+        assert_eq!(renderer.render_frame(0).unwrap().data, original.data);
+    }
+
+    #[test]
+    fn test_effects_applied_to_yuv_frames() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let clip_id = {
+            let mut t = timeline.lock().unwrap();
+            let track_id = t.add_video_track();
+            t.add_video_clip(track_id, PathBuf::from("test.mp4"), 0, 1000).unwrap()
+        };
+        let mut renderer = Renderer::new_for_export(timeline.clone(), 2, 2);
+        assert!(renderer.set_clip_effects(clip_id, EffectParams { brightness: 0.5, ..EffectParams::default() }));
+        let clip = timeline.lock().unwrap().video_tracks[0].get_clip_by_id(clip_id).unwrap().clone();
+
+        // Export 경로 YUV420P 프레임 (2x2 회색)
+        let frame = || Frame {
+            width: 2, height: 2, format: crate::ffmpeg::PixelFormat::YUV420P,
+            data: vec![100, 100, 100, 100, 128, 128], timestamp_ms: 0, color: YuvColor::BT709, duration_ms: 0,
+        };
+        let graded = renderer.frame_to_rendered(&clip, frame(), 0);
+        assert!(graded.is_yuv);
+        assert_eq!(graded.data.len(), 6);
+        assert!(graded.data[..4].iter().all(|&y| y > 100));
+
+        renderer.set_clip_effects_bypass(clip_id, true);
+        assert_eq!(*renderer.frame_to_rendered(&clip, frame(), 0).data, frame().data);
+    }
+
+    #[test]
+    fn test_clip_effects_invalidate_only_clip_range() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let (clip_a, clip_b) = {
+            let mut t = timeline.lock().unwrap();
+            let track_id = t.add_video_track();
+            let clip_a = t.add_video_clip(track_id, "a.mp4".into(), 0, 1000).unwrap();
+            let clip_b = t.add_video_clip(track_id, "b.mp4".into(), 1000, 1000).unwrap();
+            // a.mp4 소스 구간 = 2000~3000
+            let clip = t.video_tracks[0].get_clip_by_id_mut(clip_a).unwrap();
+            clip.trim_start_ms = 2000;
+            clip.trim_end_ms = 3000;
+            (clip_a, clip_b)
+        };
+        let mut renderer = Renderer::new(timeline);
+        let frame = || RenderedFrame {
+            width: 1, height: 1, data: Arc::new(vec![0u8; 4]), is_yuv: false, timestamp_ms: 0,
+        };
+        renderer.frame_cache.put("a.mp4".to_string(), 100, frame());
+        renderer.frame_cache.put("a.mp4".to_string(), 2500, frame());
+        renderer.frame_cache.put("b.mp4".to_string(), 500, frame());
+
+        // 클립 a 이펙트 변경 → a.mp4의 클립 구간만 무효화
+        let params = EffectParams { brightness: 0.2, ..EffectParams::default() };
+        renderer.set_clip_effects(clip_a, params);
+        assert!(!renderer.frame_cache.contains("a.mp4", 2500));
+        assert!(renderer.frame_cache.contains("a.mp4", 100));
+        assert!(renderer.frame_cache.contains("b.mp4", 500));
+
+        renderer.set_clip_effects_bypass(clip_b, true);
+        assert!(!renderer.frame_cache.contains("b.mp4", 500));
+        assert!(renderer.frame_cache.contains("a.mp4", 100));
+
+        // 타임라인에 없는 클립 → 전체 클리어
+        renderer.clear_clip_effects(999);
+        assert_eq!(renderer.cache_stats(), (0, 0));
+    }
+
+    #[test]
+    fn test_prefetch_follows_playback_mode() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let mut renderer = Renderer::new(timeline.clone());
+        assert!(renderer.prefetcher.is_none());
+
+        renderer.set_playback_mode(true);
+        assert_eq!(renderer.prefetcher.as_ref().map(|p| p.frames()), Some(DEFAULT_PREFETCH_FRAMES));
+        renderer.set_prefetch_frames(3);
+        assert_eq!(renderer.prefetcher.as_ref().map(|p| p.frames()), Some(3));
+        renderer.set_prefetch_frames(0);
+        assert!(renderer.prefetcher.is_none());
+
+        // 역방향 재생은 정방향 미리 읽기 없음
+        renderer.set_prefetch_frames(3);
+        renderer.set_reverse_playback(true);
+        assert!(renderer.prefetcher.is_none());
+        renderer.set_reverse_playback(false);
+        assert!(renderer.prefetcher.is_some());
+
+        renderer.set_playback_mode(false);
+        assert!(renderer.prefetcher.is_none());
+
+        // Export 렌더러는 미리 읽기 없음
+        let mut export = Renderer::new_for_export(timeline, 1920, 1080);
+        export.set_playback_mode(true);
+        assert!(export.prefetcher.is_none());
+    }
+
+    #[test]
+    fn test_step_frame_lands_on_each_frame_once() {
+        // 29.97fps: ms 반올림 스텝이면 반복/누락되는 격자에서도 한 번에 정확히 한 프레임
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30000.0 / 1001.0)));
+        {
+            let mut t = timeline.lock().unwrap();
+            let track = t.add_video_track();
+            t.add_video_clip(track, PathBuf::from("missing.mp4"), 0, 1000);
+        }
+        let mut renderer = Renderer::new(timeline.clone());
+        let frame_count = timeline.lock().unwrap().frame_count();
+        assert_eq!(frame_count, 30);
+
+        let mut timestamps = Vec::new();
+        for _ in 0..frame_count + 2 {
+            timestamps.push(renderer.step_frame(1).unwrap().timestamp_ms);
+        }
+        // 0에서 시작해 1..29, 끝에서는 마지막 프레임 유지
+        assert_eq!(renderer.current_frame(), 29);
+        let t = timeline.lock().unwrap();
+        let expected: Vec<i64> = (1..30).chain([29, 29, 29]).map(|i| t.frame_time_ms(i)).collect();
+        drop(t);
+        assert_eq!(timestamps, expected);
+
+        // 임의 시간 렌더링 후 스텝은 그 시간이 속한 프레임 기준
+        renderer.render_frame(500).unwrap();
+        assert_eq!(renderer.current_frame(), 15);
+        assert_eq!(renderer.step_frame(-1).unwrap().timestamp_ms, 467);
+        renderer.render_frame_index(0).unwrap();
+        assert_eq!(renderer.step_frame(-1).unwrap().timestamp_ms, 0);
+    }
+
+    #[test]
+    fn test_gpu_compositing_preview_only() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let mut export = Renderer::new_for_export(timeline, 1920, 1080);
+        assert!(export.set_gpu_compositing(true).is_err());
+        assert!(export.set_gpu_compositing(false).is_ok());
+        assert!(!export.gpu_compositing());
+    }
+
+    #[test]
+    fn test_preview_subtitles() {
+        use crate::subtitle::overlay::SubtitleOverlay;
+
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let mut renderer = Renderer::new(timeline);
+        let mut list = SubtitleOverlayList::new();
+        list.overlays.push(SubtitleOverlay {
+            start_ms: 0, end_ms: 1000, x: 1, y: 0, width: 1, height: 1, rgba_data: vec![255, 255, 255, 255], z_index: 0,
+        });
+        renderer.set_subtitles(Some(list));
+
+        // 빈 타임라인 → 검은 배경 위에 자막 픽셀
+        let frame = renderer.render_frame(500).unwrap();
+        assert_eq!(&frame.data[4..8], &[255, 255, 255, 255]);
+        assert_eq!(&frame.data[..4], &[0, 0, 0, 0]);
+
+        // 자막 구간 밖
+        let frame = renderer.render_frame(1000).unwrap();
+        assert_eq!(&frame.data[4..8], &[0, 0, 0, 0]);
+
+        renderer.set_subtitles(None);
+        let frame = renderer.render_frame(500).unwrap();
+        assert_eq!(&frame.data[4..8], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_preview_resolution() {
+        assert_eq!(PreviewResolution::Quarter.size(3840, 2160), (960, 540));
+        assert_eq!(PreviewResolution::Half.size(1280, 720), (640, 360));
+        // 홀수 → 짝수 내림
+        assert_eq!(PreviewResolution::Quarter.size(1366, 768), (340, 192));
+        assert_eq!(PreviewResolution::Standard.size(3840, 2160), DEFAULT_PREVIEW_SIZE);
+
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let mut renderer = Renderer::new(timeline.clone());
+        assert_eq!(renderer.render_frame(0).unwrap().width, 960);
+
+        assert_eq!(renderer.set_preview_resolution(PreviewResolution::Full), Ok((1920, 1080)));
+        let frame = renderer.render_frame(0).unwrap();
+        assert_eq!((frame.width, frame.height), (1920, 1080));
+        assert_eq!(frame.data.len(), 1920 * 1080 * 4);
+
+        assert_eq!(renderer.set_preview_resolution(PreviewResolution::Quarter), Ok((480, 270)));
+        assert_eq!(renderer.render_frame(0).unwrap().width, 480);
+
+        // Export 렌더러는 출력 해상도 고정
+        let mut export = Renderer::new_for_export(timeline, 1280, 720);
+        assert_eq!(export.set_preview_resolution(PreviewResolution::Full), Ok((1280, 720)));
+    }
+
+    #[test]
+    fn test_render_still_uses_project_resolution() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(64, 36, 30.0)));
+        let frame = Renderer::render_still(timeline, 0).unwrap();
+        assert_eq!((frame.width, frame.height), (64, 36));
+        assert!(!frame.is_yuv);
+        assert_eq!(frame.data.len(), 64 * 36 * 4);
+
+        let empty = Arc::new(Mutex::new(Timeline::new(0, 0, 30.0)));
+        assert!(Renderer::render_still(empty, 0).is_err());
+    }
+
+    #[test]
+    fn test_alpha_layer_composites_over_background() {
+        // 알파 소스 프레임 (위 행 불투명 흰색, 아래 행 투명) → Export 배경 위에 합성
+        let timeline = Arc::new(Mutex::new(Timeline::new(2, 2, 30.0)));
+        let mut renderer = Renderer::new_for_export(timeline, 2, 2);
+        let clip = VideoClip::new(1, PathBuf::from("overlay.mov"), 0, 1000);
+        let frame = Frame {
+            width: 2, height: 2, format: crate::ffmpeg::PixelFormat::RGBA,
+            data: [[255, 255, 255, 255], [255, 255, 255, 255], [255, 255, 255, 0], [255, 255, 255, 0]].concat(),
+            timestamp_ms: 0, color: YuvColor::default(), duration_ms: 0,
+        };
+        let layer = renderer.frame_to_rendered(&clip, frame, 0);
+        assert!(!covers_canvas(&layer.data, layer.is_yuv, &ClipTransform::default()));
+
+        let composed = renderer.composite_layers(vec![(layer, ClipTransform::default(), None)], [0, 0, 0], 0).unwrap();
+        assert!(composed.is_yuv);
+        assert_eq!(&composed.data[..4], &[235, 235, 16, 16]);
+    }
+
+    #[test]
+    fn test_fit_mode_pillarbox_in_export() {
+        // 4:3 소스 → 16:8 Export 캔버스: Fit은 양옆 투명(배경), Stretch는 전체
+        let timeline = Arc::new(Mutex::new(Timeline::new(16, 8, 30.0)));
+        let mut renderer = Renderer::new_for_export(timeline, 16, 8);
+        let mut clip = VideoClip::new(1, PathBuf::from("4x3.mp4"), 0, 1000);
+        renderer.source_sizes.insert(clip.source_key(), (640, 480));
+        let layer = solid_frame_yuv(16, 8, [255, 255, 255], 0);
+
+        let fitted = match renderer.fit_clip_layer(&clip, LayerResult::Frame(layer.clone())) {
+            LayerResult::Frame(frame) => frame,
+            _ => panic!("expected frame"),
+        };
+        assert!(!fitted.is_yuv);
+        assert_eq!((fitted.width, fitted.height), (16, 8));
+        // 폭 10.67 → x 2.67 ~ 13.33
+        assert_eq!(&fitted.data[..4], &[0, 0, 0, 0]);
+        assert_eq!(fitted.data[(4 * 16 + 8) * 4 + 3], 255);
+        let composed = renderer.composite_layers(vec![(fitted, ClipTransform::default(), None)], [0, 0, 0], 0).unwrap();
+        assert!(composed.is_yuv);
+        assert_eq!((composed.data[0], composed.data[8]), (16, 235));
+
+        // Fill: 꽉 채우므로 YUV 경로 유지
+        clip.fit_mode = FitMode::Fill;
+        match renderer.fit_clip_layer(&clip, LayerResult::Frame(layer.clone())) {
+            LayerResult::Frame(frame) => assert!(frame.is_yuv),
+            _ => panic!("expected frame"),
+        }
+
+        clip.fit_mode = FitMode::Stretch;
+        match renderer.fit_clip_layer(&clip, LayerResult::Frame(layer.clone())) {
+            LayerResult::Frame(frame) => assert_eq!(frame.data, layer.data),
+            _ => panic!("expected frame"),
+        }
+    }
+
+    #[test]
+    fn test_copy_bitmap_rgba_clips_to_frame() {
+        let mut frame = transparent_frame(3, 2, 0);
+        let bitmap: Vec<u8> = (1..=4).flat_map(|v| [v, v, v, 128]).collect(); // 2x2
+        copy_bitmap_rgba(&mut frame, &bitmap, 2, 1, 2, 2);
+
+        // (2,1)에 좌상단 픽셀만 들어감, 알파 유지
+        assert_eq!(&frame.data[(3 + 2) * 4..(3 + 2) * 4 + 4], &[1, 1, 1, 128]);
+        assert_eq!(frame.data.iter().filter(|&&b| b != 0).count(), 4);
+    }
+
+    #[test]
+    fn test_title_clip_layer() {
+        // 시스템 폰트가 없는 환경은 건너뜀
+        if text::shared_renderer(None).is_err() {
+            return;
+        }
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        {
+            let mut t = timeline.lock().unwrap();
+            let track_id = t.add_video_track();
+            t.add_title_clip(track_id, TitleClip::new("Title"), 0, 1000).unwrap();
+        }
+        let mut renderer = Renderer::new(timeline);
+
+        let frame = renderer.render_frame(500).unwrap();
+        assert_eq!((frame.width, frame.height), (960, 540));
+        // 하단 중앙에 흰 글자, 상단은 배경
+        assert!(frame.data.chunks_exact(4).any(|p| p == [255, 255, 255, 255]));
+        assert!(frame.data[..960 * 100 * 4].iter().all(|&b| b == 0));
+        assert_eq!(renderer.title_cache.len(), 1);
+    }
+
+    #[test]
+    fn test_invalidate_track() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let (bottom, top) = {
+            let mut t = timeline.lock().unwrap();
+            let bottom = t.add_video_track();
+            let top = t.add_video_track();
+            t.add_color_clip(bottom, ColorClip::solid([255, 0, 0, 255]), 0, 1000).unwrap();
+            t.add_color_clip(top, ColorClip::solid([0, 0, 255, 255]), 0, 1000).unwrap();
+            (bottom, top)
+        };
+        let mut renderer = Renderer::new(timeline.clone());
+        assert_eq!(&renderer.render_frame(0).unwrap().data[..4], &[0, 0, 255, 255]);
+        renderer.frame_cache.put("a.mp4".to_string(), 0, solid_frame_rgba(2, 2, [0, 0, 0], 0));
+
+        // 위 트랙 끄기 → 다음 렌더링에서 해당 트랙 레이어만 버리고 프레임 캐시는 유지
+        assert!(timeline.lock().unwrap().set_track_enabled(top, false));
+        assert_eq!(&renderer.render_frame(0).unwrap().data[..4], &[255, 0, 0, 255]);
+        assert_eq!(renderer.color_cache.len(), 1);
+        assert!(renderer.frame_cache.contains("a.mp4", 0));
+
+        // 다시 켜기 / 빈 구간에서 끈 트랙도 무효화
+        assert!(timeline.lock().unwrap().set_track_enabled(top, true));
+        assert_eq!(&renderer.render_frame(0).unwrap().data[..4], &[0, 0, 255, 255]);
+        assert!(timeline.lock().unwrap().set_track_enabled(bottom, false));
+        renderer.render_frame(5000).unwrap();
+        assert_eq!(renderer.color_cache.len(), 1);
+        assert!(renderer.last_rendered_frame.is_none());
+    }
+
+    #[test]
+    fn test_color_clip_and_background_fill() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        {
+            let mut t = timeline.lock().unwrap();
+            let track_id = t.add_video_track();
+            t.add_color_clip(track_id, ColorClip::solid([0, 0, 255, 255]), 0, 1000).unwrap();
+        }
+        let mut renderer = Renderer::new(timeline.clone());
+
+        let frame = renderer.render_frame(500).unwrap();
+        assert!(frame.data.chunks_exact(4).all(|p| p == [0, 0, 255, 255]));
+        assert_eq!(renderer.color_cache.len(), 1);
+
+        // 빈 구간: 배경 채우기 (알파 무시)
+        timeline.lock().unwrap().set_background(ColorClip::gradient([0, 0, 0, 0], [255, 255, 255, 0], 0.0));
+        let gap = renderer.render_frame(1500).unwrap();
+        assert_eq!((gap.width, gap.height), (960, 540));
+        assert!(gap.data[0] < 5 && gap.data[959 * 4] > 250);
+        assert!(gap.data.chunks_exact(4).all(|p| p[3] == 255));
+        assert!(renderer.color_cache.contains_key(&None));
+
+        // Export: 불투명 컬러 클립은 YUV 캔버스
+        let mut export_renderer = Renderer::new_for_export(timeline, 64, 36);
+        assert!(export_renderer.render_frame(500).unwrap().is_yuv);
+    }
+
+    #[test]
+    fn test_render_filmstrip_empty_timeline() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        timeline.lock().unwrap().set_background(ColorClip::solid([10, 20, 30, 255]));
+        let mut renderer = Renderer::new(timeline);
+
+        let strip = renderer.render_filmstrip(4, 16, 9).unwrap();
+        assert_eq!((strip.width, strip.height), (64, 9));
+        assert_eq!(strip.data.len(), 64 * 9 * 4);
+        // 클립 없음 → 모든 타일이 배경색
+        assert!(strip.data.chunks_exact(4).all(|p| p == [10, 20, 30, 255]));
+
+        assert!(renderer.render_filmstrip(0, 16, 9).is_err());
+    }
+
     #[test]
     fn test_renderer_with_real_video() {
         let video_path = PathBuf::from(r"C:\Users\USER\Videos\드론 대응 2.75인치 로켓 '비궁'으로 유도키트 개발, 사우디 기술협력 추진.mp4");
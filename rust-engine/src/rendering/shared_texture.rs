@@ -0,0 +1,167 @@
+// 공유 D3D11 텍스처 출력 (Windows, feature = "d3d11") - 렌더링 결과를 호스트(WPF/WinUI) 소유 텍스처에 직접 기록
+// C# 쪽 RGBA 바이트 복사 + 비트맵 업로드 생략: 호스트는 공유 핸들의 텍스처를 그대로 표시
+// 지원 포맷: B8G8R8A8_UNORM (WPF D3DImage/D2D), R8G8B8A8_UNORM
+// 키드 뮤텍스 텍스처는 AcquireSync(0) → 기록 → ReleaseSync(0) (호스트도 키 0 사용)
+
+use crate::rendering::RenderedFrame;
+use std::ffi::c_void;
+use std::sync::{Mutex, OnceLock};
+use windows::core::Interface;
+use windows::Win32::Foundation::{HANDLE, HMODULE, S_OK};
+use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_HARDWARE;
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11CreateDevice, ID3D11Device, ID3D11Device1, ID3D11DeviceContext, ID3D11Texture2D,
+    D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_RESOURCE_MISC_SHARED_KEYEDMUTEX, D3D11_SDK_VERSION,
+    D3D11_TEXTURE2D_DESC,
+};
+use windows::Win32::Graphics::Dxgi::{IDXGIAdapter, IDXGIKeyedMutex};
+use windows::Win32::Graphics::Dxgi::Common::{DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_FORMAT_R8G8B8A8_UNORM};
+
+/// 열어둔 공유 텍스처 수 (호스트가 리사이즈 시 텍스처를 새로 만들면 오래된 핸들부터 닫음)
+const MAX_OPENED_TEXTURES: usize = 4;
+
+/// 키드 뮤텍스 대기 시간 (호스트가 표시 중이면 이번 프레임은 건너뜀)
+const KEYED_MUTEX_TIMEOUT_MS: u32 = 100;
+
+/// 열린 공유 텍스처 (핸들 → D3D11 텍스처)
+struct OpenedTexture {
+    handle: usize,
+    texture: ID3D11Texture2D,
+    keyed_mutex: Option<IDXGIKeyedMutex>,
+    width: u32,
+    height: u32,
+    bgra: bool,
+}
+
+/// 공유 텍스처 기록기 (프로세스당 D3D11 디바이스 1개, Mutex로 immediate context 보호)
+pub struct SharedTextureWriter {
+    device: ID3D11Device,
+    context: ID3D11DeviceContext,
+    /// 최근 사용 순 (마지막 = 가장 최근)
+    opened: Vec<OpenedTexture>,
+    /// BGRA 변환 버퍼 (프레임 간 재사용)
+    staging: Vec<u8>,
+}
+
+impl SharedTextureWriter {
+    /// 전역 기록기 (첫 호출 시 디바이스 생성, 실패 결과도 유지)
+    pub fn global() -> Result<&'static Mutex<SharedTextureWriter>, String> {
+        static GLOBAL: OnceLock<Result<Mutex<SharedTextureWriter>, String>> = OnceLock::new();
+        GLOBAL.get_or_init(|| Self::new().map(Mutex::new)).as_ref().map_err(|e| e.clone())
+    }
+
+    fn new() -> Result<Self, String> {
+        let mut device = None;
+        let mut context = None;
+        unsafe {
+            D3D11CreateDevice(
+                None::<&IDXGIAdapter>,
+                D3D_DRIVER_TYPE_HARDWARE,
+                HMODULE::default(),
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                None,
+                D3D11_SDK_VERSION,
+                Some(&mut device),
+                None,
+                Some(&mut context),
+            )
+        }
+        .map_err(|e| format!("D3D11CreateDevice failed: {}", e))?;
+
+        match (device, context) {
+            (Some(device), Some(context)) => Ok(Self { device, context, opened: Vec::new(), staging: Vec::new() }),
+            _ => Err("D3D11CreateDevice returned no device".to_string()),
+        }
+    }
+
+    /// RGBA 프레임을 공유 텍스처에 기록 (텍스처 크기 = 프레임 크기여야 함)
+    pub fn write(&mut self, shared_handle: *mut c_void, frame: &RenderedFrame) -> Result<(), String> {
+        if frame.is_yuv {
+            return Err("Shared texture output requires an RGBA frame".to_string());
+        }
+        let index = self.open(shared_handle)?;
+        let target = &self.opened[index];
+        if (target.width, target.height) != (frame.width, frame.height) {
+            return Err(format!(
+                "Shared texture is {}x{}, frame is {}x{}",
+                target.width, target.height, frame.width, frame.height
+            ));
+        }
+
+        // WPF/D2D 텍스처는 BGRA → 채널 교환
+        let data: &[u8] = if target.bgra {
+            self.staging.clear();
+            self.staging.extend(frame.data.chunks_exact(4).flat_map(|p| [p[2], p[1], p[0], p[3]]));
+            &self.staging
+        } else {
+            &frame.data
+        };
+
+        unsafe {
+            if let Some(mutex) = &target.keyed_mutex {
+                // 시간 초과(WAIT_TIMEOUT)도 성공 HRESULT로 반환되므로 S_OK만 획득으로 처리
+                let hr = (Interface::vtable(mutex).AcquireSync)(Interface::as_raw(mutex), 0, KEYED_MUTEX_TIMEOUT_MS);
+                if hr != S_OK {
+                    return Err(format!("Shared texture busy (0x{:08X})", hr.0));
+                }
+            }
+            self.context.UpdateSubresource(&target.texture, 0, None, data.as_ptr() as *const c_void, frame.width * 4, 0);
+            if let Some(mutex) = &target.keyed_mutex {
+                mutex.ReleaseSync(0).map_err(|e| format!("ReleaseSync failed: {}", e))?;
+            }
+            // 호스트 디바이스가 바로 읽을 수 있도록 제출
+            self.context.Flush();
+        }
+        Ok(())
+    }
+
+    /// 호스트가 텍스처를 해제하기 전에 호출 (열린 참조 정리)
+    pub fn forget(&mut self, shared_handle: *mut c_void) {
+        self.opened.retain(|t| t.handle != shared_handle as usize);
+    }
+
+    /// 공유 핸들 열기 (이미 열려 있으면 재사용, 반환: opened 인덱스)
+    fn open(&mut self, shared_handle: *mut c_void) -> Result<usize, String> {
+        let handle = shared_handle as usize;
+        if let Some(index) = self.opened.iter().position(|t| t.handle == handle) {
+            // 최근 사용으로 이동
+            let texture = self.opened.remove(index);
+            self.opened.push(texture);
+            return Ok(self.opened.len() - 1);
+        }
+
+        let texture = self.open_shared_texture(HANDLE(shared_handle))?;
+        let mut desc = D3D11_TEXTURE2D_DESC::default();
+        unsafe { texture.GetDesc(&mut desc) };
+        let bgra = match desc.Format {
+            DXGI_FORMAT_B8G8R8A8_UNORM => true,
+            DXGI_FORMAT_R8G8B8A8_UNORM => false,
+            other => return Err(format!("Unsupported shared texture format {:?}", other)),
+        };
+        let keyed_mutex = if desc.MiscFlags & D3D11_RESOURCE_MISC_SHARED_KEYEDMUTEX.0 as u32 != 0 {
+            Some(texture.cast::<IDXGIKeyedMutex>().map_err(|e| format!("Keyed mutex unavailable: {}", e))?)
+        } else {
+            None
+        };
+
+        if self.opened.len() >= MAX_OPENED_TEXTURES {
+            self.opened.remove(0);
+        }
+        self.opened.push(OpenedTexture { handle, texture, keyed_mutex, width: desc.Width, height: desc.Height, bgra });
+        Ok(self.opened.len() - 1)
+    }
+
+    /// 레거시 공유 핸들 (GetSharedHandle) → NT 핸들 (CreateSharedHandle) 순서로 시도
+    fn open_shared_texture(&self, handle: HANDLE) -> Result<ID3D11Texture2D, String> {
+        let mut texture: Option<ID3D11Texture2D> = None;
+        let legacy = unsafe { self.device.OpenSharedResource(handle, &mut texture) };
+        if let (Ok(()), Some(texture)) = (legacy, texture) {
+            return Ok(texture);
+        }
+
+        let device1: ID3D11Device1 = self.device.cast()
+            .map_err(|e| format!("Failed to open shared texture: {}", e))?;
+        unsafe { device1.OpenSharedResource1::<_, ID3D11Texture2D>(handle) }
+            .map_err(|e| format!("Failed to open shared texture: {}", e))
+    }
+}
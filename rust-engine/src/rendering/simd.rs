@@ -0,0 +1,334 @@
+// SIMD 픽셀 커널 - AVX2 (x86_64 런타임 감지), 그 외 환경은 0 반환 → 호출자가 스칼라로 처리
+// 행 단위로 8/16픽셀 묶음만 처리하고 처리한 개수를 반환 (나머지 픽셀은 호출자의 스칼라 루프)
+// 모든 커널은 스칼라 구현과 비트 단위로 같은 결과 (같은 정수 연산, 같은 순서의 f32 연산)
+
+use crate::ffmpeg::colorspace::YuvCoefficients;
+use crate::rendering::effects::EffectKernel;
+
+/// AVX2 사용 가능 여부 (감지 결과는 std가 캐시)
+pub fn has_avx2() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        std::arch::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+/// YUV420P 한 행 → RGBA (정수 계수, 처리한 픽셀 수 반환)
+/// u_row/v_row: 이 행의 크로마 (픽셀 2개당 1개)
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+pub fn yuv_row_to_rgba(y_row: &[u8], u_row: &[u8], v_row: &[u8], rgba_out: &mut [u8], c: &YuvCoefficients) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2() {
+        return unsafe { avx2::yuv_row_to_rgba(y_row, u_row, v_row, rgba_out, c) };
+    }
+    0
+}
+
+/// RGBA 한 행 → Y (처리한 픽셀 수 반환)
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+pub fn rgba_row_to_y(rgba_row: &[u8], y_out: &mut [u8], c: &YuvCoefficients) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2() {
+        return unsafe { avx2::rgba_row_to_y(rgba_row, y_out, c) };
+    }
+    0
+}
+
+/// RGBA 두 행 → U/V (2x2 평균, 처리한 크로마 샘플 수 반환)
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+pub fn rgba_rows_to_uv(row0: &[u8], row1: &[u8], u_out: &mut [u8], v_out: &mut [u8], c: &YuvCoefficients) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2() {
+        return unsafe { avx2::rgba_rows_to_uv(row0, row1, u_out, v_out, c) };
+    }
+    0
+}
+
+/// RGBA 한 행에 이펙트 적용 (y: 행 번호, 처리한 픽셀 수 반환)
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+pub(crate) fn apply_effects_row(row: &mut [u8], y: usize, kernel: &EffectKernel) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2() {
+        return unsafe { avx2::apply_effects_row(row, y, kernel) };
+    }
+    0
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use crate::ffmpeg::colorspace::YuvCoefficients;
+    use crate::rendering::effects::{EffectKernel, VIGNETTE_INNER_RADIUS};
+    use std::arch::x86_64::*;
+
+    /// RGBA 8픽셀 (u32 레인) → R, G, B (i32 레인)
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn unpack_rgb(px: __m256i) -> (__m256i, __m256i, __m256i) {
+        let mask = _mm256_set1_epi32(0xFF);
+        (
+            _mm256_and_si256(px, mask),
+            _mm256_and_si256(_mm256_srli_epi32(px, 8), mask),
+            _mm256_and_si256(_mm256_srli_epi32(px, 16), mask),
+        )
+    }
+
+    /// R, G, B (0~255 i32 레인) + 알파 → RGBA 8픽셀
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn pack_rgba(r: __m256i, g: __m256i, b: __m256i, alpha: __m256i) -> __m256i {
+        let rg = _mm256_or_si256(r, _mm256_slli_epi32(g, 8));
+        let rgb = _mm256_or_si256(rg, _mm256_slli_epi32(b, 16));
+        _mm256_or_si256(rgb, alpha)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn clamp_epi32(v: __m256i, min: i32, max: i32) -> __m256i {
+        _mm256_min_epi32(_mm256_max_epi32(v, _mm256_set1_epi32(min)), _mm256_set1_epi32(max))
+    }
+
+    /// i32 레인 8개의 하위 바이트 → dst 8바이트
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn store_low_bytes(v: __m256i, dst: *mut u8) {
+        let pick = _mm256_setr_epi8(
+            0, 4, 8, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            0, 4, 8, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+        );
+        let bytes = _mm256_shuffle_epi8(v, pick);
+        let joined = _mm256_permutevar8x32_epi32(bytes, _mm256_setr_epi32(0, 4, 0, 0, 0, 0, 0, 0));
+        _mm_storel_epi64(dst as *mut __m128i, _mm256_castsi256_si128(joined));
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn yuv_row_to_rgba(y_row: &[u8], u_row: &[u8], v_row: &[u8], out: &mut [u8], c: &YuvCoefficients) -> usize {
+        let len = y_row.len().min(u_row.len() * 2).min(v_row.len() * 2).min(out.len() / 4);
+        let n = len / 8 * 8;
+        let dup = _mm256_setr_epi32(0, 0, 1, 1, 2, 2, 3, 3);
+        let c128 = _mm256_set1_epi32(128);
+        let alpha = _mm256_set1_epi32(0xFF00_0000u32 as i32);
+        let coef = |v: i32| _mm256_set1_epi32(v);
+
+        let mut x = 0;
+        while x < n {
+            let y = _mm256_cvtepu8_epi32(_mm_loadl_epi64(y_row.as_ptr().add(x) as *const __m128i));
+            let y = _mm256_mullo_epi32(_mm256_sub_epi32(y, coef(c.y_offset)), coef(c.y_scale));
+            let u4 = _mm_cvtsi32_si128((u_row.as_ptr().add(x / 2) as *const i32).read_unaligned());
+            let v4 = _mm_cvtsi32_si128((v_row.as_ptr().add(x / 2) as *const i32).read_unaligned());
+            let u = _mm256_sub_epi32(_mm256_permutevar8x32_epi32(_mm256_cvtepu8_epi32(u4), dup), c128);
+            let v = _mm256_sub_epi32(_mm256_permutevar8x32_epi32(_mm256_cvtepu8_epi32(v4), dup), c128);
+
+            let y = _mm256_add_epi32(y, c128);
+            let r = _mm256_srai_epi32(_mm256_add_epi32(y, _mm256_mullo_epi32(v, coef(c.rv))), 8);
+            let guv = _mm256_add_epi32(_mm256_mullo_epi32(u, coef(c.gu)), _mm256_mullo_epi32(v, coef(c.gv)));
+            let g = _mm256_srai_epi32(_mm256_sub_epi32(y, guv), 8);
+            let b = _mm256_srai_epi32(_mm256_add_epi32(y, _mm256_mullo_epi32(u, coef(c.bu))), 8);
+
+            let px = pack_rgba(clamp_epi32(r, 0, 255), clamp_epi32(g, 0, 255), clamp_epi32(b, 0, 255), alpha);
+            _mm256_storeu_si256(out.as_mut_ptr().add(x * 4) as *mut __m256i, px);
+            x += 8;
+        }
+        n
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rgba_row_to_y(rgba: &[u8], y_out: &mut [u8], c: &YuvCoefficients) -> usize {
+        let n = (rgba.len() / 4).min(y_out.len()) / 8 * 8;
+        let coef = |v: i32| _mm256_set1_epi32(v);
+        let mut x = 0;
+        while x < n {
+            let px = _mm256_loadu_si256(rgba.as_ptr().add(x * 4) as *const __m256i);
+            let (r, g, b) = unpack_rgb(px);
+            let sum = _mm256_add_epi32(
+                _mm256_add_epi32(
+                    _mm256_mullo_epi32(r, coef(c.yr)),
+                    _mm256_mullo_epi32(g, coef(c.yg)),
+                ),
+                _mm256_add_epi32(_mm256_mullo_epi32(b, coef(c.yb)), coef(128)),
+            );
+            let y = _mm256_add_epi32(_mm256_srai_epi32(sum, 8), coef(c.y_offset));
+            store_low_bytes(clamp_epi32(y, c.y_min, c.y_max), y_out.as_mut_ptr().add(x));
+            x += 8;
+        }
+        n
+    }
+
+    /// 두 행 16픽셀의 채널 합 → 가로 2픽셀씩 더한 8개 (2x2 블록 합)
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn block_sums(a0: __m256i, a1: __m256i, b0: __m256i, b1: __m256i) -> __m256i {
+        // hadd는 128비트 레인 단위 → [a01 a23 b01 b23 | a45 a67 b45 b67] 순서 복원
+        let sums = _mm256_hadd_epi32(_mm256_add_epi32(a0, a1), _mm256_add_epi32(b0, b1));
+        _mm256_permutevar8x32_epi32(sums, _mm256_setr_epi32(0, 1, 4, 5, 2, 3, 6, 7))
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rgba_rows_to_uv(
+        row0: &[u8],
+        row1: &[u8],
+        u_out: &mut [u8],
+        v_out: &mut [u8],
+        c: &YuvCoefficients,
+    ) -> usize {
+        let pixels = (row0.len().min(row1.len()) / 4).min(u_out.len() * 2).min(v_out.len() * 2);
+        let n = pixels / 16 * 16;
+        let c128 = _mm256_set1_epi32(128);
+        let coef = |v: i32| _mm256_set1_epi32(v);
+
+        let mut x = 0;
+        while x < n {
+            let load = |row: &[u8], offset: usize| {
+                _mm256_loadu_si256(row.as_ptr().add((x + offset) * 4) as *const __m256i)
+            };
+            let (r00, g00, b00) = unpack_rgb(load(row0, 0));
+            let (r01, g01, b01) = unpack_rgb(load(row0, 8));
+            let (r10, g10, b10) = unpack_rgb(load(row1, 0));
+            let (r11, g11, b11) = unpack_rgb(load(row1, 8));
+
+            // 2x2 평균 (합 / 4, 음수 없음)
+            let r = _mm256_srli_epi32(block_sums(r00, r10, r01, r11), 2);
+            let g = _mm256_srli_epi32(block_sums(g00, g10, g01, g11), 2);
+            let b = _mm256_srli_epi32(block_sums(b00, b10, b01, b11), 2);
+
+            let u_sum = _mm256_add_epi32(
+                _mm256_add_epi32(
+                    _mm256_mullo_epi32(r, coef(c.ur)),
+                    _mm256_mullo_epi32(g, coef(c.ug)),
+                ),
+                _mm256_add_epi32(_mm256_mullo_epi32(b, coef(c.ub)), c128),
+            );
+            let v_sum = _mm256_add_epi32(
+                _mm256_add_epi32(
+                    _mm256_mullo_epi32(r, coef(c.vr)),
+                    _mm256_mullo_epi32(g, coef(c.vg)),
+                ),
+                _mm256_add_epi32(_mm256_mullo_epi32(b, coef(c.vb)), c128),
+            );
+            let u = _mm256_add_epi32(_mm256_srai_epi32(u_sum, 8), c128);
+            let v = _mm256_add_epi32(_mm256_srai_epi32(v_sum, 8), c128);
+            store_low_bytes(clamp_epi32(u, c.c_min, c.c_max), u_out.as_mut_ptr().add(x / 2));
+            store_low_bytes(clamp_epi32(v, c.c_min, c.c_max), v_out.as_mut_ptr().add(x / 2));
+            x += 16;
+        }
+        n / 2
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn apply_effects_row(row: &mut [u8], y: usize, k: &EffectKernel) -> usize {
+        let n = (row.len() / 4).min(k.width) / 8 * 8;
+        let gamma_table: Option<[i32; 256]> = k.gamma.map(|table| table.map(i32::from));
+        // 비네팅 세로 성분은 행마다 고정
+        let dy = (y as f32 + 0.5) / k.height as f32 * 2.0 - 1.0;
+
+        let zero = _mm256_setzero_ps();
+        let one = _mm256_set1_ps(1.0);
+        let max = _mm256_set1_ps(255.0);
+        let (lum_r, lum_g, lum_b) = (_mm256_set1_ps(0.2126), _mm256_set1_ps(0.7152), _mm256_set1_ps(0.0722));
+        let luminance = |r, g, b| {
+            _mm256_add_ps(_mm256_add_ps(_mm256_mul_ps(lum_r, r), _mm256_mul_ps(lum_g, g)), _mm256_mul_ps(lum_b, b))
+        };
+        let gamma = |table: &[i32; 256], v| {
+            let index = _mm256_cvttps_epi32(_mm256_min_ps(_mm256_max_ps(v, zero), max));
+            _mm256_cvtepi32_ps(_mm256_i32gather_epi32::<4>(table.as_ptr(), index))
+        };
+
+        let mut x = 0;
+        while x < n {
+            let ptr = row.as_mut_ptr().add(x * 4) as *mut __m256i;
+            let px = _mm256_loadu_si256(ptr);
+            let (ri, gi, bi) = unpack_rgb(px);
+            let mut r = _mm256_cvtepi32_ps(ri);
+            let mut g = _mm256_cvtepi32_ps(gi);
+            let mut b = _mm256_cvtepi32_ps(bi);
+
+            if let Some(offset) = k.brightness {
+                let offset = _mm256_set1_ps(offset);
+                r = _mm256_add_ps(r, offset);
+                g = _mm256_add_ps(g, offset);
+                b = _mm256_add_ps(b, offset);
+            }
+
+            if let Some(factor) = k.contrast {
+                let (mid, factor) = (_mm256_set1_ps(128.0), _mm256_set1_ps(factor));
+                r = _mm256_add_ps(mid, _mm256_mul_ps(_mm256_sub_ps(r, mid), factor));
+                g = _mm256_add_ps(mid, _mm256_mul_ps(_mm256_sub_ps(g, mid), factor));
+                b = _mm256_add_ps(mid, _mm256_mul_ps(_mm256_sub_ps(b, mid), factor));
+            }
+
+            if let Some(table) = &gamma_table {
+                r = gamma(table, r);
+                g = gamma(table, g);
+                b = gamma(table, b);
+            }
+
+            if let Some((highlights, shadows)) = k.tone {
+                let lum = _mm256_div_ps(luminance(r, g, b), max);
+                let lum = _mm256_min_ps(_mm256_max_ps(lum, zero), one);
+                let dark = _mm256_sub_ps(one, lum);
+                let high = _mm256_mul_ps(_mm256_mul_ps(_mm256_set1_ps(highlights), lum), lum);
+                let low = _mm256_mul_ps(_mm256_mul_ps(_mm256_set1_ps(shadows), dark), dark);
+                let offset = _mm256_mul_ps(_mm256_add_ps(high, low), _mm256_set1_ps(128.0));
+                r = _mm256_add_ps(r, offset);
+                g = _mm256_add_ps(g, offset);
+                b = _mm256_add_ps(b, offset);
+            }
+
+            if let Some(factor) = k.saturation {
+                let lum = luminance(r, g, b);
+                let factor = _mm256_set1_ps(factor);
+                r = _mm256_add_ps(lum, _mm256_mul_ps(_mm256_sub_ps(r, lum), factor));
+                g = _mm256_add_ps(lum, _mm256_mul_ps(_mm256_sub_ps(g, lum), factor));
+                b = _mm256_add_ps(lum, _mm256_mul_ps(_mm256_sub_ps(b, lum), factor));
+            }
+
+            if let Some((dr, db)) = k.temperature {
+                r = _mm256_add_ps(r, _mm256_set1_ps(dr));
+                b = _mm256_add_ps(b, _mm256_set1_ps(db));
+            }
+
+            if let Some((drb, dg)) = k.tint {
+                r = _mm256_add_ps(r, _mm256_set1_ps(drb));
+                g = _mm256_add_ps(g, _mm256_set1_ps(dg));
+                b = _mm256_add_ps(b, _mm256_set1_ps(drb));
+            }
+
+            if let Some((amount, target)) = k.vignette {
+                let xf = x as f32;
+                let xs = _mm256_setr_ps(xf, xf + 1.0, xf + 2.0, xf + 3.0, xf + 4.0, xf + 5.0, xf + 6.0, xf + 7.0);
+                let dx = _mm256_sub_ps(
+                    _mm256_mul_ps(
+                        _mm256_div_ps(_mm256_add_ps(xs, _mm256_set1_ps(0.5)), _mm256_set1_ps(k.width as f32)),
+                        _mm256_set1_ps(2.0),
+                    ),
+                    one,
+                );
+                let d2 = _mm256_add_ps(_mm256_mul_ps(dx, dx), _mm256_set1_ps(dy * dy));
+                let d = _mm256_sqrt_ps(_mm256_div_ps(d2, _mm256_set1_ps(2.0)));
+                let t = _mm256_div_ps(
+                    _mm256_sub_ps(d, _mm256_set1_ps(VIGNETTE_INNER_RADIUS)),
+                    _mm256_set1_ps(1.0 - VIGNETTE_INNER_RADIUS),
+                );
+                let t = _mm256_min_ps(_mm256_max_ps(t, zero), one);
+                let curve = _mm256_mul_ps(
+                    _mm256_mul_ps(t, t),
+                    _mm256_sub_ps(_mm256_set1_ps(3.0), _mm256_mul_ps(_mm256_set1_ps(2.0), t)),
+                );
+                let weight = _mm256_mul_ps(curve, _mm256_set1_ps(amount));
+                let target = _mm256_set1_ps(target);
+                r = _mm256_add_ps(r, _mm256_mul_ps(_mm256_sub_ps(target, r), weight));
+                g = _mm256_add_ps(g, _mm256_mul_ps(_mm256_sub_ps(target, g), weight));
+                b = _mm256_add_ps(b, _mm256_mul_ps(_mm256_sub_ps(target, b), weight));
+            }
+
+            let to_u8 = |v| _mm256_cvttps_epi32(_mm256_min_ps(_mm256_max_ps(v, zero), max));
+            let alpha = _mm256_and_si256(px, _mm256_set1_epi32(0xFF00_0000u32 as i32));
+            _mm256_storeu_si256(ptr, pack_rgba(to_u8(r), to_u8(g), to_u8(b), alpha));
+            x += 8;
+        }
+        n
+    }
+}
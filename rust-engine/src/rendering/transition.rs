@@ -0,0 +1,101 @@
+// 트랜지션 블렌딩 - 앞/뒤 클립 RGBA 프레임을 진행률에 따라 합성
+// 프리뷰/Export 공통 (Export YUV 프레임은 렌더러에서 RGBA 변환 후 호출)
+
+use crate::timeline::TransitionKind;
+
+/// 두 RGBA 프레임(같은 크기)을 트랜지션 진행률(0.0~1.0)에 맞춰 합성
+/// 크기가 맞지 않으면 진행률 기준으로 한쪽 프레임 그대로 반환
+pub fn blend_transition(
+    from: &[u8],
+    to: &[u8],
+    width: u32,
+    height: u32,
+    kind: TransitionKind,
+    progress: f32,
+) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let size = w * h * 4;
+    if from.len() < size || to.len() < size {
+        return if progress < 0.5 { from.to_vec() } else { to.to_vec() };
+    }
+    let p = progress.clamp(0.0, 1.0);
+
+    match kind {
+        TransitionKind::Crossfade => from[..size].iter().zip(&to[..size])
+            .map(|(&a, &b)| (a as f32 + (b as f32 - a as f32) * p).round() as u8)
+            .collect(),
+        TransitionKind::DipToBlack => {
+            // 전반부: 앞 클립이 검정으로, 후반부: 검정에서 뒤 클립으로 (알파 유지)
+            let (src, level) = if p < 0.5 { (from, 1.0 - p * 2.0) } else { (to, p * 2.0 - 1.0) };
+            src[..size].chunks_exact(4)
+                .flat_map(|px| {
+                    let dim = |c: u8| (c as f32 * level).round() as u8;
+                    [dim(px[0]), dim(px[1]), dim(px[2]), px[3]]
+                })
+                .collect()
+        }
+        TransitionKind::Wipe => {
+            let edge = (p * w as f32).round() as usize;
+            let mut out = from[..size].to_vec();
+            for y in 0..h {
+                let row = y * w * 4;
+                out[row..row + edge * 4].copy_from_slice(&to[row..row + edge * 4]);
+            }
+            out
+        }
+        TransitionKind::Slide => {
+            // 뒤 클립 왼쪽 가장자리가 edge 위치 (앞 클립 위로 덮음)
+            let visible = (p * w as f32).round() as usize;
+            let edge = w - visible;
+            let mut out = from[..size].to_vec();
+            for y in 0..h {
+                let row = y * w * 4;
+                out[row + edge * 4..row + w * 4].copy_from_slice(&to[row..row + visible * 4]);
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: usize, height: usize, rgba: [u8; 4]) -> Vec<u8> {
+        rgba.iter().copied().cycle().take(width * height * 4).collect()
+    }
+
+    #[test]
+    fn test_crossfade_and_dip() {
+        let from = solid(2, 1, [200, 0, 0, 255]);
+        let to = solid(2, 1, [0, 100, 0, 255]);
+
+        let out = blend_transition(&from, &to, 2, 1, TransitionKind::Crossfade, 0.5);
+        assert_eq!(&out[0..4], &[100, 50, 0, 255]);
+
+        let out = blend_transition(&from, &to, 2, 1, TransitionKind::DipToBlack, 0.25);
+        assert_eq!(&out[0..4], &[100, 0, 0, 255]);
+        let out = blend_transition(&from, &to, 2, 1, TransitionKind::DipToBlack, 0.5);
+        assert_eq!(&out[0..4], &[0, 0, 0, 255]);
+        let out = blend_transition(&from, &to, 2, 1, TransitionKind::DipToBlack, 1.0);
+        assert_eq!(out, to);
+    }
+
+    #[test]
+    fn test_wipe_and_slide() {
+        // 앞 클립: 0, 뒤 클립: 픽셀 인덱스 10, 11, 12, 13
+        let from = solid(4, 1, [0, 0, 0, 255]);
+        let to: Vec<u8> = (10..14u8).flat_map(|v| [v, v, v, 255]).collect();
+
+        let out = blend_transition(&from, &to, 4, 1, TransitionKind::Wipe, 0.5);
+        let firsts: Vec<u8> = out.chunks_exact(4).map(|p| p[0]).collect();
+        assert_eq!(firsts, vec![10, 11, 0, 0]);
+
+        // 슬라이드: 뒤 클립의 왼쪽 절반이 오른쪽에 나타남
+        let out = blend_transition(&from, &to, 4, 1, TransitionKind::Slide, 0.5);
+        let firsts: Vec<u8> = out.chunks_exact(4).map(|p| p[0]).collect();
+        assert_eq!(firsts, vec![0, 0, 10, 11]);
+
+        assert_eq!(blend_transition(&from, &to, 4, 1, TransitionKind::Slide, 1.0), to);
+    }
+}
@@ -1,3 +1,5 @@
-// 자막 처리 모듈 — RGBA 오버레이 알파 블렌딩
+// 자막 처리 모듈 — 자막 파일 파싱 + 텍스트 래스터화 + RGBA 오버레이 알파 블렌딩
 
 pub mod overlay;
+pub mod parser;
+pub mod text;
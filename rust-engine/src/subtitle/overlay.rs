@@ -1,5 +1,8 @@
 // 자막 오버레이 — RGBA 비트맵 알파 블렌딩
-// C#에서 텍스트를 RGBA 비트맵으로 렌더링 → FFI로 전달 → Export 시 프레임 위에 합성
+// C# 비트맵(exporter_subtitle_list_add) 또는 엔진 텍스트 래스터화(subtitle::text) → Export 시 프레임 위에 합성
+
+use crate::ffmpeg::colorspace::{YuvCoefficients, YuvColor};
+use crate::rendering::simd;
 
 /// 단일 자막 오버레이 (시간 범위 + RGBA 비트맵)
 pub struct SubtitleOverlay {
@@ -17,6 +20,8 @@ pub struct SubtitleOverlay {
     pub height: u32,
     /// RGBA 비트맵 데이터 (width * height * 4 bytes)
     pub rgba_data: Vec<u8>,
+    /// 블렌딩 순서 (큰 값이 위, 같으면 나중에 추가된 것이 위)
+    pub z_index: i32,
 }
 
 /// 자막 오버레이 목록 (FFI에서 생성/해제)
@@ -29,9 +34,25 @@ impl SubtitleOverlayList {
         Self { overlays: Vec::new() }
     }
 
-    /// 특정 시간에 활성인 오버레이 찾기
-    pub fn get_active(&self, timestamp_ms: i64) -> Option<&SubtitleOverlay> {
-        self.overlays.iter().find(|o| timestamp_ms >= o.start_ms && timestamp_ms < o.end_ms)
+    /// 특정 시간에 활성인 오버레이 전체 (블렌딩 순서: z_index 오름차순, 같으면 추가 순서)
+    pub fn get_active(&self, timestamp_ms: i64) -> Vec<&SubtitleOverlay> {
+        let mut active: Vec<&SubtitleOverlay> = self.overlays.iter()
+            .filter(|o| timestamp_ms >= o.start_ms && timestamp_ms < o.end_ms)
+            .collect();
+        active.sort_by_key(|o| o.z_index);
+        active
+    }
+}
+
+/// 여러 오버레이를 순서대로 블렌딩 (get_active 결과, 뒤가 위)
+pub fn blend_overlays_rgba(
+    frame_rgba: &mut [u8],
+    frame_width: u32,
+    frame_height: u32,
+    overlays: &[&SubtitleOverlay],
+) {
+    for overlay in overlays {
+        blend_overlay_rgba(frame_rgba, frame_width, frame_height, overlay);
     }
 }
 
@@ -91,8 +112,17 @@ pub fn blend_overlay_rgba(
     }
 }
 
-/// YUV420P → RGBA 변환 (자막 블렌딩용)
-pub fn yuv420p_to_rgba(yuv_data: &[u8], width: u32, height: u32) -> Vec<u8> {
+/// YUV420P → RGBA 변환 (자막 블렌딩용, color: 데이터의 행렬/범위)
+pub fn yuv420p_to_rgba(yuv_data: &[u8], width: u32, height: u32, color: YuvColor) -> Vec<u8> {
+    yuv420p_to_rgba_impl(yuv_data, width, height, &color.coefficients(), true)
+}
+
+/// 스칼라 전용 yuv420p_to_rgba (SIMD 결과 비교/벤치마크 기준)
+pub fn yuv420p_to_rgba_scalar(yuv_data: &[u8], width: u32, height: u32, color: YuvColor) -> Vec<u8> {
+    yuv420p_to_rgba_impl(yuv_data, width, height, &color.coefficients(), false)
+}
+
+fn yuv420p_to_rgba_impl(yuv_data: &[u8], width: u32, height: u32, c: &YuvCoefficients, use_simd: bool) -> Vec<u8> {
     let w = width as usize;
     let h = height as usize;
     let y_size = w * h;
@@ -110,14 +140,27 @@ pub fn yuv420p_to_rgba(yuv_data: &[u8], width: u32, height: u32) -> Vec<u8> {
     let mut rgba = vec![0u8; w * h * 4];
 
     for row in 0..h {
-        for col in 0..w {
-            let y_val = y_plane[row * w + col] as i32;
+        // SIMD: 8픽셀 묶음 (크로마 행이 평면 안에 있을 때만), 나머지는 스칼라
+        let uv_row = (row / 2) * (w / 2)..(row / 2) * (w / 2) + w / 2;
+        let done = match (use_simd, u_plane.get(uv_row.clone()), v_plane.get(uv_row)) {
+            (true, Some(u_row), Some(v_row)) => simd::yuv_row_to_rgba(
+                &y_plane[row * w..(row + 1) * w],
+                u_row,
+                v_row,
+                &mut rgba[row * w * 4..(row + 1) * w * 4],
+                c,
+            ),
+            _ => 0,
+        };
+
+        for col in done..w {
+            let y_val = c.y_scale * (y_plane[row * w + col] as i32 - c.y_offset);
             let u_val = u_plane[(row / 2) * (w / 2) + col / 2] as i32 - 128;
             let v_val = v_plane[(row / 2) * (w / 2) + col / 2] as i32 - 128;
 
-            let r = (y_val + ((359 * v_val) >> 8)).clamp(0, 255);
-            let g = (y_val - ((88 * u_val + 183 * v_val) >> 8)).clamp(0, 255);
-            let b = (y_val + ((454 * u_val) >> 8)).clamp(0, 255);
+            let r = ((y_val + c.rv * v_val + 128) >> 8).clamp(0, 255);
+            let g = ((y_val - c.gu * u_val - c.gv * v_val + 128) >> 8).clamp(0, 255);
+            let b = ((y_val + c.bu * u_val + 128) >> 8).clamp(0, 255);
 
             let idx = (row * w + col) * 4;
             rgba[idx] = r as u8;
@@ -130,8 +173,17 @@ pub fn yuv420p_to_rgba(yuv_data: &[u8], width: u32, height: u32) -> Vec<u8> {
     rgba
 }
 
-/// RGBA → YUV420P 변환 (블렌딩 후 인코딩용)
-pub fn rgba_to_yuv420p(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+/// RGBA → YUV420P 변환 (블렌딩 후 인코딩용, color: 출력 행렬/범위)
+pub fn rgba_to_yuv420p(rgba: &[u8], width: u32, height: u32, color: YuvColor) -> Vec<u8> {
+    rgba_to_yuv420p_impl(rgba, width, height, &color.coefficients(), true)
+}
+
+/// 스칼라 전용 rgba_to_yuv420p (SIMD 결과 비교/벤치마크 기준)
+pub fn rgba_to_yuv420p_scalar(rgba: &[u8], width: u32, height: u32, color: YuvColor) -> Vec<u8> {
+    rgba_to_yuv420p_impl(rgba, width, height, &color.coefficients(), false)
+}
+
+fn rgba_to_yuv420p_impl(rgba: &[u8], width: u32, height: u32, c: &YuvCoefficients, use_simd: bool) -> Vec<u8> {
     let w = width as usize;
     let h = height as usize;
     let y_size = w * h;
@@ -139,24 +191,43 @@ pub fn rgba_to_yuv420p(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
 
     let mut yuv = vec![0u8; y_size + uv_size * 2];
 
-    // Y plane (BT.601)
+    // Y plane
     for row in 0..h {
-        for col in 0..w {
+        let done = match rgba.get(row * w * 4..(row + 1) * w * 4) {
+            Some(rgba_row) if use_simd => simd::rgba_row_to_y(rgba_row, &mut yuv[row * w..(row + 1) * w], c),
+            _ => 0,
+        };
+        for col in done..w {
             let idx = (row * w + col) * 4;
             let r = rgba[idx] as i32;
             let g = rgba[idx + 1] as i32;
             let b = rgba[idx + 2] as i32;
-            let y = ((66 * r + 129 * g + 25 * b + 128) >> 8) + 16;
-            yuv[row * w + col] = y.clamp(16, 235) as u8;
+            let y = ((c.yr * r + c.yg * g + c.yb * b + 128) >> 8) + c.y_offset;
+            yuv[row * w + col] = y.clamp(c.y_min, c.y_max) as u8;
         }
     }
 
-    // U, V planes (2x2 서브샘플링, BT.601)
+    // U, V planes (2x2 서브샘플링)
     let u_offset = y_size;
     let v_offset = y_size + uv_size;
 
     for row in (0..h).step_by(2) {
-        for col in (0..w).step_by(2) {
+        // SIMD: 16픽셀(크로마 8개) 묶음, 나머지와 가장자리는 스칼라
+        let next_row = (row + 1).min(h - 1);
+        let uv_row = (row / 2) * (w / 2)..(row / 2) * (w / 2) + w / 2;
+        let done = match (
+            use_simd && uv_row.end <= uv_size,
+            rgba.get(row * w * 4..(row + 1) * w * 4),
+            rgba.get(next_row * w * 4..(next_row + 1) * w * 4),
+        ) {
+            (true, Some(row0), Some(row1)) => {
+                let (u_plane, v_plane) = yuv[u_offset..].split_at_mut(uv_size);
+                simd::rgba_rows_to_uv(row0, row1, &mut u_plane[uv_row.clone()], &mut v_plane[uv_row], c)
+            }
+            _ => 0,
+        };
+
+        for col in (done * 2..w).step_by(2) {
             let mut r_sum = 0i32;
             let mut g_sum = 0i32;
             let mut b_sum = 0i32;
@@ -178,12 +249,92 @@ pub fn rgba_to_yuv420p(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
             let b = b_sum / 4;
 
             let uv_idx = (row / 2) * (w / 2) + col / 2;
-            let u = ((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128;
-            let v = ((112 * r - 94 * g - 18 * b + 128) >> 8) + 128;
-            yuv[u_offset + uv_idx] = u.clamp(0, 255) as u8;
-            yuv[v_offset + uv_idx] = v.clamp(0, 255) as u8;
+            let u = ((c.ur * r + c.ug * g + c.ub * b + 128) >> 8) + 128;
+            let v = ((c.vr * r + c.vg * g + c.vb * b + 128) >> 8) + 128;
+            yuv[u_offset + uv_idx] = u.clamp(c.c_min, c.c_max) as u8;
+            yuv[v_offset + uv_idx] = v.clamp(c.c_min, c.c_max) as u8;
         }
     }
 
     yuv
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffmpeg::colorspace::YuvMatrix;
+
+    fn pixel_overlay(start_ms: i64, end_ms: i64, color: u8, z_index: i32) -> SubtitleOverlay {
+        SubtitleOverlay {
+            start_ms, end_ms, x: 0, y: 0, width: 1, height: 1,
+            rgba_data: vec![color, color, color, 255], z_index,
+        }
+    }
+
+    #[test]
+    fn test_active_overlays_z_order() {
+        let mut list = SubtitleOverlayList::new();
+        list.overlays.push(pixel_overlay(0, 1000, 10, 1));
+        list.overlays.push(pixel_overlay(500, 1500, 20, 0));
+        list.overlays.push(pixel_overlay(0, 1000, 30, 1));
+
+        let colors = |ts| list.get_active(ts).iter().map(|o| o.rgba_data[0]).collect::<Vec<_>>();
+        assert_eq!(colors(100), vec![10, 30]);
+        assert_eq!(colors(600), vec![20, 10, 30]);
+        assert_eq!(colors(1200), vec![20]);
+        assert!(colors(2000).is_empty());
+
+        // 마지막(최상위) 오버레이가 보임
+        let mut frame = vec![0u8; 4];
+        blend_overlays_rgba(&mut frame, 1, 1, &list.get_active(600));
+        assert_eq!(frame, vec![30, 30, 30, 255]);
+    }
+
+    /// 재현 가능한 의사 난수 바이트 (LCG)
+    fn noise(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_color_conversion_simd_matches_scalar() {
+        let bt601_full = YuvColor { matrix: YuvMatrix::Bt601, full_range: true };
+        // 8/16픽셀 묶음 + 스칼라 나머지 경계
+        for color in [YuvColor::BT709, bt601_full] {
+            for (w, h) in [(64u32, 4u32), (38, 6), (22, 2), (6, 2)] {
+                let yuv = noise((w * h + (w / 2) * (h / 2) * 2) as usize, w);
+                assert_eq!(yuv420p_to_rgba(&yuv, w, h, color), yuv420p_to_rgba_scalar(&yuv, w, h, color), "{}x{}", w, h);
+
+                let rgba = noise((w * h * 4) as usize, h);
+                assert_eq!(rgba_to_yuv420p(&rgba, w, h, color), rgba_to_yuv420p_scalar(&rgba, w, h, color), "{}x{}", w, h);
+            }
+        }
+    }
+
+    #[test]
+    fn test_color_conversion_round_trip() {
+        // 단색 프레임 (서브샘플링 손실 없음) RGBA → YUV → RGBA ≈ 원본
+        for matrix in [YuvMatrix::Bt601, YuvMatrix::Bt709, YuvMatrix::Bt2020] {
+            for full_range in [false, true] {
+                let color = YuvColor { matrix, full_range };
+                for rgb in [[0u8, 0, 0], [255, 255, 255], [200, 30, 90], [16, 180, 240]] {
+                    let rgba: Vec<u8> = (0..16 * 2).flat_map(|_| [rgb[0], rgb[1], rgb[2], 255]).collect();
+                    let yuv = rgba_to_yuv420p(&rgba, 16, 2, color);
+                    let back = yuv420p_to_rgba(&yuv, 16, 2, color);
+                    for (a, b) in back.iter().zip(&rgba) {
+                        assert!((*a as i32 - *b as i32).abs() <= 2, "{:?} {:?} → {:?}", color, rgb, &back[..4]);
+                    }
+                }
+            }
+        }
+
+        // limited range: 검정 Y=16, 흰색 Y=235, 무채색 U=V=128
+        let gray: Vec<u8> = [0u8, 255, 0, 255].iter().flat_map(|&v| [v, v, v, 255]).collect();
+        assert_eq!(rgba_to_yuv420p(&gray, 2, 2, YuvColor::BT709), vec![16, 235, 16, 235, 128, 128]);
+    }
+}
@@ -0,0 +1,413 @@
+// 자막 파일 파서 — SRT / WebVTT / ASS(SSA) → 시간 구간별 텍스트 큐
+// 텍스트는 서식 태그 제거 후 줄바꿈(\n)만 유지, 스타일은 SubtitleStyle (ASS는 파일 스타일 반영)
+
+use crate::utils::json_escape;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 자막 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+    Ass,
+}
+
+impl SubtitleFormat {
+    /// 확장자로 형식 결정 (.srt / .vtt / .ass, .ssa)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "srt" => Some(SubtitleFormat::Srt),
+            "vtt" => Some(SubtitleFormat::Vtt),
+            "ass" | "ssa" => Some(SubtitleFormat::Ass),
+            _ => None,
+        }
+    }
+}
+
+/// 화면 기준 배치 (ASS \an 숫자패드 배치: 1=좌하단, 2=하단 중앙, ... 9=우상단)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleAlignment {
+    BottomLeft = 1,
+    BottomCenter = 2,
+    BottomRight = 3,
+    MiddleLeft = 4,
+    MiddleCenter = 5,
+    MiddleRight = 6,
+    TopLeft = 7,
+    TopCenter = 8,
+    TopRight = 9,
+}
+
+impl SubtitleAlignment {
+    /// 숫자패드 값 → 배치 (범위 밖이면 하단 중앙)
+    pub fn from_numpad(v: u32) -> Self {
+        match v {
+            1 => SubtitleAlignment::BottomLeft,
+            3 => SubtitleAlignment::BottomRight,
+            4 => SubtitleAlignment::MiddleLeft,
+            5 => SubtitleAlignment::MiddleCenter,
+            6 => SubtitleAlignment::MiddleRight,
+            7 => SubtitleAlignment::TopLeft,
+            8 => SubtitleAlignment::TopCenter,
+            9 => SubtitleAlignment::TopRight,
+            _ => SubtitleAlignment::BottomCenter,
+        }
+    }
+}
+
+/// 자막 번인 스타일
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleStyle {
+    /// 폰트 크기 (px, 출력 해상도 기준)
+    pub font_size: f32,
+    /// 글자 색 (RGBA)
+    pub color: [u8; 4],
+    /// 외곽선 색 (RGBA)
+    pub outline_color: [u8; 4],
+    /// 외곽선 두께 (px, 0 = 없음)
+    pub outline_width: f32,
+    pub alignment: SubtitleAlignment,
+    /// 좌우 여백 (px)
+    pub margin_h: i32,
+    /// 상하 여백 (px, 하단 배치는 아래에서, 상단 배치는 위에서)
+    pub margin_v: i32,
+}
+
+impl Default for SubtitleStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 48.0,
+            color: [255, 255, 255, 255],
+            outline_color: [0, 0, 0, 255],
+            outline_width: 2.0,
+            alignment: SubtitleAlignment::BottomCenter,
+            margin_h: 40,
+            margin_v: 40,
+        }
+    }
+}
+
+impl SubtitleStyle {
+    /// 해상도 비율만큼 크기 조정 (폰트/외곽선/여백, 프로젝트 해상도 → 프리뷰 해상도 등)
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self {
+            font_size: self.font_size * factor,
+            outline_width: self.outline_width * factor,
+            margin_h: (self.margin_h as f32 * factor).round() as i32,
+            margin_v: (self.margin_v as f32 * factor).round() as i32,
+            ..self.clone()
+        }
+    }
+}
+
+/// 자막 큐 (시간 구간 + 텍스트)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// 표시 텍스트 (여러 줄은 \n)
+    pub text: String,
+    /// 큐별 스타일 (ASS 스타일/\an 태그, 없으면 기본 스타일 사용)
+    pub style: Option<SubtitleStyle>,
+    /// 겹치는 큐의 z 순서 (ASS Layer, SRT/VTT는 0)
+    pub layer: i32,
+}
+
+/// 자막 파일 로드 (UTF-8, BOM 허용 — 형식은 확장자, 없으면 내용으로 판단)
+pub fn load_subtitle_file(path: &Path) -> Result<Vec<SubtitleCue>, String> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("자막 파일 읽기 실패 ({}): {}", path.display(), e))?;
+    let content = String::from_utf8_lossy(&bytes);
+    let format = SubtitleFormat::from_path(path).unwrap_or_else(|| detect_format(&content));
+    parse_subtitles(&content, format)
+}
+
+/// 내용으로 형식 추정 (WEBVTT 헤더 / [Script Info] 섹션, 그 외 SRT)
+pub fn detect_format(content: &str) -> SubtitleFormat {
+    let head = content.trim_start_matches('\u{feff}').trim_start();
+    if head.starts_with("WEBVTT") {
+        SubtitleFormat::Vtt
+    } else if head.starts_with("[Script Info]") {
+        SubtitleFormat::Ass
+    } else {
+        SubtitleFormat::Srt
+    }
+}
+
+/// 자막 텍스트 파싱 → 시작 시간 순 큐 목록 (길이 0 이하 큐는 제외)
+pub fn parse_subtitles(content: &str, format: SubtitleFormat) -> Result<Vec<SubtitleCue>, String> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n").replace('\r', "\n");
+    let mut cues = match format {
+        SubtitleFormat::Srt | SubtitleFormat::Vtt => parse_blocks(&content),
+        SubtitleFormat::Ass => parse_ass(&content)?,
+    };
+    cues.retain(|c| c.end_ms > c.start_ms);
+    cues.sort_by_key(|c| c.start_ms);
+    Ok(cues)
+}
+
+/// "HH:MM:SS,mmm" / "MM:SS.mmm" / "H:MM:SS.cc" → ms
+fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (clock, frac) = match s.rfind([',', '.']) {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, ""),
+    };
+
+    let mut seconds: i64 = 0;
+    let parts: Vec<&str> = clock.split(':').collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return None;
+    }
+    for part in parts {
+        seconds = seconds * 60 + part.trim().parse::<i64>().ok()?;
+    }
+
+    // 소수부 자릿수 보정 (ASS 센티초 "34" → 340ms)
+    let frac_ms = if frac.is_empty() {
+        0
+    } else {
+        let digits: String = frac.chars().take(3).collect();
+        let value = digits.parse::<i64>().ok()?;
+        value * 10i64.pow(3 - digits.len() as u32)
+    };
+    Some(seconds * 1000 + frac_ms)
+}
+
+/// SRT / VTT 큐 블록 파싱 ("시작 --> 끝" 줄 다음부터 빈 줄까지 텍스트)
+fn parse_blocks(content: &str) -> Vec<SubtitleCue> {
+    let mut cues = Vec::new();
+    for block in content.split("\n\n") {
+        let mut lines = block.lines().skip_while(|l| !l.contains("-->"));
+        let timing = match lines.next() {
+            Some(l) => l,
+            None => continue, // 번호만 있는 블록, WEBVTT 헤더, NOTE/STYLE 블록
+        };
+
+        let (start, rest) = match timing.split_once("-->") {
+            Some(parts) => parts,
+            None => continue,
+        };
+        // VTT 큐 설정 ("00:01.000 --> 00:02.000 align:start line:0")
+        let mut settings = rest.split_whitespace();
+        let end = settings.next().unwrap_or("");
+        let (start_ms, end_ms) = match (parse_timestamp(start), parse_timestamp(end)) {
+            (Some(s), Some(e)) => (s, e),
+            _ => continue,
+        };
+
+        let text: Vec<String> = lines.map(strip_html_tags).collect();
+        cues.push(SubtitleCue {
+            start_ms,
+            end_ms,
+            text: text.join("\n").trim().to_string(),
+            style: vtt_style(settings),
+            layer: 0,
+        });
+    }
+    cues
+}
+
+/// VTT line:0 (상단) 설정만 스타일로 반영
+fn vtt_style<'a>(settings: impl Iterator<Item = &'a str>) -> Option<SubtitleStyle> {
+    for setting in settings {
+        if let Some(line) = setting.strip_prefix("line:") {
+            if line.trim_end_matches('%').parse::<f32>().map(|v| v == 0.0).unwrap_or(false) {
+                return Some(SubtitleStyle { alignment: SubtitleAlignment::TopCenter, ..SubtitleStyle::default() });
+            }
+        }
+    }
+    None
+}
+
+/// SRT/VTT 서식 태그 제거 (<i>, <b>, <font ...>, <c.class>, <v Speaker> 등)
+fn strip_html_tags(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&amp;", "&").replace("&lt;", "<").replace("&gt;", ">").replace("&nbsp;", " ")
+}
+
+/// ASS 색상 "&HAABBGGRR" (AA: 00 = 불투명) → RGBA
+fn parse_ass_color(s: &str) -> Option<[u8; 4]> {
+    let hex = s.trim().trim_start_matches("&H").trim_start_matches("&h").trim_end_matches('&');
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    let [r, g, b, a] = value.to_le_bytes();
+    Some([r, g, b, 255 - a])
+}
+
+/// ASS 파싱 ([V4+ Styles] 스타일 + [Events] Dialogue)
+fn parse_ass(content: &str) -> Result<Vec<SubtitleCue>, String> {
+    let mut section = String::new();
+    let mut style_format: Vec<String> = Vec::new();
+    let mut event_format: Vec<String> = Vec::new();
+    let mut styles: HashMap<String, SubtitleStyle> = HashMap::new();
+    let mut cues = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line.to_ascii_lowercase();
+            continue;
+        }
+        let (key, value) = match line.split_once(':') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => continue,
+        };
+
+        match (section.as_str(), key) {
+            ("[v4+ styles]" | "[v4 styles]", "Format") => {
+                style_format = value.split(',').map(|f| f.trim().to_ascii_lowercase()).collect();
+            }
+            ("[v4+ styles]" | "[v4 styles]", "Style") => {
+                let fields: Vec<&str> = value.splitn(style_format.len().max(1), ',').collect();
+                let field = |name: &str| style_format.iter().position(|f| f == name).and_then(|i| fields.get(i)).map(|v| v.trim());
+                let name = field("name").unwrap_or("Default").to_string();
+                styles.insert(name, ass_style(&field));
+            }
+            ("[events]", "Format") => {
+                event_format = value.split(',').map(|f| f.trim().to_ascii_lowercase()).collect();
+            }
+            ("[events]", "Dialogue") => {
+                if event_format.is_empty() {
+                    return Err("ASS [Events] Format 줄이 없습니다".to_string());
+                }
+                // Text는 마지막 필드 (쉼표 포함 가능)
+                let fields: Vec<&str> = value.splitn(event_format.len(), ',').collect();
+                let field = |name: &str| event_format.iter().position(|f| f == name).and_then(|i| fields.get(i)).map(|v| v.trim());
+                let (start_ms, end_ms) = match (field("start").and_then(parse_timestamp), field("end").and_then(parse_timestamp)) {
+                    (Some(s), Some(e)) => (s, e),
+                    _ => continue,
+                };
+                let raw_text = field("text").unwrap_or("");
+                let mut style = field("style").and_then(|name| styles.get(name.trim_start_matches('*')).cloned());
+                if let Some(alignment) = ass_alignment_override(raw_text) {
+                    style.get_or_insert_with(SubtitleStyle::default).alignment = alignment;
+                }
+                let layer = field("layer").and_then(|v| v.parse().ok()).unwrap_or(0);
+                cues.push(SubtitleCue { start_ms, end_ms, text: strip_ass_tags(raw_text), style, layer });
+            }
+            _ => {}
+        }
+    }
+    Ok(cues)
+}
+
+/// ASS Style 줄 → SubtitleStyle (없는 필드는 기본값)
+fn ass_style<'a>(field: &impl Fn(&str) -> Option<&'a str>) -> SubtitleStyle {
+    let default = SubtitleStyle::default();
+    let number = |name: &str| field(name).and_then(|v| v.parse::<f32>().ok());
+    SubtitleStyle {
+        font_size: number("fontsize").unwrap_or(default.font_size),
+        color: field("primarycolour").and_then(parse_ass_color).unwrap_or(default.color),
+        outline_color: field("outlinecolour").and_then(parse_ass_color).unwrap_or(default.outline_color),
+        outline_width: number("outline").unwrap_or(default.outline_width),
+        alignment: number("alignment").map(|v| SubtitleAlignment::from_numpad(v as u32)).unwrap_or(default.alignment),
+        margin_h: number("marginl").map(|v| v as i32).unwrap_or(default.margin_h),
+        margin_v: number("marginv").map(|v| v as i32).unwrap_or(default.margin_v),
+    }
+}
+
+/// 텍스트 내 {\anN} 배치 태그
+fn ass_alignment_override(text: &str) -> Option<SubtitleAlignment> {
+    let start = text.find("\\an")? + 3;
+    let digit = text[start..].chars().next()?.to_digit(10)?;
+    Some(SubtitleAlignment::from_numpad(digit))
+}
+
+/// ASS 오버라이드 블록 {...} 제거 + \N, \n → 줄바꿈, \h → 공백
+fn strip_ass_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut depth = 0;
+    for c in text.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("\\N", "\n").replace("\\n", "\n").replace("\\h", " ").trim().to_string()
+}
+
+/// 큐 목록 JSON 배열 [{"start_ms":0,"end_ms":1000,"text":"..."}]
+pub fn cues_json(cues: &[SubtitleCue]) -> String {
+    let items: Vec<String> = cues.iter()
+        .map(|c| format!("{{\"start_ms\":{},\"end_ms\":{},\"text\":\"{}\"}}", c.start_ms, c.end_ms, json_escape(&c.text)))
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("00:01:02,345"), Some(62_345));
+        assert_eq!(parse_timestamp("01:02.5"), Some(62_500));
+        assert_eq!(parse_timestamp("1:00:00.00"), Some(3_600_000));
+        assert_eq!(parse_timestamp("0:00:01.34"), Some(1_340));
+        assert_eq!(parse_timestamp("abc"), None);
+    }
+
+    #[test]
+    fn test_parse_srt_and_vtt() {
+        let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\n<i>Hello</i>\r\nworld\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\nA &amp; B\r\n";
+        let cues = parse_subtitles(srt, SubtitleFormat::Srt).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!((cues[0].start_ms, cues[0].end_ms), (1_000, 2_500));
+        assert_eq!(cues[0].text, "Hello\nworld");
+        assert_eq!(cues[1].text, "A & B");
+
+        let vtt = "WEBVTT\n\nNOTE 메모\n\nintro\n00:01.000 --> 00:02.000 line:0\n<v Kim>안녕하세요\n\n00:03.000 --> 00:04.000\n둘째\n";
+        assert_eq!(detect_format(vtt), SubtitleFormat::Vtt);
+        let cues = parse_subtitles(vtt, SubtitleFormat::Vtt).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].text, "안녕하세요");
+        assert_eq!(cues[0].style.as_ref().unwrap().alignment, SubtitleAlignment::TopCenter);
+        assert!(cues[1].style.is_none());
+    }
+
+    #[test]
+    fn test_parse_ass() {
+        let ass = "[Script Info]\nTitle: test\n\n[V4+ Styles]\n\
+            Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+            Style: Default,Arial,60,&H00FFFF00,&H000000FF,&H80000000,&H00000000,0,0,0,0,100,100,0,0,1,3,0,2,10,10,30,1\n\n\
+            [Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+            Dialogue: 0,0:00:05.00,0:00:07.50,Default,,0,0,0,,{\\b1}Hello,\\Nworld\n\
+            Dialogue: 1,0:00:01.00,0:00:02.00,Default,,0,0,0,,{\\an8}Top\n";
+        assert_eq!(detect_format(ass), SubtitleFormat::Ass);
+        let cues = parse_subtitles(ass, SubtitleFormat::Ass).unwrap();
+        assert_eq!(cues.len(), 2);
+
+        // 시작 시간 순 정렬
+        assert_eq!(cues[0].text, "Top");
+        assert_eq!(cues[0].style.as_ref().unwrap().alignment, SubtitleAlignment::TopCenter);
+        assert_eq!((cues[0].layer, cues[1].layer), (1, 0));
+
+        let cue = &cues[1];
+        assert_eq!((cue.start_ms, cue.end_ms), (5_000, 7_500));
+        assert_eq!(cue.text, "Hello,\nworld");
+        let style = cue.style.as_ref().unwrap();
+        assert_eq!(style.font_size, 60.0);
+        assert_eq!(style.color, [0, 255, 255, 255]); // &H00FFFF00 = BGR(FF,FF,00) → 청록
+        assert_eq!(style.outline_color, [0, 0, 0, 127]);
+        assert_eq!(style.outline_width, 3.0);
+        assert_eq!(style.margin_v, 30);
+    }
+
+    #[test]
+    fn test_cues_json() {
+        let cues = vec![SubtitleCue { start_ms: 0, end_ms: 1000, text: "say \"hi\"\nbye".to_string(), style: None, layer: 0 }];
+        assert_eq!(cues_json(&cues), "[{\"start_ms\":0,\"end_ms\":1000,\"text\":\"say \\\"hi\\\"\\nbye\"}]");
+    }
+}
@@ -0,0 +1,400 @@
+// 텍스트 래스터라이저 — 스타일 텍스트 → RGBA 비트맵 (자막/타이틀)
+// ab_glyph 글리프 래스터화 + 외곽선(커버리지 팽창) + 줄바꿈/정렬
+// 글자가 없는 폰트는 폴백 폰트 순서대로 사용 (라틴 폰트 + 한글 폰트 등)
+
+use crate::subtitle::overlay::SubtitleOverlay;
+use crate::subtitle::parser::{SubtitleAlignment, SubtitleCue, SubtitleStyle};
+use ab_glyph::{point, Font, FontArc, FontVec, GlyphId, PxScale, ScaleFont};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// 시스템 기본 폰트 후보 (앞에서부터 존재하는 파일을 기본 + 폴백으로 사용)
+const SYSTEM_FONT_PATHS: &[&str] = &[
+    "C:\\Windows\\Fonts\\malgun.ttf",
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+    "/System/Library/Fonts/Helvetica.ttc",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
+];
+
+/// 래스터화된 텍스트 (RGBA, straight alpha)
+#[derive(Debug, Clone)]
+pub struct TextBitmap {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+    /// 외곽선 포함 여백 (텍스트 영역은 padding만큼 안쪽)
+    pub padding: u32,
+}
+
+/// 텍스트 렌더러 (폰트 + 폴백 폰트)
+pub struct TextRenderer {
+    fonts: Vec<FontArc>,
+}
+
+impl TextRenderer {
+    /// 폰트 데이터로 생성 (.ttf / .otf / .ttc 첫 번째 폰트)
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, String> {
+        Ok(Self { fonts: vec![load_font(data)?] })
+    }
+
+    /// 폰트 파일로 생성
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read(path)
+            .map_err(|e| format!("폰트 파일 읽기 실패 ({}): {}", path.display(), e))?;
+        Self::from_bytes(data)
+    }
+
+    /// 시스템 폰트 (SYSTEM_FONT_PATHS 중 존재하는 파일 전부, 첫 번째가 기본)
+    pub fn system_default() -> Result<Self, String> {
+        let fonts: Vec<FontArc> = SYSTEM_FONT_PATHS.iter()
+            .filter_map(|path| std::fs::read(path).ok())
+            .filter_map(|data| load_font(data).ok())
+            .collect();
+        if fonts.is_empty() {
+            return Err("사용 가능한 시스템 폰트가 없습니다".to_string());
+        }
+        Ok(Self { fonts })
+    }
+
+    /// 폴백 폰트 추가 (기본 폰트에 없는 글자에 사용)
+    pub fn add_fallback_file(&mut self, path: &Path) -> Result<(), String> {
+        let data = std::fs::read(path)
+            .map_err(|e| format!("폰트 파일 읽기 실패 ({}): {}", path.display(), e))?;
+        self.fonts.push(load_font(data)?);
+        Ok(())
+    }
+
+    /// 글자를 가진 첫 폰트 인덱스 + 글리프 (없으면 기본 폰트의 .notdef)
+    fn font_for(&self, c: char) -> (usize, GlyphId) {
+        for (index, font) in self.fonts.iter().enumerate() {
+            let id = font.glyph_id(c);
+            if id.0 != 0 {
+                return (index, id);
+            }
+        }
+        (0, self.fonts[0].glyph_id(c))
+    }
+
+    /// 한 줄 폭 (px, 커닝 포함)
+    fn line_width(&self, line: &str, scale: PxScale) -> f32 {
+        let mut width = 0.0;
+        let mut prev: Option<(usize, GlyphId)> = None;
+        for c in line.chars() {
+            let (font_index, id) = self.font_for(c);
+            let scaled = self.fonts[font_index].as_scaled(scale);
+            if let Some((prev_index, prev_id)) = prev {
+                if prev_index == font_index {
+                    width += scaled.kern(prev_id, id);
+                }
+            }
+            width += scaled.h_advance(id);
+            prev = Some((font_index, id));
+        }
+        width
+    }
+
+    /// 최대 폭에 맞춰 줄바꿈 (공백 단위, 한 단어가 넘치면 글자 단위)
+    fn wrap(&self, text: &str, scale: PxScale, max_width: Option<f32>) -> Vec<String> {
+        let max_width = match max_width {
+            Some(w) if w > 0.0 => w,
+            _ => return text.lines().map(str::to_string).collect(),
+        };
+
+        let mut lines = Vec::new();
+        for paragraph in text.lines() {
+            let mut current = String::new();
+            for word in paragraph.split(' ') {
+                let candidate = if current.is_empty() { word.to_string() } else { format!("{} {}", current, word) };
+                if self.line_width(&candidate, scale) <= max_width {
+                    current = candidate;
+                    continue;
+                }
+                if !current.is_empty() {
+                    lines.push(std::mem::take(&mut current));
+                }
+                // 단어 자체가 넘치면 글자 단위로 분할 (띄어쓰기 없는 긴 한글/CJK 문장)
+                for c in word.chars() {
+                    current.push(c);
+                    if self.line_width(&current, scale) > max_width && current.chars().count() > 1 {
+                        current.pop();
+                        lines.push(std::mem::replace(&mut current, c.to_string()));
+                    }
+                }
+            }
+            lines.push(current);
+        }
+        lines
+    }
+
+    /// 스타일 텍스트 → RGBA 비트맵 (빈 텍스트면 None)
+    /// max_width: 줄바꿈 기준 폭 (px, None이면 명시적 줄바꿈만)
+    pub fn render(&self, text: &str, style: &SubtitleStyle, max_width: Option<f32>) -> Option<TextBitmap> {
+        if text.trim().is_empty() || style.font_size <= 0.0 {
+            return None;
+        }
+        let scale = PxScale::from(style.font_size);
+        let primary = self.fonts[0].as_scaled(scale);
+        let line_height = primary.ascent() - primary.descent() + primary.line_gap();
+        let lines = self.wrap(text, scale, max_width);
+
+        let widths: Vec<f32> = lines.iter().map(|l| self.line_width(l, scale)).collect();
+        let text_width = widths.iter().cloned().fold(0.0f32, f32::max).ceil() as u32;
+        let text_height = (line_height * lines.len() as f32).ceil() as u32;
+        let padding = style.outline_width.max(0.0).ceil() as u32 + 1;
+        let width = text_width + padding * 2;
+        let height = text_height + padding * 2;
+
+        // 글리프 커버리지 (0.0~1.0)
+        let mut coverage = vec![0.0f32; (width * height) as usize];
+        for (i, (line, line_width)) in lines.iter().zip(&widths).enumerate() {
+            let mut x = padding as f32 + match horizontal_align(style.alignment) {
+                HorizontalAlign::Left => 0.0,
+                HorizontalAlign::Center => (text_width as f32 - line_width) / 2.0,
+                HorizontalAlign::Right => text_width as f32 - line_width,
+            };
+            let baseline = padding as f32 + line_height * i as f32 + primary.ascent();
+
+            let mut prev: Option<(usize, GlyphId)> = None;
+            for c in line.chars() {
+                let (font_index, id) = self.font_for(c);
+                let font = &self.fonts[font_index];
+                let scaled = font.as_scaled(scale);
+                if let Some((prev_index, prev_id)) = prev {
+                    if prev_index == font_index {
+                        x += scaled.kern(prev_id, id);
+                    }
+                }
+                let glyph = id.with_scale_and_position(scale, point(x, baseline));
+                if let Some(outlined) = font.outline_glyph(glyph) {
+                    let bounds = outlined.px_bounds();
+                    outlined.draw(|gx, gy, c| {
+                        let px = bounds.min.x as i32 + gx as i32;
+                        let py = bounds.min.y as i32 + gy as i32;
+                        if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                            let idx = (py as u32 * width + px as u32) as usize;
+                            coverage[idx] = coverage[idx].max(c.min(1.0));
+                        }
+                    });
+                }
+                x += scaled.h_advance(id);
+                prev = Some((font_index, id));
+            }
+        }
+
+        let outline = if style.outline_width > 0.0 {
+            dilate(&coverage, width, height, style.outline_width)
+        } else {
+            vec![0.0; coverage.len()]
+        };
+
+        // 외곽선 위에 글자 합성 (straight alpha "over")
+        let mut rgba = vec![0u8; (width * height * 4) as usize];
+        let fill_a = style.color[3] as f32 / 255.0;
+        let line_a = style.outline_color[3] as f32 / 255.0;
+        for (i, px) in rgba.chunks_exact_mut(4).enumerate() {
+            let fa = coverage[i] * fill_a;
+            let oa = outline[i] * line_a * (1.0 - fa);
+            let a = fa + oa;
+            if a <= 0.0 {
+                continue;
+            }
+            for ((out, fill), line) in px[..3].iter_mut().zip(&style.color).zip(&style.outline_color) {
+                *out = ((*fill as f32 * fa + *line as f32 * oa) / a).round() as u8;
+            }
+            px[3] = (a * 255.0).round() as u8;
+        }
+
+        Some(TextBitmap { width, height, rgba, padding })
+    }
+
+    /// 스타일 텍스트 → 프레임 위치가 정해진 자막 오버레이 (배치/여백 적용, 프레임 폭 - 좌우 여백에서 줄바꿈)
+    pub fn render_overlay(
+        &self,
+        text: &str,
+        style: &SubtitleStyle,
+        start_ms: i64,
+        end_ms: i64,
+        frame_width: u32,
+        frame_height: u32,
+    ) -> Option<SubtitleOverlay> {
+        let max_width = frame_width as f32 - style.margin_h as f32 * 2.0;
+        let bitmap = self.render(text, style, Some(max_width))?;
+        let (x, y) = overlay_position(&bitmap, style, frame_width, frame_height);
+        Some(SubtitleOverlay {
+            start_ms,
+            end_ms,
+            x,
+            y,
+            width: bitmap.width,
+            height: bitmap.height,
+            rgba_data: bitmap.rgba,
+            z_index: 0,
+        })
+    }
+}
+
+/// 폰트 데이터 로드 (.ttc는 첫 번째 폰트)
+fn load_font(data: Vec<u8>) -> Result<FontArc, String> {
+    FontVec::try_from_vec_and_index(data, 0)
+        .map(FontArc::new)
+        .map_err(|e| format!("폰트 로드 실패: {}", e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HorizontalAlign {
+    Left,
+    Center,
+    Right,
+}
+
+fn horizontal_align(alignment: SubtitleAlignment) -> HorizontalAlign {
+    match alignment {
+        SubtitleAlignment::BottomLeft | SubtitleAlignment::MiddleLeft | SubtitleAlignment::TopLeft => HorizontalAlign::Left,
+        SubtitleAlignment::BottomRight | SubtitleAlignment::MiddleRight | SubtitleAlignment::TopRight => HorizontalAlign::Right,
+        _ => HorizontalAlign::Center,
+    }
+}
+
+/// 비트맵 좌상단 위치 (텍스트 영역이 여백에 맞도록 padding 보정)
+fn overlay_position(bitmap: &TextBitmap, style: &SubtitleStyle, frame_width: u32, frame_height: u32) -> (i32, i32) {
+    let (fw, fh) = (frame_width as i32, frame_height as i32);
+    let (bw, bh, pad) = (bitmap.width as i32, bitmap.height as i32, bitmap.padding as i32);
+
+    let x = match horizontal_align(style.alignment) {
+        HorizontalAlign::Left => style.margin_h - pad,
+        HorizontalAlign::Center => (fw - bw) / 2,
+        HorizontalAlign::Right => fw - bw - style.margin_h + pad,
+    };
+    let y = match style.alignment {
+        SubtitleAlignment::TopLeft | SubtitleAlignment::TopCenter | SubtitleAlignment::TopRight => style.margin_v - pad,
+        SubtitleAlignment::MiddleLeft | SubtitleAlignment::MiddleCenter | SubtitleAlignment::MiddleRight => (fh - bh) / 2,
+        _ => fh - bh - style.margin_v + pad,
+    };
+    (x, y)
+}
+
+/// 커버리지 팽창 (반지름 radius 원형 max 필터 — 외곽선 마스크)
+fn dilate(coverage: &[f32], width: u32, height: u32, radius: f32) -> Vec<f32> {
+    let r = radius.ceil() as i32;
+    let offsets: Vec<(i32, i32)> = (-r..=r)
+        .flat_map(|dy| (-r..=r).map(move |dx| (dx, dy)))
+        .filter(|(dx, dy)| (dx * dx + dy * dy) as f32 <= radius * radius + 0.5)
+        .collect();
+
+    let (w, h) = (width as i32, height as i32);
+    let mut out = vec![0.0f32; coverage.len()];
+    for y in 0..h {
+        for x in 0..w {
+            let mut max = 0.0f32;
+            for (dx, dy) in &offsets {
+                let (sx, sy) = (x + dx, y + dy);
+                if sx >= 0 && sy >= 0 && sx < w && sy < h {
+                    max = max.max(coverage[(sy * w + sx) as usize]);
+                    if max >= 1.0 {
+                        break;
+                    }
+                }
+            }
+            out[(y * w + x) as usize] = max;
+        }
+    }
+    out
+}
+
+/// 자막 큐 목록 → 오버레이 (큐 스타일 없으면 default_style, 큐 layer → z_index)
+pub fn rasterize_cues(
+    renderer: &TextRenderer,
+    cues: &[SubtitleCue],
+    default_style: &SubtitleStyle,
+    frame_width: u32,
+    frame_height: u32,
+) -> Vec<SubtitleOverlay> {
+    cues.iter()
+        .filter_map(|cue| {
+            let style = cue.style.as_ref().unwrap_or(default_style);
+            let mut overlay = renderer.render_overlay(&cue.text, style, cue.start_ms, cue.end_ms, frame_width, frame_height)?;
+            overlay.z_index = cue.layer;
+            Some(overlay)
+        })
+        .collect()
+}
+
+/// 공유 텍스트 렌더러 (폰트 경로별 캐시, None = 시스템 기본 폰트)
+/// 폰트 파일(한글 폰트는 수 MB)을 호출마다 다시 읽지 않도록 FFI에서 사용
+pub fn shared_renderer(font_path: Option<&Path>) -> Result<Arc<TextRenderer>, String> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Arc<TextRenderer>>>> = OnceLock::new();
+    let key = font_path.map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+
+    let mut cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(renderer) = cache.get(&key) {
+        return Ok(renderer.clone());
+    }
+    let renderer = Arc::new(match font_path {
+        Some(path) => TextRenderer::from_file(path)?,
+        None => TextRenderer::system_default()?,
+    });
+    cache.insert(key, renderer.clone());
+    Ok(renderer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dilate() {
+        // 5x5 중앙 1픽셀 → 반지름 1 십자형
+        let mut coverage = vec![0.0; 25];
+        coverage[12] = 1.0;
+        let out = dilate(&coverage, 5, 5, 1.0);
+        assert_eq!(out[12], 1.0);
+        assert_eq!(out[7], 1.0);
+        assert_eq!(out[11], 1.0);
+        assert_eq!(out[6], 0.0); // 대각선은 반지름 밖
+        assert_eq!(out[0], 0.0);
+    }
+
+    #[test]
+    fn test_overlay_position() {
+        let bitmap = TextBitmap { width: 200, height: 60, rgba: Vec::new(), padding: 3 };
+        let style = SubtitleStyle::default(); // 하단 중앙, 여백 40
+        assert_eq!(overlay_position(&bitmap, &style, 1920, 1080), (860, 1080 - 60 - 40 + 3));
+
+        let style = SubtitleStyle { alignment: SubtitleAlignment::TopLeft, ..SubtitleStyle::default() };
+        assert_eq!(overlay_position(&bitmap, &style, 1920, 1080), (37, 37));
+    }
+
+    #[test]
+    fn test_render_text_with_system_font() {
+        // 시스템 폰트가 없는 환경(CI 컨테이너 등)은 건너뜀
+        let renderer = match TextRenderer::system_default() {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        let style = SubtitleStyle::default();
+        assert!(renderer.render("   ", &style, None).is_none());
+
+        let one = renderer.render("Hello", &style, None).unwrap();
+        let two = renderer.render("Hello\nworld", &style, None).unwrap();
+        assert_eq!(one.rgba.len(), (one.width * one.height * 4) as usize);
+        assert!(two.height > one.height);
+        // 글자(흰색 불투명) + 외곽선(검정) 픽셀 존재
+        assert!(one.rgba.chunks_exact(4).any(|p| p == [255, 255, 255, 255]));
+        assert!(one.rgba.chunks_exact(4).any(|p| p[3] == 255 && p[0] == 0));
+
+        // 폭 제한 → 줄바꿈
+        let wrapped = renderer.render("one two three four", &style, Some(one.width as f32)).unwrap();
+        assert!(wrapped.height > one.height);
+        assert!(wrapped.width <= one.width + one.padding * 2);
+
+        let overlay = renderer.render_overlay("Hi", &style, 0, 1000, 1920, 1080).unwrap();
+        assert!(overlay.y > 900 && overlay.x > 800);
+    }
+}
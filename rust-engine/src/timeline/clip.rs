@@ -1,6 +1,11 @@
 // 클립 모듈 - 타임라인에 배치되는 미디어 세그먼트
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use crate::timeline::keyframes::{ClipKeyframes, KeyframeProperty, KeyframeTrack};
+use crate::timeline::title::TitleClip;
+use crate::timeline::color::ColorClip;
+use crate::rendering::effects::EffectParams;
+use crate::audio::effects::AudioEffectParams;
 
 /// 클립 타입
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,6 +13,141 @@ pub enum ClipType {
     Video,
     Audio,
     Image,
+    Title,
+    Color,
+}
+
+/// 정지 이미지로 취급하는 확장자 (FFmpeg image2 디먹서로 디코딩)
+const IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "bmp"];
+
+impl ClipType {
+    /// 파일 확장자로 비디오 트랙 클립 타입 판별 (이미지 확장자 외에는 Video)
+    pub fn from_path(path: &std::path::Path) -> Self {
+        let is_image = path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| IMAGE_EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)));
+        if is_image { ClipType::Image } else { ClipType::Video }
+    }
+}
+
+/// 소스 키 구분자 (파일 경로 + 스트림 번호, 파일 이름에 쓰이지 않는 문자)
+const STREAM_KEY_SEPARATOR: char = '\u{1f}';
+
+/// 디코딩 소스 식별 키 (캐시/디코더 공유 단위)
+/// 기본 스트림은 파일 경로 그대로, 스트림 지정 시 "경로<구분자><컨테이너 스트림 번호>"
+pub fn source_key(file_path: &Path, stream_index: Option<usize>) -> String {
+    match stream_index {
+        Some(index) => format!("{}{}{}", file_path.to_string_lossy(), STREAM_KEY_SEPARATOR, index),
+        None => file_path.to_string_lossy().to_string(),
+    }
+}
+
+/// 소스 키가 해당 파일의 것인지 (기본 스트림 + 지정 스트림 모두)
+pub fn is_source_of(key: &str, file_path: &str) -> bool {
+    key.strip_prefix(file_path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(STREAM_KEY_SEPARATOR))
+}
+
+/// 클립 재생 속도 범위 (1.0 = 원속, 0.5 = 2배 슬로우, 2.0 = 2배속)
+pub const MIN_CLIP_SPEED: f64 = 0.1;
+pub const MAX_CLIP_SPEED: f64 = 16.0;
+
+/// 유효한 재생 속도인지
+pub fn is_valid_speed(speed: f64) -> bool {
+    speed.is_finite() && (MIN_CLIP_SPEED..=MAX_CLIP_SPEED).contains(&speed)
+}
+
+/// 소스 길이를 넘어선 구간의 처리 방식 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndOfMediaBehavior {
+    Freeze = 0, // 클립 소스의 마지막 프레임 유지
+    Black = 1,  // 검은 프레임
+    Loop = 2,   // 트림 시작 지점부터 반복
+}
+
+impl EndOfMediaBehavior {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => EndOfMediaBehavior::Black,
+            2 => EndOfMediaBehavior::Loop,
+            _ => EndOfMediaBehavior::Freeze,
+        }
+    }
+}
+
+/// 소스와 출력의 종횡비가 다를 때 배치 방식 (FFI u32 매핑, 트랜스폼 적용 전 단계)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitMode {
+    Fit = 0,     // 전체가 보이도록 축소 (레터박스/필러박스, 남는 영역은 투명 → 아래 트랙/배경)
+    Fill = 1,    // 출력을 꽉 채움 (넘치는 부분 중앙 크롭)
+    Stretch = 2, // 출력 크기로 늘림 (비율 무시)
+    Custom = 3,  // 원본 픽셀 크기 그대로 중앙 배치 (프로젝트 해상도 기준, 크기/위치는 트랜스폼으로 조정)
+}
+
+impl FitMode {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => FitMode::Fill,
+            2 => FitMode::Stretch,
+            3 => FitMode::Custom,
+            _ => FitMode::Fit,
+        }
+    }
+}
+
+/// 크롭 영역 (원본 각 가장자리에서 잘라낼 비율, 0.0~1.0)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CropRect {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl CropRect {
+    pub const NONE: CropRect = CropRect { left: 0.0, top: 0.0, right: 0.0, bottom: 0.0 };
+
+    /// 유효한 크롭인지 (각 값 0~1, 남는 영역이 있어야 함)
+    pub fn is_valid(&self) -> bool {
+        let values = [self.left, self.top, self.right, self.bottom];
+        values.iter().all(|v| v.is_finite() && (0.0..1.0).contains(v))
+            && self.left + self.right < 1.0
+            && self.top + self.bottom < 1.0
+    }
+}
+
+/// 클립 트랜스폼 (PIP/모션 레이아웃)
+/// 출력 프레임 기준: 원본은 출력 전체를 채운 상태에서 크롭 → 스케일/회전 → 이동
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipTransform {
+    pub offset_x: f32,     // 가로 이동 (출력 폭 비율, 0.5 = 오른쪽으로 절반)
+    pub offset_y: f32,     // 세로 이동 (출력 높이 비율, 아래 방향 +)
+    pub scale: f32,        // 배율 (1.0 = 출력 전체)
+    pub rotation_deg: f32, // 회전 (도, 시계 방향 +)
+    pub opacity: f32,      // 불투명도 (0.0~1.0)
+    pub crop: CropRect,
+}
+
+impl Default for ClipTransform {
+    fn default() -> Self {
+        Self {
+            offset_x: 0.0,
+            offset_y: 0.0,
+            scale: 1.0,
+            rotation_deg: 0.0,
+            opacity: 1.0,
+            crop: CropRect::NONE,
+        }
+    }
+}
+
+impl ClipTransform {
+    /// 변환 없음 (출력 전체를 그대로 덮음)
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// 비디오 클립
@@ -15,22 +155,66 @@ pub enum ClipType {
 pub struct VideoClip {
     pub id: u64,
     pub file_path: PathBuf,
+    pub stream_index: Option<usize>, // 사용할 비디오 스트림 (컨테이너 스트림 번호, None = 기본 스트림)
+    pub clip_type: ClipType,   // Video 또는 Image (정지 이미지: 길이 제한 없음)
     pub start_time_ms: i64,    // 타임라인 상 시작 시간
     pub duration_ms: i64,       // 타임라인 상 지속 시간
     pub trim_start_ms: i64,     // 원본 파일에서 트림 시작
     pub trim_end_ms: i64,       // 원본 파일에서 트림 끝
+    pub speed: f64,             // 재생 속도 (원본 길이 = 타임라인 길이 * speed)
+    pub end_behavior: EndOfMediaBehavior, // 소스 끝 이후 처리
+    pub frame_interpolation: bool, // 소스 프레임 사이 시점에 모션 보상 보간 (슬로우 모션)
+    pub lut_path: Option<PathBuf>, // 클립별 3D LUT (.cube), 이펙트와 별도
+    pub effects: EffectParams, // 색보정 이펙트 (프리뷰/Export 공통, 키프레임 값이 우선)
+    pub fit_mode: FitMode, // 종횡비가 다른 소스 배치 (트랜스폼 전에 적용, 타이틀은 무시)
+    pub transform: ClipTransform, // 위치/크기/회전/크롭 (합성 시 적용)
+    pub keyframes: ClipKeyframes, // 트랜스폼/이펙트 애니메이션 (정적 값보다 우선)
+    pub title: Option<TitleClip>, // 타이틀 클립 내용 (clip_type = Title일 때만, file_path 없음)
+    pub color: Option<ColorClip>, // 컬러 클립 내용 (clip_type = Color일 때만, file_path 없음)
+    pub use_source_audio: bool, // 소스 파일의 오디오를 믹서에 포함 (별도 오디오 클립으로 분리했으면 false)
 }
 
 impl VideoClip {
-    /// 새 비디오 클립 생성
+    /// 새 비디오 클립 생성 (이미지 확장자면 Image 클립)
     pub fn new(id: u64, file_path: PathBuf, start_time_ms: i64, duration_ms: i64) -> Self {
         Self {
             id,
+            clip_type: ClipType::from_path(&file_path),
             file_path,
+            stream_index: None,
             start_time_ms,
             duration_ms,
             trim_start_ms: 0,
             trim_end_ms: duration_ms,
+            speed: 1.0,
+            end_behavior: EndOfMediaBehavior::Freeze,
+            frame_interpolation: false,
+            lut_path: None,
+            effects: EffectParams::default(),
+            fit_mode: FitMode::Fit,
+            transform: ClipTransform::default(),
+            keyframes: ClipKeyframes::default(),
+            title: None,
+            color: None,
+            use_source_audio: true,
+        }
+    }
+
+    /// 새 타이틀 클립 생성 (소스 파일 없음, 엔진 텍스트 래스터라이저로 렌더링)
+    pub fn new_title(id: u64, title: TitleClip, start_time_ms: i64, duration_ms: i64) -> Self {
+        Self {
+            clip_type: ClipType::Title,
+            title: Some(title),
+            ..Self::new(id, PathBuf::new(), start_time_ms, duration_ms)
+        }
+    }
+
+    /// 새 컬러 클립 생성 (소스 파일 없음, 단색/그라디언트로 렌더링)
+    pub fn new_color(id: u64, color: ColorClip, start_time_ms: i64, duration_ms: i64) -> Self {
+        Self {
+            clip_type: ClipType::Color,
+            color: Some(color),
+            ..Self::new(id, PathBuf::new(), start_time_ms, duration_ms)
         }
     }
 
@@ -39,6 +223,41 @@ impl VideoClip {
         self.start_time_ms + self.duration_ms
     }
 
+    /// 타임라인 길이 → 원본 길이 (속도 반영)
+    pub fn source_offset(&self, timeline_offset_ms: i64) -> i64 {
+        (timeline_offset_ms as f64 * self.speed).round() as i64
+    }
+
+    /// 원본 길이 → 타임라인 길이 (속도 반영)
+    pub fn timeline_offset(&self, source_offset_ms: i64) -> i64 {
+        (source_offset_ms as f64 / self.speed).round() as i64
+    }
+
+    /// 정지 이미지 클립 여부 (소스 시간 무관하게 같은 프레임)
+    pub fn is_image(&self) -> bool {
+        self.clip_type == ClipType::Image
+    }
+
+    /// 타이틀 클립 여부 (디코딩할 소스 파일 없음)
+    pub fn is_title(&self) -> bool {
+        self.clip_type == ClipType::Title
+    }
+
+    /// 컬러 클립 여부 (디코딩할 소스 파일 없음)
+    pub fn is_color(&self) -> bool {
+        self.clip_type == ClipType::Color
+    }
+
+    /// 엔진이 생성하는 클립 여부 (타이틀/컬러 — 소스 파일/소스 오디오 없음)
+    pub fn is_generated(&self) -> bool {
+        self.is_title() || self.is_color()
+    }
+
+    /// 디코딩 소스 키 (파일 + 비디오 스트림)
+    pub fn source_key(&self) -> String {
+        source_key(&self.file_path, self.stream_index)
+    }
+
     /// 클립이 특정 시간을 포함하는지 확인
     pub fn contains_time(&self, time_ms: i64) -> bool {
         time_ms >= self.start_time_ms && time_ms < self.end_time_ms()
@@ -51,10 +270,70 @@ impl VideoClip {
         }
 
         let offset = timeline_time_ms - self.start_time_ms;
-        Some(self.trim_start_ms + offset)
+        Some(self.trim_start_ms + self.source_offset(offset))
+    }
+
+    /// 원본 파일 시간을 타임라인 시간으로 변환 (클립 범위 검사 없음)
+    pub fn source_to_timeline_time(&self, source_time_ms: i64) -> i64 {
+        self.start_time_ms + self.timeline_offset(source_time_ms - self.trim_start_ms)
+    }
+
+    /// 클립 범위 밖까지 연장한 원본 시간 (트랜지션 핸들, 파일 시작 이전은 0으로 제한)
+    pub fn source_time_with_handles(&self, timeline_time_ms: i64) -> i64 {
+        (self.trim_start_ms + self.source_offset(timeline_time_ms - self.start_time_ms)).max(0)
+    }
+
+    /// 타임라인 시간의 트랜스폼 (키프레임이 있는 속성은 보간 값, 없으면 정적 값)
+    /// 타이틀 클립은 인/아웃 애니메이션이 그 위에 더해짐
+    pub fn transform_at(&self, timeline_time_ms: i64) -> ClipTransform {
+        let mut transform = self.transform;
+        let t = timeline_time_ms - self.start_time_ms;
+
+        if !self.keyframes.is_empty() {
+            let value = |property, fallback: f32| self.keyframes.value_at(property, t).unwrap_or(fallback);
+            transform.offset_x = value(KeyframeProperty::OffsetX, transform.offset_x);
+            transform.offset_y = value(KeyframeProperty::OffsetY, transform.offset_y);
+            // 배율 0 이하는 합성 불가 → 최소값으로 제한
+            transform.scale = value(KeyframeProperty::Scale, transform.scale).max(0.001);
+            transform.rotation_deg = value(KeyframeProperty::Rotation, transform.rotation_deg);
+            transform.opacity = value(KeyframeProperty::Opacity, transform.opacity).clamp(0.0, 1.0);
+        }
+
+        if let Some(title) = &self.title {
+            let motion = title.motion_at(t, self.duration_ms);
+            transform.opacity *= motion.opacity;
+            transform.offset_x += motion.offset_x;
+            transform.offset_y += motion.offset_y;
+            transform.scale = (transform.scale * motion.scale).max(0.001);
+        }
+        transform
+    }
+
+    /// 소스 실제 길이를 넘어선 원본 시간에 end_behavior 적용
+    /// - media_duration_ms <= 0 (길이 불명): 그대로 반환
+    /// - None: 검은 프레임으로 렌더링
+    pub fn apply_end_behavior(&self, source_time_ms: i64, media_duration_ms: i64, frame_duration_ms: i64) -> Option<i64> {
+        if media_duration_ms <= 0 || source_time_ms < media_duration_ms {
+            return Some(source_time_ms);
+        }
+
+        match self.end_behavior {
+            // 마지막 실제 프레임 위치로 고정 (디코더가 마지막에 낸 다른 위치 프레임이 아님)
+            EndOfMediaBehavior::Freeze => Some((media_duration_ms - frame_duration_ms.max(1)).max(0)),
+            EndOfMediaBehavior::Black => None,
+            EndOfMediaBehavior::Loop => {
+                // 트림 시작이 소스 안에 있으면 그 지점부터, 아니면 파일 처음부터 반복
+                let loop_start = if self.trim_start_ms < media_duration_ms { self.trim_start_ms.max(0) } else { 0 };
+                let period = media_duration_ms - loop_start;
+                Some(loop_start + (source_time_ms - loop_start).rem_euclid(period))
+            }
+        }
     }
 }
 
+/// 오디오 클립 볼륨 최대값 (2.0 ≈ +6dB)
+pub const MAX_CLIP_VOLUME: f32 = 2.0;
+
 /// 오디오 클립
 #[derive(Debug, Clone)]
 pub struct AudioClip {
@@ -64,7 +343,16 @@ pub struct AudioClip {
     pub duration_ms: i64,
     pub trim_start_ms: i64,
     pub trim_end_ms: i64,
-    pub volume: f32,  // 0.0 ~ 1.0
+    pub speed: f64,   // 재생 속도 (preserve_pitch가 false면 리샘플링, 피치 함께 변경)
+    pub preserve_pitch: bool, // 속도 변경 시 피치 유지 (WSOLA 시간 늘이기)
+    pub volume: f32,  // 0.0 ~ MAX_CLIP_VOLUME (1.0 = 원본)
+    pub fade_in_ms: i64,   // 시작 페이드 길이 (0 = 없음)
+    pub fade_out_ms: i64,  // 끝 페이드 길이 (0 = 없음)
+    pub stream_index: Option<usize>, // 사용할 오디오 스트림 (컨테이너 스트림 번호, None = 기본 스트림)
+    /// 볼륨 엔벌로프 (클립 시작 기준 시간, 값 = 게인 배율, volume과 곱해짐)
+    pub volume_envelope: KeyframeTrack,
+    /// 클립 이펙트 체인 (볼륨/페이드 전 원본 신호에 적용)
+    pub effects: AudioEffectParams,
 }
 
 impl AudioClip {
@@ -77,7 +365,14 @@ impl AudioClip {
             duration_ms,
             trim_start_ms: 0,
             trim_end_ms: duration_ms,
+            speed: 1.0,
+            preserve_pitch: false,
             volume: 1.0,
+            fade_in_ms: 0,
+            fade_out_ms: 0,
+            stream_index: None,
+            volume_envelope: KeyframeTrack::default(),
+            effects: AudioEffectParams::default(),
         }
     }
 
@@ -86,15 +381,80 @@ impl AudioClip {
         self.start_time_ms + self.duration_ms
     }
 
+    /// 디코딩 소스 키 (파일 + 오디오 스트림)
+    pub fn source_key(&self) -> String {
+        source_key(&self.file_path, self.stream_index)
+    }
+
     /// 클립이 특정 시간을 포함하는지 확인
     pub fn contains_time(&self, time_ms: i64) -> bool {
         time_ms >= self.start_time_ms && time_ms < self.end_time_ms()
     }
+
+    /// 타임라인 길이 → 원본 길이 (속도 반영)
+    pub fn source_offset(&self, timeline_offset_ms: i64) -> i64 {
+        (timeline_offset_ms as f64 * self.speed).round() as i64
+    }
+
+    /// 타임라인 시간을 원본 파일 시간으로 변환 (VideoClip과 동일, 클립 밖이면 None)
+    pub fn timeline_to_source_time(&self, timeline_time_ms: i64) -> Option<i64> {
+        if !self.contains_time(timeline_time_ms) {
+            return None;
+        }
+
+        let offset = timeline_time_ms - self.start_time_ms;
+        Some(self.trim_start_ms + self.source_offset(offset))
+    }
+
+    /// 타임라인 구간 [start, end)를 클립 범위로 자른 원본 구간 (파형 슬라이스용, 겹침 없으면 None)
+    pub fn source_range(&self, timeline_start_ms: i64, timeline_end_ms: i64) -> Option<(i64, i64)> {
+        let start = timeline_start_ms.max(self.start_time_ms);
+        let end = timeline_end_ms.min(self.end_time_ms());
+        if start >= end {
+            return None;
+        }
+        let source_start = self.trim_start_ms + self.source_offset(start - self.start_time_ms);
+        let source_end = self.trim_start_ms + self.source_offset(end - self.start_time_ms);
+        Some((source_start, source_end))
+    }
+
+    /// 타임라인 시간(ms, 소수 허용)의 페이드 게인 (등전력 곡선, 페이드 길이는 클립 길이로 제한)
+    pub fn fade_gain(&self, time_ms: f64) -> f32 {
+        let mut gain = 1.0;
+        if self.fade_in_ms > 0 {
+            let length = self.fade_in_ms.min(self.duration_ms).max(1) as f64;
+            gain *= equal_power((time_ms - self.start_time_ms as f64) / length);
+        }
+        if self.fade_out_ms > 0 {
+            let length = self.fade_out_ms.min(self.duration_ms).max(1) as f64;
+            gain *= equal_power((self.end_time_ms() as f64 - time_ms) / length);
+        }
+        gain as f32
+    }
+
+    /// 타임라인 시간(ms, 소수 허용)의 엔벌로프 게인 (포인트 없으면 1.0, 음수는 0)
+    pub fn envelope_gain(&self, time_ms: f64) -> f32 {
+        if self.volume_envelope.is_empty() {
+            return 1.0;
+        }
+        // 1ms 단위 값 사이를 선형 보간 (샘플 단위 계단 잡음 방지)
+        let clip_time = time_ms - self.start_time_ms as f64;
+        let floor = clip_time.floor();
+        let a = self.volume_envelope.evaluate(floor as i64).unwrap_or(1.0);
+        let b = self.volume_envelope.evaluate(floor as i64 + 1).unwrap_or(1.0);
+        (a + (b - a) * (clip_time - floor) as f32).max(0.0)
+    }
+}
+
+/// 페이드 진행도(0~1) → 게인 (크로스페이드 시 두 클립의 합산 파워 유지)
+fn equal_power(progress: f64) -> f64 {
+    (progress.clamp(0.0, 1.0) * std::f64::consts::FRAC_PI_2).sin()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
 
     #[test]
     fn test_video_clip_creation() {
@@ -130,4 +490,161 @@ mod tests {
         assert_eq!(clip.timeline_to_source_time(1000), None);
         assert_eq!(clip.timeline_to_source_time(6000), None);
     }
+
+    #[test]
+    fn test_audio_clip_fade_gain() {
+        let mut clip = AudioClip::new(1, PathBuf::from("music.mp3"), 1000, 4000);
+        assert_eq!(clip.fade_gain(1000.0), 1.0);
+
+        clip.fade_in_ms = 1000;
+        clip.fade_out_ms = 2000;
+        assert_eq!(clip.fade_gain(1000.0), 0.0);
+        assert!((clip.fade_gain(1500.0) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert_eq!(clip.fade_gain(2500.0), 1.0);
+        assert!((clip.fade_gain(4000.0) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert_eq!(clip.fade_gain(5000.0), 0.0);
+
+        // 클립보다 긴 페이드는 클립 길이로 제한
+        clip.fade_out_ms = 0;
+        clip.fade_in_ms = 10_000;
+        assert_eq!(clip.fade_gain(5000.0), 1.0);
+    }
+
+    #[test]
+    fn test_audio_clip_envelope_gain() {
+        use crate::timeline::keyframes::{Interpolation, Keyframe};
+
+        let mut clip = AudioClip::new(1, PathBuf::from("music.mp3"), 1000, 4000);
+        assert_eq!(clip.envelope_gain(2000.0), 1.0);
+
+        // 클립 시작 기준 1000ms~2000ms 동안 1.0 → 0.2 (더킹)
+        clip.volume_envelope.set(Keyframe::new(1000, 1.0, Interpolation::Linear));
+        clip.volume_envelope.set(Keyframe::new(2000, 0.2, Interpolation::Linear));
+        assert_eq!(clip.envelope_gain(1500.0), 1.0);
+        assert!((clip.envelope_gain(2500.0) - 0.6).abs() < 1e-6);
+        assert!((clip.envelope_gain(2500.5) - 0.5996).abs() < 1e-5);
+        assert!((clip.envelope_gain(4500.0) - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_source_key() {
+        let mut clip = VideoClip::new(1, PathBuf::from("multicam.mkv"), 0, 1000);
+        assert_eq!(clip.source_key(), "multicam.mkv");
+        clip.stream_index = Some(2);
+        let key = clip.source_key();
+        assert_ne!(key, "multicam.mkv");
+        assert!(is_source_of(&key, "multicam.mkv") && is_source_of("multicam.mkv", "multicam.mkv"));
+        assert!(!is_source_of("multicam.mkv.bak", "multicam.mkv"));
+
+        // 다국어 오디오: 같은 파일의 다른 스트림은 다른 키
+        let mut dub = AudioClip::new(2, PathBuf::from("movie.mkv"), 0, 1000);
+        assert_eq!(dub.source_key(), "movie.mkv");
+        dub.stream_index = Some(2);
+        assert_ne!(dub.source_key(), AudioClip::new(3, PathBuf::from("movie.mkv"), 0, 1000).source_key());
+        assert!(is_source_of(&dub.source_key(), "movie.mkv"));
+    }
+
+    #[test]
+    fn test_clip_type_from_path() {
+        assert_eq!(ClipType::from_path(Path::new("still.PNG")), ClipType::Image);
+        assert_eq!(ClipType::from_path(Path::new("photo.jpeg")), ClipType::Image);
+        assert_eq!(ClipType::from_path(Path::new("movie.mp4")), ClipType::Video);
+        assert_eq!(ClipType::from_path(Path::new("no_extension")), ClipType::Video);
+
+        let clip = VideoClip::new(1, PathBuf::from("logo.bmp"), 0, 60000);
+        assert!(clip.is_image());
+    }
+
+    #[test]
+    fn test_audio_clip_trim_mapping() {
+        let mut clip = AudioClip::new(1, PathBuf::from("a.wav"), 2000, 3000);
+        clip.trim_start_ms = 1500;
+
+        // 타임라인 2000ms = 원본 1500ms, 클립 밖은 None
+        assert_eq!(clip.timeline_to_source_time(2000), Some(1500));
+        assert_eq!(clip.timeline_to_source_time(4999), Some(4499));
+        assert_eq!(clip.timeline_to_source_time(1999), None);
+        assert_eq!(clip.timeline_to_source_time(5000), None);
+
+        // 파형 슬라이스: 클립 범위로 잘라 원본 구간으로
+        assert_eq!(clip.source_range(0, 10000), Some((1500, 4500)));
+        assert_eq!(clip.source_range(3000, 3500), Some((2500, 3000)));
+        assert_eq!(clip.source_range(5000, 6000), None);
+
+        // 2배속: 타임라인 1초 = 원본 2초
+        clip.speed = 2.0;
+        assert_eq!(clip.timeline_to_source_time(3000), Some(3500));
+        assert_eq!(clip.source_range(2000, 2500), Some((1500, 2500)));
+    }
+
+    #[test]
+    fn test_clip_speed_mapping() {
+        let mut clip = VideoClip::new(1, PathBuf::from("test.mp4"), 1000, 2000);
+        clip.trim_start_ms = 500;
+        clip.speed = 2.0;
+
+        // 2배속: 타임라인 1초 = 원본 2초 (프레임 건너뜀)
+        assert_eq!(clip.timeline_to_source_time(2000), Some(2500));
+        assert_eq!(clip.source_to_timeline_time(2500), 2000);
+
+        // 0.5배속: 같은 원본 프레임이 두 번씩 (프레임 반복)
+        clip.speed = 0.5;
+        assert_eq!(clip.timeline_to_source_time(1066), Some(533));
+        assert_eq!(clip.timeline_to_source_time(1033), Some(517));
+        assert!(is_valid_speed(0.5) && !is_valid_speed(0.0) && !is_valid_speed(f64::NAN));
+    }
+
+    #[test]
+    fn test_apply_end_behavior() {
+        let mut clip = VideoClip::new(1, PathBuf::from("test.mp4"), 0, 10000);
+        clip.trim_start_ms = 1000;
+
+        // 소스 범위 안: 그대로
+        assert_eq!(clip.apply_end_behavior(3000, 4000, 33), Some(3000));
+        // 길이 불명: 그대로
+        assert_eq!(clip.apply_end_behavior(9000, 0, 33), Some(9000));
+
+        // Freeze: 마지막 프레임 위치
+        assert_eq!(clip.apply_end_behavior(5000, 4000, 33), Some(3967));
+
+        clip.end_behavior = EndOfMediaBehavior::Black;
+        assert_eq!(clip.apply_end_behavior(5000, 4000, 33), None);
+
+        // Loop: [1000, 4000) 구간 반복
+        clip.end_behavior = EndOfMediaBehavior::Loop;
+        assert_eq!(clip.apply_end_behavior(4000, 4000, 33), Some(1000));
+        assert_eq!(clip.apply_end_behavior(5500, 4000, 33), Some(2500));
+        assert_eq!(clip.apply_end_behavior(7000, 4000, 33), Some(1000));
+    }
+
+    #[test]
+    fn test_crop_rect_validation() {
+        assert!(CropRect::NONE.is_valid());
+        assert!(CropRect { left: 0.25, top: 0.1, right: 0.25, bottom: 0.1 }.is_valid());
+        assert!(!CropRect { left: 0.5, top: 0.0, right: 0.5, bottom: 0.0 }.is_valid());
+        assert!(!CropRect { left: -0.1, top: 0.0, right: 0.0, bottom: 0.0 }.is_valid());
+
+        let mut transform = ClipTransform::default();
+        assert!(transform.is_identity());
+        transform.scale = 0.5;
+        assert!(!transform.is_identity());
+    }
+
+    #[test]
+    fn test_transform_at_keyframes() {
+        use crate::timeline::keyframes::{Interpolation, Keyframe};
+
+        let mut clip = VideoClip::new(1, PathBuf::from("test.mp4"), 1000, 2000);
+        clip.transform.rotation_deg = 45.0;
+        assert_eq!(clip.transform_at(1500), clip.transform);
+
+        // 클립 기준 0ms → 1000ms 동안 scale 1.0 → 0.5
+        clip.keyframes.set(KeyframeProperty::Scale, Keyframe::new(0, 1.0, Interpolation::Linear));
+        clip.keyframes.set(KeyframeProperty::Scale, Keyframe::new(1000, 0.5, Interpolation::Linear));
+        let transform = clip.transform_at(1500);
+        assert!((transform.scale - 0.75).abs() < 1e-6);
+        // 키프레임 없는 속성은 정적 값 유지
+        assert_eq!(transform.rotation_deg, 45.0);
+        assert_eq!(clip.transform_at(2500).scale, 0.5);
+    }
 }
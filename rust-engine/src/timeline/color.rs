@@ -0,0 +1,100 @@
+// 컬러 클립 - 단색(RGBA) 또는 2색 선형 그라디언트 레이어 (배경 매트, 페이드용 단색 등)
+// 비디오 트랙의 VideoClip(clip_type = Color)에 내용으로 붙음 → 편집/트랜스폼/키프레임/트랜지션은 일반 클립과 동일
+// 타임라인 배경(Timeline::background, 항상 불투명)으로도 사용 — 클립이 없는 구간/레터박스 영역
+
+/// 컬러 클립 내용
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorClip {
+    /// 단색 또는 그라디언트 시작 색 (RGBA, straight alpha)
+    pub color: [u8; 4],
+    /// Some이면 선형 그라디언트 끝 색
+    pub end_color: Option<[u8; 4]>,
+    /// 그라디언트 방향 (도, 0 = 왼쪽 → 오른쪽, 90 = 위 → 아래)
+    pub angle_deg: f32,
+}
+
+impl ColorClip {
+    pub fn solid(color: [u8; 4]) -> Self {
+        Self { color, end_color: None, angle_deg: 0.0 }
+    }
+
+    pub fn gradient(start_color: [u8; 4], end_color: [u8; 4], angle_deg: f32) -> Self {
+        Self { color: start_color, end_color: Some(end_color), angle_deg }
+    }
+
+    /// 모든 픽셀이 불투명한지 (아래 레이어를 완전히 가림)
+    pub fn is_opaque(&self) -> bool {
+        self.color[3] == 255 && self.end_color.is_none_or(|c| c[3] == 255)
+    }
+
+    /// 단색이면 RGB (그라디언트면 None)
+    pub fn solid_rgb(&self) -> Option<[u8; 3]> {
+        let [r, g, b, _] = self.color;
+        self.end_color.is_none_or(|c| c == self.color).then_some([r, g, b])
+    }
+
+    /// 알파를 무시한 불투명 버전
+    pub fn opaque(self) -> Self {
+        let opaque = |[r, g, b, _]: [u8; 4]| [r, g, b, 255];
+        Self { color: opaque(self.color), end_color: self.end_color.map(opaque), ..self }
+    }
+
+    /// width x height RGBA 래스터화
+    /// 그라디언트: 방향 축으로 투영한 위치가 프레임 양 끝에서 정확히 시작/끝 색
+    pub fn render_rgba(&self, width: u32, height: u32) -> Vec<u8> {
+        let (w, h) = (width as usize, height as usize);
+        let end_color = match self.end_color {
+            Some(end_color) if end_color != self.color => end_color,
+            _ => return self.color.repeat(w * h),
+        };
+
+        let (sin, cos) = self.angle_deg.to_radians().sin_cos();
+        // 방향 축 위 프레임 길이의 절반 (모서리가 0/1이 되도록)
+        let half_extent = (cos.abs() * w as f32 + sin.abs() * h as f32) / 2.0;
+        let mut data = Vec::with_capacity(w * h * 4);
+        for y in 0..h {
+            let dy = y as f32 + 0.5 - h as f32 / 2.0;
+            for x in 0..w {
+                let dx = x as f32 + 0.5 - w as f32 / 2.0;
+                let t = if half_extent > 0.0 {
+                    ((dx * cos + dy * sin) / half_extent * 0.5 + 0.5).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                for (&a, &b) in self.color.iter().zip(&end_color) {
+                    data.push((a as f32 + (b as f32 - a as f32) * t).round() as u8);
+                }
+            }
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_solid_and_gradient() {
+        let solid = ColorClip::solid([10, 20, 30, 255]);
+        assert!(solid.is_opaque());
+        assert_eq!(solid.render_rgba(2, 1), vec![10, 20, 30, 255, 10, 20, 30, 255]);
+
+        // 왼쪽 → 오른쪽: 양 끝 픽셀은 거의 시작/끝 색
+        let horizontal = ColorClip::gradient([0, 0, 0, 255], [255, 255, 255, 255], 0.0);
+        let data = horizontal.render_rgba(100, 2);
+        assert!(data[0] < 5);
+        assert!(data[99 * 4] > 250);
+        assert_eq!(&data[..4 * 100], &data[4 * 100..]);
+
+        // 위 → 아래: 같은 행은 같은 색
+        let vertical = ColorClip::gradient([255, 0, 0, 255], [0, 0, 255, 0], 90.0);
+        assert!(!vertical.is_opaque());
+        assert!(vertical.opaque().is_opaque());
+        assert_eq!(vertical.solid_rgb(), None);
+        assert_eq!(solid.solid_rgb(), Some([10, 20, 30]));
+        let data = vertical.render_rgba(2, 100);
+        assert_eq!(&data[..4], &data[4..8]);
+        assert!(data[0] > 250 && data[99 * 8 + 2] > 250);
+    }
+}
@@ -0,0 +1,542 @@
+// 트랙 편집 연산 - 클립 이동/트림 시 같은 트랙 클립과의 겹침 처리
+// 비디오/오디오 클립 공통 (EditableClip 트레이트)
+
+use super::clip::{AudioClip, VideoClip, is_valid_speed};
+
+/// 겹침 처리 정책 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    Reject = 0,    // 겹치면 편집 거부
+    Push = 1,      // 겹치는 클립과 이후 클립을 뒤로 밀기
+    Overwrite = 2, // 겹치는 구간을 덮어쓰기 (이웃 클립 트림/분할/제거)
+}
+
+impl OverlapPolicy {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => OverlapPolicy::Push,
+            2 => OverlapPolicy::Overwrite,
+            _ => OverlapPolicy::Reject,
+        }
+    }
+}
+
+/// 클립 단위 편집
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipEdit {
+    /// 타임라인 시작 위치 이동
+    Move { start_ms: i64 },
+    /// 원본 기준 in/out 지점 변경 (시작 위치는 원본 정렬 유지하도록 이동)
+    Trim { trim_start_ms: i64, trim_end_ms: i64 },
+    /// 재생 속도 변경 (원본 구간 유지 → 타임라인 길이 변경)
+    Speed { speed: f64 },
+}
+
+impl ClipEdit {
+    /// 클립에 적용 (결과 구간이 유효하지 않으면 false, 클립은 변경될 수 있음)
+    pub fn apply<C: EditableClip>(&self, clip: &mut C) -> bool {
+        match *self {
+            ClipEdit::Move { start_ms } => {
+                if start_ms < 0 {
+                    return false;
+                }
+                clip.shift(start_ms - clip.range().0);
+                true
+            }
+            ClipEdit::Trim { trim_start_ms, trim_end_ms } => {
+                if trim_start_ms < 0 || trim_end_ms <= trim_start_ms {
+                    return false;
+                }
+                let speed = clip.speed();
+                let to_timeline = |source_ms: i64| (source_ms as f64 / speed).round() as i64;
+                let new_start = clip.range().0 + to_timeline(trim_start_ms - clip.trim_start_ms());
+                let new_duration = to_timeline(trim_end_ms - trim_start_ms);
+                if new_start < 0 || new_duration <= 0 {
+                    return false;
+                }
+                clip.trim_head(new_start);
+                clip.trim_tail(new_start + new_duration);
+                true
+            }
+            ClipEdit::Speed { speed } => {
+                if !is_valid_speed(speed) {
+                    return false;
+                }
+                let (start, end) = clip.range();
+                let source_length = (end - start) as f64 * clip.speed();
+                let new_duration = (source_length / speed).round() as i64;
+                if new_duration <= 0 {
+                    return false;
+                }
+                clip.set_speed(speed);
+                clip.trim_tail(start + new_duration);
+                true
+            }
+        }
+    }
+}
+
+/// 편집 연산이 다루는 클립 공통 인터페이스
+pub trait EditableClip: Clone {
+    fn clip_id(&self) -> u64;
+    fn set_clip_id(&mut self, id: u64);
+    /// 타임라인 구간 [start, end)
+    fn range(&self) -> (i64, i64);
+    fn trim_start_ms(&self) -> i64;
+    /// 재생 속도 (원본 길이 = 타임라인 길이 * speed)
+    fn speed(&self) -> f64;
+    fn set_speed(&mut self, speed: f64);
+    /// 시작 위치 이동 (길이/트림 유지)
+    fn shift(&mut self, delta_ms: i64);
+    /// 앞부분을 new_start까지 잘라냄 (원본 시간 정렬 유지)
+    fn trim_head(&mut self, new_start_ms: i64);
+    /// 뒷부분을 new_end부터 잘라냄
+    fn trim_tail(&mut self, new_end_ms: i64);
+    /// 배치(위치/길이/트림/속도)만 스냅샷 값으로 되돌림 (Undo/Redo, 나머지 속성은 유지)
+    fn restore_placement(&mut self, snapshot: &Self);
+}
+
+impl EditableClip for VideoClip {
+    fn clip_id(&self) -> u64 {
+        self.id
+    }
+
+    fn set_clip_id(&mut self, id: u64) {
+        self.id = id;
+    }
+
+    fn range(&self) -> (i64, i64) {
+        (self.start_time_ms, self.end_time_ms())
+    }
+
+    fn trim_start_ms(&self) -> i64 {
+        self.trim_start_ms
+    }
+
+    fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    fn set_speed(&mut self, speed: f64) {
+        self.speed = speed;
+    }
+
+    fn shift(&mut self, delta_ms: i64) {
+        self.start_time_ms += delta_ms;
+    }
+
+    fn trim_head(&mut self, new_start_ms: i64) {
+        let delta = new_start_ms - self.start_time_ms;
+        self.start_time_ms = new_start_ms;
+        self.duration_ms -= delta;
+        self.trim_start_ms += self.source_offset(delta);
+        // 키프레임은 클립 시작 기준
+        self.keyframes.shift(-delta);
+    }
+
+    fn trim_tail(&mut self, new_end_ms: i64) {
+        self.duration_ms = new_end_ms - self.start_time_ms;
+        self.trim_end_ms = self.trim_start_ms + self.source_offset(self.duration_ms);
+    }
+
+    fn restore_placement(&mut self, snapshot: &Self) {
+        // 앞부분 트림으로 옮겨진 키프레임도 되돌림
+        let delta = self.timeline_offset(snapshot.trim_start_ms - self.trim_start_ms);
+        self.keyframes.shift(-delta);
+        self.start_time_ms = snapshot.start_time_ms;
+        self.duration_ms = snapshot.duration_ms;
+        self.trim_start_ms = snapshot.trim_start_ms;
+        self.trim_end_ms = snapshot.trim_end_ms;
+        self.speed = snapshot.speed;
+    }
+}
+
+impl EditableClip for AudioClip {
+    fn clip_id(&self) -> u64 {
+        self.id
+    }
+
+    fn set_clip_id(&mut self, id: u64) {
+        self.id = id;
+    }
+
+    fn range(&self) -> (i64, i64) {
+        (self.start_time_ms, self.end_time_ms())
+    }
+
+    fn trim_start_ms(&self) -> i64 {
+        self.trim_start_ms
+    }
+
+    fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    fn set_speed(&mut self, speed: f64) {
+        self.speed = speed;
+    }
+
+    fn shift(&mut self, delta_ms: i64) {
+        self.start_time_ms += delta_ms;
+    }
+
+    fn trim_head(&mut self, new_start_ms: i64) {
+        let delta = new_start_ms - self.start_time_ms;
+        self.start_time_ms = new_start_ms;
+        self.duration_ms -= delta;
+        self.trim_start_ms += self.source_offset(delta);
+        // 볼륨 엔벌로프는 클립 시작 기준
+        self.volume_envelope.shift(-delta);
+    }
+
+    fn trim_tail(&mut self, new_end_ms: i64) {
+        self.duration_ms = new_end_ms - self.start_time_ms;
+        self.trim_end_ms = self.trim_start_ms + self.source_offset(self.duration_ms);
+    }
+
+    fn restore_placement(&mut self, snapshot: &Self) {
+        let delta = ((snapshot.trim_start_ms - self.trim_start_ms) as f64 / self.speed).round() as i64;
+        self.volume_envelope.shift(-delta);
+        self.start_time_ms = snapshot.start_time_ms;
+        self.duration_ms = snapshot.duration_ms;
+        self.trim_start_ms = snapshot.trim_start_ms;
+        self.trim_end_ms = snapshot.trim_end_ms;
+        self.speed = snapshot.speed;
+    }
+}
+
+/// 편집된 클립 구간 [start, end)에 맞춰 나머지 클립 정리
+/// - clips: 편집 대상 클립을 제외한 같은 트랙 클립
+/// - 반환: Reject로 거부되면 None, 아니면 덮어쓰기 분할로 생긴 (원본 ID, 뒷부분 ID) 목록
+pub fn resolve_overlaps<C: EditableClip>(
+    clips: &mut Vec<C>,
+    start_ms: i64,
+    end_ms: i64,
+    policy: OverlapPolicy,
+    next_clip_id: &mut u64,
+) -> Option<Vec<(u64, u64)>> {
+    let overlaps = |clip: &C| {
+        let (s, e) = clip.range();
+        s < end_ms && e > start_ms
+    };
+    let mut splits = Vec::new();
+
+    match policy {
+        OverlapPolicy::Reject => {
+            if clips.iter().any(overlaps) {
+                return None;
+            }
+        }
+        OverlapPolicy::Push => {
+            // 편집 구간 이후로 끝나는 클립 전체를 간격 유지한 채 뒤로 이동
+            let first_start = clips.iter()
+                .filter(|c| c.range().1 > start_ms)
+                .map(|c| c.range().0)
+                .min();
+            if let Some(first_start) = first_start {
+                let delta = end_ms - first_start;
+                if delta > 0 {
+                    for clip in clips.iter_mut().filter(|c| c.range().1 > start_ms) {
+                        clip.shift(delta);
+                    }
+                }
+            }
+        }
+        OverlapPolicy::Overwrite => {
+            let mut tails = Vec::new();
+            clips.retain_mut(|clip| {
+                let (s, e) = clip.range();
+                if s >= end_ms || e <= start_ms {
+                    return true;
+                }
+                if s >= start_ms && e <= end_ms {
+                    // 완전히 덮임 → 제거
+                    return false;
+                }
+                if s < start_ms && e > end_ms {
+                    // 양쪽으로 걸침 → 분할 후 가운데 제거
+                    let mut tail = clip.clone();
+                    tail.set_clip_id(*next_clip_id);
+                    *next_clip_id += 1;
+                    tail.trim_head(end_ms);
+                    splits.push((clip.clip_id(), tail.clip_id()));
+                    tails.push(tail);
+                    clip.trim_tail(start_ms);
+                } else if s < start_ms {
+                    clip.trim_tail(start_ms);
+                } else {
+                    clip.trim_head(end_ms);
+                }
+                true
+            });
+            clips.extend(tails);
+        }
+    }
+
+    Some(splits)
+}
+
+/// 트랙 클립 목록에서 클립 하나를 편집하고 겹침 처리 (실패 시 clips 변경 없음)
+/// 반환: 덮어쓰기 분할로 생긴 (원본 ID, 뒷부분 ID) 목록
+pub fn edit_clips<C: EditableClip>(
+    clips: &mut Vec<C>,
+    clip_id: u64,
+    edit: ClipEdit,
+    policy: OverlapPolicy,
+    next_clip_id: &mut u64,
+) -> Option<Vec<(u64, u64)>> {
+    let mut edited = clips.iter().find(|c| c.clip_id() == clip_id)?.clone();
+    if !edit.apply(&mut edited) {
+        return None;
+    }
+
+    let mut others: Vec<C> = clips.iter().filter(|c| c.clip_id() != clip_id).cloned().collect();
+    let (start_ms, end_ms) = edited.range();
+    let mut next_id = *next_clip_id;
+    let splits = resolve_overlaps(&mut others, start_ms, end_ms, policy, &mut next_id)?;
+
+    *next_clip_id = next_id;
+    others.push(edited);
+    others.sort_by_key(|c| c.range().0);
+    *clips = others;
+    Some(splits)
+}
+
+/// from_ms 이후 시작하는 클립 전체 이동 (리플 편집)
+pub fn ripple_shift<C: EditableClip>(clips: &mut [C], from_ms: i64, delta_ms: i64) {
+    for clip in clips.iter_mut().filter(|c| c.range().0 >= from_ms) {
+        clip.shift(delta_ms);
+    }
+}
+
+/// from_ms 이후 클립을 앞으로 당길 수 있는 최대 거리 (겹침/0 미만 방지)
+/// 이후 클립이 없으면 None (제한 없음)
+pub fn ripple_room<C: EditableClip>(clips: &[C], from_ms: i64) -> Option<i64> {
+    let first_after = clips.iter()
+        .map(|c| c.range().0)
+        .filter(|&start| start >= from_ms)
+        .min()?;
+    let prev_end = clips.iter()
+        .filter(|c| c.range().0 < from_ms)
+        .map(|c| c.range().1)
+        .max()
+        .unwrap_or(0);
+    Some((first_after - prev_end).max(0))
+}
+
+/// time_ms에 걸친 클립을 둘로 분할 (뒷부분은 새 ID)
+/// 반환: 분할된 경우 (원본 ID, 뒷부분 ID)
+pub fn split_at<C: EditableClip>(clips: &mut Vec<C>, time_ms: i64, next_clip_id: &mut u64) -> Option<(u64, u64)> {
+    let clip = clips.iter_mut().find(|c| {
+        let (start, end) = c.range();
+        start < time_ms && end > time_ms
+    })?;
+
+    let mut tail = clip.clone();
+    tail.set_clip_id(*next_clip_id);
+    *next_clip_id += 1;
+    tail.trim_head(time_ms);
+    clip.trim_tail(time_ms);
+
+    let split = (clip.clip_id(), tail.clip_id());
+    clips.push(tail);
+    clips.sort_by_key(|c| c.range().0);
+    Some(split)
+}
+
+/// 히스토리 스냅샷의 클립 구성으로 복원
+/// 양쪽에 있는 클립은 배치만 되돌림 (편집 후 바뀐 트랜스폼/이펙트 등 히스토리 밖 속성 유지)
+/// 스냅샷에만 있는 클립(편집으로 제거/분할된 클립)은 스냅샷 그대로, 현재에만 있는 클립은 제거
+pub fn restore_clips<C: EditableClip>(clips: &mut Vec<C>, snapshot: &[C]) {
+    let mut restored = Vec::with_capacity(snapshot.len());
+    for saved in snapshot {
+        match clips.iter().position(|c| c.clip_id() == saved.clip_id()) {
+            Some(index) => {
+                let mut clip = clips.swap_remove(index);
+                clip.restore_placement(saved);
+                restored.push(clip);
+            }
+            None => restored.push(saved.clone()),
+        }
+    }
+    *clips = restored;
+}
+
+/// 클립이 없는 빈 구간 [start, end) 목록 (0 ~ 마지막 클립 끝, 겹친 클립은 이어진 구간으로 취급)
+pub fn find_gaps<C: EditableClip>(clips: &[C]) -> Vec<(i64, i64)> {
+    let mut ranges: Vec<(i64, i64)> = clips.iter().map(|c| c.range()).collect();
+    ranges.sort_by_key(|r| r.0);
+
+    let mut gaps = Vec::new();
+    let mut covered_end = 0;
+    for (start, end) in ranges {
+        if start > covered_end {
+            gaps.push((covered_end, start));
+        }
+        covered_end = covered_end.max(end);
+    }
+    gaps
+}
+
+/// 서로 겹치는 클립 쌍 (앞 클립 ID, 뒤 클립 ID, 겹친 구간)
+pub fn find_overlaps<C: EditableClip>(clips: &[C]) -> Vec<(u64, u64, (i64, i64))> {
+    let mut sorted: Vec<&C> = clips.iter().collect();
+    sorted.sort_by_key(|c| c.range().0);
+
+    let mut overlaps = Vec::new();
+    for (i, first) in sorted.iter().enumerate() {
+        let (_, first_end) = first.range();
+        for second in &sorted[i + 1..] {
+            let (second_start, second_end) = second.range();
+            if second_start >= first_end {
+                break;
+            }
+            overlaps.push((first.clip_id(), second.clip_id(), (second_start, first_end.min(second_end))));
+        }
+    }
+    overlaps
+}
+
+/// 빈 구간 제거: 각 클립을 앞쪽 빈 구간 길이 합만큼 당김 (겹침/맞닿음 관계 유지)
+/// 반환: 제거한 빈 구간 수
+pub fn close_gaps<C: EditableClip>(clips: &mut [C]) -> usize {
+    clips.sort_by_key(|c| c.range().0);
+
+    let mut covered_end = 0;
+    let mut shift = 0;
+    let mut closed = 0;
+    for clip in clips.iter_mut() {
+        let (start, end) = clip.range();
+        if start > covered_end {
+            shift += start - covered_end;
+            closed += 1;
+        }
+        covered_end = covered_end.max(end);
+        if shift > 0 {
+            clip.shift(-shift);
+        }
+    }
+    closed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn clip(id: u64, start: i64, duration: i64) -> AudioClip {
+        AudioClip::new(id, PathBuf::from("test.wav"), start, duration)
+    }
+
+    fn ranges(clips: &[AudioClip]) -> Vec<(u64, i64, i64)> {
+        let mut ranges: Vec<_> = clips.iter().map(|c| (c.id, c.start_time_ms, c.end_time_ms())).collect();
+        ranges.sort_by_key(|r| r.1);
+        ranges
+    }
+
+    #[test]
+    fn test_reject_and_push() {
+        let mut clips = vec![clip(1, 0, 1000), clip(2, 2000, 1000), clip(3, 4000, 1000)];
+        let mut next_id = 10;
+
+        assert!(resolve_overlaps(&mut clips, 1500, 2500, OverlapPolicy::Reject, &mut next_id).is_none());
+        assert!(resolve_overlaps(&mut clips, 1000, 2000, OverlapPolicy::Reject, &mut next_id).is_some());
+
+        // 2000에서 시작하는 클립부터 500ms 밀림 (간격 유지)
+        resolve_overlaps(&mut clips, 1500, 2500, OverlapPolicy::Push, &mut next_id).unwrap();
+        assert_eq!(ranges(&clips), vec![(1, 0, 1000), (2, 2500, 3500), (3, 4500, 5500)]);
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut clips = vec![clip(1, 0, 3000), clip(2, 3000, 1000), clip(3, 4000, 2000)];
+        let mut next_id = 10;
+
+        // 1: 양쪽 걸침 아님(앞부분 유지), 2: 완전히 덮임, 3: 앞부분 잘림
+        let splits = resolve_overlaps(&mut clips, 2000, 5000, OverlapPolicy::Overwrite, &mut next_id).unwrap();
+        assert!(splits.is_empty());
+        assert_eq!(ranges(&clips), vec![(1, 0, 2000), (3, 5000, 6000)]);
+        let third = clips.iter().find(|c| c.id == 3).unwrap();
+        assert_eq!((third.trim_start_ms, third.trim_end_ms), (1000, 2000));
+
+        // 한 클립 가운데를 덮어쓰면 분할
+        let mut clips = vec![clip(1, 0, 3000)];
+        let splits = resolve_overlaps(&mut clips, 1000, 2000, OverlapPolicy::Overwrite, &mut next_id).unwrap();
+        assert_eq!(splits, vec![(1, 10)]);
+        assert_eq!(ranges(&clips), vec![(1, 0, 1000), (10, 2000, 3000)]);
+        assert_eq!(next_id, 11);
+    }
+
+    #[test]
+    fn test_speed_edit() {
+        let mut clips = vec![clip(1, 0, 2000), clip(2, 2000, 1000)];
+        let mut next_id = 10;
+
+        // 0.5배속: 원본 2초 → 타임라인 4초, 뒤 클립 밀림
+        edit_clips(&mut clips, 1, ClipEdit::Speed { speed: 0.5 }, OverlapPolicy::Push, &mut next_id).unwrap();
+        assert_eq!(ranges(&clips), vec![(1, 0, 4000), (2, 4000, 5000)]);
+        assert_eq!((clips[0].trim_start_ms, clips[0].trim_end_ms), (0, 2000));
+
+        // 느린 클립 트림: 원본 1000~2000 → 타임라인 2000ms 길이, 시작도 2000ms 이동
+        edit_clips(&mut clips, 1, ClipEdit::Trim { trim_start_ms: 1000, trim_end_ms: 2000 }, OverlapPolicy::Reject, &mut next_id).unwrap();
+        assert_eq!(ranges(&clips)[0], (1, 2000, 4000));
+
+        // 분할 시 뒷부분 원본 위치도 속도 반영
+        split_at(&mut clips, 3000, &mut next_id).unwrap();
+        let tail = clips.iter().find(|c| c.id == 10).unwrap();
+        assert_eq!((tail.trim_start_ms, tail.trim_end_ms), (1500, 2000));
+
+        assert!(edit_clips(&mut clips, 2, ClipEdit::Speed { speed: 0.0 }, OverlapPolicy::Push, &mut next_id).is_none());
+    }
+
+    #[test]
+    fn test_ripple_helpers() {
+        let mut clips = vec![clip(1, 0, 1000), clip(2, 3000, 1000), clip(3, 5000, 1000)];
+        assert_eq!(ripple_room(&clips, 2000), Some(2000));
+        assert_eq!(ripple_room(&clips, 6000), None);
+
+        ripple_shift(&mut clips, 3000, -2000);
+        assert_eq!(ranges(&clips), vec![(1, 0, 1000), (2, 1000, 2000), (3, 3000, 4000)]);
+
+        let mut next_id = 10;
+        assert_eq!(split_at(&mut clips, 1500, &mut next_id), Some((2, 10)));
+        assert_eq!(split_at(&mut clips, 1000, &mut next_id), None);
+        assert_eq!(ranges(&clips)[2], (10, 1500, 2000));
+    }
+
+    #[test]
+    fn test_gaps_and_overlaps() {
+        let mut clips = vec![clip(1, 500, 1000), clip(2, 1200, 1000), clip(3, 3000, 1000), clip(4, 5000, 500)];
+        assert_eq!(find_gaps(&clips), vec![(0, 500), (2200, 3000), (4000, 5000)]);
+        assert_eq!(find_overlaps(&clips), vec![(1, 2, (1200, 1500))]);
+
+        // 빈 구간만 제거, 겹침(1-2) 유지
+        assert_eq!(close_gaps(&mut clips), 3);
+        assert_eq!(ranges(&clips), vec![(1, 0, 1000), (2, 700, 1700), (3, 1700, 2700), (4, 2700, 3200)]);
+        assert!(find_gaps(&clips).is_empty());
+        assert_eq!(close_gaps(&mut clips), 0);
+    }
+
+    #[test]
+    fn test_edit_clips_move_and_trim() {
+        let mut clips = vec![clip(1, 0, 1000), clip(2, 1000, 1000)];
+        let mut next_id = 10;
+
+        // 겹치는 이동 거부 → 변경 없음
+        let moved = edit_clips(&mut clips, 2, ClipEdit::Move { start_ms: 500 }, OverlapPolicy::Reject, &mut next_id);
+        assert!(moved.is_none());
+        assert_eq!(clips[1].start_time_ms, 1000);
+
+        // 앞부분 트림: 원본 500ms부터 → 타임라인 시작도 500ms 뒤로
+        let trim = ClipEdit::Trim { trim_start_ms: 500, trim_end_ms: 1000 };
+        edit_clips(&mut clips, 2, trim, OverlapPolicy::Reject, &mut next_id).unwrap();
+        assert_eq!((clips[1].start_time_ms, clips[1].duration_ms), (1500, 500));
+
+        // 앞쪽으로 다시 늘리면 시작 위치 복귀, 원본 이전(음수)은 거부
+        let trim = ClipEdit::Trim { trim_start_ms: 0, trim_end_ms: 1000 };
+        edit_clips(&mut clips, 2, trim, OverlapPolicy::Reject, &mut next_id).unwrap();
+        assert_eq!((clips[1].start_time_ms, clips[1].trim_start_ms), (1000, 0));
+        assert!(!ClipEdit::Move { start_ms: -1 }.apply(&mut clips[0].clone()));
+    }
+}
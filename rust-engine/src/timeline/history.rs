@@ -0,0 +1,127 @@
+// 편집 히스토리 - Undo/Redo 명령 스택
+// 타임라인 변경 연산마다 되돌릴 수 있는 명령을 기록 (C# 측 별도 Undo와의 상태 불일치 방지)
+// 명령은 실행 결과 스냅샷(클립 상태)을 보관 → Undo/Redo는 스냅샷의 클립 구성/배치만 복원
+// 트랜스폼/이펙트/LUT/트랙 설정 등 속성 변경은 히스토리 밖 (Undo로 되돌아가지 않음)
+
+use super::clip::{AudioClip, VideoClip};
+use super::track::{AudioTrack, VideoTrack};
+use super::transition::Transition;
+
+/// 기본 최대 히스토리 깊이
+pub const DEFAULT_HISTORY_DEPTH: usize = 200;
+
+/// 되돌릴 수 있는 편집 명령
+#[derive(Debug, Clone)]
+pub enum EditCommand {
+    AddVideoTrack { track_id: u64 },
+    AddAudioTrack { track_id: u64 },
+    /// 제거 전 트랙 순서 위치 + 트랙 전체 (클립/트랜지션 포함)
+    RemoveVideoTrack { position: usize, track: Box<VideoTrack> },
+    RemoveAudioTrack { position: usize, track: Box<AudioTrack> },
+    /// 트랙 순서 변경 (from → to 위치)
+    MoveVideoTrack { track_id: u64, from: usize, to: usize },
+    MoveAudioTrack { track_id: u64, from: usize, to: usize },
+    AddVideoClip { track_id: u64, clip: VideoClip },
+    AddAudioClip { track_id: u64, clip: AudioClip },
+    /// 제거 시 함께 사라진 트랜지션도 복원
+    RemoveVideoClip { track_id: u64, clip: VideoClip, transitions: Vec<Transition> },
+    RemoveAudioClip { track_id: u64, clip: AudioClip },
+    /// original → head(원본 ID) + tail(새 ID)
+    SplitVideoClip { track_id: u64, original: Box<VideoClip>, head: Box<VideoClip>, tail: Box<VideoClip> },
+    SplitAudioClip { track_id: u64, original: Box<AudioClip>, head: Box<AudioClip>, tail: Box<AudioClip> },
+    /// 여러 클립에 걸친 편집 (이동/트림 + 겹침 처리): 트랙 전체 전/후 상태 (복원은 클립 배치만)
+    VideoTrackEdit { before: Box<VideoTrack>, after: Box<VideoTrack> },
+    AudioTrackEdit { before: Box<AudioTrack>, after: Box<AudioTrack> },
+    /// 여러 트랙에 걸친 편집 (리플 등): 순서대로 실행, 역순으로 되돌림
+    Batch(Vec<EditCommand>),
+}
+
+/// Undo/Redo 스택
+#[derive(Debug, Clone)]
+pub struct History {
+    undo_stack: Vec<EditCommand>,
+    redo_stack: Vec<EditCommand>,
+    max_depth: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_DEPTH)
+    }
+}
+
+impl History {
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            max_depth: max_depth.max(1),
+        }
+    }
+
+    /// 새 편집 기록 (Redo 스택 폐기, 최대 깊이 초과 시 가장 오래된 명령 제거)
+    pub fn record(&mut self, command: EditCommand) {
+        self.redo_stack.clear();
+        self.push_undo(command);
+    }
+
+    /// Undo할 명령 꺼내기
+    pub fn take_undo(&mut self) -> Option<EditCommand> {
+        self.undo_stack.pop()
+    }
+
+    /// Redo할 명령 꺼내기
+    pub fn take_redo(&mut self) -> Option<EditCommand> {
+        self.redo_stack.pop()
+    }
+
+    /// Redo 실행 후 Undo 스택으로 복귀 (Redo 스택 유지)
+    pub fn push_undo(&mut self, command: EditCommand) {
+        self.undo_stack.push(command);
+        if self.undo_stack.len() > self.max_depth {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Undo 실행 후 Redo 스택으로 이동
+    pub fn push_redo(&mut self, command: EditCommand) {
+        self.redo_stack.push(command);
+    }
+
+    pub fn undo_depth(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    pub fn redo_depth(&self) -> usize {
+        self.redo_stack.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_depth_and_redo_reset() {
+        let mut history = History::new(2);
+        for track_id in 1..=3 {
+            history.record(EditCommand::AddVideoTrack { track_id });
+        }
+        // 가장 오래된 명령 제거
+        assert_eq!(history.undo_depth(), 2);
+
+        let command = history.take_undo().unwrap();
+        assert!(matches!(command, EditCommand::AddVideoTrack { track_id: 3 }));
+        history.push_redo(command);
+        assert_eq!((history.undo_depth(), history.redo_depth()), (1, 1));
+
+        // 새 편집은 Redo 스택 폐기
+        history.record(EditCommand::AddAudioTrack { track_id: 4 });
+        assert_eq!((history.undo_depth(), history.redo_depth()), (2, 0));
+    }
+}
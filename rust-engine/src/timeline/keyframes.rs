@@ -0,0 +1,311 @@
+// 키프레임 애니메이션 - 클립 트랜스폼/이펙트 값을 시간에 따라 보간
+// 보간 공식은 C# KeyframeSystem.Interpolate와 동일 (UI 미리보기와 렌더 결과 일치)
+
+use std::collections::HashMap;
+
+/// 키프레임 보간 타입 (C# InterpolationType과 같은 순서, FFI u32 매핑)
+/// 키프레임의 보간 타입은 해당 키프레임 → 다음 키프레임 구간에 적용
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    Linear = 0,
+    Bezier = 1,
+    EaseIn = 2,
+    EaseOut = 3,
+    EaseInOut = 4,
+    Hold = 5, // 다음 키프레임까지 값 유지
+}
+
+impl Interpolation {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => Interpolation::Bezier,
+            2 => Interpolation::EaseIn,
+            3 => Interpolation::EaseOut,
+            4 => Interpolation::EaseInOut,
+            5 => Interpolation::Hold,
+            _ => Interpolation::Linear,
+        }
+    }
+}
+
+/// 애니메이션 가능한 클립 속성 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyframeProperty {
+    OffsetX = 0,
+    OffsetY = 1,
+    Scale = 2,
+    Rotation = 3,
+    Opacity = 4,
+    Brightness = 5,
+    Contrast = 6,
+    Saturation = 7,
+    Temperature = 8,
+    Gamma = 9,
+    Highlights = 10,
+    Shadows = 11,
+    Tint = 12,
+    Vignette = 13,
+}
+
+impl KeyframeProperty {
+    pub fn from_u32(v: u32) -> Option<Self> {
+        match v {
+            0 => Some(KeyframeProperty::OffsetX),
+            1 => Some(KeyframeProperty::OffsetY),
+            2 => Some(KeyframeProperty::Scale),
+            3 => Some(KeyframeProperty::Rotation),
+            4 => Some(KeyframeProperty::Opacity),
+            5 => Some(KeyframeProperty::Brightness),
+            6 => Some(KeyframeProperty::Contrast),
+            7 => Some(KeyframeProperty::Saturation),
+            8 => Some(KeyframeProperty::Temperature),
+            9 => Some(KeyframeProperty::Gamma),
+            10 => Some(KeyframeProperty::Highlights),
+            11 => Some(KeyframeProperty::Shadows),
+            12 => Some(KeyframeProperty::Tint),
+            13 => Some(KeyframeProperty::Vignette),
+            _ => None,
+        }
+    }
+
+    /// 이펙트 속성 여부 (애니메이션 시 프레임 캐시 우회 필요)
+    pub fn is_effect(&self) -> bool {
+        matches!(
+            self,
+            KeyframeProperty::Brightness
+                | KeyframeProperty::Contrast
+                | KeyframeProperty::Saturation
+                | KeyframeProperty::Temperature
+                | KeyframeProperty::Gamma
+                | KeyframeProperty::Highlights
+                | KeyframeProperty::Shadows
+                | KeyframeProperty::Tint
+                | KeyframeProperty::Vignette
+        )
+    }
+}
+
+/// 단일 키프레임
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub time_ms: i64, // 클립 시작 기준 시간
+    pub value: f32,
+    pub interpolation: Interpolation,
+    /// 베지어 진입 핸들 값 오프셋 (None이면 2/3 지점)
+    pub in_value_offset: Option<f32>,
+    /// 베지어 진출 핸들 값 오프셋 (None이면 1/3 지점)
+    pub out_value_offset: Option<f32>,
+}
+
+impl Keyframe {
+    pub fn new(time_ms: i64, value: f32, interpolation: Interpolation) -> Self {
+        Self {
+            time_ms,
+            value,
+            interpolation,
+            in_value_offset: None,
+            out_value_offset: None,
+        }
+    }
+}
+
+/// 속성 하나의 키프레임 목록 (시간순 정렬 유지)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyframeTrack {
+    keyframes: Vec<Keyframe>,
+}
+
+impl KeyframeTrack {
+    /// 키프레임 추가 (같은 시간이 있으면 교체)
+    pub fn set(&mut self, keyframe: Keyframe) {
+        match self.keyframes.binary_search_by_key(&keyframe.time_ms, |k| k.time_ms) {
+            Ok(i) => self.keyframes[i] = keyframe,
+            Err(i) => self.keyframes.insert(i, keyframe),
+        }
+    }
+
+    /// 해당 시간 키프레임 제거
+    pub fn remove(&mut self, time_ms: i64) -> bool {
+        match self.keyframes.binary_search_by_key(&time_ms, |k| k.time_ms) {
+            Ok(i) => {
+                self.keyframes.remove(i);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn get_mut(&mut self, time_ms: i64) -> Option<&mut Keyframe> {
+        let i = self.keyframes.binary_search_by_key(&time_ms, |k| k.time_ms).ok()?;
+        self.keyframes.get_mut(i)
+    }
+
+    /// 모든 키프레임 시간 이동 (클립 분할 시 뒷부분 기준 시간 보정)
+    pub fn shift(&mut self, delta_ms: i64) {
+        for keyframe in &mut self.keyframes {
+            keyframe.time_ms += delta_ms;
+        }
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    /// 시간 위치의 보간 값 (키프레임 없으면 None, 범위 밖은 첫/마지막 값)
+    pub fn evaluate(&self, time_ms: i64) -> Option<f32> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+        if time_ms <= first.time_ms {
+            return Some(first.value);
+        }
+        if time_ms >= last.time_ms {
+            return Some(last.value);
+        }
+
+        // time_ms보다 큰 첫 키프레임 = 구간 끝
+        let next = self.keyframes.partition_point(|k| k.time_ms <= time_ms);
+        let k1 = &self.keyframes[next - 1];
+        let k2 = &self.keyframes[next];
+        let t = (time_ms - k1.time_ms) as f32 / (k2.time_ms - k1.time_ms) as f32;
+        Some(interpolate_segment(k1, k2, t))
+    }
+}
+
+/// 두 키프레임 사이 보간 (t: 0.0~1.0)
+fn interpolate_segment(k1: &Keyframe, k2: &Keyframe, t: f32) -> f32 {
+    let (v1, v2) = (k1.value, k2.value);
+    match k1.interpolation {
+        Interpolation::Linear => v1 + t * (v2 - v1),
+        Interpolation::EaseIn => v1 + t * t * (v2 - v1),
+        Interpolation::EaseOut => v1 + (1.0 - (1.0 - t) * (1.0 - t)) * (v2 - v1),
+        Interpolation::EaseInOut => {
+            let eased = if t < 0.5 {
+                2.0 * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+            };
+            v1 + eased * (v2 - v1)
+        }
+        Interpolation::Hold => v1,
+        Interpolation::Bezier => {
+            // P0 (k1) → P1 (진출 핸들) → P2 (진입 핸들) → P3 (k2)
+            let p1 = k1.out_value_offset.map_or(v1 + (v2 - v1) / 3.0, |o| v1 + o);
+            let p2 = k2.in_value_offset.map_or(v2 - (v2 - v1) / 3.0, |o| v2 + o);
+            let u = 1.0 - t;
+            u * u * u * v1 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * v2
+        }
+    }
+}
+
+/// 클립의 속성별 키프레임
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClipKeyframes {
+    tracks: HashMap<KeyframeProperty, KeyframeTrack>,
+}
+
+impl ClipKeyframes {
+    pub fn set(&mut self, property: KeyframeProperty, keyframe: Keyframe) {
+        self.tracks.entry(property).or_default().set(keyframe);
+    }
+
+    pub fn remove(&mut self, property: KeyframeProperty, time_ms: i64) -> bool {
+        let removed = self.tracks.get_mut(&property).is_some_and(|t| t.remove(time_ms));
+        if self.tracks.get(&property).is_some_and(|t| t.is_empty()) {
+            self.tracks.remove(&property);
+        }
+        removed
+    }
+
+    pub fn get_mut(&mut self, property: KeyframeProperty, time_ms: i64) -> Option<&mut Keyframe> {
+        self.tracks.get_mut(&property)?.get_mut(time_ms)
+    }
+
+    /// 속성 키프레임 전체 제거 (None이면 모든 속성)
+    pub fn clear(&mut self, property: Option<KeyframeProperty>) {
+        match property {
+            Some(p) => {
+                self.tracks.remove(&p);
+            }
+            None => self.tracks.clear(),
+        }
+    }
+
+    /// 모든 속성 키프레임 시간 이동
+    pub fn shift(&mut self, delta_ms: i64) {
+        for track in self.tracks.values_mut() {
+            track.shift(delta_ms);
+        }
+    }
+
+    pub fn track(&self, property: KeyframeProperty) -> Option<&KeyframeTrack> {
+        self.tracks.get(&property)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// 이펙트 속성 중 하나라도 애니메이션 중인지
+    pub fn animates_effects(&self) -> bool {
+        self.tracks.keys().any(|p| p.is_effect())
+    }
+
+    /// 속성 값 (키프레임이 없으면 None → 정적 값 사용)
+    pub fn value_at(&self, property: KeyframeProperty, clip_time_ms: i64) -> Option<f32> {
+        self.tracks.get(&property)?.evaluate(clip_time_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(interpolation: Interpolation) -> KeyframeTrack {
+        let mut track = KeyframeTrack::default();
+        track.set(Keyframe::new(1000, 10.0, interpolation));
+        track.set(Keyframe::new(0, 0.0, interpolation));
+        track
+    }
+
+    #[test]
+    fn test_evaluate_interpolations() {
+        assert_eq!(KeyframeTrack::default().evaluate(0), None);
+
+        let linear = track(Interpolation::Linear);
+        assert_eq!(linear.evaluate(-100), Some(0.0));
+        assert_eq!(linear.evaluate(500), Some(5.0));
+        assert_eq!(linear.evaluate(2000), Some(10.0));
+
+        assert_eq!(track(Interpolation::Hold).evaluate(999), Some(0.0));
+        assert_eq!(track(Interpolation::EaseIn).evaluate(500), Some(2.5));
+        assert_eq!(track(Interpolation::EaseOut).evaluate(500), Some(7.5));
+        assert_eq!(track(Interpolation::EaseInOut).evaluate(500), Some(5.0));
+
+        // 기본 핸들 베지어 = 선형과 동일 (1/3, 2/3 제어점)
+        let bezier = track(Interpolation::Bezier).evaluate(250).unwrap();
+        assert!((bezier - 2.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_set_replaces_and_remove() {
+        let mut keyframes = ClipKeyframes::default();
+        keyframes.set(KeyframeProperty::Scale, Keyframe::new(0, 1.0, Interpolation::Linear));
+        keyframes.set(KeyframeProperty::Scale, Keyframe::new(0, 0.5, Interpolation::Linear));
+        assert_eq!(keyframes.track(KeyframeProperty::Scale).unwrap().keyframes().len(), 1);
+        assert_eq!(keyframes.value_at(KeyframeProperty::Scale, 100), Some(0.5));
+        assert!(!keyframes.animates_effects());
+
+        keyframes.set(KeyframeProperty::Brightness, Keyframe::new(0, 0.2, Interpolation::Hold));
+        assert!(keyframes.animates_effects());
+
+        assert!(keyframes.remove(KeyframeProperty::Brightness, 0));
+        assert!(!keyframes.remove(KeyframeProperty::Brightness, 0));
+        assert!(!keyframes.animates_effects());
+    }
+}
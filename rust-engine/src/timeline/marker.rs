@@ -0,0 +1,79 @@
+// 마커 - 타임라인 위치 표시 (탐색용) + Export 시 챕터 메타데이터
+// 챕터: 구간 안의 마커마다 하나, 다음 마커(또는 구간 끝)까지 (YouTube 챕터/플레이어 챕터 목록)
+
+/// 타임라인 마커
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+    pub id: u64,
+    pub time_ms: i64,
+    pub name: String,
+    /// 표시 색 RGB
+    pub color: [u8; 3],
+}
+
+impl Marker {
+    pub fn new(id: u64, time_ms: i64, name: impl Into<String>, color: [u8; 3]) -> Self {
+        Self {
+            id,
+            time_ms,
+            name: name.into(),
+            color,
+        }
+    }
+}
+
+/// Export 챕터 (출력 파일 기준 시간, 구간 시작 = 0)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub title: String,
+}
+
+/// Export 구간 [start, end)의 마커 → 챕터 (markers: 시간 순)
+/// 같은 시각의 마커는 마지막 것만 사용 (길이 0 챕터 방지)
+pub fn chapters(markers: &[Marker], range: (i64, i64)) -> Vec<Chapter> {
+    let (start_ms, end_ms) = range;
+    let inside: Vec<&Marker> = markers.iter()
+        .filter(|m| m.time_ms >= start_ms && m.time_ms < end_ms)
+        .collect();
+
+    inside.iter().enumerate()
+        .filter_map(|(i, marker)| {
+            let next_ms = inside.get(i + 1).map_or(end_ms, |next| next.time_ms);
+            (next_ms > marker.time_ms).then(|| Chapter {
+                start_ms: marker.time_ms - start_ms,
+                end_ms: next_ms - start_ms,
+                title: marker.name.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chapters_in_range() {
+        let markers = vec![
+            Marker::new(1, 0, "Intro", [255, 0, 0]),
+            Marker::new(2, 5000, "Setup", [0, 255, 0]),
+            Marker::new(3, 5000, "Build", [0, 255, 0]),
+            Marker::new(4, 12000, "Outro", [0, 0, 255]),
+        ];
+
+        // 전체 구간: 같은 시각 마커는 뒤의 것만
+        let all = chapters(&markers, (0, 15000));
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[1], Chapter { start_ms: 5000, end_ms: 12000, title: "Build".to_string() });
+        assert_eq!(all[2].end_ms, 15000);
+
+        // 부분 구간: 구간 밖 마커 제외, 출력 시간은 구간 시작 기준
+        let part = chapters(&markers, (4000, 13000));
+        assert_eq!(part.len(), 2);
+        assert_eq!((part[0].start_ms, part[0].end_ms), (1000, 8000));
+        assert_eq!((part[1].start_ms, part[1].end_ms), (8000, 9000));
+        assert!(chapters(&markers, (13000, 14000)).is_empty());
+    }
+}
@@ -4,7 +4,21 @@
 pub mod clip;
 pub mod track;
 pub mod timeline;
+pub mod keyframes;
+pub mod transition;
+pub mod history;
+pub mod edit;
+pub mod title;
+pub mod color;
+pub mod marker;
 
-pub use clip::{ClipType, VideoClip, AudioClip};
-pub use track::{VideoTrack, AudioTrack};
-pub use timeline::Timeline;
+pub use clip::{ClipType, VideoClip, AudioClip, EndOfMediaBehavior, FitMode, ClipTransform, CropRect, MAX_CLIP_VOLUME, MIN_CLIP_SPEED, MAX_CLIP_SPEED, source_key};
+pub use track::{VideoTrack, AudioTrack, AudioSource, MAX_TRACK_VOLUME};
+pub use timeline::{ClipOverlap, Timeline, TimelineStats, TrackStats, frame_to_ms, ms_to_frame};
+pub use keyframes::{ClipKeyframes, Interpolation, Keyframe, KeyframeProperty, KeyframeTrack};
+pub use transition::{Transition, TransitionKind};
+pub use history::{EditCommand, History};
+pub use edit::{ClipEdit, OverlapPolicy};
+pub use title::{TitleAnimation, TitleClip, TitleMotion};
+pub use color::ColorClip;
+pub use marker::{Chapter, Marker};
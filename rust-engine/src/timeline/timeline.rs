@@ -1,7 +1,50 @@
 // 타임라인 모듈 - 전체 프로젝트의 타임라인 관리
 
-use super::track::{VideoTrack, AudioTrack};
+use super::track::{VideoTrack, AudioTrack, AudioSource};
 use super::clip::{VideoClip, AudioClip};
+use super::keyframes::KeyframeTrack;
+use super::title::TitleClip;
+use super::color::ColorClip;
+use super::history::{EditCommand, History};
+use super::marker::{self, Chapter, Marker};
+use super::edit::{self, ClipEdit, EditableClip, OverlapPolicy, edit_clips, restore_clips, ripple_room, ripple_shift, split_at};
+use crate::rendering::effects::EffectParams;
+use crate::audio::effects::AudioEffectParams;
+
+/// 트랙별 통계
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackStats {
+    pub track_id: u64,
+    pub clip_count: usize,
+    pub enabled: bool,
+}
+
+/// 같은 트랙에서 겹치는 두 클립 (Export 전 점검용)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipOverlap {
+    pub track_id: u64,
+    /// 먼저 시작하는 클립
+    pub clip_id: u64,
+    pub other_clip_id: u64,
+    /// 겹친 구간 [start, end)
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+/// 타임라인 통계 (프로젝트 정보 패널/Export 전 점검용)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineStats {
+    pub video_tracks: Vec<TrackStats>,
+    pub audio_tracks: Vec<TrackStats>,
+    /// 서로 다른 소스 파일 수 (비디오+오디오)
+    pub source_file_count: usize,
+    /// 모든 클립 길이 합 (ms)
+    pub media_duration_ms: i64,
+    /// 타임라인 총 길이 (ms)
+    pub timeline_duration_ms: i64,
+    /// 프레임 보간 사용 클립 수
+    pub interpolated_clip_count: usize,
+}
 
 /// 타임라인 - 비디오 편집 프로젝트의 핵심
 #[derive(Debug, Clone)]
@@ -11,8 +54,18 @@ pub struct Timeline {
     pub fps: f64,
     pub video_tracks: Vec<VideoTrack>,
     pub audio_tracks: Vec<AudioTrack>,
+    /// 배경 채우기 (클립이 없는 구간/레터박스 영역, 단색 또는 그라디언트, 항상 불투명, 기본 검정)
+    background: ColorClip,
+    /// 마스터 그레이딩 (모든 트랙 합성 후 출력 프레임 전체에 LUT → 이펙트, 자막 제외)
+    pub master_effects: EffectParams,
+    pub master_lut_path: Option<std::path::PathBuf>,
+    /// 마커 (시간 순)
+    markers: Vec<Marker>,
     next_clip_id: u64,
     next_track_id: u64,
+    next_marker_id: u64,
+    /// 편집 Undo/Redo 히스토리
+    history: History,
 }
 
 impl Timeline {
@@ -24,8 +77,14 @@ impl Timeline {
             fps,
             video_tracks: Vec::new(),
             audio_tracks: Vec::new(),
+            background: ColorClip::solid([0, 0, 0, 255]),
+            master_effects: EffectParams::default(),
+            master_lut_path: None,
+            markers: Vec::new(),
             next_clip_id: 1,
             next_track_id: 1,
+            next_marker_id: 1,
+            history: History::default(),
         }
     }
 
@@ -36,6 +95,7 @@ impl Timeline {
 
         let index = self.video_tracks.len();
         self.video_tracks.push(VideoTrack::new(id, index));
+        self.history.record(EditCommand::AddVideoTrack { track_id: id });
 
         id
     }
@@ -47,10 +107,142 @@ impl Timeline {
 
         let index = self.audio_tracks.len();
         self.audio_tracks.push(AudioTrack::new(id, index));
+        self.history.record(EditCommand::AddAudioTrack { track_id: id });
 
         id
     }
 
+    /// 비디오 트랙 제거 (클립/트랜지션 포함, 잠긴 트랙은 거부)
+    /// 뒤 트랙들의 index는 한 칸씩 당겨짐
+    pub fn remove_video_track(&mut self, track_id: u64) -> bool {
+        let position = match self.video_tracks.iter().position(|t| t.id == track_id && !t.locked) {
+            Some(position) => position,
+            None => return false,
+        };
+
+        let track = self.video_tracks.remove(position);
+        self.reindex_tracks();
+        self.history.record(EditCommand::RemoveVideoTrack { position, track: Box::new(track) });
+        true
+    }
+
+    /// 오디오 트랙 제거 (클립 포함, 잠긴 트랙은 거부)
+    pub fn remove_audio_track(&mut self, track_id: u64) -> bool {
+        let position = match self.audio_tracks.iter().position(|t| t.id == track_id && !t.locked) {
+            Some(position) => position,
+            None => return false,
+        };
+
+        let track = self.audio_tracks.remove(position);
+        self.reindex_tracks();
+        self.history.record(EditCommand::RemoveAudioTrack { position, track: Box::new(track) });
+        true
+    }
+
+    /// 트랙 종류에 맞춰 트랙 제거 (트랙 ID는 비디오/오디오 공통 순번)
+    pub fn remove_track(&mut self, track_id: u64) -> bool {
+        if self.video_tracks.iter().any(|t| t.id == track_id) {
+            self.remove_video_track(track_id)
+        } else {
+            self.remove_audio_track(track_id)
+        }
+    }
+
+    /// 트랙 순서 변경 (new_index: 같은 종류 트랙 안의 새 위치, 0 = 최하단)
+    /// 사이 트랙들의 index도 함께 갱신 (index = 목록 위치 유지)
+    pub fn move_track(&mut self, track_id: u64, new_index: usize) -> bool {
+        if let Some(from) = self.video_tracks.iter().position(|t| t.id == track_id) {
+            if new_index >= self.video_tracks.len() {
+                return false;
+            }
+            if from != new_index {
+                move_item(&mut self.video_tracks, from, new_index);
+                self.reindex_tracks();
+                self.history.record(EditCommand::MoveVideoTrack { track_id, from, to: new_index });
+            }
+            return true;
+        }
+
+        if let Some(from) = self.audio_tracks.iter().position(|t| t.id == track_id) {
+            if new_index >= self.audio_tracks.len() {
+                return false;
+            }
+            if from != new_index {
+                move_item(&mut self.audio_tracks, from, new_index);
+                self.reindex_tracks();
+                self.history.record(EditCommand::MoveAudioTrack { track_id, from, to: new_index });
+            }
+            return true;
+        }
+
+        false
+    }
+
+    /// 트랙 이름 변경
+    pub fn set_track_name(&mut self, track_id: u64, name: &str) -> bool {
+        if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.name = name.to_string();
+            return true;
+        }
+        if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.name = name.to_string();
+            return true;
+        }
+        false
+    }
+
+    /// 트랙 잠금 설정 (잠긴 트랙은 클립 추가/제거/편집 거부, 리플 편집에서도 제외)
+    pub fn set_track_locked(&mut self, track_id: u64, locked: bool) -> bool {
+        if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.locked = locked;
+            return true;
+        }
+        if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.locked = locked;
+            return true;
+        }
+        false
+    }
+
+    /// 트랙 on/off (꺼진 비디오 트랙은 합성, 오디오 트랙은 믹스에서 제외 — 잠긴 트랙도 허용)
+    pub fn set_track_enabled(&mut self, track_id: u64, enabled: bool) -> bool {
+        if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.enabled = enabled;
+            return true;
+        }
+        if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.enabled = enabled;
+            return true;
+        }
+        false
+    }
+
+    /// 트랙 잠금 여부 (없는 트랙은 false)
+    pub fn is_track_locked(&self, track_id: u64) -> bool {
+        self.video_tracks.iter().any(|t| t.id == track_id && t.locked)
+            || self.audio_tracks.iter().any(|t| t.id == track_id && t.locked)
+    }
+
+    /// 클립을 편집할 수 있는 비디오 트랙 (없거나 잠겼으면 None)
+    pub fn editable_video_track_mut(&mut self, track_id: u64) -> Option<&mut VideoTrack> {
+        self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)
+    }
+
+    /// 클립을 편집할 수 있는 오디오 트랙 (없거나 잠겼으면 None)
+    pub fn editable_audio_track_mut(&mut self, track_id: u64) -> Option<&mut AudioTrack> {
+        self.audio_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)
+    }
+
+    /// 트랙 index를 목록 위치와 일치시킴 (제거/순서 변경 후)
+    fn reindex_tracks(&mut self) {
+        for (index, track) in self.video_tracks.iter_mut().enumerate() {
+            track.index = index;
+        }
+        for (index, track) in self.audio_tracks.iter_mut().enumerate() {
+            track.index = index;
+        }
+    }
+
     /// 비디오 클립 추가
     pub fn add_video_clip(
         &mut self,
@@ -59,17 +251,68 @@ impl Timeline {
         start_time_ms: i64,
         duration_ms: i64,
     ) -> Option<u64> {
-        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id)?;
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)?;
 
         let clip_id = self.next_clip_id;
         self.next_clip_id += 1;
 
         let clip = VideoClip::new(clip_id, file_path, start_time_ms, duration_ms);
-        track.add_clip(clip);
+        track.add_clip(clip.clone());
+        self.history.record(EditCommand::AddVideoClip { track_id, clip });
 
         Some(clip_id)
     }
 
+    /// 타이틀 클립 추가 (비디오 트랙, 소스 파일 없음)
+    pub fn add_title_clip(
+        &mut self,
+        track_id: u64,
+        title: TitleClip,
+        start_time_ms: i64,
+        duration_ms: i64,
+    ) -> Option<u64> {
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)?;
+
+        let clip_id = self.next_clip_id;
+        self.next_clip_id += 1;
+
+        let clip = VideoClip::new_title(clip_id, title, start_time_ms, duration_ms);
+        track.add_clip(clip.clone());
+        self.history.record(EditCommand::AddVideoClip { track_id, clip });
+
+        Some(clip_id)
+    }
+
+    /// 컬러 클립 추가 (비디오 트랙, 소스 파일 없음)
+    pub fn add_color_clip(
+        &mut self,
+        track_id: u64,
+        color: ColorClip,
+        start_time_ms: i64,
+        duration_ms: i64,
+    ) -> Option<u64> {
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)?;
+
+        let clip_id = self.next_clip_id;
+        self.next_clip_id += 1;
+
+        let clip = VideoClip::new_color(clip_id, color, start_time_ms, duration_ms);
+        track.add_clip(clip.clone());
+        self.history.record(EditCommand::AddVideoClip { track_id, clip });
+
+        Some(clip_id)
+    }
+
+    /// 배경 채우기 (단색이면 ColorClip::solid_rgb로 RGB)
+    pub fn background(&self) -> ColorClip {
+        self.background
+    }
+
+    /// 배경 채우기 설정 (배경이므로 알파는 무시하고 불투명 처리)
+    pub fn set_background(&mut self, background: ColorClip) {
+        self.background = background.opaque();
+    }
+
     /// 오디오 클립 추가
     pub fn add_audio_clip(
         &mut self,
@@ -78,33 +321,690 @@ impl Timeline {
         start_time_ms: i64,
         duration_ms: i64,
     ) -> Option<u64> {
-        let track = self.audio_tracks.iter_mut().find(|t| t.id == track_id)?;
+        let track = self.audio_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)?;
 
         let clip_id = self.next_clip_id;
         self.next_clip_id += 1;
 
         let clip = AudioClip::new(clip_id, file_path, start_time_ms, duration_ms);
-        track.add_clip(clip);
+        track.add_clip(clip.clone());
+        self.history.record(EditCommand::AddAudioClip { track_id, clip });
 
         Some(clip_id)
     }
 
     /// 비디오 클립 제거
     pub fn remove_video_clip(&mut self, track_id: u64, clip_id: u64) -> bool {
-        if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == track_id) {
-            track.remove_clip(clip_id).is_some()
-        } else {
-            false
+        let track = match self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked) {
+            Some(t) => t,
+            None => return false,
+        };
+
+        // 함께 제거될 트랜지션 보관 (Undo 시 복원)
+        let transitions = track.transitions.iter()
+            .filter(|t| t.from_clip_id == clip_id || t.to_clip_id == clip_id)
+            .copied()
+            .collect();
+        match track.remove_clip(clip_id) {
+            Some(clip) => {
+                self.history.record(EditCommand::RemoveVideoClip { track_id, clip, transitions });
+                true
+            }
+            None => false,
         }
     }
 
     /// 오디오 클립 제거
     pub fn remove_audio_clip(&mut self, track_id: u64, clip_id: u64) -> bool {
-        if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == track_id) {
-            track.remove_clip(clip_id).is_some()
+        let removed = self.audio_tracks.iter_mut()
+            .find(|t| t.id == track_id && !t.locked)
+            .and_then(|track| track.remove_clip(clip_id));
+        match removed {
+            Some(clip) => {
+                self.history.record(EditCommand::RemoveAudioClip { track_id, clip });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 비디오 클립 분할 (split_time_ms: 타임라인 시간, 클립 내부여야 함)
+    /// 원본 클립은 앞부분으로 줄이고, 뒷부분은 새 클립으로 추가
+    /// 반환값: 새로 생성된 뒷부분 클립 ID
+    pub fn split_video_clip(&mut self, track_id: u64, clip_id: u64, split_time_ms: i64) -> Option<u64> {
+        let new_clip_id = self.next_clip_id;
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)?;
+        let clip = track.get_clip_by_id_mut(clip_id)?;
+
+        if split_time_ms <= clip.start_time_ms || split_time_ms >= clip.end_time_ms() {
+            return None;
+        }
+
+        let offset = split_time_ms - clip.start_time_ms;
+
+        let original = clip.clone();
+        let mut tail = clip.clone();
+        tail.id = new_clip_id;
+        tail.start_time_ms = split_time_ms;
+        tail.duration_ms = clip.duration_ms - offset;
+        tail.trim_start_ms = clip.trim_start_ms + clip.source_offset(offset);
+        // 키프레임은 클립 시작 기준 → 뒷부분 시작 기준으로 보정
+        tail.keyframes.shift(-offset);
+
+        clip.duration_ms = offset;
+        clip.trim_end_ms = tail.trim_start_ms;
+        let head = clip.clone();
+
+        track.add_clip(tail.clone());
+        self.next_clip_id += 1;
+
+        // 원본 클립 뒤쪽 트랜지션은 새 뒷부분 클립으로 이동
+        remap_transition_source(track, clip_id, new_clip_id);
+
+        self.history.record(EditCommand::SplitVideoClip {
+            track_id,
+            original: Box::new(original),
+            head: Box::new(head),
+            tail: Box::new(tail),
+        });
+
+        Some(new_clip_id)
+    }
+
+    /// 오디오 클립 분할 (split_video_clip과 동일 규칙)
+    pub fn split_audio_clip(&mut self, track_id: u64, clip_id: u64, split_time_ms: i64) -> Option<u64> {
+        let new_clip_id = self.next_clip_id;
+        let track = self.audio_tracks.iter_mut().find(|t| t.id == track_id && !t.locked)?;
+        let clip = track.clips.iter_mut().find(|c| c.id == clip_id)?;
+
+        if split_time_ms <= clip.start_time_ms || split_time_ms >= clip.end_time_ms() {
+            return None;
+        }
+
+        let offset = split_time_ms - clip.start_time_ms;
+
+        let original = clip.clone();
+        let mut tail = clip.clone();
+        tail.id = new_clip_id;
+        tail.start_time_ms = split_time_ms;
+        tail.duration_ms = clip.duration_ms - offset;
+        tail.trim_start_ms = clip.trim_start_ms + clip.source_offset(offset);
+        // 페이드 인은 앞부분, 페이드 아웃은 뒷부분에만 유지
+        tail.fade_in_ms = 0;
+        tail.volume_envelope.shift(-offset);
+
+        clip.duration_ms = offset;
+        clip.trim_end_ms = tail.trim_start_ms;
+        clip.fade_out_ms = 0;
+        let head = clip.clone();
+
+        track.add_clip(tail.clone());
+        self.next_clip_id += 1;
+
+        self.history.record(EditCommand::SplitAudioClip {
+            track_id,
+            original: Box::new(original),
+            head: Box::new(head),
+            tail: Box::new(tail),
+        });
+
+        Some(new_clip_id)
+    }
+
+    /// 트랙 종류에 맞춰 클립 분할 (트랙 ID는 비디오/오디오 공통 순번)
+    /// 반환값: 새로 생성된 뒷부분 클립 ID
+    pub fn split_clip(&mut self, track_id: u64, clip_id: u64, split_time_ms: i64) -> Option<u64> {
+        if self.video_tracks.iter().any(|t| t.id == track_id) {
+            self.split_video_clip(track_id, clip_id, split_time_ms)
         } else {
-            false
+            self.split_audio_clip(track_id, clip_id, split_time_ms)
+        }
+    }
+
+    /// 클립 이동 (new_start_ms: 새 타임라인 시작 위치, 같은 트랙 내)
+    /// 겹치는 클립은 policy에 따라 거부/밀기/덮어쓰기
+    pub fn move_clip(&mut self, track_id: u64, clip_id: u64, new_start_ms: i64, policy: OverlapPolicy) -> bool {
+        self.edit_clip(track_id, clip_id, ClipEdit::Move { start_ms: new_start_ms }, policy)
+    }
+
+    /// 클립 트림 (원본 파일 기준 새 in/out 지점)
+    /// 앞부분 트림은 원본 프레임 위치가 유지되도록 타임라인 시작 위치도 함께 이동
+    pub fn trim_clip(
+        &mut self,
+        track_id: u64,
+        clip_id: u64,
+        new_trim_start_ms: i64,
+        new_trim_end_ms: i64,
+        policy: OverlapPolicy,
+    ) -> bool {
+        let edit = ClipEdit::Trim { trim_start_ms: new_trim_start_ms, trim_end_ms: new_trim_end_ms };
+        self.edit_clip(track_id, clip_id, edit, policy)
+    }
+
+    /// 비디오 클립 원본 in/out 지점만 설정 (타임라인 위치/길이 유지, 겹침 처리 없음, Undo 가능)
+    /// 호스트가 시작 위치를 직접 관리하는 경우용 (Razor 분할/프로젝트 복원) — 리플 트림은 trim_clip
+    /// 키프레임은 원본 시간 기준 유지 (Undo 시 배치 복원과 같은 정렬)
+    pub fn set_video_clip_trim(&mut self, track_id: u64, clip_id: u64, trim_start_ms: i64, trim_end_ms: i64) -> bool {
+        if trim_start_ms < 0 || trim_end_ms <= trim_start_ms {
+            return false;
         }
+        let track = match self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked) {
+            Some(track) => track,
+            None => return false,
+        };
+        let before = track.clone();
+        let clip = match track.get_clip_by_id_mut(clip_id) {
+            Some(clip) => clip,
+            None => return false,
+        };
+
+        let delta = clip.timeline_offset(trim_start_ms - clip.trim_start_ms);
+        clip.keyframes.shift(-delta);
+        clip.trim_start_ms = trim_start_ms;
+        clip.trim_end_ms = trim_end_ms;
+
+        self.history.record(EditCommand::VideoTrackEdit {
+            before: Box::new(before),
+            after: Box::new(track.clone()),
+        });
+        true
+    }
+
+    /// 클립 재생 속도 변경 (원본 구간 유지, 타임라인 길이가 1/speed 배로 변경)
+    /// 길어진 구간이 뒤 클립과 겹치면 policy에 따라 거부/밀기/덮어쓰기
+    pub fn set_clip_speed(&mut self, track_id: u64, clip_id: u64, speed: f64, policy: OverlapPolicy) -> bool {
+        self.edit_clip(track_id, clip_id, ClipEdit::Speed { speed }, policy)
+    }
+
+    /// 클립 편집 + 겹침 처리 (비디오/오디오 트랙 공통, 트랙 단위로 히스토리 기록)
+    pub fn edit_clip(&mut self, track_id: u64, clip_id: u64, edit: ClipEdit, policy: OverlapPolicy) -> bool {
+        if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == track_id && !t.locked) {
+            let before = track.clone();
+            let splits = match edit_clips(&mut track.clips, clip_id, edit, policy, &mut self.next_clip_id) {
+                Some(splits) => splits,
+                None => return false,
+            };
+
+            // 덮어쓰기 분할: 뒤쪽 트랜지션은 뒷부분으로, 제거된 클립의 트랜지션은 정리
+            for (original_id, tail_id) in splits {
+                remap_transition_source(track, original_id, tail_id);
+            }
+            let clips = &track.clips;
+            track.transitions.retain(|t| {
+                clips.iter().any(|c| c.id == t.from_clip_id) && clips.iter().any(|c| c.id == t.to_clip_id)
+            });
+
+            self.history.record(EditCommand::VideoTrackEdit {
+                before: Box::new(before),
+                after: Box::new(track.clone()),
+            });
+            return true;
+        }
+
+        if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == track_id && !t.locked) {
+            let before = track.clone();
+            if edit_clips(&mut track.clips, clip_id, edit, policy, &mut self.next_clip_id).is_none() {
+                return false;
+            }
+
+            self.history.record(EditCommand::AudioTrackEdit {
+                before: Box::new(before),
+                after: Box::new(track.clone()),
+            });
+            return true;
+        }
+
+        false
+    }
+
+    /// 비디오 클립 이펙트 설정 (트랙 무관하게 clip_id로 검색, 히스토리 기록 없음 — LUT와 동일)
+    pub fn set_clip_effects(&mut self, clip_id: u64, effects: EffectParams) -> bool {
+        match self.video_tracks.iter_mut().find_map(|t| t.get_clip_by_id_mut(clip_id)) {
+            Some(clip) => {
+                clip.effects = effects;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 리플 삭제: 클립 제거 후 뒤따르는 클립을 당겨 빈 공간 제거
+    /// - all_tracks: 모든 트랙의 이후 클립을 함께 이동 (트랙 간 싱크 유지)
+    /// - 다른 트랙에 걸친 클립이 있으면 겹치지 않는 만큼만 이동 (모든 트랙 동일 거리)
+    /// - 잠긴 트랙은 이동하지 않음 (대상 트랙이 잠겨 있으면 거부)
+    pub fn ripple_remove_clip(&mut self, track_id: u64, clip_id: u64, all_tracks: bool) -> bool {
+        let locked = self.locked_track_ids();
+        if locked.contains(&track_id) {
+            return false;
+        }
+        let in_scope = |id: u64| (all_tracks || id == track_id) && !locked.contains(&id);
+        let (video_before, audio_before) = self.track_snapshots(in_scope);
+
+        let removed = if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.remove_clip(clip_id).map(|c| (c.start_time_ms, c.end_time_ms()))
+        } else if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == track_id) {
+            track.remove_clip(clip_id).map(|c| (c.start_time_ms, c.end_time_ms()))
+        } else {
+            None
+        };
+        let (start_ms, end_ms) = match removed {
+            Some(range) => range,
+            None => return false,
+        };
+
+        let rooms = self.video_tracks.iter().filter(|t| in_scope(t.id)).filter_map(|t| ripple_room(&t.clips, end_ms))
+            .chain(self.audio_tracks.iter().filter(|t| in_scope(t.id)).filter_map(|t| ripple_room(&t.clips, end_ms)));
+        let shift = rooms.fold(end_ms - start_ms, i64::min);
+
+        if shift > 0 {
+            for track in self.video_tracks.iter_mut().filter(|t| in_scope(t.id)) {
+                ripple_shift(&mut track.clips, end_ms, -shift);
+            }
+            for track in self.audio_tracks.iter_mut().filter(|t| in_scope(t.id)) {
+                ripple_shift(&mut track.clips, end_ms, -shift);
+            }
+        }
+
+        self.record_track_changes(video_before, audio_before);
+        true
+    }
+
+    /// 리플 삽입: start_time_ms 이후 클립을 duration_ms만큼 밀고 새 클립 추가
+    /// 삽입 지점에 걸친 클립은 분할 후 뒷부분만 이동
+    /// - all_tracks: 모든 트랙을 함께 밀기 (트랙 간 싱크 유지, 잠긴 트랙 제외)
+    ///
+    /// 반환값: 새 클립 ID
+    pub fn ripple_insert_clip(
+        &mut self,
+        track_id: u64,
+        file_path: std::path::PathBuf,
+        start_time_ms: i64,
+        duration_ms: i64,
+        all_tracks: bool,
+    ) -> Option<u64> {
+        let is_video = self.video_tracks.iter().any(|t| t.id == track_id);
+        let locked = self.locked_track_ids();
+        if start_time_ms < 0 || duration_ms <= 0 || locked.contains(&track_id)
+            || (!is_video && !self.audio_tracks.iter().any(|t| t.id == track_id))
+        {
+            return None;
+        }
+
+        let in_scope = |id: u64| (all_tracks || id == track_id) && !locked.contains(&id);
+        let (video_before, audio_before) = self.track_snapshots(in_scope);
+
+        for track in self.video_tracks.iter_mut().filter(|t| in_scope(t.id)) {
+            if let Some((original_id, tail_id)) = split_at(&mut track.clips, start_time_ms, &mut self.next_clip_id) {
+                remap_transition_source(track, original_id, tail_id);
+            }
+            ripple_shift(&mut track.clips, start_time_ms, duration_ms);
+        }
+        for track in self.audio_tracks.iter_mut().filter(|t| in_scope(t.id)) {
+            split_at(&mut track.clips, start_time_ms, &mut self.next_clip_id);
+            ripple_shift(&mut track.clips, start_time_ms, duration_ms);
+        }
+
+        let clip_id = self.next_clip_id;
+        self.next_clip_id += 1;
+        if is_video {
+            let clip = VideoClip::new(clip_id, file_path, start_time_ms, duration_ms);
+            self.video_tracks.iter_mut().find(|t| t.id == track_id)?.add_clip(clip);
+        } else {
+            let clip = AudioClip::new(clip_id, file_path, start_time_ms, duration_ms);
+            self.audio_tracks.iter_mut().find(|t| t.id == track_id)?.add_clip(clip);
+        }
+
+        self.record_track_changes(video_before, audio_before);
+        Some(clip_id)
+    }
+
+    /// 트랙의 빈 구간 [start, end) 목록 (0 ~ 마지막 클립 끝, 없는 트랙이면 None)
+    /// 비디오 트랙의 빈 구간은 아래 트랙이 없으면 배경색(검정) 프레임으로 출력됨
+    pub fn find_gaps(&self, track_id: u64) -> Option<Vec<(i64, i64)>> {
+        if let Some(track) = self.video_tracks.iter().find(|t| t.id == track_id) {
+            return Some(edit::find_gaps(&track.clips));
+        }
+        self.audio_tracks.iter().find(|t| t.id == track_id).map(|track| edit::find_gaps(&track.clips))
+    }
+
+    /// 모든 트랙에서 같은 트랙 클립끼리 겹치는 구간 (비디오 트랙 → 오디오 트랙 순)
+    /// 오디오 트랙의 겹침은 자동 크로스페이드로 재생되지만 의도치 않은 겹침 확인용으로 포함
+    pub fn find_overlaps(&self) -> Vec<ClipOverlap> {
+        let video = self.video_tracks.iter().map(|t| (t.id, edit::find_overlaps(&t.clips)));
+        let audio = self.audio_tracks.iter().map(|t| (t.id, edit::find_overlaps(&t.clips)));
+        video.chain(audio)
+            .flat_map(|(track_id, overlaps)| {
+                overlaps.into_iter().map(move |(clip_id, other_clip_id, (start_ms, end_ms))| ClipOverlap {
+                    track_id,
+                    clip_id,
+                    other_clip_id,
+                    start_ms,
+                    end_ms,
+                })
+            })
+            .collect()
+    }
+
+    /// 모든 트랙의 빈 구간 제거 (트랙별로 클립을 앞으로 당김, 잠긴 트랙 제외)
+    /// 트랙마다 독립적으로 당기므로 트랙 간 싱크는 유지되지 않음
+    /// 반환: 제거한 빈 구간 수 (0이면 히스토리 기록 없음)
+    pub fn close_all_gaps(&mut self) -> usize {
+        let locked = self.locked_track_ids();
+        let in_scope = |id: u64| !locked.contains(&id);
+        let (video_before, audio_before) = self.track_snapshots(in_scope);
+
+        let mut closed = 0;
+        for track in self.video_tracks.iter_mut().filter(|t| in_scope(t.id)) {
+            closed += edit::close_gaps(&mut track.clips);
+        }
+        for track in self.audio_tracks.iter_mut().filter(|t| in_scope(t.id)) {
+            closed += edit::close_gaps(&mut track.clips);
+        }
+
+        if closed > 0 {
+            self.record_track_changes(video_before, audio_before);
+        }
+        closed
+    }
+
+    /// 잠긴 트랙 ID 목록 (비디오+오디오)
+    fn locked_track_ids(&self) -> Vec<u64> {
+        self.video_tracks.iter().filter(|t| t.locked).map(|t| t.id)
+            .chain(self.audio_tracks.iter().filter(|t| t.locked).map(|t| t.id))
+            .collect()
+    }
+
+    /// 편집 대상 트랙 스냅샷 (편집 전 상태)
+    fn track_snapshots(&self, in_scope: impl Fn(u64) -> bool) -> (Vec<VideoTrack>, Vec<AudioTrack>) {
+        (
+            self.video_tracks.iter().filter(|t| in_scope(t.id)).cloned().collect(),
+            self.audio_tracks.iter().filter(|t| in_scope(t.id)).cloned().collect(),
+        )
+    }
+
+    /// 스냅샷 트랙들의 편집 전/후를 하나의 히스토리 항목으로 기록
+    fn record_track_changes(&mut self, video_before: Vec<VideoTrack>, audio_before: Vec<AudioTrack>) {
+        let mut commands = Vec::new();
+        for before in video_before {
+            if let Some(after) = self.video_tracks.iter().find(|t| t.id == before.id) {
+                commands.push(EditCommand::VideoTrackEdit { before: Box::new(before), after: Box::new(after.clone()) });
+            }
+        }
+        for before in audio_before {
+            if let Some(after) = self.audio_tracks.iter().find(|t| t.id == before.id) {
+                commands.push(EditCommand::AudioTrackEdit { before: Box::new(before), after: Box::new(after.clone()) });
+            }
+        }
+        self.history.record(EditCommand::Batch(commands));
+    }
+
+    /// 마지막 편집 되돌리기 (되돌릴 편집이 없으면 false)
+    pub fn undo(&mut self) -> bool {
+        match self.history.take_undo() {
+            Some(command) => {
+                self.revert_command(&command);
+                self.history.push_redo(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 되돌린 편집 다시 실행 (다시 실행할 편집이 없으면 false)
+    pub fn redo(&mut self) -> bool {
+        match self.history.take_redo() {
+            Some(command) => {
+                self.apply_command(&command);
+                self.history.push_undo(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Undo/Redo 히스토리
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// 히스토리 초기화 (프로젝트 로드 직후 등)
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// 명령 재실행 (Redo, 히스토리 기록 없음)
+    fn apply_command(&mut self, command: &EditCommand) {
+        match command {
+            EditCommand::AddVideoTrack { track_id } => {
+                let index = self.video_tracks.len();
+                self.video_tracks.push(VideoTrack::new(*track_id, index));
+            }
+            EditCommand::AddAudioTrack { track_id } => {
+                let index = self.audio_tracks.len();
+                self.audio_tracks.push(AudioTrack::new(*track_id, index));
+            }
+            EditCommand::RemoveVideoTrack { track, .. } => {
+                self.video_tracks.retain(|t| t.id != track.id);
+                self.reindex_tracks();
+            }
+            EditCommand::RemoveAudioTrack { track, .. } => {
+                self.audio_tracks.retain(|t| t.id != track.id);
+                self.reindex_tracks();
+            }
+            EditCommand::MoveVideoTrack { from, to, .. } => {
+                move_item(&mut self.video_tracks, *from, *to);
+                self.reindex_tracks();
+            }
+            EditCommand::MoveAudioTrack { from, to, .. } => {
+                move_item(&mut self.audio_tracks, *from, *to);
+                self.reindex_tracks();
+            }
+            EditCommand::AddVideoClip { track_id, clip } => {
+                if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.add_clip(clip.clone());
+                }
+            }
+            EditCommand::AddAudioClip { track_id, clip } => {
+                if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.add_clip(clip.clone());
+                }
+            }
+            EditCommand::RemoveVideoClip { track_id, clip, .. } => {
+                if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.remove_clip(clip.id);
+                }
+            }
+            EditCommand::RemoveAudioClip { track_id, clip } => {
+                if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.remove_clip(clip.id);
+                }
+            }
+            EditCommand::SplitVideoClip { track_id, original, head, tail } => {
+                if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    if let Some(clip) = track.get_clip_by_id_mut(original.id) {
+                        clip.restore_placement(head);
+                        track.add_clip((**tail).clone());
+                        remap_transition_source(track, original.id, tail.id);
+                    }
+                }
+            }
+            EditCommand::SplitAudioClip { track_id, original, head, tail } => {
+                if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    if let Some(clip) = track.clips.iter_mut().find(|c| c.id == original.id) {
+                        clip.restore_placement(head);
+                        // 분할은 페이드 아웃을 뒷부분으로 넘김
+                        clip.fade_out_ms = head.fade_out_ms;
+                        track.add_clip((**tail).clone());
+                    }
+                }
+            }
+            EditCommand::VideoTrackEdit { after, .. } => self.restore_video_track(after),
+            EditCommand::AudioTrackEdit { after, .. } => self.restore_audio_track(after),
+            EditCommand::Batch(commands) => {
+                for command in commands {
+                    self.apply_command(command);
+                }
+            }
+        }
+    }
+
+    /// 명령 되돌리기 (Undo, 히스토리 기록 없음)
+    fn revert_command(&mut self, command: &EditCommand) {
+        match command {
+            // 이후 편집은 먼저 되돌려지므로 트랙은 비어 있음
+            EditCommand::AddVideoTrack { track_id } => {
+                self.video_tracks.retain(|t| t.id != *track_id);
+                self.reindex_tracks();
+            }
+            EditCommand::AddAudioTrack { track_id } => {
+                self.audio_tracks.retain(|t| t.id != *track_id);
+                self.reindex_tracks();
+            }
+            EditCommand::RemoveVideoTrack { position, track } => {
+                let position = (*position).min(self.video_tracks.len());
+                self.video_tracks.insert(position, (**track).clone());
+                self.reindex_tracks();
+            }
+            EditCommand::RemoveAudioTrack { position, track } => {
+                let position = (*position).min(self.audio_tracks.len());
+                self.audio_tracks.insert(position, (**track).clone());
+                self.reindex_tracks();
+            }
+            EditCommand::MoveVideoTrack { from, to, .. } => {
+                move_item(&mut self.video_tracks, *to, *from);
+                self.reindex_tracks();
+            }
+            EditCommand::MoveAudioTrack { from, to, .. } => {
+                move_item(&mut self.audio_tracks, *to, *from);
+                self.reindex_tracks();
+            }
+            EditCommand::AddVideoClip { track_id, clip } => {
+                if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.remove_clip(clip.id);
+                }
+            }
+            EditCommand::AddAudioClip { track_id, clip } => {
+                if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.remove_clip(clip.id);
+                }
+            }
+            EditCommand::RemoveVideoClip { track_id, clip, transitions } => {
+                if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.add_clip(clip.clone());
+                    track.transitions.extend(transitions.iter().copied());
+                }
+            }
+            EditCommand::RemoveAudioClip { track_id, clip } => {
+                if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.add_clip(clip.clone());
+                }
+            }
+            EditCommand::SplitVideoClip { track_id, original, tail, .. } => {
+                if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    remap_transition_source(track, tail.id, original.id);
+                    track.remove_clip(tail.id);
+                    if let Some(clip) = track.get_clip_by_id_mut(original.id) {
+                        clip.restore_placement(original);
+                    }
+                }
+            }
+            EditCommand::SplitAudioClip { track_id, original, tail, .. } => {
+                if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == *track_id) {
+                    track.remove_clip(tail.id);
+                    if let Some(clip) = track.clips.iter_mut().find(|c| c.id == original.id) {
+                        clip.restore_placement(original);
+                        clip.fade_out_ms = original.fade_out_ms;
+                    }
+                }
+            }
+            EditCommand::VideoTrackEdit { before, .. } => self.restore_video_track(before),
+            EditCommand::AudioTrackEdit { before, .. } => self.restore_audio_track(before),
+            EditCommand::Batch(commands) => {
+                for command in commands.iter().rev() {
+                    self.revert_command(command);
+                }
+            }
+        }
+    }
+
+    /// 트랙 스냅샷 복원 — 편집이 바꾸는 클립 구성/배치와 트랜지션만 되돌림
+    /// 트랙 설정(on/off, 그레이딩 등)과 클립 속성(트랜스폼/이펙트/LUT 등)은 히스토리 밖 → 현재 값 유지
+    fn restore_video_track(&mut self, snapshot: &VideoTrack) {
+        if let Some(track) = self.video_tracks.iter_mut().find(|t| t.id == snapshot.id) {
+            restore_clips(&mut track.clips, &snapshot.clips);
+            // 남은 클립 사이의 현재 트랜지션 유지, 편집으로 사라진 트랜지션은 스냅샷에서 복원
+            let clips = &track.clips;
+            let has_clip = |id: u64| clips.iter().any(|c| c.id == id);
+            track.transitions.retain(|t| has_clip(t.from_clip_id) && has_clip(t.to_clip_id));
+            for transition in &snapshot.transitions {
+                let linked = track.transitions.iter()
+                    .any(|t| t.from_clip_id == transition.from_clip_id || t.to_clip_id == transition.to_clip_id);
+                if !linked && has_clip(transition.from_clip_id) && has_clip(transition.to_clip_id) {
+                    track.transitions.push(*transition);
+                }
+            }
+        }
+    }
+
+    fn restore_audio_track(&mut self, snapshot: &AudioTrack) {
+        if let Some(track) = self.audio_tracks.iter_mut().find(|t| t.id == snapshot.id) {
+            restore_clips(&mut track.clips, &snapshot.clips);
+        }
+    }
+
+    /// 마커 추가 (시간 순 유지, 같은 시각이면 뒤에) → 마커 ID
+    pub fn add_marker(&mut self, time_ms: i64, name: &str, color: [u8; 3]) -> u64 {
+        let id = self.next_marker_id;
+        self.next_marker_id += 1;
+
+        let index = self.markers.partition_point(|m| m.time_ms <= time_ms);
+        self.markers.insert(index, Marker::new(id, time_ms, name, color));
+        id
+    }
+
+    /// 마커 제거 (없으면 false)
+    pub fn remove_marker(&mut self, marker_id: u64) -> bool {
+        let before = self.markers.len();
+        self.markers.retain(|m| m.id != marker_id);
+        self.markers.len() != before
+    }
+
+    /// 마커 목록 (시간 순)
+    pub fn markers(&self) -> &[Marker] {
+        &self.markers
+    }
+
+    /// Export 구간의 챕터 (구간 시작 기준 시간)
+    pub fn chapters(&self, range: (i64, i64)) -> Vec<Chapter> {
+        marker::chapters(&self.markers, range)
+    }
+
+    /// 스냅: candidate_ms에서 tolerance_ms 이내의 가장 가까운 지점 (없으면 None)
+    /// 지점: 0, 모든 트랙의 클립 시작/끝, 마커, 재생 헤드
+    /// - exclude_clip_id: 드래그 중인 클립 (자기 경계에는 스냅하지 않음)
+    pub fn snap_time(
+        &self,
+        candidate_ms: i64,
+        tolerance_ms: i64,
+        playhead_ms: Option<i64>,
+        exclude_clip_id: Option<u64>,
+    ) -> Option<i64> {
+        let included = |id: u64| exclude_clip_id != Some(id);
+        let video_edges = self.video_tracks.iter().flat_map(|t| &t.clips)
+            .filter(|c| included(c.id))
+            .flat_map(|c| [c.start_time_ms, c.end_time_ms()]);
+        let audio_edges = self.audio_tracks.iter().flat_map(|t| &t.clips)
+            .filter(|c| included(c.id))
+            .flat_map(|c| [c.start_time_ms, c.end_time_ms()]);
+        let markers = self.markers.iter().map(|m| m.time_ms);
+
+        std::iter::once(0)
+            .chain(video_edges)
+            .chain(audio_edges)
+            .chain(markers)
+            .chain(playhead_ms)
+            .filter(|point| (point - candidate_ms).abs() <= tolerance_ms)
+            .min_by_key(|point| (point - candidate_ms).abs())
     }
 
     /// 타임라인 총 길이 계산 (ms)
@@ -126,6 +1026,59 @@ impl Timeline {
         video_max.max(audio_max)
     }
 
+    /// 프레임 N의 시작 시간 (ms, 프로젝트 fps 격자 — Export의 N번째 프레임 시각과 동일)
+    pub fn frame_time_ms(&self, index: i64) -> i64 {
+        frame_to_ms(index, self.fps)
+    }
+
+    /// 시간이 속한 프레임 번호 (frame_time_ms(N) <= t < frame_time_ms(N + 1))
+    pub fn frame_at(&self, timestamp_ms: i64) -> i64 {
+        ms_to_frame(timestamp_ms, self.fps)
+    }
+
+    /// 전체 프레임 수 (Export 프레임 수와 동일)
+    pub fn frame_count(&self) -> i64 {
+        let duration_ms = self.duration_ms();
+        if duration_ms > 0 { self.frame_at(duration_ms - 1) + 1 } else { 0 }
+    }
+
+    /// 타임라인 통계 계산
+    pub fn stats(&self) -> TimelineStats {
+        let mut sources = std::collections::HashSet::new();
+        let mut media_duration_ms = 0i64;
+
+        for clip in self.video_tracks.iter().flat_map(|t| &t.clips) {
+            if !clip.is_generated() {
+                sources.insert(&clip.file_path);
+            }
+            media_duration_ms += clip.duration_ms;
+        }
+        for clip in self.audio_tracks.iter().flat_map(|t| &t.clips) {
+            sources.insert(&clip.file_path);
+            media_duration_ms += clip.duration_ms;
+        }
+
+        TimelineStats {
+            video_tracks: self.video_tracks.iter().map(|t| TrackStats {
+                track_id: t.id,
+                clip_count: t.clips.len(),
+                enabled: t.enabled,
+            }).collect(),
+            audio_tracks: self.audio_tracks.iter().map(|t| TrackStats {
+                track_id: t.id,
+                clip_count: t.clips.len(),
+                enabled: t.enabled,
+            }).collect(),
+            source_file_count: sources.len(),
+            media_duration_ms,
+            timeline_duration_ms: self.duration_ms(),
+            interpolated_clip_count: self.video_tracks.iter()
+                .flat_map(|t| &t.clips)
+                .filter(|c| c.frame_interpolation)
+                .count(),
+        }
+    }
+
     /// 특정 시간에 활성화된 비디오 클립들 찾기 (모든 트랙)
     pub fn get_video_clips_at_time(&self, time_ms: i64) -> Vec<(&VideoTrack, &VideoClip)> {
         let mut clips = Vec::new();
@@ -144,42 +1097,110 @@ impl Timeline {
 
     /// 특정 시간에 활성화된 오디오 클립들 찾기 (모든 트랙)
     pub fn get_audio_clips_at_time(&self, time_ms: i64) -> Vec<&AudioClip> {
+        let any_solo = self.has_solo_audio_track();
         self.audio_tracks
             .iter()
+            .filter(|track| track.is_audible(any_solo))
             .flat_map(|track| track.get_clips_at_time(time_ms))
             .collect()
     }
 
+    /// 마스터 그레이딩(LUT/이펙트) 설정 여부
+    pub fn has_master_grade(&self) -> bool {
+        self.master_lut_path.is_some() || !self.master_effects.is_default()
+    }
+
+    /// 솔로 지정된 (활성) 오디오 트랙 존재 여부
+    pub fn has_solo_audio_track(&self) -> bool {
+        self.audio_tracks.iter().any(|t| t.enabled && t.solo)
+    }
+
     /// 특정 시간에 오디오를 제공할 수 있는 모든 소스 (오디오 트랙 + 비디오 트랙)
     /// 비디오 파일에도 오디오 스트림이 있으므로, 비디오 클립도 AudioClip으로 변환하여 반환
-    pub fn get_all_audio_sources_at_time(&self, time_ms: i64) -> Vec<AudioClip> {
+    /// 오디오 트랙 솔로 중에는 비디오 클립 오디오도 제외
+    pub fn get_all_audio_sources_at_time(&self, time_ms: i64) -> Vec<AudioSource> {
         let mut sources = Vec::new();
+        let any_solo = self.has_solo_audio_track();
 
-        // 오디오 트랙의 클립
-        for clip in self.get_audio_clips_at_time(time_ms) {
-            sources.push(clip.clone());
+        // 오디오 트랙의 클립 (트랙 볼륨/팬, 겹친 클립 크로스페이드 포함)
+        for track in self.audio_tracks.iter().filter(|t| t.is_audible(any_solo)) {
+            for clip in track.get_clips_at_time(time_ms) {
+                sources.push(AudioSource {
+                    clip: track.with_crossfades(clip),
+                    track_volume: track.volume,
+                    pan: track.pan,
+                    track_id: Some(track.id),
+                    track_effects: track.effects.clone(),
+                });
+            }
+        }
+        if any_solo {
+            return sources;
         }
 
-        // 비디오 트랙의 클립 → AudioClip으로 변환 (비디오 파일의 오디오 스트림 추출)
-        for (_, video_clip) in self.get_video_clips_at_time(time_ms) {
-            sources.push(AudioClip {
+        // 비디오 트랙의 클립 → AudioClip으로 변환 (비디오 파일의 오디오 스트림 추출, 이미지/타이틀/소스 오디오 끈 클립 제외)
+        let video_clips = self.get_video_clips_at_time(time_ms).into_iter()
+            .filter(|(_, c)| c.use_source_audio && !c.is_image() && !c.is_generated());
+        for (_, video_clip) in video_clips {
+            sources.push(AudioSource::new(AudioClip {
                 id: video_clip.id,
                 file_path: video_clip.file_path.clone(),
                 start_time_ms: video_clip.start_time_ms,
                 duration_ms: video_clip.duration_ms,
                 trim_start_ms: video_clip.trim_start_ms,
                 trim_end_ms: video_clip.trim_end_ms,
+                speed: video_clip.speed,
+                preserve_pitch: false,
                 volume: 1.0,
-            });
+                fade_in_ms: 0,
+                fade_out_ms: 0,
+                stream_index: None,
+                volume_envelope: KeyframeTrack::default(),
+                effects: AudioEffectParams::default(),
+            }));
         }
 
         sources
     }
 }
 
+/// 프레임 N의 시작 시간 (ms 내림, fps > 0)
+/// 프리뷰 스텝/재생과 Export가 같은 함수를 써야 ms 반올림 차이로 프레임이 반복/누락되지 않음
+pub fn frame_to_ms(index: i64, fps: f64) -> i64 {
+    (index as f64 * 1000.0 / fps).floor() as i64
+}
+
+/// 시간이 속한 프레임 번호 (frame_to_ms의 역함수, 부동소수 오차는 경계 확인으로 보정)
+pub fn ms_to_frame(timestamp_ms: i64, fps: f64) -> i64 {
+    let mut index = (timestamp_ms as f64 * fps / 1000.0).floor() as i64;
+    while frame_to_ms(index + 1, fps) <= timestamp_ms {
+        index += 1;
+    }
+    while index > i64::MIN && frame_to_ms(index, fps) > timestamp_ms {
+        index -= 1;
+    }
+    index
+}
+
+/// from_clip_id 기준 트랜지션을 다른 클립으로 이동 (분할/분할 취소)
+fn remap_transition_source(track: &mut VideoTrack, from_clip_id: u64, to_clip_id: u64) {
+    for transition in track.transitions.iter_mut().filter(|t| t.from_clip_id == from_clip_id) {
+        transition.from_clip_id = to_clip_id;
+    }
+}
+
+/// 목록 안에서 항목 위치 이동 (트랙 순서 변경/되돌리기)
+fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) {
+    if from < items.len() && to < items.len() {
+        let item = items.remove(from);
+        items.insert(to, item);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::timeline::{ClipTransform, Interpolation, Keyframe, KeyframeProperty};
     use std::path::PathBuf;
 
     #[test]
@@ -190,6 +1211,373 @@ mod tests {
         assert_eq!(timeline.fps, 30.0);
         assert_eq!(timeline.video_tracks.len(), 0);
         assert_eq!(timeline.audio_tracks.len(), 0);
+        assert_eq!(timeline.background().solid_rgb(), Some([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_markers() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let outro = timeline.add_marker(9000, "Outro", [0, 0, 255]);
+        let intro = timeline.add_marker(0, "Intro", [255, 0, 0]);
+        timeline.add_marker(4000, "Demo", [0, 255, 0]);
+
+        let names: Vec<&str> = timeline.markers().iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["Intro", "Demo", "Outro"]);
+        assert_ne!(intro, outro);
+
+        assert!(timeline.remove_marker(outro));
+        assert!(!timeline.remove_marker(outro));
+        let chapters = timeline.chapters((0, 10000));
+        assert_eq!(chapters.len(), 2);
+        assert_eq!((chapters[1].start_ms, chapters[1].end_ms), (4000, 10000));
+    }
+
+    #[test]
+    fn test_track_management() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let v1 = timeline.add_video_track();
+        let v2 = timeline.add_video_track();
+        let v3 = timeline.add_video_track();
+        let clip = timeline.add_video_clip(v1, PathBuf::from("a.mp4"), 0, 1000).unwrap();
+
+        // 순서 변경: index = 목록 위치
+        assert!(timeline.move_track(v3, 0));
+        let order: Vec<(u64, usize)> = timeline.video_tracks.iter().map(|t| (t.id, t.index)).collect();
+        assert_eq!(order, vec![(v3, 0), (v1, 1), (v2, 2)]);
+        assert!(!timeline.move_track(v3, 3));
+
+        // 잠금: 클립 편집/트랙 제거 거부, 순서 변경은 허용
+        assert!(timeline.set_track_name(v1, "Main"));
+        assert!(timeline.set_track_locked(v1, true));
+        assert!(timeline.add_video_clip(v1, PathBuf::from("b.mp4"), 2000, 1000).is_none());
+        assert!(!timeline.move_clip(v1, clip, 500, OverlapPolicy::Reject));
+        assert!(!timeline.remove_video_clip(v1, clip));
+        assert!(timeline.editable_video_track_mut(v1).is_none());
+        assert!(!timeline.remove_track(v1));
+        // on/off는 트랙 설정 → 잠겨 있어도 허용
+        assert!(timeline.set_track_enabled(v1, false));
+        assert!(!timeline.video_tracks.iter().find(|t| t.id == v1).unwrap().enabled);
+        assert!(timeline.set_track_enabled(v1, true));
+        assert!(!timeline.set_track_enabled(999, false));
+        timeline.set_track_locked(v1, false);
+
+        // 이전 편집 Undo는 on/off 상태를 건드리지 않음
+        assert!(timeline.move_clip(v1, clip, 500, OverlapPolicy::Reject));
+        assert!(timeline.set_track_enabled(v1, false));
+        assert!(timeline.undo());
+        let track = timeline.video_tracks.iter().find(|t| t.id == v1).unwrap();
+        assert_eq!((track.clips[0].start_time_ms, track.enabled), (0, false));
+        assert!(timeline.set_track_enabled(v1, true));
+
+        // 제거 + Undo: 원래 위치/클립 복원, 이름 유지
+        assert!(timeline.remove_track(v1));
+        let order: Vec<(u64, usize)> = timeline.video_tracks.iter().map(|t| (t.id, t.index)).collect();
+        assert_eq!(order, vec![(v3, 0), (v2, 1)]);
+        assert!(timeline.undo());
+        assert_eq!(timeline.video_tracks[1].id, v1);
+        assert_eq!(timeline.video_tracks[1].name, "Main");
+        assert_eq!(timeline.video_tracks[1].clips.len(), 1);
+
+        // 순서 변경 Undo/Redo
+        assert!(timeline.undo());
+        assert_eq!(timeline.video_tracks.iter().map(|t| t.id).collect::<Vec<_>>(), vec![v1, v2, v3]);
+        assert!(timeline.redo());
+        assert_eq!(timeline.video_tracks[0].id, v3);
+        assert!(timeline.video_tracks.iter().enumerate().all(|(i, t)| t.index == i));
+    }
+
+    #[test]
+    fn test_gaps_overlaps_and_close() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let video = timeline.add_video_track();
+        let audio = timeline.add_audio_track();
+        let locked = timeline.add_audio_track();
+        timeline.add_video_clip(video, PathBuf::from("a.mp4"), 0, 1000);
+        timeline.add_video_clip(video, PathBuf::from("b.mp4"), 2000, 1000);
+        timeline.add_audio_clip(audio, PathBuf::from("a.wav"), 500, 1000);
+        let overlapping = timeline.add_audio_clip(audio, PathBuf::from("b.wav"), 1200, 1000).unwrap();
+        timeline.add_audio_clip(locked, PathBuf::from("c.wav"), 3000, 1000);
+        timeline.set_track_locked(locked, true);
+
+        assert_eq!(timeline.find_gaps(video), Some(vec![(1000, 2000)]));
+        assert_eq!(timeline.find_gaps(audio), Some(vec![(0, 500)]));
+        assert_eq!(timeline.find_gaps(999), None);
+        let overlaps = timeline.find_overlaps();
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!((overlaps[0].track_id, overlaps[0].other_clip_id), (audio, overlapping));
+        assert_eq!((overlaps[0].start_ms, overlaps[0].end_ms), (1200, 1500));
+
+        // 잠긴 트랙은 그대로, 나머지는 한 번의 Undo로 복원
+        assert_eq!(timeline.close_all_gaps(), 2);
+        assert_eq!(timeline.video_tracks[0].clips[1].start_time_ms, 1000);
+        assert_eq!(timeline.audio_tracks[0].clips[0].start_time_ms, 0);
+        assert_eq!(timeline.audio_tracks[1].clips[0].start_time_ms, 3000);
+        assert_eq!(timeline.close_all_gaps(), 0);
+        assert!(timeline.undo());
+        assert_eq!(timeline.find_gaps(video), Some(vec![(1000, 2000)]));
+    }
+
+    #[test]
+    fn test_snap_time() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let video = timeline.add_video_track();
+        let audio = timeline.add_audio_track();
+        let dragged = timeline.add_video_clip(video, PathBuf::from("a.mp4"), 1000, 1000).unwrap();
+        timeline.add_audio_clip(audio, PathBuf::from("a.wav"), 2500, 1000);
+        timeline.add_marker(4000, "Drop", [255, 0, 0]);
+
+        assert_eq!(timeline.snap_time(1040, 100, None, None), Some(1000));
+        assert_eq!(timeline.snap_time(2440, 100, None, None), Some(2500));
+        assert_eq!(timeline.snap_time(3950, 100, None, None), Some(4000));
+        assert_eq!(timeline.snap_time(30, 100, None, None), Some(0));
+        assert_eq!(timeline.snap_time(5000, 100, Some(5080), None), Some(5080));
+        assert_eq!(timeline.snap_time(1500, 100, None, None), None);
+
+        // 드래그 중인 클립의 경계는 제외
+        assert_eq!(timeline.snap_time(1040, 100, None, Some(dragged)), None);
+    }
+
+    #[test]
+    fn test_split_video_clip() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        let clip_id = timeline
+            .add_video_clip(track_id, PathBuf::from("test.mp4"), 1000, 6000)
+            .unwrap();
+        timeline.video_tracks[0].clips[0].trim_start_ms = 500;
+        timeline.video_tracks[0].clips[0].trim_end_ms = 6500;
+
+        // 클립 경계는 분할 불가
+        assert!(timeline.split_video_clip(track_id, clip_id, 1000).is_none());
+        assert!(timeline.split_video_clip(track_id, clip_id, 7000).is_none());
+
+        let tail_id = timeline.split_video_clip(track_id, clip_id, 3000).unwrap();
+        let track = &timeline.video_tracks[0];
+        assert_eq!(track.clips.len(), 2);
+
+        let head = track.get_clip_by_id(clip_id).unwrap();
+        assert_eq!((head.start_time_ms, head.duration_ms), (1000, 2000));
+        assert_eq!((head.trim_start_ms, head.trim_end_ms), (500, 2500));
+
+        let tail = track.get_clip_by_id(tail_id).unwrap();
+        assert_eq!((tail.start_time_ms, tail.duration_ms), (3000, 4000));
+        assert_eq!((tail.trim_start_ms, tail.trim_end_ms), (2500, 6500));
+    }
+
+    #[test]
+    fn test_split_clip_audio_and_keyframes() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let video_track = timeline.add_video_track();
+        let audio_track = timeline.add_audio_track();
+        let video_id = timeline
+            .add_video_clip(video_track, PathBuf::from("test.mp4"), 0, 4000)
+            .unwrap();
+        let audio_id = timeline
+            .add_audio_clip(audio_track, PathBuf::from("test.wav"), 1000, 4000)
+            .unwrap();
+
+        // 뒷부분 키프레임은 새 클립 시작 기준 시간으로 이동
+        timeline.video_tracks[0].clips[0].keyframes.set(
+            KeyframeProperty::Opacity,
+            Keyframe::new(3000, 0.5, Interpolation::Linear),
+        );
+        let video_tail = timeline.split_clip(video_track, video_id, 1000).unwrap();
+        let tail = timeline.video_tracks[0].get_clip_by_id(video_tail).unwrap();
+        assert_eq!(tail.keyframes.track(KeyframeProperty::Opacity).unwrap().keyframes()[0].time_ms, 2000);
+
+        let audio_tail = timeline.split_clip(audio_track, audio_id, 2500).unwrap();
+        let clips = &timeline.audio_tracks[0].clips;
+        assert_eq!((clips[0].id, clips[0].duration_ms, clips[0].trim_end_ms), (audio_id, 1500, 1500));
+        assert_eq!((clips[1].id, clips[1].start_time_ms, clips[1].trim_start_ms), (audio_tail, 2500, 1500));
+
+        assert!(timeline.split_clip(audio_track, audio_id, 2500).is_none());
+        assert!(timeline.undo());
+        assert_eq!(timeline.audio_tracks[0].clips.len(), 1);
+        assert_eq!(timeline.audio_tracks[0].clips[0].duration_ms, 4000);
+    }
+
+    #[test]
+    fn test_move_clip_overwrite_undo() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        let a = timeline.add_video_clip(track_id, PathBuf::from("a.mp4"), 0, 3000).unwrap();
+        let b = timeline.add_video_clip(track_id, PathBuf::from("b.mp4"), 5000, 1000).unwrap();
+
+        assert!(!timeline.move_clip(track_id, b, 2000, OverlapPolicy::Reject));
+        assert!(timeline.move_clip(track_id, b, 1000, OverlapPolicy::Overwrite));
+
+        // a는 b 앞뒤로 분할됨 (a 앞부분 + b + a 뒷부분)
+        let track = &timeline.video_tracks[0];
+        let ids: Vec<u64> = track.clips.iter().map(|c| c.id).collect();
+        assert_eq!(ids.len(), 3);
+        assert_eq!((ids[0], ids[1]), (a, b));
+        assert_eq!(track.clips[2].trim_start_ms, 2000);
+
+        // Undo 한 번으로 트랙 전체 복원
+        assert!(timeline.undo());
+        let track = &timeline.video_tracks[0];
+        assert_eq!(track.clips.len(), 2);
+        assert_eq!(track.get_clip_by_id(b).unwrap().start_time_ms, 5000);
+        assert!(timeline.redo());
+        assert_eq!(timeline.video_tracks[0].clips.len(), 3);
+    }
+
+    #[test]
+    fn test_undo_keeps_unrecorded_properties() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        let clip_id = timeline.add_video_clip(track_id, PathBuf::from("a.mp4"), 0, 3000).unwrap();
+        assert!(timeline.move_clip(track_id, clip_id, 1000, OverlapPolicy::Reject));
+
+        // 이동 후 히스토리 밖 속성 변경
+        let transform = ClipTransform { offset_x: 0.25, opacity: 0.5, ..ClipTransform::default() };
+        timeline.video_tracks[0].get_clip_by_id_mut(clip_id).unwrap().transform = transform;
+        timeline.video_tracks[0].effects.brightness = 0.3;
+
+        // 이동만 되돌리고 트랜스폼/트랙 그레이딩은 유지
+        assert!(timeline.undo());
+        let clip = timeline.video_tracks[0].get_clip_by_id(clip_id).unwrap();
+        assert_eq!(clip.start_time_ms, 0);
+        assert_eq!(clip.transform, transform);
+        assert_eq!(timeline.video_tracks[0].effects.brightness, 0.3);
+        assert!(timeline.redo());
+        let clip = timeline.video_tracks[0].get_clip_by_id(clip_id).unwrap();
+        assert_eq!((clip.start_time_ms, clip.transform), (1000, transform));
+
+        // 분할 Undo도 원본 클립 속성 유지
+        timeline.split_video_clip(track_id, clip_id, 2000).unwrap();
+        timeline.video_tracks[0].get_clip_by_id_mut(clip_id).unwrap().lut_path = Some(PathBuf::from("look.cube"));
+        assert!(timeline.undo());
+        let clip = timeline.video_tracks[0].get_clip_by_id(clip_id).unwrap();
+        assert_eq!((clip.duration_ms, clip.lut_path.clone()), (3000, Some(PathBuf::from("look.cube"))));
+
+        // 앞부분 트림 Undo: 트림이 옮긴 키프레임은 원래 위치로
+        timeline.video_tracks[0].get_clip_by_id_mut(clip_id).unwrap().keyframes.set(
+            KeyframeProperty::Opacity,
+            Keyframe::new(2000, 0.5, Interpolation::Linear),
+        );
+        assert!(timeline.trim_clip(track_id, clip_id, 500, 3000, OverlapPolicy::Reject));
+        assert!(timeline.undo());
+        let clip = timeline.video_tracks[0].get_clip_by_id(clip_id).unwrap();
+        assert_eq!(clip.trim_start_ms, 0);
+        assert_eq!(clip.keyframes.track(KeyframeProperty::Opacity).unwrap().keyframes()[0].time_ms, 2000);
+    }
+
+    #[test]
+    fn test_set_video_clip_trim_keeps_placement() {
+        // Razor 분할 뒷부분처럼 호스트가 시작 위치를 정한 클립 (앞 클립과 맞닿음)
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        timeline.add_video_clip(track_id, PathBuf::from("a.mp4"), 0, 2000).unwrap();
+        let tail = timeline.add_video_clip(track_id, PathBuf::from("a.mp4"), 2000, 1000).unwrap();
+
+        assert!(timeline.set_video_clip_trim(track_id, tail, 2000, 3000));
+        let clip = timeline.video_tracks[0].get_clip_by_id(tail).unwrap();
+        assert_eq!((clip.start_time_ms, clip.duration_ms), (2000, 1000));
+        assert_eq!((clip.trim_start_ms, clip.trim_end_ms), (2000, 3000));
+        assert!(!timeline.set_video_clip_trim(track_id, tail, 3000, 3000));
+
+        assert!(timeline.undo());
+        let clip = timeline.video_tracks[0].get_clip_by_id(tail).unwrap();
+        assert_eq!((clip.start_time_ms, clip.trim_start_ms, clip.trim_end_ms), (2000, 0, 1000));
+    }
+
+    #[test]
+    fn test_ripple_remove_and_insert() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let video = timeline.add_video_track();
+        let audio = timeline.add_audio_track();
+        let a = timeline.add_video_clip(video, PathBuf::from("a.mp4"), 0, 2000).unwrap();
+        let b = timeline.add_video_clip(video, PathBuf::from("b.mp4"), 2000, 2000).unwrap();
+        let c = timeline.add_video_clip(video, PathBuf::from("c.mp4"), 4000, 2000).unwrap();
+        let music = timeline.add_audio_clip(audio, PathBuf::from("m.wav"), 5000, 1000).unwrap();
+
+        // 단일 트랙 리플 삭제: c만 당겨짐
+        assert!(timeline.ripple_remove_clip(video, b, false));
+        assert_eq!(timeline.video_tracks[0].get_clip_by_id(c).unwrap().start_time_ms, 2000);
+        assert_eq!(timeline.audio_tracks[0].clips[0].start_time_ms, 5000);
+        assert!(timeline.undo());
+        assert_eq!(timeline.video_tracks[0].clips.len(), 3);
+
+        // 전체 트랙 리플 삭제: 오디오도 같은 거리만큼 이동
+        assert!(timeline.ripple_remove_clip(video, b, true));
+        assert_eq!(timeline.audio_tracks[0].clips[0].start_time_ms, 3000);
+        assert!(timeline.undo());
+
+        // 리플 삽입: a 가운데 삽입 → a 분할, 이후 클립 1000ms 이동
+        let inserted = timeline.ripple_insert_clip(video, PathBuf::from("d.mp4"), 1000, 1000, true).unwrap();
+        let track = &timeline.video_tracks[0];
+        let starts: Vec<(u64, i64)> = track.clips.iter().map(|c| (c.id, c.start_time_ms)).collect();
+        assert_eq!(starts[0], (a, 0));
+        assert_eq!(starts[1], (inserted, 1000));
+        assert_eq!(starts[2].1, 2000);
+        assert_eq!(&starts[3..], &[(b, 3000), (c, 5000)]);
+        assert_eq!(timeline.audio_tracks[0].get_clips_at_time(6500)[0].id, music);
+
+        // Undo 한 번으로 모든 트랙 복원
+        assert!(timeline.undo());
+        assert_eq!(timeline.video_tracks[0].clips.len(), 3);
+        assert_eq!(timeline.audio_tracks[0].clips[0].start_time_ms, 5000);
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        let clip_id = timeline
+            .add_video_clip(track_id, PathBuf::from("test.mp4"), 0, 6000)
+            .unwrap();
+        let tail_id = timeline.split_video_clip(track_id, clip_id, 2000).unwrap();
+        assert!(timeline.remove_video_clip(track_id, tail_id));
+        assert_eq!(timeline.history().undo_depth(), 4);
+
+        // 제거 취소 → 분할 취소 → 원본 클립 복원
+        assert!(timeline.undo());
+        assert_eq!(timeline.video_tracks[0].clips.len(), 2);
+        assert!(timeline.undo());
+        let track = &timeline.video_tracks[0];
+        assert_eq!(track.clips.len(), 1);
+        assert_eq!(track.clips[0].duration_ms, 6000);
+        assert_eq!(track.clips[0].trim_end_ms, 6000);
+
+        // 클립 추가/트랙 추가 취소
+        assert!(timeline.undo());
+        assert!(timeline.undo());
+        assert!(timeline.video_tracks.is_empty());
+        assert!(!timeline.undo());
+        assert_eq!(timeline.history().redo_depth(), 4);
+
+        // 전부 다시 실행 (같은 ID 유지)
+        while timeline.redo() {}
+        let track = &timeline.video_tracks[0];
+        assert_eq!(track.id, track_id);
+        assert_eq!(track.clips.len(), 1);
+        assert_eq!((track.clips[0].id, track.clips[0].duration_ms), (clip_id, 2000));
+
+        // 새 편집은 Redo 스택 폐기
+        timeline.undo();
+        timeline.add_audio_track();
+        assert!(!timeline.redo());
+    }
+
+    #[test]
+    fn test_timeline_stats() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let video_track = timeline.add_video_track();
+        let audio_track = timeline.add_audio_track();
+
+        timeline.add_video_clip(video_track, PathBuf::from("a.mp4"), 0, 3000);
+        timeline.add_video_clip(video_track, PathBuf::from("a.mp4"), 5000, 2000);
+        timeline.add_audio_clip(audio_track, PathBuf::from("b.wav"), 0, 4000);
+
+        let stats = timeline.stats();
+        assert_eq!(stats.video_tracks.len(), 1);
+        assert_eq!(stats.video_tracks[0].clip_count, 2);
+        assert_eq!(stats.audio_tracks[0].clip_count, 1);
+        assert_eq!(stats.source_file_count, 2);
+        assert_eq!(stats.media_duration_ms, 9000);
+        assert_eq!(stats.timeline_duration_ms, 7000);
+        assert_eq!(stats.interpolated_clip_count, 0);
     }
 
     #[test]
@@ -222,6 +1610,44 @@ mod tests {
         assert_eq!(timeline.video_tracks[0].clips[0].id, clip_id.unwrap());
     }
 
+    #[test]
+    fn test_add_title_clip() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        timeline.add_video_clip(track_id, PathBuf::from("test.mp4"), 0, 5000);
+        let title_id = timeline.add_title_clip(track_id, TitleClip::new("Intro"), 5000, 2000).unwrap();
+
+        let clip = timeline.video_tracks[0].get_clip_by_id(title_id).unwrap();
+        assert!(clip.is_title());
+        assert_eq!(clip.title.as_ref().unwrap().text, "Intro");
+
+        // 타이틀은 오디오 소스/소스 파일이 아님
+        assert!(timeline.get_all_audio_sources_at_time(6000).is_empty());
+        assert_eq!(timeline.stats().source_file_count, 1);
+
+        assert!(timeline.undo());
+        assert!(timeline.video_tracks[0].get_clip_by_id(title_id).is_none());
+    }
+
+    #[test]
+    fn test_add_color_clip() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        timeline.add_video_clip(track_id, PathBuf::from("test.mp4"), 0, 5000);
+        let color_id = timeline.add_color_clip(track_id, ColorClip::solid([255, 0, 0, 255]), 5000, 2000).unwrap();
+
+        let clip = timeline.video_tracks[0].get_clip_by_id(color_id).unwrap();
+        assert!(clip.is_color() && clip.is_generated());
+        assert_eq!(clip.color, Some(ColorClip::solid([255, 0, 0, 255])));
+
+        // 컬러 클립도 오디오 소스/소스 파일이 아님
+        assert!(timeline.get_all_audio_sources_at_time(6000).is_empty());
+        assert_eq!(timeline.stats().source_file_count, 1);
+
+        timeline.video_tracks[0].locked = true;
+        assert!(timeline.add_color_clip(track_id, ColorClip::solid([0; 4]), 8000, 1000).is_none());
+    }
+
     #[test]
     fn test_remove_video_clip() {
         let mut timeline = Timeline::new(1920, 1080, 30.0);
@@ -254,6 +1680,28 @@ mod tests {
         assert_eq!(timeline.duration_ms(), 10000);
     }
 
+    #[test]
+    fn test_frame_index_round_trip() {
+        // 29.97fps: 프레임 시작은 ms 내림, 모든 ms는 정확히 한 프레임에 속함
+        let mut timeline = Timeline::new(1920, 1080, 30000.0 / 1001.0);
+        for index in 0..2000 {
+            let start = timeline.frame_time_ms(index);
+            assert_eq!(timeline.frame_at(start), index);
+            assert_eq!(timeline.frame_at(timeline.frame_time_ms(index + 1) - 1), index);
+        }
+        assert_eq!(timeline.frame_time_ms(1), 33);
+        assert_eq!(timeline.frame_time_ms(3), 100);
+
+        // 30fps 1초 = 30프레임 (Export 프레임 수와 동일)
+        timeline.fps = 30.0;
+        assert_eq!(timeline.frame_count(), 0);
+        let track = timeline.add_video_track();
+        timeline.add_video_clip(track, PathBuf::from("v1.mp4"), 0, 1000);
+        assert_eq!(timeline.frame_count(), 30);
+        assert_eq!(timeline.frame_at(999), 29);
+        assert_eq!(timeline.frame_at(1000), 30);
+    }
+
     #[test]
     fn test_get_clips_at_time() {
         let mut timeline = Timeline::new(1920, 1080, 30.0);
@@ -270,4 +1718,62 @@ mod tests {
         let clips_at_6000 = timeline.get_video_clips_at_time(6000);
         assert_eq!(clips_at_6000.len(), 0);
     }
+
+    #[test]
+    fn test_audio_sources_track_mix() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let video_track = timeline.add_video_track();
+        let music_track = timeline.add_audio_track();
+        let voice_track = timeline.add_audio_track();
+        timeline.add_video_clip(video_track, PathBuf::from("v1.mp4"), 0, 5000);
+        timeline.add_audio_clip(music_track, PathBuf::from("music.mp3"), 0, 5000);
+        timeline.add_audio_clip(voice_track, PathBuf::from("voice.wav"), 0, 5000);
+
+        timeline.audio_tracks[0].volume = 0.5;
+        timeline.audio_tracks[0].pan = -0.25;
+        let sources = timeline.get_all_audio_sources_at_time(1000);
+        assert_eq!(sources.len(), 3);
+        assert_eq!((sources[0].track_volume, sources[0].pan), (0.5, -0.25));
+        // 비디오 클립 오디오는 트랙 설정 없음
+        assert_eq!((sources[2].track_volume, sources[2].pan), (1.0, 0.0));
+
+        // 소스 오디오 끈 비디오 클립은 믹스에서 제외
+        timeline.video_tracks[0].clips[0].use_source_audio = false;
+        assert_eq!(timeline.get_all_audio_sources_at_time(1000).len(), 2);
+        timeline.video_tracks[0].clips[0].use_source_audio = true;
+
+        // 솔로: 솔로 트랙만 (비디오 클립 오디오 포함 나머지 제외)
+        timeline.audio_tracks[1].solo = true;
+        let sources = timeline.get_all_audio_sources_at_time(1000);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].clip.file_path, PathBuf::from("voice.wav"));
+
+        // 솔로 + 뮤트 → 무음, 비활성 트랙의 솔로는 무시
+        timeline.audio_tracks[1].muted = true;
+        assert!(timeline.get_all_audio_sources_at_time(1000).is_empty());
+        timeline.audio_tracks[1].enabled = false;
+        assert_eq!(timeline.get_all_audio_sources_at_time(1000).len(), 2);
+    }
+
+    #[test]
+    fn test_split_audio_clip_fades_and_envelope() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_audio_track();
+        let clip_id = timeline.add_audio_clip(track_id, PathBuf::from("music.mp3"), 0, 10_000).unwrap();
+        timeline.audio_tracks[0].clips[0].fade_in_ms = 500;
+        timeline.audio_tracks[0].clips[0].fade_out_ms = 2000;
+        timeline.audio_tracks[0].clips[0].volume_envelope.set(Keyframe::new(6000, 0.25, Interpolation::Linear));
+
+        let tail_id = timeline.split_audio_clip(track_id, clip_id, 4000).unwrap();
+        let clips = &timeline.audio_tracks[0].clips;
+        assert_eq!((clips[0].fade_in_ms, clips[0].fade_out_ms), (500, 0));
+        assert_eq!((clips[1].id, clips[1].fade_in_ms, clips[1].fade_out_ms), (tail_id, 0, 2000));
+        // 엔벌로프는 뒷부분 시작 기준으로 이동
+        assert_eq!(clips[1].volume_envelope.keyframes()[0].time_ms, 2000);
+
+        // 분할 취소 시 원래 페이드 복원
+        assert!(timeline.undo());
+        let clip = &timeline.audio_tracks[0].clips[0];
+        assert_eq!((clip.fade_in_ms, clip.fade_out_ms), (500, 2000));
+    }
 }
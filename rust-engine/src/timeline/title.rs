@@ -0,0 +1,162 @@
+// 타이틀 클립 - 엔진 텍스트 래스터라이저로 그리는 텍스트 레이어 (로어서드/인트로 타이틀)
+// 비디오 트랙의 VideoClip(clip_type = Title)에 내용으로 붙음 → 편집/트랜스폼/키프레임/트랜지션은 일반 클립과 동일
+// 스타일 크기(폰트/외곽선/여백)는 프로젝트 해상도 기준 픽셀
+
+use crate::subtitle::parser::SubtitleStyle;
+use std::path::PathBuf;
+
+/// 타이틀 인/아웃 애니메이션 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleAnimation {
+    None = 0,
+    Fade = 1,
+    SlideUp = 2,    // 아래에서 올라옴 / 위로 사라짐
+    SlideDown = 3,  // 위에서 내려옴 / 아래로 사라짐
+    SlideLeft = 4,  // 오른쪽에서 들어옴 / 왼쪽으로 사라짐
+    SlideRight = 5, // 왼쪽에서 들어옴 / 오른쪽으로 사라짐
+    Zoom = 6,       // 작게 시작해서 커짐 / 작아지며 사라짐
+}
+
+impl TitleAnimation {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => TitleAnimation::Fade,
+            2 => TitleAnimation::SlideUp,
+            3 => TitleAnimation::SlideDown,
+            4 => TitleAnimation::SlideLeft,
+            5 => TitleAnimation::SlideRight,
+            6 => TitleAnimation::Zoom,
+            _ => TitleAnimation::None,
+        }
+    }
+
+    /// 슬라이드 이동 방향 (x, y, 아래 방향 +)
+    fn direction(self) -> (f32, f32) {
+        match self {
+            TitleAnimation::SlideUp => (0.0, -1.0),
+            TitleAnimation::SlideDown => (0.0, 1.0),
+            TitleAnimation::SlideLeft => (-1.0, 0.0),
+            TitleAnimation::SlideRight => (1.0, 0.0),
+            _ => (0.0, 0.0),
+        }
+    }
+}
+
+/// 슬라이드 이동 거리 (출력 크기 비율)
+const SLIDE_DISTANCE: f32 = 0.1;
+
+/// 줌 애니메이션 시작/끝 배율
+const ZOOM_MIN_SCALE: f32 = 0.8;
+
+/// 애니메이션 결과 (클립 트랜스폼에 곱/더해짐)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TitleMotion {
+    pub opacity: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub scale: f32,
+}
+
+impl TitleMotion {
+    pub const IDENTITY: Self = Self { opacity: 1.0, offset_x: 0.0, offset_y: 0.0, scale: 1.0 };
+
+    /// hidden: 0 = 완전히 보임, 1 = 완전히 숨김 / sign: 인 -1 (반대편에서 진입), 아웃 +1
+    fn apply(&mut self, animation: TitleAnimation, hidden: f32, sign: f32) {
+        if animation == TitleAnimation::None {
+            return;
+        }
+        self.opacity *= 1.0 - hidden;
+        let (dx, dy) = animation.direction();
+        self.offset_x += sign * dx * SLIDE_DISTANCE * hidden;
+        self.offset_y += sign * dy * SLIDE_DISTANCE * hidden;
+        if animation == TitleAnimation::Zoom {
+            self.scale *= 1.0 - (1.0 - ZOOM_MIN_SCALE) * hidden;
+        }
+    }
+}
+
+/// 타이틀 내용 (텍스트 + 폰트 + 스타일 + 인/아웃 애니메이션)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleClip {
+    /// 표시 텍스트 (여러 줄은 \n)
+    pub text: String,
+    /// 폰트 파일 (None이면 시스템 기본 폰트)
+    pub font_path: Option<PathBuf>,
+    pub style: SubtitleStyle,
+    pub animation_in: TitleAnimation,
+    pub animation_in_ms: i64,
+    pub animation_out: TitleAnimation,
+    pub animation_out_ms: i64,
+}
+
+impl TitleClip {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            font_path: None,
+            style: SubtitleStyle::default(),
+            animation_in: TitleAnimation::None,
+            animation_in_ms: 0,
+            animation_out: TitleAnimation::None,
+            animation_out_ms: 0,
+        }
+    }
+
+    /// 클립 시작 기준 offset_ms의 애니메이션 값 (duration_ms: 클립 길이)
+    pub fn motion_at(&self, offset_ms: i64, duration_ms: i64) -> TitleMotion {
+        let mut motion = TitleMotion::IDENTITY;
+        if self.animation_in_ms > 0 && offset_ms < self.animation_in_ms {
+            let shown = smoothstep(offset_ms as f32 / self.animation_in_ms as f32);
+            motion.apply(self.animation_in, 1.0 - shown, -1.0);
+        }
+        let remaining_ms = duration_ms - offset_ms;
+        if self.animation_out_ms > 0 && remaining_ms < self.animation_out_ms {
+            let hidden = smoothstep(1.0 - remaining_ms as f32 / self.animation_out_ms as f32);
+            motion.apply(self.animation_out, hidden, 1.0);
+        }
+        motion
+    }
+}
+
+/// 가감속 곡선 (0~1)
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_motion() {
+        let mut title = TitleClip::new("Intro");
+        assert_eq!(title.motion_at(0, 3000), TitleMotion::IDENTITY);
+
+        title.animation_in = TitleAnimation::SlideUp;
+        title.animation_in_ms = 1000;
+        title.animation_out = TitleAnimation::Fade;
+        title.animation_out_ms = 500;
+
+        // 인: 아래(+y)에서 투명하게 시작
+        let start = title.motion_at(0, 3000);
+        assert_eq!(start.opacity, 0.0);
+        assert!((start.offset_y - SLIDE_DISTANCE).abs() < 1e-6);
+
+        let mid = title.motion_at(500, 3000);
+        assert!((mid.opacity - 0.5).abs() < 1e-6);
+        assert!(mid.offset_y > 0.0 && mid.offset_y < SLIDE_DISTANCE);
+
+        assert_eq!(title.motion_at(1500, 3000), TitleMotion::IDENTITY);
+
+        // 아웃: 페이드만 (위치 유지)
+        let end = title.motion_at(2999, 3000);
+        assert!(end.opacity < 0.01);
+        assert_eq!(end.offset_y, 0.0);
+
+        // 줌 아웃
+        title.animation_out = TitleAnimation::Zoom;
+        assert!((title.motion_at(3000, 3000).scale - ZOOM_MIN_SCALE).abs() < 1e-6);
+    }
+}
@@ -1,6 +1,10 @@
 // 트랙 모듈 - 클립들을 담는 레이어
 
 use super::clip::{VideoClip, AudioClip};
+use super::transition::Transition;
+use crate::audio::effects::AudioEffectParams;
+use crate::rendering::effects::EffectParams;
+use std::path::PathBuf;
 
 /// 비디오 트랙
 #[derive(Debug, Clone)]
@@ -9,6 +13,14 @@ pub struct VideoTrack {
     pub index: usize,  // 트랙 순서 (0 = 최하단)
     pub clips: Vec<VideoClip>,
     pub enabled: bool,
+    pub transitions: Vec<Transition>,
+    /// 트랙 이름 (표시용, 기본 빈 문자열)
+    pub name: String,
+    /// 잠금 (클립 추가/제거/편집 거부)
+    pub locked: bool,
+    /// 트랙 그레이딩 (클립 이펙트/트랜스폼/트랜지션 이후 트랙 레이어 전체에 LUT → 이펙트)
+    pub effects: EffectParams,
+    pub lut_path: Option<PathBuf>,
 }
 
 impl VideoTrack {
@@ -19,9 +31,19 @@ impl VideoTrack {
             index,
             clips: Vec::new(),
             enabled: true,
+            transitions: Vec::new(),
+            name: String::new(),
+            locked: false,
+            effects: EffectParams::default(),
+            lut_path: None,
         }
     }
 
+    /// 트랙 그레이딩(LUT/이펙트) 설정 여부
+    pub fn has_grade(&self) -> bool {
+        self.lut_path.is_some() || !self.effects.is_default()
+    }
+
     /// 클립 추가
     pub fn add_clip(&mut self, clip: VideoClip) {
         self.clips.push(clip);
@@ -29,9 +51,10 @@ impl VideoTrack {
         self.clips.sort_by_key(|c| c.start_time_ms);
     }
 
-    /// 클립 제거
+    /// 클립 제거 (연결된 트랜지션도 제거)
     pub fn remove_clip(&mut self, clip_id: u64) -> Option<VideoClip> {
         if let Some(index) = self.clips.iter().position(|c| c.id == clip_id) {
+            self.transitions.retain(|t| t.from_clip_id != clip_id && t.to_clip_id != clip_id);
             Some(self.clips.remove(index))
         } else {
             None
@@ -56,8 +79,58 @@ impl VideoTrack {
     pub fn get_clip_by_id_mut(&mut self, clip_id: u64) -> Option<&mut VideoClip> {
         self.clips.iter_mut().find(|c| c.id == clip_id)
     }
+
+    /// 트랜지션 설정 (같은 클립 경계의 기존 트랜지션은 교체)
+    /// 두 클립이 맞닿아 있어야 하며, 길이는 짧은 클립 길이로 제한
+    pub fn set_transition(&mut self, mut transition: Transition) -> bool {
+        let (from, to) = match (
+            self.get_clip_by_id(transition.from_clip_id),
+            self.get_clip_by_id(transition.to_clip_id),
+        ) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return false,
+        };
+        if transition.duration_ms <= 0 || from.end_time_ms() != to.start_time_ms {
+            return false;
+        }
+        transition.duration_ms = transition.duration_ms.min(from.duration_ms).min(to.duration_ms);
+
+        self.transitions.retain(|t| {
+            t.from_clip_id != transition.from_clip_id && t.to_clip_id != transition.to_clip_id
+        });
+        self.transitions.push(transition);
+        true
+    }
+
+    /// 앞 클립 기준 트랜지션 제거
+    pub fn remove_transition(&mut self, from_clip_id: u64) -> bool {
+        let before = self.transitions.len();
+        self.transitions.retain(|t| t.from_clip_id != from_clip_id);
+        self.transitions.len() != before
+    }
+
+    /// 특정 시간에 진행 중인 트랜지션 (트랜지션, 앞 클립, 뒤 클립, 진행률)
+    /// 편집으로 클립이 더 이상 맞닿아 있지 않으면 무시
+    pub fn transition_at(&self, time_ms: i64) -> Option<(&Transition, &VideoClip, &VideoClip, f32)> {
+        if !self.enabled {
+            return None;
+        }
+
+        self.transitions.iter().find_map(|transition| {
+            let from = self.get_clip_by_id(transition.from_clip_id)?;
+            let to = self.get_clip_by_id(transition.to_clip_id)?;
+            if from.end_time_ms() != to.start_time_ms {
+                return None;
+            }
+            let progress = transition.progress(to.start_time_ms, time_ms)?;
+            Some((transition, from, to, progress))
+        })
+    }
 }
 
+/// 트랙 볼륨 최대값 (2.0 ≈ +6dB)
+pub const MAX_TRACK_VOLUME: f32 = 2.0;
+
 /// 오디오 트랙
 #[derive(Debug, Clone)]
 pub struct AudioTrack {
@@ -66,6 +139,18 @@ pub struct AudioTrack {
     pub clips: Vec<AudioClip>,
     pub enabled: bool,
     pub muted: bool,
+    /// 트랙 볼륨 (0.0 ~ MAX_TRACK_VOLUME, 클립 볼륨과 곱해짐)
+    pub volume: f32,
+    /// 팬 (-1.0 = 왼쪽, 0.0 = 중앙, 1.0 = 오른쪽)
+    pub pan: f32,
+    /// 솔로 (솔로 트랙이 하나라도 있으면 솔로 트랙만 재생)
+    pub solo: bool,
+    /// 트랙 이름 (표시용, 기본 빈 문자열)
+    pub name: String,
+    /// 잠금 (클립 추가/제거/편집 거부)
+    pub locked: bool,
+    /// 트랙 이펙트 체인 (트랙 클립 합산 후, 볼륨/팬 적용된 버스에 적용)
+    pub effects: AudioEffectParams,
 }
 
 impl AudioTrack {
@@ -77,9 +162,20 @@ impl AudioTrack {
             clips: Vec::new(),
             enabled: true,
             muted: false,
+            volume: 1.0,
+            pan: 0.0,
+            solo: false,
+            name: String::new(),
+            locked: false,
+            effects: AudioEffectParams::default(),
         }
     }
 
+    /// 믹스에 포함되는지 (any_solo: 타임라인에 솔로 트랙 존재 여부)
+    pub fn is_audible(&self, any_solo: bool) -> bool {
+        self.enabled && !self.muted && (!any_solo || self.solo)
+    }
+
     /// 클립 추가
     pub fn add_clip(&mut self, clip: AudioClip) {
         self.clips.push(clip);
@@ -106,11 +202,56 @@ impl AudioTrack {
             .filter(|clip| clip.contains_time(time_ms))
             .collect()
     }
+
+    /// 같은 트랙에서 겹치는 클립과 자동 크로스페이드 (겹친 길이만큼 페이드, 지정한 페이드가 더 길면 유지)
+    pub fn with_crossfades(&self, clip: &AudioClip) -> AudioClip {
+        let mut mixed = clip.clone();
+        for other in self.clips.iter().filter(|c| c.id != clip.id) {
+            // 앞 클립 꼬리와 겹침 → 페이드 인
+            if other.start_time_ms <= clip.start_time_ms && other.end_time_ms() > clip.start_time_ms {
+                let overlap = other.end_time_ms().min(clip.end_time_ms()) - clip.start_time_ms;
+                mixed.fade_in_ms = mixed.fade_in_ms.max(overlap);
+            }
+            // 뒤 클립 머리와 겹침 → 페이드 아웃
+            if other.start_time_ms > clip.start_time_ms && other.start_time_ms < clip.end_time_ms() {
+                let overlap = clip.end_time_ms() - other.start_time_ms;
+                mixed.fade_out_ms = mixed.fade_out_ms.max(overlap);
+            }
+        }
+        mixed
+    }
+}
+
+/// 믹서 입력 소스 (클립 + 소속 트랙의 볼륨/팬/이펙트, 비디오 클립 오디오는 1.0/중앙/이펙트 없음)
+#[derive(Debug, Clone)]
+pub struct AudioSource {
+    pub clip: AudioClip,
+    pub track_volume: f32,
+    pub pan: f32,
+    /// 소속 오디오 트랙 (비디오 클립 오디오는 None)
+    pub track_id: Option<u64>,
+    pub track_effects: AudioEffectParams,
+}
+
+impl AudioSource {
+    /// 트랙 설정 없는 소스 (볼륨 1.0, 중앙, 트랙 이펙트 없음)
+    pub fn new(clip: AudioClip) -> Self {
+        Self { clip, track_volume: 1.0, pan: 0.0, track_id: None, track_effects: AudioEffectParams::default() }
+    }
+
+    /// 좌/우 채널 게인 (클립 볼륨 × 트랙 볼륨 × 밸런스 팬)
+    /// 스테레오 소스용 밸런스 방식: 중앙은 원본 그대로, 한쪽으로 돌리면 반대쪽만 감쇠
+    pub fn channel_gains(&self) -> (f32, f32) {
+        let gain = self.clip.volume * self.track_volume;
+        let pan = self.pan.clamp(-1.0, 1.0);
+        (gain * (1.0 - pan).min(1.0), gain * (1.0 + pan).min(1.0))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::timeline::TransitionKind;
     use std::path::PathBuf;
 
     #[test]
@@ -166,6 +307,35 @@ mod tests {
         assert!(clip_at_9000.is_none());
     }
 
+    #[test]
+    fn test_video_track_transitions() {
+        let mut track = VideoTrack::new(1, 0);
+        track.add_clip(VideoClip::new(1, PathBuf::from("a.mp4"), 0, 5000));
+        track.add_clip(VideoClip::new(2, PathBuf::from("b.mp4"), 5000, 600));
+        track.add_clip(VideoClip::new(3, PathBuf::from("c.mp4"), 6000, 3000));
+
+        // 맞닿지 않은 클립 / 길이 0은 거부
+        assert!(!track.set_transition(Transition::new(2, 3, TransitionKind::Wipe, 500)));
+        assert!(!track.set_transition(Transition::new(1, 2, TransitionKind::Wipe, 0)));
+
+        // 길이는 짧은 클립(600ms)으로 제한
+        assert!(track.set_transition(Transition::new(1, 2, TransitionKind::Crossfade, 2000)));
+        assert_eq!(track.transitions[0].duration_ms, 600);
+        assert!(track.set_transition(Transition::new(1, 2, TransitionKind::Wipe, 400)));
+        assert_eq!(track.transitions.len(), 1);
+
+        let (transition, from, to, progress) = track.transition_at(5100).unwrap();
+        assert_eq!(transition.kind, TransitionKind::Wipe);
+        assert_eq!((from.id, to.id), (1, 2));
+        assert!((progress - 0.75).abs() < 1e-6);
+        assert!(track.transition_at(5200).is_none());
+
+        // 클립 제거 시 트랜지션도 제거
+        track.remove_clip(2);
+        assert!(track.transitions.is_empty());
+        assert!(!track.remove_transition(1));
+    }
+
     #[test]
     fn test_track_disabled() {
         let mut track = VideoTrack::new(1, 0);
@@ -177,4 +347,36 @@ mod tests {
         let result = track.get_clip_at_time(2000);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_audio_track_crossfades() {
+        let mut track = AudioTrack::new(1, 0);
+        track.add_clip(AudioClip::new(1, PathBuf::from("a.mp3"), 0, 5000));
+        track.add_clip(AudioClip::new(2, PathBuf::from("b.mp3"), 4000, 5000));
+        track.add_clip(AudioClip::new(3, PathBuf::from("c.mp3"), 9000, 1000));
+
+        let first = track.with_crossfades(&track.clips[0]);
+        assert_eq!((first.fade_in_ms, first.fade_out_ms), (0, 1000));
+        let second = track.with_crossfades(&track.clips[1]);
+        assert_eq!((second.fade_in_ms, second.fade_out_ms), (1000, 0));
+
+        // 맞닿은 클립은 크로스페이드 없음, 지정한 페이드는 유지
+        track.clips[2].fade_in_ms = 200;
+        let third = track.with_crossfades(&track.clips[2]);
+        assert_eq!((third.fade_in_ms, third.fade_out_ms), (200, 0));
+    }
+
+    #[test]
+    fn test_audio_source_channel_gains() {
+        let mut clip = AudioClip::new(1, PathBuf::from("music.mp3"), 0, 5000);
+        clip.volume = 0.5;
+        let mut source = AudioSource { track_volume: 2.0, ..AudioSource::new(clip) };
+        assert_eq!(source.channel_gains(), (1.0, 1.0));
+
+        // 오른쪽 50% → 왼쪽만 절반
+        source.pan = 0.5;
+        assert_eq!(source.channel_gains(), (0.5, 1.0));
+        source.pan = -1.0;
+        assert_eq!(source.channel_gains(), (1.0, 0.0));
+    }
 }
@@ -0,0 +1,77 @@
+// 트랜지션 - 같은 트랙에서 맞닿은 두 클립 사이 전환 효과
+// 전환 구간은 컷 지점(다음 클립 시작) 중심: [cut - d/2, cut + d - d/2)
+// 구간 동안 앞 클립은 끝 이후 소스(핸들), 뒤 클립은 시작 이전 소스를 사용
+
+/// 트랜지션 종류 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    Crossfade = 0,  // 디졸브
+    DipToBlack = 1, // 앞 클립 → 검정 → 뒤 클립
+    Wipe = 2,       // 왼쪽 → 오른쪽으로 뒤 클립이 드러남
+    Slide = 3,      // 뒤 클립이 오른쪽에서 밀려 들어옴
+}
+
+impl TransitionKind {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => TransitionKind::DipToBlack,
+            2 => TransitionKind::Wipe,
+            3 => TransitionKind::Slide,
+            _ => TransitionKind::Crossfade,
+        }
+    }
+}
+
+/// 두 클립 사이 트랜지션
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    pub from_clip_id: u64,
+    pub to_clip_id: u64,
+    pub kind: TransitionKind,
+    pub duration_ms: i64,
+}
+
+impl Transition {
+    pub fn new(from_clip_id: u64, to_clip_id: u64, kind: TransitionKind, duration_ms: i64) -> Self {
+        Self {
+            from_clip_id,
+            to_clip_id,
+            kind,
+            duration_ms,
+        }
+    }
+
+    /// 전환 구간 [start, end) (cut_ms: 뒤 클립 시작 시간)
+    pub fn window(&self, cut_ms: i64) -> (i64, i64) {
+        let start = cut_ms - self.duration_ms / 2;
+        (start, start + self.duration_ms)
+    }
+
+    /// 구간 내 진행률 (0.0 = 앞 클립, 1.0 = 뒤 클립), 구간 밖이면 None
+    pub fn progress(&self, cut_ms: i64, time_ms: i64) -> Option<f32> {
+        let (start, end) = self.window(cut_ms);
+        if self.duration_ms <= 0 || time_ms < start || time_ms >= end {
+            return None;
+        }
+        Some((time_ms - start) as f32 / self.duration_ms as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_window_progress() {
+        let transition = Transition::new(1, 2, TransitionKind::Crossfade, 1000);
+        assert_eq!(transition.window(5000), (4500, 5500));
+        assert_eq!(transition.progress(5000, 4499), None);
+        assert_eq!(transition.progress(5000, 4500), Some(0.0));
+        assert_eq!(transition.progress(5000, 5000), Some(0.5));
+        assert_eq!(transition.progress(5000, 5500), None);
+
+        assert_eq!(TransitionKind::from_u32(3), TransitionKind::Slide);
+        assert_eq!(TransitionKind::from_u32(99), TransitionKind::Crossfade);
+    }
+}
@@ -0,0 +1,102 @@
+// 에러 분류 + 마지막 에러 (FFI 호출 스레드별)
+// 엔진 에러는 String으로 전달되므로 메시지(FFmpeg/OS 에러 문구 포함)로 분류
+// FFI 함수가 실패하면 호출 스레드에 기록 → 호스트가 engine_get_last_error로 원인별 안내 표시 (errno와 같은 방식)
+
+use std::cell::RefCell;
+
+/// 에러 분류 (FFI i32 매핑)
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// 기록된 에러 없음
+    None = 0,
+    /// 파일/URL 열기 실패 (원인 불명)
+    OpenFailed = 1,
+    /// 파일/폰트 등이 없음
+    NotFound = 2,
+    /// 접근 권한 없음 (읽기 전용 폴더, 다른 프로그램이 사용 중)
+    PermissionDenied = 3,
+    /// 코덱/포맷 미지원 (디코더/인코더 없음)
+    CodecUnsupported = 4,
+    /// 손상되었거나 해석할 수 없는 데이터
+    InvalidData = 5,
+    /// 디스크 공간 부족
+    DiskFull = 6,
+    /// 네트워크 연결/타임아웃
+    Network = 7,
+    OutOfMemory = 8,
+    /// 사용자 취소
+    Cancelled = 9,
+    Other = 99,
+}
+
+/// 분류별 메시지 문구 (소문자, 먼저 일치한 분류 사용 → 구체적인 분류가 앞)
+const PATTERNS: [(ErrorCategory, &[&str]); 9] = [
+    (ErrorCategory::Cancelled, &["cancelled", "canceled", "취소"]),
+    (ErrorCategory::DiskFull, &["no space left", "not enough space", "disk full", "os error 28)", "os error 112)"]),
+    (ErrorCategory::OutOfMemory, &["out of memory", "cannot allocate memory", "enomem"]),
+    (ErrorCategory::PermissionDenied, &["permission denied", "access is denied", "os error 13)", "os error 5)"]),
+    (ErrorCategory::CodecUnsupported, &[
+        "decoder not found", "encoder not found", "codec not", "unsupported", "not supported",
+        "미지원", "디코더 없음", "인코더 없음",
+    ]),
+    (ErrorCategory::Network, &["connection", "timed out", "timeout", "immediate exit requested", "network", "unreachable"]),
+    (ErrorCategory::NotFound, &["no such file", "cannot find", "not found", "os error 2)", "os error 3)"]),
+    (ErrorCategory::InvalidData, &["invalid data", "corrupt", "invalid utf-8", "parse", "파싱"]),
+    (ErrorCategory::OpenFailed, &["failed to open", "could not open", "open failed", "열기 실패"]),
+];
+
+/// 에러 메시지 분류
+pub fn classify(message: &str) -> ErrorCategory {
+    let message = message.to_lowercase();
+    PATTERNS.iter()
+        .find(|(_, patterns)| patterns.iter().any(|p| message.contains(p)))
+        .map_or(ErrorCategory::Other, |(category, _)| *category)
+}
+
+thread_local! {
+    /// 이 스레드에서 마지막으로 실패한 FFI 호출의 (분류, 메시지)
+    static LAST_ERROR: RefCell<Option<(ErrorCategory, String)>> = const { RefCell::new(None) };
+}
+
+/// 마지막 에러 기록 (메시지로 분류)
+pub fn set_last_error(message: impl Into<String>) {
+    let message = message.into();
+    let category = classify(&message);
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some((category, message)));
+}
+
+/// 마지막 에러 (성공한 호출은 지우지 않음 → 실패 직후에만 의미 있음)
+pub fn last_error() -> Option<(ErrorCategory, String)> {
+    LAST_ERROR.with(|last| last.borrow().clone())
+}
+
+/// 마지막 에러 지우기
+pub fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_and_last_error() {
+        assert_eq!(classify("Failed to open file: No such file or directory"), ErrorCategory::NotFound);
+        assert_eq!(classify("Failed to open file: Invalid data found when processing input"), ErrorCategory::InvalidData);
+        assert_eq!(classify("Failed to open file: Immediate exit requested"), ErrorCategory::Network);
+        assert_eq!(classify("Decoder not found for codec prores"), ErrorCategory::CodecUnsupported);
+        assert_eq!(classify("Failed to write frame: No space left on device (os error 28)"), ErrorCategory::DiskFull);
+        assert_eq!(classify("임시 파일 이동 실패: Access is denied. (os error 5)"), ErrorCategory::PermissionDenied);
+        assert_eq!(classify("Export가 취소되었습니다"), ErrorCategory::Cancelled);
+        assert_eq!(classify("Failed to open decoder: stub"), ErrorCategory::OpenFailed);
+        assert_eq!(classify("Unexpected"), ErrorCategory::Other);
+
+        // 스레드별 기록
+        set_last_error("media_probe: Failed to open file: Permission denied");
+        std::thread::spawn(|| assert!(last_error().is_none())).join().unwrap();
+        assert_eq!(last_error().map(|(c, _)| c), Some(ErrorCategory::PermissionDenied));
+        clear_last_error();
+        assert!(last_error().is_none());
+    }
+}
@@ -0,0 +1,119 @@
+// 핸들 테이블 - FFI로 넘기는 객체를 포인터 대신 세대 번호가 붙은 u64 id로 관리
+// 파괴된(stale) 핸들, 다른 종류의 핸들, 임의 값이 들어와도 조회 실패로 끝남 (프로세스 크래시 없음)
+// id 구성: [63..56] 테이블 태그 | [55..32] 슬롯 세대 | [31..0] 슬롯 번호 + 1 (0은 항상 무효)
+// 슬롯은 재사용되지만 제거할 때마다 세대가 올라가므로 이전 id는 다시 유효해지지 않음 (2^24회 재사용 전까지)
+
+use std::sync::Arc;
+
+/// 세대 비트 수 (태그와 슬롯 번호 사이)
+const GENERATION_BITS: u32 = 24;
+const GENERATION_MASK: u32 = (1 << GENERATION_BITS) - 1;
+
+struct Slot<T> {
+    generation: u32,
+    value: Option<Arc<T>>,
+}
+
+/// 세대 카운트 핸들 테이블 (동기화는 호출 측 Mutex)
+pub struct HandleTable<T> {
+    /// 테이블 종류 (다른 테이블의 id 거부)
+    tag: u8,
+    slots: Vec<Slot<T>>,
+    /// 비어 있는 슬롯 번호
+    free: Vec<u32>,
+}
+
+impl<T> HandleTable<T> {
+    /// 빈 테이블 (tag: 0이 아닌 테이블 종류 번호)
+    pub const fn new(tag: u8) -> Self {
+        Self { tag, slots: Vec::new(), free: Vec::new() }
+    }
+
+    fn encode(&self, index: u32, generation: u32) -> u64 {
+        (u64::from(self.tag) << 56) | (u64::from(generation) << 32) | (u64::from(index) + 1)
+    }
+
+    /// id → 슬롯 번호 (태그/세대가 맞는 살아 있는 슬롯만)
+    fn slot_index(&self, handle: u64) -> Option<usize> {
+        if (handle >> 56) as u8 != self.tag {
+            return None;
+        }
+        let index = (handle as u32).checked_sub(1)? as usize;
+        let generation = (handle >> 32) as u32 & GENERATION_MASK;
+        let slot = self.slots.get(index)?;
+        (slot.generation == generation && slot.value.is_some()).then_some(index)
+    }
+
+    /// 객체 등록 → id
+    pub fn insert(&mut self, value: Arc<T>) -> u64 {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot { generation: 1, value: None });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.value = Some(value);
+        let generation = slot.generation;
+        self.encode(index, generation)
+    }
+
+    /// id로 조회 (무효 id면 None)
+    pub fn get(&self, handle: u64) -> Option<Arc<T>> {
+        let index = self.slot_index(handle)?;
+        self.slots[index].value.clone()
+    }
+
+    /// 등록 해제 (이후 같은 id는 무효, 진행 중인 호출이 가진 Arc는 유지)
+    pub fn remove(&mut self, handle: u64) -> Option<Arc<T>> {
+        let index = self.slot_index(handle)?;
+        let slot = &mut self.slots[index];
+        // 세대 0은 건너뜀 (id가 0이 되지 않도록 세대는 1부터)
+        slot.generation = (slot.generation % GENERATION_MASK) + 1;
+        self.free.push(index as u32);
+        slot.value.take()
+    }
+
+    /// 등록된 객체 수
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_table_generations() {
+        let mut table = HandleTable::new(1);
+        let a = table.insert(Arc::new("a"));
+        let b = table.insert(Arc::new("b"));
+        assert_ne!(a, 0);
+        assert_eq!(table.get(a).as_deref(), Some(&"a"));
+        assert_eq!(table.len(), 2);
+
+        // 제거 후 같은 슬롯을 재사용해도 이전 id는 무효
+        assert_eq!(table.remove(a).as_deref(), Some(&"a"));
+        assert!(table.get(a).is_none());
+        assert!(table.remove(a).is_none());
+        let c = table.insert(Arc::new("c"));
+        assert_ne!(c, a);
+        assert_eq!(c as u32, a as u32);
+        assert!(table.get(a).is_none());
+        assert_eq!(table.get(c).as_deref(), Some(&"c"));
+
+        // 다른 테이블의 id, 임의 값
+        let other: HandleTable<&str> = HandleTable::new(2);
+        assert!(other.get(b).is_none());
+        assert!(table.get(0).is_none());
+        assert!(table.get(b + 100).is_none());
+        assert!(table.get(0x0000_7ff6_1234_5678).is_none());
+        assert_eq!(table.get(b).as_deref(), Some(&"b"));
+    }
+}
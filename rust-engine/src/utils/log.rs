@@ -0,0 +1,132 @@
+// 엔진 로그 - 레벨별 진단 메시지를 호스트 콜백(C# 로그 창)으로 전달
+// 콜백이 없으면 stderr 출력 (기존 eprintln!과 동일), 최소 레벨 미만은 포맷팅 없이 버림
+// 사용: log_error!/log_warn!/log_info!/log_debug! (format! 인자 그대로)
+// 콜백은 메시지를 낸 스레드(렌더/Export/디코딩 워커 등)에서 호출됨
+
+use std::ffi::{c_char, CString};
+use std::fmt;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Mutex;
+
+/// 로그 레벨 (FFI i32 매핑, 클수록 심각)
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// 프레임 단위 진단 (디코더/인코더 내부 상태, 진행률)
+    Debug = 0,
+    /// 작업 시작/완료, 선택된 인코더 등
+    Info = 1,
+    /// 폴백/재시도 (작업은 계속됨)
+    Warn = 2,
+    /// 작업 실패
+    Error = 3,
+}
+
+impl LogLevel {
+    pub fn from_i32(v: i32) -> Self {
+        match v {
+            i32::MIN..=0 => LogLevel::Debug,
+            1 => LogLevel::Info,
+            2 => LogLevel::Warn,
+            _ => LogLevel::Error,
+        }
+    }
+}
+
+/// 호스트 로그 콜백 (message: UTF-8, 콜백 안에서만 유효)
+pub type LogCallback = extern "C" fn(level: i32, message: *const c_char);
+
+/// 기본 최소 레벨 (디버그 빌드는 Debug까지 출력)
+const DEFAULT_LEVEL: LogLevel = if cfg!(debug_assertions) { LogLevel::Debug } else { LogLevel::Info };
+
+static MIN_LEVEL: AtomicI32 = AtomicI32::new(DEFAULT_LEVEL as i32);
+static CALLBACK: Mutex<Option<LogCallback>> = Mutex::new(None);
+
+/// 호스트 콜백 등록 (None이면 stderr로 복귀)
+pub fn set_callback(callback: Option<LogCallback>) {
+    if let Ok(mut current) = CALLBACK.lock() {
+        *current = callback;
+    }
+}
+
+/// 최소 레벨 설정 (미만은 버림)
+pub fn set_level(level: LogLevel) {
+    MIN_LEVEL.store(level as i32, Ordering::Relaxed);
+}
+
+/// 레벨이 출력 대상인지 (무거운 진단 문자열을 만들기 전에 확인)
+pub fn enabled(level: LogLevel) -> bool {
+    level as i32 >= MIN_LEVEL.load(Ordering::Relaxed)
+}
+
+/// 메시지 출력 (매크로에서 호출)
+pub fn write(level: LogLevel, args: fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+    let message = fmt::format(args);
+    // 콜백 호출 중에는 lock을 잡지 않음 (콜백에서 재등록해도 교착 없음)
+    let callback = CALLBACK.lock().ok().and_then(|c| *c);
+    match callback {
+        Some(callback) => {
+            // 메시지 안의 NUL은 제거 (CString 변환 실패 방지)
+            let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+            callback(level as i32, message.as_ptr());
+        }
+        None => eprintln!("{}", message),
+    }
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => { $crate::utils::log::write($crate::utils::log::LogLevel::Error, format_args!($($arg)*)) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::utils::log::write($crate::utils::log::LogLevel::Warn, format_args!($($arg)*)) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => { $crate::utils::log::write($crate::utils::log::LogLevel::Info, format_args!($($arg)*)) };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => { $crate::utils::log::write($crate::utils::log::LogLevel::Debug, format_args!($($arg)*)) };
+}
+
+pub(crate) use {log_debug, log_error, log_info, log_warn};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    static RECEIVED: Mutex<Vec<(i32, String)>> = Mutex::new(Vec::new());
+
+    extern "C" fn capture(level: i32, message: *const c_char) {
+        let message = unsafe { CStr::from_ptr(message) }.to_string_lossy().into_owned();
+        // 병렬 테스트의 다른 로그는 무시
+        if message.starts_with("[LOG_TEST]") {
+            RECEIVED.lock().unwrap().push((level, message));
+        }
+    }
+
+    #[test]
+    fn test_log_callback_and_level() {
+        assert_eq!(LogLevel::from_i32(-5), LogLevel::Debug);
+        assert_eq!(LogLevel::from_i32(9), LogLevel::Error);
+
+        set_callback(Some(capture));
+        set_level(LogLevel::Warn);
+        log_info!("[LOG_TEST] 버려짐 {}", 1);
+        log_warn!("[LOG_TEST] 폴백 {}", 2);
+        log_error!("[LOG_TEST] 실패\0{}", 3);
+        set_level(DEFAULT_LEVEL);
+        set_callback(None);
+
+        let received = RECEIVED.lock().unwrap().clone();
+        assert_eq!(received, vec![
+            (LogLevel::Warn as i32, "[LOG_TEST] 폴백 2".to_string()),
+            (LogLevel::Error as i32, "[LOG_TEST] 실패3".to_string()),
+        ]);
+    }
+}
@@ -1,4 +1,27 @@
 // 공통 유틸리티 모듈
 // 에러 처리, 로깅, 헬퍼 함수
 
-// TODO: 유틸리티 함수 구현
+pub mod error;
+pub mod handle_table;
+pub mod log;
+pub mod peak_cache;
+pub mod peak_pyramid;
+pub mod proxy;
+pub mod scene_detect;
+pub mod thumbnail_fit;
+
+/// JSON 문자열 이스케이프
+pub fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
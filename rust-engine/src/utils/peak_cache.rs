@@ -0,0 +1,216 @@
+// 오디오 파형 피크 디스크 캐시 - 프로젝트 재오픈 시 오디오 파일 재디코딩 생략
+// 키: 파일 내용 해시 + 오디오 스트림 + samples_per_peak (경로/수정 시각과 무관 → 복사/이동해도 재사용)
+// 저장 위치: 캐시 디렉토리 지정 시 <dir>/<hash>_<spp>.vcpeaks, 아니면 미디어 옆 <파일명>.<spp>.vcpeaks
+// 기본이 아닌 오디오 스트림은 spp 앞에 s<스트림 번호> 추가 (<hash>_s2_<spp>, <파일명>.s2.<spp>)
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// 캐시 파일 식별자 + 포맷 버전
+const MAGIC: &[u8; 4] = b"VCPK";
+const VERSION: u32 = 1;
+
+/// 캐시 파일 확장자
+const EXTENSION: &str = "vcpeaks";
+
+/// 해시에 사용하는 샘플 블록 크기 (앞/중간/끝 3곳)
+/// 전체 파일을 읽지 않아 수 GB 파일도 즉시 키 계산
+const HASH_BLOCK_SIZE: u64 = 1024 * 1024;
+
+/// 헤더 크기: magic(4) + version(4) + hash(8) + spp(4) + channels(4) + rate(4) + duration(8) + count(4)
+const HEADER_SIZE: usize = 40;
+
+/// 캐시되는 피크 데이터 (extract_audio_peaks 결과와 동일)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedPeaks {
+    pub peaks: Vec<f32>,
+    pub channels: u32,
+    pub sample_rate: u32,
+    pub duration_ms: i64,
+}
+
+/// 파일 내용 해시 (FNV-1a 64, 파일 크기 + 앞/중간/끝 블록)
+pub fn file_hash(media_path: &Path) -> io::Result<u64> {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut file = File::open(media_path)?;
+    let size = file.metadata()?.len();
+
+    let mut hash = FNV_OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+    feed(&size.to_le_bytes());
+
+    let mut block = Vec::new();
+    let last_offset = size.saturating_sub(HASH_BLOCK_SIZE);
+    let mut offsets = vec![0, last_offset / 2, last_offset];
+    offsets.dedup();
+    for offset in offsets {
+        file.seek(SeekFrom::Start(offset))?;
+        block.clear();
+        (&mut file).take(HASH_BLOCK_SIZE).read_to_end(&mut block)?;
+        feed(&block);
+    }
+    Ok(hash)
+}
+
+/// 캐시 파일 경로 (stream_index: None이면 기본 오디오 스트림)
+pub fn cache_path(
+    media_path: &Path,
+    hash: u64,
+    stream_index: Option<usize>,
+    samples_per_peak: u32,
+    cache_dir: Option<&Path>,
+) -> PathBuf {
+    let stream = stream_index.map(|index| format!("s{}", index));
+    match cache_dir {
+        Some(dir) => {
+            let stream = stream.map(|s| s + "_").unwrap_or_default();
+            dir.join(format!("{:016x}_{}{}.{}", hash, stream, samples_per_peak, EXTENSION))
+        }
+        None => {
+            let stream = stream.map(|s| format!(".{}", s)).unwrap_or_default();
+            let mut name = media_path.file_name().unwrap_or_default().to_os_string();
+            name.push(format!("{}.{}.{}", stream, samples_per_peak, EXTENSION));
+            media_path.with_file_name(name)
+        }
+    }
+}
+
+/// 캐시 조회 (없거나 해시/spp 불일치/손상이면 None)
+pub fn load(
+    media_path: &Path,
+    stream_index: Option<usize>,
+    samples_per_peak: u32,
+    cache_dir: Option<&Path>,
+) -> Option<CachedPeaks> {
+    let hash = file_hash(media_path).ok()?;
+    let bytes = std::fs::read(cache_path(media_path, hash, stream_index, samples_per_peak, cache_dir)).ok()?;
+    decode(&bytes, hash, samples_per_peak)
+}
+
+/// 캐시 저장 (임시 파일에 쓴 뒤 rename → 중간에 실패해도 손상된 캐시가 남지 않음)
+/// 반환: 저장된 캐시 파일 경로
+pub fn store(
+    media_path: &Path,
+    stream_index: Option<usize>,
+    samples_per_peak: u32,
+    peaks: &CachedPeaks,
+    cache_dir: Option<&Path>,
+) -> io::Result<PathBuf> {
+    let hash = file_hash(media_path)?;
+    let path = cache_path(media_path, hash, stream_index, samples_per_peak, cache_dir);
+    if let Some(dir) = cache_dir {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = File::create(&temp_path)
+        .and_then(|mut file| file.write_all(&encode(peaks, hash, samples_per_peak)))
+        .and_then(|_| std::fs::rename(&temp_path, &path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result.map(|_| path)
+}
+
+fn encode(peaks: &CachedPeaks, hash: u64, samples_per_peak: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + peaks.peaks.len() * 4);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&hash.to_le_bytes());
+    bytes.extend_from_slice(&samples_per_peak.to_le_bytes());
+    bytes.extend_from_slice(&peaks.channels.to_le_bytes());
+    bytes.extend_from_slice(&peaks.sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&peaks.duration_ms.to_le_bytes());
+    bytes.extend_from_slice(&(peaks.peaks.len() as u32).to_le_bytes());
+    for p in &peaks.peaks {
+        bytes.extend_from_slice(&p.to_le_bytes());
+    }
+    bytes
+}
+
+fn decode(bytes: &[u8], hash: u64, samples_per_peak: u32) -> Option<CachedPeaks> {
+    if bytes.len() < HEADER_SIZE || &bytes[0..4] != MAGIC {
+        return None;
+    }
+    let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+    let u64_at = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+
+    if u32_at(4) != VERSION || u64_at(8) != hash || u32_at(16) != samples_per_peak {
+        return None;
+    }
+    let count = u32_at(36) as usize;
+    let data = &bytes[HEADER_SIZE..];
+    if data.len() != count * 4 {
+        return None;
+    }
+
+    Some(CachedPeaks {
+        peaks: data.chunks_exact(4).map(|c| f32::from_le_bytes(c.try_into().unwrap())).collect(),
+        channels: u32_at(20),
+        sample_rate: u32_at(24),
+        duration_ms: u64_at(28) as i64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_media(name: &str, contents: &[u8]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("vortex_peak_cache_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("clip.wav");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let media = temp_media("roundtrip", &[1, 2, 3, 4, 5]);
+        let peaks = CachedPeaks { peaks: vec![0.0, 0.5, 1.0], channels: 2, sample_rate: 48000, duration_ms: 1234 };
+
+        // 미디어 옆 저장
+        assert_eq!(load(&media, None, 1024, None), None);
+        let path = store(&media, None, 1024, &peaks, None).unwrap();
+        assert_eq!(path.parent(), media.parent());
+        assert_eq!(load(&media, None, 1024, None), Some(peaks.clone()));
+        // 다른 samples_per_peak / 오디오 스트림은 별도 항목
+        assert_eq!(load(&media, None, 512, None), None);
+        assert_eq!(load(&media, Some(2), 1024, None), None);
+
+        // 캐시 디렉토리 저장
+        let cache_dir = media.parent().unwrap().join("cache");
+        store(&media, None, 1024, &peaks, Some(&cache_dir)).unwrap();
+        assert_eq!(load(&media, None, 1024, Some(&cache_dir)), Some(peaks.clone()));
+        let dub = CachedPeaks { peaks: vec![0.1], ..peaks.clone() };
+        store(&media, Some(2), 1024, &dub, Some(&cache_dir)).unwrap();
+        assert_eq!(load(&media, Some(2), 1024, Some(&cache_dir)), Some(dub));
+        assert_eq!(load(&media, None, 1024, Some(&cache_dir)), Some(peaks));
+
+        let _ = std::fs::remove_dir_all(media.parent().unwrap());
+    }
+
+    #[test]
+    fn test_modified_media_invalidates_cache() {
+        let media = temp_media("invalidate", &[1, 2, 3, 4, 5]);
+        let peaks = CachedPeaks { peaks: vec![0.25], channels: 1, sample_rate: 44100, duration_ms: 10 };
+        store(&media, None, 256, &peaks, None).unwrap();
+
+        // 같은 크기, 다른 내용 → 해시 불일치
+        std::fs::write(&media, [1, 2, 3, 4, 6]).unwrap();
+        assert_eq!(load(&media, None, 256, None), None);
+
+        let _ = std::fs::remove_dir_all(media.parent().unwrap());
+    }
+}
@@ -0,0 +1,111 @@
+// 오디오 파형 피크 피라미드 - 한 번 디코딩한 기본 피크로 여러 줌 레벨 피크 구성 (밉맵)
+// 레벨 0 = BASE_SAMPLES_PER_PEAK, 위 레벨은 아래 레벨 LEVEL_FACTOR개의 max (256, 1024, 4096, ...)
+// 조회: 임의 구간 + 임의 samples_per_peak → 요청보다 촘촘한 레벨 중 가장 거친 레벨에서 max로 재구성 (재디코딩 없음)
+
+/// 레벨 0 samples_per_peak (피크 디스크 캐시 키로도 사용)
+pub const BASE_SAMPLES_PER_PEAK: u32 = 256;
+
+/// 레벨 간 배율
+const LEVEL_FACTOR: usize = 4;
+
+/// 최대 레벨 수 (256 ~ 262144 samples/peak)
+const MAX_LEVELS: usize = 6;
+
+/// 줌 레벨별 피크 (원본 샘플레이트 기준)
+#[derive(Debug, Clone)]
+pub struct PeakPyramid {
+    /// levels[k]: BASE_SAMPLES_PER_PEAK * LEVEL_FACTOR^k 샘플당 피크
+    levels: Vec<Vec<f32>>,
+    sample_rate: u32,
+}
+
+impl PeakPyramid {
+    /// 레벨 0 피크(BASE_SAMPLES_PER_PEAK 단위)로 피라미드 구성
+    pub fn build(base_peaks: Vec<f32>, sample_rate: u32) -> Self {
+        let mut levels = vec![base_peaks];
+        while levels.len() < MAX_LEVELS {
+            let below = &levels[levels.len() - 1];
+            if below.len() <= 1 {
+                break;
+            }
+            let level = below.chunks(LEVEL_FACTOR)
+                .map(|chunk| chunk.iter().copied().fold(0.0f32, f32::max))
+                .collect();
+            levels.push(level);
+        }
+        Self { levels, sample_rate }
+    }
+
+    /// 레벨 수
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// 레벨의 samples_per_peak
+    fn level_samples_per_peak(level: usize) -> u64 {
+        u64::from(BASE_SAMPLES_PER_PEAK) * (LEVEL_FACTOR as u64).pow(level as u32)
+    }
+
+    /// [start_ms, end_ms) 구간을 samples_per_peak 단위로 나눈 피크 (마지막 블록은 구간/오디오 끝까지)
+    /// 요청이 레벨 0보다 촘촘하면 레벨 0 피크를 반복
+    pub fn query(&self, start_ms: i64, end_ms: i64, samples_per_peak: u32) -> Vec<f32> {
+        if samples_per_peak == 0 || end_ms <= start_ms || self.sample_rate == 0 {
+            return Vec::new();
+        }
+        let level = (0..self.levels.len())
+            .rev()
+            .find(|&k| Self::level_samples_per_peak(k) <= u64::from(samples_per_peak))
+            .unwrap_or(0);
+        let peaks = &self.levels[level];
+        let level_spp = Self::level_samples_per_peak(level);
+
+        let to_sample = |ms: i64| (ms.max(0) as u64) * u64::from(self.sample_rate) / 1000;
+        let total_samples = peaks.len() as u64 * level_spp;
+        let start = to_sample(start_ms);
+        let end = to_sample(end_ms).min(total_samples);
+        if start >= end {
+            return Vec::new();
+        }
+
+        let spp = u64::from(samples_per_peak);
+        (0..(end - start).div_ceil(spp))
+            .map(|i| {
+                let block_start = start + i * spp;
+                let block_end = (block_start + spp).min(end);
+                let first = (block_start / level_spp) as usize;
+                let last = (block_end.div_ceil(level_spp) as usize).clamp(first + 1, peaks.len());
+                peaks[first..last].iter().copied().fold(0.0f32, f32::max)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pyramid_query() {
+        // 1000Hz, 레벨 0 피크 64개 = 16384 샘플 = 16.384초
+        let base: Vec<f32> = (0..64).map(|i| i as f32 / 64.0).collect();
+        let pyramid = PeakPyramid::build(base.clone(), 1000);
+        assert_eq!(pyramid.level_count(), 4); // 64, 16, 4, 1
+
+        // 레벨 0과 같은 해상도 → 그대로
+        assert_eq!(pyramid.query(0, 16384, 256), base);
+        // 1024 = 레벨 1 (4개씩 max)
+        let level1 = pyramid.query(0, 16384, 1024);
+        assert_eq!(level1.len(), 16);
+        assert_eq!(level1[0], base[3]);
+        // 레벨 경계와 맞지 않는 해상도/구간 → 덮는 피크의 max
+        let odd = pyramid.query(1000, 3000, 700);
+        assert_eq!(odd.len(), 3);
+        assert_eq!(odd[0], base[6]); // 샘플 1000~1700 → 레벨 0 [3, 7)
+        assert_eq!(odd[2], base[11]); // 마지막 블록은 구간 끝(3000)까지
+
+        // 레벨 0보다 촘촘하면 반복, 오디오 끝 이후는 잘림
+        assert_eq!(pyramid.query(0, 512, 128), vec![base[0], base[0], base[1], base[1]]);
+        assert_eq!(pyramid.query(16000, 20000, 256).len(), 2);
+        assert!(pyramid.query(20000, 30000, 256).is_empty());
+    }
+}
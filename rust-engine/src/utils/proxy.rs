@@ -0,0 +1,265 @@
+// 프록시 미디어 - 무거운 소스(4K, 10bit 등)의 저해상도 H.264 사본을 백그라운드로 생성
+// 프록시 사용을 켠 프리뷰 Renderer는 등록된 프록시를 원본 대신 디코딩, Export는 항상 원본
+// 프록시는 비디오만 (오디오는 원본에서 믹싱), 원본과 같은 타임스탬프 유지 (CFR 격자에 그 시각의 원본 프레임 배치)
+// 저장 위치: <proxy_dir>/<파일 내용 해시>_<높이>p.mp4 (peak_cache와 같은 해시 → 경로/이름이 바뀌어도 재사용)
+
+use crate::encoding::encoder::{EncodePass, EncoderType, RateControl, VideoCodec, VideoEncoder, VideoEncoderSettings};
+use crate::ffmpeg::{Decoder, PixelFormat};
+use crate::utils::peak_cache::file_hash;
+use crate::utils::thumbnail_fit::{fitted_size, ThumbnailFit};
+use crate::utils::log::{log_error, log_info};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 기본 프록시 높이 (프리뷰 960x540과 같은 세로 해상도)
+pub const DEFAULT_PROXY_HEIGHT: u32 = 540;
+
+/// 프록시 화질 (x264 CRF, 프리뷰 용도라 원본보다 낮음)
+const PROXY_CRF: u32 = 26;
+
+/// 프록시 최대 FPS (고프레임레이트 소스는 프리뷰에 필요한 만큼만)
+const MAX_PROXY_FPS: f64 = 60.0;
+
+/// 원본 경로 → 프록시 경로 (프리뷰 Renderer가 조회)
+static REGISTRY: Mutex<BTreeMap<PathBuf, PathBuf>> = Mutex::new(BTreeMap::new());
+/// 등록 변경 횟수 (Renderer가 값이 바뀌면 열린 디코더를 다시 열어 새 프록시 반영)
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 프록시 등록 (이미 있는 프록시 파일을 재사용할 때도 호출, 같은 원본은 교체)
+pub fn register(source: &Path, proxy: &Path) {
+    if let Ok(mut registry) = REGISTRY.lock() {
+        registry.insert(source.to_path_buf(), proxy.to_path_buf());
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// 프록시 등록 해제 (반환: 등록되어 있었는지)
+pub fn unregister(source: &Path) -> bool {
+    let removed = REGISTRY.lock().map(|mut registry| registry.remove(source).is_some()).unwrap_or(false);
+    if removed {
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+    removed
+}
+
+/// 원본에 등록된 프록시 경로
+pub fn proxy_for(source: &Path) -> Option<PathBuf> {
+    REGISTRY.lock().ok()?.get(source).cloned()
+}
+
+/// 등록 변경 횟수
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+/// 프록시 파일 경로 (<proxy_dir>/<해시>_<높이>p.mp4, 원본을 읽을 수 없으면 에러)
+pub fn proxy_path(source: &Path, proxy_dir: &Path, height: u32) -> io::Result<PathBuf> {
+    let hash = file_hash(source)?;
+    Ok(proxy_dir.join(format!("{:016x}_{}p.mp4", hash, height)))
+}
+
+/// 프록시 해상도 (표시 비율 유지, 높이 이하로만 축소, YUV420 인코딩을 위해 짝수)
+pub fn proxy_size(display_width: u32, display_height: u32, height: u32) -> (u32, u32) {
+    let box_height = height.min(display_height.max(2));
+    let box_width = display_width.max(2);
+    let (width, height) = fitted_size(display_width, display_height, box_width, box_height, ThumbnailFit::Letterbox);
+    ((width & !1).max(2), (height & !1).max(2))
+}
+
+/// 프록시 생성 작업 공유 상태
+struct ProxyShared {
+    /// 진행률 (0~100)
+    progress: AtomicU32,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+    /// 에러 메시지 (있으면 실패)
+    error: Mutex<Option<String>>,
+}
+
+/// 프록시 생성 작업 핸들 (C#에서 폴링으로 상태 확인)
+/// 성공하면 원본 → 프록시가 자동 등록되어 프록시 사용 중인 프리뷰에 반영
+pub struct ProxyJob {
+    shared: Arc<ProxyShared>,
+    output_path: PathBuf,
+}
+
+impl ProxyJob {
+    /// 프록시 생성 시작 (백그라운드 스레드, output_path가 이미 있으면 덮어씀)
+    pub fn start(source: PathBuf, output_path: PathBuf, height: u32) -> Self {
+        let shared = Arc::new(ProxyShared {
+            progress: AtomicU32::new(0),
+            cancelled: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            error: Mutex::new(None),
+        });
+
+        let job = shared.clone();
+        let output = output_path.clone();
+        std::thread::spawn(move || {
+            match generate(&source, &output, height, &job) {
+                Ok(()) => {
+                    register(&source, &output);
+                    job.progress.store(100, Ordering::SeqCst);
+                    log_info!("[PROXY] 완료: {} → {}", source.display(), output.display());
+                }
+                Err(msg) => {
+                    if let Ok(mut err) = job.error.lock() {
+                        *err = Some(msg.clone());
+                    }
+                    log_error!("[PROXY] 에러 {}: {}", source.display(), msg);
+                }
+            }
+            job.finished.store(true, Ordering::SeqCst);
+        });
+
+        Self { shared, output_path }
+    }
+
+    /// 진행률 (0~100)
+    pub fn progress(&self) -> u32 {
+        self.shared.progress.load(Ordering::SeqCst)
+    }
+
+    /// 취소 요청 (작성 중인 파일은 삭제)
+    pub fn cancel(&self) {
+        self.shared.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// 완료 여부 (성공/실패/취소)
+    pub fn is_finished(&self) -> bool {
+        self.shared.finished.load(Ordering::SeqCst)
+    }
+
+    /// 에러 메시지 (None이면 성공 또는 진행 중)
+    pub fn error(&self) -> Option<String> {
+        self.shared.error.lock().ok().and_then(|e| e.clone())
+    }
+
+    /// 프록시 파일 경로
+    pub fn output_path(&self) -> &Path {
+        &self.output_path
+    }
+}
+
+/// 프록시 인코딩 (임시 파일에 쓴 뒤 완료 시 output_path로 이동 → 중단돼도 불완전한 프록시가 남지 않음)
+fn generate(source: &Path, output_path: &Path, height: u32, shared: &ProxyShared) -> Result<(), String> {
+    let (display_width, display_height, source_fps, duration_ms) = {
+        let decoder = Decoder::open(source)?;
+        let (width, height) = decoder.display_size();
+        (width, height, decoder.fps(), decoder.duration_ms())
+    };
+    let (width, height) = proxy_size(display_width, display_height, height);
+    let fps = if source_fps.is_finite() && source_fps > 0.0 { source_fps.min(MAX_PROXY_FPS) } else { 30.0 };
+
+    // 인코더는 비ASCII 경로를 열지 못할 수 있어 임시 디렉토리의 ASCII 이름으로 인코딩
+    let temp_path = std::env::temp_dir().join(format!(
+        "vortex_proxy_{}_{}.mp4",
+        std::process::id(),
+        output_path.file_stem().and_then(|s| s.to_str()).filter(|s| s.is_ascii()).unwrap_or("proxy")
+    ));
+    let result = encode(source, &temp_path, width, height, fps, duration_ms, shared)
+        .and_then(|()| move_file(&temp_path, output_path).map_err(|e| format!("프록시 파일 이동 실패: {}", e)));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn encode(
+    source: &Path,
+    temp_path: &Path,
+    width: u32,
+    height: u32,
+    fps: f64,
+    duration_ms: i64,
+    shared: &ProxyShared,
+) -> Result<(), String> {
+    let mut decoder = Decoder::open_with_resolution(source, width, height)?;
+    let settings = VideoEncoderSettings {
+        width,
+        height,
+        fps,
+        encoder_type: EncoderType::Auto,
+        codec: VideoCodec::H264,
+        rate_control: RateControl::crf(PROXY_CRF),
+        pass: EncodePass::Single,
+        high_bit_depth_container: false,
+    };
+    let mut encoder = VideoEncoder::new(&temp_path.to_string_lossy(), &settings)?;
+    encoder.write_header()?;
+
+    // 출력 프레임 n(n/fps 초)에는 그 시각에 표시 중인 원본 프레임 (VFR/시작 오프셋이 있어도 원본 시간 유지)
+    let frame_ms = 1000.0 / fps;
+    let mut next_index: i64 = 0;
+    let mut previous = None;
+    for frame in decoder.frames() {
+        let frame = frame?;
+        if shared.cancelled.load(Ordering::SeqCst) {
+            return Err("Cancelled".to_string());
+        }
+        if frame.format != PixelFormat::RGBA || frame.width != width || frame.height != height {
+            return Err(format!("Unexpected proxy source frame {}x{} {:?}", frame.width, frame.height, frame.format));
+        }
+
+        while (next_index as f64 * frame_ms) < frame.timestamp_ms as f64 {
+            let fill = previous.as_ref().unwrap_or(&frame);
+            encoder.encode_frame(&fill.data, width, height)?;
+            next_index += 1;
+        }
+        if duration_ms > 0 {
+            let progress = (frame.timestamp_ms * 100 / duration_ms).clamp(0, 99) as u32;
+            shared.progress.store(progress, Ordering::SeqCst);
+        }
+        previous = Some(frame);
+    }
+
+    // 마지막 프레임은 표시 길이(모르면 한 프레임)까지 유지
+    let last = previous.ok_or("No video frames decoded")?;
+    let end_ms = (last.timestamp_ms + last.duration_ms.max(frame_ms.ceil() as i64)).max(duration_ms);
+    while (next_index as f64 * frame_ms) < end_ms as f64 {
+        encoder.encode_frame(&last.data, width, height)?;
+        next_index += 1;
+    }
+
+    encoder.finish()
+}
+
+/// 파일 이동 (다른 드라이브면 복사 후 삭제)
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_size_and_registry() {
+        assert_eq!(proxy_size(3840, 2160, 540), (960, 540));
+        // 세로 영상, 홀수 크기는 짝수로 내림
+        assert_eq!(proxy_size(2160, 3840, 540), (304, 540));
+        assert_eq!(proxy_size(1440, 1080, 541), (720, 540));
+        // 원본보다 크게 만들지 않음
+        assert_eq!(proxy_size(640, 360, 540), (640, 360));
+
+        let source = Path::new("/media/proxy_test_source.mov");
+        assert!(proxy_for(source).is_none());
+        let before = generation();
+        register(source, Path::new("/proxies/a_540p.mp4"));
+        assert_eq!(proxy_for(source), Some(PathBuf::from("/proxies/a_540p.mp4")));
+        assert!(generation() > before);
+        assert!(unregister(source));
+        assert!(!unregister(source));
+    }
+}
@@ -0,0 +1,196 @@
+// 장면 전환 감지 - RGB 히스토그램 차이 기반
+// 저해상도로 디코딩한 프레임을 연속 비교하여 컷 위치(소스 시간)를 찾음
+
+use crate::ffmpeg::{Decoder, DecodeResult};
+use std::path::Path;
+
+/// 분석용 디코딩 해상도 (히스토그램 비교에는 저해상도로 충분)
+const ANALYSIS_WIDTH: u32 = 160;
+const ANALYSIS_HEIGHT: u32 = 90;
+
+/// 채널당 히스토그램 bin 수
+const HISTOGRAM_BINS: usize = 16;
+
+/// 장면 감지 파라미터
+#[derive(Debug, Clone, Copy)]
+pub struct SceneDetectParams {
+    /// 히스토그램 차이 임계값 (0.0~1.0, 클수록 둔감)
+    pub threshold: f32,
+    /// 컷 사이 최소 간격 (ms) — 플래시/빠른 팬에 의한 연속 검출 방지
+    pub min_scene_ms: i64,
+    /// 샘플링 간격 (ms, 0 이하면 소스 프레임 간격)
+    pub sample_interval_ms: i64,
+}
+
+impl Default for SceneDetectParams {
+    fn default() -> Self {
+        Self {
+            threshold: 0.35,
+            min_scene_ms: 1000,
+            sample_interval_ms: 0,
+        }
+    }
+}
+
+/// 정규화된 RGB 히스토그램 (채널별 HISTOGRAM_BINS개, 합 = 채널당 1.0)
+fn rgb_histogram(rgba: &[u8]) -> Vec<f32> {
+    let mut hist = vec![0f32; HISTOGRAM_BINS * 3];
+    let shift = 8 - HISTOGRAM_BINS.trailing_zeros();
+    let mut pixel_count = 0usize;
+
+    for px in rgba.chunks_exact(4) {
+        hist[(px[0] >> shift) as usize] += 1.0;
+        hist[HISTOGRAM_BINS + (px[1] >> shift) as usize] += 1.0;
+        hist[HISTOGRAM_BINS * 2 + (px[2] >> shift) as usize] += 1.0;
+        pixel_count += 1;
+    }
+
+    if pixel_count > 0 {
+        let inv = 1.0 / pixel_count as f32;
+        for v in hist.iter_mut() {
+            *v *= inv;
+        }
+    }
+    hist
+}
+
+/// 두 히스토그램 차이 (0.0=동일, 1.0=완전히 다름)
+fn histogram_distance(a: &[f32], b: &[f32]) -> f32 {
+    let sad: f32 = a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum();
+    // 채널당 최대 SAD = 2.0 → 3채널 합 최대 6.0
+    sad / 6.0
+}
+
+/// 순차 프레임 입력으로 컷을 찾는 감지기
+pub struct SceneDetector {
+    params: SceneDetectParams,
+    prev_histogram: Option<Vec<f32>>,
+    last_cut_ms: Option<i64>,
+    cuts: Vec<i64>,
+}
+
+impl SceneDetector {
+    pub fn new(params: SceneDetectParams) -> Self {
+        Self {
+            params,
+            prev_histogram: None,
+            last_cut_ms: None,
+            cuts: Vec::new(),
+        }
+    }
+
+    /// RGBA 프레임 입력 (timestamp 오름차순), 컷이면 true
+    pub fn push_frame(&mut self, rgba: &[u8], timestamp_ms: i64) -> bool {
+        let histogram = rgb_histogram(rgba);
+
+        let is_cut = match &self.prev_histogram {
+            Some(prev) => {
+                let far_enough = match self.last_cut_ms {
+                    Some(last) => timestamp_ms - last >= self.params.min_scene_ms,
+                    None => true,
+                };
+                far_enough && histogram_distance(prev, &histogram) > self.params.threshold
+            }
+            None => {
+                // 첫 프레임 = 장면 시작 (컷으로 기록하지 않음)
+                self.last_cut_ms = Some(timestamp_ms);
+                false
+            }
+        };
+
+        if is_cut {
+            self.cuts.push(timestamp_ms);
+            self.last_cut_ms = Some(timestamp_ms);
+        }
+
+        self.prev_histogram = Some(histogram);
+        is_cut
+    }
+
+    /// 감지된 컷 목록 (소스 시간 ms)
+    pub fn into_cuts(self) -> Vec<i64> {
+        self.cuts
+    }
+}
+
+/// 비디오 파일의 [start_ms, end_ms) 구간에서 장면 컷 위치 감지
+/// 반환값: 컷 소스 시간 목록 (오름차순, start_ms 자체는 포함하지 않음)
+pub fn detect_scenes(
+    file_path: &Path,
+    start_ms: i64,
+    end_ms: i64,
+    params: SceneDetectParams,
+) -> Result<Vec<i64>, String> {
+    let mut decoder = Decoder::open_with_resolution(file_path, ANALYSIS_WIDTH, ANALYSIS_HEIGHT)?;
+    // 순차 스캔: seek 없이 forward decode
+    decoder.set_forward_threshold(i64::MAX / 2);
+
+    let duration_ms = decoder.duration_ms();
+    let end_ms = if duration_ms > 0 { end_ms.min(duration_ms) } else { end_ms };
+
+    let step_ms = if params.sample_interval_ms > 0 {
+        params.sample_interval_ms
+    } else if decoder.fps() > 0.0 {
+        (1000.0 / decoder.fps()).max(1.0) as i64
+    } else {
+        33
+    };
+
+    let mut detector = SceneDetector::new(params);
+    let mut t = start_ms.max(0);
+
+    while t < end_ms {
+        match decoder.decode_frame(t)? {
+            DecodeResult::Frame(frame) => {
+                detector.push_frame(&frame.data, t);
+            }
+            DecodeResult::FrameSkipped => {}
+            DecodeResult::EndOfStream(frame) => {
+                detector.push_frame(&frame.data, t);
+                break;
+            }
+            DecodeResult::EndOfStreamEmpty => break,
+        }
+        t += step_ms;
+    }
+
+    Ok(detector.into_cuts())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(r: u8, g: u8, b: u8) -> Vec<u8> {
+        [r, g, b, 255].repeat(64)
+    }
+
+    #[test]
+    fn test_histogram_distance() {
+        let a = rgb_histogram(&solid(0, 0, 0));
+        let b = rgb_histogram(&solid(255, 255, 255));
+        assert!(histogram_distance(&a, &a) < 1e-6);
+        assert!((histogram_distance(&a, &b) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_scene_detector_cuts() {
+        let mut detector = SceneDetector::new(SceneDetectParams {
+            threshold: 0.35,
+            min_scene_ms: 1000,
+            sample_interval_ms: 0,
+        });
+
+        let dark = solid(10, 10, 10);
+        let bright = solid(240, 240, 240);
+
+        assert!(!detector.push_frame(&dark, 0));
+        assert!(!detector.push_frame(&dark, 500));
+        assert!(detector.push_frame(&bright, 1500));
+        // 최소 간격 이내 → 무시
+        assert!(!detector.push_frame(&dark, 2000));
+        assert!(detector.push_frame(&bright, 3000));
+
+        assert_eq!(detector.into_cuts(), vec![1500, 3000]);
+    }
+}
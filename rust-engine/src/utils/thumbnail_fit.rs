@@ -0,0 +1,106 @@
+// 썸네일 종횡비 맞춤 - 요청 크기(박스)에 원본 비율을 유지하여 배치
+// Stretch: 박스 크기로 그대로 스케일 (기존 동작, 16:9가 아니면 왜곡)
+// Letterbox: 박스 안에 전체가 들어가도록 축소 후 남는 영역은 검정 패딩
+// Crop: 박스를 꽉 채우도록 스케일 후 중앙 크롭
+
+/// 썸네일 맞춤 모드 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailFit {
+    Stretch = 0,
+    Letterbox = 1,
+    Crop = 2,
+}
+
+impl ThumbnailFit {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => ThumbnailFit::Letterbox,
+            2 => ThumbnailFit::Crop,
+            _ => ThumbnailFit::Stretch,
+        }
+    }
+}
+
+/// 원본 비율을 유지한 스케일 크기 계산
+/// - src_width/height: 표시 기준 원본 크기 (SAR 반영)
+/// - Letterbox: 박스 이하, Crop: 박스 이상 (한 축은 박스와 일치)
+pub fn fitted_size(src_width: u32, src_height: u32, box_width: u32, box_height: u32, fit: ThumbnailFit) -> (u32, u32) {
+    if fit == ThumbnailFit::Stretch || src_width == 0 || src_height == 0 {
+        return (box_width, box_height);
+    }
+
+    let scale_x = box_width as f64 / src_width as f64;
+    let scale_y = box_height as f64 / src_height as f64;
+    let scale = match fit {
+        ThumbnailFit::Letterbox => scale_x.min(scale_y),
+        _ => scale_x.max(scale_y),
+    };
+
+    let width = ((src_width as f64 * scale).round() as u32).max(1);
+    let height = ((src_height as f64 * scale).round() as u32).max(1);
+    match fit {
+        ThumbnailFit::Letterbox => (width.min(box_width), height.min(box_height)),
+        _ => (width.max(box_width), height.max(box_height)),
+    }
+}
+
+/// RGBA 이미지를 박스 중앙에 배치 (작은 축은 검정 패딩, 큰 축은 중앙 크롭)
+pub fn fit_rgba(data: &[u8], width: u32, height: u32, box_width: u32, box_height: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let (bw, bh) = (box_width as usize, box_height as usize);
+
+    let mut out = Vec::with_capacity(bw * bh * 4);
+    for _ in 0..bw * bh {
+        out.extend_from_slice(&[0, 0, 0, 255]);
+    }
+    if data.len() < w * h * 4 {
+        return out;
+    }
+
+    // 축별: 원본이 크면 원본 쪽 오프셋(크롭), 작으면 박스 쪽 오프셋(패딩)
+    let (src_x, dst_x) = if w > bw { ((w - bw) / 2, 0) } else { (0, (bw - w) / 2) };
+    let (src_y, dst_y) = if h > bh { ((h - bh) / 2, 0) } else { (0, (bh - h) / 2) };
+    let copy_w = w.min(bw);
+    let copy_h = h.min(bh);
+
+    for y in 0..copy_h {
+        let si = ((src_y + y) * w + src_x) * 4;
+        let di = ((dst_y + y) * bw + dst_x) * 4;
+        out[di..di + copy_w * 4].copy_from_slice(&data[si..si + copy_w * 4]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fitted_size() {
+        // 2.39:1 시네마스코프 → 16:9 박스
+        assert_eq!(fitted_size(1920, 804, 160, 90, ThumbnailFit::Letterbox), (160, 67));
+        assert_eq!(fitted_size(1920, 804, 160, 90, ThumbnailFit::Crop), (215, 90));
+        // 세로 영상
+        assert_eq!(fitted_size(1080, 1920, 160, 90, ThumbnailFit::Letterbox), (51, 90));
+        assert_eq!(fitted_size(1080, 1920, 160, 90, ThumbnailFit::Stretch), (160, 90));
+        // 동일 비율은 박스와 일치
+        assert_eq!(fitted_size(1920, 1080, 160, 90, ThumbnailFit::Crop), (160, 90));
+    }
+
+    #[test]
+    fn test_fit_rgba_letterbox_and_crop() {
+        // 2x1 흰색 → 2x3 박스: 가운데 행만 흰색
+        let white = vec![255u8; 2 * 4];
+        let out = fit_rgba(&white, 2, 1, 2, 3);
+        assert_eq!(out.len(), 2 * 3 * 4);
+        assert_eq!(&out[0..4], &[0, 0, 0, 255]);
+        assert!(out[8..16].iter().all(|&b| b == 255));
+        assert_eq!(&out[16..20], &[0, 0, 0, 255]);
+
+        // 4x1 (값 0,1,2,3) → 2x1 박스: 가운데 두 픽셀
+        let row: Vec<u8> = (0..4u8).flat_map(|v| [v, v, v, 255]).collect();
+        let out = fit_rgba(&row, 4, 1, 2, 1);
+        assert_eq!(out, vec![1, 1, 1, 255, 2, 2, 2, 255]);
+    }
+}